@@ -103,6 +103,166 @@ enum LIBRA_ERRNO
 typedef int32_t LIBRA_ERRNO;
 #endif // __cplusplus
 
+/// An enum representing the filtering mode of a texture sampler.
+enum LIBRA_FILTER_MODE
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  /// Linear filtering.
+  LIBRA_FILTER_MODE_LINEAR = 0,
+  /// Nearest-neighbour (point) filtering.
+  LIBRA_FILTER_MODE_NEAREST,
+};
+#ifndef __cplusplus
+typedef uint32_t LIBRA_FILTER_MODE;
+#endif // __cplusplus
+
+/// An enum representing the format of a framebuffer.
+///
+/// The names of the formats follow the names used by `#pragma format`.
+enum LIBRA_IMAGE_FORMAT
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  /// An unknown format.
+  LIBRA_IMAGE_FORMAT_UNKNOWN = 0,
+  /// `R8_UNORM`.
+  LIBRA_IMAGE_FORMAT_R8_UNORM,
+  /// `R8_UINT`.
+  LIBRA_IMAGE_FORMAT_R8_UINT,
+  /// `R8_SINT`.
+  LIBRA_IMAGE_FORMAT_R8_SINT,
+  /// `R8G8_UNORM`.
+  LIBRA_IMAGE_FORMAT_R8G8_UNORM,
+  /// `R8G8_UINT`.
+  LIBRA_IMAGE_FORMAT_R8G8_UINT,
+  /// `R8G8_SINT`.
+  LIBRA_IMAGE_FORMAT_R8G8_SINT,
+  /// `R8G8B8A8_UNORM`.
+  LIBRA_IMAGE_FORMAT_R8G8B8A8_UNORM,
+  /// `R8G8B8A8_UINT`.
+  LIBRA_IMAGE_FORMAT_R8G8B8A8_UINT,
+  /// `R8G8B8A8_SINT`.
+  LIBRA_IMAGE_FORMAT_R8G8B8A8_SINT,
+  /// `R8G8B8A8_SRGB`.
+  LIBRA_IMAGE_FORMAT_R8G8B8A8_SRGB,
+  /// `A2B10G10R10_UNORM_PACK32`.
+  LIBRA_IMAGE_FORMAT_A2B10G10R10_UNORM_PACK32,
+  /// `A2B10G10R10_UINT_PACK32`.
+  LIBRA_IMAGE_FORMAT_A2B10G10R10_UINT_PACK32,
+  /// `R16_UINT`.
+  LIBRA_IMAGE_FORMAT_R16_UINT,
+  /// `R16_SINT`.
+  LIBRA_IMAGE_FORMAT_R16_SINT,
+  /// `R16_SFLOAT`.
+  LIBRA_IMAGE_FORMAT_R16_SFLOAT,
+  /// `R16G16_UINT`.
+  LIBRA_IMAGE_FORMAT_R16G16_UINT,
+  /// `R16G16_SINT`.
+  LIBRA_IMAGE_FORMAT_R16G16_SINT,
+  /// `R16G16_SFLOAT`.
+  LIBRA_IMAGE_FORMAT_R16G16_SFLOAT,
+  /// `R16G16B16A16_UINT`.
+  LIBRA_IMAGE_FORMAT_R16G16B16A16_UINT,
+  /// `R16G16B16A16_SINT`.
+  LIBRA_IMAGE_FORMAT_R16G16B16A16_SINT,
+  /// `R16G16B16A16_SFLOAT`.
+  LIBRA_IMAGE_FORMAT_R16G16B16A16_SFLOAT,
+  /// `R32_UINT`.
+  LIBRA_IMAGE_FORMAT_R32_UINT,
+  /// `R32_SINT`.
+  LIBRA_IMAGE_FORMAT_R32_SINT,
+  /// `R32_SFLOAT`.
+  LIBRA_IMAGE_FORMAT_R32_SFLOAT,
+  /// `R32G32_UINT`.
+  LIBRA_IMAGE_FORMAT_R32G32_UINT,
+  /// `R32G32_SINT`.
+  LIBRA_IMAGE_FORMAT_R32G32_SINT,
+  /// `R32G32_SFLOAT`.
+  LIBRA_IMAGE_FORMAT_R32G32_SFLOAT,
+  /// `R32G32B32A32_UINT`.
+  LIBRA_IMAGE_FORMAT_R32G32B32A32_UINT,
+  /// `R32G32B32A32_SINT`.
+  LIBRA_IMAGE_FORMAT_R32G32B32A32_SINT,
+  /// `R32G32B32A32_SFLOAT`.
+  LIBRA_IMAGE_FORMAT_R32G32B32A32_SFLOAT,
+};
+#ifndef __cplusplus
+typedef uint32_t LIBRA_IMAGE_FORMAT;
+#endif // __cplusplus
+
+/// A stage of filter chain creation that a shader pass goes through.
+enum LIBRA_LOAD_STAGE
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  /// The shader pass was preprocessed and compiled to SPIR-V.
+  LIBRA_LOAD_STAGE_PREPROCESSING = 0,
+  /// The shader pass was compiled for the graphics API of the runtime.
+  LIBRA_LOAD_STAGE_COMPILING,
+  /// The pipeline of the shader pass was created.
+  LIBRA_LOAD_STAGE_PIPELINE,
+};
+#ifndef __cplusplus
+typedef uint32_t LIBRA_LOAD_STAGE;
+#endif // __cplusplus
+
+/// A point in the recording of the commands of a filter chain for a frame.
+enum LIBRA_LATENCY_MARKER
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  /// The filter chain is about to record the commands for the frame.
+  LIBRA_LATENCY_MARKER_RECORD_BEGIN = 0,
+  /// The filter chain recorded all commands for the frame.
+  LIBRA_LATENCY_MARKER_RECORD_END,
+};
+#ifndef __cplusplus
+typedef uint32_t LIBRA_LATENCY_MARKER;
+#endif // __cplusplus
+
+/// The severity of a log message.
+enum LIBRA_LOG_LEVEL
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  /// An error.
+  LIBRA_LOG_LEVEL_ERROR = 1,
+  /// A potential problem.
+  LIBRA_LOG_LEVEL_WARN,
+  /// Informational messages.
+  LIBRA_LOG_LEVEL_INFO,
+  /// Messages for debugging.
+  LIBRA_LOG_LEVEL_DEBUG,
+  /// Verbose messages for debugging.
+  LIBRA_LOG_LEVEL_TRACE,
+};
+#ifndef __cplusplus
+typedef uint32_t LIBRA_LOG_LEVEL;
+#endif // __cplusplus
+
+/// The widget that settings UIs should show for a shader parameter.
+enum LIBRA_PARAMETER_WIDGET
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  /// A slider from the minimum to the maximum. This is the default.
+  LIBRA_PARAMETER_WIDGET_SLIDER = 0,
+  /// A checkbox that toggles between the minimum and the maximum.
+  LIBRA_PARAMETER_WIDGET_CHECKBOX,
+  /// A combo box with a label for every step from the minimum to the maximum.
+  LIBRA_PARAMETER_WIDGET_COMBO,
+};
+#ifndef __cplusplus
+typedef uint32_t LIBRA_PARAMETER_WIDGET;
+#endif // __cplusplus
+
 /// An enum representing orientation for use in preset contexts.
 enum LIBRA_PRESET_CTX_ORIENTATION
 #ifdef __cplusplus
@@ -143,6 +303,94 @@ enum LIBRA_PRESET_CTX_RUNTIME
 typedef uint32_t LIBRA_PRESET_CTX_RUNTIME;
 #endif // __cplusplus
 
+/// An enum representing the graphics runtimes a librashader instance can be built with.
+enum LIBRA_RUNTIME
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  /// OpenGL 3.3+
+  LIBRA_RUNTIME_GL_CORE = 0,
+  /// Vulkan
+  LIBRA_RUNTIME_VULKAN,
+  /// Direct3D 9
+  LIBRA_RUNTIME_D3D9,
+  /// Direct3D 11
+  LIBRA_RUNTIME_D3D11,
+  /// Direct3D 12
+  LIBRA_RUNTIME_D3D12,
+  /// Metal
+  LIBRA_RUNTIME_METAL,
+};
+#ifndef __cplusplus
+typedef uint32_t LIBRA_RUNTIME;
+#endif // __cplusplus
+
+/// An enum representing the scaling type of a shader pass.
+enum LIBRA_SCALE_TYPE
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  /// Scale by the size of the input quad.
+  LIBRA_SCALE_TYPE_INPUT = 0,
+  /// Scale the framebuffer in absolute units.
+  LIBRA_SCALE_TYPE_ABSOLUTE,
+  /// Scale by the size of the viewport.
+  LIBRA_SCALE_TYPE_VIEWPORT,
+  /// Scale by the size of the original input quad.
+  LIBRA_SCALE_TYPE_ORIGINAL,
+  /// Scale by the size of the largest rectangle with the aspect ratio of the original
+  /// input quad that fits in the viewport.
+  LIBRA_SCALE_TYPE_VIEWPORT_ASPECT,
+  /// Scale by the size of the other axis, corrected by the aspect ratio of the original
+  /// input quad.
+  LIBRA_SCALE_TYPE_ORIGINAL_ASPECT,
+};
+#ifndef __cplusplus
+typedef uint32_t LIBRA_SCALE_TYPE;
+#endif // __cplusplus
+
+/// An enum representing the wrapping (address) mode of a texture sampler.
+enum LIBRA_WRAP_MODE
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  /// Clamp texture to border.
+  LIBRA_WRAP_MODE_CLAMP_TO_BORDER = 0,
+  /// Clamp texture to edge.
+  LIBRA_WRAP_MODE_CLAMP_TO_EDGE,
+  /// Repeat addressing mode.
+  LIBRA_WRAP_MODE_REPEAT,
+  /// Mirrored repeat addressing mode.
+  LIBRA_WRAP_MODE_MIRRORED_REPEAT,
+};
+#ifndef __cplusplus
+typedef uint32_t LIBRA_WRAP_MODE;
+#endif // __cplusplus
+
+/// Opaque struct for a cancellation token.
+typedef struct _cancel_token _cancel_token;
+
+/// Opaque struct for a librashader instance.
+typedef struct _librashader_instance _librashader_instance;
+
+/// Opaque struct for a Direct3D 11 device context.
+typedef struct _device_context_d3d11 _device_context_d3d11;
+
+/// Opaque struct for a Direct3D 9 device context.
+typedef struct _device_context_d3d9 _device_context_d3d9;
+
+/// Opaque struct for an OpenGL device context.
+typedef struct _device_context_gl _device_context_gl;
+
+/// Opaque struct for a Metal device context.
+typedef struct _device_context_mtl _device_context_mtl;
+
+/// Opaque struct for a Vulkan device context.
+typedef struct _device_context_vk _device_context_vk;
+
 /// Opaque struct for a Direct3D 11 filter chain.
 typedef struct _filter_chain_d3d11 _filter_chain_d3d11;
 
@@ -161,18 +409,51 @@ typedef struct _filter_chain_mtl _filter_chain_mtl;
 /// Opaque struct for a Vulkan filter chain.
 typedef struct _filter_chain_vk _filter_chain_vk;
 
-/// The error type for librashader C API.
+/// A librashader error object, along with the backtrace of where it was returned from the API.
 typedef struct _libra_error _libra_error;
 
+/// Opaque struct for parameter translations.
+typedef struct _preset_translations _preset_translations;
+
 /// Opaque struct for a shader preset.
 typedef struct _shader_preset _shader_preset;
 
 /// Opaque struct for a preset context.
 typedef struct _preset_ctx _preset_ctx;
 
+/// A function that allocates `size` bytes of memory aligned to `align` bytes.
+///
+/// `align` is always a power of two. The function must return null if the allocation failed,
+/// in which case librashader aborts the process.
+typedef void *(*libra_alloc_callback_t)(void *userdata, size_t size, size_t align);
+
+/// A function that frees memory previously returned by the allocation function.
+///
+/// `size` and `align` are the same as the ones the memory was allocated with.
+typedef void (*libra_free_callback_t)(void *userdata, void *ptr, size_t size, size_t align);
+
+/// Memory allocation functions to use for the memory librashader allocates.
+///
+/// The functions may be called from multiple threads at once.
+typedef struct libra_allocator_t {
+  /// The function to allocate memory with.
+  libra_alloc_callback_t alloc;
+  /// The function to free memory with.
+  libra_free_callback_t free;
+  /// A pointer that is passed to `alloc` and `free` as is.
+  void *userdata;
+} libra_allocator_t;
+
 /// A handle to a librashader error object.
 typedef struct _libra_error *libra_error_t;
 
+/// A handle to a token to cancel filter chain creation with.
+typedef struct _cancel_token *libra_cancel_token_t;
+
+/// A handle to a librashader instance, that owns the compile thread pool, the cache directory
+/// and the log callback shared by the filter chains created from it.
+typedef struct _librashader_instance *libra_instance_handle_t;
+
 /// A handle to a shader preset object.
 typedef struct _shader_preset *libra_shader_preset_t;
 
@@ -202,6 +483,30 @@ typedef struct libra_preset_opt_t {
   ///
   /// This is only supported on API 2 and above, otherwise this has no effect.
   bool frametime_uniforms;
+  /// Substitute a placeholder texture for lookup textures that are missing or fail to load,
+  /// instead of failing to create the filter chain.
+  ///
+  /// A warning is logged for every texture that is substituted.
+  ///
+  /// This is only supported on API 3 and above, otherwise this has no effect.
+  bool fallback_textures;
+  /// An array of paths to root directories that every file referenced by the preset,
+  /// including shaders, includes, textures and referenced presets, must reside in.
+  ///
+  /// If this is null, files referenced by the preset are not restricted. This should be
+  /// set when loading presets from untrusted sources.
+  ///
+  /// This is only supported on API 3 and above, otherwise this has no effect.
+  const char *const *sandbox_roots;
+  /// The number of paths in `sandbox_roots`.
+  ///
+  /// This is only supported on API 3 and above, otherwise this has no effect.
+  uint64_t sandbox_roots_length;
+  /// Fail to load presets that contain unknown keys, instead of collecting them
+  /// as warnings that can be retrieved with `libra_preset_get_warnings`.
+  ///
+  /// This is only supported on API 3 and above, otherwise this has no effect.
+  bool strict;
 } libra_preset_opt_t;
 
 /// A preset parameter.
@@ -230,17 +535,209 @@ typedef struct libra_preset_param_list_t {
   uint64_t length;
 } libra_preset_param_list_t;
 
+/// A handle to a set of parameter translations.
+typedef struct _preset_translations *libra_preset_translations_t;
+
+/// The widget that settings UIs should show for a preset parameter.
+typedef struct libra_preset_param_widget_t {
+  /// The name of the parameter.
+  const char *name;
+  /// The kind of widget to show.
+  LIBRA_PARAMETER_WIDGET widget;
+  /// The labels for every step from the minimum to the maximum of the parameter,
+  /// if `widget` is `LIBRA_PARAMETER_WIDGET_COMBO`. Otherwise, this is null.
+  const char *const *labels;
+  /// The number of labels in `labels`.
+  uint64_t labels_length;
+} libra_preset_param_widget_t;
+
+/// A list of preset parameter widgets.
+typedef struct libra_preset_param_widget_list_t {
+  /// A pointer to the widgets.
+  const struct libra_preset_param_widget_t *widgets;
+  /// The number of widgets in the list. This field
+  /// is readonly, and changing it will lead to undefined
+  /// behaviour on free.
+  uint64_t length;
+} libra_preset_param_widget_list_t;
+
+/// A shader pass of a preset.
+typedef struct libra_preset_pass_t {
+  /// The fully qualified path to the shader source of the pass.
+  const char *path;
+  /// The alias of the pass, or null if the pass has no alias.
+  const char *alias;
+  /// The filtering mode that the pass expects for its input.
+  LIBRA_FILTER_MODE filter;
+  /// The texture addressing (wrap) mode that the pass expects for its input.
+  LIBRA_WRAP_MODE wrap_mode;
+  /// The number to which to wrap the frame count before passing it to the uniforms.
+  uint32_t frame_count_mod;
+  /// Whether or not the pass expects an sRGB framebuffer output.
+  bool srgb_framebuffer;
+  /// Whether or not the pass expects a float framebuffer output.
+  bool float_framebuffer;
+  /// Whether or not mipmaps are generated for the input texture of the pass.
+  bool mipmap_input;
+  /// The scaling type of the output framebuffer on the X axis.
+  LIBRA_SCALE_TYPE scale_type_x;
+  /// The scaling type of the output framebuffer on the Y axis.
+  LIBRA_SCALE_TYPE scale_type_y;
+  /// The scaling factor of the output framebuffer on the X axis.
+  float scale_x;
+  /// The scaling factor of the output framebuffer on the Y axis.
+  float scale_y;
+} libra_preset_pass_t;
+
+/// A list of preset shader passes.
+typedef struct libra_preset_pass_list_t {
+  /// A pointer to the passes.
+  const struct libra_preset_pass_t *passes;
+  /// The number of passes in the list. This field
+  /// is readonly, and changing it will lead to undefined
+  /// behaviour on free.
+  uint64_t length;
+} libra_preset_pass_list_t;
+
+/// A lookup texture of a preset.
+typedef struct libra_preset_texture_t {
+  /// The name of the texture.
+  const char *name;
+  /// The fully qualified path to the texture.
+  const char *path;
+  /// The filtering mode to use when sampling the texture.
+  LIBRA_FILTER_MODE filter_mode;
+  /// The texture addressing (wrap) mode to use when sampling the texture.
+  LIBRA_WRAP_MODE wrap_mode;
+  /// Whether or not mipmaps are generated for the texture.
+  bool mipmap;
+  /// Whether or not the texture is sampled as sRGB encoded data.
+  bool srgb;
+} libra_preset_texture_t;
+
+/// A list of preset lookup textures.
+typedef struct libra_preset_texture_list_t {
+  /// A pointer to the textures.
+  const struct libra_preset_texture_t *textures;
+  /// The number of textures in the list. This field
+  /// is readonly, and changing it will lead to undefined
+  /// behaviour on free.
+  uint64_t length;
+} libra_preset_texture_list_t;
+
+/// A problem with a preset that was ignored when parsing.
+typedef struct libra_preset_warning_t {
+  /// A message describing the warning, suitable to show to users.
+  const char *message;
+  /// The fully qualified path to the preset file with the problem.
+  const char *path;
+  /// The key that was not recognized.
+  const char *key;
+  /// The line of the key in the preset file, starting at 1.
+  uint32_t line;
+  /// The column of the key in the preset file, starting at 1.
+  uint32_t column;
+} libra_preset_warning_t;
+
+/// A list of preset warnings.
+typedef struct libra_preset_warning_list_t {
+  /// A pointer to the warnings.
+  const struct libra_preset_warning_t *warnings;
+  /// The number of warnings in the list. This field
+  /// is readonly, and changing it will lead to undefined
+  /// behaviour on free.
+  uint64_t length;
+} libra_preset_warning_list_t;
+
+/// A stable content hash of a preset and all of its resources.
+typedef struct libra_preset_hash_t {
+  /// The bytes of the hash.
+  uint8_t hash[32];
+} libra_preset_hash_t;
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// A GL function loader that librashader needs to be initialized with.
 typedef const void *(*libra_gl_loader_t)(const char*);
 #endif
 
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// A handle to OpenGL resources that are shared between filter chains on the same context.
+typedef struct _device_context_gl *libra_gl_device_context_t;
+#endif
+
+/// A function that is called every time a shader pass completes a stage of filter chain creation.
+///
+/// `pass` is the index of the shader pass, and `total` is the number of shader passes in the preset.
+/// Shader passes may be compiled in parallel, so the function may be called from multiple threads,
+/// and passes may complete a stage out of order.
+typedef void (*libra_progress_callback_t)(void *userdata,
+                                          LIBRA_LOAD_STAGE stage,
+                                          uint32_t pass,
+                                          uint32_t total);
+
+/// A callback to report the progress of filter chain creation with.
+typedef struct libra_progress_t {
+  /// The function to call, or null to not report progress.
+  libra_progress_callback_t callback;
+  /// A pointer that is passed to `callback` as is.
+  void *userdata;
+} libra_progress_t;
+
+/// A function that is called before and after the commands of every frame are recorded.
+///
+/// `frame_count` is the frame count that was passed to the filter chain for the frame.
+/// The function is called on the thread that records the frame, at the time of the marker.
+typedef void (*libra_latency_marker_callback_t)(void *userdata,
+                                                LIBRA_LATENCY_MARKER marker,
+                                                size_t frame_count);
+
+/// A callback to report latency markers with, to forward to latency SDKs such as
+/// NVIDIA Reflex or AMD Anti-Lag.
+typedef struct libra_latency_marker_t {
+  /// The function to call, or null to not report latency markers.
+  libra_latency_marker_callback_t callback;
+  /// A pointer that is passed to `callback` as is.
+  void *userdata;
+} libra_latency_marker_t;
+
+/// A function that receives the log messages of librashader.
+///
+/// `message` is a null-terminated string that is only valid for the duration of the call.
+/// The function may be called from the compile threads of the instance.
+typedef void (*libra_log_callback_t)(void *userdata, LIBRA_LOG_LEVEL level, const char *message);
+
+/// A callback to receive the log messages of librashader with.
+typedef struct libra_log_t {
+  /// The function to call, or null to not receive log messages.
+  libra_log_callback_t callback;
+  /// A pointer that is passed to `callback` as is.
+  void *userdata;
+} libra_log_t;
+
+/// Options for librashader instance creation.
+typedef struct instance_opt_t {
+  /// The librashader API version.
+  LIBRASHADER_API_VERSION version;
+  /// The number of threads used to compile shaders. If zero, the number of threads is
+  /// chosen by the number of available CPUs.
+  size_t compile_threads;
+  /// The stack size in bytes of the compile threads. If zero, the default stack size is used.
+  size_t compile_stack_size;
+  /// The directory of the shader and pipeline cache as a null-terminated UTF-8 string,
+  /// or null to use the default cache directory of the platform. The string only needs to
+  /// be valid until instance creation returns.
+  const char *cache_dir;
+  /// A callback that receives the log messages of librashader while filter chains are
+  /// created from the instance. If the callback is null, log messages are not reported.
+  struct libra_log_t log_callback;
+} instance_opt_t;
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Options for filter chain creation.
 typedef struct filter_chain_gl_opt_t {
   /// The librashader API version.
   LIBRASHADER_API_VERSION version;
-  /// The GLSL version. Should be at least `330`.
+  /// The GLSL version. Should be at least `330`. Ignored on OpenGL ES contexts.
   uint16_t glsl_version;
   /// Whether or not to use the Direct State Access APIs. Only available on OpenGL 4.5+.
   /// Using the shader cache requires this option, so this option will implicitly
@@ -251,6 +748,60 @@ typedef struct filter_chain_gl_opt_t {
   /// Disable the shader object cache. Shaders will be
   /// recompiled rather than loaded from the cache.
   bool disable_cache;
+  /// Whether the origin of input images is at the bottom left, as is the case for textures
+  /// rendered to by OpenGL. If set, the input is flipped vertically once before the first pass.
+  bool input_bottom_left;
+  /// The maximum number of history frames to keep. If zero, the number of history frames
+  /// is not capped. Shader passes that sample older frames receive the oldest kept frame.
+  uint32_t max_history;
+  /// The policy to follow when the framebuffer format of a shader pass is not supported.
+  /// 0 = fall back to a wider format, 1 = fall back to the smallest supported format,
+  /// 2 = fail filter chain creation. Default is 0.
+  uint32_t format_fallback;
+  /// Whether input images are external textures (`GL_TEXTURE_EXTERNAL_OES`), such as the
+  /// frames of an Android `SurfaceTexture`. If set, the input is drawn into an internal
+  /// texture before the first pass. Requires `GL_OES_EGL_image_external`.
+  bool input_external;
+  /// Disable history and feedback, for latency-sensitive modes such as runahead or netplay,
+  /// where frames are invalidated frequently. No history frames are kept, and shader passes
+  /// that sample history or feedback textures receive black textures instead.
+  bool disable_temporal;
+  /// Freeze the `FrameCount` uniform while the `frame_direction` frame option is negative,
+  /// as RetroArch does while rewinding. Frontends that keep counting frames while rewinding
+  /// should enable this, so that shaders animated by the frame count do not jump.
+  bool freeze_frame_count_on_rewind;
+  /// Record CPU-side statistics of the commands recorded for every frame, such as the number
+  /// of draw calls, which can be read with `libra_*_filter_chain_get_frame_statistics` after
+  /// the frame is drawn. This is meant for performance tuning and regression tracking, and
+  /// has a small cost.
+  bool collect_statistics;
+  /// A callback that is called as every shader pass of the preset is preprocessed, compiled,
+  /// and has its pipeline created, so that frontends can show the progress of loading
+  /// large presets. If the callback is null, progress is not reported.
+  struct libra_progress_t progress;
+  /// A token to cancel filter chain creation with from another thread, or null. If cancelled,
+  /// creation stops between shader passes and fails. The token must stay valid until
+  /// filter chain creation returns.
+  libra_cancel_token_t cancel;
+  /// A device context to share samplers and LUT textures with other filter chains, or null.
+  /// The device context must be created for the same device as the filter chain, and must
+  /// stay valid until filter chain creation returns.
+  libra_gl_device_context_t device_context;
+  /// Append a built-in pass after the passes of the preset that simulates or corrects for
+  /// colour vision deficiencies. The pass is controlled at runtime with the
+  /// `LIBRA_COLOR_ASSIST_MODE` (0 = off, 1 = protanopia, 2 = deuteranopia, 3 = tritanopia),
+  /// `LIBRA_COLOR_ASSIST_SIMULATE` and `LIBRA_COLOR_ASSIST_STRENGTH` parameters.
+  bool color_assist;
+  /// Append a built-in pass after the passes of the preset that inserts black frames between
+  /// the subframes of every frame, for motion clarity on high refresh rate displays.
+  /// The pass follows the `current_subframe` and `total_subframes` frame options, and is
+  /// controlled at runtime with the `LIBRA_BFI_ENABLE`, `LIBRA_BFI_LIT_SUBFRAMES`,
+  /// `LIBRA_BFI_DARK_LEVEL` and `LIBRA_BFI_COMPENSATION` parameters.
+  bool black_frame_insertion;
+  /// A librashader instance to create the filter chain from, or null. Shaders are compiled on
+  /// the thread pool of the instance, with the cache directory and log callback of the instance.
+  /// The instance must stay valid until filter chain creation returns.
+  libra_instance_handle_t instance;
 } filter_chain_gl_opt_t;
 #endif
 
@@ -259,6 +810,11 @@ typedef struct filter_chain_gl_opt_t {
 typedef struct _filter_chain_gl *libra_gl_filter_chain_t;
 #endif
 
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// A handle to an OpenGL filter chain that is being created on a worker thread.
+typedef struct _pending_filter_chain_gl *libra_gl_pending_filter_chain_t;
+#endif
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// OpenGL parameters for an image.
 typedef struct libra_image_gl_t {
@@ -315,9 +871,86 @@ typedef struct frame_gl_opt_t {
   float frames_per_second;
   /// Time in milliseconds between the current and previous frame. Default is 0.
   uint32_t frametime_delta;
+  /// Whether the provided MVP is in row-major order and needs to be transposed.
+  /// Default is false.
+  bool transpose_mvp;
+  /// Whether to flip the Y axis of the MVP, which flips the output vertically.
+  /// This applies to the default MVP if no MVP is provided. Default is false.
+  bool flip_mvp_y;
+  /// The kernel used to scale the input to the viewport when there are no enabled
+  /// shader passes, and to scale the output of the final pass to the viewport if
+  /// `scale_final_pass` is set. 0 = nearest-neighbour, 1 = bilinear, 2 = bicubic,
+  /// 3 = Lanczos. Default is 0.
+  uint32_t identity_scaling;
+  /// Whether to draw the final pass at the size given by its scaling, and scale its output
+  /// to the viewport with the `identity_scaling` kernel. Default is false, which draws the
+  /// final pass directly to the viewport.
+  bool scale_final_pass;
+  /// The sequence of sub-pixel offsets bound to the `FrameJitter` uniform.
+  /// 0 = no jitter, 1 = Halton (2, 3), 2 = `jitter_offset`. Default is 0.
+  uint32_t jitter_sequence;
+  /// The sub-pixel offset of the current frame in pixels, if `jitter_sequence` is 2.
+  /// Default is (0, 0).
+  float jitter_offset[2];
 } frame_gl_opt_t;
 #endif
 
+/// A handle to a shader parameter of a filter chain.
+///
+/// A parameter handle is only valid for the filter chain it was retrieved from.
+typedef uint32_t libra_param_handle_t;
+
+/// Information about a shader pass of a filter chain.
+typedef struct libra_pass_info_t {
+  /// The framebuffer format requested by the preset or by the `#pragma format` of the shader.
+  LIBRA_IMAGE_FORMAT requested_format;
+  /// The framebuffer format that the pass renders to.
+  ///
+  /// This differs from the requested format if the device does not support rendering
+  /// to the requested format, and a fallback format was chosen instead.
+  LIBRA_IMAGE_FORMAT format;
+  /// The alias of the pass, or null if the pass has no alias.
+  const char *alias;
+  /// The fully qualified path to the shader source of the pass, or null if the pass
+  /// was not loaded from a file.
+  const char *path;
+  /// The filtering mode that the pass expects for its input.
+  LIBRA_FILTER_MODE filter;
+  /// The texture addressing (wrap) mode that the pass expects for its input.
+  LIBRA_WRAP_MODE wrap_mode;
+  /// The scaling type of the framebuffer of the pass on the X axis.
+  LIBRA_SCALE_TYPE scale_type_x;
+  /// The scaling type of the framebuffer of the pass on the Y axis.
+  LIBRA_SCALE_TYPE scale_type_y;
+  /// The scaling factor of the framebuffer of the pass on the X axis.
+  float scale_x;
+  /// The scaling factor of the framebuffer of the pass on the Y axis.
+  float scale_y;
+} libra_pass_info_t;
+
+/// CPU-side statistics of the commands recorded by a filter chain for a single frame.
+typedef struct libra_frame_statistics_t {
+  /// The number of draw calls and compute dispatches.
+  uint32_t draw_calls;
+  /// The number of barriers and resource transitions of pass framebuffers and history frames.
+  ///
+  /// This is always 0 for runtimes that do not have explicit barriers.
+  uint32_t barriers;
+  /// The number of uniform buffer and push constant uploads.
+  uint32_t uploads;
+  /// The number of texture and uniform buffer descriptors written or bound.
+  uint32_t descriptor_writes;
+} libra_frame_statistics_t;
+
+/// A vertex of a mesh to draw the final pass with.
+typedef struct libra_vertex_t {
+  /// The position of the vertex, where `(0, 0)` is the top-left and `(1, 1)` the bottom-right
+  /// corner of the viewport before the MVP is applied.
+  float position[4];
+  /// The texture coordinate of the vertex, where `(0, 0)` is the top-left corner of the image.
+  float texcoord[2];
+} libra_vertex_t;
+
 #if defined(LIBRA_RUNTIME_VULKAN)
 /// Handles required to instantiate vulkan
 typedef struct libra_device_vk_t {
@@ -338,6 +971,11 @@ typedef struct libra_device_vk_t {
 } libra_device_vk_t;
 #endif
 
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// A handle to Vulkan resources that are shared between filter chains on the same device.
+typedef struct _device_context_vk *libra_vk_device_context_t;
+#endif
+
 #if defined(LIBRA_RUNTIME_VULKAN)
 /// Options for filter chain creation.
 typedef struct filter_chain_vk_opt_t {
@@ -350,10 +988,82 @@ typedef struct filter_chain_vk_opt_t {
   /// Use dynamic rendering over explicit render pass objects.
   /// It is recommended if possible to use dynamic rendering,
   /// because render-pass mode will create new framebuffers per pass.
+  /// Dynamic rendering requires the `dynamicRendering` feature of Vulkan 1.3, or of
+  /// `VK_KHR_dynamic_rendering` on Vulkan 1.2, in which case the device must be created
+  /// with the extension enabled. If the device does not support dynamic rendering,
+  /// render pass objects are used instead.
   bool use_dynamic_rendering;
   /// Disable the shader object cache. Shaders will be
   /// recompiled rather than loaded from the cache.
   bool disable_cache;
+  /// The maximum number of history frames to keep. If zero, the number of history frames
+  /// is not capped. Shader passes that sample older frames receive the oldest kept frame.
+  uint32_t max_history;
+  /// A sampler Y'CbCr conversion for inputs in a Y'CbCr format, such as NV12 frames from
+  /// video decoders. If not null, input images are converted to RGB in an internal pass
+  /// before the shader passes run. The conversion must match the format of the input images.
+  VkSamplerYcbcrConversion ycbcr_conversion;
+  /// A sampler created with `ycbcr_conversion`. Must not be null if `ycbcr_conversion`
+  /// is not null.
+  VkSampler ycbcr_sampler;
+  /// The policy to follow when the framebuffer format of a shader pass is not supported.
+  /// 0 = fall back to a wider format, 1 = fall back to the smallest supported format,
+  /// 2 = fail filter chain creation. Default is 0.
+  uint32_t format_fallback;
+  /// Disable history and feedback, for latency-sensitive modes such as runahead or netplay,
+  /// where frames are invalidated frequently. No history frames are kept, and shader passes
+  /// that sample history or feedback textures receive black textures instead.
+  bool disable_temporal;
+  /// Freeze the `FrameCount` uniform while the `frame_direction` frame option is negative,
+  /// as RetroArch does while rewinding. Frontends that keep counting frames while rewinding
+  /// should enable this, so that shaders animated by the frame count do not jump.
+  bool freeze_frame_count_on_rewind;
+  /// Build pipelines for new output formats on a worker thread instead of stalling the
+  /// frame that needs them, to keep frame pacing smooth when the output format changes.
+  /// Until the pipeline is built, the final pass is not drawn to outputs of that format,
+  /// which are left unchanged, and `libra_vk_filter_chain_has_pending_pipelines` reports
+  /// `true` after the frame is recorded. The pipeline is swapped in at the start of a later frame.
+  bool async_pipeline_rebuild;
+  /// Record CPU-side statistics of the commands recorded for every frame, such as the number
+  /// of draw calls, which can be read with `libra_*_filter_chain_get_frame_statistics` after
+  /// the frame is drawn. This is meant for performance tuning and regression tracking, and
+  /// has a small cost.
+  bool collect_statistics;
+  /// A callback that is called as every shader pass of the preset is preprocessed, compiled,
+  /// and has its pipeline created, so that frontends can show the progress of loading
+  /// large presets. If the callback is null, progress is not reported.
+  struct libra_progress_t progress;
+  /// A token to cancel filter chain creation with from another thread, or null. If cancelled,
+  /// creation stops between shader passes and fails. The token must stay valid until
+  /// filter chain creation returns.
+  libra_cancel_token_t cancel;
+  /// Enable behaviour that is safe on implementations of `VK_KHR_portability_subset`,
+  /// such as MoltenVK. Framebuffer formats that can not be blitted or linearly filtered
+  /// by the device fall back to formats that can, as mipmap generation requires.
+  bool portability;
+  /// A device context to share samplers and LUT textures with other filter chains, or null.
+  /// The device context must be created for the same device as the filter chain, and must
+  /// stay valid until filter chain creation returns.
+  libra_vk_device_context_t device_context;
+  /// Append a built-in pass after the passes of the preset that simulates or corrects for
+  /// colour vision deficiencies. The pass is controlled at runtime with the
+  /// `LIBRA_COLOR_ASSIST_MODE` (0 = off, 1 = protanopia, 2 = deuteranopia, 3 = tritanopia),
+  /// `LIBRA_COLOR_ASSIST_SIMULATE` and `LIBRA_COLOR_ASSIST_STRENGTH` parameters.
+  bool color_assist;
+  /// Append a built-in pass after the passes of the preset that inserts black frames between
+  /// the subframes of every frame, for motion clarity on high refresh rate displays.
+  /// The pass follows the `current_subframe` and `total_subframes` frame options, and is
+  /// controlled at runtime with the `LIBRA_BFI_ENABLE`, `LIBRA_BFI_LIT_SUBFRAMES`,
+  /// `LIBRA_BFI_DARK_LEVEL` and `LIBRA_BFI_COMPENSATION` parameters.
+  bool black_frame_insertion;
+  /// A callback that is called before and after the commands of every frame are recorded,
+  /// to forward to latency SDKs such as NVIDIA Reflex or AMD Anti-Lag. If the callback is
+  /// null, latency markers are not reported.
+  struct libra_latency_marker_t latency_markers;
+  /// A librashader instance to create the filter chain from, or null. Shaders are compiled on
+  /// the thread pool of the instance, with the cache directory and log callback of the instance.
+  /// The instance must stay valid until filter chain creation returns.
+  libra_instance_handle_t instance;
 } filter_chain_vk_opt_t;
 #endif
 
@@ -362,6 +1072,11 @@ typedef struct filter_chain_vk_opt_t {
 typedef struct _filter_chain_vk *libra_vk_filter_chain_t;
 #endif
 
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// A handle to a Vulkan filter chain that is being created on a worker thread.
+typedef struct _pending_filter_chain_vk *libra_vk_pending_filter_chain_t;
+#endif
+
 #if defined(LIBRA_RUNTIME_VULKAN)
 /// Vulkan parameters for an image.
 typedef struct libra_image_vk_t {
@@ -404,9 +1119,58 @@ typedef struct frame_vk_opt_t {
   float frames_per_second;
   /// Time in milliseconds between the current and previous frame. Default is 0.
   uint32_t frametime_delta;
+  /// Whether the provided MVP is in row-major order and needs to be transposed.
+  /// Default is false.
+  bool transpose_mvp;
+  /// Whether to flip the Y axis of the MVP, which flips the output vertically.
+  /// This applies to the default MVP if no MVP is provided. Default is false.
+  bool flip_mvp_y;
+  /// The kernel used to scale the input to the viewport when there are no enabled
+  /// shader passes, and to scale the output of the final pass to the viewport if
+  /// `scale_final_pass` is set. 0 = nearest-neighbour, 1 = bilinear, 2 = bicubic,
+  /// 3 = Lanczos. Default is 0.
+  uint32_t identity_scaling;
+  /// Whether to draw the final pass at the size given by its scaling, and scale its output
+  /// to the viewport with the `identity_scaling` kernel. Default is false, which draws the
+  /// final pass directly to the viewport.
+  bool scale_final_pass;
+  /// The sequence of sub-pixel offsets bound to the `FrameJitter` uniform.
+  /// 0 = no jitter, 1 = Halton (2, 3), 2 = `jitter_offset`. Default is 0.
+  uint32_t jitter_sequence;
+  /// The sub-pixel offset of the current frame in pixels, if `jitter_sequence` is 2.
+  /// Default is (0, 0).
+  float jitter_offset[2];
+  /// The layout of the output image when the frame is recorded. If not
+  /// `VK_IMAGE_LAYOUT_UNDEFINED`, the output image is transitioned from this layout to
+  /// `VK_IMAGE_LAYOUT_COLOR_ATTACHMENT_OPTIMAL` before it is drawn to. Otherwise, the output image
+  /// must already be in `VK_IMAGE_LAYOUT_COLOR_ATTACHMENT_OPTIMAL`.
+  VkImageLayout output_layout;
+  /// The layout to transition the output image to after the final pass, such as
+  /// `VK_IMAGE_LAYOUT_PRESENT_SRC_KHR`. If `VK_IMAGE_LAYOUT_UNDEFINED`, the output image is
+  /// left in `VK_IMAGE_LAYOUT_COLOR_ATTACHMENT_OPTIMAL`.
+  VkImageLayout final_output_layout;
+  /// A `VkImageView` of the output image for the final pass to draw to, or null for the filter
+  /// chain to create a view for the frame. The view must be a 2D view of the first mip level and
+  /// array layer of the output image, in the format of the output image.
+  VkImageView output_view;
+  /// A `VkRenderPass` owned by the caller to draw the final pass in, or null for the filter
+  /// chain to draw the final pass in its own rendering. If not null, the final pass is recorded
+  /// to `final_command_buffer`, and `output_layout` and `final_output_layout` are ignored.
+  VkRenderPass final_render_pass;
+  /// The index of the subpass of `final_render_pass` to draw the final pass in.
+  uint32_t final_subpass;
+  /// A secondary `VkCommandBuffer` to record the final pass to if `final_render_pass` is not
+  /// null. It must be recording, begun with `VK_COMMAND_BUFFER_USAGE_RENDER_PASS_CONTINUE_BIT`
+  /// for `final_render_pass` and `final_subpass`, and executed after the frame command buffer.
+  VkCommandBuffer final_command_buffer;
 } frame_vk_opt_t;
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// A handle to Direct3D 11 resources that are shared between filter chains on the same device.
+typedef struct _device_context_d3d11 *libra_d3d11_device_context_t;
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
 /// Options for Direct3D 11 filter chain creation.
 typedef struct filter_chain_d3d11_opt_t {
@@ -418,6 +1182,64 @@ typedef struct filter_chain_d3d11_opt_t {
   /// Disable the shader object cache. Shaders will be
   /// recompiled rather than loaded from the cache.
   bool disable_cache;
+  /// The maximum number of history frames to keep. If zero, the number of history frames
+  /// is not capped. Shader passes that sample older frames receive the oldest kept frame.
+  uint32_t max_history;
+  /// Acquire the keyed mutex of the input texture before sampling it, and release it once
+  /// the frame has been recorded. Inputs without a keyed mutex are sampled as is.
+  ///
+  /// This is needed for shared textures created with `D3D11_RESOURCE_MISC_SHARED_KEYEDMUTEX`,
+  /// and is only supported when frames are recorded on the immediate context. Recording a
+  /// frame on a deferred context with this option enabled returns an error.
+  bool use_keyed_mutex;
+  /// The key to acquire the keyed mutex of the input texture with.
+  uint64_t keyed_mutex_acquire_key;
+  /// The key to release the keyed mutex of the input texture with.
+  uint64_t keyed_mutex_release_key;
+  /// The policy to follow when the framebuffer format of a shader pass is not supported.
+  /// 0 = fall back to a wider format, 1 = fall back to the smallest supported format,
+  /// 2 = fail filter chain creation. Default is 0.
+  uint32_t format_fallback;
+  /// Disable history and feedback, for latency-sensitive modes such as runahead or netplay,
+  /// where frames are invalidated frequently. No history frames are kept, and shader passes
+  /// that sample history or feedback textures receive black textures instead.
+  bool disable_temporal;
+  /// Freeze the `FrameCount` uniform while the `frame_direction` frame option is negative,
+  /// as RetroArch does while rewinding. Frontends that keep counting frames while rewinding
+  /// should enable this, so that shaders animated by the frame count do not jump.
+  bool freeze_frame_count_on_rewind;
+  /// Record CPU-side statistics of the commands recorded for every frame, such as the number
+  /// of draw calls, which can be read with `libra_*_filter_chain_get_frame_statistics` after
+  /// the frame is drawn. This is meant for performance tuning and regression tracking, and
+  /// has a small cost.
+  bool collect_statistics;
+  /// A callback that is called as every shader pass of the preset is preprocessed, compiled,
+  /// and has its pipeline created, so that frontends can show the progress of loading
+  /// large presets. If the callback is null, progress is not reported.
+  struct libra_progress_t progress;
+  /// A token to cancel filter chain creation with from another thread, or null. If cancelled,
+  /// creation stops between shader passes and fails. The token must stay valid until
+  /// filter chain creation returns.
+  libra_cancel_token_t cancel;
+  /// A device context to share samplers and LUT textures with other filter chains, or null.
+  /// The device context must be created for the same device as the filter chain, and must
+  /// stay valid until filter chain creation returns.
+  libra_d3d11_device_context_t device_context;
+  /// Append a built-in pass after the passes of the preset that simulates or corrects for
+  /// colour vision deficiencies. The pass is controlled at runtime with the
+  /// `LIBRA_COLOR_ASSIST_MODE` (0 = off, 1 = protanopia, 2 = deuteranopia, 3 = tritanopia),
+  /// `LIBRA_COLOR_ASSIST_SIMULATE` and `LIBRA_COLOR_ASSIST_STRENGTH` parameters.
+  bool color_assist;
+  /// Append a built-in pass after the passes of the preset that inserts black frames between
+  /// the subframes of every frame, for motion clarity on high refresh rate displays.
+  /// The pass follows the `current_subframe` and `total_subframes` frame options, and is
+  /// controlled at runtime with the `LIBRA_BFI_ENABLE`, `LIBRA_BFI_LIT_SUBFRAMES`,
+  /// `LIBRA_BFI_DARK_LEVEL` and `LIBRA_BFI_COMPENSATION` parameters.
+  bool black_frame_insertion;
+  /// A librashader instance to create the filter chain from, or null. Shaders are compiled on
+  /// the thread pool of the instance, with the cache directory and log callback of the instance.
+  /// The instance must stay valid until filter chain creation returns.
+  libra_instance_handle_t instance;
 } filter_chain_d3d11_opt_t;
 #endif
 
@@ -426,6 +1248,11 @@ typedef struct filter_chain_d3d11_opt_t {
 typedef struct _filter_chain_d3d11 *libra_d3d11_filter_chain_t;
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// A handle to a Direct3D 11 filter chain that is being created on a worker thread.
+typedef struct _pending_filter_chain_d3d11 *libra_d3d11_pending_filter_chain_t;
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
 /// Options for each Direct3D 11 shader frame.
 typedef struct frame_d3d11_opt_t {
@@ -454,9 +1281,35 @@ typedef struct frame_d3d11_opt_t {
   float frames_per_second;
   /// Time in milliseconds between the current and previous frame. Default is 0.
   uint32_t frametime_delta;
+  /// Whether the provided MVP is in row-major order and needs to be transposed.
+  /// Default is false.
+  bool transpose_mvp;
+  /// Whether to flip the Y axis of the MVP, which flips the output vertically.
+  /// This applies to the default MVP if no MVP is provided. Default is false.
+  bool flip_mvp_y;
+  /// The kernel used to scale the input to the viewport when there are no enabled
+  /// shader passes, and to scale the output of the final pass to the viewport if
+  /// `scale_final_pass` is set. 0 = nearest-neighbour, 1 = bilinear, 2 = bicubic,
+  /// 3 = Lanczos. Default is 0.
+  uint32_t identity_scaling;
+  /// Whether to draw the final pass at the size given by its scaling, and scale its output
+  /// to the viewport with the `identity_scaling` kernel. Default is false, which draws the
+  /// final pass directly to the viewport.
+  bool scale_final_pass;
+  /// The sequence of sub-pixel offsets bound to the `FrameJitter` uniform.
+  /// 0 = no jitter, 1 = Halton (2, 3), 2 = `jitter_offset`. Default is 0.
+  uint32_t jitter_sequence;
+  /// The sub-pixel offset of the current frame in pixels, if `jitter_sequence` is 2.
+  /// Default is (0, 0).
+  float jitter_offset[2];
 } frame_d3d11_opt_t;
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// A handle to Direct3D 9 resources that are shared between filter chains on the same device.
+typedef struct _device_context_d3d9 *libra_d3d9_device_context_t;
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Options for Direct3D 11 filter chain creation.
 typedef struct filter_chain_d3d9_opt_t {
@@ -468,6 +1321,53 @@ typedef struct filter_chain_d3d9_opt_t {
   /// Disable the shader object cache. Shaders will be
   /// recompiled rather than loaded from the cache.
   bool disable_cache;
+  /// The maximum number of history frames to keep. If zero, the number of history frames
+  /// is not capped. Shader passes that sample older frames receive the oldest kept frame.
+  uint32_t max_history;
+  /// The policy to follow when the framebuffer format of a shader pass is not supported.
+  /// 0 = fall back to a wider format, 1 = fall back to the smallest supported format,
+  /// 2 = fail filter chain creation. Default is 0.
+  uint32_t format_fallback;
+  /// Disable history and feedback, for latency-sensitive modes such as runahead or netplay,
+  /// where frames are invalidated frequently. No history frames are kept, and shader passes
+  /// that sample history or feedback textures receive black textures instead.
+  bool disable_temporal;
+  /// Freeze the `FrameCount` uniform while the `frame_direction` frame option is negative,
+  /// as RetroArch does while rewinding. Frontends that keep counting frames while rewinding
+  /// should enable this, so that shaders animated by the frame count do not jump.
+  bool freeze_frame_count_on_rewind;
+  /// Record CPU-side statistics of the commands recorded for every frame, such as the number
+  /// of draw calls, which can be read with `libra_*_filter_chain_get_frame_statistics` after
+  /// the frame is drawn. This is meant for performance tuning and regression tracking, and
+  /// has a small cost.
+  bool collect_statistics;
+  /// A callback that is called as every shader pass of the preset is preprocessed, compiled,
+  /// and has its pipeline created, so that frontends can show the progress of loading
+  /// large presets. If the callback is null, progress is not reported.
+  struct libra_progress_t progress;
+  /// A token to cancel filter chain creation with from another thread, or null. If cancelled,
+  /// creation stops between shader passes and fails. The token must stay valid until
+  /// filter chain creation returns.
+  libra_cancel_token_t cancel;
+  /// A device context to share samplers and LUT textures with other filter chains, or null.
+  /// The device context must be created for the same device as the filter chain, and must
+  /// stay valid until filter chain creation returns.
+  libra_d3d9_device_context_t device_context;
+  /// Append a built-in pass after the passes of the preset that simulates or corrects for
+  /// colour vision deficiencies. The pass is controlled at runtime with the
+  /// `LIBRA_COLOR_ASSIST_MODE` (0 = off, 1 = protanopia, 2 = deuteranopia, 3 = tritanopia),
+  /// `LIBRA_COLOR_ASSIST_SIMULATE` and `LIBRA_COLOR_ASSIST_STRENGTH` parameters.
+  bool color_assist;
+  /// Append a built-in pass after the passes of the preset that inserts black frames between
+  /// the subframes of every frame, for motion clarity on high refresh rate displays.
+  /// The pass follows the `current_subframe` and `total_subframes` frame options, and is
+  /// controlled at runtime with the `LIBRA_BFI_ENABLE`, `LIBRA_BFI_LIT_SUBFRAMES`,
+  /// `LIBRA_BFI_DARK_LEVEL` and `LIBRA_BFI_COMPENSATION` parameters.
+  bool black_frame_insertion;
+  /// A librashader instance to create the filter chain from, or null. Shaders are compiled on
+  /// the thread pool of the instance, with the cache directory and log callback of the instance.
+  /// The instance must stay valid until filter chain creation returns.
+  libra_instance_handle_t instance;
 } filter_chain_d3d9_opt_t;
 #endif
 
@@ -476,6 +1376,11 @@ typedef struct filter_chain_d3d9_opt_t {
 typedef struct _filter_chain_d3d9 *libra_d3d9_filter_chain_t;
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// A handle to a Direct3D 9 filter chain that is being created on a worker thread.
+typedef struct _pending_filter_chain_d3d9 *libra_d3d9_pending_filter_chain_t;
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Options for each Direct3D 11 shader frame.
 typedef struct frame_d3d9_opt_t {
@@ -504,9 +1409,43 @@ typedef struct frame_d3d9_opt_t {
   float frames_per_second;
   /// Time in milliseconds between the current and previous frame. Default is 0.
   uint32_t frametime_delta;
+  /// Whether the provided MVP is in row-major order and needs to be transposed.
+  /// Default is false.
+  bool transpose_mvp;
+  /// Whether to flip the Y axis of the MVP, which flips the output vertically.
+  /// This applies to the default MVP if no MVP is provided. Default is false.
+  bool flip_mvp_y;
+  /// The kernel used to scale the input to the viewport when there are no enabled
+  /// shader passes, and to scale the output of the final pass to the viewport if
+  /// `scale_final_pass` is set. 0 = nearest-neighbour, 1 = bilinear, 2 = bicubic,
+  /// 3 = Lanczos. Default is 0.
+  uint32_t identity_scaling;
+  /// Whether to draw the final pass at the size given by its scaling, and scale its output
+  /// to the viewport with the `identity_scaling` kernel. Default is false, which draws the
+  /// final pass directly to the viewport.
+  bool scale_final_pass;
+  /// The sequence of sub-pixel offsets bound to the `FrameJitter` uniform.
+  /// 0 = no jitter, 1 = Halton (2, 3), 2 = `jitter_offset`. Default is 0.
+  uint32_t jitter_sequence;
+  /// The sub-pixel offset of the current frame in pixels, if `jitter_sequence` is 2.
+  /// Default is (0, 0).
+  float jitter_offset[2];
 } frame_d3d9_opt_t;
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// A range of descriptors on a shader visible descriptor heap owned by the caller.
+typedef struct libra_descriptor_heap_range_d3d12_t {
+  /// A pointer to the shader visible `ID3D12DescriptorHeap`, or null if the filter chain
+  /// should create its own heap.
+  ID3D12DescriptorHeap * heap;
+  /// The index of the first descriptor of the range in the heap.
+  uint32_t offset;
+  /// The number of descriptors in the range.
+  uint32_t count;
+} libra_descriptor_heap_range_d3d12_t;
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
 /// Options for Direct3D11 filter chain creation.
 typedef struct filter_chain_d3d12_opt_t {
@@ -521,6 +1460,72 @@ typedef struct filter_chain_d3d12_opt_t {
   /// Disable the shader object cache. Shaders will be
   /// recompiled rather than loaded from the cache.
   bool disable_cache;
+  /// The maximum number of history frames to keep. If zero, the number of history frames
+  /// is not capped. Shader passes that sample older frames receive the oldest kept frame.
+  uint32_t max_history;
+  /// The policy to follow when the framebuffer format of a shader pass is not supported.
+  /// 0 = fall back to a wider format, 1 = fall back to the smallest supported format,
+  /// 2 = fail filter chain creation. Default is 0.
+  uint32_t format_fallback;
+  /// Disable history and feedback, for latency-sensitive modes such as runahead or netplay,
+  /// where frames are invalidated frequently. No history frames are kept, and shader passes
+  /// that sample history or feedback textures receive black textures instead.
+  bool disable_temporal;
+  /// Freeze the `FrameCount` uniform while the `frame_direction` frame option is negative,
+  /// as RetroArch does while rewinding. Frontends that keep counting frames while rewinding
+  /// should enable this, so that shaders animated by the frame count do not jump.
+  bool freeze_frame_count_on_rewind;
+  /// Build pipelines for new output formats on a worker thread instead of stalling the
+  /// frame that needs them, to keep frame pacing smooth when the output format changes.
+  /// Until the pipeline is built, the final pass is not drawn to outputs of that format,
+  /// which are left unchanged. The pipeline is swapped in at the start of a later frame.
+  bool async_pipeline_rebuild;
+  /// Record CPU-side statistics of the commands recorded for every frame, such as the number
+  /// of draw calls, which can be read with `libra_*_filter_chain_get_frame_statistics` after
+  /// the frame is drawn. This is meant for performance tuning and regression tracking, and
+  /// has a small cost.
+  bool collect_statistics;
+  /// A callback that is called as every shader pass of the preset is preprocessed, compiled,
+  /// and has its pipeline created, so that frontends can show the progress of loading
+  /// large presets. If the callback is null, progress is not reported.
+  struct libra_progress_t progress;
+  /// A token to cancel filter chain creation with from another thread, or null. If cancelled,
+  /// creation stops between shader passes and fails. The token must stay valid until
+  /// filter chain creation returns.
+  libra_cancel_token_t cancel;
+  /// Append a built-in pass after the passes of the preset that simulates or corrects for
+  /// colour vision deficiencies. The pass is controlled at runtime with the
+  /// `LIBRA_COLOR_ASSIST_MODE` (0 = off, 1 = protanopia, 2 = deuteranopia, 3 = tritanopia),
+  /// `LIBRA_COLOR_ASSIST_SIMULATE` and `LIBRA_COLOR_ASSIST_STRENGTH` parameters.
+  bool color_assist;
+  /// Append a built-in pass after the passes of the preset that inserts black frames between
+  /// the subframes of every frame, for motion clarity on high refresh rate displays.
+  /// The pass follows the `current_subframe` and `total_subframes` frame options, and is
+  /// controlled at runtime with the `LIBRA_BFI_ENABLE`, `LIBRA_BFI_LIT_SUBFRAMES`,
+  /// `LIBRA_BFI_DARK_LEVEL` and `LIBRA_BFI_COMPENSATION` parameters.
+  bool black_frame_insertion;
+  /// A callback that is called before and after the commands of every frame are recorded,
+  /// to forward to latency SDKs such as NVIDIA Reflex or AMD Anti-Lag. If the callback is
+  /// null, latency markers are not reported.
+  struct libra_latency_marker_t latency_markers;
+  /// A librashader instance to create the filter chain from, or null. Shaders are compiled on
+  /// the thread pool of the instance, with the cache directory and log callback of the instance.
+  /// The instance must stay valid until filter chain creation returns.
+  libra_instance_handle_t instance;
+  /// A range of a shader visible `D3D12_DESCRIPTOR_HEAP_TYPE_CBV_SRV_UAV` heap to sub-allocate
+  /// the shader resource views bound to shader passes from, instead of creating a heap.
+  /// The range must hold 16 descriptors for every shader pass, including the 4 built-in
+  /// identity passes which are reserved even if they are never used, and any appended
+  /// built-in passes, and 4096 descriptors for mipmap generation. The descriptors in the range must not be used by the caller while the
+  /// filter chain is alive.
+  ///
+  /// If both `resource_heap` and `sampler_heap` are set, frames do not switch descriptor heaps,
+  /// and both heaps must be bound to the command list before a frame is recorded.
+  struct libra_descriptor_heap_range_d3d12_t resource_heap;
+  /// A range of a shader visible `D3D12_DESCRIPTOR_HEAP_TYPE_SAMPLER` heap to sub-allocate
+  /// the samplers bound to shader passes from, instead of creating a heap.
+  /// The range must hold 16 descriptors for every shader pass, counted as for `resource_heap`.
+  struct libra_descriptor_heap_range_d3d12_t sampler_heap;
 } filter_chain_d3d12_opt_t;
 #endif
 
@@ -529,6 +1534,11 @@ typedef struct filter_chain_d3d12_opt_t {
 typedef struct _filter_chain_d3d12 *libra_d3d12_filter_chain_t;
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// A handle to a Direct3D 12 filter chain that is being created on a worker thread.
+typedef struct _pending_filter_chain_d3d12 *libra_d3d12_pending_filter_chain_t;
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
 /// Direct3D 12 parameters for the source image.
 typedef struct libra_source_image_d3d12_t {
@@ -578,6 +1588,49 @@ typedef struct libra_image_d3d12_t {
 } libra_image_d3d12_t;
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// The command lists to hand the output of a shader pass off to for mipmap generation.
+typedef struct libra_mipmap_handoff_lists_d3d12_t {
+  /// A pointer to a command list of type `D3D12_COMMAND_LIST_TYPE_COMPUTE` to record the mipmap
+  /// generation for the output of the shader pass to. The command list must be ready for recording.
+  ID3D12GraphicsCommandList * compute;
+  /// A pointer to a graphics command list to record the rest of the frame to. The command list
+  /// must be ready for recording.
+  ID3D12GraphicsCommandList * graphics;
+} libra_mipmap_handoff_lists_d3d12_t;
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// A function that is called with the index of every intermediate shader pass whose output
+/// needs mipmaps. To generate the mipmaps on a compute queue, the function writes the command
+/// lists to hand the output off to to `out` and returns true. Otherwise, it returns false,
+/// and the mipmaps are recorded inline.
+typedef bool (*libra_mipmap_handoff_callback_d3d12_t)(void *userdata,
+                                                      uint32_t pass,
+                                                      struct libra_mipmap_handoff_lists_d3d12_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// A callback to record the mipmap generation of intermediate passes onto compute command lists with.
+///
+/// When the output of a pass is handed off, it is transitioned to
+/// `D3D12_RESOURCE_STATE_NON_PIXEL_SHADER_RESOURCE` at the end of the current graphics command
+/// list, its mipmaps are recorded to the compute command list, and it is transitioned back to
+/// `D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE` at the start of the new graphics command list,
+/// which the rest of the frame is recorded to.
+///
+/// The filter chain does not close or submit any command list. The compute command list must
+/// be executed after the current graphics command list has completed, and the new graphics
+/// command list after the compute command list has completed. If the filter chain was created
+/// with descriptor heaps owned by the caller, both heaps must also be bound to the command lists.
+typedef struct libra_mipmap_handoff_d3d12_t {
+  /// The function to call, or null to record mipmaps inline.
+  libra_mipmap_handoff_callback_d3d12_t callback;
+  /// A pointer that is passed to `callback` as is.
+  void *userdata;
+} libra_mipmap_handoff_d3d12_t;
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
 /// Options for each Direct3D 12 shader frame.
 typedef struct frame_d3d12_opt_t {
@@ -606,9 +1659,39 @@ typedef struct frame_d3d12_opt_t {
   float frames_per_second;
   /// Time in milliseconds between the current and previous frame. Default is 0.
   uint32_t frametime_delta;
+  /// Whether the provided MVP is in row-major order and needs to be transposed.
+  /// Default is false.
+  bool transpose_mvp;
+  /// Whether to flip the Y axis of the MVP, which flips the output vertically.
+  /// This applies to the default MVP if no MVP is provided. Default is false.
+  bool flip_mvp_y;
+  /// The kernel used to scale the input to the viewport when there are no enabled
+  /// shader passes, and to scale the output of the final pass to the viewport if
+  /// `scale_final_pass` is set. 0 = nearest-neighbour, 1 = bilinear, 2 = bicubic,
+  /// 3 = Lanczos. Default is 0.
+  uint32_t identity_scaling;
+  /// Whether to draw the final pass at the size given by its scaling, and scale its output
+  /// to the viewport with the `identity_scaling` kernel. Default is false, which draws the
+  /// final pass directly to the viewport.
+  bool scale_final_pass;
+  /// The sequence of sub-pixel offsets bound to the `FrameJitter` uniform.
+  /// 0 = no jitter, 1 = Halton (2, 3), 2 = `jitter_offset`. Default is 0.
+  uint32_t jitter_sequence;
+  /// The sub-pixel offset of the current frame in pixels, if `jitter_sequence` is 2.
+  /// Default is (0, 0).
+  float jitter_offset[2];
+  /// A callback to record the mipmap generation of intermediate passes onto compute command
+  /// lists with, instead of recording it inline on the graphics command list of the frame.
+  /// If the callback is null, mipmaps are recorded inline.
+  struct libra_mipmap_handoff_d3d12_t mipmap_handoff;
 } frame_d3d12_opt_t;
 #endif
 
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// A handle to Metal resources that are shared between filter chains on the same device.
+typedef struct _device_context_mtl *libra_mtl_device_context_t;
+#endif
+
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
 /// Options for filter chain creation.
 typedef struct filter_chain_mtl_opt_t {
@@ -616,6 +1699,49 @@ typedef struct filter_chain_mtl_opt_t {
   LIBRASHADER_API_VERSION version;
   /// Whether or not to explicitly disable mipmap generation regardless of shader preset settings.
   bool force_no_mipmaps;
+  /// The maximum number of history frames to keep. If zero, the number of history frames
+  /// is not capped. Shader passes that sample older frames receive the oldest kept frame.
+  uint32_t max_history;
+  /// Disable history and feedback, for latency-sensitive modes such as runahead or netplay,
+  /// where frames are invalidated frequently. No history frames are kept, and shader passes
+  /// that sample history or feedback textures receive black textures instead.
+  bool disable_temporal;
+  /// Freeze the `FrameCount` uniform while the `frame_direction` frame option is negative,
+  /// as RetroArch does while rewinding. Frontends that keep counting frames while rewinding
+  /// should enable this, so that shaders animated by the frame count do not jump.
+  bool freeze_frame_count_on_rewind;
+  /// Record CPU-side statistics of the commands recorded for every frame, such as the number
+  /// of draw calls, which can be read with `libra_*_filter_chain_get_frame_statistics` after
+  /// the frame is drawn. This is meant for performance tuning and regression tracking, and
+  /// has a small cost.
+  bool collect_statistics;
+  /// A callback that is called as every shader pass of the preset is preprocessed, compiled,
+  /// and has its pipeline created, so that frontends can show the progress of loading
+  /// large presets. If the callback is null, progress is not reported.
+  struct libra_progress_t progress;
+  /// A token to cancel filter chain creation with from another thread, or null. If cancelled,
+  /// creation stops between shader passes and fails. The token must stay valid until
+  /// filter chain creation returns.
+  libra_cancel_token_t cancel;
+  /// A device context to share samplers and LUT textures with other filter chains, or null.
+  /// The device context must be created for the same device as the filter chain, and must
+  /// stay valid until filter chain creation returns.
+  libra_mtl_device_context_t device_context;
+  /// Append a built-in pass after the passes of the preset that simulates or corrects for
+  /// colour vision deficiencies. The pass is controlled at runtime with the
+  /// `LIBRA_COLOR_ASSIST_MODE` (0 = off, 1 = protanopia, 2 = deuteranopia, 3 = tritanopia),
+  /// `LIBRA_COLOR_ASSIST_SIMULATE` and `LIBRA_COLOR_ASSIST_STRENGTH` parameters.
+  bool color_assist;
+  /// Append a built-in pass after the passes of the preset that inserts black frames between
+  /// the subframes of every frame, for motion clarity on high refresh rate displays.
+  /// The pass follows the `current_subframe` and `total_subframes` frame options, and is
+  /// controlled at runtime with the `LIBRA_BFI_ENABLE`, `LIBRA_BFI_LIT_SUBFRAMES`,
+  /// `LIBRA_BFI_DARK_LEVEL` and `LIBRA_BFI_COMPENSATION` parameters.
+  bool black_frame_insertion;
+  /// A librashader instance to create the filter chain from, or null. Shaders are compiled on
+  /// the thread pool of the instance, with the cache directory and log callback of the instance.
+  /// The instance must stay valid until filter chain creation returns.
+  libra_instance_handle_t instance;
 } filter_chain_mtl_opt_t;
 #endif
 
@@ -624,6 +1750,11 @@ typedef struct filter_chain_mtl_opt_t {
 typedef struct _filter_chain_mtl *libra_mtl_filter_chain_t;
 #endif
 
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// A handle to a Metal filter chain that is being created on a worker thread.
+typedef struct _pending_filter_chain_mtl *libra_mtl_pending_filter_chain_t;
+#endif
+
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
 /// Options for each Metal shader frame.
 typedef struct frame_mtl_opt_t {
@@ -652,6 +1783,27 @@ typedef struct frame_mtl_opt_t {
   float frames_per_second;
   /// Time in milliseconds between the current and previous frame. Default is 0.
   uint32_t frametime_delta;
+  /// Whether the provided MVP is in row-major order and needs to be transposed.
+  /// Default is false.
+  bool transpose_mvp;
+  /// Whether to flip the Y axis of the MVP, which flips the output vertically.
+  /// This applies to the default MVP if no MVP is provided. Default is false.
+  bool flip_mvp_y;
+  /// The kernel used to scale the input to the viewport when there are no enabled
+  /// shader passes, and to scale the output of the final pass to the viewport if
+  /// `scale_final_pass` is set. 0 = nearest-neighbour, 1 = bilinear, 2 = bicubic,
+  /// 3 = Lanczos. Default is 0.
+  uint32_t identity_scaling;
+  /// Whether to draw the final pass at the size given by its scaling, and scale its output
+  /// to the viewport with the `identity_scaling` kernel. Default is false, which draws the
+  /// final pass directly to the viewport.
+  bool scale_final_pass;
+  /// The sequence of sub-pixel offsets bound to the `FrameJitter` uniform.
+  /// 0 = no jitter, 1 = Halton (2, 3), 2 = `jitter_offset`. Default is 0.
+  uint32_t jitter_sequence;
+  /// The sub-pixel offset of the current frame in pixels, if `jitter_sequence` is 2.
+  /// Default is (0, 0).
+  float jitter_offset[2];
 } frame_mtl_opt_t;
 #endif
 
@@ -664,6 +1816,26 @@ typedef LIBRASHADER_ABI_VERSION (*PFN_libra_instance_abi_version)(void);
 /// Function pointer definition for libra_abi_version
 typedef LIBRASHADER_API_VERSION (*PFN_libra_instance_api_version)(void);
 
+/// Function pointer definition for libra_instance_runtime_supported
+typedef bool (*PFN_libra_instance_runtime_supported)(LIBRA_RUNTIME runtime);
+
+/// Function pointer definition for libra_instance_option_supported
+typedef bool (*PFN_libra_instance_option_supported)(const char *options,
+                                                    const char *field,
+                                                    LIBRASHADER_API_VERSION version);
+
+/// Function pointer definition for libra_instance_version_string
+typedef const char *(*PFN_libra_instance_version_string)(void);
+
+/// Function pointer definition for libra_instance_commit_string
+typedef const char *(*PFN_libra_instance_commit_string)(void);
+
+/// Function pointer definition for libra_instance_features_string
+typedef const char *(*PFN_libra_instance_features_string)(void);
+
+/// Function pointer definition for libra_instance_set_allocator
+typedef bool (*PFN_libra_instance_set_allocator)(const struct libra_allocator_t *allocator);
+
 /// Function pointer definition for
 ///libra_preset_create
 typedef libra_error_t (*PFN_libra_preset_create)(const char *filename, libra_shader_preset_t *out);
@@ -672,6 +1844,11 @@ typedef libra_error_t (*PFN_libra_preset_create)(const char *filename, libra_sha
 ///libra_preset_free
 typedef libra_error_t (*PFN_libra_preset_free)(libra_shader_preset_t *preset);
 
+/// Function pointer definition for
+///libra_preset_clone
+typedef libra_error_t (*PFN_libra_preset_clone)(const libra_shader_preset_t *preset,
+                                                libra_shader_preset_t *out);
+
 /// Function pointer definition for
 ///libra_preset_set_param
 typedef libra_error_t (*PFN_libra_preset_set_param)(libra_shader_preset_t *preset,
@@ -684,6 +1861,13 @@ typedef libra_error_t (*PFN_libra_preset_get_param)(const libra_shader_preset_t
                                                     const char *name,
                                                     float *value);
 
+/// Function pointer definition for
+///libra_preset_set_pass_define
+typedef libra_error_t (*PFN_libra_preset_set_pass_define)(libra_shader_preset_t *preset,
+                                                          uint64_t pass,
+                                                          const char *name,
+                                                          const char *value);
+
 /// Function pointer definition for
 ///libra_preset_print
 typedef libra_error_t (*PFN_libra_preset_print)(libra_shader_preset_t *preset);
@@ -697,6 +1881,53 @@ typedef libra_error_t (*PFN_libra_preset_get_runtime_params)(const libra_shader_
 ///libra_preset_free_runtime_params
 typedef libra_error_t (*PFN_libra_preset_free_runtime_params)(struct libra_preset_param_list_t preset);
 
+/// Function pointer definition for
+///libra_preset_get_runtime_params_localized
+typedef libra_error_t (*PFN_libra_preset_get_runtime_params_localized)(const libra_shader_preset_t *preset,
+                                                                       const libra_preset_translations_t *translations,
+                                                                       struct libra_preset_param_list_t *out);
+
+/// Function pointer definition for
+///libra_preset_get_runtime_param_widgets
+typedef libra_error_t (*PFN_libra_preset_get_runtime_param_widgets)(const libra_shader_preset_t *preset,
+                                                                    struct libra_preset_param_widget_list_t *out);
+
+/// Function pointer definition for
+///libra_preset_free_runtime_param_widgets
+typedef libra_error_t (*PFN_libra_preset_free_runtime_param_widgets)(struct libra_preset_param_widget_list_t widgets);
+
+/// Function pointer definition for
+///libra_preset_get_passes
+typedef libra_error_t (*PFN_libra_preset_get_passes)(const libra_shader_preset_t *preset,
+                                                     struct libra_preset_pass_list_t *out);
+
+/// Function pointer definition for
+///libra_preset_free_passes
+typedef libra_error_t (*PFN_libra_preset_free_passes)(struct libra_preset_pass_list_t passes);
+
+/// Function pointer definition for
+///libra_preset_get_textures
+typedef libra_error_t (*PFN_libra_preset_get_textures)(const libra_shader_preset_t *preset,
+                                                       struct libra_preset_texture_list_t *out);
+
+/// Function pointer definition for
+///libra_preset_free_textures
+typedef libra_error_t (*PFN_libra_preset_free_textures)(struct libra_preset_texture_list_t textures);
+
+/// Function pointer definition for
+///libra_preset_get_warnings
+typedef libra_error_t (*PFN_libra_preset_get_warnings)(const libra_shader_preset_t *preset,
+                                                       struct libra_preset_warning_list_t *out);
+
+/// Function pointer definition for
+///libra_preset_free_warnings
+typedef libra_error_t (*PFN_libra_preset_free_warnings)(struct libra_preset_warning_list_t warnings);
+
+/// Function pointer definition for
+///libra_preset_get_content_hash
+typedef libra_error_t (*PFN_libra_preset_get_content_hash)(const libra_shader_preset_t *preset,
+                                                           struct libra_preset_hash_t *out);
+
 /// Function pointer definition for
 ///libra_preset_create_with_context
 typedef libra_error_t (*PFN_libra_preset_create_with_context)(const char *filename,
@@ -769,6 +2000,20 @@ typedef libra_error_t (*PFN_libra_preset_ctx_set_core_aspect_orientation)(libra_
 typedef libra_error_t (*PFN_libra_preset_ctx_set_runtime)(libra_preset_ctx_t *context,
                                                           LIBRA_PRESET_CTX_RUNTIME value);
 
+/// Function pointer definition for
+///libra_preset_translations_create
+typedef libra_error_t (*PFN_libra_preset_translations_create)(libra_preset_translations_t *out);
+
+/// Function pointer definition for
+///libra_preset_translations_free
+typedef libra_error_t (*PFN_libra_preset_translations_free)(libra_preset_translations_t *translations);
+
+/// Function pointer definition for
+///libra_preset_translations_set
+typedef libra_error_t (*PFN_libra_preset_translations_set)(libra_preset_translations_t *translations,
+                                                           const char *name,
+                                                           const char *description);
+
 /// Function pointer definition for libra_error_errno
 typedef LIBRA_ERRNO (*PFN_libra_error_errno)(libra_error_t error);
 
@@ -781,9 +2026,40 @@ typedef int32_t (*PFN_libra_error_free)(libra_error_t *error);
 /// Function pointer definition for libra_error_write
 typedef int32_t (*PFN_libra_error_write)(libra_error_t error, char **out);
 
+/// Function pointer definition for libra_error_write_source
+typedef int32_t (*PFN_libra_error_write_source)(libra_error_t error, size_t depth, char **out);
+
+/// Function pointer definition for libra_error_write_backtrace
+typedef int32_t (*PFN_libra_error_write_backtrace)(libra_error_t error, char **out);
+
 /// Function pointer definition for libra_error_free_string
 typedef int32_t (*PFN_libra_error_free_string)(char **out);
 
+/// Function pointer definition for
+///libra_cancel_token_create
+typedef libra_error_t (*PFN_libra_cancel_token_create)(libra_cancel_token_t *out);
+
+/// Function pointer definition for
+///libra_cancel_token_cancel
+typedef libra_error_t (*PFN_libra_cancel_token_cancel)(const libra_cancel_token_t *token);
+
+/// Function pointer definition for
+///libra_cancel_token_free
+typedef libra_error_t (*PFN_libra_cancel_token_free)(libra_cancel_token_t *token);
+
+/// Function pointer definition for
+///libra_pass_info_free
+typedef libra_error_t (*PFN_libra_pass_info_free)(libra_pass_info_t *info);
+
+/// Function pointer definition for
+///libra_instance_create
+typedef libra_error_t (*PFN_libra_instance_create)(const struct instance_opt_t *options,
+                                                   libra_instance_handle_t *out);
+
+/// Function pointer definition for
+///libra_instance_free
+typedef libra_error_t (*PFN_libra_instance_free)(libra_instance_handle_t *instance);
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Function pointer definition for
 ///libra_gl_filter_chain_create
@@ -793,6 +2069,44 @@ typedef libra_error_t (*PFN_libra_gl_filter_chain_create)(libra_shader_preset_t
                                                           libra_gl_filter_chain_t *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_create_async
+typedef libra_error_t (*PFN_libra_gl_filter_chain_create_async)(libra_shader_preset_t *preset,
+                                                                libra_gl_loader_t loader,
+                                                                const struct filter_chain_gl_opt_t *options,
+                                                                libra_gl_pending_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_poll_pending
+typedef libra_error_t (*PFN_libra_gl_filter_chain_poll_pending)(libra_gl_pending_filter_chain_t *pending,
+                                                                libra_gl_filter_chain_t *out,
+                                                                bool *ready);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_wait_pending
+typedef libra_error_t (*PFN_libra_gl_filter_chain_wait_pending)(libra_gl_pending_filter_chain_t *pending,
+                                                                libra_gl_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_free_pending
+typedef libra_error_t (*PFN_libra_gl_filter_chain_free_pending)(libra_gl_pending_filter_chain_t *pending);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_create_identity
+typedef libra_error_t (*PFN_libra_gl_filter_chain_create_identity)(libra_gl_loader_t loader,
+                                                                   const struct filter_chain_gl_opt_t *options,
+                                                                   libra_gl_filter_chain_t *out);
+#endif
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Function pointer definition for
 ///libra_gl_filter_chain_frame
@@ -823,33 +2137,154 @@ typedef libra_error_t (*PFN_libra_gl_filter_chain_get_param)(const libra_gl_filt
 
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Function pointer definition for
-///libra_gl_filter_chain_set_active_pass_count
-typedef libra_error_t (*PFN_libra_gl_filter_chain_set_active_pass_count)(libra_gl_filter_chain_t *chain,
-                                                                         uint32_t value);
+///libra_gl_filter_chain_get_param_handle
+typedef libra_error_t (*PFN_libra_gl_filter_chain_get_param_handle)(const libra_gl_filter_chain_t *chain,
+                                                                    const char *param_name,
+                                                                    libra_param_handle_t *out);
 #endif
 
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Function pointer definition for
-///libra_gl_filter_chain_get_active_pass_count
-typedef libra_error_t (*PFN_libra_gl_filter_chain_get_active_pass_count)(const libra_gl_filter_chain_t *chain,
-                                                                         uint32_t *out);
+///libra_gl_filter_chain_set_param_by_handle
+typedef libra_error_t (*PFN_libra_gl_filter_chain_set_param_by_handle)(libra_gl_filter_chain_t *chain,
+                                                                       libra_param_handle_t handle,
+                                                                       float value);
 #endif
 
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Function pointer definition for
-///libra_gl_filter_chain_free
-typedef libra_error_t (*PFN_libra_gl_filter_chain_free)(libra_gl_filter_chain_t *chain);
+///libra_gl_filter_chain_get_param_by_handle
+typedef libra_error_t (*PFN_libra_gl_filter_chain_get_param_by_handle)(const libra_gl_filter_chain_t *chain,
+                                                                       libra_param_handle_t handle,
+                                                                       float *out);
 #endif
 
-#if defined(LIBRA_RUNTIME_VULKAN)
+#if defined(LIBRA_RUNTIME_OPENGL)
 /// Function pointer definition for
-///libra_vk_filter_chain_create
-typedef libra_error_t (*PFN_libra_vk_filter_chain_create)(libra_shader_preset_t *preset,
+///libra_gl_filter_chain_get_runtime_params
+typedef libra_error_t (*PFN_libra_gl_filter_chain_get_runtime_params)(const libra_gl_filter_chain_t *chain,
+                                                                      struct libra_preset_param_list_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_set_active_pass_count
+typedef libra_error_t (*PFN_libra_gl_filter_chain_set_active_pass_count)(libra_gl_filter_chain_t *chain,
+                                                                         uint32_t value);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_get_active_pass_count
+typedef libra_error_t (*PFN_libra_gl_filter_chain_get_active_pass_count)(const libra_gl_filter_chain_t *chain,
+                                                                         uint32_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_get_history_depth
+typedef libra_error_t (*PFN_libra_gl_filter_chain_get_history_depth)(const libra_gl_filter_chain_t *chain,
+                                                                     uint32_t *requested,
+                                                                     uint32_t *kept);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_invalidate_history
+typedef libra_error_t (*PFN_libra_gl_filter_chain_invalidate_history)(libra_gl_filter_chain_t *chain,
+                                                                      uint32_t frames);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_set_output_mesh
+typedef libra_error_t (*PFN_libra_gl_filter_chain_set_output_mesh)(libra_gl_filter_chain_t *chain,
+                                                                   const struct libra_vertex_t *vertices,
+                                                                   size_t count);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_get_pass_info
+typedef libra_error_t (*PFN_libra_gl_filter_chain_get_pass_info)(const libra_gl_filter_chain_t *chain,
+                                                                 uint32_t index,
+                                                                 struct libra_pass_info_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_filter_chain_get_frame_statistics
+typedef libra_error_t (*PFN_libra_gl_filter_chain_get_frame_statistics)(const libra_gl_filter_chain_t *chain,
+                                                                        struct libra_frame_statistics_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+
+/// Function pointer definition for
+///libra_gl_filter_chain_free
+typedef libra_error_t (*PFN_libra_gl_filter_chain_free)(libra_gl_filter_chain_t *chain);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_device_context_create
+typedef libra_error_t (*PFN_libra_gl_device_context_create)(libra_gl_loader_t loader,
+                                                            libra_gl_device_context_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Function pointer definition for
+///libra_gl_device_context_free
+typedef libra_error_t (*PFN_libra_gl_device_context_free)(libra_gl_device_context_t *context);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_create
+typedef libra_error_t (*PFN_libra_vk_filter_chain_create)(libra_shader_preset_t *preset,
                                                           struct libra_device_vk_t vulkan,
                                                           const struct filter_chain_vk_opt_t *options,
                                                           libra_vk_filter_chain_t *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_create_async
+typedef libra_error_t (*PFN_libra_vk_filter_chain_create_async)(libra_shader_preset_t *preset,
+                                                                struct libra_device_vk_t vulkan,
+                                                                const struct filter_chain_vk_opt_t *options,
+                                                                libra_vk_pending_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_poll_pending
+typedef libra_error_t (*PFN_libra_vk_filter_chain_poll_pending)(libra_vk_pending_filter_chain_t *pending,
+                                                                libra_vk_filter_chain_t *out,
+                                                                bool *ready);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_wait_pending
+typedef libra_error_t (*PFN_libra_vk_filter_chain_wait_pending)(libra_vk_pending_filter_chain_t *pending,
+                                                                libra_vk_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_free_pending
+typedef libra_error_t (*PFN_libra_vk_filter_chain_free_pending)(libra_vk_pending_filter_chain_t *pending);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_create_identity
+typedef libra_error_t (*PFN_libra_vk_filter_chain_create_identity)(struct libra_device_vk_t vulkan,
+                                                                   const struct filter_chain_vk_opt_t *options,
+                                                                   libra_vk_filter_chain_t *out);
+#endif
+
 #if defined(LIBRA_RUNTIME_VULKAN)
 /// Function pointer definition for
 ///libra_vk_filter_chain_create_deferred
@@ -889,6 +2324,37 @@ typedef libra_error_t (*PFN_libra_vk_filter_chain_get_param)(const libra_vk_filt
                                                              float *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_get_param_handle
+typedef libra_error_t (*PFN_libra_vk_filter_chain_get_param_handle)(const libra_vk_filter_chain_t *chain,
+                                                                    const char *param_name,
+                                                                    libra_param_handle_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_set_param_by_handle
+typedef libra_error_t (*PFN_libra_vk_filter_chain_set_param_by_handle)(libra_vk_filter_chain_t *chain,
+                                                                       libra_param_handle_t handle,
+                                                                       float value);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_get_param_by_handle
+typedef libra_error_t (*PFN_libra_vk_filter_chain_get_param_by_handle)(const libra_vk_filter_chain_t *chain,
+                                                                       libra_param_handle_t handle,
+                                                                       float *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_get_runtime_params
+typedef libra_error_t (*PFN_libra_vk_filter_chain_get_runtime_params)(const libra_vk_filter_chain_t *chain,
+                                                                      struct libra_preset_param_list_t *out);
+#endif
+
 #if defined(LIBRA_RUNTIME_VULKAN)
 /// Function pointer definition for
 ///libra_vk_filter_chain_set_active_pass_count
@@ -904,11 +2370,91 @@ typedef libra_error_t (*PFN_libra_vk_filter_chain_get_active_pass_count)(const l
 #endif
 
 #if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_get_history_depth
+typedef libra_error_t (*PFN_libra_vk_filter_chain_get_history_depth)(const libra_vk_filter_chain_t *chain,
+                                                                     uint32_t *requested,
+                                                                     uint32_t *kept);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_invalidate_history
+typedef libra_error_t (*PFN_libra_vk_filter_chain_invalidate_history)(libra_vk_filter_chain_t *chain,
+                                                                      uint32_t frames);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_set_output_mesh
+typedef libra_error_t (*PFN_libra_vk_filter_chain_set_output_mesh)(libra_vk_filter_chain_t *chain,
+                                                                   const struct libra_vertex_t *vertices,
+                                                                   size_t count);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_set_timestamp_queries
+typedef libra_error_t (*PFN_libra_vk_filter_chain_set_timestamp_queries)(libra_vk_filter_chain_t *chain,
+                                                                         VkQueryPool pool,
+                                                                         uint32_t first_query);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_get_pass_info
+typedef libra_error_t (*PFN_libra_vk_filter_chain_get_pass_info)(const libra_vk_filter_chain_t *chain,
+                                                                 uint32_t index,
+                                                                 struct libra_pass_info_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_get_frame_statistics
+typedef libra_error_t (*PFN_libra_vk_filter_chain_get_frame_statistics)(const libra_vk_filter_chain_t *chain,
+                                                                        struct libra_frame_statistics_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_has_pending_pipelines
+typedef libra_error_t (*PFN_libra_vk_filter_chain_has_pending_pipelines)(const libra_vk_filter_chain_t *chain,
+                                                                         bool *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+
 /// Function pointer definition for
 ///libra_vk_filter_chain_free
 typedef libra_error_t (*PFN_libra_vk_filter_chain_free)(libra_vk_filter_chain_t *chain);
 #endif
 
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_free_deferred
+typedef libra_error_t (*PFN_libra_vk_filter_chain_free_deferred)(libra_vk_filter_chain_t *chain,
+                                                                 VkFence fence);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_filter_chain_collect_deferred
+typedef libra_error_t (*PFN_libra_vk_filter_chain_collect_deferred)(void);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_device_context_create
+typedef libra_error_t (*PFN_libra_vk_device_context_create)(struct libra_device_vk_t vulkan,
+                                                            libra_vk_device_context_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Function pointer definition for
+///libra_vk_device_context_free
+typedef libra_error_t (*PFN_libra_vk_device_context_free)(libra_vk_device_context_t *context);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
 /// Function pointer definition for
 ///libra_d3d11_filter_chain_create
@@ -918,6 +2464,44 @@ typedef libra_error_t (*PFN_libra_d3d11_filter_chain_create)(libra_shader_preset
                                                              libra_d3d11_filter_chain_t *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_create_async
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_create_async)(libra_shader_preset_t *preset,
+                                                                   ID3D11Device * device,
+                                                                   const struct filter_chain_d3d11_opt_t *options,
+                                                                   libra_d3d11_pending_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_poll_pending
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_poll_pending)(libra_d3d11_pending_filter_chain_t *pending,
+                                                                   libra_d3d11_filter_chain_t *out,
+                                                                   bool *ready);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_wait_pending
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_wait_pending)(libra_d3d11_pending_filter_chain_t *pending,
+                                                                   libra_d3d11_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_free_pending
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_free_pending)(libra_d3d11_pending_filter_chain_t *pending);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_create_identity
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_create_identity)(ID3D11Device * device,
+                                                                      const struct filter_chain_d3d11_opt_t *options,
+                                                                      libra_d3d11_filter_chain_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
 /// Function pointer definition for
 ///libra_d3d11_filter_chain_create_deferred
@@ -957,6 +2541,37 @@ typedef libra_error_t (*PFN_libra_d3d11_filter_chain_get_param)(const libra_d3d1
                                                                 float *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_get_param_handle
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_get_param_handle)(const libra_d3d11_filter_chain_t *chain,
+                                                                       const char *param_name,
+                                                                       libra_param_handle_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_set_param_by_handle
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_set_param_by_handle)(libra_d3d11_filter_chain_t *chain,
+                                                                          libra_param_handle_t handle,
+                                                                          float value);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_get_param_by_handle
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_get_param_by_handle)(const libra_d3d11_filter_chain_t *chain,
+                                                                          libra_param_handle_t handle,
+                                                                          float *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_get_runtime_params
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_get_runtime_params)(const libra_d3d11_filter_chain_t *chain,
+                                                                         struct libra_preset_param_list_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
 /// Function pointer definition for
 ///libra_d3d11_filter_chain_set_active_pass_count
@@ -972,11 +2587,63 @@ typedef libra_error_t (*PFN_libra_d3d11_filter_chain_get_active_pass_count)(cons
 #endif
 
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_get_history_depth
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_get_history_depth)(const libra_d3d11_filter_chain_t *chain,
+                                                                        uint32_t *requested,
+                                                                        uint32_t *kept);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_invalidate_history
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_invalidate_history)(libra_d3d11_filter_chain_t *chain,
+                                                                         uint32_t frames);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_set_output_mesh
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_set_output_mesh)(libra_d3d11_filter_chain_t *chain,
+                                                                      const struct libra_vertex_t *vertices,
+                                                                      size_t count);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_get_pass_info
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_get_pass_info)(const libra_d3d11_filter_chain_t *chain,
+                                                                    uint32_t index,
+                                                                    struct libra_pass_info_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_filter_chain_get_frame_statistics
+typedef libra_error_t (*PFN_libra_d3d11_filter_chain_get_frame_statistics)(const libra_d3d11_filter_chain_t *chain,
+                                                                           struct libra_frame_statistics_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+
 /// Function pointer definition for
 ///libra_d3d11_filter_chain_free
 typedef libra_error_t (*PFN_libra_d3d11_filter_chain_free)(libra_d3d11_filter_chain_t *chain);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_device_context_create
+typedef libra_error_t (*PFN_libra_d3d11_device_context_create)(ID3D11Device * device,
+                                                               libra_d3d11_device_context_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Function pointer definition for
+///libra_d3d11_device_context_free
+typedef libra_error_t (*PFN_libra_d3d11_device_context_free)(libra_d3d11_device_context_t *context);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Function pointer definition for
 ///libra_d3d9_filter_chain_create
@@ -986,6 +2653,44 @@ typedef libra_error_t (*PFN_libra_d3d9_filter_chain_create)(libra_shader_preset_
                                                             libra_d3d9_filter_chain_t *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_create_async
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_create_async)(libra_shader_preset_t *preset,
+                                                                  IDirect3DDevice9 * device,
+                                                                  const struct filter_chain_d3d9_opt_t *options,
+                                                                  libra_d3d9_pending_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_poll_pending
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_poll_pending)(libra_d3d9_pending_filter_chain_t *pending,
+                                                                  libra_d3d9_filter_chain_t *out,
+                                                                  bool *ready);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_wait_pending
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_wait_pending)(libra_d3d9_pending_filter_chain_t *pending,
+                                                                  libra_d3d9_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_free_pending
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_free_pending)(libra_d3d9_pending_filter_chain_t *pending);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_create_identity
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_create_identity)(IDirect3DDevice9 * device,
+                                                                     const struct filter_chain_d3d9_opt_t *options,
+                                                                     libra_d3d9_filter_chain_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Function pointer definition for
 ///libra_d3d9_filter_chain_frame
@@ -1014,6 +2719,37 @@ typedef libra_error_t (*PFN_libra_d3d9_filter_chain_get_param)(const libra_d3d9_
                                                                float *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_get_param_handle
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_get_param_handle)(const libra_d3d9_filter_chain_t *chain,
+                                                                      const char *param_name,
+                                                                      libra_param_handle_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_set_param_by_handle
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_set_param_by_handle)(libra_d3d9_filter_chain_t *chain,
+                                                                         libra_param_handle_t handle,
+                                                                         float value);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_get_param_by_handle
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_get_param_by_handle)(const libra_d3d9_filter_chain_t *chain,
+                                                                         libra_param_handle_t handle,
+                                                                         float *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_get_runtime_params
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_get_runtime_params)(const libra_d3d9_filter_chain_t *chain,
+                                                                        struct libra_preset_param_list_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Function pointer definition for
 ///libra_d3d9_filter_chain_set_active_pass_count
@@ -1030,25 +2766,114 @@ typedef libra_error_t (*PFN_libra_d3d9_filter_chain_get_active_pass_count)(const
 
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Function pointer definition for
-///libra_d3d9_filter_chain_free
-typedef libra_error_t (*PFN_libra_d3d9_filter_chain_free)(libra_d3d9_filter_chain_t *chain);
+///libra_d3d9_filter_chain_get_history_depth
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_get_history_depth)(const libra_d3d9_filter_chain_t *chain,
+                                                                       uint32_t *requested,
+                                                                       uint32_t *kept);
 #endif
 
-#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Function pointer definition for
-///libra_d3d12_filter_chain_create
-typedef libra_error_t (*PFN_libra_d3d12_filter_chain_create)(libra_shader_preset_t *preset,
-                                                             ID3D12Device * device,
-                                                             const struct filter_chain_d3d12_opt_t *options,
-                                                             libra_d3d12_filter_chain_t *out);
+///libra_d3d9_filter_chain_invalidate_history
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_invalidate_history)(libra_d3d9_filter_chain_t *chain,
+                                                                        uint32_t frames);
 #endif
 
-#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Function pointer definition for
-///libra_d3d12_filter_chain_create_deferred
-typedef libra_error_t (*PFN_libra_d3d12_filter_chain_create_deferred)(libra_shader_preset_t *preset,
-                                                                      ID3D12Device * device,
-                                                                      ID3D12GraphicsCommandList * command_list,
+///libra_d3d9_filter_chain_set_output_mesh
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_set_output_mesh)(libra_d3d9_filter_chain_t *chain,
+                                                                     const struct libra_vertex_t *vertices,
+                                                                     size_t count);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_get_pass_info
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_get_pass_info)(const libra_d3d9_filter_chain_t *chain,
+                                                                   uint32_t index,
+                                                                   struct libra_pass_info_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_filter_chain_get_frame_statistics
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_get_frame_statistics)(const libra_d3d9_filter_chain_t *chain,
+                                                                          struct libra_frame_statistics_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+
+/// Function pointer definition for
+///libra_d3d9_filter_chain_free
+typedef libra_error_t (*PFN_libra_d3d9_filter_chain_free)(libra_d3d9_filter_chain_t *chain);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_device_context_create
+typedef libra_error_t (*PFN_libra_d3d9_device_context_create)(libra_d3d9_device_context_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Function pointer definition for
+///libra_d3d9_device_context_free
+typedef libra_error_t (*PFN_libra_d3d9_device_context_free)(libra_d3d9_device_context_t *context);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_create
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_create)(libra_shader_preset_t *preset,
+                                                             ID3D12Device * device,
+                                                             const struct filter_chain_d3d12_opt_t *options,
+                                                             libra_d3d12_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_create_async
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_create_async)(libra_shader_preset_t *preset,
+                                                                   ID3D12Device * device,
+                                                                   const struct filter_chain_d3d12_opt_t *options,
+                                                                   libra_d3d12_pending_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_poll_pending
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_poll_pending)(libra_d3d12_pending_filter_chain_t *pending,
+                                                                   libra_d3d12_filter_chain_t *out,
+                                                                   bool *ready);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_wait_pending
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_wait_pending)(libra_d3d12_pending_filter_chain_t *pending,
+                                                                   libra_d3d12_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_free_pending
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_free_pending)(libra_d3d12_pending_filter_chain_t *pending);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_create_identity
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_create_identity)(ID3D12Device * device,
+                                                                      const struct filter_chain_d3d12_opt_t *options,
+                                                                      libra_d3d12_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_create_deferred
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_create_deferred)(libra_shader_preset_t *preset,
+                                                                      ID3D12Device * device,
+                                                                      ID3D12GraphicsCommandList * command_list,
                                                                       const struct filter_chain_d3d12_opt_t *options,
                                                                       libra_d3d12_filter_chain_t *out);
 #endif
@@ -1082,6 +2907,37 @@ typedef libra_error_t (*PFN_libra_d3d12_filter_chain_get_param)(const libra_d3d1
                                                                 float *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_get_param_handle
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_get_param_handle)(const libra_d3d12_filter_chain_t *chain,
+                                                                       const char *param_name,
+                                                                       libra_param_handle_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_set_param_by_handle
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_set_param_by_handle)(libra_d3d12_filter_chain_t *chain,
+                                                                          libra_param_handle_t handle,
+                                                                          float value);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_get_param_by_handle
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_get_param_by_handle)(const libra_d3d12_filter_chain_t *chain,
+                                                                          libra_param_handle_t handle,
+                                                                          float *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_get_runtime_params
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_get_runtime_params)(const libra_d3d12_filter_chain_t *chain,
+                                                                         struct libra_preset_param_list_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
 /// Function pointer definition for
 ///libra_d3d12_filter_chain_set_active_pass_count
@@ -1097,11 +2953,72 @@ typedef libra_error_t (*PFN_libra_d3d12_filter_chain_get_active_pass_count)(cons
 #endif
 
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_get_history_depth
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_get_history_depth)(const libra_d3d12_filter_chain_t *chain,
+                                                                        uint32_t *requested,
+                                                                        uint32_t *kept);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_invalidate_history
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_invalidate_history)(libra_d3d12_filter_chain_t *chain,
+                                                                         uint32_t frames);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_set_output_mesh
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_set_output_mesh)(libra_d3d12_filter_chain_t *chain,
+                                                                      const struct libra_vertex_t *vertices,
+                                                                      size_t count);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_set_timestamp_queries
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_set_timestamp_queries)(libra_d3d12_filter_chain_t *chain,
+                                                                            ID3D12QueryHeap * heap,
+                                                                            uint32_t first_query);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_get_pass_info
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_get_pass_info)(const libra_d3d12_filter_chain_t *chain,
+                                                                    uint32_t index,
+                                                                    struct libra_pass_info_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_get_frame_statistics
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_get_frame_statistics)(const libra_d3d12_filter_chain_t *chain,
+                                                                           struct libra_frame_statistics_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+
 /// Function pointer definition for
 ///libra_d3d12_filter_chain_free
 typedef libra_error_t (*PFN_libra_d3d12_filter_chain_free)(libra_d3d12_filter_chain_t *chain);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_free_deferred
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_free_deferred)(libra_d3d12_filter_chain_t *chain,
+                                                                    ID3D12Fence * fence,
+                                                                    uint64_t value);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Function pointer definition for
+///libra_d3d12_filter_chain_collect_deferred
+typedef libra_error_t (*PFN_libra_d3d12_filter_chain_collect_deferred)(void);
+#endif
+
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
 /// Function pointer definition for
 ///libra_mtl_filter_chain_create
@@ -1111,6 +3028,44 @@ typedef libra_error_t (*PFN_libra_mtl_filter_chain_create)(libra_shader_preset_t
                                                            libra_mtl_filter_chain_t *out);
 #endif
 
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_create_async
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_create_async)(libra_shader_preset_t *preset,
+                                                                 id<MTLCommandQueue> queue,
+                                                                 const struct filter_chain_mtl_opt_t *options,
+                                                                 libra_mtl_pending_filter_chain_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_poll_pending
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_poll_pending)(libra_mtl_pending_filter_chain_t *pending,
+                                                                 libra_mtl_filter_chain_t *out,
+                                                                 bool *ready);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_wait_pending
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_wait_pending)(libra_mtl_pending_filter_chain_t *pending,
+                                                                 libra_mtl_filter_chain_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_free_pending
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_free_pending)(libra_mtl_pending_filter_chain_t *pending);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_create_identity
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_create_identity)(id<MTLCommandQueue> queue,
+                                                                    const struct filter_chain_mtl_opt_t *options,
+                                                                    libra_mtl_filter_chain_t *out);
+#endif
+
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
 /// Function pointer definition for
 ///libra_mtl_filter_chain_create_deferred
@@ -1150,6 +3105,37 @@ typedef libra_error_t (*PFN_libra_mtl_filter_chain_get_param)(const libra_mtl_fi
                                                               float *out);
 #endif
 
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_get_param_handle
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_get_param_handle)(const libra_mtl_filter_chain_t *chain,
+                                                                     const char *param_name,
+                                                                     libra_param_handle_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_set_param_by_handle
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_set_param_by_handle)(libra_mtl_filter_chain_t *chain,
+                                                                        libra_param_handle_t handle,
+                                                                        float value);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_get_param_by_handle
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_get_param_by_handle)(const libra_mtl_filter_chain_t *chain,
+                                                                        libra_param_handle_t handle,
+                                                                        float *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_get_runtime_params
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_get_runtime_params)(const libra_mtl_filter_chain_t *chain,
+                                                                       struct libra_preset_param_list_t *out);
+#endif
+
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
 /// Function pointer definition for
 ///libra_mtl_filter_chain_set_active_pass_count
@@ -1165,11 +3151,76 @@ typedef libra_error_t (*PFN_libra_mtl_filter_chain_get_active_pass_count)(const
 #endif
 
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_get_history_depth
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_get_history_depth)(const libra_mtl_filter_chain_t *chain,
+                                                                      uint32_t *requested,
+                                                                      uint32_t *kept);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_invalidate_history
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_invalidate_history)(libra_mtl_filter_chain_t *chain,
+                                                                       uint32_t frames);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_set_output_mesh
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_set_output_mesh)(libra_mtl_filter_chain_t *chain,
+                                                                    const struct libra_vertex_t *vertices,
+                                                                    size_t count);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_get_pass_info
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_get_pass_info)(const libra_mtl_filter_chain_t *chain,
+                                                                  uint32_t index,
+                                                                  struct libra_pass_info_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_get_frame_statistics
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_get_frame_statistics)(const libra_mtl_filter_chain_t *chain,
+                                                                         struct libra_frame_statistics_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+
 /// Function pointer definition for
 ///libra_mtl_filter_chain_free
 typedef libra_error_t (*PFN_libra_mtl_filter_chain_free)(libra_mtl_filter_chain_t *chain);
 #endif
 
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_free_deferred
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_free_deferred)(libra_mtl_filter_chain_t *chain,
+                                                                  id<MTLCommandBuffer> command_buffer);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_filter_chain_collect_deferred
+typedef libra_error_t (*PFN_libra_mtl_filter_chain_collect_deferred)(void);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_device_context_create
+typedef libra_error_t (*PFN_libra_mtl_device_context_create)(id<MTLCommandQueue> queue,
+                                                             libra_mtl_device_context_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Function pointer definition for
+///libra_mtl_device_context_free
+typedef libra_error_t (*PFN_libra_mtl_device_context_free)(libra_mtl_device_context_t *context);
+#endif
+
 /// The current version of the librashader API.
 /// Pass this into `version` for config structs.
 ///
@@ -1186,7 +3237,60 @@ typedef libra_error_t (*PFN_libra_mtl_filter_chain_free)(libra_mtl_filter_chain_
 /// - API version 2: 0.6.0
 ///     - Added original aspect uniforms
 ///     - Added frame time uniforms
-#define LIBRASHADER_CURRENT_VERSION 2
+/// - API version 3: 0.6.3
+///     - Added fallback textures
+///     - Added preset sandboxing
+///     - Added parameter handles
+///     - Added MVP transpose and Y-flip frame options
+///     - Added bottom-left input origin option for OpenGL
+///     - Added identity pass for filter chains without enabled passes
+///     - Added identity filter chain constructors
+///     - Added history depth cap option and history depth getters
+///     - Added built-in scaling kernels for the identity pass
+///     - Added the `scale_final_pass` frame option
+///     - Added keyed mutex support for Direct3D 11 inputs
+///     - Added Y'CbCr conversion input support for Vulkan
+///     - Added sRGB lookup textures
+///     - Added the FrameJitter uniform and jitter frame options
+///     - Added framebuffer format fallbacks and `libra_*_filter_chain_get_pass_info`
+///     - Added the `format_fallback` filter chain option
+///     - Added external texture input support for OpenGL
+///     - Added the `disable_temporal` filter chain option
+///     - Added `libra_*_filter_chain_invalidate_history`
+///     - Added the `progress` filter chain option
+///     - Added the `cancel` filter chain option and `libra_cancel_token_*`
+///     - Added the `strict` preset option and `libra_preset_get_warnings`
+///     - Added the `portability` Vulkan filter chain option
+///     - Added parameter widget hints and `libra_preset_get_runtime_param_widgets`
+///     - Added parameter translations and `libra_preset_get_runtime_params_localized`
+///     - Added the `freeze_frame_count_on_rewind` filter chain option
+///     - Added per-pass defines and `libra_preset_set_pass_define`
+///     - Added the `async_pipeline_rebuild` filter chain option for Vulkan and Direct3D 12,
+///       and `libra_vk_filter_chain_has_pending_pipelines`
+///     - Added `libra_instance_runtime_supported` and build information getters
+///     - Added `libra_preset_clone`
+///     - Added the `collect_statistics` filter chain option and `libra_*_filter_chain_get_frame_statistics`
+///     - Added `libra_instance_set_allocator`
+///     - Added `libra_error_write_source` and `libra_error_write_backtrace`
+///     - Added `libra_instance_option_supported`
+///     - Added the `device_context` filter chain option and `libra_*_device_context_*`
+///     - Added `libra_*_filter_chain_free_deferred` and `libra_*_filter_chain_collect_deferred` for Vulkan, Direct3D 12 and Metal
+///     - Added `libra_*_filter_chain_set_output_mesh` and `libra_vertex_t`
+///     - Added the `color_assist` filter chain option
+///     - Added the `black_frame_insertion` filter chain option
+///     - Added the `latency_markers` filter chain option and `libra_*_filter_chain_set_timestamp_queries` for Vulkan and Direct3D 12
+///     - Added `libra_instance_create`, `libra_instance_free` and the `instance` filter chain option
+///     - Added the `output_layout`, `final_output_layout` and `output_view` Vulkan frame options
+///     - Added `libra_*_filter_chain_create_async` and the `poll_pending`, `wait_pending` and
+///       `free_pending` functions of the pending filter chain handles
+///     - Added `libra_*_filter_chain_get_runtime_params` to list the parameters of a filter chain
+///     - Added the alias, source path, filter, wrap and scaling of passes to `libra_pass_info_t`, and `libra_pass_info_free`
+///     - Added the `LIBRA_SCALE_TYPE_VIEWPORT_ASPECT` and `LIBRA_SCALE_TYPE_ORIGINAL_ASPECT` scale types
+///     - Vulkan filter chains fall back to render pass objects if `use_dynamic_rendering` is set but the device does not support dynamic rendering
+///     - Added the `final_render_pass`, `final_subpass` and `final_command_buffer` Vulkan frame options
+///     - Added the `resource_heap` and `sampler_heap` Direct3D 12 filter chain options
+///     - Added the `mipmap_handoff` Direct3D 12 frame option
+#define LIBRASHADER_CURRENT_VERSION 3
 
 /// The current version of the librashader ABI.
 /// Used by the loader to check ABI compatibility.
@@ -1212,13 +3316,99 @@ typedef libra_error_t (*PFN_libra_mtl_filter_chain_free)(libra_mtl_filter_chain_
 extern "C" {
 #endif // __cplusplus
 
+/// Set the functions librashader allocates and frees memory with.
+///
+/// The allocator is used for all memory allocated by librashader, including presets,
+/// filter chains, errors and strings returned to the caller. Memory allocated by the shader
+/// compilers librashader links against, and by graphics drivers, is not affected.
+///
+/// The allocator can only be set once, and must be set before calling any other librashader
+/// function that allocates memory. Returns true if the allocator was set, or false if
+/// `allocator` or any of its functions are null, or if librashader already allocated memory,
+/// in which case the system allocator stays in use.
+///
+/// Allocation failures abort the process, so frontends that need to bound the memory
+/// librashader uses should check the limit before loading presets and creating filter chains.
+///
+/// ## Safety
+/// - `allocator` must be either null or a valid pointer to a `libra_allocator_t`.
+/// - The functions of the allocator must stay valid, and `userdata` must stay valid for them,
+///   until the process exits.
+bool libra_instance_set_allocator(const struct libra_allocator_t *allocator);
+
+/// Create a token to cancel filter chain creation with.
+///
+/// The token is passed to filter chain creation with the `cancel` filter chain option,
+/// and can be cancelled from any thread while the filter chain is being created.
+/// A token that was cancelled stays cancelled, so a new token must be created for
+/// every filter chain that may be cancelled.
+///
+/// ## Safety
+///  - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_cancel_token_t`.
+/// ## Returns
+///  - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+libra_error_t libra_cancel_token_create(libra_cancel_token_t *out);
+
+/// Cancel filter chain creation with the token.
+///
+/// Filter chain creation with this token stops between shader passes, releases the
+/// objects that were already created, and fails. This function is thread safe.
+///
+/// ## Safety
+/// - `token` must be either null, or a valid and aligned pointer to a `libra_cancel_token_t`.
+libra_error_t libra_cancel_token_cancel(const libra_cancel_token_t *token);
+
+/// Free the cancellation token.
+///
+/// If `token` is null, this function does nothing. The resulting value in `token` then becomes
+/// null. Filter chain creation that was passed the token must have returned before the token
+/// is freed.
+///
+/// ## Safety
+/// - `token` must be a valid and aligned pointer to a `libra_cancel_token_t`
+libra_error_t libra_cancel_token_free(libra_cancel_token_t *token);
+
+/// Free the strings of information about a shader pass.
+///
+/// The `alias` and `path` fields of `info` then become null.
+///
+/// ## Safety
+/// - `info` must be a valid and aligned pointer to a `libra_pass_info_t` that was written by
+///   `libra_*_filter_chain_get_pass_info`.
+libra_error_t libra_pass_info_free(libra_pass_info_t *info);
+
+/// Create a librashader instance, that owns the thread pool that compiles shaders, the
+/// directory of the shader and pipeline cache, and the callback that receives log messages.
+///
+/// Filter chains are created from the instance by passing it in the `instance` filter chain
+/// option. Filter chains created without an instance use the global thread pool and the
+/// default cache directory.
+///
+/// ## Safety
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_instance_handle_t`.
+/// ## Returns
+/// - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+libra_error_t libra_instance_create(const struct instance_opt_t *options,
+                                   libra_instance_handle_t *out);
+
+/// Free the librashader instance.
+///
+/// If `instance` is null, this function does nothing. The resulting value in `instance` then
+/// becomes null. Filter chains that were created from the instance stay valid, but the
+/// instance must not be freed while a filter chain is being created from it.
+///
+/// ## Safety
+/// - `instance` must be a valid and aligned pointer to a `libra_instance_handle_t`
+libra_error_t libra_instance_free(libra_instance_handle_t *instance);
+
 /// Get the error code corresponding to this error object.
 ///
 /// ## Safety
 ///   - `error` must be valid and initialized.
 LIBRA_ERRNO libra_error_errno(libra_error_t error);
 
-/// Print the error message.
+/// Print the error message, followed by the errors that caused it and the backtrace if captured.
 ///
 /// If `error` is null, this function does nothing and returns 1. Otherwise, this function returns 0.
 /// ## Safety
@@ -1242,11 +3432,41 @@ int32_t libra_error_free(libra_error_t *error);
 int32_t libra_error_write(libra_error_t error,
                           char **out);
 
-/// Frees an error string previously allocated by `libra_error_write`.
+/// Writes the message of an error in the chain of errors that caused the error into `out`.
+///
+/// The error at depth 0 is the error itself, the error at depth 1 is the error that caused it,
+/// and so on. The chain can be iterated by increasing `depth` until this function returns 1.
+///
+/// If `error` is null or there is no error at `depth`, this function does nothing and returns 1.
+/// Otherwise, this function returns 0.
+/// ## Safety
+///   - `error` must be a valid and initialized instance of `libra_error_t`.
+///   - `out` must be a non-null pointer. The resulting string must not be modified,
+///     and must be freed with `libra_error_free_string`.
+int32_t libra_error_write_source(libra_error_t error, size_t depth, char **out);
+
+/// Writes the backtrace of where the error was returned from the API into `out`.
+///
+/// The backtrace is captured when the error is returned across the API boundary, so it ends
+/// in the API function that failed rather than where the error was first raised.
+/// Backtraces are only captured if the `RUST_LIB_BACKTRACE` or `RUST_BACKTRACE` environment
+/// variable is set to `1` when the first error is returned.
+///
+/// If `error` is null or no backtrace was captured, this function does nothing and returns 1.
+/// Otherwise, this function returns 0.
+/// ## Safety
+///   - `error` must be a valid and initialized instance of `libra_error_t`.
+///   - `out` must be a non-null pointer. The resulting string must not be modified,
+///     and must be freed with `libra_error_free_string`.
+int32_t libra_error_write_backtrace(libra_error_t error, char **out);
+
+/// Frees an error string previously allocated by `libra_error_write`, `libra_error_write_source`
+/// or `libra_error_write_backtrace`.
 ///
 /// After freeing, the pointer will be set to null.
 /// ## Safety
-///   - If `libra_error_write` is not null, it must point to a string previously returned by `libra_error_write`.
+///   - If `out` is not null, it must point to a string previously returned by `libra_error_write`,
+///     `libra_error_write_source` or `libra_error_write_backtrace`.
 ///     Attempting to free anything else, including strings or objects from other librashader functions, is immediate
 ///     Undefined Behaviour.
 int32_t libra_error_free_string(char **out);
@@ -1314,6 +3534,24 @@ libra_error_t libra_preset_create_with_options(const char *filename,
 /// - `preset` must be a valid and aligned pointer to a `libra_shader_preset_t`.
 libra_error_t libra_preset_free(libra_shader_preset_t *preset);
 
+/// Create a copy of the preset.
+///
+/// The copy is a deep copy that shares no state with the original preset, and must be
+/// freed separately with `libra_preset_free`. Since creating a filter chain invalidates
+/// the preset it is created from, a copy can be passed to filter chain creation to keep
+/// using the original preset afterwards.
+///
+/// Keeping an unmodified copy also allows parameter edits to be reset to the values
+/// in the preset, by freeing the edited preset and cloning the unmodified copy again.
+///
+/// ## Safety
+/// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+/// - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_shader_preset_t`.
+/// ## Returns
+///  - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+libra_error_t libra_preset_clone(const libra_shader_preset_t *preset,
+                                 libra_shader_preset_t *out);
+
 /// Set the value of the parameter in the preset.
 ///
 /// ## Safety
@@ -1331,6 +3569,23 @@ libra_error_t libra_preset_get_param(const libra_shader_preset_t *preset,
                                      const char *name,
                                      float *value);
 
+/// Inject a preprocessor define into the source of a shader pass of the preset.
+///
+/// The define is inserted as `#define <name> <value>` after the version header of the
+/// pass when a filter chain is created from the preset, replacing any previous define
+/// with the same name in that pass. Passes with different defines are compiled and
+/// cached separately.
+///
+/// ## Safety
+/// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+/// - `name` must be null or a valid and aligned pointer to a string.
+/// - `value` may be null, in which case the define has no replacement text. Otherwise,
+///    it must be a valid and aligned pointer to a string.
+libra_error_t libra_preset_set_pass_define(libra_shader_preset_t *preset,
+                                           uint64_t pass,
+                                           const char *name,
+                                           const char *value);
+
 /// Pretty print the shader preset.
 ///
 /// ## Safety
@@ -1339,6 +3594,9 @@ libra_error_t libra_preset_print(libra_shader_preset_t *preset);
 
 /// Get a list of runtime parameters.
 ///
+/// The parameters are read from the shader sources of the preset, so this does not require a filter
+/// chain or graphics device to be created. Parameters declared in more than one pass are only listed once.
+///
 /// ## Safety
 /// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
 /// - `out` must be an aligned pointer to a `libra_preset_parameter_list_t`.
@@ -1350,6 +3608,24 @@ libra_error_t libra_preset_print(libra_shader_preset_t *preset);
 libra_error_t libra_preset_get_runtime_params(const libra_shader_preset_t *preset,
                                               struct libra_preset_param_list_t *out);
 
+/// Get a list of runtime parameters, with descriptions replaced by their translations.
+///
+/// Parameters without a translation in `translations` keep the description declared in
+/// the shader. The output struct must be freed with `libra_preset_free_runtime_params`.
+///
+/// ## Safety
+/// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+/// - `translations` must be null or a valid and aligned pointer to a `libra_preset_translations_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_parameter_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+/// - It is safe to call `libra_preset_get_runtime_params_localized` multiple times, however
+///   the output struct must only be freed once per call.
+libra_error_t libra_preset_get_runtime_params_localized(const libra_shader_preset_t *preset,
+                                                        const libra_preset_translations_t *translations,
+                                                        struct libra_preset_param_list_t *out);
+
 /// Free the runtime parameters.
 ///
 /// Unlike the other `free` functions provided by librashader,
@@ -1371,11 +3647,178 @@ libra_error_t libra_preset_get_runtime_params(const libra_shader_preset_t *prese
 ///   in undefined behaviour.
 libra_error_t libra_preset_free_runtime_params(struct libra_preset_param_list_t preset);
 
-#if defined(LIBRA_RUNTIME_OPENGL)
+/// Get a list of the widgets that settings UIs should show for the runtime parameters.
+///
+/// Widgets are hinted after the step of a `#pragma parameter` declaration, and are listed
+/// in the same order as the parameters returned by `libra_preset_get_runtime_params`.
+/// Parameters without a hint are shown as sliders.
+///
+/// ## Safety
+/// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_param_widget_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+/// - It is safe to call `libra_preset_get_runtime_param_widgets` multiple times, however
+///   the output struct must only be freed once per call.
+libra_error_t libra_preset_get_runtime_param_widgets(const libra_shader_preset_t *preset,
+                                                     struct libra_preset_param_widget_list_t *out);
+
+/// Free a list of parameter widgets.
+///
+/// Like `libra_preset_free_runtime_params`, `libra_preset_free_runtime_param_widgets` takes the struct directly.
+///
+/// ## Safety
+/// - Any pointers rooted at `widgets` becomes invalid after this function returns,
+///   including any strings accessible via the input `libra_preset_param_widget_list_t`.
+///   The caller must ensure that there are no live pointers, aliased or unaliased,
+///   to data accessible via the input `libra_preset_param_widget_list_t`.
+///
+/// - If any struct fields of the input `libra_preset_param_widget_list_t` was modified from
+///   their values given after `libra_preset_get_runtime_param_widgets`, this may result
+///   in undefined behaviour.
+libra_error_t libra_preset_free_runtime_param_widgets(struct libra_preset_param_widget_list_t widgets);
+
+/// Get a list of the shader passes in the preset.
+///
+/// This can be used to display the contents of a preset before creating a filter chain.
+///
+/// ## Safety
+/// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_pass_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+/// - It is safe to call `libra_preset_get_passes` multiple times, however
+///   the output struct must only be freed once per call.
+libra_error_t libra_preset_get_passes(const libra_shader_preset_t *preset,
+                                      struct libra_preset_pass_list_t *out);
+
+/// Free a list of shader passes.
+///
+/// Like `libra_preset_free_runtime_params`, `libra_preset_free_passes` takes the struct directly.
+///
+/// ## Safety
+/// - Any pointers rooted at `passes` becomes invalid after this function returns,
+///   including any strings accessible via the input `libra_preset_pass_list_t`.
+///   The caller must ensure that there are no live pointers, aliased or unaliased,
+///   to data accessible via the input `libra_preset_pass_list_t`.
+///
+/// - If any struct fields of the input `libra_preset_pass_list_t` was modified from
+///   their values given after `libra_preset_get_passes`, this may result
+///   in undefined behaviour.
+libra_error_t libra_preset_free_passes(struct libra_preset_pass_list_t passes);
+
+/// Get a list of the lookup textures in the preset.
+///
+/// This can be used to check that every texture exists before creating a filter chain.
+///
+/// ## Safety
+/// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_texture_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+/// - It is safe to call `libra_preset_get_textures` multiple times, however
+///   the output struct must only be freed once per call.
+libra_error_t libra_preset_get_textures(const libra_shader_preset_t *preset,
+                                        struct libra_preset_texture_list_t *out);
+
+/// Free a list of lookup textures.
+///
+/// Like `libra_preset_free_runtime_params`, `libra_preset_free_textures` takes the struct directly.
+///
+/// ## Safety
+/// - Any pointers rooted at `textures` becomes invalid after this function returns,
+///   including any strings accessible via the input `libra_preset_texture_list_t`.
+///   The caller must ensure that there are no live pointers, aliased or unaliased,
+///   to data accessible via the input `libra_preset_texture_list_t`.
+///
+/// - If any struct fields of the input `libra_preset_texture_list_t` was modified from
+///   their values given after `libra_preset_get_textures`, this may result
+///   in undefined behaviour.
+libra_error_t libra_preset_free_textures(struct libra_preset_texture_list_t textures);
+
+/// Get a list of the problems that were ignored when parsing the preset, such as
+/// unknown keys.
+///
+/// Frontends can show these to users to help them find typos in their presets.
+/// Presets loaded with the `strict` preset option fail to load instead, so the list is always empty.
+///
+/// ## Safety
+/// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_warning_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+/// - It is safe to call `libra_preset_get_warnings` multiple times, however
+///   the output struct must only be freed once per call.
+libra_error_t libra_preset_get_warnings(const libra_shader_preset_t *preset,
+                                        struct libra_preset_warning_list_t *out);
+
+/// Free a list of preset warnings.
+///
+/// Like `libra_preset_free_runtime_params`, `libra_preset_free_warnings` takes the struct directly.
+///
+/// ## Safety
+/// - Any pointers rooted at `warnings` becomes invalid after this function returns,
+///   including any strings accessible via the input `libra_preset_warning_list_t`.
+///   The caller must ensure that there are no live pointers, aliased or unaliased,
+///   to data accessible via the input `libra_preset_warning_list_t`.
+///
+/// - If any struct fields of the input `libra_preset_warning_list_t` was modified from
+///   their values given after `libra_preset_get_warnings`, this may result
+///   in undefined behaviour.
+libra_error_t libra_preset_free_warnings(struct libra_preset_warning_list_t warnings);
+
+/// Compute a stable content hash of the preset and all of its resources.
+///
+/// The hash covers the preprocessed source of every shader pass, including the contents of every
+/// included file, the decoded pixels of every texture, and the configuration of every pass, texture
+/// and parameter. Paths are not hashed, so the same preset installed in different locations has
+/// the same hash. The hash can be used as a cache key or to deduplicate and verify presets.
+///
+/// Every resource of the preset is loaded from disk to compute the hash.
+///
+/// ## Safety
+/// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_hash_t`.
+libra_error_t libra_preset_get_content_hash(const libra_shader_preset_t *preset,
+                                            struct libra_preset_hash_t *out);
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Create a device context to share sampler objects and LUT textures between filter chains
+/// created on the same OpenGL context.
+///
+/// The device context is passed to filter chain creation with the `device_context` filter
+/// chain option. Filter chains keep the resources they use alive, so the device context
+/// can be freed while filter chains created with it are still in use.
+///
+/// ## Safety
+/// - The OpenGL context that filter chains are created on must be current on the calling thread.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_gl_device_context_create(libra_gl_loader_t loader,
+                                             libra_gl_device_context_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Free an OpenGL device context.
+///
+/// The resulting value in `context` then becomes null.
+/// ## Safety
+/// - `context` must be either null or a valid and aligned pointer to an initialized `libra_gl_device_context_t`.
+libra_error_t libra_gl_device_context_free(libra_gl_device_context_t *context);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
 /// Create the filter chain given the shader preset.
 ///
 /// The shader preset is immediately invalidated and must be recreated after
 /// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
+///
+/// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+/// compute pass fail to load with an error with code `REFLECT_ERROR`.
 ///
 /// ## Safety:
 /// - `preset` must be either null, or valid and aligned.
@@ -1387,6 +3830,101 @@ libra_error_t libra_gl_filter_chain_create(libra_shader_preset_t *preset,
                                            libra_gl_filter_chain_t *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Start compiling the shaders of the filter chain given the shader preset on a worker thread.
+///
+/// Shaders are compiled without blocking the calling thread. The filter chain is taken from
+/// the pending handle written to `out` with `libra_gl_filter_chain_poll_pending` or
+/// `libra_gl_filter_chain_wait_pending`, which create the rest of the filter chain with the
+/// OpenGL context loaded by `loader`.
+/// The progress callback of `options` is called on the worker thread while shaders are
+/// compiled, and on the thread that takes the filter chain afterwards.
+///
+/// The shader preset is immediately invalidated and must be recreated after
+/// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
+///
+/// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+/// compute pass fail to load with an error with code `REFLECT_ERROR`.
+///
+/// ## Safety:
+/// - The progress callback and its userdata, the cancel token and the instance of `options`
+///   must stay valid until the filter chain is taken from the pending handle. Freeing the
+///   pending handle does not stop the worker thread, so to release them before, cancel
+///   creation with the cancel token and take the result with
+///   `libra_gl_filter_chain_wait_pending`.
+/// - `preset` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_gl_filter_chain_create_async(libra_shader_preset_t *preset,
+                                                 libra_gl_loader_t loader,
+                                                 const struct filter_chain_gl_opt_t *options,
+                                                 libra_gl_pending_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Take the filter chain from a pending handle if its shaders have finished compiling, without
+/// blocking on shader compilation.
+///
+/// The OpenGL context the filter chain was created with must be current on the calling
+/// thread, since the rest of the filter chain is created on it once shaders are compiled.
+///
+/// If its shaders have finished compiling, `ready` is set to true and the resulting value in
+/// `pending` becomes null. The filter chain is then written to `out`, or the error of filter chain
+/// creation is returned. Otherwise, `ready` is set to false and `out` is unchanged.
+///
+/// ## Safety
+/// - `pending` must be a valid and aligned pointer to an initialized `libra_gl_pending_filter_chain_t`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+/// - `ready` must be a valid and aligned pointer to a `bool`.
+libra_error_t libra_gl_filter_chain_poll_pending(libra_gl_pending_filter_chain_t *pending,
+                                                 libra_gl_filter_chain_t *out,
+                                                 bool *ready);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Block until the filter chain of a pending handle has finished creating, and take it.
+///
+/// The OpenGL context the filter chain was created with must be current on the calling
+/// thread, since the rest of the filter chain is created on it once shaders are compiled.
+///
+/// The resulting value in `pending` becomes null. The filter chain is then written to `out`,
+/// or the error of filter chain creation is returned.
+///
+/// ## Safety
+/// - `pending` must be a valid and aligned pointer to an initialized `libra_gl_pending_filter_chain_t`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_gl_filter_chain_wait_pending(libra_gl_pending_filter_chain_t *pending,
+                                                 libra_gl_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Free a pending filter chain handle without taking the filter chain.
+///
+/// Shader compilation is not stopped, but its result is discarded once it is done.
+/// Compilation can be stopped early with the cancellation token of the filter chain options.
+/// The resulting value in `pending` then becomes null.
+///
+/// ## Safety
+/// - `pending` must be either null or a valid and aligned pointer to an initialized `libra_gl_pending_filter_chain_t`.
+libra_error_t libra_gl_filter_chain_free_pending(libra_gl_pending_filter_chain_t *pending);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Create a filter chain without any shader passes, that only scales the input
+/// to the viewport.
+///
+/// This allows the filter chain to be used to present frames even when no
+/// shader preset is active.
+///
+/// ## Safety:
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_gl_filter_chain_create_identity(libra_gl_loader_t loader,
+                                                    const struct filter_chain_gl_opt_t *options,
+                                                    libra_gl_filter_chain_t *out);
+#endif
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Draw a frame with the given parameters for the given filter chain.
 ///
@@ -1451,6 +3989,64 @@ libra_error_t libra_gl_filter_chain_get_param(const libra_gl_filter_chain_t *cha
                                               float *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Gets a handle to a parameter of the filter chain.
+///
+/// The handle can be used to get or set the parameter with
+/// `libra_gl_filter_chain_get_param_by_handle` and `libra_gl_filter_chain_set_param_by_handle`,
+/// without looking up the parameter by name.
+///
+/// If the parameter does not exist, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_gl_filter_chain_t`.
+/// - `param_name` must be either null or a null terminated string.
+libra_error_t libra_gl_filter_chain_get_param_handle(const libra_gl_filter_chain_t *chain,
+                                                     const char *param_name,
+                                                     libra_param_handle_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Sets a parameter for the filter chain by its handle.
+///
+/// If the handle is invalid, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_gl_filter_chain_t`.
+libra_error_t libra_gl_filter_chain_set_param_by_handle(libra_gl_filter_chain_t *chain,
+                                                        libra_param_handle_t handle,
+                                                        float value);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Gets a parameter for the filter chain by its handle.
+///
+/// If the handle is invalid, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_gl_filter_chain_t`.
+libra_error_t libra_gl_filter_chain_get_param_by_handle(const libra_gl_filter_chain_t *chain,
+                                                        libra_param_handle_t handle,
+                                                        float *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Get a list of the runtime parameters of the filter chain.
+///
+/// Parameters are listed in the order of their handles, so the handle of a parameter is its
+/// index in the list. The initial value of a parameter is the value declared in the shader,
+/// which may differ from the value set by the preset. Parameters that are set by the preset
+/// but not declared by any pass have an empty description.
+///
+/// The output struct must be freed with `libra_preset_free_runtime_params`.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_gl_filter_chain_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_param_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+libra_error_t libra_gl_filter_chain_get_runtime_params(const libra_gl_filter_chain_t *chain,
+                                                       struct libra_preset_param_list_t *out);
+#endif
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Sets the number of active passes for this chain.
 ///
@@ -1469,6 +4065,77 @@ libra_error_t libra_gl_filter_chain_get_active_pass_count(const libra_gl_filter_
                                                           uint32_t *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Gets the number of history frames kept by this chain, and the number of history
+/// frames requested by the shader passes.
+///
+/// If the history depth was capped with `max_history`, fewer frames may be kept than
+/// were requested.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_gl_filter_chain_t`.
+/// - `requested` and `kept` must be valid and aligned.
+libra_error_t libra_gl_filter_chain_get_history_depth(const libra_gl_filter_chain_t *chain,
+                                                      uint32_t *requested,
+                                                      uint32_t *kept);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Invalidates the last `frames` frames of history, such as after a rollback in runahead
+/// or netplay, so that shader passes do not blend frames that were rolled back.
+///
+/// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+/// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_gl_filter_chain_t`.
+libra_error_t libra_gl_filter_chain_invalidate_history(libra_gl_filter_chain_t *chain,
+                                                       uint32_t frames);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Sets the mesh to draw the final pass with, in place of the default quad.
+///
+/// The `count` vertices are drawn as a triangle strip. Positions are in the space of the
+/// default final pass quad, where `(0, 0)` is the top-left and `(1, 1)` the bottom-right
+/// corner of the viewport before the MVP is applied. Texture coordinates are in the same
+/// orientation. If `vertices` is null, the default quad is drawn again.
+///
+/// If `vertices` is not null and `count` is less than 3, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_gl_filter_chain_t`.
+/// - `vertices` must be either null or a valid and aligned pointer to an array of `count` `libra_vertex_t`.
+libra_error_t libra_gl_filter_chain_set_output_mesh(libra_gl_filter_chain_t *chain,
+                                                    const struct libra_vertex_t *vertices,
+                                                    size_t count);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Gets information about the shader pass at the given index, including the framebuffer
+/// format chosen for the pass.
+///
+/// The strings of the output struct must be freed with `libra_pass_info_free`.
+/// If the index is out of range, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_gl_filter_chain_t`.
+/// - `out` must be valid and aligned.
+libra_error_t libra_gl_filter_chain_get_pass_info(const libra_gl_filter_chain_t *chain,
+                                                  uint32_t index,
+                                                  struct libra_pass_info_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_OPENGL)
+/// Gets the CPU-side statistics of the commands recorded for the last frame, such as the
+/// number of draw calls.
+///
+/// If the filter chain was not created with the `collect_statistics` option, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_gl_filter_chain_t`.
+/// - `out` must be valid and aligned.
+libra_error_t libra_gl_filter_chain_get_frame_statistics(const libra_gl_filter_chain_t *chain,
+                                                         struct libra_frame_statistics_t *out);
+#endif
+
 #if defined(LIBRA_RUNTIME_OPENGL)
 /// Free a GL filter chain.
 ///
@@ -1479,16 +4146,41 @@ libra_error_t libra_gl_filter_chain_get_active_pass_count(const libra_gl_filter_
 libra_error_t libra_gl_filter_chain_free(libra_gl_filter_chain_t *chain);
 #endif
 
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Create a device context to share samplers and LUT textures between filter chains
+/// created on the same device.
+///
+/// The device context is passed to filter chain creation with the `device_context` filter
+/// chain option. Filter chains keep the resources they use alive, so the device context
+/// can be freed while filter chains created with it are still in use.
+///
+/// ## Safety
+/// - The handles provided in `vulkan` must be valid for the filter chains the device
+///   context is shared with.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_vk_device_context_create(struct libra_device_vk_t vulkan,
+                                             libra_vk_device_context_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Free a Vulkan device context.
+///
+/// The resulting value in `context` then becomes null.
+/// ## Safety
+/// - `context` must be either null or a valid and aligned pointer to an initialized `libra_vk_device_context_t`.
+libra_error_t libra_vk_device_context_free(libra_vk_device_context_t *context);
+#endif
+
 #if defined(LIBRA_RUNTIME_VULKAN)
 /// Create the filter chain given the shader preset.
 ///
 /// The shader preset is immediately invalidated and must be recreated after
 /// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
 ///
 /// ## Safety:
 /// - The handles provided in `vulkan` must be valid for the command buffers that
 ///   `libra_vk_filter_chain_frame` will write to.
-///    created with the `VK_KHR_dynamic_rendering` extension.
 /// - `preset` must be either null, or valid and aligned.
 /// - `options` must be either null, or valid and aligned.
 /// - `out` must be aligned, but may be null, invalid, or uninitialized.
@@ -1498,12 +4190,103 @@ libra_error_t libra_vk_filter_chain_create(libra_shader_preset_t *preset,
                                            libra_vk_filter_chain_t *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Start creating the filter chain given the shader preset on a worker thread.
+///
+/// Shaders are compiled without blocking the calling thread. The filter chain is taken from
+/// the pending handle written to `out` with `libra_vk_filter_chain_poll_pending` or
+/// `libra_vk_filter_chain_wait_pending`.
+/// The progress callback of `options` is called on the worker thread.
+///
+/// The shader preset is immediately invalidated and must be recreated after
+/// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
+///
+/// ## Safety:
+/// - The handles provided in `vulkan` must be valid for the command buffers that
+///   `libra_vk_filter_chain_frame` will write to.
+/// - The progress callback and its userdata, the cancel token and the instance of `options`,
+///   and the handles of `vulkan`, must stay valid until the filter chain is taken from the
+///   pending handle. Freeing the pending handle does not stop the worker thread, so to
+///   release them before, cancel creation with the cancel token and take the result with
+///   `libra_vk_filter_chain_wait_pending`.
+/// - LUTs are uploaded by submitting to the queue of `vulkan` on the worker thread. The queue
+///   must not be used by any other thread until the filter chain is taken from the pending handle,
+///   so a queue that the frontend does not submit to, such as a second graphics queue, should be given.
+/// - `preset` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_vk_filter_chain_create_async(libra_shader_preset_t *preset,
+                                                 struct libra_device_vk_t vulkan,
+                                                 const struct filter_chain_vk_opt_t *options,
+                                                 libra_vk_pending_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Take the filter chain from a pending handle if it has finished creating, without blocking.
+///
+/// If the filter chain has finished creating, `ready` is set to true and the resulting value in
+/// `pending` becomes null. The filter chain is then written to `out`, or the error of filter chain
+/// creation is returned. Otherwise, `ready` is set to false and `out` is unchanged.
+///
+/// ## Safety
+/// - `pending` must be a valid and aligned pointer to an initialized `libra_vk_pending_filter_chain_t`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+/// - `ready` must be a valid and aligned pointer to a `bool`.
+libra_error_t libra_vk_filter_chain_poll_pending(libra_vk_pending_filter_chain_t *pending,
+                                                 libra_vk_filter_chain_t *out,
+                                                 bool *ready);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Block until the filter chain of a pending handle has finished creating, and take it.
+///
+/// The resulting value in `pending` becomes null. The filter chain is then written to `out`,
+/// or the error of filter chain creation is returned.
+///
+/// ## Safety
+/// - `pending` must be a valid and aligned pointer to an initialized `libra_vk_pending_filter_chain_t`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_vk_filter_chain_wait_pending(libra_vk_pending_filter_chain_t *pending,
+                                                 libra_vk_filter_chain_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Free a pending filter chain handle without taking the filter chain.
+///
+/// Filter chain creation is not stopped, but the filter chain is freed once it is created.
+/// Creation can be stopped early with the cancellation token of the filter chain options.
+/// The resulting value in `pending` then becomes null.
+///
+/// ## Safety
+/// - `pending` must be either null or a valid and aligned pointer to an initialized `libra_vk_pending_filter_chain_t`.
+libra_error_t libra_vk_filter_chain_free_pending(libra_vk_pending_filter_chain_t *pending);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Create a filter chain without any shader passes, that only scales the input
+/// to the viewport.
+///
+/// This allows the filter chain to be used to present frames even when no
+/// shader preset is active.
+///
+/// ## Safety:
+/// - The handles provided in `vulkan` must be valid for the command buffers that
+///   `libra_vk_filter_chain_frame` will write to.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_vk_filter_chain_create_identity(struct libra_device_vk_t vulkan,
+                                                    const struct filter_chain_vk_opt_t *options,
+                                                    libra_vk_filter_chain_t *out);
+#endif
+
 #if defined(LIBRA_RUNTIME_VULKAN)
 /// Create the filter chain given the shader preset deferring and GPU-side initialization
 /// to the caller. This function therefore requires no external synchronization of the device queue.
 ///
 /// The shader preset is immediately invalidated and must be recreated after
 /// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
 ///
 /// ## Safety:
 /// - The handles provided in `vulkan` must be valid for the command buffers that
@@ -1526,9 +4309,10 @@ libra_error_t libra_vk_filter_chain_create_deferred(libra_shader_preset_t *prese
 /// Records rendering commands for a frame with the given parameters for the given filter chain
 /// to the input command buffer.
 ///
-/// A pipeline barrier **will not** be created for the final pass. The output image must be
-/// in `VK_COLOR_ATTACHMENT_OPTIMAL`, and will remain so after all shader passes. The caller must transition
-/// the output image to the final layout.
+/// Unless `final_output_layout` is set in `opt`, a pipeline barrier **will not** be created for the
+/// final pass. The output image must be in `VK_COLOR_ATTACHMENT_OPTIMAL` unless its current layout is
+/// given in the `output_layout` option, and will remain so after all shader passes. The caller must
+/// transition the output image to the final layout.
 ///
 /// ## Parameters
 ///
@@ -1597,12 +4381,70 @@ libra_error_t libra_vk_filter_chain_get_param(const libra_vk_filter_chain_t *cha
 #endif
 
 #if defined(LIBRA_RUNTIME_VULKAN)
-/// Sets the number of active passes for this chain.
+/// Gets a handle to a parameter of the filter chain.
+///
+/// The handle can be used to get or set the parameter with
+/// `libra_vk_filter_chain_get_param_by_handle` and `libra_vk_filter_chain_set_param_by_handle`,
+/// without looking up the parameter by name.
 ///
+/// If the parameter does not exist, returns an error.
 /// ## Safety
 /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
-libra_error_t libra_vk_filter_chain_set_active_pass_count(libra_vk_filter_chain_t *chain,
-                                                          uint32_t value);
+/// - `param_name` must be either null or a null terminated string.
+libra_error_t libra_vk_filter_chain_get_param_handle(const libra_vk_filter_chain_t *chain,
+                                                     const char *param_name,
+                                                     libra_param_handle_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Sets a parameter for the filter chain by its handle.
+///
+/// If the handle is invalid, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+libra_error_t libra_vk_filter_chain_set_param_by_handle(libra_vk_filter_chain_t *chain,
+                                                        libra_param_handle_t handle,
+                                                        float value);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Gets a parameter for the filter chain by its handle.
+///
+/// If the handle is invalid, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+libra_error_t libra_vk_filter_chain_get_param_by_handle(const libra_vk_filter_chain_t *chain,
+                                                        libra_param_handle_t handle,
+                                                        float *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Get a list of the runtime parameters of the filter chain.
+///
+/// Parameters are listed in the order of their handles, so the handle of a parameter is its
+/// index in the list. The initial value of a parameter is the value declared in the shader,
+/// which may differ from the value set by the preset. Parameters that are set by the preset
+/// but not declared by any pass have an empty description.
+///
+/// The output struct must be freed with `libra_preset_free_runtime_params`.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_param_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+libra_error_t libra_vk_filter_chain_get_runtime_params(const libra_vk_filter_chain_t *chain,
+                                                       struct libra_preset_param_list_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Sets the number of active passes for this chain.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+libra_error_t libra_vk_filter_chain_set_active_pass_count(libra_vk_filter_chain_t *chain,
+                                                          uint32_t value);
 #endif
 
 #if defined(LIBRA_RUNTIME_VULKAN)
@@ -1614,6 +4456,105 @@ libra_error_t libra_vk_filter_chain_get_active_pass_count(const libra_vk_filter_
                                                           uint32_t *out);
 #endif
 
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Gets the number of history frames kept by this chain, and the number of history
+/// frames requested by the shader passes.
+///
+/// If the history depth was capped with `max_history`, fewer frames may be kept than
+/// were requested.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+/// - `requested` and `kept` must be valid and aligned.
+libra_error_t libra_vk_filter_chain_get_history_depth(const libra_vk_filter_chain_t *chain,
+                                                      uint32_t *requested,
+                                                      uint32_t *kept);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Invalidates the last `frames` frames of history, such as after a rollback in runahead
+/// or netplay, so that shader passes do not blend frames that were rolled back.
+///
+/// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+/// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+libra_error_t libra_vk_filter_chain_invalidate_history(libra_vk_filter_chain_t *chain,
+                                                       uint32_t frames);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Sets the mesh to draw the final pass with, in place of the default quad.
+///
+/// The `count` vertices are drawn as a triangle strip. Positions are in the space of the
+/// default final pass quad, where `(0, 0)` is the top-left and `(1, 1)` the bottom-right
+/// corner of the viewport before the MVP is applied. Texture coordinates are in the same
+/// orientation. If `vertices` is null, the default quad is drawn again.
+///
+/// If `vertices` is not null and `count` is less than 3, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+/// - `vertices` must be either null or a valid and aligned pointer to an array of `count` `libra_vertex_t`.
+/// - The command buffers recorded with the previous mesh must have completed execution.
+libra_error_t libra_vk_filter_chain_set_output_mesh(libra_vk_filter_chain_t *chain,
+                                                    const struct libra_vertex_t *vertices,
+                                                    size_t count);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Writes GPU timestamps around the commands of the next frame to the given query pool.
+///
+/// The timestamp at `first_query` is written before the first command of the frame, and the
+/// timestamp at `first_query + 1` after the last command. The queries are only written for
+/// the next frame, so that frontends can rotate queries between frames in flight.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+/// - `pool` must be a `VK_QUERY_TYPE_TIMESTAMP` query pool with at least `first_query + 2` queries.
+/// - Both queries must be reset before the command buffer of the next frame executes.
+libra_error_t libra_vk_filter_chain_set_timestamp_queries(libra_vk_filter_chain_t *chain,
+                                                          VkQueryPool pool,
+                                                          uint32_t first_query);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Gets information about the shader pass at the given index, including the framebuffer
+/// format chosen for the pass.
+///
+/// The strings of the output struct must be freed with `libra_pass_info_free`.
+/// If the index is out of range, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+/// - `out` must be valid and aligned.
+libra_error_t libra_vk_filter_chain_get_pass_info(const libra_vk_filter_chain_t *chain,
+                                                  uint32_t index,
+                                                  struct libra_pass_info_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Gets the CPU-side statistics of the commands recorded for the last frame, such as the
+/// number of draw calls.
+///
+/// If the filter chain was not created with the `collect_statistics` option, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+/// - `out` must be valid and aligned.
+libra_error_t libra_vk_filter_chain_get_frame_statistics(const libra_vk_filter_chain_t *chain,
+                                                         struct libra_frame_statistics_t *out);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Gets whether the final pass of the last recorded frame was not drawn to some outputs,
+/// because the pipelines for their formats were still being built with the
+/// `async_pipeline_rebuild` option. Outputs that were not drawn are left unchanged.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+/// - `out` must be valid and aligned.
+libra_error_t libra_vk_filter_chain_has_pending_pipelines(const libra_vk_filter_chain_t *chain,
+                                                          bool *out);
+#endif
+
 #if defined(LIBRA_RUNTIME_VULKAN)
 /// Free a Vulkan filter chain.
 ///
@@ -1623,11 +4564,69 @@ libra_error_t libra_vk_filter_chain_get_active_pass_count(const libra_vk_filter_
 libra_error_t libra_vk_filter_chain_free(libra_vk_filter_chain_t *chain);
 #endif
 
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Free a Vulkan filter chain once `fence` is signaled, without waiting for the
+/// frames that were recorded with it to complete.
+///
+/// The filter chain is queued, and freed by a later call to `libra_vk_filter_chain_free_deferred`
+/// or `libra_vk_filter_chain_collect_deferred` after `fence` is signaled. If `fence` is
+/// `VK_NULL_HANDLE`, the filter chain is freed immediately, as with `libra_vk_filter_chain_free`.
+///
+/// The resulting value in `chain` then becomes null.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+/// - `fence` must be `VK_NULL_HANDLE`, or a fence of the device the filter chain was created with
+///   that is signaled after the last command buffer that the filter chain recorded into
+///   has completed. The fence must stay valid until the filter chain is freed.
+libra_error_t libra_vk_filter_chain_free_deferred(libra_vk_filter_chain_t *chain,
+                                                  VkFence fence);
+#endif
+
+#if defined(LIBRA_RUNTIME_VULKAN)
+/// Free the Vulkan filter chains queued with `libra_vk_filter_chain_free_deferred`
+/// whose fence is signaled.
+///
+/// Frontends should call this before destroying the device, after waiting for the device
+/// to be idle, so that every queued filter chain is freed.
+///
+/// ## Safety
+/// - The fences of the queued filter chains must still be valid.
+libra_error_t libra_vk_filter_chain_collect_deferred(void);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Create a device context to share sampler states and LUT textures between filter chains
+/// created on the same device.
+///
+/// The device context is passed to filter chain creation with the `device_context` filter
+/// chain option. Filter chains keep the resources they use alive, so the device context
+/// can be freed while filter chains created with it are still in use.
+///
+/// ## Safety
+/// - `device` must not be null.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d11_device_context_create(ID3D11Device * device,
+                                                libra_d3d11_device_context_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Free a Direct3D 11 device context.
+///
+/// The resulting value in `context` then becomes null.
+/// ## Safety
+/// - `context` must be either null or a valid and aligned pointer to an initialized `libra_d3d11_device_context_t`.
+libra_error_t libra_d3d11_device_context_free(libra_d3d11_device_context_t *context);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
 /// Create the filter chain given the shader preset.
 ///
 /// The shader preset is immediately invalidated and must be recreated after
 /// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
+///
+/// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+/// compute pass fail to load with an error with code `REFLECT_ERROR`.
 ///
 /// ## Safety:
 /// - `preset` must be either null, or valid and aligned.
@@ -1640,6 +4639,103 @@ libra_error_t libra_d3d11_filter_chain_create(libra_shader_preset_t *preset,
                                               libra_d3d11_filter_chain_t *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Start compiling the shaders of the filter chain given the shader preset on a worker thread.
+///
+/// Shaders are compiled without blocking the calling thread. The filter chain is taken from
+/// the pending handle written to `out` with `libra_d3d11_filter_chain_poll_pending` or
+/// `libra_d3d11_filter_chain_wait_pending`, which create the rest of the filter chain with
+/// `device`.
+/// The progress callback of `options` is called on the worker thread while shaders are
+/// compiled, and on the thread that takes the filter chain afterwards.
+///
+/// The shader preset is immediately invalidated and must be recreated after
+/// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
+///
+/// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+/// compute pass fail to load with an error with code `REFLECT_ERROR`.
+///
+/// ## Safety:
+/// - The progress callback and its userdata, the cancel token and the instance of `options`
+///   must stay valid until the filter chain is taken from the pending handle. Freeing the
+///   pending handle does not stop the worker thread, so to release them before, cancel
+///   creation with the cancel token and take the result with
+///   `libra_d3d11_filter_chain_wait_pending`.
+/// - `preset` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `device` must not be null.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d11_filter_chain_create_async(libra_shader_preset_t *preset,
+                                                    ID3D11Device * device,
+                                                    const struct filter_chain_d3d11_opt_t *options,
+                                                    libra_d3d11_pending_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Take the filter chain from a pending handle if its shaders have finished compiling, without
+/// blocking on shader compilation.
+///
+/// The rest of the filter chain is created with the device on the calling thread once
+/// shaders are compiled, so the immediate context of the device must not be used concurrently.
+///
+/// If its shaders have finished compiling, `ready` is set to true and the resulting value in
+/// `pending` becomes null. The filter chain is then written to `out`, or the error of filter chain
+/// creation is returned. Otherwise, `ready` is set to false and `out` is unchanged.
+///
+/// ## Safety
+/// - `pending` must be a valid and aligned pointer to an initialized `libra_d3d11_pending_filter_chain_t`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+/// - `ready` must be a valid and aligned pointer to a `bool`.
+libra_error_t libra_d3d11_filter_chain_poll_pending(libra_d3d11_pending_filter_chain_t *pending,
+                                                    libra_d3d11_filter_chain_t *out,
+                                                    bool *ready);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Block until the filter chain of a pending handle has finished creating, and take it.
+///
+/// The rest of the filter chain is created with the device on the calling thread once
+/// shaders are compiled, so the immediate context of the device must not be used concurrently.
+///
+/// The resulting value in `pending` becomes null. The filter chain is then written to `out`,
+/// or the error of filter chain creation is returned.
+///
+/// ## Safety
+/// - `pending` must be a valid and aligned pointer to an initialized `libra_d3d11_pending_filter_chain_t`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d11_filter_chain_wait_pending(libra_d3d11_pending_filter_chain_t *pending,
+                                                    libra_d3d11_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Free a pending filter chain handle without taking the filter chain.
+///
+/// Shader compilation is not stopped, but its result is discarded once it is done.
+/// Compilation can be stopped early with the cancellation token of the filter chain options.
+/// The resulting value in `pending` then becomes null.
+///
+/// ## Safety
+/// - `pending` must be either null or a valid and aligned pointer to an initialized `libra_d3d11_pending_filter_chain_t`.
+libra_error_t libra_d3d11_filter_chain_free_pending(libra_d3d11_pending_filter_chain_t *pending);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Create a filter chain without any shader passes, that only scales the input
+/// to the viewport.
+///
+/// This allows the filter chain to be used to present frames even when no
+/// shader preset is active.
+///
+/// ## Safety:
+/// - `options` must be either null, or valid and aligned.
+/// - `device` must not be null.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d11_filter_chain_create_identity(ID3D11Device * device,
+                                                       const struct filter_chain_d3d11_opt_t *options,
+                                                       libra_d3d11_filter_chain_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
 /// Create the filter chain given the shader preset, deferring and GPU-side initialization
 /// to the caller. This function is therefore requires no external synchronization of the
@@ -1648,6 +4744,10 @@ libra_error_t libra_d3d11_filter_chain_create(libra_shader_preset_t *preset,
 ///
 /// The shader preset is immediately invalidated and must be recreated after
 /// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
+///
+/// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+/// compute pass fail to load with an error with code `REFLECT_ERROR`.
 ///
 /// ## Safety:
 /// - `preset` must be either null, or valid and aligned.
@@ -1686,6 +4786,8 @@ libra_error_t libra_d3d11_filter_chain_create_deferred(libra_shader_preset_t *pr
 ///
 /// - `frame_count` is the number of frames passed to the shader
 /// - `image` is a pointer to a `ID3D11ShaderResourceView` that will serve as the source image for the frame.
+///    The view must be a `D3D11_SRV_DIMENSION_TEXTURE2D` view of a texture created with
+///    `D3D11_BIND_SHADER_RESOURCE`.
 /// - `out` is a pointer to a `ID3D11RenderTargetView` that will serve as the render target for the frame.
 ///
 /// - `viewport` is a pointer to a `libra_viewport_t` that specifies the area onto which scissor and viewport
@@ -1744,6 +4846,64 @@ libra_error_t libra_d3d11_filter_chain_get_param(const libra_d3d11_filter_chain_
                                                  float *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Gets a handle to a parameter of the filter chain.
+///
+/// The handle can be used to get or set the parameter with
+/// `libra_d3d11_filter_chain_get_param_by_handle` and `libra_d3d11_filter_chain_set_param_by_handle`,
+/// without looking up the parameter by name.
+///
+/// If the parameter does not exist, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d11_filter_chain_t`.
+/// - `param_name` must be either null or a null terminated string.
+libra_error_t libra_d3d11_filter_chain_get_param_handle(const libra_d3d11_filter_chain_t *chain,
+                                                        const char *param_name,
+                                                        libra_param_handle_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Sets a parameter for the filter chain by its handle.
+///
+/// If the handle is invalid, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d11_filter_chain_t`.
+libra_error_t libra_d3d11_filter_chain_set_param_by_handle(libra_d3d11_filter_chain_t *chain,
+                                                           libra_param_handle_t handle,
+                                                           float value);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Gets a parameter for the filter chain by its handle.
+///
+/// If the handle is invalid, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d11_filter_chain_t`.
+libra_error_t libra_d3d11_filter_chain_get_param_by_handle(const libra_d3d11_filter_chain_t *chain,
+                                                           libra_param_handle_t handle,
+                                                           float *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Get a list of the runtime parameters of the filter chain.
+///
+/// Parameters are listed in the order of their handles, so the handle of a parameter is its
+/// index in the list. The initial value of a parameter is the value declared in the shader,
+/// which may differ from the value set by the preset. Parameters that are set by the preset
+/// but not declared by any pass have an empty description.
+///
+/// The output struct must be freed with `libra_preset_free_runtime_params`.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d11_filter_chain_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_param_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+libra_error_t libra_d3d11_filter_chain_get_runtime_params(const libra_d3d11_filter_chain_t *chain,
+                                                          struct libra_preset_param_list_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
 /// Sets the number of active passes for this chain.
 ///
@@ -1762,6 +4922,77 @@ libra_error_t libra_d3d11_filter_chain_get_active_pass_count(const libra_d3d11_f
                                                              uint32_t *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Gets the number of history frames kept by this chain, and the number of history
+/// frames requested by the shader passes.
+///
+/// If the history depth was capped with `max_history`, fewer frames may be kept than
+/// were requested.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d11_filter_chain_t`.
+/// - `requested` and `kept` must be valid and aligned.
+libra_error_t libra_d3d11_filter_chain_get_history_depth(const libra_d3d11_filter_chain_t *chain,
+                                                         uint32_t *requested,
+                                                         uint32_t *kept);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Invalidates the last `frames` frames of history, such as after a rollback in runahead
+/// or netplay, so that shader passes do not blend frames that were rolled back.
+///
+/// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+/// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d11_filter_chain_t`.
+libra_error_t libra_d3d11_filter_chain_invalidate_history(libra_d3d11_filter_chain_t *chain,
+                                                          uint32_t frames);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Sets the mesh to draw the final pass with, in place of the default quad.
+///
+/// The `count` vertices are drawn as a triangle strip. Positions are in the space of the
+/// default final pass quad, where `(0, 0)` is the top-left and `(1, 1)` the bottom-right
+/// corner of the viewport before the MVP is applied. Texture coordinates are in the same
+/// orientation. If `vertices` is null, the default quad is drawn again.
+///
+/// If `vertices` is not null and `count` is less than 3, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d11_filter_chain_t`.
+/// - `vertices` must be either null or a valid and aligned pointer to an array of `count` `libra_vertex_t`.
+libra_error_t libra_d3d11_filter_chain_set_output_mesh(libra_d3d11_filter_chain_t *chain,
+                                                       const struct libra_vertex_t *vertices,
+                                                       size_t count);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Gets information about the shader pass at the given index, including the framebuffer
+/// format chosen for the pass.
+///
+/// The strings of the output struct must be freed with `libra_pass_info_free`.
+/// If the index is out of range, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d11_filter_chain_t`.
+/// - `out` must be valid and aligned.
+libra_error_t libra_d3d11_filter_chain_get_pass_info(const libra_d3d11_filter_chain_t *chain,
+                                                     uint32_t index,
+                                                     struct libra_pass_info_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
+/// Gets the CPU-side statistics of the commands recorded for the last frame, such as the
+/// number of draw calls.
+///
+/// If the filter chain was not created with the `collect_statistics` option, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d11_filter_chain_t`.
+/// - `out` must be valid and aligned.
+libra_error_t libra_d3d11_filter_chain_get_frame_statistics(const libra_d3d11_filter_chain_t *chain,
+                                                            struct libra_frame_statistics_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11))
 /// Free a D3D11 filter chain.
 ///
@@ -1771,11 +5002,38 @@ libra_error_t libra_d3d11_filter_chain_get_active_pass_count(const libra_d3d11_f
 libra_error_t libra_d3d11_filter_chain_free(libra_d3d11_filter_chain_t *chain);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Create a device context to share sampler states and LUT textures between filter chains
+/// created on the same device.
+///
+/// The device context is passed to filter chain creation with the `device_context` filter
+/// chain option. Filter chains keep the resources they use alive, so the device context
+/// can be freed while filter chains created with it are still in use. The device context
+/// must only be used with filter chains created on the same device.
+///
+/// ## Safety
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d9_device_context_create(libra_d3d9_device_context_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Free a Direct3D 9 device context.
+///
+/// The resulting value in `context` then becomes null.
+/// ## Safety
+/// - `context` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_device_context_t`.
+libra_error_t libra_d3d9_device_context_free(libra_d3d9_device_context_t *context);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Create the filter chain given the shader preset.
 ///
 /// The shader preset is immediately invalidated and must be recreated after
 /// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
+///
+/// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+/// compute pass fail to load with an error with code `REFLECT_ERROR`.
 ///
 /// ## Safety:
 /// - `preset` must be either null, or valid and aligned.
@@ -1789,32 +5047,129 @@ libra_error_t libra_d3d9_filter_chain_create(libra_shader_preset_t *preset,
 #endif
 
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
-/// Draw a frame with the given parameters for the given filter chain.
+/// Start compiling the shaders of the filter chain given the shader preset on a worker thread.
 ///
-/// ## Parameters
-/// - `chain` is a handle to the filter chain.
-/// - `frame_count` is the number of frames passed to the shader
-/// - `image` is a pointer to a `IDirect3DTexture9` that will serve as the source image for the frame.
-/// - `out` is a pointer to a `IDirect3DSurface9` that will serve as the render target for the frame.
+/// Shaders are compiled without blocking the calling thread. The filter chain is taken from
+/// the pending handle written to `out` with `libra_d3d9_filter_chain_poll_pending` or
+/// `libra_d3d9_filter_chain_wait_pending`, which create the rest of the filter chain with
+/// `device`.
+/// The progress callback of `options` is called on the worker thread while shaders are
+/// compiled, and on the thread that takes the filter chain afterwards.
 ///
-/// - `viewport` is a pointer to a `libra_viewport_t` that specifies the area onto which scissor and viewport
-///    will be applied to the render target. It may be null, in which case a default viewport spanning the
-///    entire render target will be used.
-/// - `mvp` is a pointer to an array of 16 `float` values to specify the model view projection matrix to
-///    be passed to the shader.
-/// - `options` is a pointer to options for the frame. Valid options are dependent on the `LIBRASHADER_API_VERSION`
-///    passed in. It may be null, in which case default options for the filter chain are used.
+/// The shader preset is immediately invalidated and must be recreated after
+/// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
 ///
-/// ## Safety
-/// - `chain` may be null, invalid, but not uninitialized. If `chain` is null or invalid, this
-///    function will return an error.
-/// - `viewport` may be null, or if it is not null, must be an aligned pointer to an instance of `libra_viewport_t`.
-/// - `mvp` may be null, or if it is not null, must be an aligned pointer to 16 consecutive `float`
-///    values for the model view projection matrix.
-/// - `opt` may be null, or if it is not null, must be an aligned pointer to a valid `frame_d3d9_opt_t`
-///    struct.
-/// - `out` must not be null.
-/// - `image` must not be null.
+/// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+/// compute pass fail to load with an error with code `REFLECT_ERROR`.
+///
+/// ## Safety:
+/// - The progress callback and its userdata, the cancel token and the instance of `options`
+///   must stay valid until the filter chain is taken from the pending handle. Freeing the
+///   pending handle does not stop the worker thread, so to release them before, cancel
+///   creation with the cancel token and take the result with
+///   `libra_d3d9_filter_chain_wait_pending`.
+/// - `preset` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `device` must not be null.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d9_filter_chain_create_async(libra_shader_preset_t *preset,
+                                                   IDirect3DDevice9 * device,
+                                                   const struct filter_chain_d3d9_opt_t *options,
+                                                   libra_d3d9_pending_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Take the filter chain from a pending handle if its shaders have finished compiling, without
+/// blocking on shader compilation.
+///
+/// The rest of the filter chain is created with the device on the calling thread once
+/// shaders are compiled, so the device must not be used concurrently.
+///
+/// If its shaders have finished compiling, `ready` is set to true and the resulting value in
+/// `pending` becomes null. The filter chain is then written to `out`, or the error of filter chain
+/// creation is returned. Otherwise, `ready` is set to false and `out` is unchanged.
+///
+/// ## Safety
+/// - `pending` must be a valid and aligned pointer to an initialized `libra_d3d9_pending_filter_chain_t`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+/// - `ready` must be a valid and aligned pointer to a `bool`.
+libra_error_t libra_d3d9_filter_chain_poll_pending(libra_d3d9_pending_filter_chain_t *pending,
+                                                   libra_d3d9_filter_chain_t *out,
+                                                   bool *ready);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Block until the filter chain of a pending handle has finished creating, and take it.
+///
+/// The rest of the filter chain is created with the device on the calling thread once
+/// shaders are compiled, so the device must not be used concurrently.
+///
+/// The resulting value in `pending` becomes null. The filter chain is then written to `out`,
+/// or the error of filter chain creation is returned.
+///
+/// ## Safety
+/// - `pending` must be a valid and aligned pointer to an initialized `libra_d3d9_pending_filter_chain_t`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d9_filter_chain_wait_pending(libra_d3d9_pending_filter_chain_t *pending,
+                                                   libra_d3d9_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Free a pending filter chain handle without taking the filter chain.
+///
+/// Shader compilation is not stopped, but its result is discarded once it is done.
+/// Compilation can be stopped early with the cancellation token of the filter chain options.
+/// The resulting value in `pending` then becomes null.
+///
+/// ## Safety
+/// - `pending` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_pending_filter_chain_t`.
+libra_error_t libra_d3d9_filter_chain_free_pending(libra_d3d9_pending_filter_chain_t *pending);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Create a filter chain without any shader passes, that only scales the input
+/// to the viewport.
+///
+/// This allows the filter chain to be used to present frames even when no
+/// shader preset is active.
+///
+/// ## Safety:
+/// - `options` must be either null, or valid and aligned.
+/// - `device` must not be null.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d9_filter_chain_create_identity(IDirect3DDevice9 * device,
+                                                      const struct filter_chain_d3d9_opt_t *options,
+                                                      libra_d3d9_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Draw a frame with the given parameters for the given filter chain.
+///
+/// ## Parameters
+/// - `chain` is a handle to the filter chain.
+/// - `frame_count` is the number of frames passed to the shader
+/// - `image` is a pointer to a `IDirect3DTexture9` that will serve as the source image for the frame.
+/// - `out` is a pointer to a `IDirect3DSurface9` that will serve as the render target for the frame.
+///
+/// - `viewport` is a pointer to a `libra_viewport_t` that specifies the area onto which scissor and viewport
+///    will be applied to the render target. It may be null, in which case a default viewport spanning the
+///    entire render target will be used.
+/// - `mvp` is a pointer to an array of 16 `float` values to specify the model view projection matrix to
+///    be passed to the shader.
+/// - `options` is a pointer to options for the frame. Valid options are dependent on the `LIBRASHADER_API_VERSION`
+///    passed in. It may be null, in which case default options for the filter chain are used.
+///
+/// ## Safety
+/// - `chain` may be null, invalid, but not uninitialized. If `chain` is null or invalid, this
+///    function will return an error.
+/// - `viewport` may be null, or if it is not null, must be an aligned pointer to an instance of `libra_viewport_t`.
+/// - `mvp` may be null, or if it is not null, must be an aligned pointer to 16 consecutive `float`
+///    values for the model view projection matrix.
+/// - `opt` may be null, or if it is not null, must be an aligned pointer to a valid `frame_d3d9_opt_t`
+///    struct.
+/// - `out` must not be null.
+/// - `image` must not be null.
 /// - You must ensure that only one thread has access to `chain` before you call this function. Only one
 ///   thread at a time may call this function.
 libra_error_t libra_d3d9_filter_chain_frame(libra_d3d9_filter_chain_t *chain,
@@ -1850,6 +5205,64 @@ libra_error_t libra_d3d9_filter_chain_get_param(const libra_d3d9_filter_chain_t
                                                 float *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Gets a handle to a parameter of the filter chain.
+///
+/// The handle can be used to get or set the parameter with
+/// `libra_d3d9_filter_chain_get_param_by_handle` and `libra_d3d9_filter_chain_set_param_by_handle`,
+/// without looking up the parameter by name.
+///
+/// If the parameter does not exist, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_filter_chain_t`.
+/// - `param_name` must be either null or a null terminated string.
+libra_error_t libra_d3d9_filter_chain_get_param_handle(const libra_d3d9_filter_chain_t *chain,
+                                                       const char *param_name,
+                                                       libra_param_handle_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Sets a parameter for the filter chain by its handle.
+///
+/// If the handle is invalid, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_filter_chain_t`.
+libra_error_t libra_d3d9_filter_chain_set_param_by_handle(libra_d3d9_filter_chain_t *chain,
+                                                          libra_param_handle_t handle,
+                                                          float value);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Gets a parameter for the filter chain by its handle.
+///
+/// If the handle is invalid, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_filter_chain_t`.
+libra_error_t libra_d3d9_filter_chain_get_param_by_handle(const libra_d3d9_filter_chain_t *chain,
+                                                          libra_param_handle_t handle,
+                                                          float *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Get a list of the runtime parameters of the filter chain.
+///
+/// Parameters are listed in the order of their handles, so the handle of a parameter is its
+/// index in the list. The initial value of a parameter is the value declared in the shader,
+/// which may differ from the value set by the preset. Parameters that are set by the preset
+/// but not declared by any pass have an empty description.
+///
+/// The output struct must be freed with `libra_preset_free_runtime_params`.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_filter_chain_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_param_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+libra_error_t libra_d3d9_filter_chain_get_runtime_params(const libra_d3d9_filter_chain_t *chain,
+                                                         struct libra_preset_param_list_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Sets the number of active passes for this chain.
 ///
@@ -1868,6 +5281,77 @@ libra_error_t libra_d3d9_filter_chain_get_active_pass_count(const libra_d3d9_fil
                                                             uint32_t *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Gets the number of history frames kept by this chain, and the number of history
+/// frames requested by the shader passes.
+///
+/// If the history depth was capped with `max_history`, fewer frames may be kept than
+/// were requested.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_filter_chain_t`.
+/// - `requested` and `kept` must be valid and aligned.
+libra_error_t libra_d3d9_filter_chain_get_history_depth(const libra_d3d9_filter_chain_t *chain,
+                                                        uint32_t *requested,
+                                                        uint32_t *kept);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Invalidates the last `frames` frames of history, such as after a rollback in runahead
+/// or netplay, so that shader passes do not blend frames that were rolled back.
+///
+/// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+/// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_filter_chain_t`.
+libra_error_t libra_d3d9_filter_chain_invalidate_history(libra_d3d9_filter_chain_t *chain,
+                                                         uint32_t frames);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Sets the mesh to draw the final pass with, in place of the default quad.
+///
+/// The `count` vertices are drawn as a triangle strip. Positions are in the space of the
+/// default final pass quad, where `(0, 0)` is the top-left and `(1, 1)` the bottom-right
+/// corner of the viewport before the MVP is applied. Texture coordinates are in the same
+/// orientation. If `vertices` is null, the default quad is drawn again.
+///
+/// If `vertices` is not null and `count` is less than 3, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_filter_chain_t`.
+/// - `vertices` must be either null or a valid and aligned pointer to an array of `count` `libra_vertex_t`.
+libra_error_t libra_d3d9_filter_chain_set_output_mesh(libra_d3d9_filter_chain_t *chain,
+                                                      const struct libra_vertex_t *vertices,
+                                                      size_t count);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Gets information about the shader pass at the given index, including the framebuffer
+/// format chosen for the pass.
+///
+/// The strings of the output struct must be freed with `libra_pass_info_free`.
+/// If the index is out of range, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_filter_chain_t`.
+/// - `out` must be valid and aligned.
+libra_error_t libra_d3d9_filter_chain_get_pass_info(const libra_d3d9_filter_chain_t *chain,
+                                                    uint32_t index,
+                                                    struct libra_pass_info_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
+/// Gets the CPU-side statistics of the commands recorded for the last frame, such as the
+/// number of draw calls.
+///
+/// If the filter chain was not created with the `collect_statistics` option, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_filter_chain_t`.
+/// - `out` must be valid and aligned.
+libra_error_t libra_d3d9_filter_chain_get_frame_statistics(const libra_d3d9_filter_chain_t *chain,
+                                                           struct libra_frame_statistics_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9))
 /// Free a d3d9 filter chain.
 ///
@@ -1882,6 +5366,10 @@ libra_error_t libra_d3d9_filter_chain_free(libra_d3d9_filter_chain_t *chain);
 ///
 /// The shader preset is immediately invalidated and must be recreated after
 /// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
+///
+/// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+/// compute pass fail to load with an error with code `REFLECT_ERROR`.
 ///
 /// ## Safety:
 /// - `preset` must be either null, or valid and aligned.
@@ -1894,12 +5382,104 @@ libra_error_t libra_d3d12_filter_chain_create(libra_shader_preset_t *preset,
                                               libra_d3d12_filter_chain_t *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Start creating the filter chain given the shader preset on a worker thread.
+///
+/// Shaders are compiled without blocking the calling thread. The filter chain is taken from
+/// the pending handle written to `out` with `libra_d3d12_filter_chain_poll_pending` or
+/// `libra_d3d12_filter_chain_wait_pending`.
+/// The progress callback of `options` is called on the worker thread.
+///
+/// The shader preset is immediately invalidated and must be recreated after
+/// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
+///
+/// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+/// compute pass fail to load with an error with code `REFLECT_ERROR`.
+///
+/// ## Safety:
+/// - `device` must not be null.
+/// - The progress callback and its userdata, the cancel token and the instance of `options`,
+///   and `device`, must stay valid until the filter chain is taken from the
+///   pending handle. Freeing the pending handle does not stop the worker thread, so to
+///   release them before, cancel creation with the cancel token and take the result with
+///   `libra_d3d12_filter_chain_wait_pending`.
+/// - `preset` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d12_filter_chain_create_async(libra_shader_preset_t *preset,
+                                                    ID3D12Device * device,
+                                                    const struct filter_chain_d3d12_opt_t *options,
+                                                    libra_d3d12_pending_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Take the filter chain from a pending handle if it has finished creating, without blocking.
+///
+/// If the filter chain has finished creating, `ready` is set to true and the resulting value in
+/// `pending` becomes null. The filter chain is then written to `out`, or the error of filter chain
+/// creation is returned. Otherwise, `ready` is set to false and `out` is unchanged.
+///
+/// ## Safety
+/// - `pending` must be a valid and aligned pointer to an initialized `libra_d3d12_pending_filter_chain_t`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+/// - `ready` must be a valid and aligned pointer to a `bool`.
+libra_error_t libra_d3d12_filter_chain_poll_pending(libra_d3d12_pending_filter_chain_t *pending,
+                                                    libra_d3d12_filter_chain_t *out,
+                                                    bool *ready);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Block until the filter chain of a pending handle has finished creating, and take it.
+///
+/// The resulting value in `pending` becomes null. The filter chain is then written to `out`,
+/// or the error of filter chain creation is returned.
+///
+/// ## Safety
+/// - `pending` must be a valid and aligned pointer to an initialized `libra_d3d12_pending_filter_chain_t`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d12_filter_chain_wait_pending(libra_d3d12_pending_filter_chain_t *pending,
+                                                    libra_d3d12_filter_chain_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Free a pending filter chain handle without taking the filter chain.
+///
+/// Filter chain creation is not stopped, but the filter chain is freed once it is created.
+/// Creation can be stopped early with the cancellation token of the filter chain options.
+/// The resulting value in `pending` then becomes null.
+///
+/// ## Safety
+/// - `pending` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_pending_filter_chain_t`.
+libra_error_t libra_d3d12_filter_chain_free_pending(libra_d3d12_pending_filter_chain_t *pending);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Create a filter chain without any shader passes, that only scales the input
+/// to the viewport.
+///
+/// This allows the filter chain to be used to present frames even when no
+/// shader preset is active.
+///
+/// ## Safety:
+/// - `options` must be either null, or valid and aligned.
+/// - `device` must not be null.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_d3d12_filter_chain_create_identity(ID3D12Device * device,
+                                                       const struct filter_chain_d3d12_opt_t *options,
+                                                       libra_d3d12_filter_chain_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
 /// Create the filter chain given the shader preset deferring and GPU-side initialization
 /// to the caller. This function therefore requires no external synchronization of the device queue.
 ///
 /// The shader preset is immediately invalidated and must be recreated after
 /// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
+///
+/// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+/// compute pass fail to load with an error with code `REFLECT_ERROR`.
 ///
 /// ## Safety:
 /// - `preset` must be either null, or valid and aligned.
@@ -2007,6 +5587,64 @@ libra_error_t libra_d3d12_filter_chain_get_param(const libra_d3d12_filter_chain_
                                                  float *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Gets a handle to a parameter of the filter chain.
+///
+/// The handle can be used to get or set the parameter with
+/// `libra_d3d12_filter_chain_get_param_by_handle` and `libra_d3d12_filter_chain_set_param_by_handle`,
+/// without looking up the parameter by name.
+///
+/// If the parameter does not exist, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+/// - `param_name` must be either null or a null terminated string.
+libra_error_t libra_d3d12_filter_chain_get_param_handle(const libra_d3d12_filter_chain_t *chain,
+                                                        const char *param_name,
+                                                        libra_param_handle_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Sets a parameter for the filter chain by its handle.
+///
+/// If the handle is invalid, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+libra_error_t libra_d3d12_filter_chain_set_param_by_handle(libra_d3d12_filter_chain_t *chain,
+                                                           libra_param_handle_t handle,
+                                                           float value);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Gets a parameter for the filter chain by its handle.
+///
+/// If the handle is invalid, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+libra_error_t libra_d3d12_filter_chain_get_param_by_handle(const libra_d3d12_filter_chain_t *chain,
+                                                           libra_param_handle_t handle,
+                                                           float *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Get a list of the runtime parameters of the filter chain.
+///
+/// Parameters are listed in the order of their handles, so the handle of a parameter is its
+/// index in the list. The initial value of a parameter is the value declared in the shader,
+/// which may differ from the value set by the preset. Parameters that are set by the preset
+/// but not declared by any pass have an empty description.
+///
+/// The output struct must be freed with `libra_preset_free_runtime_params`.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_param_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+libra_error_t libra_d3d12_filter_chain_get_runtime_params(const libra_d3d12_filter_chain_t *chain,
+                                                          struct libra_preset_param_list_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
 /// Sets the number of active passes for this chain.
 ///
@@ -2025,6 +5663,102 @@ libra_error_t libra_d3d12_filter_chain_get_active_pass_count(const libra_d3d12_f
                                                              uint32_t *out);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Gets the number of history frames kept by this chain, and the number of history
+/// frames requested by the shader passes.
+///
+/// If the history depth was capped with `max_history`, fewer frames may be kept than
+/// were requested.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+/// - `requested` and `kept` must be valid and aligned.
+libra_error_t libra_d3d12_filter_chain_get_history_depth(const libra_d3d12_filter_chain_t *chain,
+                                                         uint32_t *requested,
+                                                         uint32_t *kept);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Invalidates the last `frames` frames of history, such as after a rollback in runahead
+/// or netplay, so that shader passes do not blend frames that were rolled back.
+///
+/// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+/// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+libra_error_t libra_d3d12_filter_chain_invalidate_history(libra_d3d12_filter_chain_t *chain,
+                                                          uint32_t frames);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Sets the mesh to draw the final pass with, in place of the default quad.
+///
+/// The `count` vertices are drawn as a triangle strip. Positions are in the space of the
+/// default final pass quad, where `(0, 0)` is the top-left and `(1, 1)` the bottom-right
+/// corner of the viewport before the MVP is applied. Texture coordinates are in the same
+/// orientation. If `vertices` is null, the default quad is drawn again.
+///
+/// If `vertices` is not null and `count` is less than 3, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+/// - `vertices` must be either null or a valid and aligned pointer to an array of `count` `libra_vertex_t`.
+/// - The command lists recorded with the previous mesh must have completed execution.
+libra_error_t libra_d3d12_filter_chain_set_output_mesh(libra_d3d12_filter_chain_t *chain,
+                                                       const struct libra_vertex_t *vertices,
+                                                       size_t count);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Writes GPU timestamps around the commands of the next frame to the given query heap.
+///
+/// The timestamp at `first_query` is written before the first command of the frame, and the
+/// timestamp at `first_query + 1` after the last command. The queries are only written for
+/// the next frame, so that frontends can rotate queries between frames in flight.
+///
+/// If the `mipmap_handoff` frame option hands the frame off to other command lists, the query
+/// pair spans several command lists. The timestamp at `first_query` is written to the command
+/// list given to `libra_d3d12_filter_chain_frame`, and the timestamp at `first_query + 1` to the
+/// last graphics command list returned by the callback. The measured time then includes any
+/// time the graphics queue waits for the compute command lists.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+/// - `heap` must be a non-null pointer to a `D3D12_QUERY_HEAP_TYPE_TIMESTAMP` query heap
+///   with at least `first_query + 2` queries, that stays alive until every command list of
+///   the next frame has completed execution.
+/// - If the frame is handed off, the graphics command lists that contain the queries must be
+///   executed on the same queue.
+libra_error_t libra_d3d12_filter_chain_set_timestamp_queries(libra_d3d12_filter_chain_t *chain,
+                                                             ID3D12QueryHeap * heap,
+                                                             uint32_t first_query);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Gets information about the shader pass at the given index, including the framebuffer
+/// format chosen for the pass.
+///
+/// The strings of the output struct must be freed with `libra_pass_info_free`.
+/// If the index is out of range, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+/// - `out` must be valid and aligned.
+libra_error_t libra_d3d12_filter_chain_get_pass_info(const libra_d3d12_filter_chain_t *chain,
+                                                     uint32_t index,
+                                                     struct libra_pass_info_t *out);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Gets the CPU-side statistics of the commands recorded for the last frame, such as the
+/// number of draw calls.
+///
+/// If the filter chain was not created with the `collect_statistics` option, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+/// - `out` must be valid and aligned.
+libra_error_t libra_d3d12_filter_chain_get_frame_statistics(const libra_d3d12_filter_chain_t *chain,
+                                                            struct libra_frame_statistics_t *out);
+#endif
+
 #if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
 /// Free a D3D12 filter chain.
 ///
@@ -2034,11 +5768,67 @@ libra_error_t libra_d3d12_filter_chain_get_active_pass_count(const libra_d3d12_f
 libra_error_t libra_d3d12_filter_chain_free(libra_d3d12_filter_chain_t *chain);
 #endif
 
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Free a D3D12 filter chain once `fence` reaches `value`, without waiting for the
+/// frames that were recorded with it to complete.
+///
+/// The filter chain is queued, and freed by a later call to `libra_d3d12_filter_chain_free_deferred`
+/// or `libra_d3d12_filter_chain_collect_deferred` after the completed value of `fence` is
+/// at least `value`.
+///
+/// The resulting value in `chain` then becomes null.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+/// - `fence` must not be null, and must be signaled with `value` after the last command list
+///   that the filter chain recorded into has completed.
+libra_error_t libra_d3d12_filter_chain_free_deferred(libra_d3d12_filter_chain_t *chain,
+                                                     ID3D12Fence * fence,
+                                                     uint64_t value);
+#endif
+
+#if (defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12))
+/// Free the D3D12 filter chains queued with `libra_d3d12_filter_chain_free_deferred`
+/// whose fence has reached its value.
+///
+/// Frontends should call this before releasing the device, after waiting for the
+/// command queue to be idle, so that every queued filter chain is freed.
+libra_error_t libra_d3d12_filter_chain_collect_deferred(void);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Create a device context to share sampler states and LUT textures between filter chains
+/// created on the same device.
+///
+/// The device context is passed to filter chain creation with the `device_context` filter
+/// chain option. Filter chains keep the resources they use alive, so the device context
+/// can be freed while filter chains created with it are still in use.
+///
+/// ## Safety
+/// - `queue` must be valid for the device of the filter chains the device context is
+///   shared with.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_mtl_device_context_create(id<MTLCommandQueue> queue,
+                                              libra_mtl_device_context_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Free a Metal device context.
+///
+/// The resulting value in `context` then becomes null.
+/// ## Safety
+/// - `context` must be either null or a valid and aligned pointer to an initialized `libra_mtl_device_context_t`.
+libra_error_t libra_mtl_device_context_free(libra_mtl_device_context_t *context);
+#endif
+
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
 /// Create the filter chain given the shader preset.
 ///
 /// The shader preset is immediately invalidated and must be recreated after
 /// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
+///
+/// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+/// compute pass fail to load with an error with code `REFLECT_ERROR`.
 ///
 /// ## Safety:
 /// - `queue` must be valid for the command buffers
@@ -2053,12 +5843,117 @@ libra_error_t libra_mtl_filter_chain_create(libra_shader_preset_t *preset,
                                             libra_mtl_filter_chain_t *out);
 #endif
 
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Start compiling the shaders of the filter chain given the shader preset on a worker thread.
+///
+/// Shaders are compiled without blocking the calling thread. The filter chain is taken from
+/// the pending handle written to `out` with `libra_mtl_filter_chain_poll_pending` or
+/// `libra_mtl_filter_chain_wait_pending`, which create the rest of the filter chain with
+/// `queue`.
+/// The progress callback of `options` is called on the worker thread while shaders are
+/// compiled, and on the thread that takes the filter chain afterwards.
+///
+/// The shader preset is immediately invalidated and must be recreated after
+/// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
+///
+/// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+/// compute pass fail to load with an error with code `REFLECT_ERROR`.
+///
+/// ## Safety:
+/// - `queue` must be valid for the command buffers
+///     that `libra_mtl_filter_chain_frame` will write to.
+/// - `queue` must be a reference to a `id<MTLCommandQueue>`.
+/// - The progress callback and its userdata, the cancel token and the instance of `options`
+///   must stay valid until the filter chain is taken from the pending handle. Freeing the
+///   pending handle does not stop the worker thread, so to release them before, cancel
+///   creation with the cancel token and take the result with
+///   `libra_mtl_filter_chain_wait_pending`.
+/// - `preset` must be either null, or valid and aligned.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_mtl_filter_chain_create_async(libra_shader_preset_t *preset,
+                                                  id<MTLCommandQueue> queue,
+                                                  const struct filter_chain_mtl_opt_t *options,
+                                                  libra_mtl_pending_filter_chain_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Take the filter chain from a pending handle if its shaders have finished compiling, without
+/// blocking on shader compilation.
+///
+/// The rest of the filter chain is created on the calling thread once shaders are compiled,
+/// which waits for its LUTs to be uploaded with the command queue.
+///
+/// If its shaders have finished compiling, `ready` is set to true and the resulting value in
+/// `pending` becomes null. The filter chain is then written to `out`, or the error of filter chain
+/// creation is returned. Otherwise, `ready` is set to false and `out` is unchanged.
+///
+/// ## Safety
+/// - `pending` must be a valid and aligned pointer to an initialized `libra_mtl_pending_filter_chain_t`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+/// - `ready` must be a valid and aligned pointer to a `bool`.
+libra_error_t libra_mtl_filter_chain_poll_pending(libra_mtl_pending_filter_chain_t *pending,
+                                                  libra_mtl_filter_chain_t *out,
+                                                  bool *ready);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Block until the filter chain of a pending handle has finished creating, and take it.
+///
+/// The rest of the filter chain is created on the calling thread once shaders are compiled,
+/// which waits for its LUTs to be uploaded with the command queue.
+///
+/// The resulting value in `pending` becomes null. The filter chain is then written to `out`,
+/// or the error of filter chain creation is returned.
+///
+/// ## Safety
+/// - `pending` must be a valid and aligned pointer to an initialized `libra_mtl_pending_filter_chain_t`.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_mtl_filter_chain_wait_pending(libra_mtl_pending_filter_chain_t *pending,
+                                                  libra_mtl_filter_chain_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Free a pending filter chain handle without taking the filter chain.
+///
+/// Shader compilation is not stopped, but its result is discarded once it is done.
+/// Compilation can be stopped early with the cancellation token of the filter chain options.
+/// The resulting value in `pending` then becomes null.
+///
+/// ## Safety
+/// - `pending` must be either null or a valid and aligned pointer to an initialized `libra_mtl_pending_filter_chain_t`.
+libra_error_t libra_mtl_filter_chain_free_pending(libra_mtl_pending_filter_chain_t *pending);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Create a filter chain without any shader passes, that only scales the input
+/// to the viewport.
+///
+/// This allows the filter chain to be used to present frames even when no
+/// shader preset is active.
+///
+/// ## Safety:
+/// - `queue` must be valid for the command buffers
+///     that `libra_mtl_filter_chain_frame` will write to.
+/// - `queue` must be a reference to a `id<MTLCommandQueue>`.
+/// - `options` must be either null, or valid and aligned.
+/// - `out` must be aligned, but may be null, invalid, or uninitialized.
+libra_error_t libra_mtl_filter_chain_create_identity(id<MTLCommandQueue> queue,
+                                                     const struct filter_chain_mtl_opt_t *options,
+                                                     libra_mtl_filter_chain_t *out);
+#endif
+
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
 /// Create the filter chain given the shader preset deferring and GPU-side initialization
 /// to the caller. This function therefore requires no external synchronization of the device queue.
 ///
 /// The shader preset is immediately invalidated and must be recreated after
 /// the filter chain is created.
+/// Use `libra_preset_clone` to keep a copy of the preset for later use.
+///
+/// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+/// compute pass fail to load with an error with code `REFLECT_ERROR`.
 ///
 /// ## Safety:
 /// - `queue` must be valid for the command buffers
@@ -2143,6 +6038,64 @@ libra_error_t libra_mtl_filter_chain_get_param(const libra_mtl_filter_chain_t *c
                                                float *out);
 #endif
 
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Gets a handle to a parameter of the filter chain.
+///
+/// The handle can be used to get or set the parameter with
+/// `libra_mtl_filter_chain_get_param_by_handle` and `libra_mtl_filter_chain_set_param_by_handle`,
+/// without looking up the parameter by name.
+///
+/// If the parameter does not exist, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+/// - `param_name` must be either null or a null terminated string.
+libra_error_t libra_mtl_filter_chain_get_param_handle(const libra_mtl_filter_chain_t *chain,
+                                                      const char *param_name,
+                                                      libra_param_handle_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Sets a parameter for the filter chain by its handle.
+///
+/// If the handle is invalid, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+libra_error_t libra_mtl_filter_chain_set_param_by_handle(libra_mtl_filter_chain_t *chain,
+                                                         libra_param_handle_t handle,
+                                                         float value);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Gets a parameter for the filter chain by its handle.
+///
+/// If the handle is invalid, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+libra_error_t libra_mtl_filter_chain_get_param_by_handle(const libra_mtl_filter_chain_t *chain,
+                                                         libra_param_handle_t handle,
+                                                         float *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Get a list of the runtime parameters of the filter chain.
+///
+/// Parameters are listed in the order of their handles, so the handle of a parameter is its
+/// index in the list. The initial value of a parameter is the value declared in the shader,
+/// which may differ from the value set by the preset. Parameters that are set by the preset
+/// but not declared by any pass have an empty description.
+///
+/// The output struct must be freed with `libra_preset_free_runtime_params`.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+/// - `out` must be an aligned pointer to a `libra_preset_param_list_t`.
+/// - The output struct should be treated as immutable. Mutating any struct fields
+///   in the returned struct may at best cause memory leaks, and at worse
+///   cause undefined behaviour when later freed.
+libra_error_t libra_mtl_filter_chain_get_runtime_params(const libra_mtl_filter_chain_t *chain,
+                                                        struct libra_preset_param_list_t *out);
+#endif
+
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
 /// Sets the number of active passes for this chain.
 ///
@@ -2161,6 +6114,77 @@ libra_error_t libra_mtl_filter_chain_get_active_pass_count(const libra_mtl_filte
                                                            uint32_t *out);
 #endif
 
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Gets the number of history frames kept by this chain, and the number of history
+/// frames requested by the shader passes.
+///
+/// If the history depth was capped with `max_history`, fewer frames may be kept than
+/// were requested.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+/// - `requested` and `kept` must be valid and aligned.
+libra_error_t libra_mtl_filter_chain_get_history_depth(const libra_mtl_filter_chain_t *chain,
+                                                       uint32_t *requested,
+                                                       uint32_t *kept);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Invalidates the last `frames` frames of history, such as after a rollback in runahead
+/// or netplay, so that shader passes do not blend frames that were rolled back.
+///
+/// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+/// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+///
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+libra_error_t libra_mtl_filter_chain_invalidate_history(libra_mtl_filter_chain_t *chain,
+                                                        uint32_t frames);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Sets the mesh to draw the final pass with, in place of the default quad.
+///
+/// The `count` vertices are drawn as a triangle strip. Positions are in the space of the
+/// default final pass quad, where `(0, 0)` is the top-left and `(1, 1)` the bottom-right
+/// corner of the viewport before the MVP is applied. Texture coordinates are in the same
+/// orientation. If `vertices` is null, the default quad is drawn again.
+///
+/// If `vertices` is not null and `count` is less than 3, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+/// - `vertices` must be either null or a valid and aligned pointer to an array of `count` `libra_vertex_t`.
+libra_error_t libra_mtl_filter_chain_set_output_mesh(libra_mtl_filter_chain_t *chain,
+                                                     const struct libra_vertex_t *vertices,
+                                                     size_t count);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Gets information about the shader pass at the given index, including the framebuffer
+/// format chosen for the pass.
+///
+/// The strings of the output struct must be freed with `libra_pass_info_free`.
+/// If the index is out of range, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+/// - `out` must be valid and aligned.
+libra_error_t libra_mtl_filter_chain_get_pass_info(const libra_mtl_filter_chain_t *chain,
+                                                   uint32_t index,
+                                                   struct libra_pass_info_t *out);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Gets the CPU-side statistics of the commands recorded for the last frame, such as the
+/// number of draw calls.
+///
+/// If the filter chain was not created with the `collect_statistics` option, returns an error.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+/// - `out` must be valid and aligned.
+libra_error_t libra_mtl_filter_chain_get_frame_statistics(const libra_mtl_filter_chain_t *chain,
+                                                          struct libra_frame_statistics_t *out);
+#endif
+
 #if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
 /// Free a Metal filter chain.
 ///
@@ -2170,12 +6194,117 @@ libra_error_t libra_mtl_filter_chain_get_active_pass_count(const libra_mtl_filte
 libra_error_t libra_mtl_filter_chain_free(libra_mtl_filter_chain_t *chain);
 #endif
 
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Free a Metal filter chain once `command_buffer` has completed, without waiting for the
+/// frames that were recorded with it to complete.
+///
+/// The filter chain is queued, and freed by a later call to `libra_mtl_filter_chain_free_deferred`
+/// or `libra_mtl_filter_chain_collect_deferred` on the same thread after `command_buffer`
+/// has completed.
+///
+/// The resulting value in `chain` then becomes null.
+/// ## Safety
+/// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+/// - `command_buffer` must be the last command buffer that the filter chain recorded into,
+///   and must be committed before the filter chain is freed.
+libra_error_t libra_mtl_filter_chain_free_deferred(libra_mtl_filter_chain_t *chain,
+                                                   id<MTLCommandBuffer> command_buffer);
+#endif
+
+#if (defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL) && defined(__OBJC__))
+/// Free the Metal filter chains queued with `libra_mtl_filter_chain_free_deferred` on
+/// this thread whose command buffer has completed.
+///
+/// Frontends should call this before releasing the device, after waiting for the
+/// command buffers to complete, so that every queued filter chain is freed.
+libra_error_t libra_mtl_filter_chain_collect_deferred(void);
+#endif
+
+/// Create an empty set of parameter translations.
+///
+/// Translations map parameter names to localized descriptions, and can be used with any
+/// number of presets with `libra_preset_get_runtime_params_localized`.
+///
+/// ## Safety
+///  - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_preset_translations_t`.
+/// ## Returns
+///  - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+libra_error_t libra_preset_translations_create(libra_preset_translations_t *out);
+
+/// Free the parameter translations.
+///
+/// If `translations` is null, this function does nothing. The resulting value in `translations` then becomes
+/// null.
+///
+/// ## Safety
+/// - `translations` must be a valid and aligned pointer to a `libra_preset_translations_t`
+libra_error_t libra_preset_translations_free(libra_preset_translations_t *translations);
+
+/// Set the localized description of a parameter.
+///
+/// Subsequent calls with the same parameter name will overwrite the description.
+///
+/// ## Safety
+/// - `translations` must be null or a valid and aligned pointer to a `libra_preset_translations_t`.
+/// - `name` must be null or a valid and aligned pointer to a string.
+/// - `description` must be null or a valid and aligned pointer to a string.
+libra_error_t libra_preset_translations_set(libra_preset_translations_t *translations,
+                                            const char *name,
+                                            const char *description);
+
 /// Get the ABI version of the loaded instance.
 LIBRASHADER_ABI_VERSION libra_instance_abi_version(void);
 
 /// Get the API version of the loaded instance.
 LIBRASHADER_API_VERSION libra_instance_api_version(void);
 
+/// Get whether the loaded instance was built with support for the given runtime.
+///
+/// A runtime is only supported if its feature was enabled and the instance was built
+/// for a platform where that runtime is available. For example, Direct3D 12 is never
+/// supported by instances built for Linux or macOS.
+bool libra_instance_runtime_supported(LIBRA_RUNTIME runtime);
+
+/// Get whether the loaded instance honours a field of an options struct of the given
+/// API version, such as `rotation` of `frame_gl_opt_t` for API version 1.
+///
+/// Options structs are read according to their `version` field. Fields that were added in
+/// a later API version than `version`, or that the loaded instance does not know about
+/// because it is older than the frontend, are not read and take their default value instead.
+/// Frontends can use this to tell which options an instance honours before relying on them.
+///
+/// Returns false if `options` or `field` are null or unknown, or if the runtime of the
+/// options struct is not supported by the loaded instance.
+///
+/// ## Safety
+/// - `options` and `field` must be either null or valid null-terminated strings.
+bool libra_instance_option_supported(const char *options,
+                                     const char *field,
+                                     LIBRASHADER_API_VERSION version);
+
+/// Get the version of the loaded instance as a null-terminated string, such as `0.6.2`.
+///
+/// The returned string is static and must not be freed.
+const char *libra_instance_version_string(void);
+
+/// Get the commit the loaded instance was built from as a null-terminated string.
+///
+/// The commit is taken from the `LIBRASHADER_COMMIT` environment variable at build time if set,
+/// otherwise from the git checkout the instance was built in. If neither is available,
+/// the string is `unknown`.
+///
+/// The returned string is static and must not be freed.
+const char *libra_instance_commit_string(void);
+
+/// Get the Cargo features the loaded instance was built with as a null-terminated,
+/// comma-separated string, such as `runtime-opengl,runtime-vulkan,stable`.
+///
+/// Use `libra_instance_runtime_supported` to check whether a runtime is actually
+/// available, since runtime features are ignored on platforms that do not support them.
+///
+/// The returned string is static and must not be freed.
+const char *libra_instance_features_string(void);
+
 /// Create a wildcard context
 ///
 /// The C API does not allow directly setting certain variables
@@ -83,6 +83,26 @@ size_t __librashader__noop_instance_abi_version(void) { return 0; }
 
 size_t __librashader__noop_instance_api_version(void) { return 0; }
 
+bool __librashader__noop_instance_runtime_supported(LIBRA_RUNTIME runtime) {
+    return false;
+}
+
+bool __librashader__noop_instance_option_supported(
+    const char *options, const char *field, LIBRASHADER_API_VERSION version) {
+    return false;
+}
+
+const char *__librashader__noop_instance_version_string(void) { return ""; }
+
+const char *__librashader__noop_instance_commit_string(void) { return ""; }
+
+const char *__librashader__noop_instance_features_string(void) { return ""; }
+
+bool __librashader__noop_instance_set_allocator(
+    const struct libra_allocator_t *allocator) {
+    return false;
+}
+
 LIBRA_ERRNO __librashader__noop_error_errno(libra_error_t error) {
     return LIBRA_ERRNO_UNKNOWN_ERROR;
 }
@@ -95,8 +115,49 @@ int32_t __librashader__noop_error_write(libra_error_t error, char **out) {
     return 1;
 }
 
+int32_t __librashader__noop_error_write_source(libra_error_t error,
+                                               size_t depth, char **out) {
+    return 1;
+}
+
+int32_t __librashader__noop_error_write_backtrace(libra_error_t error,
+                                                  char **out) {
+    return 1;
+}
+
 int32_t __librashader__noop_error_free_string(char **out) { return 1; }
 
+libra_error_t __librashader__noop_cancel_token_create(
+    libra_cancel_token_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_cancel_token_cancel(
+    const libra_cancel_token_t *token) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_cancel_token_free(
+    libra_cancel_token_t *token) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_pass_info_free(libra_pass_info_t *info) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_instance_create(
+    const struct instance_opt_t *options, libra_instance_handle_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_instance_free(
+    libra_instance_handle_t *instance) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_preset_ctx_create(libra_preset_ctx_t *out) {
     *out = NULL;
     return NULL;
@@ -156,6 +217,23 @@ libra_error_t __librashader__noop_preset_ctx_set_core_aspect_orientation(
     return NULL;
 }
 
+libra_error_t __librashader__noop_preset_translations_create(
+    libra_preset_translations_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_preset_translations_free(
+    libra_preset_translations_t *translations) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_preset_translations_set(
+    libra_preset_translations_t *translations, const char *name,
+    const char *description) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_preset_create_with_context(
     const char *filename, libra_preset_ctx_t *context,
     libra_shader_preset_t *out) {
@@ -181,6 +259,12 @@ libra_error_t __librashader__noop_preset_free(libra_shader_preset_t *preset) {
     return NULL;
 }
 
+libra_error_t __librashader__noop_preset_clone(
+    const libra_shader_preset_t *preset, libra_shader_preset_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
 libra_error_t __librashader__noop_preset_set_param(
     libra_shader_preset_t *preset, const char *name, float value) {
     return NULL;
@@ -191,6 +275,12 @@ libra_error_t __librashader__noop_preset_get_param(
     return NULL;
 }
 
+libra_error_t __librashader__noop_preset_set_pass_define(
+    libra_shader_preset_t *preset, uint64_t pass, const char *name,
+    const char *value) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_preset_print(libra_shader_preset_t *preset) {
     return NULL;
 }
@@ -202,6 +292,51 @@ libra_error_t __librashader__noop_preset_free_runtime_params(
     struct libra_preset_param_list_t out) {
     return NULL;
 }
+libra_error_t __librashader__noop_preset_get_runtime_params_localized(
+    const libra_shader_preset_t *preset,
+    const libra_preset_translations_t *translations,
+    struct libra_preset_param_list_t *out) {
+    return NULL;
+}
+libra_error_t __librashader__noop_preset_get_runtime_param_widgets(
+    const libra_shader_preset_t *preset,
+    struct libra_preset_param_widget_list_t *out) {
+    return NULL;
+}
+libra_error_t __librashader__noop_preset_free_runtime_param_widgets(
+    struct libra_preset_param_widget_list_t widgets) {
+    return NULL;
+}
+libra_error_t __librashader__noop_preset_get_passes(
+    const libra_shader_preset_t *preset, struct libra_preset_pass_list_t *out) {
+    return NULL;
+}
+libra_error_t __librashader__noop_preset_free_passes(
+    struct libra_preset_pass_list_t out) {
+    return NULL;
+}
+libra_error_t __librashader__noop_preset_get_textures(
+    const libra_shader_preset_t *preset,
+    struct libra_preset_texture_list_t *out) {
+    return NULL;
+}
+libra_error_t __librashader__noop_preset_free_textures(
+    struct libra_preset_texture_list_t out) {
+    return NULL;
+}
+libra_error_t __librashader__noop_preset_get_warnings(
+    const libra_shader_preset_t *preset,
+    struct libra_preset_warning_list_t *out) {
+    return NULL;
+}
+libra_error_t __librashader__noop_preset_free_warnings(
+    struct libra_preset_warning_list_t out) {
+    return NULL;
+}
+libra_error_t __librashader__noop_preset_get_content_hash(
+    const libra_shader_preset_t *preset, struct libra_preset_hash_t *out) {
+    return NULL;
+}
 #if defined(LIBRA_RUNTIME_OPENGL)
 libra_error_t __librashader__noop_gl_filter_chain_create(
     libra_shader_preset_t *preset, libra_gl_loader_t loader,
@@ -211,6 +346,36 @@ libra_error_t __librashader__noop_gl_filter_chain_create(
     return NULL;
 }
 
+libra_error_t __librashader__noop_gl_filter_chain_create_async(
+    libra_shader_preset_t *preset, libra_gl_loader_t loader,
+    const struct filter_chain_gl_opt_t *options,
+    libra_gl_pending_filter_chain_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_filter_chain_poll_pending(
+    libra_gl_pending_filter_chain_t *pending,
+    libra_gl_filter_chain_t *out, bool *ready) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_filter_chain_wait_pending(
+    libra_gl_pending_filter_chain_t *pending, libra_gl_filter_chain_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_filter_chain_free_pending(
+    libra_gl_pending_filter_chain_t *pending) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_filter_chain_create_identity(
+    libra_gl_loader_t loader, const struct filter_chain_gl_opt_t *options,
+    libra_gl_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
 libra_error_t __librashader__noop_gl_filter_chain_frame(
     libra_gl_filter_chain_t *chain, size_t frame_count,
     struct libra_image_gl_t image, struct libra_image_gl_t out,
@@ -234,6 +399,27 @@ libra_error_t __librashader__noop_gl_filter_chain_get_param(
     return NULL;
 }
 
+libra_error_t __librashader__noop_gl_filter_chain_get_param_handle(
+    const libra_gl_filter_chain_t *chain, const char *param_name,
+    libra_param_handle_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_filter_chain_set_param_by_handle(
+    libra_gl_filter_chain_t *chain, libra_param_handle_t handle, float value) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_filter_chain_get_param_by_handle(
+    const libra_gl_filter_chain_t *chain, libra_param_handle_t handle, float *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_filter_chain_get_runtime_params(
+    const libra_gl_filter_chain_t *chain, struct libra_preset_param_list_t *out) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_gl_filter_chain_set_active_pass_count(
     libra_gl_filter_chain_t *chain, uint32_t value) {
     return NULL;
@@ -243,6 +429,45 @@ libra_error_t __librashader__noop_gl_filter_chain_get_active_pass_count(
     const libra_gl_filter_chain_t *chain, uint32_t *out) {
     return NULL;
 }
+
+libra_error_t __librashader__noop_gl_filter_chain_get_history_depth(
+    const libra_gl_filter_chain_t *chain, uint32_t *requested, uint32_t *kept) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_filter_chain_invalidate_history(
+    libra_gl_filter_chain_t *chain, uint32_t frames) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_filter_chain_set_output_mesh(
+    libra_gl_filter_chain_t *chain, const struct libra_vertex_t *vertices,
+    size_t count) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_filter_chain_get_pass_info(
+    const libra_gl_filter_chain_t *chain, uint32_t index,
+    struct libra_pass_info_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_filter_chain_get_frame_statistics(
+    const libra_gl_filter_chain_t *chain,
+    struct libra_frame_statistics_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_device_context_create(
+    libra_gl_loader_t loader, libra_gl_device_context_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_gl_device_context_free(
+    libra_gl_device_context_t *context) {
+    return NULL;
+}
 #endif
 
 #if defined(LIBRA_RUNTIME_VULKAN)
@@ -253,6 +478,36 @@ libra_error_t __librashader__noop_vk_filter_chain_create(
     return NULL;
 }
 
+libra_error_t __librashader__noop_vk_filter_chain_create_async(
+    libra_shader_preset_t *preset, struct libra_device_vk_t vulkan,
+    const struct filter_chain_vk_opt_t *options,
+    libra_vk_pending_filter_chain_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_poll_pending(
+    libra_vk_pending_filter_chain_t *pending,
+    libra_vk_filter_chain_t *out, bool *ready) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_wait_pending(
+    libra_vk_pending_filter_chain_t *pending, libra_vk_filter_chain_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_free_pending(
+    libra_vk_pending_filter_chain_t *pending) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_create_identity(
+    struct libra_device_vk_t vulkan, const struct filter_chain_vk_opt_t *options,
+    libra_vk_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
 libra_error_t __librashader__noop_vk_filter_chain_create_deferred(
     libra_shader_preset_t *preset, struct libra_device_vk_t vulkan,
     VkCommandBuffer command_buffer, const struct filter_chain_vk_opt_t *options,
@@ -284,6 +539,27 @@ libra_error_t __librashader__noop_vk_filter_chain_get_param(
     return NULL;
 }
 
+libra_error_t __librashader__noop_vk_filter_chain_get_param_handle(
+    const libra_vk_filter_chain_t *chain, const char *param_name,
+    libra_param_handle_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_set_param_by_handle(
+    libra_vk_filter_chain_t *chain, libra_param_handle_t handle, float value) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_get_param_by_handle(
+    const libra_vk_filter_chain_t *chain, libra_param_handle_t handle, float *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_get_runtime_params(
+    const libra_vk_filter_chain_t *chain, struct libra_preset_param_list_t *out) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_vk_filter_chain_set_active_pass_count(
     libra_vk_filter_chain_t *chain, uint32_t value) {
     return NULL;
@@ -293,6 +569,64 @@ libra_error_t __librashader__noop_vk_filter_chain_get_active_pass_count(
     const libra_vk_filter_chain_t *chain, uint32_t *out) {
     return NULL;
 }
+
+libra_error_t __librashader__noop_vk_filter_chain_get_history_depth(
+    const libra_vk_filter_chain_t *chain, uint32_t *requested, uint32_t *kept) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_invalidate_history(
+    libra_vk_filter_chain_t *chain, uint32_t frames) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_set_output_mesh(
+    libra_vk_filter_chain_t *chain, const struct libra_vertex_t *vertices,
+    size_t count) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_set_timestamp_queries(
+    libra_vk_filter_chain_t *chain, VkQueryPool pool, uint32_t first_query) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_get_pass_info(
+    const libra_vk_filter_chain_t *chain, uint32_t index,
+    struct libra_pass_info_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_get_frame_statistics(
+    const libra_vk_filter_chain_t *chain,
+    struct libra_frame_statistics_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_has_pending_pipelines(
+    const libra_vk_filter_chain_t *chain, bool *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_device_context_create(
+    struct libra_device_vk_t vulkan, libra_vk_device_context_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_device_context_free(
+    libra_vk_device_context_t *context) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_free_deferred(
+    libra_vk_filter_chain_t *chain, VkFence fence) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_vk_filter_chain_collect_deferred(void) {
+    return NULL;
+}
 #endif
 
 #if defined(LIBRA_RUNTIME_D3D11)
@@ -304,6 +638,36 @@ libra_error_t __librashader__noop_d3d11_filter_chain_create(
     return NULL;
 }
 
+libra_error_t __librashader__noop_d3d11_filter_chain_create_async(
+    libra_shader_preset_t *preset, ID3D11Device *device,
+    const struct filter_chain_d3d11_opt_t *options,
+    libra_d3d11_pending_filter_chain_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_poll_pending(
+    libra_d3d11_pending_filter_chain_t *pending,
+    libra_d3d11_filter_chain_t *out, bool *ready) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_wait_pending(
+    libra_d3d11_pending_filter_chain_t *pending, libra_d3d11_filter_chain_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_free_pending(
+    libra_d3d11_pending_filter_chain_t *pending) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_create_identity(
+    ID3D11Device *device, const struct filter_chain_d3d11_opt_t *options,
+    libra_d3d11_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
 libra_error_t __librashader__noop_d3d11_filter_chain_create_deferred(
     libra_shader_preset_t *preset, ID3D11Device *device,
     ID3D11DeviceContext *device_context,
@@ -336,6 +700,28 @@ libra_error_t __librashader__noop_d3d11_filter_chain_get_param(
     return NULL;
 }
 
+libra_error_t __librashader__noop_d3d11_filter_chain_get_param_handle(
+    const libra_d3d11_filter_chain_t *chain, const char *param_name,
+    libra_param_handle_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_set_param_by_handle(
+    libra_d3d11_filter_chain_t *chain, libra_param_handle_t handle, float value) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_get_param_by_handle(
+    const libra_d3d11_filter_chain_t *chain, libra_param_handle_t handle, float *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_get_runtime_params(
+    const libra_d3d11_filter_chain_t *chain,
+    struct libra_preset_param_list_t *out) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_d3d11_filter_chain_set_active_pass_count(
     libra_d3d11_filter_chain_t *chain, uint32_t value) {
     return NULL;
@@ -345,6 +731,45 @@ libra_error_t __librashader__noop_d3d11_filter_chain_get_active_pass_count(
     const libra_d3d11_filter_chain_t *chain, uint32_t *out) {
     return NULL;
 }
+
+libra_error_t __librashader__noop_d3d11_filter_chain_get_history_depth(
+    const libra_d3d11_filter_chain_t *chain, uint32_t *requested, uint32_t *kept) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_invalidate_history(
+    libra_d3d11_filter_chain_t *chain, uint32_t frames) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_set_output_mesh(
+    libra_d3d11_filter_chain_t *chain, const struct libra_vertex_t *vertices,
+    size_t count) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_get_pass_info(
+    const libra_d3d11_filter_chain_t *chain, uint32_t index,
+    struct libra_pass_info_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_filter_chain_get_frame_statistics(
+    const libra_d3d11_filter_chain_t *chain,
+    struct libra_frame_statistics_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_device_context_create(
+    ID3D11Device *device, libra_d3d11_device_context_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d11_device_context_free(
+    libra_d3d11_device_context_t *context) {
+    return NULL;
+}
 #endif
 
 #if defined(LIBRA_RUNTIME_D3D12)
@@ -356,6 +781,36 @@ libra_error_t __librashader__noop_d3d12_filter_chain_create(
     return NULL;
 }
 
+libra_error_t __librashader__noop_d3d12_filter_chain_create_async(
+    libra_shader_preset_t *preset, ID3D12Device * device,
+    const struct filter_chain_d3d12_opt_t *options,
+    libra_d3d12_pending_filter_chain_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_poll_pending(
+    libra_d3d12_pending_filter_chain_t *pending,
+    libra_d3d12_filter_chain_t *out, bool *ready) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_wait_pending(
+    libra_d3d12_pending_filter_chain_t *pending, libra_d3d12_filter_chain_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_free_pending(
+    libra_d3d12_pending_filter_chain_t *pending) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_create_identity(
+    ID3D12Device *device, const struct filter_chain_d3d12_opt_t *options,
+    libra_d3d12_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
 libra_error_t __librashader__noop_d3d12_filter_chain_create_deferred(
     libra_shader_preset_t *preset, ID3D12Device *device,
     ID3D12GraphicsCommandList *command_list,
@@ -388,6 +843,28 @@ libra_error_t __librashader__noop_d3d12_filter_chain_get_param(
     return NULL;
 }
 
+libra_error_t __librashader__noop_d3d12_filter_chain_get_param_handle(
+    const libra_d3d12_filter_chain_t *chain, const char *param_name,
+    libra_param_handle_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_set_param_by_handle(
+    libra_d3d12_filter_chain_t *chain, libra_param_handle_t handle, float value) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_get_param_by_handle(
+    const libra_d3d12_filter_chain_t *chain, libra_param_handle_t handle, float *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_get_runtime_params(
+    const libra_d3d12_filter_chain_t *chain,
+    struct libra_preset_param_list_t *out) {
+    return NULL;
+}
+
 libra_error_t __librashader__noop_d3d12_filter_chain_set_active_pass_count(
     libra_d3d12_filter_chain_t *chain, uint32_t value) {
     return NULL;
@@ -397,6 +874,49 @@ libra_error_t __librashader__noop_d3d12_filter_chain_get_active_pass_count(
     const libra_d3d12_filter_chain_t *chain, uint32_t *out) {
     return NULL;
 }
+
+libra_error_t __librashader__noop_d3d12_filter_chain_get_history_depth(
+    const libra_d3d12_filter_chain_t *chain, uint32_t *requested, uint32_t *kept) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_invalidate_history(
+    libra_d3d12_filter_chain_t *chain, uint32_t frames) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_set_output_mesh(
+    libra_d3d12_filter_chain_t *chain, const struct libra_vertex_t *vertices,
+    size_t count) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_set_timestamp_queries(
+    libra_d3d12_filter_chain_t *chain, ID3D12QueryHeap * heap, uint32_t
+    first_query) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_get_pass_info(
+    const libra_d3d12_filter_chain_t *chain, uint32_t index,
+    struct libra_pass_info_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_get_frame_statistics(
+    const libra_d3d12_filter_chain_t *chain,
+    struct libra_frame_statistics_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_free_deferred(
+    libra_d3d12_filter_chain_t *chain, ID3D12Fence *fence, uint64_t value) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d12_filter_chain_collect_deferred(void) {
+    return NULL;
+}
 #endif
 
 #if defined(LIBRA_RUNTIME_D3D9)
@@ -408,6 +928,36 @@ libra_error_t __librashader__noop_d3d9_filter_chain_create(
     return NULL;
 }
 
+libra_error_t __librashader__noop_d3d9_filter_chain_create_async(
+    libra_shader_preset_t *preset, IDirect3DDevice9 *device,
+    const struct filter_chain_d3d9_opt_t *options,
+    libra_d3d9_pending_filter_chain_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_poll_pending(
+    libra_d3d9_pending_filter_chain_t *pending,
+    libra_d3d9_filter_chain_t *out, bool *ready) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_wait_pending(
+    libra_d3d9_pending_filter_chain_t *pending, libra_d3d9_filter_chain_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_free_pending(
+    libra_d3d9_pending_filter_chain_t *pending) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_create_identity(
+    IDirect3DDevice9 *device, const struct filter_chain_d3d9_opt_t *options,
+    libra_d3d9_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
 libra_error_t __librashader__noop_d3d9_filter_chain_frame(
     libra_d3d9_filter_chain_t *chain, size_t frame_count,
     IDirect3DTexture9 *image, IDirect3DSurface9 * out,
@@ -431,65 +981,225 @@ libra_error_t __librashader__noop_d3d9_filter_chain_get_param(
     return NULL;
 }
 
-libra_error_t __librashader__noop_d3d9_filter_chain_set_active_pass_count(
-    libra_d3d9_filter_chain_t *chain, uint32_t value) {
+libra_error_t __librashader__noop_d3d9_filter_chain_get_param_handle(
+    const libra_d3d9_filter_chain_t *chain, const char *param_name,
+    libra_param_handle_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_set_param_by_handle(
+    libra_d3d9_filter_chain_t *chain, libra_param_handle_t handle, float value) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_get_param_by_handle(
+    const libra_d3d9_filter_chain_t *chain, libra_param_handle_t handle, float *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_get_runtime_params(
+    const libra_d3d9_filter_chain_t *chain,
+    struct libra_preset_param_list_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_set_active_pass_count(
+    libra_d3d9_filter_chain_t *chain, uint32_t value) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_get_active_pass_count(
+    const libra_d3d9_filter_chain_t *chain, uint32_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_get_history_depth(
+    const libra_d3d9_filter_chain_t *chain, uint32_t *requested, uint32_t *kept) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_invalidate_history(
+    libra_d3d9_filter_chain_t *chain, uint32_t frames) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_set_output_mesh(
+    libra_d3d9_filter_chain_t *chain, const struct libra_vertex_t *vertices,
+    size_t count) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_get_pass_info(
+    const libra_d3d9_filter_chain_t *chain, uint32_t index,
+    struct libra_pass_info_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_filter_chain_get_frame_statistics(
+    const libra_d3d9_filter_chain_t *chain,
+    struct libra_frame_statistics_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_device_context_create(
+    libra_d3d9_device_context_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_d3d9_device_context_free(
+    libra_d3d9_device_context_t *context) {
+    return NULL;
+}
+#endif
+
+#if defined(LIBRA_RUNTIME_METAL)
+libra_error_t __librashader__noop_mtl_filter_chain_create(
+    libra_shader_preset_t *preset, id<MTLCommandQueue> queue,
+    const struct filter_chain_mtl_opt_t *options,
+    libra_mtl_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_create_async(
+    libra_shader_preset_t *preset, id<MTLCommandQueue> queue,
+    const struct filter_chain_mtl_opt_t *options,
+    libra_mtl_pending_filter_chain_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_poll_pending(
+    libra_mtl_pending_filter_chain_t *pending,
+    libra_mtl_filter_chain_t *out, bool *ready) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_wait_pending(
+    libra_mtl_pending_filter_chain_t *pending, libra_mtl_filter_chain_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_free_pending(
+    libra_mtl_pending_filter_chain_t *pending) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_create_identity(
+    id<MTLCommandQueue> queue, const struct filter_chain_mtl_opt_t *options,
+    libra_mtl_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_create_deferred(
+    libra_shader_preset_t *preset, id<MTLCommandQueue> queue,
+    id<MTLCommandBuffer> command_buffer,
+    const struct filter_chain_mtl_opt_t *options,
+    libra_mtl_filter_chain_t *out) {
+    *out = NULL;
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_frame(
+    libra_mtl_filter_chain_t *chain, id<MTLCommandBuffer> command_buffer,
+    size_t frame_count, id<MTLTexture> image, id<MTLTexture> output,
+    const struct libra_viewport_t *viewport, const float *mvp,
+    const struct frame_mtl_opt_t *opt) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_free(
+    libra_mtl_filter_chain_t *chain) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_set_param(
+    libra_mtl_filter_chain_t *chain, const char *param_name, float value) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_get_param(
+    const libra_mtl_filter_chain_t *chain, const char *param_name, float *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_get_param_handle(
+    const libra_mtl_filter_chain_t *chain, const char *param_name,
+    libra_param_handle_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_set_param_by_handle(
+    libra_mtl_filter_chain_t *chain, libra_param_handle_t handle, float value) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_get_param_by_handle(
+    const libra_mtl_filter_chain_t *chain, libra_param_handle_t handle, float *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_get_runtime_params(
+    const libra_mtl_filter_chain_t *chain, struct libra_preset_param_list_t *out) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_set_active_pass_count(
+    libra_mtl_filter_chain_t *chain, uint32_t value) {
+    return NULL;
+}
+
+libra_error_t __librashader__noop_mtl_filter_chain_get_active_pass_count(
+    const libra_mtl_filter_chain_t *chain, uint32_t *out) {
     return NULL;
 }
 
-libra_error_t __librashader__noop_d3d9_filter_chain_get_active_pass_count(
-    const libra_d3d9_filter_chain_t *chain, uint32_t *out) {
+libra_error_t __librashader__noop_mtl_filter_chain_get_history_depth(
+    const libra_mtl_filter_chain_t *chain, uint32_t *requested, uint32_t *kept) {
     return NULL;
 }
-#endif
 
-#if defined(LIBRA_RUNTIME_METAL)
-libra_error_t __librashader__noop_mtl_filter_chain_create(
-    libra_shader_preset_t *preset, id<MTLCommandQueue> queue,
-    const struct filter_chain_mtl_opt_t *options,
-    libra_mtl_filter_chain_t *out) {
-    *out = NULL;
+libra_error_t __librashader__noop_mtl_filter_chain_invalidate_history(
+    libra_mtl_filter_chain_t *chain, uint32_t frames) {
     return NULL;
 }
 
-libra_error_t __librashader__noop_mtl_filter_chain_create_deferred(
-    libra_shader_preset_t *preset, id<MTLCommandQueue> queue,
-    id<MTLCommandBuffer> command_buffer,
-    const struct filter_chain_mtl_opt_t *options,
-    libra_mtl_filter_chain_t *out) {
-    *out = NULL;
+libra_error_t __librashader__noop_mtl_filter_chain_set_output_mesh(
+    libra_mtl_filter_chain_t *chain, const struct libra_vertex_t *vertices,
+    size_t count) {
     return NULL;
 }
 
-libra_error_t __librashader__noop_mtl_filter_chain_frame(
-    libra_mtl_filter_chain_t *chain, id<MTLCommandBuffer> command_buffer,
-    size_t frame_count, id<MTLTexture> image, id<MTLTexture> output,
-    const struct libra_viewport_t *viewport, const float *mvp,
-    const struct frame_mtl_opt_t *opt) {
+libra_error_t __librashader__noop_mtl_filter_chain_get_pass_info(
+    const libra_mtl_filter_chain_t *chain, uint32_t index,
+    struct libra_pass_info_t *out) {
     return NULL;
 }
 
-libra_error_t __librashader__noop_mtl_filter_chain_free(
-    libra_mtl_filter_chain_t *chain) {
+libra_error_t __librashader__noop_mtl_filter_chain_get_frame_statistics(
+    const libra_mtl_filter_chain_t *chain,
+    struct libra_frame_statistics_t *out) {
     return NULL;
 }
 
-libra_error_t __librashader__noop_mtl_filter_chain_set_param(
-    libra_mtl_filter_chain_t *chain, const char *param_name, float value) {
+libra_error_t __librashader__noop_mtl_device_context_create(
+    id<MTLCommandQueue> queue, libra_mtl_device_context_t *out) {
+    *out = NULL;
     return NULL;
 }
 
-libra_error_t __librashader__noop_mtl_filter_chain_get_param(
-    const libra_mtl_filter_chain_t *chain, const char *param_name, float *out) {
+libra_error_t __librashader__noop_mtl_device_context_free(
+    libra_mtl_device_context_t *context) {
     return NULL;
 }
 
-libra_error_t __librashader__noop_mtl_filter_chain_set_active_pass_count(
-    libra_mtl_filter_chain_t *chain, uint32_t value) {
+libra_error_t __librashader__noop_mtl_filter_chain_free_deferred(
+    libra_mtl_filter_chain_t *chain, id<MTLCommandBuffer> command_buffer) {
     return NULL;
 }
 
-libra_error_t __librashader__noop_mtl_filter_chain_get_active_pass_count(
-    const libra_mtl_filter_chain_t *chain, uint32_t *out) {
+libra_error_t __librashader__noop_mtl_filter_chain_collect_deferred(void) {
     return NULL;
 }
 #endif
@@ -506,6 +1216,47 @@ typedef struct libra_instance_t {
     /// The null instance has API version 0.
     PFN_libra_instance_api_version instance_api_version;
 
+    /// Get whether the loaded instance was built with support for the given
+    /// runtime.
+    ///
+    /// The null instance supports no runtimes.
+    PFN_libra_instance_runtime_supported instance_runtime_supported;
+
+    /// Get whether the loaded instance honours a field of an options struct of
+    /// the given API version, such as `rotation` of `frame_gl_opt_t` for API
+    /// version 1.
+    ///
+    /// Fields that were added in a later API version than `version`, or that
+    /// the loaded instance does not know about, take their default value.
+    ///
+    /// The null instance always returns false.
+    PFN_libra_instance_option_supported instance_option_supported;
+
+    /// Get the version of the loaded instance as a null-terminated string.
+    ///
+    /// The null instance returns an empty string.
+    PFN_libra_instance_version_string instance_version_string;
+
+    /// Get the commit the loaded instance was built from as a null-terminated
+    /// string.
+    ///
+    /// The null instance returns an empty string.
+    PFN_libra_instance_commit_string instance_commit_string;
+
+    /// Get the features the loaded instance was built with as a
+    /// null-terminated, comma-separated string.
+    ///
+    /// The null instance returns an empty string.
+    PFN_libra_instance_features_string instance_features_string;
+
+    /// Set the functions librashader allocates and frees memory with.
+    ///
+    /// The allocator can only be set once, and must be set before calling any
+    /// other librashader function that allocates memory.
+    ///
+    /// The null instance always returns false.
+    PFN_libra_instance_set_allocator instance_set_allocator;
+
     /// Create a wildcard context
     ///
     /// The C API does not allow directly setting certain variables
@@ -653,6 +1404,43 @@ typedef struct libra_instance_t {
     PFN_libra_preset_ctx_set_core_aspect_orientation
         preset_ctx_set_core_aspect_orientation;
 
+    /// Create an empty set of parameter translations.
+    ///
+    /// Translations map parameter names to localized descriptions, and can be
+    /// used with any number of presets with
+    /// `libra_preset_get_runtime_params_localized`.
+    ///
+    /// ## Safety
+    ///  - `out` must be either null, or an aligned pointer to an uninitialized
+    ///  or invalid `libra_preset_translations_t`.
+    /// ## Returns
+    ///  - If any parameters are null, `out` is unchanged, and this function
+    ///  returns `LIBRA_ERR_INVALID_PARAMETER`.
+    PFN_libra_preset_translations_create preset_translations_create;
+
+    /// Free the parameter translations.
+    ///
+    /// If `translations` is null, this function does nothing. The resulting
+    /// value in `translations` then becomes null.
+    ///
+    /// ## Safety
+    /// - `translations` must be a valid and aligned pointer to a
+    /// `libra_preset_translations_t`
+    PFN_libra_preset_translations_free preset_translations_free;
+
+    /// Set the localized description of a parameter.
+    ///
+    /// Subsequent calls with the same parameter name will overwrite the
+    /// description.
+    ///
+    /// ## Safety
+    /// - `translations` must be null or a valid and aligned pointer to a
+    /// `libra_preset_translations_t`.
+    /// - `name` must be null or a valid and aligned pointer to a string.
+    /// - `description` must be null or a valid and aligned pointer to a
+    /// string.
+    PFN_libra_preset_translations_set preset_translations_set;
+
     /// Load a preset.
     ///
     /// If this function is not loaded, `out` will unconditionally be set to
@@ -722,6 +1510,28 @@ typedef struct libra_instance_t {
     /// - `preset` must be a valid and aligned pointer to a shader preset.
     PFN_libra_preset_free preset_free;
 
+    /// Create a copy of the preset.
+    ///
+    /// The copy is a deep copy that shares no state with the original preset,
+    /// and must be freed separately with `libra_preset_free`. Since creating a
+    /// filter chain invalidates the preset it is created from, a copy can be
+    /// passed to filter chain creation to keep using the original preset
+    /// afterwards.
+    ///
+    /// Keeping an unmodified copy also allows parameter edits to be reset to
+    /// the values in the preset, by freeing the edited preset and cloning the
+    /// unmodified copy again.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a shader
+    ///   preset.
+    /// - `out` must be either null, or an aligned pointer to an uninitialized
+    ///   or invalid `libra_shader_preset_t`.
+    /// ## Returns
+    ///  - If any parameters are null, `out` is unchanged, and this function
+    ///    returns `LIBRA_ERR_INVALID_PARAMETER`.
+    PFN_libra_preset_clone preset_clone;
+
     /// Set the value of the parameter in the preset.
     ///
     /// ## Safety
@@ -739,6 +1549,22 @@ typedef struct libra_instance_t {
     /// - `value` may be a pointer to a uninitialized `float`.
     PFN_libra_preset_get_param preset_get_param;
 
+    /// Inject a preprocessor define into the source of a shader pass of the
+    /// preset.
+    ///
+    /// The define is inserted as `#define <name> <value>` after the version
+    /// header of the pass when a filter chain is created from the preset,
+    /// replacing any previous define with the same name in that pass. Passes
+    /// with different defines are compiled and cached separately.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a shader
+    /// preset.
+    /// - `name` must be null or a valid and aligned pointer to a string.
+    /// - `value` may be null, in which case the define has no replacement
+    /// text. Otherwise, it must be a valid and aligned pointer to a string.
+    PFN_libra_preset_set_pass_define preset_set_pass_define;
+
     /// Pretty print the shader preset.
     ///
     /// ## Safety
@@ -748,6 +1574,10 @@ typedef struct libra_instance_t {
 
     /// Get a list of runtime parameter names.
     ///
+    /// The parameters are read from the shader sources of the preset, so this
+    /// does not require a filter chain or graphics device to be created.
+    /// Parameters declared in more than one pass are only listed once.
+    ///
     /// ## Safety
     /// - `preset` must be null or a valid and aligned pointer to a shader
     /// preset.
@@ -787,13 +1617,199 @@ typedef struct libra_instance_t {
     ///   result in undefined behaviour.
     PFN_libra_preset_free_runtime_params preset_free_runtime_params;
 
+    /// Get a list of runtime parameters, with descriptions replaced by their
+    /// translations.
+    ///
+    /// Parameters without a translation in `translations` keep the description
+    /// declared in the shader. The output struct must be freed with
+    /// `libra_preset_free_runtime_params`.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a shader
+    /// preset.
+    /// - `translations` must be null or a valid and aligned pointer to a
+    /// `libra_preset_translations_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_parameter_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct
+    /// fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    /// - It is safe to call `libra_preset_get_runtime_params_localized`
+    /// multiple times, however
+    ///   the output struct must only be freed once per call.
+    PFN_libra_preset_get_runtime_params_localized
+        preset_get_runtime_params_localized;
+
+    /// Get a list of the widgets that settings UIs should show for the
+    /// runtime parameters.
+    ///
+    /// Widgets are hinted after the step of a `#pragma parameter`
+    /// declaration, and are listed in the same order as the parameters
+    /// returned by `libra_preset_get_runtime_params`. Parameters without a
+    /// hint are shown as sliders.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a shader
+    /// preset.
+    /// - `out` must be an aligned pointer to a
+    /// `libra_preset_param_widget_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct
+    /// fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    /// - It is safe to call `libra_preset_get_runtime_param_widgets` multiple
+    /// times, however
+    ///   the output struct must only be freed once per call.
+    PFN_libra_preset_get_runtime_param_widgets
+        preset_get_runtime_param_widgets;
+
+    /// Free a list of parameter widgets.
+    ///
+    /// Like `libra_preset_free_runtime_params`,
+    /// `libra_preset_free_runtime_param_widgets` takes the struct directly.
+    ///
+    /// ## Safety
+    /// - Any pointers rooted at `widgets` becomes invalid after this function
+    /// returns,
+    ///   including any strings accessible via the input
+    ///   `libra_preset_param_widget_list_t`.
+    ///
+    /// - If any struct fields of the input `libra_preset_param_widget_list_t`
+    /// was modified from
+    ///   their values given after `libra_preset_get_runtime_param_widgets`,
+    ///   this may result in undefined behaviour.
+    PFN_libra_preset_free_runtime_param_widgets
+        preset_free_runtime_param_widgets;
+
+    /// Get a list of the shader passes in the preset.
+    ///
+    /// This can be used to display the contents of a preset before creating a
+    /// filter chain.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a shader
+    /// preset.
+    /// - `out` must be an aligned pointer to a `libra_preset_pass_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct
+    /// fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    /// - It is safe to call `libra_preset_get_passes` multiple times, however
+    ///   the output struct must only be freed once per call.
+    PFN_libra_preset_get_passes preset_get_passes;
+
+    /// Free a list of shader passes.
+    ///
+    /// Like `libra_preset_free_runtime_params`, `libra_preset_free_passes`
+    /// takes the struct directly.
+    ///
+    /// ## Safety
+    /// - Any pointers rooted at `passes` becomes invalid after this function
+    /// returns,
+    ///   including any strings accessible via the input
+    ///   `libra_preset_pass_list_t`.
+    ///
+    /// - If any struct fields of the input `libra_preset_pass_list_t` was
+    /// modified from
+    ///   their values given after `libra_preset_get_passes`, this may result
+    ///   in undefined behaviour.
+    PFN_libra_preset_free_passes preset_free_passes;
+
+    /// Get a list of the lookup textures in the preset.
+    ///
+    /// This can be used to check that every texture exists before creating a
+    /// filter chain.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a shader
+    /// preset.
+    /// - `out` must be an aligned pointer to a `libra_preset_texture_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct
+    /// fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    /// - It is safe to call `libra_preset_get_textures` multiple times, however
+    ///   the output struct must only be freed once per call.
+    PFN_libra_preset_get_textures preset_get_textures;
+
+    /// Free a list of lookup textures.
+    ///
+    /// Like `libra_preset_free_runtime_params`, `libra_preset_free_textures`
+    /// takes the struct directly.
+    ///
+    /// ## Safety
+    /// - Any pointers rooted at `textures` becomes invalid after this function
+    /// returns,
+    ///   including any strings accessible via the input
+    ///   `libra_preset_texture_list_t`.
+    ///
+    /// - If any struct fields of the input `libra_preset_texture_list_t` was
+    /// modified from
+    ///   their values given after `libra_preset_get_textures`, this may result
+    ///   in undefined behaviour.
+    PFN_libra_preset_free_textures preset_free_textures;
+
+    /// Get a list of the problems that were ignored when parsing the preset,
+    /// such as unknown keys.
+    ///
+    /// Frontends can show these to users to help them find typos in their
+    /// presets. Presets loaded with the `strict` preset option fail to load
+    /// instead, so the list is always empty.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a shader
+    /// preset.
+    /// - `out` must be an aligned pointer to a `libra_preset_warning_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct
+    /// fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    /// - It is safe to call `libra_preset_get_warnings` multiple times, however
+    ///   the output struct must only be freed once per call.
+    PFN_libra_preset_get_warnings preset_get_warnings;
+
+    /// Free a list of preset warnings.
+    ///
+    /// Like `libra_preset_free_runtime_params`, `libra_preset_free_warnings`
+    /// takes the struct directly.
+    ///
+    /// ## Safety
+    /// - Any pointers rooted at `warnings` becomes invalid after this function
+    /// returns,
+    ///   including any strings accessible via the input
+    ///   `libra_preset_warning_list_t`.
+    ///
+    /// - If any struct fields of the input `libra_preset_warning_list_t` was
+    /// modified from
+    ///   their values given after `libra_preset_get_warnings`, this may result
+    ///   in undefined behaviour.
+    PFN_libra_preset_free_warnings preset_free_warnings;
+
+    /// Compute a stable content hash of the preset and all of its resources.
+    ///
+    /// The hash covers the preprocessed source of every shader pass,
+    /// including the contents of every included file, the decoded pixels of
+    /// every texture, and the configuration of every pass, texture and
+    /// parameter. Paths are not hashed, so the same preset installed in
+    /// different locations has the same hash. The hash can be used as a cache
+    /// key or to deduplicate and verify presets.
+    ///
+    /// Every resource of the preset is loaded from disk to compute the hash.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a shader
+    /// preset.
+    /// - `out` must be an aligned pointer to a `libra_preset_hash_t`.
+    PFN_libra_preset_get_content_hash preset_get_content_hash;
+
     /// Get the error code corresponding to this error object.
     ///
     /// ## Safety
     ///   - `error` must be valid and initialized.
     PFN_libra_error_errno error_errno;
 
-    /// Print the error message.
+    /// Print the error message, followed by the errors that caused it and the
+    /// backtrace if captured.
     ///
     /// If `error` is null, this function does nothing and returns 1. Otherwise,
     /// this function returns 0.
@@ -820,21 +1836,103 @@ typedef struct libra_instance_t {
     ///   modified.
     PFN_libra_error_write error_write;
 
-    /// Frees an error string previously allocated by `libra_error_write`.
+    /// Writes the message of an error in the chain of errors that caused the
+    /// error into `out`.
+    ///
+    /// The error at depth 0 is the error itself, the error at depth 1 is the
+    /// error that caused it, and so on. The chain can be iterated by increasing
+    /// `depth` until this function returns 1.
+    ///
+    /// If `error` is null or there is no error at `depth`, this function does
+    /// nothing and returns 1. Otherwise, this function returns 0.
+    /// ## Safety
+    ///   - `error` must be a valid and initialized instance of `libra_error_t`.
+    ///   - `out` must be a non-null pointer. The resulting string must not be
+    ///   modified, and must be freed with `libra_error_free_string`.
+    PFN_libra_error_write_source error_write_source;
+
+    /// Writes the backtrace of where the error was created into `out`.
+    ///
+    /// Backtraces are only captured if the `RUST_LIB_BACKTRACE` or
+    /// `RUST_BACKTRACE` environment variable is set to `1` when the first error
+    /// is created.
+    ///
+    /// If `error` is null or no backtrace was captured, this function does
+    /// nothing and returns 1. Otherwise, this function returns 0.
+    /// ## Safety
+    ///   - `error` must be a valid and initialized instance of `libra_error_t`.
+    ///   - `out` must be a non-null pointer. The resulting string must not be
+    ///   modified, and must be freed with `libra_error_free_string`.
+    PFN_libra_error_write_backtrace error_write_backtrace;
+
+    /// Frees an error string previously allocated by `libra_error_write`,
+    /// `libra_error_write_source` or `libra_error_write_backtrace`.
     ///
     /// After freeing, the pointer will be set to null.
     /// ## Safety
-    ///   - If `libra_error_write` is not null, it must point to a string
-    ///   previously returned by `libra_error_write`.
+    ///   - If `out` is not null, it must point to a string previously returned
+    ///   by `libra_error_write`, `libra_error_write_source` or
+    ///   `libra_error_write_backtrace`.
     ///     Attempting to free anything else, including strings or objects from
     ///     other librashader functions, is immediate Undefined Behaviour.
     PFN_libra_error_free_string error_free_string;
 
+    /// Create a token to cancel filter chain creation with.
+    ///
+    /// The token is passed to filter chain creation with the `cancel` filter
+    /// chain option, and can be cancelled from any thread while the filter
+    /// chain is being created. A token that was cancelled stays cancelled.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null.
+    PFN_libra_cancel_token_create cancel_token_create;
+
+    /// Cancel filter chain creation with the token.
+    ///
+    /// Filter chain creation with this token stops between shader passes,
+    /// releases the objects that were already created, and fails.
+    ///
+    /// If this function is not loaded, this function does nothing.
+    PFN_libra_cancel_token_cancel cancel_token_cancel;
+
+    /// Free the cancellation token.
+    ///
+    /// Filter chain creation that was passed the token must have returned
+    /// before the token is freed.
+    ///
+    /// If this function is not loaded, this function does nothing.
+    PFN_libra_cancel_token_free cancel_token_free;
+
+    /// Free the strings of information about a shader pass.
+    ///
+    /// If this function is not loaded, this function does nothing.
+    PFN_libra_pass_info_free pass_info_free;
+
+    /// Create a librashader instance, that owns the thread pool that compiles
+    /// shaders, the directory of the shader and pipeline cache, and the
+    /// callback that receives log messages.
+    ///
+    /// Filter chains are created from the instance by passing it in the
+    /// `instance` filter chain option.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null.
+    PFN_libra_instance_create instance_create;
+
+    /// Free the librashader instance.
+    ///
+    /// The instance must not be freed while a filter chain is being created
+    /// from it.
+    ///
+    /// If this function is not loaded, this function does nothing.
+    PFN_libra_instance_free instance_free;
+
 #if defined(LIBRA_RUNTIME_OPENGL)
     /// Create the filter chain given the shader preset.
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
     ///
     /// If this function is not loaded, `out` will unconditionally be set to
     /// null. If this function returns an error, the state of `out` is
@@ -846,35 +1944,125 @@ typedef struct libra_instance_t {
     /// - `out` must be aligned, but may be null, invalid, or uninitialized.
     PFN_libra_gl_filter_chain_create gl_filter_chain_create;
 
-    /// Draw a frame with the given parameters for the given filter chain.
+    /// Start compiling the shaders of the filter chain given the shader preset
+    /// on a worker thread.
+    PFN_libra_gl_filter_chain_create_async gl_filter_chain_create_async;
+
+    /// Take the filter chain from a pending handle if its shaders have
+    /// finished compiling, without blocking on shader compilation.
+    PFN_libra_gl_filter_chain_poll_pending gl_filter_chain_poll_pending;
+
+    /// Block until the filter chain of a pending handle has finished creating,
+    /// and take it.
+    PFN_libra_gl_filter_chain_wait_pending gl_filter_chain_wait_pending;
+
+    /// Free a pending filter chain handle without taking the filter chain.
+    PFN_libra_gl_filter_chain_free_pending gl_filter_chain_free_pending;
+
+    /// Create a filter chain without any shader passes, that only scales the
+    /// input to the viewport.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_gl_filter_chain_create_identity
+        gl_filter_chain_create_identity;
+
+    /// Draw a frame with the given parameters for the given filter chain.
+    ///
+    /// ## Safety
+    /// - `chain` may be null, invalid, but not uninitialized. If `chain` is
+    /// null or invalid, this
+    ///    function will return an error.
+    /// - `mvp` may be null, or if it is not null, must be an aligned pointer to
+    /// 16 consecutive `float`
+    ///    values for the model view projection matrix.
+    /// - `opt` may be null, or if it is not null, must be an aligned pointer to
+    /// a valid `frame_gl_opt_t`
+    ///    struct.
+    PFN_libra_gl_filter_chain_frame gl_filter_chain_frame;
+
+    /// Free a GL filter chain.
+    ///
+    /// The resulting value in `chain` then becomes null.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_gl_filter_chain_t`.
+    PFN_libra_gl_filter_chain_free gl_filter_chain_free;
+
+    /// Gets the number of active passes for this chain.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_gl_filter_chain_t`.
+    PFN_libra_gl_filter_chain_get_active_pass_count
+        gl_filter_chain_get_active_pass_count;
+
+    /// Gets the number of history frames kept by this chain, and the number
+    /// of history frames requested by the shader passes.
+    ///
+    /// If the history depth was capped with `max_history`, fewer frames may be
+    /// kept than were requested.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_gl_filter_chain_t`.
+    /// - `requested` and `kept` must be valid and aligned.
+    PFN_libra_gl_filter_chain_get_history_depth
+        gl_filter_chain_get_history_depth;
+
+    /// Invalidates the last `frames` frames of history, such as after a
+    /// rollback in runahead or netplay, so that shader passes do not blend
+    /// frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are
+    /// cleared to black, as is the feedback of the previous frame. Unlike
+    /// `clear_history`, older history frames are kept.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_gl_filter_chain_t`.
+    PFN_libra_gl_filter_chain_invalidate_history
+        gl_filter_chain_invalidate_history;
+
+    /// Sets the mesh to draw the final pass with, in place of the default quad.
+    ///
+    /// The `count` vertices are drawn as a triangle strip. If `vertices` is
+    /// null, the default quad is drawn again.
     ///
     /// ## Safety
-    /// - `chain` may be null, invalid, but not uninitialized. If `chain` is
-    /// null or invalid, this
-    ///    function will return an error.
-    /// - `mvp` may be null, or if it is not null, must be an aligned pointer to
-    /// 16 consecutive `float`
-    ///    values for the model view projection matrix.
-    /// - `opt` may be null, or if it is not null, must be an aligned pointer to
-    /// a valid `frame_gl_opt_t`
-    ///    struct.
-    PFN_libra_gl_filter_chain_frame gl_filter_chain_frame;
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_gl_filter_chain_t`.
+    /// - `vertices` must be either null or a valid and aligned pointer to an
+    /// array of `count` `libra_vertex_t`.
+    PFN_libra_gl_filter_chain_set_output_mesh gl_filter_chain_set_output_mesh;
 
-    /// Free a GL filter chain.
+    /// Gets information about the shader pass at the given index, including
+    /// the framebuffer format chosen for the pass.
     ///
-    /// The resulting value in `chain` then becomes null.
+    /// The strings of the output struct must be freed with
+    /// `libra_pass_info_free`. If the index is out of range, returns an error.
     /// ## Safety
     /// - `chain` must be either null or a valid and aligned pointer to an
     /// initialized `libra_gl_filter_chain_t`.
-    PFN_libra_gl_filter_chain_free gl_filter_chain_free;
+    /// - `out` must be valid and aligned.
+    PFN_libra_gl_filter_chain_get_pass_info gl_filter_chain_get_pass_info;
 
-    /// Gets the number of active passes for this chain.
+    /// Gets the CPU-side statistics of the commands recorded for the last
+    /// frame, such as the number of draw calls.
     ///
+    /// If the filter chain was not created with the `collect_statistics`
+    /// option, returns an error.
     /// ## Safety
     /// - `chain` must be either null or a valid and aligned pointer to an
     /// initialized `libra_gl_filter_chain_t`.
-    PFN_libra_gl_filter_chain_get_active_pass_count
-        gl_filter_chain_get_active_pass_count;
+    /// - `out` must be valid and aligned.
+    PFN_libra_gl_filter_chain_get_frame_statistics
+        gl_filter_chain_get_frame_statistics;
 
     /// Sets the number of active passes for this chain.
     ///
@@ -893,6 +2081,43 @@ typedef struct libra_instance_t {
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_gl_filter_chain_get_param gl_filter_chain_get_param;
 
+    /// Gets a handle to a parameter of the filter chain.
+    ///
+    /// The handle can be used to get or set the parameter with
+    /// `libra_gl_filter_chain_get_param_by_handle` and
+    /// `libra_gl_filter_chain_set_param_by_handle`, without looking up the
+    /// parameter by name.
+    ///
+    /// If the parameter does not exist, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_gl_filter_chain_t`.
+    /// - `param_name` must be either null or a null terminated string.
+    PFN_libra_gl_filter_chain_get_param_handle
+        gl_filter_chain_get_param_handle;
+
+    /// Sets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_gl_filter_chain_t`.
+    PFN_libra_gl_filter_chain_set_param_by_handle
+        gl_filter_chain_set_param_by_handle;
+
+    /// Gets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_gl_filter_chain_t`.
+    PFN_libra_gl_filter_chain_get_param_by_handle
+        gl_filter_chain_get_param_by_handle;
+
+    /// Get a list of the runtime parameters of the filter chain.
+    PFN_libra_gl_filter_chain_get_runtime_params
+        gl_filter_chain_get_runtime_params;
+
     /// Sets a parameter for the filter chain.
     ///
     /// If the parameter does not exist, returns an error.
@@ -901,6 +2126,30 @@ typedef struct libra_instance_t {
     /// initialized `libra_gl_filter_chain_t`.
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_gl_filter_chain_set_param gl_filter_chain_set_param;
+
+    /// Create a device context to share sampler objects and LUT textures between
+    /// filter chains created on the same OpenGL context.
+    ///
+    /// The device context is passed to filter chain creation with the
+    /// `device_context` filter chain option.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety
+    /// - The OpenGL context that filter chains are created on must be current on the
+    /// calling thread.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_gl_device_context_create gl_device_context_create;
+
+    /// Free an OpenGL device context.
+    ///
+    /// The resulting value in `context` then becomes null.
+    /// ## Safety
+    /// - `context` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_gl_device_context_t`.
+    PFN_libra_gl_device_context_free gl_device_context_free;
 #endif
 
 #if defined(LIBRA_RUNTIME_VULKAN)
@@ -908,6 +2157,7 @@ typedef struct libra_instance_t {
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
     ///
     /// If this function is not loaded, `out` will unconditionally be set to
     /// null. If this function returns an error, the state of `out` is
@@ -915,21 +2165,49 @@ typedef struct libra_instance_t {
     ///
     /// ## Safety:
     /// - The handles provided in `vulkan` must be valid for the command buffers
-    /// that
-    ///   `libra_vk_filter_chain_frame` will write to. Namely, the VkDevice must
-    ///   have been
-    ///    created with the `VK_KHR_dynamic_rendering` extension.
+    ///   that `libra_vk_filter_chain_frame` will write to.
     /// - `preset` must be either null, or valid and aligned.
     /// - `options` must be either null, or valid and aligned.
     /// - `out` must be aligned, but may be null, invalid, or uninitialized.
     PFN_libra_vk_filter_chain_create vk_filter_chain_create;
 
+    /// Start creating the filter chain given the shader preset on a worker
+    /// thread.
+    PFN_libra_vk_filter_chain_create_async vk_filter_chain_create_async;
+
+    /// Take the filter chain from a pending handle if it has finished creating,
+    /// without blocking.
+    PFN_libra_vk_filter_chain_poll_pending vk_filter_chain_poll_pending;
+
+    /// Block until the filter chain of a pending handle has finished creating,
+    /// and take it.
+    PFN_libra_vk_filter_chain_wait_pending vk_filter_chain_wait_pending;
+
+    /// Free a pending filter chain handle without taking the filter chain.
+    PFN_libra_vk_filter_chain_free_pending vk_filter_chain_free_pending;
+
+    /// Create a filter chain without any shader passes, that only scales the
+    /// input to the viewport.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - The handles provided in `vulkan` must be valid for the command buffers
+    ///   that `libra_vk_filter_chain_frame` will write to.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_vk_filter_chain_create_identity
+        vk_filter_chain_create_identity;
+
     /// Create the filter chain given the shader preset deferring and GPU-side
     /// initialization to the caller. This function therefore requires no
     /// external synchronization of the device queue.
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
     ///
     /// If this function is not loaded, `out` will unconditionally be set to
     /// null. If this function returns an error, the state of `out` is
@@ -937,10 +2215,7 @@ typedef struct libra_instance_t {
     ///
     /// ## Safety:
     /// - The handles provided in `vulkan` must be valid for the command buffers
-    /// that
-    ///   `libra_vk_filter_chain_frame` will write to. Namely, the VkDevice must
-    ///   have been
-    ///    created with the `VK_KHR_dynamic_rendering` extension.
+    ///   that `libra_vk_filter_chain_frame` will write to.
     /// - `preset` must be either null, or valid and aligned.
     /// - `options` must be either null, or valid and aligned.
     /// - `out` must be aligned, but may be null, invalid, or uninitialized.
@@ -990,6 +2265,91 @@ typedef struct libra_instance_t {
     PFN_libra_vk_filter_chain_get_active_pass_count
         vk_filter_chain_get_active_pass_count;
 
+    /// Gets the number of history frames kept by this chain, and the number
+    /// of history frames requested by the shader passes.
+    ///
+    /// If the history depth was capped with `max_history`, fewer frames may be
+    /// kept than were requested.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_vk_filter_chain_t`.
+    /// - `requested` and `kept` must be valid and aligned.
+    PFN_libra_vk_filter_chain_get_history_depth
+        vk_filter_chain_get_history_depth;
+
+    /// Invalidates the last `frames` frames of history, such as after a
+    /// rollback in runahead or netplay, so that shader passes do not blend
+    /// frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are
+    /// cleared to black, as is the feedback of the previous frame. Unlike
+    /// `clear_history`, older history frames are kept.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_vk_filter_chain_t`.
+    PFN_libra_vk_filter_chain_invalidate_history
+        vk_filter_chain_invalidate_history;
+
+    /// Sets the mesh to draw the final pass with, in place of the default quad.
+    ///
+    /// The `count` vertices are drawn as a triangle strip. If `vertices` is
+    /// null, the default quad is drawn again.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_vk_filter_chain_t`.
+    /// - `vertices` must be either null or a valid and aligned pointer to an
+    /// array of `count` `libra_vertex_t`.
+    /// - The command buffers recorded with the previous mesh must have
+    /// completed execution.
+    PFN_libra_vk_filter_chain_set_output_mesh vk_filter_chain_set_output_mesh;
+
+    /// Writes GPU timestamps around the commands of the next frame to the given
+    /// query pool.
+    /// - `pool` must be a `VK_QUERY_TYPE_TIMESTAMP` query pool with at least
+    /// `first_query + 2` queries.
+    /// - Both queries must be reset before the command buffer of the next frame
+    /// executes.
+    PFN_libra_vk_filter_chain_set_timestamp_queries
+        vk_filter_chain_set_timestamp_queries;
+
+    /// Gets information about the shader pass at the given index, including
+    /// the framebuffer format chosen for the pass.
+    ///
+    /// The strings of the output struct must be freed with
+    /// `libra_pass_info_free`. If the index is out of range, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_vk_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    PFN_libra_vk_filter_chain_get_pass_info vk_filter_chain_get_pass_info;
+
+    /// Gets the CPU-side statistics of the commands recorded for the last
+    /// frame, such as the number of draw calls.
+    ///
+    /// If the filter chain was not created with the `collect_statistics`
+    /// option, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_vk_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    PFN_libra_vk_filter_chain_get_frame_statistics
+        vk_filter_chain_get_frame_statistics;
+
+    /// Gets whether the final pass of the last recorded frame was not drawn to
+    /// some outputs, because the pipelines for their formats were still being
+    /// built with the `async_pipeline_rebuild` option. Outputs that were not
+    /// drawn are left unchanged.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_vk_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    PFN_libra_vk_filter_chain_has_pending_pipelines
+        vk_filter_chain_has_pending_pipelines;
+
     /// Sets the number of active passes for this chain.
     ///
     /// ## Safety
@@ -1007,6 +2367,43 @@ typedef struct libra_instance_t {
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_vk_filter_chain_get_param vk_filter_chain_get_param;
 
+    /// Gets a handle to a parameter of the filter chain.
+    ///
+    /// The handle can be used to get or set the parameter with
+    /// `libra_vk_filter_chain_get_param_by_handle` and
+    /// `libra_vk_filter_chain_set_param_by_handle`, without looking up the
+    /// parameter by name.
+    ///
+    /// If the parameter does not exist, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_vk_filter_chain_t`.
+    /// - `param_name` must be either null or a null terminated string.
+    PFN_libra_vk_filter_chain_get_param_handle
+        vk_filter_chain_get_param_handle;
+
+    /// Sets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_vk_filter_chain_t`.
+    PFN_libra_vk_filter_chain_set_param_by_handle
+        vk_filter_chain_set_param_by_handle;
+
+    /// Gets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_vk_filter_chain_t`.
+    PFN_libra_vk_filter_chain_get_param_by_handle
+        vk_filter_chain_get_param_by_handle;
+
+    /// Get a list of the runtime parameters of the filter chain.
+    PFN_libra_vk_filter_chain_get_runtime_params
+        vk_filter_chain_get_runtime_params;
+
     /// Sets a parameter for the filter chain.
     ///
     /// If the parameter does not exist, returns an error.
@@ -1015,6 +2412,52 @@ typedef struct libra_instance_t {
     /// initialized `libra_vk_filter_chain_t`.
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_vk_filter_chain_set_param vk_filter_chain_set_param;
+
+    /// Create a device context to share samplers and LUT textures between
+    /// filter chains created on the same device.
+    ///
+    /// The device context is passed to filter chain creation with the
+    /// `device_context` filter chain option.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety
+    /// - The handles provided in `vulkan` must be valid for the filter chains the
+    /// device context is shared with.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_vk_device_context_create vk_device_context_create;
+
+    /// Free a Vulkan device context.
+    ///
+    /// The resulting value in `context` then becomes null.
+    /// ## Safety
+    /// - `context` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_vk_device_context_t`.
+    PFN_libra_vk_device_context_free vk_device_context_free;
+
+    /// Free a Vulkan filter chain once `fence` is signaled, without waiting for the
+    /// frames that were recorded with it to complete.
+    ///
+    /// The filter chain is freed by a later call to `vk_filter_chain_free_deferred`
+    /// or `vk_filter_chain_collect_deferred` after `fence` is signaled.
+    ///
+    /// If this function is not loaded, this function does nothing.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_vk_filter_chain_t`.
+    /// - `fence` must be `VK_NULL_HANDLE`, or signaled after the last command buffer
+    /// that the filter chain recorded into has completed.
+    PFN_libra_vk_filter_chain_free_deferred vk_filter_chain_free_deferred;
+
+    /// Free the Vulkan filter chains queued with `vk_filter_chain_free_deferred`
+    /// whose fence is signaled.
+    ///
+    /// If this function is not loaded, this function does nothing.
+    PFN_libra_vk_filter_chain_collect_deferred
+        vk_filter_chain_collect_deferred;
 #endif
 
 #if defined(LIBRA_RUNTIME_D3D11)
@@ -1022,6 +2465,7 @@ typedef struct libra_instance_t {
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
     ///
     /// If this function is not loaded, `out` will unconditionally be set to
     /// null. If this function returns an error, the state of `out` is
@@ -1033,6 +2477,35 @@ typedef struct libra_instance_t {
     /// - `out` must be aligned, but may be null, invalid, or uninitialized.
     PFN_libra_d3d11_filter_chain_create d3d11_filter_chain_create;
 
+    /// Start compiling the shaders of the filter chain given the shader preset
+    /// on a worker thread.
+    PFN_libra_d3d11_filter_chain_create_async d3d11_filter_chain_create_async;
+
+    /// Take the filter chain from a pending handle if its shaders have
+    /// finished compiling, without blocking on shader compilation.
+    PFN_libra_d3d11_filter_chain_poll_pending d3d11_filter_chain_poll_pending;
+
+    /// Block until the filter chain of a pending handle has finished creating,
+    /// and take it.
+    PFN_libra_d3d11_filter_chain_wait_pending d3d11_filter_chain_wait_pending;
+
+    /// Free a pending filter chain handle without taking the filter chain.
+    PFN_libra_d3d11_filter_chain_free_pending d3d11_filter_chain_free_pending;
+
+    /// Create a filter chain without any shader passes, that only scales the
+    /// input to the viewport.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `options` must be either null, or valid and aligned.
+    /// - `device` must not be null.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_d3d11_filter_chain_create_identity
+        d3d11_filter_chain_create_identity;
+
     /// Create the filter chain given the shader preset, deferring and GPU-side
     /// initialization
     /// to the caller. This function is therefore requires no external
@@ -1042,6 +2515,7 @@ typedef struct libra_instance_t {
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
     ///
     /// If this function is not loaded, `out` will unconditionally be set to
     /// null. If this function returns an error, the state of `out` is
@@ -1101,6 +2575,69 @@ typedef struct libra_instance_t {
     PFN_libra_d3d11_filter_chain_get_active_pass_count
         d3d11_filter_chain_get_active_pass_count;
 
+    /// Gets the number of history frames kept by this chain, and the number
+    /// of history frames requested by the shader passes.
+    ///
+    /// If the history depth was capped with `max_history`, fewer frames may be
+    /// kept than were requested.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d11_filter_chain_t`.
+    /// - `requested` and `kept` must be valid and aligned.
+    PFN_libra_d3d11_filter_chain_get_history_depth
+        d3d11_filter_chain_get_history_depth;
+
+    /// Invalidates the last `frames` frames of history, such as after a
+    /// rollback in runahead or netplay, so that shader passes do not blend
+    /// frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are
+    /// cleared to black, as is the feedback of the previous frame. Unlike
+    /// `clear_history`, older history frames are kept.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d11_filter_chain_t`.
+    PFN_libra_d3d11_filter_chain_invalidate_history
+        d3d11_filter_chain_invalidate_history;
+
+    /// Sets the mesh to draw the final pass with, in place of the default quad.
+    ///
+    /// The `count` vertices are drawn as a triangle strip. If `vertices` is
+    /// null, the default quad is drawn again.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d11_filter_chain_t`.
+    /// - `vertices` must be either null or a valid and aligned pointer to an
+    /// array of `count` `libra_vertex_t`.
+    PFN_libra_d3d11_filter_chain_set_output_mesh
+        d3d11_filter_chain_set_output_mesh;
+
+    /// Gets information about the shader pass at the given index, including
+    /// the framebuffer format chosen for the pass.
+    ///
+    /// The strings of the output struct must be freed with
+    /// `libra_pass_info_free`. If the index is out of range, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d11_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    PFN_libra_d3d11_filter_chain_get_pass_info d3d11_filter_chain_get_pass_info;
+
+    /// Gets the CPU-side statistics of the commands recorded for the last
+    /// frame, such as the number of draw calls.
+    ///
+    /// If the filter chain was not created with the `collect_statistics`
+    /// option, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d11_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    PFN_libra_d3d11_filter_chain_get_frame_statistics
+        d3d11_filter_chain_get_frame_statistics;
+
     /// Sets the number of active passes for this chain.
     ///
     /// ## Safety
@@ -1118,6 +2655,43 @@ typedef struct libra_instance_t {
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_d3d11_filter_chain_get_param d3d11_filter_chain_get_param;
 
+    /// Gets a handle to a parameter of the filter chain.
+    ///
+    /// The handle can be used to get or set the parameter with
+    /// `libra_d3d11_filter_chain_get_param_by_handle` and
+    /// `libra_d3d11_filter_chain_set_param_by_handle`, without looking up the
+    /// parameter by name.
+    ///
+    /// If the parameter does not exist, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d11_filter_chain_t`.
+    /// - `param_name` must be either null or a null terminated string.
+    PFN_libra_d3d11_filter_chain_get_param_handle
+        d3d11_filter_chain_get_param_handle;
+
+    /// Sets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d11_filter_chain_t`.
+    PFN_libra_d3d11_filter_chain_set_param_by_handle
+        d3d11_filter_chain_set_param_by_handle;
+
+    /// Gets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d11_filter_chain_t`.
+    PFN_libra_d3d11_filter_chain_get_param_by_handle
+        d3d11_filter_chain_get_param_by_handle;
+
+    /// Get a list of the runtime parameters of the filter chain.
+    PFN_libra_d3d11_filter_chain_get_runtime_params
+        d3d11_filter_chain_get_runtime_params;
+
     /// Sets a parameter for the filter chain.
     ///
     /// If the parameter does not exist, returns an error.
@@ -1126,6 +2700,29 @@ typedef struct libra_instance_t {
     /// initialized `libra_d3d11_filter_chain_t`.
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_d3d11_filter_chain_set_param d3d11_filter_chain_set_param;
+
+    /// Create a device context to share sampler states and LUT textures between
+    /// filter chains created on the same device.
+    ///
+    /// The device context is passed to filter chain creation with the
+    /// `device_context` filter chain option.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety
+    /// - `device` must not be null.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_d3d11_device_context_create d3d11_device_context_create;
+
+    /// Free a Direct3D 11 device context.
+    ///
+    /// The resulting value in `context` then becomes null.
+    /// ## Safety
+    /// - `context` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d11_device_context_t`.
+    PFN_libra_d3d11_device_context_free d3d11_device_context_free;
 #endif
 
 #if defined(LIBRA_RUNTIME_D3D12)
@@ -1133,6 +2730,7 @@ typedef struct libra_instance_t {
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
     ///
     /// If this function is not loaded, `out` will unconditionally be set to
     /// null. If this function returns an error, the state of `out` is
@@ -1144,6 +2742,35 @@ typedef struct libra_instance_t {
     /// - `out` must be aligned, but may be null, invalid, or uninitialized.
     PFN_libra_d3d12_filter_chain_create d3d12_filter_chain_create;
 
+    /// Start creating the filter chain given the shader preset on a worker
+    /// thread.
+    PFN_libra_d3d12_filter_chain_create_async d3d12_filter_chain_create_async;
+
+    /// Take the filter chain from a pending handle if it has finished creating,
+    /// without blocking.
+    PFN_libra_d3d12_filter_chain_poll_pending d3d12_filter_chain_poll_pending;
+
+    /// Block until the filter chain of a pending handle has finished creating,
+    /// and take it.
+    PFN_libra_d3d12_filter_chain_wait_pending d3d12_filter_chain_wait_pending;
+
+    /// Free a pending filter chain handle without taking the filter chain.
+    PFN_libra_d3d12_filter_chain_free_pending d3d12_filter_chain_free_pending;
+
+    /// Create a filter chain without any shader passes, that only scales the
+    /// input to the viewport.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `options` must be either null, or valid and aligned.
+    /// - `device` must not be null.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_d3d12_filter_chain_create_identity
+        d3d12_filter_chain_create_identity;
+
     /// Create the filter chain given the shader preset deferring and GPU-side
     /// initialization
     /// to the caller. This function therefore requires no external
@@ -1151,6 +2778,7 @@ typedef struct libra_instance_t {
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
     ///
     /// If this function is not loaded, `out` will unconditionally be set to
     /// null. If this function returns an error, the state of `out` is
@@ -1197,8 +2825,85 @@ typedef struct libra_instance_t {
     /// ## Safety
     /// - `chain` must be either null or a valid and aligned pointer to an
     /// initialized `libra_d3d12_filter_chain_t`.
-    PFN_libra_d3d12_filter_chain_get_active_pass_count
-        d3d12_filter_chain_get_active_pass_count;
+    PFN_libra_d3d12_filter_chain_get_active_pass_count
+        d3d12_filter_chain_get_active_pass_count;
+
+    /// Gets the number of history frames kept by this chain, and the number
+    /// of history frames requested by the shader passes.
+    ///
+    /// If the history depth was capped with `max_history`, fewer frames may be
+    /// kept than were requested.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d12_filter_chain_t`.
+    /// - `requested` and `kept` must be valid and aligned.
+    PFN_libra_d3d12_filter_chain_get_history_depth
+        d3d12_filter_chain_get_history_depth;
+
+    /// Invalidates the last `frames` frames of history, such as after a
+    /// rollback in runahead or netplay, so that shader passes do not blend
+    /// frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are
+    /// cleared to black, as is the feedback of the previous frame. Unlike
+    /// `clear_history`, older history frames are kept.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d12_filter_chain_t`.
+    PFN_libra_d3d12_filter_chain_invalidate_history
+        d3d12_filter_chain_invalidate_history;
+
+    /// Sets the mesh to draw the final pass with, in place of the default quad.
+    ///
+    /// The `count` vertices are drawn as a triangle strip. If `vertices` is
+    /// null, the default quad is drawn again.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d12_filter_chain_t`.
+    /// - `vertices` must be either null or a valid and aligned pointer to an
+    /// array of `count` `libra_vertex_t`.
+    /// - The command lists recorded with the previous mesh must have completed
+    /// execution.
+    PFN_libra_d3d12_filter_chain_set_output_mesh
+        d3d12_filter_chain_set_output_mesh;
+
+    /// Writes GPU timestamps around the commands of the next frame to the given
+    /// query heap.
+    /// - `heap` must be a non-null pointer to a
+    /// `D3D12_QUERY_HEAP_TYPE_TIMESTAMP` query heap
+    ///   with at least `first_query + 2` queries, that stays alive until every
+    ///   command list of the next frame has completed execution.
+    /// - If the frame is handed off with `mipmap_handoff`, the timestamps are
+    ///   written to different command lists, which must be executed on the same
+    ///   queue.
+    PFN_libra_d3d12_filter_chain_set_timestamp_queries
+        d3d12_filter_chain_set_timestamp_queries;
+
+    /// Gets information about the shader pass at the given index, including
+    /// the framebuffer format chosen for the pass.
+    ///
+    /// The strings of the output struct must be freed with
+    /// `libra_pass_info_free`. If the index is out of range, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d12_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    PFN_libra_d3d12_filter_chain_get_pass_info d3d12_filter_chain_get_pass_info;
+
+    /// Gets the CPU-side statistics of the commands recorded for the last
+    /// frame, such as the number of draw calls.
+    ///
+    /// If the filter chain was not created with the `collect_statistics`
+    /// option, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d12_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    PFN_libra_d3d12_filter_chain_get_frame_statistics
+        d3d12_filter_chain_get_frame_statistics;
 
     /// Sets the number of active passes for this chain.
     ///
@@ -1217,6 +2922,43 @@ typedef struct libra_instance_t {
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_d3d12_filter_chain_get_param d3d12_filter_chain_get_param;
 
+    /// Gets a handle to a parameter of the filter chain.
+    ///
+    /// The handle can be used to get or set the parameter with
+    /// `libra_d3d12_filter_chain_get_param_by_handle` and
+    /// `libra_d3d12_filter_chain_set_param_by_handle`, without looking up the
+    /// parameter by name.
+    ///
+    /// If the parameter does not exist, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d12_filter_chain_t`.
+    /// - `param_name` must be either null or a null terminated string.
+    PFN_libra_d3d12_filter_chain_get_param_handle
+        d3d12_filter_chain_get_param_handle;
+
+    /// Sets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d12_filter_chain_t`.
+    PFN_libra_d3d12_filter_chain_set_param_by_handle
+        d3d12_filter_chain_set_param_by_handle;
+
+    /// Gets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d12_filter_chain_t`.
+    PFN_libra_d3d12_filter_chain_get_param_by_handle
+        d3d12_filter_chain_get_param_by_handle;
+
+    /// Get a list of the runtime parameters of the filter chain.
+    PFN_libra_d3d12_filter_chain_get_runtime_params
+        d3d12_filter_chain_get_runtime_params;
+
     /// Sets a parameter for the filter chain.
     ///
     /// If the parameter does not exist, returns an error.
@@ -1225,6 +2967,29 @@ typedef struct libra_instance_t {
     /// initialized `libra_d3d12_filter_chain_t`.
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_d3d12_filter_chain_set_param d3d12_filter_chain_set_param;
+
+    /// Free a D3D12 filter chain once `fence` reaches `value`, without waiting for
+    /// the frames that were recorded with it to complete.
+    ///
+    /// The filter chain is freed by a later call to
+    /// `d3d12_filter_chain_free_deferred` or `d3d12_filter_chain_collect_deferred`
+    /// after the completed value of `fence` is at least `value`.
+    ///
+    /// If this function is not loaded, this function does nothing.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d12_filter_chain_t`.
+    /// - `fence` must not be null, and must be signaled with `value` after the last
+    /// command list that the filter chain recorded into has completed.
+    PFN_libra_d3d12_filter_chain_free_deferred d3d12_filter_chain_free_deferred;
+
+    /// Free the D3D12 filter chains queued with `d3d12_filter_chain_free_deferred`
+    /// whose fence has reached its value.
+    ///
+    /// If this function is not loaded, this function does nothing.
+    PFN_libra_d3d12_filter_chain_collect_deferred
+        d3d12_filter_chain_collect_deferred;
 #endif
 
 #if defined(LIBRA_RUNTIME_D3D9)
@@ -1232,6 +2997,7 @@ typedef struct libra_instance_t {
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
     ///
     /// If this function is not loaded, `out` will unconditionally be set to
     /// null. If this function returns an error, the state of `out` is
@@ -1243,6 +3009,35 @@ typedef struct libra_instance_t {
     /// - `out` must be aligned, but may be null, invalid, or uninitialized.
     PFN_libra_d3d9_filter_chain_create d3d9_filter_chain_create;
 
+    /// Start compiling the shaders of the filter chain given the shader preset
+    /// on a worker thread.
+    PFN_libra_d3d9_filter_chain_create_async d3d9_filter_chain_create_async;
+
+    /// Take the filter chain from a pending handle if its shaders have
+    /// finished compiling, without blocking on shader compilation.
+    PFN_libra_d3d9_filter_chain_poll_pending d3d9_filter_chain_poll_pending;
+
+    /// Block until the filter chain of a pending handle has finished creating,
+    /// and take it.
+    PFN_libra_d3d9_filter_chain_wait_pending d3d9_filter_chain_wait_pending;
+
+    /// Free a pending filter chain handle without taking the filter chain.
+    PFN_libra_d3d9_filter_chain_free_pending d3d9_filter_chain_free_pending;
+
+    /// Create a filter chain without any shader passes, that only scales the
+    /// input to the viewport.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `options` must be either null, or valid and aligned.
+    /// - `device` must not be null.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_d3d9_filter_chain_create_identity
+        d3d9_filter_chain_create_identity;
+
     /// Draw a frame with the given parameters for the given filter chain.
     ///
     /// ## Safety
@@ -1273,6 +3068,69 @@ typedef struct libra_instance_t {
     PFN_libra_d3d9_filter_chain_get_active_pass_count
         d3d9_filter_chain_get_active_pass_count;
 
+    /// Gets the number of history frames kept by this chain, and the number
+    /// of history frames requested by the shader passes.
+    ///
+    /// If the history depth was capped with `max_history`, fewer frames may be
+    /// kept than were requested.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d9_filter_chain_t`.
+    /// - `requested` and `kept` must be valid and aligned.
+    PFN_libra_d3d9_filter_chain_get_history_depth
+        d3d9_filter_chain_get_history_depth;
+
+    /// Invalidates the last `frames` frames of history, such as after a
+    /// rollback in runahead or netplay, so that shader passes do not blend
+    /// frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are
+    /// cleared to black, as is the feedback of the previous frame. Unlike
+    /// `clear_history`, older history frames are kept.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d9_filter_chain_t`.
+    PFN_libra_d3d9_filter_chain_invalidate_history
+        d3d9_filter_chain_invalidate_history;
+
+    /// Sets the mesh to draw the final pass with, in place of the default quad.
+    ///
+    /// The `count` vertices are drawn as a triangle strip. If `vertices` is
+    /// null, the default quad is drawn again.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d9_filter_chain_t`.
+    /// - `vertices` must be either null or a valid and aligned pointer to an
+    /// array of `count` `libra_vertex_t`.
+    PFN_libra_d3d9_filter_chain_set_output_mesh
+        d3d9_filter_chain_set_output_mesh;
+
+    /// Gets information about the shader pass at the given index, including
+    /// the framebuffer format chosen for the pass.
+    ///
+    /// The strings of the output struct must be freed with
+    /// `libra_pass_info_free`. If the index is out of range, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d9_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    PFN_libra_d3d9_filter_chain_get_pass_info d3d9_filter_chain_get_pass_info;
+
+    /// Gets the CPU-side statistics of the commands recorded for the last
+    /// frame, such as the number of draw calls.
+    ///
+    /// If the filter chain was not created with the `collect_statistics`
+    /// option, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d9_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    PFN_libra_d3d9_filter_chain_get_frame_statistics
+        d3d9_filter_chain_get_frame_statistics;
+
     /// Sets the number of active passes for this chain.
     ///
     /// ## Safety
@@ -1290,6 +3148,43 @@ typedef struct libra_instance_t {
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_d3d9_filter_chain_get_param d3d9_filter_chain_get_param;
 
+    /// Gets a handle to a parameter of the filter chain.
+    ///
+    /// The handle can be used to get or set the parameter with
+    /// `libra_d3d9_filter_chain_get_param_by_handle` and
+    /// `libra_d3d9_filter_chain_set_param_by_handle`, without looking up the
+    /// parameter by name.
+    ///
+    /// If the parameter does not exist, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d9_filter_chain_t`.
+    /// - `param_name` must be either null or a null terminated string.
+    PFN_libra_d3d9_filter_chain_get_param_handle
+        d3d9_filter_chain_get_param_handle;
+
+    /// Sets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d9_filter_chain_t`.
+    PFN_libra_d3d9_filter_chain_set_param_by_handle
+        d3d9_filter_chain_set_param_by_handle;
+
+    /// Gets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d9_filter_chain_t`.
+    PFN_libra_d3d9_filter_chain_get_param_by_handle
+        d3d9_filter_chain_get_param_by_handle;
+
+    /// Get a list of the runtime parameters of the filter chain.
+    PFN_libra_d3d9_filter_chain_get_runtime_params
+        d3d9_filter_chain_get_runtime_params;
+
     /// Sets a parameter for the filter chain.
     ///
     /// If the parameter does not exist, returns an error.
@@ -1298,6 +3193,28 @@ typedef struct libra_instance_t {
     /// initialized `libra_d3d9_filter_chain_t`.
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_d3d9_filter_chain_set_param d3d9_filter_chain_set_param;
+
+    /// Create a device context to share sampler states and LUT textures between
+    /// filter chains created on the same device.
+    ///
+    /// The device context is passed to filter chain creation with the
+    /// `device_context` filter chain option.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_d3d9_device_context_create d3d9_device_context_create;
+
+    /// Free a Direct3D 9 device context.
+    ///
+    /// The resulting value in `context` then becomes null.
+    /// ## Safety
+    /// - `context` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_d3d9_device_context_t`.
+    PFN_libra_d3d9_device_context_free d3d9_device_context_free;
 #endif
 
 #if defined(LIBRA_RUNTIME_METAL)
@@ -1305,6 +3222,7 @@ typedef struct libra_instance_t {
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
     ///
     /// If this function is not loaded, `out` will unconditionally be set to
     /// null. If this function returns an error, the state of `out` is
@@ -1316,6 +3234,36 @@ typedef struct libra_instance_t {
     /// - `out` must be aligned, but may be null, invalid, or uninitialized.
     PFN_libra_mtl_filter_chain_create mtl_filter_chain_create;
 
+    /// Start compiling the shaders of the filter chain given the shader preset
+    /// on a worker thread.
+    PFN_libra_mtl_filter_chain_create_async mtl_filter_chain_create_async;
+
+    /// Take the filter chain from a pending handle if its shaders have
+    /// finished compiling, without blocking on shader compilation.
+    PFN_libra_mtl_filter_chain_poll_pending mtl_filter_chain_poll_pending;
+
+    /// Block until the filter chain of a pending handle has finished creating,
+    /// and take it.
+    PFN_libra_mtl_filter_chain_wait_pending mtl_filter_chain_wait_pending;
+
+    /// Free a pending filter chain handle without taking the filter chain.
+    PFN_libra_mtl_filter_chain_free_pending mtl_filter_chain_free_pending;
+
+    /// Create a filter chain without any shader passes, that only scales the
+    /// input to the viewport.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety:
+    /// - `queue` must be valid for the command buffers
+    ///   that `libra_mtl_filter_chain_frame` will write to.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_mtl_filter_chain_create_identity
+        mtl_filter_chain_create_identity;
+
     /// Create the filter chain given the shader preset deferring and GPU-side
     /// initialization
     /// to the caller. This function therefore requires no external
@@ -1323,6 +3271,7 @@ typedef struct libra_instance_t {
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
     ///
     /// If this function is not loaded, `out` will unconditionally be set to
     /// null. If this function returns an error, the state of `out` is
@@ -1371,6 +3320,68 @@ typedef struct libra_instance_t {
     PFN_libra_mtl_filter_chain_get_active_pass_count
         mtl_filter_chain_get_active_pass_count;
 
+    /// Gets the number of history frames kept by this chain, and the number
+    /// of history frames requested by the shader passes.
+    ///
+    /// If the history depth was capped with `max_history`, fewer frames may be
+    /// kept than were requested.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_mtl_filter_chain_t`.
+    /// - `requested` and `kept` must be valid and aligned.
+    PFN_libra_mtl_filter_chain_get_history_depth
+        mtl_filter_chain_get_history_depth;
+
+    /// Invalidates the last `frames` frames of history, such as after a
+    /// rollback in runahead or netplay, so that shader passes do not blend
+    /// frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are
+    /// cleared to black, as is the feedback of the previous frame. Unlike
+    /// `clear_history`, older history frames are kept.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_mtl_filter_chain_t`.
+    PFN_libra_mtl_filter_chain_invalidate_history
+        mtl_filter_chain_invalidate_history;
+
+    /// Sets the mesh to draw the final pass with, in place of the default quad.
+    ///
+    /// The `count` vertices are drawn as a triangle strip. If `vertices` is
+    /// null, the default quad is drawn again.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_mtl_filter_chain_t`.
+    /// - `vertices` must be either null or a valid and aligned pointer to an
+    /// array of `count` `libra_vertex_t`.
+    PFN_libra_mtl_filter_chain_set_output_mesh mtl_filter_chain_set_output_mesh;
+
+    /// Gets information about the shader pass at the given index, including
+    /// the framebuffer format chosen for the pass.
+    ///
+    /// The strings of the output struct must be freed with
+    /// `libra_pass_info_free`. If the index is out of range, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_mtl_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    PFN_libra_mtl_filter_chain_get_pass_info mtl_filter_chain_get_pass_info;
+
+    /// Gets the CPU-side statistics of the commands recorded for the last
+    /// frame, such as the number of draw calls.
+    ///
+    /// If the filter chain was not created with the `collect_statistics`
+    /// option, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_mtl_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    PFN_libra_mtl_filter_chain_get_frame_statistics
+        mtl_filter_chain_get_frame_statistics;
+
     /// Sets the number of active passes for this chain.
     ///
     /// ## Safety
@@ -1388,6 +3399,43 @@ typedef struct libra_instance_t {
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_mtl_filter_chain_get_param mtl_filter_chain_get_param;
 
+    /// Gets a handle to a parameter of the filter chain.
+    ///
+    /// The handle can be used to get or set the parameter with
+    /// `libra_mtl_filter_chain_get_param_by_handle` and
+    /// `libra_mtl_filter_chain_set_param_by_handle`, without looking up the
+    /// parameter by name.
+    ///
+    /// If the parameter does not exist, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_mtl_filter_chain_t`.
+    /// - `param_name` must be either null or a null terminated string.
+    PFN_libra_mtl_filter_chain_get_param_handle
+        mtl_filter_chain_get_param_handle;
+
+    /// Sets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_mtl_filter_chain_t`.
+    PFN_libra_mtl_filter_chain_set_param_by_handle
+        mtl_filter_chain_set_param_by_handle;
+
+    /// Gets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_mtl_filter_chain_t`.
+    PFN_libra_mtl_filter_chain_get_param_by_handle
+        mtl_filter_chain_get_param_by_handle;
+
+    /// Get a list of the runtime parameters of the filter chain.
+    PFN_libra_mtl_filter_chain_get_runtime_params
+        mtl_filter_chain_get_runtime_params;
+
     /// Sets a parameter for the filter chain.
     ///
     /// If the parameter does not exist, returns an error.
@@ -1396,6 +3444,53 @@ typedef struct libra_instance_t {
     /// initialized `libra_mtl_filter_chain_t`.
     /// - `param_name` must be either null or a null terminated string.
     PFN_libra_mtl_filter_chain_set_param mtl_filter_chain_set_param;
+
+    /// Create a device context to share sampler states and LUT textures between
+    /// filter chains created on the same device.
+    ///
+    /// The device context is passed to filter chain creation with the
+    /// `device_context` filter chain option.
+    ///
+    /// If this function is not loaded, `out` will unconditionally be set to
+    /// null. If this function returns an error, the state of `out` is
+    /// unspecified.
+    ///
+    /// ## Safety
+    /// - `queue` must be valid for the device of the filter chains the device
+    /// context is shared with.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    PFN_libra_mtl_device_context_create mtl_device_context_create;
+
+    /// Free a Metal device context.
+    ///
+    /// The resulting value in `context` then becomes null.
+    /// ## Safety
+    /// - `context` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_mtl_device_context_t`.
+    PFN_libra_mtl_device_context_free mtl_device_context_free;
+
+    /// Free a Metal filter chain once `command_buffer` has completed, without
+    /// waiting for the frames that were recorded with it to complete.
+    ///
+    /// The filter chain is freed by a later call to `mtl_filter_chain_free_deferred`
+    /// or `mtl_filter_chain_collect_deferred` on the same thread after
+    /// `command_buffer` has completed.
+    ///
+    /// If this function is not loaded, this function does nothing.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an
+    /// initialized `libra_mtl_filter_chain_t`.
+    /// - `command_buffer` must be the last command buffer that the filter chain
+    /// recorded into.
+    PFN_libra_mtl_filter_chain_free_deferred mtl_filter_chain_free_deferred;
+
+    /// Free the Metal filter chains queued with `mtl_filter_chain_free_deferred`
+    /// on this thread whose command buffer has completed.
+    ///
+    /// If this function is not loaded, this function does nothing.
+    PFN_libra_mtl_filter_chain_collect_deferred
+        mtl_filter_chain_collect_deferred;
 #endif
 
     /// Helper flag for if the librashader instance was loaded.
@@ -1424,6 +3519,17 @@ libra_instance_t __librashader_make_null_instance(void) {
 
     instance.instance_abi_version = __librashader__noop_instance_abi_version;
     instance.instance_api_version = __librashader__noop_instance_api_version;
+    instance.instance_runtime_supported =
+        __librashader__noop_instance_runtime_supported;
+    instance.instance_option_supported =
+        __librashader__noop_instance_option_supported;
+    instance.instance_version_string =
+        __librashader__noop_instance_version_string;
+    instance.instance_commit_string = __librashader__noop_instance_commit_string;
+    instance.instance_features_string =
+        __librashader__noop_instance_features_string;
+    instance.instance_set_allocator =
+        __librashader__noop_instance_set_allocator;
 
     instance.preset_ctx_create = __librashader__noop_preset_ctx_create;
     instance.preset_ctx_free = __librashader__noop_preset_ctx_free;
@@ -1447,61 +3553,176 @@ libra_instance_t __librashader_make_null_instance(void) {
     instance.preset_ctx_set_core_aspect_orientation =
         __librashader__noop_preset_ctx_set_core_aspect_orientation;
 
+    instance.preset_translations_create =
+        __librashader__noop_preset_translations_create;
+    instance.preset_translations_free =
+        __librashader__noop_preset_translations_free;
+    instance.preset_translations_set =
+        __librashader__noop_preset_translations_set;
+
     instance.preset_create = __librashader__noop_preset_create;
     instance.preset_create_with_context =
         __librashader__noop_preset_create_with_context;
     instance.preset_create_with_options =
                 __librashader__noop_preset_create_with_options;
     instance.preset_free = __librashader__noop_preset_free;
+    instance.preset_clone = __librashader__noop_preset_clone;
     instance.preset_set_param = __librashader__noop_preset_set_param;
     instance.preset_get_param = __librashader__noop_preset_get_param;
+    instance.preset_set_pass_define =
+        __librashader__noop_preset_set_pass_define;
     instance.preset_print = __librashader__noop_preset_print;
     instance.preset_get_runtime_params =
         __librashader__noop_preset_get_runtime_params;
     instance.preset_free_runtime_params =
         __librashader__noop_preset_free_runtime_params;
+    instance.preset_get_runtime_params_localized =
+        __librashader__noop_preset_get_runtime_params_localized;
+    instance.preset_get_runtime_param_widgets =
+        __librashader__noop_preset_get_runtime_param_widgets;
+    instance.preset_free_runtime_param_widgets =
+        __librashader__noop_preset_free_runtime_param_widgets;
+    instance.preset_get_passes = __librashader__noop_preset_get_passes;
+    instance.preset_free_passes = __librashader__noop_preset_free_passes;
+    instance.preset_get_textures = __librashader__noop_preset_get_textures;
+    instance.preset_free_textures = __librashader__noop_preset_free_textures;
+    instance.preset_get_warnings = __librashader__noop_preset_get_warnings;
+    instance.preset_free_warnings = __librashader__noop_preset_free_warnings;
+    instance.preset_get_content_hash =
+        __librashader__noop_preset_get_content_hash;
 
     instance.error_errno = __librashader__noop_error_errno;
     instance.error_print = __librashader__noop_error_print;
     instance.error_free = __librashader__noop_error_free;
     instance.error_write = __librashader__noop_error_write;
+    instance.error_write_source = __librashader__noop_error_write_source;
+    instance.error_write_backtrace = __librashader__noop_error_write_backtrace;
     instance.error_free_string = __librashader__noop_error_free_string;
 
+    instance.cancel_token_create = __librashader__noop_cancel_token_create;
+    instance.cancel_token_cancel = __librashader__noop_cancel_token_cancel;
+    instance.cancel_token_free = __librashader__noop_cancel_token_free;
+    instance.pass_info_free = __librashader__noop_pass_info_free;
+    instance.instance_create = __librashader__noop_instance_create;
+    instance.instance_free = __librashader__noop_instance_free;
+
 #if defined(LIBRA_RUNTIME_OPENGL)
     instance.gl_filter_chain_create =
         __librashader__noop_gl_filter_chain_create;
+    instance.gl_filter_chain_create_async =
+        __librashader__noop_gl_filter_chain_create_async;
+    instance.gl_filter_chain_poll_pending =
+        __librashader__noop_gl_filter_chain_poll_pending;
+    instance.gl_filter_chain_wait_pending =
+        __librashader__noop_gl_filter_chain_wait_pending;
+    instance.gl_filter_chain_free_pending =
+        __librashader__noop_gl_filter_chain_free_pending;
+    instance.gl_filter_chain_create_identity =
+        __librashader__noop_gl_filter_chain_create_identity;
     instance.gl_filter_chain_frame = __librashader__noop_gl_filter_chain_frame;
     instance.gl_filter_chain_free = __librashader__noop_gl_filter_chain_free;
     instance.gl_filter_chain_get_active_pass_count =
         __librashader__noop_gl_filter_chain_get_active_pass_count;
+    instance.gl_filter_chain_get_history_depth =
+        __librashader__noop_gl_filter_chain_get_history_depth;
+    instance.gl_filter_chain_invalidate_history =
+        __librashader__noop_gl_filter_chain_invalidate_history;
+    instance.gl_filter_chain_set_output_mesh =
+        __librashader__noop_gl_filter_chain_set_output_mesh;
+    instance.gl_filter_chain_get_pass_info =
+        __librashader__noop_gl_filter_chain_get_pass_info;
+    instance.gl_filter_chain_get_frame_statistics =
+        __librashader__noop_gl_filter_chain_get_frame_statistics;
     instance.gl_filter_chain_set_active_pass_count =
         __librashader__noop_gl_filter_chain_set_active_pass_count;
     instance.gl_filter_chain_get_param =
         __librashader__noop_gl_filter_chain_get_param;
+    instance.gl_filter_chain_get_param_handle =
+        __librashader__noop_gl_filter_chain_get_param_handle;
+    instance.gl_filter_chain_set_param_by_handle =
+        __librashader__noop_gl_filter_chain_set_param_by_handle;
+    instance.gl_filter_chain_get_param_by_handle =
+        __librashader__noop_gl_filter_chain_get_param_by_handle;
+    instance.gl_filter_chain_get_runtime_params =
+        __librashader__noop_gl_filter_chain_get_runtime_params;
     instance.gl_filter_chain_set_param =
         __librashader__noop_gl_filter_chain_set_param;
+    instance.gl_device_context_create =
+        __librashader__noop_gl_device_context_create;
+    instance.gl_device_context_free =
+        __librashader__noop_gl_device_context_free;
 #endif
 
 #if defined(LIBRA_RUNTIME_VULKAN)
     instance.vk_filter_chain_create =
         __librashader__noop_vk_filter_chain_create;
+    instance.vk_filter_chain_create_async =
+        __librashader__noop_vk_filter_chain_create_async;
+    instance.vk_filter_chain_poll_pending =
+        __librashader__noop_vk_filter_chain_poll_pending;
+    instance.vk_filter_chain_wait_pending =
+        __librashader__noop_vk_filter_chain_wait_pending;
+    instance.vk_filter_chain_free_pending =
+        __librashader__noop_vk_filter_chain_free_pending;
+    instance.vk_filter_chain_create_identity =
+        __librashader__noop_vk_filter_chain_create_identity;
     instance.vk_filter_chain_create_deferred =
         __librashader__noop_vk_filter_chain_create_deferred;
     instance.vk_filter_chain_frame = __librashader__noop_vk_filter_chain_frame;
     instance.vk_filter_chain_free = __librashader__noop_vk_filter_chain_free;
     instance.vk_filter_chain_get_active_pass_count =
         __librashader__noop_vk_filter_chain_get_active_pass_count;
+    instance.vk_filter_chain_get_history_depth =
+        __librashader__noop_vk_filter_chain_get_history_depth;
+    instance.vk_filter_chain_invalidate_history =
+        __librashader__noop_vk_filter_chain_invalidate_history;
+    instance.vk_filter_chain_set_output_mesh =
+        __librashader__noop_vk_filter_chain_set_output_mesh;
+    instance.vk_filter_chain_set_timestamp_queries =
+        __librashader__noop_vk_filter_chain_set_timestamp_queries;
+    instance.vk_filter_chain_get_pass_info =
+        __librashader__noop_vk_filter_chain_get_pass_info;
+    instance.vk_filter_chain_get_frame_statistics =
+        __librashader__noop_vk_filter_chain_get_frame_statistics;
+    instance.vk_filter_chain_has_pending_pipelines =
+        __librashader__noop_vk_filter_chain_has_pending_pipelines;
     instance.vk_filter_chain_set_active_pass_count =
         __librashader__noop_vk_filter_chain_set_active_pass_count;
     instance.vk_filter_chain_get_param =
         __librashader__noop_vk_filter_chain_get_param;
+    instance.vk_filter_chain_get_param_handle =
+        __librashader__noop_vk_filter_chain_get_param_handle;
+    instance.vk_filter_chain_set_param_by_handle =
+        __librashader__noop_vk_filter_chain_set_param_by_handle;
+    instance.vk_filter_chain_get_param_by_handle =
+        __librashader__noop_vk_filter_chain_get_param_by_handle;
+    instance.vk_filter_chain_get_runtime_params =
+        __librashader__noop_vk_filter_chain_get_runtime_params;
     instance.vk_filter_chain_set_param =
         __librashader__noop_vk_filter_chain_set_param;
+    instance.vk_device_context_create =
+        __librashader__noop_vk_device_context_create;
+    instance.vk_device_context_free =
+        __librashader__noop_vk_device_context_free;
+    instance.vk_filter_chain_free_deferred =
+        __librashader__noop_vk_filter_chain_free_deferred;
+    instance.vk_filter_chain_collect_deferred =
+        __librashader__noop_vk_filter_chain_collect_deferred;
 #endif
 
 #if defined(LIBRA_RUNTIME_D3D11)
     instance.d3d11_filter_chain_create =
         __librashader__noop_d3d11_filter_chain_create;
+    instance.d3d11_filter_chain_create_async =
+        __librashader__noop_d3d11_filter_chain_create_async;
+    instance.d3d11_filter_chain_poll_pending =
+        __librashader__noop_d3d11_filter_chain_poll_pending;
+    instance.d3d11_filter_chain_wait_pending =
+        __librashader__noop_d3d11_filter_chain_wait_pending;
+    instance.d3d11_filter_chain_free_pending =
+        __librashader__noop_d3d11_filter_chain_free_pending;
+    instance.d3d11_filter_chain_create_identity =
+        __librashader__noop_d3d11_filter_chain_create_identity;
     instance.d3d11_filter_chain_create_deferred =
         __librashader__noop_d3d11_filter_chain_create_deferred;
     instance.d3d11_filter_chain_frame =
@@ -1510,17 +3731,49 @@ libra_instance_t __librashader_make_null_instance(void) {
         __librashader__noop_d3d11_filter_chain_free;
     instance.d3d11_filter_chain_get_active_pass_count =
         __librashader__noop_d3d11_filter_chain_get_active_pass_count;
+    instance.d3d11_filter_chain_get_history_depth =
+        __librashader__noop_d3d11_filter_chain_get_history_depth;
+    instance.d3d11_filter_chain_invalidate_history =
+        __librashader__noop_d3d11_filter_chain_invalidate_history;
+    instance.d3d11_filter_chain_set_output_mesh =
+        __librashader__noop_d3d11_filter_chain_set_output_mesh;
+    instance.d3d11_filter_chain_get_pass_info =
+        __librashader__noop_d3d11_filter_chain_get_pass_info;
+    instance.d3d11_filter_chain_get_frame_statistics =
+        __librashader__noop_d3d11_filter_chain_get_frame_statistics;
     instance.d3d11_filter_chain_set_active_pass_count =
         __librashader__noop_d3d11_filter_chain_set_active_pass_count;
     instance.d3d11_filter_chain_get_param =
         __librashader__noop_d3d11_filter_chain_get_param;
+    instance.d3d11_filter_chain_get_param_handle =
+        __librashader__noop_d3d11_filter_chain_get_param_handle;
+    instance.d3d11_filter_chain_set_param_by_handle =
+        __librashader__noop_d3d11_filter_chain_set_param_by_handle;
+    instance.d3d11_filter_chain_get_param_by_handle =
+        __librashader__noop_d3d11_filter_chain_get_param_by_handle;
+    instance.d3d11_filter_chain_get_runtime_params =
+        __librashader__noop_d3d11_filter_chain_get_runtime_params;
     instance.d3d11_filter_chain_set_param =
         __librashader__noop_d3d11_filter_chain_set_param;
+    instance.d3d11_device_context_create =
+        __librashader__noop_d3d11_device_context_create;
+    instance.d3d11_device_context_free =
+        __librashader__noop_d3d11_device_context_free;
 #endif
 
 #if defined(LIBRA_RUNTIME_D3D12)
     instance.d3d12_filter_chain_create =
         __librashader__noop_d3d12_filter_chain_create;
+    instance.d3d12_filter_chain_create_async =
+        __librashader__noop_d3d12_filter_chain_create_async;
+    instance.d3d12_filter_chain_poll_pending =
+        __librashader__noop_d3d12_filter_chain_poll_pending;
+    instance.d3d12_filter_chain_wait_pending =
+        __librashader__noop_d3d12_filter_chain_wait_pending;
+    instance.d3d12_filter_chain_free_pending =
+        __librashader__noop_d3d12_filter_chain_free_pending;
+    instance.d3d12_filter_chain_create_identity =
+        __librashader__noop_d3d12_filter_chain_create_identity;
     instance.d3d12_filter_chain_create_deferred =
         __librashader__noop_d3d12_filter_chain_create_deferred;
     instance.d3d12_filter_chain_frame =
@@ -1529,17 +3782,51 @@ libra_instance_t __librashader_make_null_instance(void) {
         __librashader__noop_d3d12_filter_chain_free;
     instance.d3d12_filter_chain_get_active_pass_count =
         __librashader__noop_d3d12_filter_chain_get_active_pass_count;
+    instance.d3d12_filter_chain_get_history_depth =
+        __librashader__noop_d3d12_filter_chain_get_history_depth;
+    instance.d3d12_filter_chain_invalidate_history =
+        __librashader__noop_d3d12_filter_chain_invalidate_history;
+    instance.d3d12_filter_chain_set_output_mesh =
+        __librashader__noop_d3d12_filter_chain_set_output_mesh;
+    instance.d3d12_filter_chain_set_timestamp_queries =
+        __librashader__noop_d3d12_filter_chain_set_timestamp_queries;
+    instance.d3d12_filter_chain_get_pass_info =
+        __librashader__noop_d3d12_filter_chain_get_pass_info;
+    instance.d3d12_filter_chain_get_frame_statistics =
+        __librashader__noop_d3d12_filter_chain_get_frame_statistics;
     instance.d3d12_filter_chain_set_active_pass_count =
         __librashader__noop_d3d12_filter_chain_set_active_pass_count;
     instance.d3d12_filter_chain_get_param =
         __librashader__noop_d3d12_filter_chain_get_param;
+    instance.d3d12_filter_chain_get_param_handle =
+        __librashader__noop_d3d12_filter_chain_get_param_handle;
+    instance.d3d12_filter_chain_set_param_by_handle =
+        __librashader__noop_d3d12_filter_chain_set_param_by_handle;
+    instance.d3d12_filter_chain_get_param_by_handle =
+        __librashader__noop_d3d12_filter_chain_get_param_by_handle;
+    instance.d3d12_filter_chain_get_runtime_params =
+        __librashader__noop_d3d12_filter_chain_get_runtime_params;
     instance.d3d12_filter_chain_set_param =
         __librashader__noop_d3d12_filter_chain_set_param;
+    instance.d3d12_filter_chain_free_deferred =
+        __librashader__noop_d3d12_filter_chain_free_deferred;
+    instance.d3d12_filter_chain_collect_deferred =
+        __librashader__noop_d3d12_filter_chain_collect_deferred;
 #endif
 
 #if defined(LIBRA_RUNTIME_D3D9)
     instance.d3d9_filter_chain_create =
         __librashader__noop_d3d9_filter_chain_create;
+    instance.d3d9_filter_chain_create_async =
+        __librashader__noop_d3d9_filter_chain_create_async;
+    instance.d3d9_filter_chain_poll_pending =
+        __librashader__noop_d3d9_filter_chain_poll_pending;
+    instance.d3d9_filter_chain_wait_pending =
+        __librashader__noop_d3d9_filter_chain_wait_pending;
+    instance.d3d9_filter_chain_free_pending =
+        __librashader__noop_d3d9_filter_chain_free_pending;
+    instance.d3d9_filter_chain_create_identity =
+        __librashader__noop_d3d9_filter_chain_create_identity;
     instance.d3d9_filter_chain_create_deferred =
         __librashader__noop_d3d9_filter_chain_create_deferred;
     instance.d3d9_filter_chain_frame =
@@ -1548,17 +3835,49 @@ libra_instance_t __librashader_make_null_instance(void) {
         __librashader__noop_d3d9_filter_chain_free;
     instance.d3d9_filter_chain_get_active_pass_count =
         __librashader__noop_d3d9_filter_chain_get_active_pass_count;
+    instance.d3d9_filter_chain_get_history_depth =
+        __librashader__noop_d3d9_filter_chain_get_history_depth;
+    instance.d3d9_filter_chain_invalidate_history =
+        __librashader__noop_d3d9_filter_chain_invalidate_history;
+    instance.d3d9_filter_chain_set_output_mesh =
+        __librashader__noop_d3d9_filter_chain_set_output_mesh;
+    instance.d3d9_filter_chain_get_pass_info =
+        __librashader__noop_d3d9_filter_chain_get_pass_info;
+    instance.d3d9_filter_chain_get_frame_statistics =
+        __librashader__noop_d3d9_filter_chain_get_frame_statistics;
     instance.d3d9_filter_chain_set_active_pass_count =
         __librashader__noop_d3d9_filter_chain_set_active_pass_count;
     instance.d3d9_filter_chain_get_param =
         __librashader__noop_d3d9_filter_chain_get_param;
+    instance.d3d9_filter_chain_get_param_handle =
+        __librashader__noop_d3d9_filter_chain_get_param_handle;
+    instance.d3d9_filter_chain_set_param_by_handle =
+        __librashader__noop_d3d9_filter_chain_set_param_by_handle;
+    instance.d3d9_filter_chain_get_param_by_handle =
+        __librashader__noop_d3d9_filter_chain_get_param_by_handle;
+    instance.d3d9_filter_chain_get_runtime_params =
+        __librashader__noop_d3d9_filter_chain_get_runtime_params;
     instance.d3d9_filter_chain_set_param =
         __librashader__noop_d3d9_filter_chain_set_param;
+    instance.d3d9_device_context_create =
+        __librashader__noop_d3d9_device_context_create;
+    instance.d3d9_device_context_free =
+        __librashader__noop_d3d9_device_context_free;
 #endif
 
 #if defined(LIBRA_RUNTIME_METAL)
     instance.mtl_filter_chain_create =
         __librashader__noop_mtl_filter_chain_create;
+    instance.mtl_filter_chain_create_async =
+        __librashader__noop_mtl_filter_chain_create_async;
+    instance.mtl_filter_chain_poll_pending =
+        __librashader__noop_mtl_filter_chain_poll_pending;
+    instance.mtl_filter_chain_wait_pending =
+        __librashader__noop_mtl_filter_chain_wait_pending;
+    instance.mtl_filter_chain_free_pending =
+        __librashader__noop_mtl_filter_chain_free_pending;
+    instance.mtl_filter_chain_create_identity =
+        __librashader__noop_mtl_filter_chain_create_identity;
     instance.mtl_filter_chain_create_deferred =
         __librashader__noop_mtl_filter_chain_create_deferred;
     instance.mtl_filter_chain_frame =
@@ -1566,12 +3885,38 @@ libra_instance_t __librashader_make_null_instance(void) {
     instance.mtl_filter_chain_free = __librashader__noop_mtl_filter_chain_free;
     instance.mtl_filter_chain_get_active_pass_count =
         __librashader__noop_mtl_filter_chain_get_active_pass_count;
+    instance.mtl_filter_chain_get_history_depth =
+        __librashader__noop_mtl_filter_chain_get_history_depth;
+    instance.mtl_filter_chain_invalidate_history =
+        __librashader__noop_mtl_filter_chain_invalidate_history;
+    instance.mtl_filter_chain_set_output_mesh =
+        __librashader__noop_mtl_filter_chain_set_output_mesh;
+    instance.mtl_filter_chain_get_pass_info =
+        __librashader__noop_mtl_filter_chain_get_pass_info;
+    instance.mtl_filter_chain_get_frame_statistics =
+        __librashader__noop_mtl_filter_chain_get_frame_statistics;
     instance.mtl_filter_chain_set_active_pass_count =
         __librashader__noop_mtl_filter_chain_set_active_pass_count;
     instance.mtl_filter_chain_get_param =
         __librashader__noop_mtl_filter_chain_get_param;
+    instance.mtl_filter_chain_get_param_handle =
+        __librashader__noop_mtl_filter_chain_get_param_handle;
+    instance.mtl_filter_chain_set_param_by_handle =
+        __librashader__noop_mtl_filter_chain_set_param_by_handle;
+    instance.mtl_filter_chain_get_param_by_handle =
+        __librashader__noop_mtl_filter_chain_get_param_by_handle;
+    instance.mtl_filter_chain_get_runtime_params =
+        __librashader__noop_mtl_filter_chain_get_runtime_params;
     instance.mtl_filter_chain_set_param =
         __librashader__noop_mtl_filter_chain_set_param;
+    instance.mtl_device_context_create =
+        __librashader__noop_mtl_device_context_create;
+    instance.mtl_device_context_free =
+        __librashader__noop_mtl_device_context_free;
+    instance.mtl_filter_chain_free_deferred =
+        __librashader__noop_mtl_filter_chain_free_deferred;
+    instance.mtl_filter_chain_collect_deferred =
+        __librashader__noop_mtl_filter_chain_collect_deferred;
 #endif
     instance.instance_loaded = false;
 
@@ -1603,6 +3948,12 @@ libra_instance_t librashader_load_instance(void) {
 
     _LIBRASHADER_ASSIGN(librashader, instance, instance_abi_version);
     _LIBRASHADER_ASSIGN(librashader, instance, instance_api_version);
+    _LIBRASHADER_ASSIGN(librashader, instance, instance_runtime_supported);
+    _LIBRASHADER_ASSIGN(librashader, instance, instance_option_supported);
+    _LIBRASHADER_ASSIGN(librashader, instance, instance_version_string);
+    _LIBRASHADER_ASSIGN(librashader, instance, instance_commit_string);
+    _LIBRASHADER_ASSIGN(librashader, instance, instance_features_string);
+    _LIBRASHADER_ASSIGN(librashader, instance, instance_set_allocator);
 
     // Ensure ABI matches.
     if (instance.instance_abi_version() != LIBRASHADER_CURRENT_ABI) {
@@ -1625,101 +3976,297 @@ libra_instance_t librashader_load_instance(void) {
     _LIBRASHADER_ASSIGN(librashader, instance,
                         preset_ctx_set_core_aspect_orientation);
 
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_translations_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_translations_free);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_translations_set);
+
     _LIBRASHADER_ASSIGN(librashader, instance, preset_create);
     _LIBRASHADER_ASSIGN(librashader, instance, preset_create_with_context);
     _LIBRASHADER_ASSIGN(librashader, instance, preset_create_with_options);
 
     _LIBRASHADER_ASSIGN(librashader, instance, preset_free);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_clone);
     _LIBRASHADER_ASSIGN(librashader, instance, preset_set_param);
     _LIBRASHADER_ASSIGN(librashader, instance, preset_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_set_pass_define);
     _LIBRASHADER_ASSIGN(librashader, instance, preset_print);
     _LIBRASHADER_ASSIGN(librashader, instance, preset_get_runtime_params);
     _LIBRASHADER_ASSIGN(librashader, instance, preset_free_runtime_params);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        preset_get_runtime_params_localized);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        preset_get_runtime_param_widgets);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        preset_free_runtime_param_widgets);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_get_passes);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_free_passes);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_get_textures);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_free_textures);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_get_warnings);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_free_warnings);
+    _LIBRASHADER_ASSIGN(librashader, instance, preset_get_content_hash);
 
     _LIBRASHADER_ASSIGN(librashader, instance, error_errno);
     _LIBRASHADER_ASSIGN(librashader, instance, error_print);
     _LIBRASHADER_ASSIGN(librashader, instance, error_free);
     _LIBRASHADER_ASSIGN(librashader, instance, error_write);
+    _LIBRASHADER_ASSIGN(librashader, instance, error_write_source);
+    _LIBRASHADER_ASSIGN(librashader, instance, error_write_backtrace);
     _LIBRASHADER_ASSIGN(librashader, instance, error_free_string);
 
+    _LIBRASHADER_ASSIGN(librashader, instance, cancel_token_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, cancel_token_cancel);
+    _LIBRASHADER_ASSIGN(librashader, instance, cancel_token_free);
+    _LIBRASHADER_ASSIGN(librashader, instance, pass_info_free);
+    _LIBRASHADER_ASSIGN(librashader, instance, instance_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, instance_free);
+
 #if defined(LIBRA_RUNTIME_OPENGL)
     _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_create_async);
+    _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_poll_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_wait_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_free_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        gl_filter_chain_create_identity);
     _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_frame);
     _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_free);
     _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        gl_filter_chain_get_param_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        gl_filter_chain_set_param_by_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        gl_filter_chain_get_param_by_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        gl_filter_chain_get_runtime_params);
     _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_set_param);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         gl_filter_chain_get_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        gl_filter_chain_get_history_depth);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        gl_filter_chain_invalidate_history);
+    _LIBRASHADER_ASSIGN(librashader, instance, gl_filter_chain_set_output_mesh);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        gl_filter_chain_get_pass_info);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        gl_filter_chain_get_frame_statistics);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         gl_filter_chain_set_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance, gl_device_context_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, gl_device_context_free);
 
 #endif
 
 #if defined(LIBRA_RUNTIME_VULKAN)
     _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_create_async);
+    _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_poll_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_wait_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_free_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_create_identity);
     _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_create_deferred);
     _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_frame);
     _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_free);
     _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_get_param_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_set_param_by_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_get_param_by_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_get_runtime_params);
     _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_set_param);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         vk_filter_chain_get_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_get_history_depth);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_invalidate_history);
+    _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_set_output_mesh);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_set_timestamp_queries);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_get_pass_info);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_get_frame_statistics);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_has_pending_pipelines);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         vk_filter_chain_set_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance, vk_device_context_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, vk_device_context_free);
+    _LIBRASHADER_ASSIGN(librashader, instance, vk_filter_chain_free_deferred);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        vk_filter_chain_collect_deferred);
 #endif
 
 #if defined(_WIN32) && defined(LIBRA_RUNTIME_D3D11)
     _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_create_async);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_poll_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_wait_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_free_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d11_filter_chain_create_identity);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         d3d11_filter_chain_create_deferred);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_frame);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_free);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d11_filter_chain_get_param_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d11_filter_chain_set_param_by_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d11_filter_chain_get_param_by_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d11_filter_chain_get_runtime_params);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d11_filter_chain_set_param);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         d3d11_filter_chain_get_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d11_filter_chain_get_history_depth);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d11_filter_chain_invalidate_history);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d11_filter_chain_set_output_mesh);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d11_filter_chain_get_pass_info);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d11_filter_chain_get_frame_statistics);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         d3d11_filter_chain_set_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d11_device_context_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d11_device_context_free);
 #endif
 
 #if defined(_WIN32) && defined(LIBRA_RUNTIME_D3D12)
     _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_create_async);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_poll_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_wait_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_free_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_create_identity);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         d3d12_filter_chain_create_deferred);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_frame);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_free);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_get_param_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_set_param_by_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_get_param_by_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_get_runtime_params);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d12_filter_chain_set_param);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         d3d12_filter_chain_get_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_get_history_depth);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_invalidate_history);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_set_output_mesh);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_set_timestamp_queries);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_get_pass_info);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_get_frame_statistics);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         d3d12_filter_chain_set_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_free_deferred);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d12_filter_chain_collect_deferred);
 #endif
 
 #if defined(_WIN32) && defined(LIBRA_RUNTIME_D3D9)
     _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_create_async);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_poll_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_wait_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_free_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d9_filter_chain_create_identity);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_frame);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_free);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d9_filter_chain_get_param_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d9_filter_chain_set_param_by_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d9_filter_chain_get_param_by_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d9_filter_chain_get_runtime_params);
     _LIBRASHADER_ASSIGN(librashader, instance, d3d9_filter_chain_set_param);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         d3d9_filter_chain_get_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d9_filter_chain_get_history_depth);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d9_filter_chain_invalidate_history);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d9_filter_chain_set_output_mesh);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d9_filter_chain_get_pass_info);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        d3d9_filter_chain_get_frame_statistics);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         d3d9_filter_chain_set_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d9_device_context_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, d3d9_device_context_free);
 #endif
 
 #if defined(__APPLE__) && defined(LIBRA_RUNTIME_METAL)
     _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_create_async);
+    _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_poll_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_wait_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_free_pending);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        mtl_filter_chain_create_identity);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         mtl_filter_chain_create_deferred);
     _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_frame);
     _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_free);
     _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_get_param);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        mtl_filter_chain_get_param_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        mtl_filter_chain_set_param_by_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        mtl_filter_chain_get_param_by_handle);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        mtl_filter_chain_get_runtime_params);
     _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_set_param);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         mtl_filter_chain_get_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        mtl_filter_chain_get_history_depth);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        mtl_filter_chain_invalidate_history);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        mtl_filter_chain_set_output_mesh);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        mtl_filter_chain_get_pass_info);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        mtl_filter_chain_get_frame_statistics);
     _LIBRASHADER_ASSIGN(librashader, instance,
                         mtl_filter_chain_set_active_pass_count);
+    _LIBRASHADER_ASSIGN(librashader, instance, mtl_device_context_create);
+    _LIBRASHADER_ASSIGN(librashader, instance, mtl_device_context_free);
+    _LIBRASHADER_ASSIGN(librashader, instance, mtl_filter_chain_free_deferred);
+    _LIBRASHADER_ASSIGN(librashader, instance,
+                        mtl_filter_chain_collect_deferred);
 #endif
     instance.instance_loaded = true;
     return instance;
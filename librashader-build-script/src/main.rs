@@ -1,5 +1,6 @@
 use carlog::*;
 use clap::Parser;
+use std::ffi::OsStr;
 use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
@@ -15,10 +16,134 @@ struct Args {
     target: Option<String>,
     #[arg(long, default_value_t = false, global = true)]
     stable: bool,
+    /// Only build the given runtimes instead of every runtime.
+    #[arg(long, value_delimiter = ',', global = true)]
+    runtimes: Vec<Runtime>,
+    /// Hide every symbol of the static library except for the C API.
+    #[arg(long, default_value_t = false, global = true)]
+    hide_symbols: bool,
     #[arg(last = true)]
     cargoflags: Vec<String>,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum Runtime {
+    Opengl,
+    Vulkan,
+    D3d9,
+    D3d11,
+    D3d12,
+    Metal,
+}
+
+impl Runtime {
+    fn feature(self) -> &'static str {
+        match self {
+            Runtime::Opengl => "runtime-opengl",
+            Runtime::Vulkan => "runtime-vulkan",
+            Runtime::D3d9 => "runtime-d3d9",
+            Runtime::D3d11 => "runtime-d3d11",
+            Runtime::D3d12 => "runtime-d3d12",
+            Runtime::Metal => "runtime-metal",
+        }
+    }
+}
+
+fn run_tool(env_var: &str, default: &str, args: &[&OsStr]) -> bool {
+    let program = env::var(env_var).unwrap_or_else(|_| default.to_string());
+    match Command::new(&program).args(args).status() {
+        Ok(status) if status.success() => true,
+        Ok(status) => {
+            carlog_error!(format!("{program} exited with {status}"));
+            false
+        }
+        Err(err) => {
+            carlog_error!(format!("unable to run {program}"));
+            carlog_error!(format!("{err}"));
+            false
+        }
+    }
+}
+
+/// Partially link the static library into a single object, and make every symbol that is not
+/// part of the C API local to it.
+///
+/// This keeps the Rust standard library, allocator shims and C++ dependencies of librashader
+/// from clashing with other static libraries linked into the same binary.
+fn hide_symbols(output_dir: &Path) -> bool {
+    let archive = output_dir.join("librashader.a");
+    let object = output_dir.join("librashader.o");
+
+    if cfg!(target_os = "macos") {
+        let exports = output_dir.join("librashader.exports");
+        if let Err(err) = fs::write(&exports, "_libra_*\n") {
+            carlog_error!("unable to write exported symbols list");
+            carlog_error!(format!("{err}"));
+            return false;
+        }
+
+        if !run_tool(
+            "LD",
+            "ld",
+            &[
+                "-r".as_ref(),
+                "-all_load".as_ref(),
+                "-exported_symbols_list".as_ref(),
+                exports.as_os_str(),
+                archive.as_os_str(),
+                "-o".as_ref(),
+                object.as_os_str(),
+            ],
+        ) {
+            return false;
+        }
+        let _ = fs::remove_file(exports);
+    } else {
+        if !run_tool(
+            "LD",
+            "ld",
+            &[
+                "-r".as_ref(),
+                "--whole-archive".as_ref(),
+                archive.as_os_str(),
+                "-o".as_ref(),
+                object.as_os_str(),
+            ],
+        ) {
+            return false;
+        }
+
+        if !run_tool(
+            "OBJCOPY",
+            "objcopy",
+            &[
+                "--wildcard".as_ref(),
+                "--keep-global-symbol=libra_*".as_ref(),
+                object.as_os_str(),
+            ],
+        ) {
+            return false;
+        }
+    }
+
+    if let Err(err) = fs::remove_file(&archive) {
+        carlog_error!("unable to remove librashader.a");
+        carlog_error!(format!("{err}"));
+        return false;
+    }
+
+    if !run_tool(
+        "AR",
+        "ar",
+        &["rcs".as_ref(), archive.as_os_str(), object.as_os_str()],
+    ) {
+        return false;
+    }
+
+    let _ = fs::remove_file(object);
+    true
+}
+
 pub fn main() -> ExitCode {
     // Do not update files on docsrs
     if env::var("DOCS_RS").is_ok() {
@@ -56,6 +181,12 @@ pub fn main() -> ExitCode {
         carlog_warning!("C headers will not be generated");
         cmd.args(["--features", "stable"]);
     }
+    if !args.runtimes.is_empty() {
+        let features: Vec<&str> = args.runtimes.iter().map(|r| r.feature()).collect();
+        carlog_info!("Selecting", format!("runtimes {}", features.join(", ")));
+        cmd.arg("--no-default-features");
+        cmd.args(["--features", &features.join(",")]);
+    }
     if !args.cargoflags.is_empty() {
         cmd.args(args.cargoflags);
     }
@@ -180,5 +311,18 @@ pub fn main() -> ExitCode {
         }
     }
 
+    if args.hide_symbols {
+        if cfg!(target_os = "windows") {
+            carlog_warning!("hiding symbols is not supported for Windows static libraries");
+        } else {
+            carlog_info!("Hiding", "symbols of librashader.a");
+            if !hide_symbols(&output_dir) {
+                carlog_error!("failed to hide symbols of librashader.a");
+                return ExitCode::FAILURE;
+            }
+            carlog_ok!("Hidden", "symbols other than the C API in librashader.a");
+        }
+    }
+
     ExitCode::SUCCESS
 }
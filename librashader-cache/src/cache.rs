@@ -1,5 +1,6 @@
 use crate::cacheable::Cacheable;
 use crate::key::CacheKey;
+use std::path::PathBuf;
 
 pub(crate) mod internal {
     #[derive(Debug, Error)]
@@ -10,6 +11,7 @@ pub(crate) mod internal {
 
     use platform_dirs::AppDirs;
     use std::any::Any;
+    use std::cell::RefCell;
     use std::error::Error;
     use std::panic::catch_unwind;
     use std::path::PathBuf;
@@ -17,8 +19,14 @@ pub(crate) mod internal {
     use persy::{ByteVec, Config, Persy, ValueMode};
     use thiserror::Error;
 
+    thread_local! {
+        pub(crate) static CACHE_DIR: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+    }
+
     pub(crate) fn get_cache_dir() -> Result<PathBuf, Box<dyn Error>> {
-        let cache_dir = if let Some(cache_dir) =
+        let cache_dir = if let Some(cache_dir) = CACHE_DIR.with_borrow(|dir| dir.clone()) {
+            cache_dir
+        } else if let Some(cache_dir) =
             AppDirs::new(Some("librashader"), false).map(|a| a.cache_dir)
         {
             cache_dir
@@ -110,6 +118,14 @@ pub(crate) mod internal {
     }
 }
 
+/// Set the directory of the cache used by the current thread.
+///
+/// If `None`, the current thread uses the default cache directory of the platform.
+/// Returns the directory that was previously set for the current thread.
+pub fn set_thread_cache_dir(dir: Option<PathBuf>) -> Option<PathBuf> {
+    internal::CACHE_DIR.replace(dir)
+}
+
 /// Cache a shader object (usually bytecode) created by the keyed objects.
 ///
 /// - `factory` is the function that compiles the values passed as keys to a shader object.
@@ -36,6 +36,9 @@ where
             let mut hasher = blake3::Hasher::new();
             hasher.update(source.vertex.as_bytes());
             hasher.update(source.fragment.as_bytes());
+            if let Some(compute) = &source.compute {
+                hasher.update(compute.as_bytes());
+            }
             let hash = hasher.finalize();
             hash
         };
@@ -73,6 +76,15 @@ where
     }
 }
 
+/// Compile the shader source to SPIR-V and store the result in the cache, so that
+/// runtimes that compile the same source later load it from the cache instead.
+///
+/// If the cache is not available, the source is compiled without being stored.
+pub fn precompile_spirv(source: &ShaderSource) -> Result<(), ShaderCompileError> {
+    <Glslang as ShaderInputCompiler<CachedCompilation<SpirvCompilation>>>::compile(source)?;
+    Ok(())
+}
+
 #[cfg(all(target_os = "windows", feature = "d3d"))]
 impl<T> FromCompilation<CachedCompilation<SpirvCompilation>, T> for DXIL
 where
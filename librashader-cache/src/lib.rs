@@ -13,10 +13,11 @@ mod key;
 pub use cacheable::Cacheable;
 pub use key::CacheKey;
 
-pub use compilation::CachedCompilation;
+pub use compilation::{precompile_spirv, CachedCompilation};
 
 pub use cache::cache_pipeline;
 pub use cache::cache_shader_object;
+pub use cache::set_thread_cache_dir;
 
 #[cfg(all(target_os = "windows", feature = "d3d"))]
 mod d3d;
@@ -1,3 +1,27 @@
+use std::env;
+use std::process::Command;
+
+const FEATURES: &[&str] = &[
+    "runtime-opengl",
+    "runtime-vulkan",
+    "runtime-d3d9",
+    "runtime-d3d11",
+    "runtime-d3d12",
+    "runtime-metal",
+    "reflect-unstable",
+    "stable",
+];
+
+/// The runtimes that can be built for the target, with the feature that enables them.
+fn runtime_available(feature: &str, target_os: &str, target_vendor: &str) -> bool {
+    match feature {
+        "runtime-opengl" | "runtime-vulkan" => true,
+        "runtime-d3d9" | "runtime-d3d11" | "runtime-d3d12" => target_os == "windows",
+        "runtime-metal" => target_vendor == "apple",
+        _ => false,
+    }
+}
+
 pub fn main() {
     #[cfg(all(target_os = "windows", feature = "runtime-d3d12"))]
     {
@@ -5,4 +29,47 @@ pub fn main() {
         println!("cargo:rustc-link-arg=/DELAYLOAD:dxcompiler.dll");
         println!("cargo:rustc-link-arg=/DELAYLOAD:d3d12.dll");
     }
+
+    // Source tarballs have no git history, so packagers can provide the commit themselves.
+    let commit = env::var("LIBRASHADER_COMMIT").ok().or_else(|| {
+        let output = Command::new("git")
+            .args(["rev-parse", "--short=12", "HEAD"])
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    });
+    println!(
+        "cargo:rustc-env=LIBRASHADER_COMMIT={}",
+        commit.as_deref().unwrap_or("unknown")
+    );
+
+    // Optional dependencies are also exposed as features, so only report the public ones.
+    let features: Vec<&str> = FEATURES
+        .iter()
+        .copied()
+        .filter(|feature| {
+            let key = format!("CARGO_FEATURE_{}", feature.to_uppercase().replace('-', "_"));
+            env::var_os(key).is_some()
+        })
+        .collect();
+    println!(
+        "cargo:rustc-env=LIBRASHADER_FEATURES={}",
+        features.join(",")
+    );
+
+    // Runtimes can be enabled for targets they are not available on, such as when building
+    // every runtime by default. Helpers shared by the runtime APIs are only needed if at least
+    // one runtime is built.
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let target_vendor = env::var("CARGO_CFG_TARGET_VENDOR").unwrap_or_default();
+    println!("cargo:rustc-check-cfg=cfg(libra_runtime)");
+    if features
+        .iter()
+        .any(|feature| runtime_available(feature, &target_os, &target_vendor))
+    {
+        println!("cargo:rustc-cfg=libra_runtime");
+    }
 }
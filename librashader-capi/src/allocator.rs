@@ -0,0 +1,163 @@
+//! librashader allocator C API (`libra_instance_set_allocator`).
+use crate::ctypes::libra_allocator_t;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::UnsafeCell;
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// No allocation was made yet, and an allocator can still be set.
+const UNSET: u8 = 0;
+/// An allocator is being set by `libra_instance_set_allocator`.
+const SETTING: u8 = 1;
+/// The allocator of the host is in use.
+const HOST: u8 = 2;
+/// The system allocator is in use.
+const SYSTEM: u8 = 3;
+
+static STATE: AtomicU8 = AtomicU8::new(UNSET);
+
+struct HostAllocator {
+    userdata: *mut c_void,
+    alloc: unsafe extern "C" fn(userdata: *mut c_void, size: usize, align: usize) -> *mut c_void,
+    free: unsafe extern "C" fn(userdata: *mut c_void, ptr: *mut c_void, size: usize, align: usize),
+}
+
+struct HostAllocatorCell(UnsafeCell<Option<HostAllocator>>);
+
+// SAFETY: The cell is only written to while `STATE` is `SETTING`, which only one thread
+// can enter, and only read from after `STATE` was set to `HOST` with release ordering.
+unsafe impl Sync for HostAllocatorCell {}
+
+static HOST_ALLOCATOR: HostAllocatorCell = HostAllocatorCell(UnsafeCell::new(None));
+
+/// The global allocator of librashader, which forwards to the allocator set by the host
+/// or to the system allocator if none was set before the first allocation.
+struct LibrashaderAllocator;
+
+#[global_allocator]
+static ALLOCATOR: LibrashaderAllocator = LibrashaderAllocator;
+
+impl LibrashaderAllocator {
+    #[inline]
+    fn host() -> Option<&'static HostAllocator> {
+        loop {
+            match STATE.load(Ordering::Acquire) {
+                // SAFETY: the host allocator is never written to after `STATE` is `HOST`.
+                HOST => return unsafe { (*HOST_ALLOCATOR.0.get()).as_ref() },
+                SYSTEM => return None,
+                SETTING => std::hint::spin_loop(),
+                _ => {
+                    // The first allocation locks in the system allocator.
+                    if STATE
+                        .compare_exchange(UNSET, SYSTEM, Ordering::AcqRel, Ordering::Acquire)
+                        .is_ok()
+                    {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for LibrashaderAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        match Self::host() {
+            Some(host) => unsafe {
+                (host.alloc)(host.userdata, layout.size(), layout.align()).cast()
+            },
+            None => unsafe { System.alloc(layout) },
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        match Self::host() {
+            Some(host) => unsafe {
+                (host.free)(host.userdata, ptr.cast(), layout.size(), layout.align())
+            },
+            None => unsafe { System.dealloc(ptr, layout) },
+        }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if Self::host().is_none() {
+            return unsafe { System.alloc_zeroed(layout) };
+        }
+
+        let ptr = unsafe { self.alloc(layout) };
+        if !ptr.is_null() {
+            unsafe { ptr.write_bytes(0, layout.size()) };
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if Self::host().is_none() {
+            return unsafe { System.realloc(ptr, layout, new_size) };
+        }
+
+        // SAFETY: the caller guarantees that the new size does not overflow when aligned.
+        let new_layout = unsafe { Layout::from_size_align_unchecked(new_size, layout.align()) };
+        let new_ptr = unsafe { self.alloc(new_layout) };
+        if !new_ptr.is_null() {
+            unsafe {
+                std::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+                self.dealloc(ptr, layout);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Function pointer definition for libra_instance_set_allocator
+pub type PFN_libra_instance_set_allocator =
+    unsafe extern "C" fn(allocator: *const libra_allocator_t) -> bool;
+
+/// Set the functions librashader allocates and frees memory with.
+///
+/// The allocator is used for all memory allocated by librashader, including presets,
+/// filter chains, errors and strings returned to the caller. Memory allocated by the shader
+/// compilers librashader links against, and by graphics drivers, is not affected.
+///
+/// The allocator can only be set once, and must be set before calling any other librashader
+/// function that allocates memory. Returns true if the allocator was set, or false if
+/// `allocator` or any of its functions are null, or if librashader already allocated memory,
+/// in which case the system allocator stays in use.
+///
+/// Allocation failures abort the process, so frontends that need to bound the memory
+/// librashader uses should check the limit before loading presets and creating filter chains.
+///
+/// ## Safety
+/// - `allocator` must be either null or a valid pointer to a `libra_allocator_t`.
+/// - The functions of the allocator must stay valid, and `userdata` must stay valid for them,
+///   until the process exits.
+#[no_mangle]
+pub unsafe extern "C" fn libra_instance_set_allocator(allocator: *const libra_allocator_t) -> bool {
+    // SAFETY: the caller guarantees that the allocator is null or valid.
+    let Some(&libra_allocator_t {
+        alloc: Some(alloc),
+        free: Some(free),
+        userdata,
+    }) = (unsafe { allocator.as_ref() })
+    else {
+        return false;
+    };
+
+    if STATE
+        .compare_exchange(UNSET, SETTING, Ordering::Acquire, Ordering::Relaxed)
+        .is_err()
+    {
+        return false;
+    }
+
+    // SAFETY: no other thread accesses the host allocator while `STATE` is `SETTING`.
+    unsafe {
+        *HOST_ALLOCATOR.0.get() = Some(HostAllocator {
+            userdata,
+            alloc,
+            free,
+        });
+    }
+    STATE.store(HOST, Ordering::Release);
+    true
+}
@@ -0,0 +1,70 @@
+//! librashader cancellation token C API (`libra_cancel_token_*`).
+
+use crate::ctypes::libra_cancel_token_t;
+use crate::error::{assert_non_null, assert_some_ptr};
+use crate::ffi::extern_fn;
+use librashader::runtime::CancellationToken;
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+const _: () = crate::assert_thread_safe::<CancellationToken>();
+
+extern_fn! {
+    /// Create a token to cancel filter chain creation with.
+    ///
+    /// The token is passed to filter chain creation with the `cancel` filter chain option,
+    /// and can be cancelled from any thread while the filter chain is being created.
+    /// A token that was cancelled stays cancelled, so a new token must be created for
+    /// every filter chain that may be cancelled.
+    ///
+    /// ## Safety
+    ///  - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_cancel_token_t`.
+    /// ## Returns
+    ///  - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+    fn libra_cancel_token_create(
+        out: *mut MaybeUninit<libra_cancel_token_t>
+    ) {
+        assert_non_null!(out);
+
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                CancellationToken::new(),
+            )))));
+        }
+    }
+}
+
+extern_fn! {
+    /// Cancel filter chain creation with the token.
+    ///
+    /// Filter chain creation with this token stops between shader passes, releases the
+    /// objects that were already created, and fails. This function is thread safe.
+    ///
+    /// ## Safety
+    /// - `token` must be either null, or a valid and aligned pointer to a `libra_cancel_token_t`.
+    fn libra_cancel_token_cancel(
+        token: *const libra_cancel_token_t
+    ) |token| {
+        assert_some_ptr!(token);
+        token.cancel();
+    }
+}
+
+extern_fn! {
+    /// Free the cancellation token.
+    ///
+    /// If `token` is null, this function does nothing. The resulting value in `token` then becomes
+    /// null. Filter chain creation that was passed the token must have returned before the token
+    /// is freed.
+    ///
+    /// ## Safety
+    /// - `token` must be a valid and aligned pointer to a `libra_cancel_token_t`
+    fn libra_cancel_token_free(token: *mut libra_cancel_token_t) {
+        assert_non_null!(token);
+        unsafe {
+            let token_ptr = &mut *token;
+            let token = token_ptr.take();
+            drop(Box::from_raw(token.unwrap().as_ptr()));
+        }
+    }
+}
@@ -1,8 +1,10 @@
 //! Binding types for the librashader C API.
-use crate::error::LibrashaderError;
+use crate::error::LibrashaderErrorObject;
+use librashader::preprocess::ParameterWidget;
 use librashader::presets::context::{Orientation, VideoDriver, WildcardContext};
-use librashader::presets::ShaderPreset;
-use std::mem::MaybeUninit;
+use librashader::presets::{ParameterTranslations, ScaleType, ShaderPreset};
+use librashader::{FilterMode, ImageFormat, WrapMode};
+use std::ffi::{c_char, c_void};
 use std::ptr::NonNull;
 
 /// A handle to a shader preset object.
@@ -11,8 +13,11 @@ pub type libra_shader_preset_t = Option<NonNull<ShaderPreset>>;
 /// A handle to a preset wildcard context object.
 pub type libra_preset_ctx_t = Option<NonNull<WildcardContext>>;
 
+/// A handle to a set of parameter translations.
+pub type libra_preset_translations_t = Option<NonNull<ParameterTranslations>>;
+
 /// A handle to a librashader error object.
-pub type libra_error_t = Option<NonNull<LibrashaderError>>;
+pub type libra_error_t = Option<NonNull<LibrashaderErrorObject>>;
 
 /// An enum representing orientation for use in preset contexts.
 #[repr(u32)]
@@ -66,6 +71,209 @@ impl From<LIBRA_PRESET_CTX_RUNTIME> for VideoDriver {
     }
 }
 
+/// An enum representing the graphics runtimes a librashader instance can be built with.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone)]
+pub enum LIBRA_RUNTIME {
+    /// OpenGL 3.3+
+    GlCore = 0,
+    /// Vulkan
+    Vulkan,
+    /// Direct3D 9
+    D3D9,
+    /// Direct3D 11
+    D3D11,
+    /// Direct3D 12
+    D3D12,
+    /// Metal
+    Metal,
+}
+
+/// An enum representing the filtering mode of a texture sampler.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone)]
+pub enum LIBRA_FILTER_MODE {
+    /// Linear filtering.
+    Linear = 0,
+    /// Nearest-neighbour (point) filtering.
+    Nearest,
+}
+
+impl From<FilterMode> for LIBRA_FILTER_MODE {
+    fn from(value: FilterMode) -> Self {
+        match value {
+            FilterMode::Linear => LIBRA_FILTER_MODE::Linear,
+            FilterMode::Nearest => LIBRA_FILTER_MODE::Nearest,
+        }
+    }
+}
+
+/// An enum representing the wrapping (address) mode of a texture sampler.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone)]
+pub enum LIBRA_WRAP_MODE {
+    /// Clamp texture to border.
+    ClampToBorder = 0,
+    /// Clamp texture to edge.
+    ClampToEdge,
+    /// Repeat addressing mode.
+    Repeat,
+    /// Mirrored repeat addressing mode.
+    MirroredRepeat,
+}
+
+impl From<WrapMode> for LIBRA_WRAP_MODE {
+    fn from(value: WrapMode) -> Self {
+        match value {
+            WrapMode::ClampToBorder => LIBRA_WRAP_MODE::ClampToBorder,
+            WrapMode::ClampToEdge => LIBRA_WRAP_MODE::ClampToEdge,
+            WrapMode::Repeat => LIBRA_WRAP_MODE::Repeat,
+            WrapMode::MirroredRepeat => LIBRA_WRAP_MODE::MirroredRepeat,
+        }
+    }
+}
+
+/// An enum representing the scaling type of a shader pass.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone)]
+pub enum LIBRA_SCALE_TYPE {
+    /// Scale by the size of the input quad.
+    Input = 0,
+    /// Scale the framebuffer in absolute units.
+    Absolute,
+    /// Scale by the size of the viewport.
+    Viewport,
+    /// Scale by the size of the original input quad.
+    Original,
+    /// Scale by the size of the largest rectangle with the aspect ratio of the original
+    /// input quad that fits in the viewport.
+    ViewportAspect,
+    /// Scale by the size of the other axis, corrected by the aspect ratio of the original
+    /// input quad.
+    OriginalAspect,
+}
+
+impl From<ScaleType> for LIBRA_SCALE_TYPE {
+    fn from(value: ScaleType) -> Self {
+        match value {
+            ScaleType::Input => LIBRA_SCALE_TYPE::Input,
+            ScaleType::Absolute => LIBRA_SCALE_TYPE::Absolute,
+            ScaleType::Viewport => LIBRA_SCALE_TYPE::Viewport,
+            ScaleType::Original => LIBRA_SCALE_TYPE::Original,
+            ScaleType::ViewportAspect => LIBRA_SCALE_TYPE::ViewportAspect,
+            ScaleType::OriginalAspect => LIBRA_SCALE_TYPE::OriginalAspect,
+        }
+    }
+}
+
+/// An enum representing the format of a framebuffer.
+///
+/// The names of the formats follow the names used by `#pragma format`.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone)]
+pub enum LIBRA_IMAGE_FORMAT {
+    /// An unknown format.
+    Unknown = 0,
+    /// `R8_UNORM`.
+    R8Unorm,
+    /// `R8_UINT`.
+    R8Uint,
+    /// `R8_SINT`.
+    R8Sint,
+    /// `R8G8_UNORM`.
+    R8G8Unorm,
+    /// `R8G8_UINT`.
+    R8G8Uint,
+    /// `R8G8_SINT`.
+    R8G8Sint,
+    /// `R8G8B8A8_UNORM`.
+    R8G8B8A8Unorm,
+    /// `R8G8B8A8_UINT`.
+    R8G8B8A8Uint,
+    /// `R8G8B8A8_SINT`.
+    R8G8B8A8Sint,
+    /// `R8G8B8A8_SRGB`.
+    R8G8B8A8Srgb,
+    /// `A2B10G10R10_UNORM_PACK32`.
+    A2B10G10R10UnormPack32,
+    /// `A2B10G10R10_UINT_PACK32`.
+    A2B10G10R10UintPack32,
+    /// `R16_UINT`.
+    R16Uint,
+    /// `R16_SINT`.
+    R16Sint,
+    /// `R16_SFLOAT`.
+    R16Sfloat,
+    /// `R16G16_UINT`.
+    R16G16Uint,
+    /// `R16G16_SINT`.
+    R16G16Sint,
+    /// `R16G16_SFLOAT`.
+    R16G16Sfloat,
+    /// `R16G16B16A16_UINT`.
+    R16G16B16A16Uint,
+    /// `R16G16B16A16_SINT`.
+    R16G16B16A16Sint,
+    /// `R16G16B16A16_SFLOAT`.
+    R16G16B16A16Sfloat,
+    /// `R32_UINT`.
+    R32Uint,
+    /// `R32_SINT`.
+    R32Sint,
+    /// `R32_SFLOAT`.
+    R32Sfloat,
+    /// `R32G32_UINT`.
+    R32G32Uint,
+    /// `R32G32_SINT`.
+    R32G32Sint,
+    /// `R32G32_SFLOAT`.
+    R32G32Sfloat,
+    /// `R32G32B32A32_UINT`.
+    R32G32B32A32Uint,
+    /// `R32G32B32A32_SINT`.
+    R32G32B32A32Sint,
+    /// `R32G32B32A32_SFLOAT`.
+    R32G32B32A32Sfloat,
+}
+
+impl From<ImageFormat> for LIBRA_IMAGE_FORMAT {
+    fn from(value: ImageFormat) -> Self {
+        match value {
+            ImageFormat::Unknown => LIBRA_IMAGE_FORMAT::Unknown,
+            ImageFormat::R8Unorm => LIBRA_IMAGE_FORMAT::R8Unorm,
+            ImageFormat::R8Uint => LIBRA_IMAGE_FORMAT::R8Uint,
+            ImageFormat::R8Sint => LIBRA_IMAGE_FORMAT::R8Sint,
+            ImageFormat::R8G8Unorm => LIBRA_IMAGE_FORMAT::R8G8Unorm,
+            ImageFormat::R8G8Uint => LIBRA_IMAGE_FORMAT::R8G8Uint,
+            ImageFormat::R8G8Sint => LIBRA_IMAGE_FORMAT::R8G8Sint,
+            ImageFormat::R8G8B8A8Unorm => LIBRA_IMAGE_FORMAT::R8G8B8A8Unorm,
+            ImageFormat::R8G8B8A8Uint => LIBRA_IMAGE_FORMAT::R8G8B8A8Uint,
+            ImageFormat::R8G8B8A8Sint => LIBRA_IMAGE_FORMAT::R8G8B8A8Sint,
+            ImageFormat::R8G8B8A8Srgb => LIBRA_IMAGE_FORMAT::R8G8B8A8Srgb,
+            ImageFormat::A2B10G10R10UnormPack32 => LIBRA_IMAGE_FORMAT::A2B10G10R10UnormPack32,
+            ImageFormat::A2B10G10R10UintPack32 => LIBRA_IMAGE_FORMAT::A2B10G10R10UintPack32,
+            ImageFormat::R16Uint => LIBRA_IMAGE_FORMAT::R16Uint,
+            ImageFormat::R16Sint => LIBRA_IMAGE_FORMAT::R16Sint,
+            ImageFormat::R16Sfloat => LIBRA_IMAGE_FORMAT::R16Sfloat,
+            ImageFormat::R16G16Uint => LIBRA_IMAGE_FORMAT::R16G16Uint,
+            ImageFormat::R16G16Sint => LIBRA_IMAGE_FORMAT::R16G16Sint,
+            ImageFormat::R16G16Sfloat => LIBRA_IMAGE_FORMAT::R16G16Sfloat,
+            ImageFormat::R16G16B16A16Uint => LIBRA_IMAGE_FORMAT::R16G16B16A16Uint,
+            ImageFormat::R16G16B16A16Sint => LIBRA_IMAGE_FORMAT::R16G16B16A16Sint,
+            ImageFormat::R16G16B16A16Sfloat => LIBRA_IMAGE_FORMAT::R16G16B16A16Sfloat,
+            ImageFormat::R32Uint => LIBRA_IMAGE_FORMAT::R32Uint,
+            ImageFormat::R32Sint => LIBRA_IMAGE_FORMAT::R32Sint,
+            ImageFormat::R32Sfloat => LIBRA_IMAGE_FORMAT::R32Sfloat,
+            ImageFormat::R32G32Uint => LIBRA_IMAGE_FORMAT::R32G32Uint,
+            ImageFormat::R32G32Sint => LIBRA_IMAGE_FORMAT::R32G32Sint,
+            ImageFormat::R32G32Sfloat => LIBRA_IMAGE_FORMAT::R32G32Sfloat,
+            ImageFormat::R32G32B32A32Uint => LIBRA_IMAGE_FORMAT::R32G32B32A32Uint,
+            ImageFormat::R32G32B32A32Sint => LIBRA_IMAGE_FORMAT::R32G32B32A32Sint,
+            ImageFormat::R32G32B32A32Sfloat => LIBRA_IMAGE_FORMAT::R32G32B32A32Sfloat,
+        }
+    }
+}
+
 #[cfg(feature = "runtime-opengl")]
 use librashader::runtime::gl::FilterChain as FilterChainGL;
 
@@ -74,6 +282,25 @@ use librashader::runtime::gl::FilterChain as FilterChainGL;
 #[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-opengl")))]
 pub type libra_gl_filter_chain_t = Option<NonNull<FilterChainGL>>;
 
+/// An OpenGL filter chain that is being created on a worker thread.
+#[cfg(feature = "runtime-opengl")]
+pub type PendingFilterChainGL = librashader::runtime::LocalPendingFilterChain<
+    Result<FilterChainGL, librashader::runtime::gl::error::FilterChainError>,
+>;
+
+/// A handle to an OpenGL filter chain that is being created on a worker thread.
+#[cfg(feature = "runtime-opengl")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-opengl")))]
+pub type libra_gl_pending_filter_chain_t = Option<NonNull<PendingFilterChainGL>>;
+
+#[cfg(feature = "runtime-opengl")]
+use librashader::runtime::gl::DeviceContext as DeviceContextGL;
+
+/// A handle to OpenGL resources that are shared between filter chains on the same context.
+#[cfg(feature = "runtime-opengl")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-opengl")))]
+pub type libra_gl_device_context_t = Option<NonNull<DeviceContextGL>>;
+
 /// A handle to a Direct3D 11 filter chain.
 #[cfg(any(
     feature = "__cbindgen_internal",
@@ -92,6 +319,43 @@ use librashader::runtime::d3d11::FilterChain as FilterChainD3D11;
 ))]
 pub type libra_d3d11_filter_chain_t = Option<NonNull<FilterChainD3D11>>;
 
+/// A Direct3D 11 filter chain that is being created on a worker thread.
+#[cfg(any(
+    feature = "__cbindgen_internal",
+    all(target_os = "windows", feature = "runtime-d3d11")
+))]
+pub type PendingFilterChainD3D11 = librashader::runtime::LocalPendingFilterChain<
+    Result<FilterChainD3D11, librashader::runtime::d3d11::error::FilterChainError>,
+>;
+
+/// A handle to a Direct3D 11 filter chain that is being created on a worker thread.
+#[cfg_attr(
+    feature = "docsrs",
+    doc(cfg(all(target_os = "windows", feature = "runtime-d3d11")))
+)]
+#[cfg(any(
+    feature = "__cbindgen_internal",
+    all(target_os = "windows", feature = "runtime-d3d11")
+))]
+pub type libra_d3d11_pending_filter_chain_t = Option<NonNull<PendingFilterChainD3D11>>;
+
+#[cfg(any(
+    feature = "__cbindgen_internal",
+    all(target_os = "windows", feature = "runtime-d3d11")
+))]
+use librashader::runtime::d3d11::DeviceContext as DeviceContextD3D11;
+
+/// A handle to Direct3D 11 resources that are shared between filter chains on the same device.
+#[cfg_attr(
+    feature = "docsrs",
+    doc(cfg(all(target_os = "windows", feature = "runtime-d3d11")))
+)]
+#[cfg(any(
+    feature = "__cbindgen_internal",
+    all(target_os = "windows", feature = "runtime-d3d11")
+))]
+pub type libra_d3d11_device_context_t = Option<NonNull<DeviceContextD3D11>>;
+
 #[cfg(any(
     feature = "__cbindgen_internal",
     all(target_os = "windows", feature = "runtime-d3d12")
@@ -104,6 +368,22 @@ use librashader::runtime::d3d12::FilterChain as FilterChainD3D12;
 ))]
 pub type libra_d3d12_filter_chain_t = Option<NonNull<FilterChainD3D12>>;
 
+/// A Direct3D 12 filter chain that is being created on a worker thread.
+#[cfg(any(
+    feature = "__cbindgen_internal",
+    all(target_os = "windows", feature = "runtime-d3d12")
+))]
+pub type PendingFilterChainD3D12 = librashader::runtime::PendingFilterChain<
+    Result<FilterChainD3D12, librashader::runtime::d3d12::error::FilterChainError>,
+>;
+
+/// A handle to a Direct3D 12 filter chain that is being created on a worker thread.
+#[cfg(any(
+    feature = "__cbindgen_internal",
+    all(target_os = "windows", feature = "runtime-d3d12")
+))]
+pub type libra_d3d12_pending_filter_chain_t = Option<NonNull<PendingFilterChainD3D12>>;
+
 /// A handle to a Direct3D 9 filter chain.
 #[cfg(any(
     feature = "__cbindgen_internal",
@@ -122,6 +402,43 @@ use librashader::runtime::d3d9::FilterChain as FilterChainD3D9;
 ))]
 pub type libra_d3d9_filter_chain_t = Option<NonNull<FilterChainD3D9>>;
 
+/// A Direct3D 9 filter chain that is being created on a worker thread.
+#[cfg(any(
+    feature = "__cbindgen_internal",
+    all(target_os = "windows", feature = "runtime-d3d9")
+))]
+pub type PendingFilterChainD3D9 = librashader::runtime::LocalPendingFilterChain<
+    Result<FilterChainD3D9, librashader::runtime::d3d9::error::FilterChainError>,
+>;
+
+/// A handle to a Direct3D 9 filter chain that is being created on a worker thread.
+#[cfg_attr(
+    feature = "docsrs",
+    doc(cfg(all(target_os = "windows", feature = "runtime-d3d9")))
+)]
+#[cfg(any(
+    feature = "__cbindgen_internal",
+    all(target_os = "windows", feature = "runtime-d3d9")
+))]
+pub type libra_d3d9_pending_filter_chain_t = Option<NonNull<PendingFilterChainD3D9>>;
+
+#[cfg(any(
+    feature = "__cbindgen_internal",
+    all(target_os = "windows", feature = "runtime-d3d9")
+))]
+use librashader::runtime::d3d9::DeviceContext as DeviceContextD3D9;
+
+/// A handle to Direct3D 9 resources that are shared between filter chains on the same device.
+#[cfg_attr(
+    feature = "docsrs",
+    doc(cfg(all(target_os = "windows", feature = "runtime-d3d9")))
+)]
+#[cfg(any(
+    feature = "__cbindgen_internal",
+    all(target_os = "windows", feature = "runtime-d3d9")
+))]
+pub type libra_d3d9_device_context_t = Option<NonNull<DeviceContextD3D9>>;
+
 #[cfg(feature = "runtime-vulkan")]
 use librashader::runtime::vk::FilterChain as FilterChainVulkan;
 /// A handle to a Vulkan filter chain.
@@ -129,6 +446,24 @@ use librashader::runtime::vk::FilterChain as FilterChainVulkan;
 #[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-vulkan")))]
 pub type libra_vk_filter_chain_t = Option<NonNull<FilterChainVulkan>>;
 
+/// A Vulkan filter chain that is being created on a worker thread.
+#[cfg(feature = "runtime-vulkan")]
+pub type PendingFilterChainVulkan = librashader::runtime::PendingFilterChain<
+    Result<FilterChainVulkan, librashader::runtime::vk::error::FilterChainError>,
+>;
+
+/// A handle to a Vulkan filter chain that is being created on a worker thread.
+#[cfg(feature = "runtime-vulkan")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-vulkan")))]
+pub type libra_vk_pending_filter_chain_t = Option<NonNull<PendingFilterChainVulkan>>;
+
+#[cfg(feature = "runtime-vulkan")]
+use librashader::runtime::vk::DeviceContext as DeviceContextVulkan;
+/// A handle to Vulkan resources that are shared between filter chains on the same device.
+#[cfg(feature = "runtime-vulkan")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-vulkan")))]
+pub type libra_vk_device_context_t = Option<NonNull<DeviceContextVulkan>>;
+
 #[cfg(all(target_os = "macos", feature = "runtime-metal"))]
 use librashader::runtime::mtl::FilterChain as FilterChainMetal;
 
@@ -147,6 +482,98 @@ use librashader::runtime::mtl::FilterChain as FilterChainMetal;
 ))]
 pub type libra_mtl_filter_chain_t = Option<NonNull<FilterChainMetal>>;
 
+/// A Metal filter chain that is being created on a worker thread.
+#[cfg(any(
+    feature = "__cbindgen_internal",
+    all(
+        target_vendor = "apple",
+        feature = "runtime-metal",
+        feature = "__cbindgen_internal_objc"
+    )
+))]
+pub type PendingFilterChainMetal = librashader::runtime::LocalPendingFilterChain<
+    Result<FilterChainMetal, librashader::runtime::mtl::error::FilterChainError>,
+>;
+
+/// A handle to a Metal filter chain that is being created on a worker thread.
+#[cfg_attr(
+    feature = "docsrs",
+    doc(cfg(all(target_vendor = "apple", feature = "runtime-metal")))
+)]
+#[cfg(any(
+    feature = "__cbindgen_internal",
+    all(
+        target_vendor = "apple",
+        feature = "runtime-metal",
+        feature = "__cbindgen_internal_objc"
+    )
+))]
+pub type libra_mtl_pending_filter_chain_t = Option<NonNull<PendingFilterChainMetal>>;
+
+#[cfg(all(target_os = "macos", feature = "runtime-metal"))]
+use librashader::runtime::mtl::DeviceContext as DeviceContextMetal;
+
+/// A handle to Metal resources that are shared between filter chains on the same device.
+#[cfg_attr(
+    feature = "docsrs",
+    doc(cfg(all(target_vendor = "apple", feature = "runtime-metal")))
+)]
+#[cfg(any(
+    feature = "__cbindgen_internal",
+    all(
+        target_vendor = "apple",
+        feature = "runtime-metal",
+        feature = "__cbindgen_internal_objc"
+    )
+))]
+pub type libra_mtl_device_context_t = Option<NonNull<DeviceContextMetal>>;
+
+#[cfg(any(
+    feature = "runtime-opengl",
+    feature = "runtime-vulkan",
+    feature = "runtime-d3d9",
+    feature = "runtime-d3d11",
+    feature = "runtime-d3d12",
+    feature = "runtime-metal"
+))]
+use librashader::runtime::CancellationToken;
+/// A handle to a token to cancel filter chain creation with.
+#[cfg(any(
+    feature = "runtime-opengl",
+    feature = "runtime-vulkan",
+    feature = "runtime-d3d9",
+    feature = "runtime-d3d11",
+    feature = "runtime-d3d12",
+    feature = "runtime-metal"
+))]
+pub type libra_cancel_token_t = Option<NonNull<CancellationToken>>;
+
+#[cfg(any(
+    feature = "runtime-opengl",
+    feature = "runtime-vulkan",
+    feature = "runtime-d3d9",
+    feature = "runtime-d3d11",
+    feature = "runtime-d3d12",
+    feature = "runtime-metal"
+))]
+use librashader::runtime::LibrashaderInstance;
+/// A handle to a librashader instance, that owns the compile thread pool, the cache directory
+/// and the log callback shared by the filter chains created from it.
+#[cfg(any(
+    feature = "runtime-opengl",
+    feature = "runtime-vulkan",
+    feature = "runtime-d3d9",
+    feature = "runtime-d3d11",
+    feature = "runtime-d3d12",
+    feature = "runtime-metal"
+))]
+pub type libra_instance_handle_t = Option<NonNull<LibrashaderInstance>>;
+
+/// A handle to a shader parameter of a filter chain.
+///
+/// A parameter handle is only valid for the filter chain it was retrieved from.
+pub type libra_param_handle_t = u32;
+
 /// Defines the output origin for a rendered frame.
 #[repr(C)]
 pub struct libra_viewport_t {
@@ -162,13 +589,366 @@ pub struct libra_viewport_t {
     pub height: u32,
 }
 
+/// Information about a shader pass of a filter chain.
+#[repr(C)]
+pub struct libra_pass_info_t {
+    /// The framebuffer format requested by the preset or by the `#pragma format` of the shader.
+    pub requested_format: LIBRA_IMAGE_FORMAT,
+    /// The framebuffer format that the pass renders to.
+    ///
+    /// This differs from the requested format if the device does not support rendering
+    /// to the requested format, and a fallback format was chosen instead.
+    pub format: LIBRA_IMAGE_FORMAT,
+    /// The alias of the pass, or null if the pass has no alias.
+    pub alias: *const c_char,
+    /// The fully qualified path to the shader source of the pass, or null if the pass
+    /// was not loaded from a file.
+    pub path: *const c_char,
+    /// The filtering mode that the pass expects for its input.
+    pub filter: LIBRA_FILTER_MODE,
+    /// The texture addressing (wrap) mode that the pass expects for its input.
+    pub wrap_mode: LIBRA_WRAP_MODE,
+    /// The scaling type of the framebuffer of the pass on the X axis.
+    pub scale_type_x: LIBRA_SCALE_TYPE,
+    /// The scaling type of the framebuffer of the pass on the Y axis.
+    pub scale_type_y: LIBRA_SCALE_TYPE,
+    /// The scaling factor of the framebuffer of the pass on the X axis.
+    pub scale_x: f32,
+    /// The scaling factor of the framebuffer of the pass on the Y axis.
+    pub scale_y: f32,
+}
+
+/// CPU-side statistics of the commands recorded by a filter chain for a single frame.
+#[repr(C)]
+pub struct libra_frame_statistics_t {
+    /// The number of draw calls and compute dispatches.
+    pub draw_calls: u32,
+    /// The number of barriers and resource transitions of pass framebuffers and history frames.
+    ///
+    /// This is always 0 for runtimes that do not have explicit barriers.
+    pub barriers: u32,
+    /// The number of uniform buffer and push constant uploads.
+    pub uploads: u32,
+    /// The number of texture and uniform buffer descriptors written or bound.
+    pub descriptor_writes: u32,
+}
+
+/// A vertex of a mesh to draw the final pass with.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct libra_vertex_t {
+    /// The position of the vertex, where `(0, 0)` is the top-left and `(1, 1)` the bottom-right
+    /// corner of the viewport before the MVP is applied.
+    pub position: [f32; 4],
+    /// The texture coordinate of the vertex, where `(0, 0)` is the top-left corner of the image.
+    pub texcoord: [f32; 2],
+}
+
+/// The widget that settings UIs should show for a shader parameter.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone)]
+pub enum LIBRA_PARAMETER_WIDGET {
+    /// A slider from the minimum to the maximum. This is the default.
+    Slider = 0,
+    /// A checkbox that toggles between the minimum and the maximum.
+    Checkbox,
+    /// A combo box with a label for every step from the minimum to the maximum.
+    Combo,
+}
+
+impl From<&ParameterWidget> for LIBRA_PARAMETER_WIDGET {
+    fn from(value: &ParameterWidget) -> Self {
+        match value {
+            ParameterWidget::Slider => LIBRA_PARAMETER_WIDGET::Slider,
+            ParameterWidget::Checkbox => LIBRA_PARAMETER_WIDGET::Checkbox,
+            ParameterWidget::Combo(_) => LIBRA_PARAMETER_WIDGET::Combo,
+        }
+    }
+}
+
+/// A stage of filter chain creation that a shader pass goes through.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone)]
+pub enum LIBRA_LOAD_STAGE {
+    /// The shader pass was preprocessed and compiled to SPIR-V.
+    Preprocessing = 0,
+    /// The shader pass was compiled for the graphics API of the runtime.
+    Compiling,
+    /// The pipeline of the shader pass was created.
+    Pipeline,
+}
+
+/// A function that is called every time a shader pass completes a stage of filter chain creation.
+///
+/// `pass` is the index of the shader pass, and `total` is the number of shader passes in the preset.
+/// Shader passes may be compiled in parallel, so the function may be called from multiple threads,
+/// and passes may complete a stage out of order.
+pub type libra_progress_callback_t = Option<
+    unsafe extern "C" fn(userdata: *mut c_void, stage: LIBRA_LOAD_STAGE, pass: u32, total: u32),
+>;
+
+/// A callback to report the progress of filter chain creation with.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct libra_progress_t {
+    /// The function to call, or null to not report progress.
+    pub callback: libra_progress_callback_t,
+    /// A pointer that is passed to `callback` as is.
+    pub userdata: *mut c_void,
+}
+
+impl Default for libra_progress_t {
+    fn default() -> Self {
+        libra_progress_t {
+            callback: None,
+            userdata: std::ptr::null_mut(),
+        }
+    }
+}
+
+/// A point in the recording of the commands of a filter chain for a frame.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone)]
+pub enum LIBRA_LATENCY_MARKER {
+    /// The filter chain is about to record the commands for the frame.
+    RecordBegin = 0,
+    /// The filter chain recorded all commands for the frame.
+    RecordEnd,
+}
+
+/// A function that is called before and after the commands of every frame are recorded.
+///
+/// `frame_count` is the frame count that was passed to the filter chain for the frame.
+/// The function is called on the thread that records the frame, at the time of the marker.
+pub type libra_latency_marker_callback_t = Option<
+    unsafe extern "C" fn(userdata: *mut c_void, marker: LIBRA_LATENCY_MARKER, frame_count: usize),
+>;
+
+/// A callback to report latency markers with, to forward to latency SDKs such as
+/// NVIDIA Reflex or AMD Anti-Lag.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct libra_latency_marker_t {
+    /// The function to call, or null to not report latency markers.
+    pub callback: libra_latency_marker_callback_t,
+    /// A pointer that is passed to `callback` as is.
+    pub userdata: *mut c_void,
+}
+
+impl Default for libra_latency_marker_t {
+    fn default() -> Self {
+        libra_latency_marker_t {
+            callback: None,
+            userdata: std::ptr::null_mut(),
+        }
+    }
+}
+
+/// The severity of a log message.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone)]
+pub enum LIBRA_LOG_LEVEL {
+    /// An error.
+    Error = 1,
+    /// A potential problem.
+    Warn,
+    /// Informational messages.
+    Info,
+    /// Messages for debugging.
+    Debug,
+    /// Verbose messages for debugging.
+    Trace,
+}
+
+/// A function that receives the log messages of librashader.
+///
+/// `message` is a null-terminated string that is only valid for the duration of the call.
+/// The function may be called from the compile threads of the instance.
+pub type libra_log_callback_t = Option<
+    unsafe extern "C" fn(userdata: *mut c_void, level: LIBRA_LOG_LEVEL, message: *const c_char),
+>;
+
+/// A callback to receive the log messages of librashader with.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct libra_log_t {
+    /// The function to call, or null to not receive log messages.
+    pub callback: libra_log_callback_t,
+    /// A pointer that is passed to `callback` as is.
+    pub userdata: *mut c_void,
+}
+
+impl Default for libra_log_t {
+    fn default() -> Self {
+        libra_log_t {
+            callback: None,
+            userdata: std::ptr::null_mut(),
+        }
+    }
+}
+
+/// A function that allocates `size` bytes of memory aligned to `align` bytes.
+///
+/// `align` is always a power of two. The function must return null if the allocation failed,
+/// in which case librashader aborts the process.
+pub type libra_alloc_callback_t =
+    Option<unsafe extern "C" fn(userdata: *mut c_void, size: usize, align: usize) -> *mut c_void>;
+
+/// A function that frees memory previously returned by the allocation function.
+///
+/// `size` and `align` are the same as the ones the memory was allocated with.
+pub type libra_free_callback_t = Option<
+    unsafe extern "C" fn(userdata: *mut c_void, ptr: *mut c_void, size: usize, align: usize),
+>;
+
+/// Memory allocation functions to use for the memory librashader allocates.
+///
+/// The functions may be called from multiple threads at once.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct libra_allocator_t {
+    /// The function to allocate memory with.
+    pub alloc: libra_alloc_callback_t,
+    /// The function to free memory with.
+    pub free: libra_free_callback_t,
+    /// A pointer that is passed to `alloc` and `free` as is.
+    pub userdata: *mut c_void,
+}
+
+#[cfg(libra_runtime)]
 pub(crate) trait FromUninit<T>
 where
     Self: Sized,
 {
-    fn from_uninit(value: MaybeUninit<Self>) -> T;
+    fn from_uninit(value: std::mem::MaybeUninit<Self>) -> T;
 }
 
+/// Conversion of a field of a C API options struct into the field of the runtime options.
+#[cfg(libra_runtime)]
+pub(crate) trait IntoOption<T> {
+    fn into_option(self) -> T;
+}
+
+#[cfg(libra_runtime)]
+mod runtime_options {
+    use super::{
+        libra_cancel_token_t, libra_instance_handle_t, libra_latency_marker_t, libra_log_t,
+        libra_progress_t, IntoOption, LIBRA_LATENCY_MARKER, LIBRA_LOAD_STAGE, LIBRA_LOG_LEVEL,
+    };
+    use librashader::runtime::{
+        CancellationToken, LatencyMarker, LatencyMarkerCallback, LibrashaderInstance, LoadStage,
+        LogCallback, LogLevel, ProgressCallback,
+    };
+    use librashader::ScalingKernel;
+    use std::ffi::{c_char, c_void, CStr, CString};
+    use std::path::PathBuf;
+
+    struct Userdata(*mut c_void);
+    // SAFETY: the caller is responsible for the callback being callable from any thread.
+    unsafe impl Send for Userdata {}
+    unsafe impl Sync for Userdata {}
+
+    impl Userdata {
+        fn get(&self) -> *mut c_void {
+            self.0
+        }
+    }
+
+    impl IntoOption<Option<ProgressCallback>> for libra_progress_t {
+        fn into_option(self) -> Option<ProgressCallback> {
+            let callback = self.callback?;
+            let userdata = Userdata(self.userdata);
+
+            Some(ProgressCallback::new(move |progress| {
+                let stage = match progress.stage {
+                    LoadStage::Preprocessing => LIBRA_LOAD_STAGE::Preprocessing,
+                    LoadStage::Compiling => LIBRA_LOAD_STAGE::Compiling,
+                    LoadStage::Pipeline => LIBRA_LOAD_STAGE::Pipeline,
+                };
+
+                unsafe {
+                    callback(
+                        userdata.get(),
+                        stage,
+                        progress.pass as u32,
+                        progress.total as u32,
+                    )
+                }
+            }))
+        }
+    }
+
+    impl IntoOption<Option<LatencyMarkerCallback>> for libra_latency_marker_t {
+        fn into_option(self) -> Option<LatencyMarkerCallback> {
+            let callback = self.callback?;
+            let userdata = Userdata(self.userdata);
+
+            Some(LatencyMarkerCallback::new(move |event| {
+                let marker = match event.marker {
+                    LatencyMarker::RecordBegin => LIBRA_LATENCY_MARKER::RecordBegin,
+                    LatencyMarker::RecordEnd => LIBRA_LATENCY_MARKER::RecordEnd,
+                };
+
+                unsafe { callback(userdata.get(), marker, event.frame_count) }
+            }))
+        }
+    }
+
+    impl IntoOption<Option<CancellationToken>> for libra_cancel_token_t {
+        fn into_option(self) -> Option<CancellationToken> {
+            // SAFETY: the token must be valid until filter chain creation returns.
+            self.map(|token| unsafe { token.as_ref() }.clone())
+        }
+    }
+
+    impl IntoOption<Option<LogCallback>> for libra_log_t {
+        fn into_option(self) -> Option<LogCallback> {
+            let callback = self.callback?;
+            let userdata = Userdata(self.userdata);
+
+            Some(LogCallback::new(move |level, message| {
+                let level = match level {
+                    LogLevel::Error => LIBRA_LOG_LEVEL::Error,
+                    LogLevel::Warn => LIBRA_LOG_LEVEL::Warn,
+                    LogLevel::Info => LIBRA_LOG_LEVEL::Info,
+                    LogLevel::Debug => LIBRA_LOG_LEVEL::Debug,
+                    LogLevel::Trace => LIBRA_LOG_LEVEL::Trace,
+                };
+
+                // interior nul bytes would truncate the message, so they are dropped.
+                let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+                unsafe { callback(userdata.get(), level, message.as_ptr()) }
+            }))
+        }
+    }
+
+    impl IntoOption<Option<LibrashaderInstance>> for libra_instance_handle_t {
+        fn into_option(self) -> Option<LibrashaderInstance> {
+            // SAFETY: the instance must be valid until filter chain creation returns.
+            self.map(|instance| unsafe { instance.as_ref() }.clone())
+        }
+    }
+
+    impl IntoOption<ScalingKernel> for u32 {
+        fn into_option(self) -> ScalingKernel {
+            ScalingKernel::from(self)
+        }
+    }
+
+    impl IntoOption<Option<PathBuf>> for *const c_char {
+        fn into_option(self) -> Option<PathBuf> {
+            if self.is_null() {
+                return None;
+            }
+
+            // SAFETY: the string must be null-terminated and valid for the duration of the call.
+            let path = unsafe { CStr::from_ptr(self) };
+            Some(PathBuf::from(path.to_string_lossy().into_owned()))
+        }
+    }
+}
+
+#[cfg(libra_runtime)]
 macro_rules! config_set_field {
     (@POINTER $options:ident.$field:ident <- $ptr:ident) => {
         $options.$field = unsafe { ::std::ptr::addr_of!((*$ptr).$field).read() };
@@ -176,11 +956,17 @@ macro_rules! config_set_field {
     (@POINTER @NEGATIVE $options:ident.$field:ident <- $ptr:ident) => {
         $options.$field = unsafe { !::std::ptr::addr_of!((*$ptr).$field).read() };
     };
+    (@POINTER @INTO $options:ident.$field:ident <- $ptr:ident) => {
+        $options.$field = $crate::ctypes::IntoOption::into_option(unsafe {
+            ::std::ptr::addr_of!((*$ptr).$field).read()
+        });
+    };
     (@LITERAL $options:ident.$field:ident <- $value:literal) => {
         $options.$field = $value;
     };
 }
 
+#[cfg(libra_runtime)]
 macro_rules! config_version_set {
     // "optimized" version for normal behaviour
     (@ROOT $realver:ident $version:literal => [$($field:ident),+ $(,)?] ($options:ident <- $ptr:ident)) => {
@@ -211,6 +997,14 @@ macro_rules! config_version_set {
         }
     };
 
+    // Allow conversion of variables whose type differs from the option.
+    (@SINGLE $realver:ident $version:literal => [(into $field:ident)] ($options:ident <- $ptr:ident)) => {
+        #[allow(unused_comparisons)]
+        if $realver >= $version {
+            $crate::ctypes::config_set_field!(@POINTER @INTO $options.$field <- $ptr);
+        }
+    };
+
     (@SINGLE $realver:ident $version:literal => [$field:ident] ($options:ident <- $ptr:ident)) => {
         #[allow(unused_comparisons)]
         if $realver >= $version {
@@ -219,6 +1013,59 @@ macro_rules! config_version_set {
     };
 }
 
+/// A field of a versioned configuration struct.
+#[cfg(libra_runtime)]
+pub(crate) struct ConfigField {
+    /// The name of the field.
+    pub name: &'static str,
+    /// The API version the field is declared for.
+    pub version: crate::LIBRASHADER_API_VERSION,
+    /// Whether the field is read from the struct, rather than set to a literal.
+    pub read: bool,
+}
+
+/// A configuration struct declared with `config_struct!`.
+#[cfg(libra_runtime)]
+pub(crate) trait ConfigStruct {
+    /// The name of the struct in the C API.
+    const NAME: &'static str;
+    /// The fields of the struct, in the order they are declared.
+    const FIELDS: &'static [ConfigField];
+
+    /// Whether the field is read from a struct of the given API version.
+    fn reads_field(field: &str, version: crate::LIBRASHADER_API_VERSION) -> bool {
+        Self::FIELDS
+            .iter()
+            .rev()
+            .find(|f| f.name == field && f.version <= version)
+            .is_some_and(|f| f.read)
+    }
+}
+
+#[cfg(libra_runtime)]
+macro_rules! config_field {
+    ($version:literal ($field:ident: $value:literal)) => {
+        $crate::ctypes::ConfigField {
+            name: stringify!($field),
+            version: $version,
+            read: false,
+        }
+    };
+    ($version:literal (!$field:ident)) => {
+        $crate::ctypes::config_field!($version $field)
+    };
+    ($version:literal (into $field:ident)) => {
+        $crate::ctypes::config_field!($version $field)
+    };
+    ($version:literal $field:ident) => {
+        $crate::ctypes::ConfigField {
+            name: stringify!($field),
+            version: $version,
+            read: true,
+        }
+    };
+}
+
 /// Macro to declare a configuration struct, with options to change behaviour based on
 /// API version.
 ///
@@ -229,7 +1076,11 @@ macro_rules! config_version_set {
 ///     * `disable_cache` is defaulted to `true` for API version 0, regardless of `Default::default`
 ///        but is not declared for API 0.
 /// * Declare `use_dynamic_rendering` with normal behaviour, and `disable_cache` for API version 1.
-/// * All fields that are undeclared inherit `Default::default`
+/// * All fields that are undeclared inherit `Default::default`, as do fields declared for a
+///   later API version than the version of the struct passed in.
+///
+/// The declared fields can be queried with `libra_instance_option_supported`, so that frontends
+/// can tell which options a possibly older instance honours.
 ///
 /// ```rust
 /// config_struct! {
@@ -239,6 +1090,7 @@ macro_rules! config_version_set {
 ///     }
 /// }
 /// ```
+#[cfg(libra_runtime)]
 macro_rules! config_struct {
     (impl $rust:ty => $capi:ty {$($version:literal => [$($field:tt),+]);+ $(;)?}) => {
         impl $crate::ctypes::FromUninit<$rust> for $capi {
@@ -253,11 +1105,23 @@ macro_rules! config_struct {
                 options
             }
         }
+
+        impl $crate::ctypes::ConfigStruct for $capi {
+            const NAME: &'static str = stringify!($capi);
+            const FIELDS: &'static [$crate::ctypes::ConfigField] = &[
+                $($($crate::ctypes::config_field!($version $field),)+)+
+            ];
+        }
     }
 }
 
+#[cfg(libra_runtime)]
+pub(crate) use config_field;
+#[cfg(libra_runtime)]
 pub(crate) use config_set_field;
+#[cfg(libra_runtime)]
 pub(crate) use config_struct;
+#[cfg(libra_runtime)]
 pub(crate) use config_version_set;
 
 #[doc(hidden)]
@@ -293,5 +1157,21 @@ mod __cbindgen_opaque_forward_declarations {
         FilterChainVulkan;
         /// Opaque struct for a Metal filter chain.
         FilterChainMetal;
+        /// Opaque struct for a cancellation token.
+        CancellationToken;
+        /// Opaque struct for a librashader instance.
+        LibrashaderInstance;
+        /// Opaque struct for parameter translations.
+        ParameterTranslations;
+        /// Opaque struct for an OpenGL device context.
+        DeviceContextGL;
+        /// Opaque struct for a Direct3D 11 device context.
+        DeviceContextD3D11;
+        /// Opaque struct for a Direct3D 9 device context.
+        DeviceContextD3D9;
+        /// Opaque struct for a Vulkan device context.
+        DeviceContextVulkan;
+        /// Opaque struct for a Metal device context.
+        DeviceContextMetal;
     }
 }
@@ -1,5 +1,7 @@
 //! librashader error C API. (`libra_error_*`).
 use std::any::Any;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::error::Error;
 use std::ffi::{c_char, CString};
 use std::mem::MaybeUninit;
 use std::ptr::NonNull;
@@ -41,6 +43,10 @@ pub enum LibrashaderError {
     #[error("The provided parameter name was invalid.")]
     UnknownShaderParameter(*const c_char),
 
+    /// An invalid shader parameter handle was provided.
+    #[error("The provided parameter handle was invalid.")]
+    UnknownShaderParameterHandle(u32),
+
     /// An error occurred with the OpenGL filter chain.
     #[cfg(feature = "runtime-opengl")]
     #[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-opengl")))]
@@ -89,11 +95,40 @@ pub enum LibrashaderError {
     #[cfg(all(target_vendor = "apple", feature = "runtime-metal"))]
     #[error("There was an error in the Metal filter chain.")]
     MetalFilterError(#[from] librashader::runtime::mtl::error::FilterChainError),
+
+    /// An error occurred when creating a librashader instance.
+    #[cfg(libra_runtime)]
+    #[error("There was an error creating the librashader instance.")]
+    InstanceError(#[from] librashader::runtime::InstanceError),
     /// This error is unreachable.
     #[error("This error is not reachable")]
     Infallible(#[from] std::convert::Infallible),
 }
 
+/// A librashader error object, along with the backtrace of where it was returned from the API.
+#[derive(Debug)]
+pub struct LibrashaderErrorObject {
+    error: LibrashaderError,
+    backtrace: Backtrace,
+}
+
+impl LibrashaderErrorObject {
+    /// The error.
+    pub fn error(&self) -> &LibrashaderError {
+        &self.error
+    }
+
+    /// The backtrace of where the error was returned from the API.
+    ///
+    /// The backtrace is captured when the error is returned across the API boundary, so it
+    /// ends in the API function that failed rather than where the error was first raised.
+    /// Backtraces are only captured if enabled with the `RUST_LIB_BACKTRACE` or
+    /// `RUST_BACKTRACE` environment variables.
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
+}
+
 /// Error codes for librashader error types.
 #[repr(i32)]
 pub enum LIBRA_ERRNO {
@@ -136,22 +171,31 @@ pub unsafe extern "C" fn libra_error_errno(error: libra_error_t) -> LIBRA_ERRNO
         return LIBRA_ERRNO::UNKNOWN_ERROR;
     };
 
-    unsafe { error.as_ref().get_code() }
+    unsafe { error.as_ref().error.get_code() }
 }
 
 /// Function pointer definition for libra_error_print
 pub type PFN_libra_error_print = extern "C" fn(error: libra_error_t) -> i32;
 #[no_mangle]
-/// Print the error message.
+/// Print the error message, followed by the errors that caused it and the backtrace if captured.
 ///
 /// If `error` is null, this function does nothing and returns 1. Otherwise, this function returns 0.
 /// ## Safety
 ///   - `error` must be a valid and initialized instance of `libra_error_t`.
 pub unsafe extern "C" fn libra_error_print(error: libra_error_t) -> i32 {
     let Some(error) = error else { return 1 };
-    unsafe {
-        let error = error.as_ref();
-        println!("{error:?}: {error}");
+    let object = unsafe { error.as_ref() };
+    let error = &object.error;
+    println!("{error:?}: {error}");
+
+    let mut source = error.source();
+    while let Some(error) = source {
+        println!("  caused by: {error}");
+        source = error.source();
+    }
+
+    if object.backtrace.status() == BacktraceStatus::Captured {
+        println!("{}", object.backtrace);
     }
     0
 }
@@ -195,29 +239,91 @@ pub unsafe extern "C" fn libra_error_write(
     out: *mut MaybeUninit<*mut c_char>,
 ) -> i32 {
     let Some(error) = error else { return 1 };
+    let error = unsafe { &error.as_ref().error };
+    unsafe { write_string(format!("{error:?}: {error}"), out) }
+}
+
+/// Function pointer definition for libra_error_write_source
+pub type PFN_libra_error_write_source =
+    extern "C" fn(error: libra_error_t, depth: usize, out: *mut MaybeUninit<*mut c_char>) -> i32;
+#[no_mangle]
+/// Writes the message of an error in the chain of errors that caused the error into `out`.
+///
+/// The error at depth 0 is the error itself, the error at depth 1 is the error that caused it,
+/// and so on. The chain can be iterated by increasing `depth` until this function returns 1.
+///
+/// If `error` is null or there is no error at `depth`, this function does nothing and returns 1.
+/// Otherwise, this function returns 0.
+/// ## Safety
+///   - `error` must be a valid and initialized instance of `libra_error_t`.
+///   - `out` must be a non-null pointer. The resulting string must not be modified,
+///     and must be freed with `libra_error_free_string`.
+pub unsafe extern "C" fn libra_error_write_source(
+    error: libra_error_t,
+    depth: usize,
+    out: *mut MaybeUninit<*mut c_char>,
+) -> i32 {
+    let Some(error) = error else { return 1 };
+    let error: &(dyn Error + 'static) = unsafe { &error.as_ref().error };
+    let Some(error) = std::iter::successors(Some(error), |&error| error.source()).nth(depth) else {
+        return 1;
+    };
+    unsafe { write_string(error.to_string(), out) }
+}
+
+/// Function pointer definition for libra_error_write_backtrace
+pub type PFN_libra_error_write_backtrace =
+    extern "C" fn(error: libra_error_t, out: *mut MaybeUninit<*mut c_char>) -> i32;
+#[no_mangle]
+/// Writes the backtrace of where the error was returned from the API into `out`.
+///
+/// The backtrace is captured when the error is returned across the API boundary, so it ends
+/// in the API function that failed rather than where the error was first raised.
+/// Backtraces are only captured if the `RUST_LIB_BACKTRACE` or `RUST_BACKTRACE` environment
+/// variable is set to `1` when the first error is returned.
+///
+/// If `error` is null or no backtrace was captured, this function does nothing and returns 1.
+/// Otherwise, this function returns 0.
+/// ## Safety
+///   - `error` must be a valid and initialized instance of `libra_error_t`.
+///   - `out` must be a non-null pointer. The resulting string must not be modified,
+///     and must be freed with `libra_error_free_string`.
+pub unsafe extern "C" fn libra_error_write_backtrace(
+    error: libra_error_t,
+    out: *mut MaybeUninit<*mut c_char>,
+) -> i32 {
+    let Some(error) = error else { return 1 };
+    let backtrace = unsafe { &error.as_ref().backtrace };
+    if backtrace.status() != BacktraceStatus::Captured {
+        return 1;
+    }
+    unsafe { write_string(backtrace.to_string(), out) }
+}
+
+/// Write a string allocated for `libra_error_free_string` into `out`.
+unsafe fn write_string(string: String, out: *mut MaybeUninit<*mut c_char>) -> i32 {
     if out.is_null() {
         return 1;
     }
 
-    unsafe {
-        let error = error.as_ref();
-        let Ok(cstring) = CString::new(format!("{error:?}: {error}")) else {
-            return 1;
-        };
+    let Ok(cstring) = CString::new(string) else {
+        return 1;
+    };
 
-        out.write(MaybeUninit::new(cstring.into_raw()))
-    }
+    unsafe { out.write(MaybeUninit::new(cstring.into_raw())) }
     0
 }
 
 /// Function pointer definition for libra_error_free_string
 pub type PFN_libra_error_free_string = extern "C" fn(out: *mut *mut c_char) -> i32;
 #[no_mangle]
-/// Frees an error string previously allocated by `libra_error_write`.
+/// Frees an error string previously allocated by `libra_error_write`, `libra_error_write_source`
+/// or `libra_error_write_backtrace`.
 ///
 /// After freeing, the pointer will be set to null.
 /// ## Safety
-///   - If `libra_error_write` is not null, it must point to a string previously returned by `libra_error_write`.
+///   - If `out` is not null, it must point to a string previously returned by `libra_error_write`,
+///     `libra_error_write_source` or `libra_error_write_backtrace`.
 ///     Attempting to free anything else, including strings or objects from other librashader functions, is immediate
 ///     Undefined Behaviour.
 pub unsafe extern "C" fn libra_error_free_string(out: *mut *mut c_char) -> i32 {
@@ -244,7 +350,10 @@ impl LibrashaderError {
             LibrashaderError::ShaderCompileError(_) | LibrashaderError::ShaderReflectError(_) => {
                 LIBRA_ERRNO::REFLECT_ERROR
             }
-            LibrashaderError::UnknownShaderParameter(_) => LIBRA_ERRNO::SHADER_PARAMETER_ERROR,
+            LibrashaderError::UnknownShaderParameter(_)
+            | LibrashaderError::UnknownShaderParameterHandle(_) => {
+                LIBRA_ERRNO::SHADER_PARAMETER_ERROR
+            }
             #[cfg(feature = "runtime-opengl")]
             LibrashaderError::OpenGlFilterError(_) => LIBRA_ERRNO::RUNTIME_ERROR,
             #[cfg(all(target_os = "windows", feature = "runtime-d3d11"))]
@@ -257,6 +366,8 @@ impl LibrashaderError {
             LibrashaderError::VulkanFilterError(_) => LIBRA_ERRNO::RUNTIME_ERROR,
             #[cfg(all(target_vendor = "apple", feature = "runtime-metal"))]
             LibrashaderError::MetalFilterError(_) => LIBRA_ERRNO::RUNTIME_ERROR,
+            #[cfg(libra_runtime)]
+            LibrashaderError::InstanceError(_) => LIBRA_ERRNO::RUNTIME_ERROR,
             LibrashaderError::Infallible(_) => LIBRA_ERRNO::UNKNOWN_ERROR,
         }
     }
@@ -265,7 +376,10 @@ impl LibrashaderError {
     }
 
     pub(crate) fn export(self) -> libra_error_t {
-        NonNull::new(Box::into_raw(Box::new(self)))
+        NonNull::new(Box::into_raw(Box::new(LibrashaderErrorObject {
+            error: self,
+            backtrace: Backtrace::capture(),
+        })))
     }
 }
 
@@ -0,0 +1,103 @@
+//! librashader instance C API (`libra_instance_create`, `libra_instance_free`).
+
+use crate::ctypes::{config_struct, libra_instance_handle_t, libra_log_t, FromUninit};
+use crate::error::assert_non_null;
+use crate::ffi::extern_fn;
+use crate::LIBRASHADER_API_VERSION;
+use librashader::runtime::{InstanceOptions, LibrashaderInstance};
+use std::ffi::c_char;
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+const _: () = crate::assert_thread_safe::<LibrashaderInstance>();
+
+/// Options for librashader instance creation.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct instance_opt_t {
+    /// The librashader API version.
+    pub version: LIBRASHADER_API_VERSION,
+    /// The number of threads used to compile shaders. If zero, the number of threads is
+    /// chosen by the number of available CPUs.
+    pub compile_threads: usize,
+    /// The stack size in bytes of the compile threads. If zero, the default stack size is used.
+    pub compile_stack_size: usize,
+    /// The directory of the shader and pipeline cache as a null-terminated UTF-8 string,
+    /// or null to use the default cache directory of the platform. The string only needs to
+    /// be valid until instance creation returns.
+    pub cache_dir: *const c_char,
+    /// A callback that receives the log messages of librashader while filter chains are
+    /// created from the instance. If the callback is null, log messages are not reported.
+    pub log_callback: libra_log_t,
+}
+
+impl Default for instance_opt_t {
+    fn default() -> Self {
+        instance_opt_t {
+            version: 0,
+            compile_threads: 0,
+            compile_stack_size: 0,
+            cache_dir: std::ptr::null(),
+            log_callback: libra_log_t::default(),
+        }
+    }
+}
+
+config_struct! {
+    impl InstanceOptions => instance_opt_t {
+        3 => [compile_threads, compile_stack_size, (into cache_dir), (into log_callback)];
+    }
+}
+
+extern_fn! {
+    /// Create a librashader instance, that owns the thread pool that compiles shaders, the
+    /// directory of the shader and pipeline cache, and the callback that receives log messages.
+    ///
+    /// Filter chains are created from the instance by passing it in the `instance` filter chain
+    /// option. Filter chains created without an instance use the global thread pool and the
+    /// default cache directory.
+    ///
+    /// ## Safety
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_instance_handle_t`.
+    /// ## Returns
+    /// - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+    fn libra_instance_create(
+        options: *const MaybeUninit<instance_opt_t>,
+        out: *mut MaybeUninit<libra_instance_handle_t>
+    ) {
+        assert_non_null!(out);
+
+        let options = if options.is_null() {
+            InstanceOptions::default()
+        } else {
+            FromUninit::from_uninit(unsafe { options.read() })
+        };
+
+        let instance = LibrashaderInstance::new(options)?;
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                instance,
+            )))));
+        }
+    }
+}
+
+extern_fn! {
+    /// Free the librashader instance.
+    ///
+    /// If `instance` is null, this function does nothing. The resulting value in `instance` then
+    /// becomes null. Filter chains that were created from the instance stay valid, but the
+    /// instance must not be freed while a filter chain is being created from it.
+    ///
+    /// ## Safety
+    /// - `instance` must be a valid and aligned pointer to a `libra_instance_handle_t`
+    fn libra_instance_free(instance: *mut libra_instance_handle_t) {
+        assert_non_null!(instance);
+        unsafe {
+            let instance_ptr = &mut *instance;
+            let instance = instance_ptr.take();
+            drop(Box::from_raw(instance.unwrap().as_ptr()));
+        }
+    }
+}
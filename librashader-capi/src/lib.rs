@@ -52,6 +52,11 @@
 //! but only if you are certain that the safety invariants are upheld on each call. Failure to check for errors
 //! may result in **undefined behaviour** stemming from failure to uphold safety invariants.
 //!
+//! ## Compute passes
+//! Shader passes written as compute shaders with `#pragma stage compute` are only supported by the Vulkan runtime.
+//! Creating a filter chain for a preset that contains a compute pass with any other runtime fails with an error
+//! with code `REFLECT_ERROR`.
+//!
 //! ## Thread safety
 //!
 //! Except for the metal runtime, it is in general, **safe** to create a filter chain instance from a different thread,
@@ -72,9 +77,21 @@
 
 extern crate alloc;
 
+pub mod allocator;
+#[cfg(any(
+    feature = "runtime-opengl",
+    feature = "runtime-vulkan",
+    feature = "runtime-d3d9",
+    feature = "runtime-d3d11",
+    feature = "runtime-d3d12",
+    feature = "runtime-metal"
+))]
+pub mod cancel;
 pub mod ctypes;
 pub mod error;
 mod ffi;
+#[cfg(libra_runtime)]
+pub mod instance;
 pub mod presets;
 
 #[cfg(feature = "reflect-unstable")]
@@ -82,6 +99,7 @@ pub mod presets;
 pub mod reflect;
 
 pub mod runtime;
+pub mod translations;
 pub mod version;
 pub mod wildcard;
 
@@ -1,11 +1,21 @@
 //! librashader preset C API (`libra_preset_*`).
-use crate::ctypes::{libra_preset_ctx_t, libra_shader_preset_t};
+use crate::ctypes::{
+    libra_preset_ctx_t, libra_preset_translations_t, libra_shader_preset_t, LIBRA_FILTER_MODE,
+    LIBRA_PARAMETER_WIDGET, LIBRA_SCALE_TYPE, LIBRA_WRAP_MODE,
+};
 use crate::error::{assert_non_null, assert_some_ptr, LibrashaderError};
 use crate::ffi::extern_fn;
 use crate::LIBRASHADER_API_VERSION;
-use librashader::presets::{ShaderFeatures, ShaderPreset, WildcardContext};
+use librashader::preprocess::{ParameterWidget, PreprocessError, ShaderParameter};
+use librashader::presets::{
+    ParsePresetError, ParsePresetWarning, PathSandbox, ShaderDefine, ShaderFeatures, ShaderPreset,
+    ShaderPresetPack, WildcardContext,
+};
+use librashader::runtime::{ParameterHandle, RuntimeParameters};
+use std::error::Error;
 use std::ffi::{c_char, CStr, CString};
 use std::mem::MaybeUninit;
+use std::path::{Path, PathBuf};
 use std::ptr::{addr_of_mut, NonNull};
 
 const _: () = crate::assert_thread_safe::<ShaderPreset>();
@@ -38,6 +48,132 @@ pub struct libra_preset_param_t {
     pub step: f32,
 }
 
+/// A list of preset parameter widgets.
+#[repr(C)]
+pub struct libra_preset_param_widget_list_t {
+    /// A pointer to the widgets.
+    pub widgets: *const libra_preset_param_widget_t,
+    /// The number of widgets in the list. This field
+    /// is readonly, and changing it will lead to undefined
+    /// behaviour on free.
+    pub length: u64,
+}
+
+/// The widget that settings UIs should show for a preset parameter.
+#[repr(C)]
+pub struct libra_preset_param_widget_t {
+    /// The name of the parameter.
+    pub name: *const c_char,
+    /// The kind of widget to show.
+    pub widget: LIBRA_PARAMETER_WIDGET,
+    /// The labels for every step from the minimum to the maximum of the parameter,
+    /// if `widget` is `LIBRA_PARAMETER_WIDGET_COMBO`. Otherwise, this is null.
+    pub labels: *const *const c_char,
+    /// The number of labels in `labels`.
+    pub labels_length: u64,
+}
+
+/// A list of preset shader passes.
+#[repr(C)]
+pub struct libra_preset_pass_list_t {
+    /// A pointer to the passes.
+    pub passes: *const libra_preset_pass_t,
+    /// The number of passes in the list. This field
+    /// is readonly, and changing it will lead to undefined
+    /// behaviour on free.
+    pub length: u64,
+}
+
+/// A shader pass of a preset.
+#[repr(C)]
+pub struct libra_preset_pass_t {
+    /// The fully qualified path to the shader source of the pass.
+    pub path: *const c_char,
+    /// The alias of the pass, or null if the pass has no alias.
+    pub alias: *const c_char,
+    /// The filtering mode that the pass expects for its input.
+    pub filter: LIBRA_FILTER_MODE,
+    /// The texture addressing (wrap) mode that the pass expects for its input.
+    pub wrap_mode: LIBRA_WRAP_MODE,
+    /// The number to which to wrap the frame count before passing it to the uniforms.
+    pub frame_count_mod: u32,
+    /// Whether or not the pass expects an sRGB framebuffer output.
+    pub srgb_framebuffer: bool,
+    /// Whether or not the pass expects a float framebuffer output.
+    pub float_framebuffer: bool,
+    /// Whether or not mipmaps are generated for the input texture of the pass.
+    pub mipmap_input: bool,
+    /// The scaling type of the output framebuffer on the X axis.
+    pub scale_type_x: LIBRA_SCALE_TYPE,
+    /// The scaling type of the output framebuffer on the Y axis.
+    pub scale_type_y: LIBRA_SCALE_TYPE,
+    /// The scaling factor of the output framebuffer on the X axis.
+    pub scale_x: f32,
+    /// The scaling factor of the output framebuffer on the Y axis.
+    pub scale_y: f32,
+}
+
+/// A list of preset lookup textures.
+#[repr(C)]
+pub struct libra_preset_texture_list_t {
+    /// A pointer to the textures.
+    pub textures: *const libra_preset_texture_t,
+    /// The number of textures in the list. This field
+    /// is readonly, and changing it will lead to undefined
+    /// behaviour on free.
+    pub length: u64,
+}
+
+/// A list of preset warnings.
+#[repr(C)]
+pub struct libra_preset_warning_list_t {
+    /// A pointer to the warnings.
+    pub warnings: *const libra_preset_warning_t,
+    /// The number of warnings in the list. This field
+    /// is readonly, and changing it will lead to undefined
+    /// behaviour on free.
+    pub length: u64,
+}
+
+/// A problem with a preset that was ignored when parsing.
+#[repr(C)]
+pub struct libra_preset_warning_t {
+    /// A message describing the warning, suitable to show to users.
+    pub message: *const c_char,
+    /// The fully qualified path to the preset file with the problem.
+    pub path: *const c_char,
+    /// The key that was not recognized.
+    pub key: *const c_char,
+    /// The line of the key in the preset file, starting at 1.
+    pub line: u32,
+    /// The column of the key in the preset file, starting at 1.
+    pub column: u32,
+}
+
+/// A stable content hash of a preset and all of its resources.
+#[repr(C)]
+pub struct libra_preset_hash_t {
+    /// The bytes of the hash.
+    pub hash: [u8; 32],
+}
+
+/// A lookup texture of a preset.
+#[repr(C)]
+pub struct libra_preset_texture_t {
+    /// The name of the texture.
+    pub name: *const c_char,
+    /// The fully qualified path to the texture.
+    pub path: *const c_char,
+    /// The filtering mode to use when sampling the texture.
+    pub filter_mode: LIBRA_FILTER_MODE,
+    /// The texture addressing (wrap) mode to use when sampling the texture.
+    pub wrap_mode: LIBRA_WRAP_MODE,
+    /// Whether or not mipmaps are generated for the texture.
+    pub mipmap: bool,
+    /// Whether or not the texture is sampled as sRGB encoded data.
+    pub srgb: bool,
+}
+
 /// Options struct for loading shader presets.
 ///
 /// Using this struct with `libra_preset_create_with_options` is the only way to
@@ -59,6 +195,30 @@ pub struct libra_preset_opt_t {
     ///
     /// This is only supported on API 2 and above, otherwise this has no effect.
     pub frametime_uniforms: bool,
+    /// Substitute a placeholder texture for lookup textures that are missing or fail to load,
+    /// instead of failing to create the filter chain.
+    ///
+    /// A warning is logged for every texture that is substituted.
+    ///
+    /// This is only supported on API 3 and above, otherwise this has no effect.
+    pub fallback_textures: bool,
+    /// An array of paths to root directories that every file referenced by the preset,
+    /// including shaders, includes, textures and referenced presets, must reside in.
+    ///
+    /// If this is null, files referenced by the preset are not restricted. This should be
+    /// set when loading presets from untrusted sources.
+    ///
+    /// This is only supported on API 3 and above, otherwise this has no effect.
+    pub sandbox_roots: *const *const c_char,
+    /// The number of paths in `sandbox_roots`.
+    ///
+    /// This is only supported on API 3 and above, otherwise this has no effect.
+    pub sandbox_roots_length: u64,
+    /// Fail to load presets that contain unknown keys, instead of collecting them
+    /// as warnings that can be retrieved with `libra_preset_get_warnings`.
+    ///
+    /// This is only supported on API 3 and above, otherwise this has no effect.
+    pub strict: bool,
 }
 
 extern_fn! {
@@ -166,9 +326,21 @@ extern_fn! {
         let filename = unsafe { CStr::from_ptr(filename) };
         let filename = filename.to_str()?;
 
+        let mut context = if context.is_null() {
+            Box::new(WildcardContext::new())
+        } else {
+            unsafe {
+                let context_ptr = &mut *context;
+                let context = context_ptr.take();
+                Box::from_raw(context.unwrap().as_ptr())
+            }
+        };
+
+        context.add_path_defaults(filename);
+
         // This control flow is like this because the wrapper makes it hard to return early..
         if options.is_null() {
-            let preset = ShaderPreset::try_parse(filename, ShaderFeatures::NONE)?;
+            let preset = ShaderPreset::try_parse_with_context(filename, ShaderFeatures::NONE, *context)?;
             unsafe {
                 out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
                     preset,
@@ -181,18 +353,6 @@ extern_fn! {
 
             let api_version = unsafe { addr_of_mut!((*opt_ptr).version).read() };
 
-            let mut context = if context.is_null() {
-                Box::new(WildcardContext::new())
-            } else {
-                unsafe {
-                    let context_ptr = &mut *context;
-                    let context = context_ptr.take();
-                    Box::from_raw(context.unwrap().as_ptr())
-                }
-            };
-
-            context.add_path_defaults(filename);
-
             let mut flags = ShaderFeatures::NONE;
 
             // Original Aspect and Frametime Uniforms are an API 2 feature.
@@ -209,7 +369,51 @@ extern_fn! {
                 }
             }
 
-            let preset = ShaderPreset::try_parse(filename, flags)?;
+            let mut sandbox = None;
+
+            // Fallback textures and sandboxing are an API 3 feature.
+            if api_version >= 3 {
+                let fallback_textures = unsafe { addr_of_mut!((*opt_ptr).fallback_textures).read() };
+                let sandbox_roots = unsafe { addr_of_mut!((*opt_ptr).sandbox_roots).read() };
+                let sandbox_roots_length = unsafe { addr_of_mut!((*opt_ptr).sandbox_roots_length).read() };
+                let strict = unsafe { addr_of_mut!((*opt_ptr).strict).read() };
+
+                if fallback_textures {
+                    flags |= ShaderFeatures::FALLBACK_TEXTURES;
+                }
+
+                if strict {
+                    flags |= ShaderFeatures::STRICT_PRESET;
+                }
+
+                if !sandbox_roots.is_null() {
+                    // SAFETY: sandbox_roots is not null, and must point to sandbox_roots_length paths.
+                    let sandbox_roots = unsafe {
+                        std::slice::from_raw_parts(sandbox_roots, sandbox_roots_length as usize)
+                    };
+
+                    let mut roots = Vec::with_capacity(sandbox_roots.len());
+                    for &root in sandbox_roots {
+                        assert_non_null!(root);
+                        let root = unsafe { CStr::from_ptr(root) };
+                        let root = Path::new(root.to_str()?);
+                        let root = root
+                            .canonicalize()
+                            .map_err(|e| ParsePresetError::IOError(root.to_path_buf(), e))?;
+                        roots.push(root);
+                    }
+
+                    sandbox = Some(
+                        PathSandbox::new(&roots)
+                            .map_err(|e| ParsePresetError::IOError(PathBuf::new(), e))?,
+                    );
+                }
+            }
+
+            let preset = match sandbox {
+                Some(sandbox) => ShaderPreset::try_parse_with_sandbox(filename, flags, *context, sandbox)?,
+                None => ShaderPreset::try_parse_with_context(filename, flags, *context)?,
+            };
             unsafe {
                 out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
                     preset,
@@ -237,6 +441,38 @@ extern_fn! {
     }
 }
 
+extern_fn! {
+    /// Create a copy of the preset.
+    ///
+    /// The copy is a deep copy that shares no state with the original preset, and must be
+    /// freed separately with `libra_preset_free`. Since creating a filter chain invalidates
+    /// the preset it is created from, a copy can be passed to filter chain creation to keep
+    /// using the original preset afterwards.
+    ///
+    /// Keeping an unmodified copy also allows parameter edits to be reset to the values
+    /// in the preset, by freeing the edited preset and cloning the unmodified copy again.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+    /// - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_shader_preset_t`.
+    /// ## Returns
+    ///  - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+    fn libra_preset_clone(
+        preset: *const libra_shader_preset_t,
+        out: *mut MaybeUninit<libra_shader_preset_t>
+    ) |preset| {
+        assert_some_ptr!(preset);
+        assert_non_null!(out);
+
+        let preset = preset.clone();
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                preset,
+            )))))
+        }
+    }
+}
+
 extern_fn! {
     /// Set the value of the parameter in the preset.
     ///
@@ -284,6 +520,47 @@ extern_fn! {
     }
 }
 
+extern_fn! {
+    /// Inject a preprocessor define into the source of a shader pass of the preset.
+    ///
+    /// The define is inserted as `#define <name> <value>` after the version header of the
+    /// pass when a filter chain is created from the preset, replacing any previous define
+    /// with the same name in that pass. Passes with different defines are compiled and
+    /// cached separately.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+    /// - `name` must be null or a valid and aligned pointer to a string.
+    /// - `value` may be null, in which case the define has no replacement text. Otherwise,
+    ///    it must be a valid and aligned pointer to a string.
+    fn libra_preset_set_pass_define(
+        preset: *mut libra_shader_preset_t,
+        pass: u64,
+        name: *const c_char,
+        value: *const c_char
+    ) |name|; mut |preset| {
+        let name = unsafe { CStr::from_ptr(name) };
+        let name = name.to_str()?;
+        let value = if value.is_null() {
+            ""
+        } else {
+            unsafe { CStr::from_ptr(value) }.to_str()?
+        };
+
+        assert_some_ptr!(mut preset);
+        let define = ShaderDefine::new(name, value);
+        if !define.is_valid() {
+            return Err(LibrashaderError::InvalidParameter("name"));
+        }
+
+        let Some(pass) = preset.passes.get_mut(pass as usize) else {
+            return Err(LibrashaderError::InvalidParameter("pass"));
+        };
+
+        pass.meta.set_define(define);
+    }
+}
+
 extern_fn! {
     /// Pretty print the shader preset.
     ///
@@ -298,6 +575,12 @@ extern_fn! {
 extern_fn! {
     /// Get a list of runtime parameters.
     ///
+    /// The parameters are read from the shader sources of the preset, so this does not require a filter
+    /// chain or graphics device to be created. Parameters declared in more than one pass are only listed once.
+    ///
+    /// Parameters are listed in the order of the passes that declare them, and in declaration
+    /// order within a pass. The order is stable for the same preset and shader sources.
+    ///
     /// ## Safety
     /// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
     /// - `out` must be an aligned pointer to a `libra_preset_parameter_list_t`.
@@ -314,34 +597,103 @@ extern_fn! {
         assert_non_null!(out);
 
         let iter = librashader::presets::get_parameter_meta(preset)?;
-        let mut values = Vec::new();
-        for param in iter {
-            let name = CString::new(param.id.to_string())
-            .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
-            let description = CString::new(param.description)
-            .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
-            values.push(libra_preset_param_t {
-                name: name.into_raw().cast_const(),
-                description: description.into_raw().cast_const(),
-                initial: param.initial,
-                minimum: param.minimum,
-                maximum: param.maximum,
-                step: param.step
-            })
+        let list = param_list_from_iter(iter)?;
+
+        unsafe {
+            out.write(MaybeUninit::new(list));
         }
+    }
+}
 
-        let values = values.into_boxed_slice();
-        let (parts, len) = crate::ffi::boxed_slice_into_raw_parts(values);
+extern_fn! {
+    /// Get a list of runtime parameters, with descriptions replaced by their translations.
+    ///
+    /// Parameters without a translation in `translations` keep the description declared in
+    /// the shader. The output struct must be freed with `libra_preset_free_runtime_params`.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+    /// - `translations` must be null or a valid and aligned pointer to a `libra_preset_translations_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_parameter_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    /// - It is safe to call `libra_preset_get_runtime_params_localized` multiple times, however
+    ///   the output struct must only be freed once per call.
+    fn libra_preset_get_runtime_params_localized(
+        preset: *const libra_shader_preset_t,
+        translations: *const libra_preset_translations_t,
+        out: *mut MaybeUninit<libra_preset_param_list_t>
+    ) |preset, translations| {
+        assert_some_ptr!(preset);
+        assert_some_ptr!(translations);
+        assert_non_null!(out);
+
+        let iter = librashader::presets::get_parameter_meta_localized(preset, translations)?;
+        let list = param_list_from_iter(iter)?;
 
         unsafe {
-            out.write(MaybeUninit::new(libra_preset_param_list_t {
-                parameters: parts,
-                length: len as u64,
-            }));
+            out.write(MaybeUninit::new(list));
         }
     }
 }
 
+/// Get the runtime parameters of a filter chain, in the order of their handles.
+///
+/// Parameters that are set by the preset but not declared by any shader pass have no
+/// description, and are bounded to their initial value.
+pub(crate) fn runtime_param_list(
+    parameters: &RuntimeParameters,
+) -> Result<libra_preset_param_list_t, LibrashaderError> {
+    let iter = (0..parameters.len() as u32).filter_map(|handle| {
+        let handle = ParameterHandle::from(handle);
+        if let Some(meta) = parameters.parameter_meta(handle) {
+            return Some(meta.clone());
+        }
+
+        let name = parameters.parameter_name(handle)?;
+        let initial = parameters.initial_value(name)?;
+        Some(ShaderParameter {
+            id: name.into(),
+            description: String::new(),
+            initial,
+            minimum: initial,
+            maximum: initial,
+            step: 0.0,
+            widget: ParameterWidget::default(),
+        })
+    });
+    param_list_from_iter(iter)
+}
+
+fn param_list_from_iter(
+    iter: impl Iterator<Item = ShaderParameter>,
+) -> Result<libra_preset_param_list_t, LibrashaderError> {
+    let mut values = Vec::new();
+    for param in iter {
+        let name = CString::new(param.id.to_string())
+            .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+        let description = CString::new(param.description)
+            .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+        values.push(libra_preset_param_t {
+            name: name.into_raw().cast_const(),
+            description: description.into_raw().cast_const(),
+            initial: param.initial,
+            minimum: param.minimum,
+            maximum: param.maximum,
+            step: param.step,
+        })
+    }
+
+    let values = values.into_boxed_slice();
+    let (parts, len) = crate::ffi::boxed_slice_into_raw_parts(values);
+
+    Ok(libra_preset_param_list_t {
+        parameters: parts,
+        length: len as u64,
+    })
+}
+
 extern_fn! {
     /// Free the runtime parameters.
     ///
@@ -378,3 +730,385 @@ extern_fn! {
         }
     }
 }
+
+extern_fn! {
+    /// Get a list of the widgets that settings UIs should show for the runtime parameters.
+    ///
+    /// Widgets are hinted after the step of a `#pragma parameter` declaration, and are listed
+    /// in the same order as the parameters returned by `libra_preset_get_runtime_params`.
+    /// Parameters without a hint are shown as sliders.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_param_widget_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    /// - It is safe to call `libra_preset_get_runtime_param_widgets` multiple times, however
+    ///   the output struct must only be freed once per call.
+    fn libra_preset_get_runtime_param_widgets(
+        preset: *const libra_shader_preset_t,
+        out: *mut MaybeUninit<libra_preset_param_widget_list_t>
+    ) |preset| {
+        assert_some_ptr!(preset);
+        assert_non_null!(out);
+
+        let iter = librashader::presets::get_parameter_meta(preset)?;
+        let mut values = Vec::new();
+        for param in iter {
+            let name = CString::new(param.id.to_string())
+                .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+
+            let (labels, labels_length) = match &param.widget {
+                ParameterWidget::Combo(labels) => {
+                    let mut strings = Vec::with_capacity(labels.len());
+                    for label in labels {
+                        let label = CString::new(label.as_str())
+                            .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+                        strings.push(label.into_raw().cast_const());
+                    }
+                    let (labels, len) = crate::ffi::boxed_slice_into_raw_parts(strings.into_boxed_slice());
+                    (labels.cast_const(), len as u64)
+                }
+                _ => (std::ptr::null(), 0),
+            };
+
+            values.push(libra_preset_param_widget_t {
+                name: name.into_raw().cast_const(),
+                widget: LIBRA_PARAMETER_WIDGET::from(&param.widget),
+                labels,
+                labels_length,
+            })
+        }
+
+        let values = values.into_boxed_slice();
+        let (parts, len) = crate::ffi::boxed_slice_into_raw_parts(values);
+
+        unsafe {
+            out.write(MaybeUninit::new(libra_preset_param_widget_list_t {
+                widgets: parts,
+                length: len as u64,
+            }));
+        }
+    }
+}
+
+extern_fn! {
+    /// Free a list of parameter widgets.
+    ///
+    /// Like `libra_preset_free_runtime_params`, `libra_preset_free_runtime_param_widgets` takes the struct directly.
+    ///
+    /// ## Safety
+    /// - Any pointers rooted at `widgets` becomes invalid after this function returns,
+    ///   including any strings accessible via the input `libra_preset_param_widget_list_t`.
+    ///   The caller must ensure that there are no live pointers, aliased or unaliased,
+    ///   to data accessible via the input `libra_preset_param_widget_list_t`.
+    ///
+    /// - If any struct fields of the input `libra_preset_param_widget_list_t` was modified from
+    ///   their values given after `libra_preset_get_runtime_param_widgets`, this may result
+    ///   in undefined behaviour.
+    fn libra_preset_free_runtime_param_widgets(widgets: libra_preset_param_widget_list_t) {
+        unsafe {
+            let values =
+                crate::ffi::boxed_slice_from_raw_parts(widgets.widgets.cast_mut(),
+                widgets.length as usize).into_vec();
+
+            for value in values {
+                drop(CString::from_raw(value.name.cast_mut()));
+                if value.labels.is_null() {
+                    continue;
+                }
+
+                let labels = crate::ffi::boxed_slice_from_raw_parts(value.labels.cast_mut(),
+                    value.labels_length as usize).into_vec();
+                for label in labels {
+                    drop(CString::from_raw(label.cast_mut()));
+                }
+            }
+        }
+    }
+}
+
+extern_fn! {
+    /// Get a list of the shader passes in the preset.
+    ///
+    /// This can be used to display the contents of a preset before creating a filter chain.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_pass_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    /// - It is safe to call `libra_preset_get_passes` multiple times, however
+    ///   the output struct must only be freed once per call.
+    fn libra_preset_get_passes(
+        preset: *const libra_shader_preset_t,
+        out: *mut MaybeUninit<libra_preset_pass_list_t>
+    ) |preset| {
+        assert_some_ptr!(preset);
+        assert_non_null!(out);
+
+        let mut values = Vec::new();
+        for pass in preset.passes.iter() {
+            let path = CString::new(pass.path.to_string_lossy().into_owned())
+                .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+            let alias = match &pass.meta.alias {
+                Some(alias) => CString::new(alias.to_string())
+                    .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?
+                    .into_raw()
+                    .cast_const(),
+                None => std::ptr::null(),
+            };
+
+            let scaling = &pass.meta.scaling;
+            values.push(libra_preset_pass_t {
+                path: path.into_raw().cast_const(),
+                alias,
+                filter: pass.meta.filter.into(),
+                wrap_mode: pass.meta.wrap_mode.into(),
+                frame_count_mod: pass.meta.frame_count_mod,
+                srgb_framebuffer: pass.meta.srgb_framebuffer,
+                float_framebuffer: pass.meta.float_framebuffer,
+                mipmap_input: pass.meta.mipmap_input,
+                scale_type_x: scaling.x.scale_type.into(),
+                scale_type_y: scaling.y.scale_type.into(),
+                scale_x: scaling.x.factor.into(),
+                scale_y: scaling.y.factor.into(),
+            })
+        }
+
+        let values = values.into_boxed_slice();
+        let (parts, len) = crate::ffi::boxed_slice_into_raw_parts(values);
+
+        unsafe {
+            out.write(MaybeUninit::new(libra_preset_pass_list_t {
+                passes: parts,
+                length: len as u64,
+            }));
+        }
+    }
+}
+
+extern_fn! {
+    /// Free a list of shader passes.
+    ///
+    /// Like `libra_preset_free_runtime_params`, `libra_preset_free_passes` takes the struct directly.
+    ///
+    /// ## Safety
+    /// - Any pointers rooted at `passes` becomes invalid after this function returns,
+    ///   including any strings accessible via the input `libra_preset_pass_list_t`.
+    ///   The caller must ensure that there are no live pointers, aliased or unaliased,
+    ///   to data accessible via the input `libra_preset_pass_list_t`.
+    ///
+    /// - If any struct fields of the input `libra_preset_pass_list_t` was modified from
+    ///   their values given after `libra_preset_get_passes`, this may result
+    ///   in undefined behaviour.
+    fn libra_preset_free_passes(passes: libra_preset_pass_list_t) {
+        unsafe {
+            let values =
+                crate::ffi::boxed_slice_from_raw_parts(passes.passes.cast_mut(),
+                passes.length as usize).into_vec();
+
+            for value in values {
+                drop(CString::from_raw(value.path.cast_mut()));
+                if !value.alias.is_null() {
+                    drop(CString::from_raw(value.alias.cast_mut()));
+                }
+            }
+        }
+    }
+}
+
+extern_fn! {
+    /// Get a list of the lookup textures in the preset.
+    ///
+    /// This can be used to check that every texture exists before creating a filter chain.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_texture_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    /// - It is safe to call `libra_preset_get_textures` multiple times, however
+    ///   the output struct must only be freed once per call.
+    fn libra_preset_get_textures(
+        preset: *const libra_shader_preset_t,
+        out: *mut MaybeUninit<libra_preset_texture_list_t>
+    ) |preset| {
+        assert_some_ptr!(preset);
+        assert_non_null!(out);
+
+        let mut values = Vec::new();
+        for texture in preset.textures.iter() {
+            let name = CString::new(texture.meta.name.to_string())
+                .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+            let path = CString::new(texture.path.to_string_lossy().into_owned())
+                .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+
+            values.push(libra_preset_texture_t {
+                name: name.into_raw().cast_const(),
+                path: path.into_raw().cast_const(),
+                filter_mode: texture.meta.filter_mode.into(),
+                wrap_mode: texture.meta.wrap_mode.into(),
+                mipmap: texture.meta.mipmap,
+                srgb: texture.meta.srgb,
+            })
+        }
+
+        let values = values.into_boxed_slice();
+        let (parts, len) = crate::ffi::boxed_slice_into_raw_parts(values);
+
+        unsafe {
+            out.write(MaybeUninit::new(libra_preset_texture_list_t {
+                textures: parts,
+                length: len as u64,
+            }));
+        }
+    }
+}
+
+extern_fn! {
+    /// Free a list of lookup textures.
+    ///
+    /// Like `libra_preset_free_runtime_params`, `libra_preset_free_textures` takes the struct directly.
+    ///
+    /// ## Safety
+    /// - Any pointers rooted at `textures` becomes invalid after this function returns,
+    ///   including any strings accessible via the input `libra_preset_texture_list_t`.
+    ///   The caller must ensure that there are no live pointers, aliased or unaliased,
+    ///   to data accessible via the input `libra_preset_texture_list_t`.
+    ///
+    /// - If any struct fields of the input `libra_preset_texture_list_t` was modified from
+    ///   their values given after `libra_preset_get_textures`, this may result
+    ///   in undefined behaviour.
+    fn libra_preset_free_textures(textures: libra_preset_texture_list_t) {
+        unsafe {
+            let values =
+                crate::ffi::boxed_slice_from_raw_parts(textures.textures.cast_mut(),
+                textures.length as usize).into_vec();
+
+            for value in values {
+                drop(CString::from_raw(value.name.cast_mut()));
+                drop(CString::from_raw(value.path.cast_mut()));
+            }
+        }
+    }
+}
+
+extern_fn! {
+    /// Get a list of the problems that were ignored when parsing the preset, such as
+    /// unknown keys.
+    ///
+    /// Frontends can show these to users to help them find typos in their presets.
+    /// Presets loaded with the `strict` preset option fail to load instead, so the list is always empty.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_warning_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    /// - It is safe to call `libra_preset_get_warnings` multiple times, however
+    ///   the output struct must only be freed once per call.
+    fn libra_preset_get_warnings(
+        preset: *const libra_shader_preset_t,
+        out: *mut MaybeUninit<libra_preset_warning_list_t>
+    ) |preset| {
+        assert_some_ptr!(preset);
+        assert_non_null!(out);
+
+        let mut values = Vec::new();
+        for warning in preset.warnings.iter() {
+            let ParsePresetWarning::UnknownKey { path, key, row, col } = warning;
+            let message = CString::new(warning.to_string())
+                .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+            let path = CString::new(path.to_string_lossy().into_owned())
+                .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+            let key = CString::new(key.as_str())
+                .map_err(|err| LibrashaderError::UnknownError(Box::new(err)))?;
+
+            values.push(libra_preset_warning_t {
+                message: message.into_raw().cast_const(),
+                path: path.into_raw().cast_const(),
+                key: key.into_raw().cast_const(),
+                line: *row,
+                column: *col as u32,
+            })
+        }
+
+        let values = values.into_boxed_slice();
+        let (parts, len) = crate::ffi::boxed_slice_into_raw_parts(values);
+
+        unsafe {
+            out.write(MaybeUninit::new(libra_preset_warning_list_t {
+                warnings: parts,
+                length: len as u64,
+            }));
+        }
+    }
+}
+
+extern_fn! {
+    /// Free a list of preset warnings.
+    ///
+    /// Like `libra_preset_free_runtime_params`, `libra_preset_free_warnings` takes the struct directly.
+    ///
+    /// ## Safety
+    /// - Any pointers rooted at `warnings` becomes invalid after this function returns,
+    ///   including any strings accessible via the input `libra_preset_warning_list_t`.
+    ///   The caller must ensure that there are no live pointers, aliased or unaliased,
+    ///   to data accessible via the input `libra_preset_warning_list_t`.
+    ///
+    /// - If any struct fields of the input `libra_preset_warning_list_t` was modified from
+    ///   their values given after `libra_preset_get_warnings`, this may result
+    ///   in undefined behaviour.
+    fn libra_preset_free_warnings(warnings: libra_preset_warning_list_t) {
+        unsafe {
+            let values =
+                crate::ffi::boxed_slice_from_raw_parts(warnings.warnings.cast_mut(),
+                warnings.length as usize).into_vec();
+
+            for value in values {
+                drop(CString::from_raw(value.message.cast_mut()));
+                drop(CString::from_raw(value.path.cast_mut()));
+                drop(CString::from_raw(value.key.cast_mut()));
+            }
+        }
+    }
+}
+
+extern_fn! {
+    /// Compute a stable content hash of the preset and all of its resources.
+    ///
+    /// The hash covers the preprocessed source of every shader pass, including the contents of every
+    /// included file, the decoded pixels of every texture, and the configuration of every pass, texture
+    /// and parameter. Paths are not hashed, so the same preset installed in different locations has
+    /// the same hash. The hash can be used as a cache key or to deduplicate and verify presets.
+    ///
+    /// Every resource of the preset is loaded from disk to compute the hash.
+    ///
+    /// ## Safety
+    /// - `preset` must be null or a valid and aligned pointer to a `libra_shader_preset_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_hash_t`.
+    fn libra_preset_get_content_hash(
+        preset: *const libra_shader_preset_t,
+        out: *mut MaybeUninit<libra_preset_hash_t>
+    ) |preset| {
+        assert_some_ptr!(preset);
+        assert_non_null!(out);
+
+        let pack = ShaderPresetPack::load_from_preset::<Box<dyn Error + Send + Sync>>(preset.clone())
+            .map_err(|err| match err.downcast::<PreprocessError>() {
+                Ok(err) => LibrashaderError::PreprocessError(*err),
+                Err(err) => LibrashaderError::UnknownError(Box::new(err)),
+            })?;
+
+        unsafe {
+            out.write(MaybeUninit::new(libra_preset_hash_t {
+                hash: pack.content_hash(),
+            }))
+        }
+    }
+}
@@ -1,21 +1,28 @@
 use crate::ctypes::{
-    config_struct, libra_d3d12_filter_chain_t, libra_shader_preset_t, libra_viewport_t, FromUninit,
+    config_struct, libra_cancel_token_t, libra_d3d12_filter_chain_t,
+    libra_d3d12_pending_filter_chain_t, libra_frame_statistics_t, libra_instance_handle_t,
+    libra_latency_marker_t, libra_param_handle_t, libra_pass_info_t, libra_progress_t,
+    libra_shader_preset_t, libra_vertex_t, libra_viewport_t, FromUninit, IntoOption,
 };
 use crate::error::{assert_non_null, assert_some_ptr, LibrashaderError};
 use crate::ffi::extern_fn;
-use std::ffi::c_char;
+use crate::presets::libra_preset_param_list_t;
+use crate::runtime::deferred::DeferredQueue;
 use std::ffi::CStr;
+use std::ffi::{c_char, c_void};
 use std::mem::{ManuallyDrop, MaybeUninit};
 use std::ptr::NonNull;
 use std::slice;
 use windows::Win32::Graphics::Direct3D12::{
-    ID3D12Device, ID3D12GraphicsCommandList, ID3D12Resource, D3D12_CPU_DESCRIPTOR_HANDLE,
+    ID3D12DescriptorHeap, ID3D12Device, ID3D12Fence, ID3D12GraphicsCommandList, ID3D12QueryHeap,
+    ID3D12Resource, D3D12_CPU_DESCRIPTOR_HANDLE,
 };
 use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT;
 
 use crate::LIBRASHADER_API_VERSION;
 use librashader::runtime::d3d12::{
-    D3D12InputImage, D3D12OutputView, FilterChain, FilterChainOptions, FrameOptions,
+    D3D12DescriptorHeapRange, D3D12InputImage, D3D12MipmapHandoff, D3D12MipmapHandoffCallback,
+    D3D12OutputView, FilterChain, FilterChainOptions, FrameOptions,
 };
 use librashader::runtime::{FilterChainParameters, Size, Viewport};
 
@@ -75,6 +82,116 @@ pub struct libra_output_image_d3d12_t {
     pub height: u32,
 }
 
+/// A range of descriptors on a shader visible descriptor heap owned by the caller.
+#[repr(C)]
+#[derive(Default, Debug, Clone)]
+pub struct libra_descriptor_heap_range_d3d12_t {
+    /// A pointer to the shader visible `ID3D12DescriptorHeap`, or null if the filter chain
+    /// should create its own heap.
+    pub heap: Option<ManuallyDrop<ID3D12DescriptorHeap>>,
+    /// The index of the first descriptor of the range in the heap.
+    pub offset: u32,
+    /// The number of descriptors in the range.
+    pub count: u32,
+}
+
+impl IntoOption<Option<D3D12DescriptorHeapRange>> for libra_descriptor_heap_range_d3d12_t {
+    fn into_option(self) -> Option<D3D12DescriptorHeapRange> {
+        let heap = self.heap?;
+        Some(D3D12DescriptorHeapRange {
+            heap: ID3D12DescriptorHeap::clone(&heap),
+            offset: self.offset,
+            count: self.count,
+        })
+    }
+}
+
+/// The command lists to hand the output of a shader pass off to for mipmap generation.
+#[repr(C)]
+pub struct libra_mipmap_handoff_lists_d3d12_t {
+    /// A pointer to a command list of type `D3D12_COMMAND_LIST_TYPE_COMPUTE` to record the mipmap
+    /// generation for the output of the shader pass to. The command list must be ready for recording.
+    pub compute: Option<ManuallyDrop<ID3D12GraphicsCommandList>>,
+    /// A pointer to a graphics command list to record the rest of the frame to. The command list
+    /// must be ready for recording.
+    pub graphics: Option<ManuallyDrop<ID3D12GraphicsCommandList>>,
+}
+
+/// A function that is called with the index of every intermediate shader pass whose output
+/// needs mipmaps. To generate the mipmaps on a compute queue, the function writes the command
+/// lists to hand the output off to to `out` and returns true. Otherwise, it returns false,
+/// and the mipmaps are recorded inline.
+pub type libra_mipmap_handoff_callback_d3d12_t = Option<
+    unsafe extern "C" fn(
+        userdata: *mut c_void,
+        pass: u32,
+        out: *mut libra_mipmap_handoff_lists_d3d12_t,
+    ) -> bool,
+>;
+
+/// A callback to record the mipmap generation of intermediate passes onto compute command lists with.
+///
+/// When the output of a pass is handed off, it is transitioned to
+/// `D3D12_RESOURCE_STATE_NON_PIXEL_SHADER_RESOURCE` at the end of the current graphics command
+/// list, its mipmaps are recorded to the compute command list, and it is transitioned back to
+/// `D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE` at the start of the new graphics command list,
+/// which the rest of the frame is recorded to.
+///
+/// The filter chain does not close or submit any command list. The compute command list must
+/// be executed after the current graphics command list has completed, and the new graphics
+/// command list after the compute command list has completed. If the filter chain was created
+/// with descriptor heaps owned by the caller, both heaps must also be bound to the command lists.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct libra_mipmap_handoff_d3d12_t {
+    /// The function to call, or null to record mipmaps inline.
+    pub callback: libra_mipmap_handoff_callback_d3d12_t,
+    /// A pointer that is passed to `callback` as is.
+    pub userdata: *mut c_void,
+}
+
+impl Default for libra_mipmap_handoff_d3d12_t {
+    fn default() -> Self {
+        libra_mipmap_handoff_d3d12_t {
+            callback: None,
+            userdata: std::ptr::null_mut(),
+        }
+    }
+}
+
+struct HandoffUserdata(*mut c_void);
+// SAFETY: the caller is responsible for the callback being callable from any thread.
+unsafe impl Send for HandoffUserdata {}
+unsafe impl Sync for HandoffUserdata {}
+
+impl HandoffUserdata {
+    fn get(&self) -> *mut c_void {
+        self.0
+    }
+}
+
+impl IntoOption<Option<D3D12MipmapHandoffCallback>> for libra_mipmap_handoff_d3d12_t {
+    fn into_option(self) -> Option<D3D12MipmapHandoffCallback> {
+        let callback = self.callback?;
+        let userdata = HandoffUserdata(self.userdata);
+
+        Some(D3D12MipmapHandoffCallback::new(move |pass| {
+            let mut lists = libra_mipmap_handoff_lists_d3d12_t {
+                compute: None,
+                graphics: None,
+            };
+            if !unsafe { callback(userdata.get(), pass as u32, &mut lists) } {
+                return None;
+            }
+
+            Some(D3D12MipmapHandoff {
+                compute: ID3D12GraphicsCommandList::clone(lists.compute.as_ref()?),
+                graphics: ID3D12GraphicsCommandList::clone(lists.graphics.as_ref()?),
+            })
+        }))
+    }
+}
+
 /// Options for each Direct3D 12 shader frame.
 #[repr(C)]
 #[derive(Default, Debug, Clone)]
@@ -104,6 +221,31 @@ pub struct frame_d3d12_opt_t {
     pub frames_per_second: f32,
     /// Time in milliseconds between the current and previous frame. Default is 0.
     pub frametime_delta: u32,
+    /// Whether the provided MVP is in row-major order and needs to be transposed.
+    /// Default is false.
+    pub transpose_mvp: bool,
+    /// Whether to flip the Y axis of the MVP, which flips the output vertically.
+    /// This applies to the default MVP if no MVP is provided. Default is false.
+    pub flip_mvp_y: bool,
+    /// The kernel used to scale the input to the viewport when there are no enabled
+    /// shader passes, and to scale the output of the final pass to the viewport if
+    /// `scale_final_pass` is set. 0 = nearest-neighbour, 1 = bilinear, 2 = bicubic,
+    /// 3 = Lanczos. Default is 0.
+    pub identity_scaling: u32,
+    /// Whether to draw the final pass at the size given by its scaling, and scale its output
+    /// to the viewport with the `identity_scaling` kernel. Default is false, which draws the
+    /// final pass directly to the viewport.
+    pub scale_final_pass: bool,
+    /// The sequence of sub-pixel offsets bound to the `FrameJitter` uniform.
+    /// 0 = no jitter, 1 = Halton (2, 3), 2 = `jitter_offset`. Default is 0.
+    pub jitter_sequence: u32,
+    /// The sub-pixel offset of the current frame in pixels, if `jitter_sequence` is 2.
+    /// Default is (0, 0).
+    pub jitter_offset: [f32; 2],
+    /// A callback to record the mipmap generation of intermediate passes onto compute command
+    /// lists with, instead of recording it inline on the graphics command list of the frame.
+    /// If the callback is null, mipmaps are recorded inline.
+    pub mipmap_handoff: libra_mipmap_handoff_d3d12_t,
 }
 
 config_struct! {
@@ -111,6 +253,15 @@ config_struct! {
         0 => [clear_history, frame_direction];
         1 => [rotation, total_subframes, current_subframe];
         2 => [aspect_ratio, frames_per_second, frametime_delta];
+        3 => [
+            transpose_mvp,
+            flip_mvp_y,
+            (into identity_scaling),
+            scale_final_pass,
+            jitter_sequence,
+            jitter_offset,
+            (into mipmap_handoff)
+        ];
     }
 }
 
@@ -132,11 +283,93 @@ pub struct filter_chain_d3d12_opt_t {
     /// Disable the shader object cache. Shaders will be
     /// recompiled rather than loaded from the cache.
     pub disable_cache: bool,
+    /// The maximum number of history frames to keep. If zero, the number of history frames
+    /// is not capped. Shader passes that sample older frames receive the oldest kept frame.
+    pub max_history: u32,
+    /// The policy to follow when the framebuffer format of a shader pass is not supported.
+    /// 0 = fall back to a wider format, 1 = fall back to the smallest supported format,
+    /// 2 = fail filter chain creation. Default is 0.
+    pub format_fallback: u32,
+    /// Disable history and feedback, for latency-sensitive modes such as runahead or netplay,
+    /// where frames are invalidated frequently. No history frames are kept, and shader passes
+    /// that sample history or feedback textures receive black textures instead.
+    pub disable_temporal: bool,
+    /// Freeze the `FrameCount` uniform while the `frame_direction` frame option is negative,
+    /// as RetroArch does while rewinding. Frontends that keep counting frames while rewinding
+    /// should enable this, so that shaders animated by the frame count do not jump.
+    pub freeze_frame_count_on_rewind: bool,
+    /// Build pipelines for new output formats on a worker thread instead of stalling the
+    /// frame that needs them, to keep frame pacing smooth when the output format changes.
+    /// Until the pipeline is built, the final pass is not drawn to outputs of that format,
+    /// which are left unchanged. The pipeline is swapped in at the start of a later frame.
+    pub async_pipeline_rebuild: bool,
+    /// Record CPU-side statistics of the commands recorded for every frame, such as the number
+    /// of draw calls, which can be read with `libra_*_filter_chain_get_frame_statistics` after
+    /// the frame is drawn. This is meant for performance tuning and regression tracking, and
+    /// has a small cost.
+    pub collect_statistics: bool,
+    /// A callback that is called as every shader pass of the preset is preprocessed, compiled,
+    /// and has its pipeline created, so that frontends can show the progress of loading
+    /// large presets. If the callback is null, progress is not reported.
+    pub progress: libra_progress_t,
+    /// A token to cancel filter chain creation with from another thread, or null. If cancelled,
+    /// creation stops between shader passes and fails. The token must stay valid until
+    /// filter chain creation returns.
+    pub cancel: libra_cancel_token_t,
+    /// Append a built-in pass after the passes of the preset that simulates or corrects for
+    /// colour vision deficiencies. The pass is controlled at runtime with the
+    /// `LIBRA_COLOR_ASSIST_MODE` (0 = off, 1 = protanopia, 2 = deuteranopia, 3 = tritanopia),
+    /// `LIBRA_COLOR_ASSIST_SIMULATE` and `LIBRA_COLOR_ASSIST_STRENGTH` parameters.
+    pub color_assist: bool,
+    /// Append a built-in pass after the passes of the preset that inserts black frames between
+    /// the subframes of every frame, for motion clarity on high refresh rate displays.
+    /// The pass follows the `current_subframe` and `total_subframes` frame options, and is
+    /// controlled at runtime with the `LIBRA_BFI_ENABLE`, `LIBRA_BFI_LIT_SUBFRAMES`,
+    /// `LIBRA_BFI_DARK_LEVEL` and `LIBRA_BFI_COMPENSATION` parameters.
+    pub black_frame_insertion: bool,
+    /// A callback that is called before and after the commands of every frame are recorded,
+    /// to forward to latency SDKs such as NVIDIA Reflex or AMD Anti-Lag. If the callback is
+    /// null, latency markers are not reported.
+    pub latency_markers: libra_latency_marker_t,
+    /// A librashader instance to create the filter chain from, or null. Shaders are compiled on
+    /// the thread pool of the instance, with the cache directory and log callback of the instance.
+    /// The instance must stay valid until filter chain creation returns.
+    pub instance: libra_instance_handle_t,
+    /// A range of a shader visible `D3D12_DESCRIPTOR_HEAP_TYPE_CBV_SRV_UAV` heap to sub-allocate
+    /// the shader resource views bound to shader passes from, instead of creating a heap.
+    /// The range must hold 16 descriptors for every shader pass, including the 4 built-in
+    /// identity passes which are reserved even if they are never used, and any appended
+    /// built-in passes, and 4096 descriptors for mipmap generation. The descriptors in the range must not be used by the caller while the
+    /// filter chain is alive.
+    ///
+    /// If both `resource_heap` and `sampler_heap` are set, frames do not switch descriptor heaps,
+    /// and both heaps must be bound to the command list before a frame is recorded.
+    pub resource_heap: libra_descriptor_heap_range_d3d12_t,
+    /// A range of a shader visible `D3D12_DESCRIPTOR_HEAP_TYPE_SAMPLER` heap to sub-allocate
+    /// the samplers bound to shader passes from, instead of creating a heap.
+    /// The range must hold 16 descriptors for every shader pass, counted as for `resource_heap`.
+    pub sampler_heap: libra_descriptor_heap_range_d3d12_t,
 }
 
 config_struct! {
     impl FilterChainOptions => filter_chain_d3d12_opt_t {
         0 =>  [force_hlsl_pipeline, force_no_mipmaps, disable_cache];
+        3 => [
+            max_history,
+            format_fallback,
+            disable_temporal,
+            freeze_frame_count_on_rewind,
+            async_pipeline_rebuild,
+            collect_statistics,
+            (into progress),
+            (into cancel),
+            color_assist,
+            black_frame_insertion,
+            (into latency_markers),
+            (into instance),
+            (into resource_heap),
+            (into sampler_heap)
+        ];
     }
 }
 
@@ -145,6 +378,10 @@ extern_fn! {
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
+    ///
+    /// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+    /// compute pass fail to load with an error with code `REFLECT_ERROR`.
     ///
     /// ## Safety:
     /// - `preset` must be either null, or valid and aligned.
@@ -185,12 +422,203 @@ extern_fn! {
     }
 }
 
+extern_fn! {
+    /// Start creating the filter chain given the shader preset on a worker thread.
+    ///
+    /// Shaders are compiled without blocking the calling thread. The filter chain is taken from
+    /// the pending handle written to `out` with `libra_d3d12_filter_chain_poll_pending` or
+    /// `libra_d3d12_filter_chain_wait_pending`.
+    /// The progress callback of `options` is called on the worker thread.
+    ///
+    /// The shader preset is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
+    ///
+    /// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+    /// compute pass fail to load with an error with code `REFLECT_ERROR`.
+    ///
+    /// ## Safety:
+    /// - `device` must not be null.
+    /// - The progress callback and its userdata, the cancel token and the instance of `options`,
+    ///   and `device`, must stay valid until the filter chain is taken from the
+    ///   pending handle. Freeing the pending handle does not stop the worker thread, so to
+    ///   release them before, cancel creation with the cancel token and take the result with
+    ///   `libra_d3d12_filter_chain_wait_pending`.
+    /// - `preset` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_d3d12_filter_chain_create_async(
+        preset: *mut libra_shader_preset_t,
+        device: ManuallyDrop<ID3D12Device>,
+        options: *const MaybeUninit<filter_chain_d3d12_opt_t>,
+        out: *mut MaybeUninit<libra_d3d12_pending_filter_chain_t>
+    ) {
+        assert_non_null!(preset);
+        let preset = unsafe {
+            let preset_ptr = &mut *preset;
+            let preset = preset_ptr.take();
+            Box::from_raw(preset.unwrap().as_ptr())
+        };
+
+        let options = if options.is_null() {
+            None
+        } else {
+            Some(unsafe { options.read() })
+        };
+
+        let options = options.map(FromUninit::from_uninit);
+
+        unsafe {
+            let pending = FilterChain::load_from_preset_async(*preset, &device, options)?;
+
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                pending,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Take the filter chain from a pending handle if it has finished creating, without blocking.
+    ///
+    /// If the filter chain has finished creating, `ready` is set to true and the resulting value in
+    /// `pending` becomes null. The filter chain is then written to `out`, or the error of filter chain
+    /// creation is returned. Otherwise, `ready` is set to false and `out` is unchanged.
+    ///
+    /// ## Safety
+    /// - `pending` must be a valid and aligned pointer to an initialized `libra_d3d12_pending_filter_chain_t`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    /// - `ready` must be a valid and aligned pointer to a `bool`.
+    fn libra_d3d12_filter_chain_poll_pending(
+        pending: *mut libra_d3d12_pending_filter_chain_t,
+        out: *mut MaybeUninit<libra_d3d12_filter_chain_t>,
+        ready: *mut bool
+    ) {
+        assert_non_null!(pending);
+        assert_non_null!(out);
+        assert_non_null!(ready);
+
+        let pending_ptr = unsafe { &mut *pending };
+        let Some(handle) = pending_ptr else {
+            return Err(LibrashaderError::InvalidParameter("pending"));
+        };
+
+        let result = unsafe { handle.as_mut().try_take() };
+        unsafe {
+            ready.write(result.is_some());
+        }
+
+        let Some(result) = result else {
+            return Ok(());
+        };
+
+        unsafe {
+            drop(Box::from_raw(pending_ptr.take().unwrap().as_ptr()));
+        }
+
+        let chain = result?;
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Block until the filter chain of a pending handle has finished creating, and take it.
+    ///
+    /// The resulting value in `pending` becomes null. The filter chain is then written to `out`,
+    /// or the error of filter chain creation is returned.
+    ///
+    /// ## Safety
+    /// - `pending` must be a valid and aligned pointer to an initialized `libra_d3d12_pending_filter_chain_t`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_d3d12_filter_chain_wait_pending(
+        pending: *mut libra_d3d12_pending_filter_chain_t,
+        out: *mut MaybeUninit<libra_d3d12_filter_chain_t>
+    ) {
+        assert_non_null!(pending);
+        assert_non_null!(out);
+
+        let Some(handle) = (unsafe { &mut *pending }).take() else {
+            return Err(LibrashaderError::InvalidParameter("pending"));
+        };
+
+        let pending = unsafe { Box::from_raw(handle.as_ptr()) };
+        let chain = pending.wait()?;
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Free a pending filter chain handle without taking the filter chain.
+    ///
+    /// Filter chain creation is not stopped, but the filter chain is freed once it is created.
+    /// Creation can be stopped early with the cancellation token of the filter chain options.
+    /// The resulting value in `pending` then becomes null.
+    ///
+    /// ## Safety
+    /// - `pending` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_pending_filter_chain_t`.
+    fn libra_d3d12_filter_chain_free_pending(
+        pending: *mut libra_d3d12_pending_filter_chain_t
+    ) {
+        assert_non_null!(pending);
+        unsafe {
+            let pending_ptr = &mut *pending;
+            let pending = pending_ptr.take();
+            drop(Box::from_raw(pending.unwrap().as_ptr()))
+        };
+    }
+}
+
+extern_fn! {
+    /// Create a filter chain without any shader passes, that only scales the input
+    /// to the viewport.
+    ///
+    /// This allows the filter chain to be used to present frames even when no
+    /// shader preset is active.
+    ///
+    /// ## Safety:
+    /// - `options` must be either null, or valid and aligned.
+    /// - `device` must not be null.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_d3d12_filter_chain_create_identity(
+        device: ManuallyDrop<ID3D12Device>,
+        options: *const MaybeUninit<filter_chain_d3d12_opt_t>,
+        out: *mut MaybeUninit<libra_d3d12_filter_chain_t>
+    ) {
+        let options = if options.is_null() {
+            None
+        } else {
+            Some(unsafe { options.read() })
+        };
+
+        let options = options.map(FromUninit::from_uninit);
+        unsafe {
+            let chain = FilterChain::new_identity(&device, options.as_ref())?;
+
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
 extern_fn! {
     /// Create the filter chain given the shader preset deferring and GPU-side initialization
     /// to the caller. This function therefore requires no external synchronization of the device queue.
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
+    ///
+    /// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+    /// compute pass fail to load with an error with code `REFLECT_ERROR`.
     ///
     /// ## Safety:
     /// - `preset` must be either null, or valid and aligned.
@@ -435,6 +863,106 @@ extern_fn! {
     }
 }
 
+extern_fn! {
+    /// Gets a handle to a parameter of the filter chain.
+    ///
+    /// The handle can be used to get or set the parameter with
+    /// `libra_d3d12_filter_chain_get_param_by_handle` and `libra_d3d12_filter_chain_set_param_by_handle`,
+    /// without looking up the parameter by name.
+    ///
+    /// If the parameter does not exist, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+    /// - `param_name` must be either null or a null terminated string.
+    fn libra_d3d12_filter_chain_get_param_handle(
+        chain: *const libra_d3d12_filter_chain_t,
+        param_name: *const c_char,
+        out: *mut MaybeUninit<libra_param_handle_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        assert_non_null!(param_name);
+        unsafe {
+            let name = CStr::from_ptr(param_name);
+            let name = name.to_str()?;
+
+            let Some(handle) = chain.parameters().handle(name) else {
+                return Err(LibrashaderError::UnknownShaderParameter(param_name))
+            };
+
+            out.write(MaybeUninit::new(handle.into()));
+        }
+    }
+}
+
+extern_fn! {
+    /// Sets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+    fn libra_d3d12_filter_chain_set_param_by_handle(
+        chain: *mut libra_d3d12_filter_chain_t,
+        handle: libra_param_handle_t,
+        value: f32
+    ) |chain| {
+        assert_some_ptr!(chain);
+        if chain.parameters().set_parameter_by_handle(handle.into(), value).is_none() {
+            return Err(LibrashaderError::UnknownShaderParameterHandle(handle))
+        }
+    }
+}
+
+extern_fn! {
+    /// Gets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+    fn libra_d3d12_filter_chain_get_param_by_handle(
+        chain: *const libra_d3d12_filter_chain_t,
+        handle: libra_param_handle_t,
+        out: *mut MaybeUninit<f32>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let Some(value) = chain.parameters().parameter_value_by_handle(handle.into()) else {
+            return Err(LibrashaderError::UnknownShaderParameterHandle(handle))
+        };
+
+        unsafe {
+            out.write(MaybeUninit::new(value));
+        }
+    }
+}
+
+extern_fn! {
+    /// Get a list of the runtime parameters of the filter chain.
+    ///
+    /// Parameters are listed in the order of their handles, so the handle of a parameter is its
+    /// index in the list. The initial value of a parameter is the value declared in the shader,
+    /// which may differ from the value set by the preset. Parameters that are set by the preset
+    /// but not declared by any pass have an empty description.
+    ///
+    /// The output struct must be freed with `libra_preset_free_runtime_params`.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_param_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    fn libra_d3d12_filter_chain_get_runtime_params(
+        chain: *const libra_d3d12_filter_chain_t,
+        out: *mut MaybeUninit<libra_preset_param_list_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        assert_non_null!(out);
+        let list = crate::presets::runtime_param_list(chain.parameters())?;
+        unsafe {
+            out.write(MaybeUninit::new(list));
+        }
+    }
+}
+
 extern_fn! {
     /// Sets the number of active passes for this chain.
     ///
@@ -466,6 +994,153 @@ extern_fn! {
     }
 }
 
+extern_fn! {
+    /// Gets the number of history frames kept by this chain, and the number of history
+    /// frames requested by the shader passes.
+    ///
+    /// If the history depth was capped with `max_history`, fewer frames may be kept than
+    /// were requested.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+    /// - `requested` and `kept` must be valid and aligned.
+    fn libra_d3d12_filter_chain_get_history_depth(
+        chain: *const libra_d3d12_filter_chain_t,
+        requested: *mut MaybeUninit<u32>,
+        kept: *mut MaybeUninit<u32>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let depth = chain.history_depth();
+        unsafe {
+            requested.write(MaybeUninit::new(depth.requested as u32));
+            kept.write(MaybeUninit::new(depth.kept as u32));
+        }
+    }
+}
+
+extern_fn! {
+    /// Invalidates the last `frames` frames of history, such as after a rollback in runahead
+    /// or netplay, so that shader passes do not blend frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+    /// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+    fn libra_d3d12_filter_chain_invalidate_history(
+        chain: *mut libra_d3d12_filter_chain_t,
+        frames: u32
+    ) mut |chain| {
+        assert_some_ptr!(mut chain);
+        chain.invalidate_history(frames as usize);
+    }
+}
+
+extern_fn! {
+    /// Sets the mesh to draw the final pass with, in place of the default quad.
+    ///
+    /// The `count` vertices are drawn as a triangle strip. Positions are in the space of the
+    /// default final pass quad, where `(0, 0)` is the top-left and `(1, 1)` the bottom-right
+    /// corner of the viewport before the MVP is applied. Texture coordinates are in the same
+    /// orientation. If `vertices` is null, the default quad is drawn again.
+    ///
+    /// If `vertices` is not null and `count` is less than 3, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+    /// - `vertices` must be either null or a valid and aligned pointer to an array of `count` `libra_vertex_t`.
+    /// - The command lists recorded with the previous mesh must have completed execution.
+    fn libra_d3d12_filter_chain_set_output_mesh(
+        chain: *mut libra_d3d12_filter_chain_t,
+        vertices: *const libra_vertex_t,
+        count: usize
+    ) mut |chain| {
+        assert_some_ptr!(mut chain);
+        let mesh = unsafe { crate::runtime::output_mesh(vertices, count)? };
+        unsafe { chain.set_output_mesh(mesh.as_ref())? };
+    }
+}
+
+extern_fn! {
+    /// Writes GPU timestamps around the commands of the next frame to the given query heap.
+    ///
+    /// The timestamp at `first_query` is written before the first command of the frame, and the
+    /// timestamp at `first_query + 1` after the last command. The queries are only written for
+    /// the next frame, so that frontends can rotate queries between frames in flight.
+    ///
+    /// If the `mipmap_handoff` frame option hands the frame off to other command lists, the query
+    /// pair spans several command lists. The timestamp at `first_query` is written to the command
+    /// list given to `libra_d3d12_filter_chain_frame`, and the timestamp at `first_query + 1` to the
+    /// last graphics command list returned by the callback. The measured time then includes any
+    /// time the graphics queue waits for the compute command lists.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+    /// - `heap` must be a non-null pointer to a `D3D12_QUERY_HEAP_TYPE_TIMESTAMP` query heap
+    ///   with at least `first_query + 2` queries, that stays alive until every command list of
+    ///   the next frame has completed execution.
+    /// - If the frame is handed off, the graphics command lists that contain the queries must be
+    ///   executed on the same queue.
+    fn libra_d3d12_filter_chain_set_timestamp_queries(
+        chain: *mut libra_d3d12_filter_chain_t,
+        heap: ManuallyDrop<ID3D12QueryHeap>,
+        first_query: u32
+    ) mut |chain| {
+        assert_some_ptr!(mut chain);
+        unsafe { chain.set_timestamp_queries(&heap, first_query) };
+    }
+}
+
+extern_fn! {
+    /// Gets information about the shader pass at the given index, including the framebuffer
+    /// format chosen for the pass.
+    ///
+    /// The strings of the output struct must be freed with `libra_pass_info_free`.
+    /// If the index is out of range, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    fn libra_d3d12_filter_chain_get_pass_info(
+        chain: *const libra_d3d12_filter_chain_t,
+        index: u32,
+        out: *mut MaybeUninit<libra_pass_info_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let Some(info) = chain.pass_info(index as usize) else {
+            return Err(LibrashaderError::InvalidParameter("index"));
+        };
+        let info = crate::runtime::pass_info(info)?;
+        unsafe {
+            out.write(MaybeUninit::new(info));
+        }
+    }
+}
+
+extern_fn! {
+    /// Gets the CPU-side statistics of the commands recorded for the last frame, such as the
+    /// number of draw calls.
+    ///
+    /// If the filter chain was not created with the `collect_statistics` option, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    fn libra_d3d12_filter_chain_get_frame_statistics(
+        chain: *const libra_d3d12_filter_chain_t,
+        out: *mut MaybeUninit<libra_frame_statistics_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let Some(statistics) = chain.frame_statistics() else {
+            return Err(LibrashaderError::InvalidParameter("chain"));
+        };
+        unsafe {
+            out.write(MaybeUninit::new(libra_frame_statistics_t {
+                draw_calls: statistics.draw_calls,
+                barriers: statistics.barriers,
+                uploads: statistics.uploads,
+                descriptor_writes: statistics.descriptor_writes,
+            }));
+        }
+    }
+}
+
 extern_fn! {
     /// Free a D3D12 filter chain.
     ///
@@ -481,3 +1156,46 @@ extern_fn! {
         };
     }
 }
+
+static DEFERRED: DeferredQueue<FilterChain, (ID3D12Fence, u64)> = DeferredQueue::new();
+
+extern_fn! {
+    /// Free a D3D12 filter chain once `fence` reaches `value`, without waiting for the
+    /// frames that were recorded with it to complete.
+    ///
+    /// The filter chain is queued, and freed by a later call to `libra_d3d12_filter_chain_free_deferred`
+    /// or `libra_d3d12_filter_chain_collect_deferred` after the completed value of `fence` is
+    /// at least `value`.
+    ///
+    /// The resulting value in `chain` then becomes null.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_d3d12_filter_chain_t`.
+    /// - `fence` must not be null, and must be signaled with `value` after the last command list
+    ///   that the filter chain recorded into has completed.
+    fn libra_d3d12_filter_chain_free_deferred(
+        chain: *mut libra_d3d12_filter_chain_t,
+        fence: ManuallyDrop<ID3D12Fence>,
+        value: u64
+    ) {
+        assert_non_null!(chain);
+        let chain = unsafe {
+            let chain_ptr = &mut *chain;
+            let chain = chain_ptr.take();
+            Box::from_raw(chain.unwrap().as_ptr())
+        };
+
+        DEFERRED.push(chain, ((*fence).clone(), value));
+        DEFERRED.collect(|_, (fence, value)| unsafe { fence.GetCompletedValue() } >= *value);
+    }
+}
+
+extern_fn! {
+    /// Free the D3D12 filter chains queued with `libra_d3d12_filter_chain_free_deferred`
+    /// whose fence has reached its value.
+    ///
+    /// Frontends should call this before releasing the device, after waiting for the
+    /// command queue to be idle, so that every queued filter chain is freed.
+    fn libra_d3d12_filter_chain_collect_deferred() {
+        DEFERRED.collect(|_, (fence, value)| unsafe { fence.GetCompletedValue() } >= *value);
+    }
+}
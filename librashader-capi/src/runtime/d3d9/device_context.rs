@@ -0,0 +1,56 @@
+use crate::ctypes::{libra_d3d9_device_context_t, IntoOption};
+use crate::error::assert_non_null;
+use crate::ffi::extern_fn;
+use librashader::runtime::d3d9::DeviceContext;
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+impl IntoOption<Option<DeviceContext>> for libra_d3d9_device_context_t {
+    fn into_option(self) -> Option<DeviceContext> {
+        // SAFETY: the device context must be valid until filter chain creation returns.
+        self.map(|context| unsafe { context.as_ref() }.clone())
+    }
+}
+
+extern_fn! {
+    /// Create a device context to share sampler states and LUT textures between filter chains
+    /// created on the same device.
+    ///
+    /// The device context is passed to filter chain creation with the `device_context` filter
+    /// chain option. Filter chains keep the resources they use alive, so the device context
+    /// can be freed while filter chains created with it are still in use. The device context
+    /// must only be used with filter chains created on the same device.
+    ///
+    /// ## Safety
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_d3d9_device_context_create(
+        out: *mut MaybeUninit<libra_d3d9_device_context_t>
+    ) {
+        assert_non_null!(out);
+
+        let context = DeviceContext::new()?;
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                context,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Free a Direct3D 9 device context.
+    ///
+    /// The resulting value in `context` then becomes null.
+    /// ## Safety
+    /// - `context` must be either null or a valid and aligned pointer to an initialized `libra_d3d9_device_context_t`.
+    fn libra_d3d9_device_context_free(
+        context: *mut libra_d3d9_device_context_t
+    ) {
+        assert_non_null!(context);
+        unsafe {
+            let context_ptr = &mut *context;
+            let context = context_ptr.take();
+            drop(Box::from_raw(context.unwrap().as_ptr()))
+        };
+    }
+}
@@ -0,0 +1,41 @@
+//! Deferred destruction of filter chains that frames in flight may still use.
+
+use std::sync::{Mutex, PoisonError};
+
+/// A queue of filter chains that are freed once the GPU has signaled that the frames
+/// recorded with them have completed.
+pub(crate) struct DeferredQueue<T, F> {
+    pending: Mutex<Vec<(Box<T>, F)>>,
+}
+
+impl<T, F> DeferredQueue<T, F> {
+    pub const fn new() -> Self {
+        Self {
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Queue the filter chain to be freed once `fence` is signaled.
+    pub fn push(&self, chain: Box<T>, fence: F) {
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((chain, fence));
+    }
+
+    /// Free every queued filter chain whose fence is signaled.
+    pub fn collect(&self, mut is_signaled: impl FnMut(&T, &F) -> bool) {
+        let finished: Vec<_> = {
+            let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+            let (finished, remaining) = std::mem::take(&mut *pending)
+                .into_iter()
+                .partition(|(chain, fence)| is_signaled(chain, fence));
+            *pending = remaining;
+            finished
+        };
+
+        // Filter chains are dropped outside the lock, so that freeing one can not block
+        // other threads from queueing theirs.
+        drop(finished);
+    }
+}
@@ -0,0 +1,61 @@
+use super::libra_gl_loader_t;
+use crate::ctypes::{libra_gl_device_context_t, IntoOption};
+use crate::error::assert_non_null;
+use crate::ffi::extern_fn;
+use librashader::runtime::gl::DeviceContext;
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+impl IntoOption<Option<DeviceContext>> for libra_gl_device_context_t {
+    fn into_option(self) -> Option<DeviceContext> {
+        // SAFETY: the device context must be valid until filter chain creation returns.
+        self.map(|context| unsafe { context.as_ref() }.clone())
+    }
+}
+
+extern_fn! {
+    /// Create a device context to share sampler objects and LUT textures between filter chains
+    /// created on the same OpenGL context.
+    ///
+    /// The device context is passed to filter chain creation with the `device_context` filter
+    /// chain option. Filter chains keep the resources they use alive, so the device context
+    /// can be freed while filter chains created with it are still in use.
+    ///
+    /// ## Safety
+    /// - The OpenGL context that filter chains are created on must be current on the calling thread.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_gl_device_context_create(
+        loader: libra_gl_loader_t,
+        out: *mut MaybeUninit<libra_gl_device_context_t>
+    ) {
+        assert_non_null!(out);
+
+        unsafe {
+            let context = glow::Context::from_loader_function_cstr(
+                |proc_name| loader(proc_name.as_ptr()));
+
+            let context = DeviceContext::new(&context)?;
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                context,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Free an OpenGL device context.
+    ///
+    /// The resulting value in `context` then becomes null.
+    /// ## Safety
+    /// - `context` must be either null or a valid and aligned pointer to an initialized `libra_gl_device_context_t`.
+    fn libra_gl_device_context_free(
+        context: *mut libra_gl_device_context_t
+    ) {
+        assert_non_null!(context);
+        unsafe {
+            let context_ptr = &mut *context;
+            let context = context_ptr.take();
+            drop(Box::from_raw(context.unwrap().as_ptr()))
+        };
+    }
+}
@@ -1,4 +1,9 @@
 //! librashader runtime C APIs.
+use crate::ctypes::libra_pass_info_t;
+use crate::error::assert_non_null;
+use crate::ffi::extern_fn;
+use std::ffi::CString;
+
 #[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-opengl")))]
 #[cfg(feature = "runtime-opengl")]
 pub mod gl;
@@ -50,3 +55,172 @@ pub mod d3d12;
     )
 ))]
 pub mod mtl;
+
+#[cfg(any(
+    feature = "runtime-vulkan",
+    feature = "__cbindgen_internal",
+    all(target_os = "windows", feature = "runtime-d3d12"),
+    all(
+        target_vendor = "apple",
+        feature = "runtime-metal",
+        feature = "__cbindgen_internal_objc"
+    )
+))]
+mod deferred;
+
+/// Create the output mesh of a filter chain from the vertices passed to
+/// `libra_*_filter_chain_set_output_mesh`.
+///
+/// ## Safety
+/// `vertices` must be either null or a valid and aligned pointer to `count` vertices.
+#[cfg(any(
+    feature = "runtime-opengl",
+    feature = "runtime-vulkan",
+    feature = "runtime-d3d9",
+    feature = "runtime-d3d11",
+    feature = "runtime-d3d12",
+    feature = "runtime-metal"
+))]
+pub(crate) unsafe fn output_mesh(
+    vertices: *const crate::ctypes::libra_vertex_t,
+    count: usize,
+) -> Result<Option<librashader::runtime::OutputMesh>, crate::error::LibrashaderError> {
+    use librashader::runtime::{OutputMesh, VertexInput};
+
+    if vertices.is_null() {
+        return Ok(None);
+    }
+
+    let vertices = unsafe { std::slice::from_raw_parts(vertices, count) };
+    let vertices = vertices
+        .iter()
+        .map(|vertex| VertexInput {
+            position: vertex.position,
+            texcoord: vertex.texcoord,
+        })
+        .collect();
+
+    OutputMesh::new(vertices)
+        .map(Some)
+        .ok_or(crate::error::LibrashaderError::InvalidParameter("count"))
+}
+
+/// Convert information about a shader pass to its C representation.
+///
+/// The strings of the returned struct are owned, and must be freed with `libra_pass_info_free`.
+#[cfg(any(
+    feature = "runtime-opengl",
+    feature = "runtime-vulkan",
+    feature = "runtime-d3d9",
+    feature = "runtime-d3d11",
+    feature = "runtime-d3d12",
+    feature = "runtime-metal"
+))]
+pub(crate) fn pass_info(
+    info: librashader::runtime::PassInfo,
+) -> Result<libra_pass_info_t, crate::error::LibrashaderError> {
+    let to_c_string = |string: String| {
+        CString::new(string)
+            .map(|string| string.into_raw().cast_const())
+            .map_err(|err| crate::error::LibrashaderError::UnknownError(Box::new(err)))
+    };
+
+    let alias = match info.alias {
+        Some(alias) => to_c_string(alias.to_string())?,
+        None => std::ptr::null(),
+    };
+    let path = match info.path {
+        Some(path) => match to_c_string(path.to_string_lossy().into_owned()) {
+            Ok(path) => path,
+            Err(err) => {
+                if !alias.is_null() {
+                    drop(unsafe { CString::from_raw(alias.cast_mut()) });
+                }
+                return Err(err);
+            }
+        },
+        None => std::ptr::null(),
+    };
+
+    Ok(libra_pass_info_t {
+        requested_format: info.requested_format.into(),
+        format: info.format.into(),
+        alias,
+        path,
+        filter: info.filter.into(),
+        wrap_mode: info.wrap_mode.into(),
+        scale_type_x: info.scaling.x.scale_type.into(),
+        scale_type_y: info.scaling.y.scale_type.into(),
+        scale_x: info.scaling.x.factor.into(),
+        scale_y: info.scaling.y.factor.into(),
+    })
+}
+
+extern_fn! {
+    /// Free the strings of information about a shader pass.
+    ///
+    /// The `alias` and `path` fields of `info` then become null.
+    ///
+    /// ## Safety
+    /// - `info` must be a valid and aligned pointer to a `libra_pass_info_t` that was written by
+    ///   `libra_*_filter_chain_get_pass_info`.
+    fn libra_pass_info_free(info: *mut libra_pass_info_t) {
+        assert_non_null!(info);
+        unsafe {
+            let info = &mut *info;
+            for string in [&mut info.alias, &mut info.path] {
+                let string = std::mem::replace(string, std::ptr::null());
+                if !string.is_null() {
+                    drop(CString::from_raw(string.cast_mut()));
+                }
+            }
+        }
+    }
+}
+
+/// Whether the field of the options struct with the given name is read from a struct
+/// of the given API version.
+#[allow(unused_variables)]
+pub(crate) fn reads_option(
+    options: &str,
+    field: &str,
+    version: crate::LIBRASHADER_API_VERSION,
+) -> bool {
+    #[allow(unused_macros)]
+    macro_rules! find {
+        ($($ty:ty),+) => {
+            $(
+                if options == <$ty as crate::ctypes::ConfigStruct>::NAME {
+                    return <$ty as crate::ctypes::ConfigStruct>::reads_field(field, version);
+                }
+            )+
+        };
+    }
+
+    #[cfg(libra_runtime)]
+    find!(crate::instance::instance_opt_t);
+
+    #[cfg(feature = "runtime-opengl")]
+    find!(gl::filter_chain_gl_opt_t, gl::frame_gl_opt_t);
+
+    #[cfg(feature = "runtime-vulkan")]
+    find!(vk::filter_chain_vk_opt_t, vk::frame_vk_opt_t);
+
+    #[cfg(all(target_os = "windows", feature = "runtime-d3d11"))]
+    find!(d3d11::filter_chain_d3d11_opt_t, d3d11::frame_d3d11_opt_t);
+
+    #[cfg(all(target_os = "windows", feature = "runtime-d3d9"))]
+    find!(d3d9::filter_chain_d3d9_opt_t, d3d9::frame_d3d9_opt_t);
+
+    #[cfg(all(target_os = "windows", feature = "runtime-d3d12"))]
+    find!(d3d12::filter_chain_d3d12_opt_t, d3d12::frame_d3d12_opt_t);
+
+    #[cfg(all(
+        target_vendor = "apple",
+        feature = "runtime-metal",
+        feature = "__cbindgen_internal_objc"
+    ))]
+    find!(mtl::filter_chain_mtl_opt_t, mtl::frame_mtl_opt_t);
+
+    false
+}
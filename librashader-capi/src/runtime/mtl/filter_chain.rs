@@ -1,20 +1,27 @@
 use crate::ctypes::{
-    config_struct, libra_mtl_filter_chain_t, libra_shader_preset_t, libra_viewport_t, FromUninit,
+    config_struct, libra_cancel_token_t, libra_frame_statistics_t, libra_instance_handle_t,
+    libra_mtl_device_context_t, libra_mtl_filter_chain_t, libra_mtl_pending_filter_chain_t,
+    libra_param_handle_t, libra_pass_info_t, libra_progress_t, libra_shader_preset_t,
+    libra_vertex_t, libra_viewport_t, FromUninit,
 };
 use crate::error::{assert_non_null, assert_some_ptr, LibrashaderError};
 use crate::ffi::extern_fn;
+use crate::presets::libra_preset_param_list_t;
+use crate::runtime::deferred::DeferredQueue;
 use librashader::runtime::mtl::{FilterChain, FilterChainOptions, FrameOptions};
 use std::ffi::c_char;
 use std::ffi::CStr;
 use std::mem::MaybeUninit;
+use std::ptr;
 use std::ptr::NonNull;
 use std::slice;
 
 use librashader::runtime::FilterChainParameters;
 use librashader::runtime::{Size, Viewport};
 
+use objc2::rc::Retained;
 use objc2::runtime::ProtocolObject;
-use objc2_metal::{MTLCommandBuffer, MTLCommandQueue, MTLTexture};
+use objc2_metal::{MTLCommandBuffer, MTLCommandBufferStatus, MTLCommandQueue, MTLTexture};
 
 use crate::LIBRASHADER_API_VERSION;
 
@@ -56,6 +63,27 @@ pub struct frame_mtl_opt_t {
     pub frames_per_second: f32,
     /// Time in milliseconds between the current and previous frame. Default is 0.
     pub frametime_delta: u32,
+    /// Whether the provided MVP is in row-major order and needs to be transposed.
+    /// Default is false.
+    pub transpose_mvp: bool,
+    /// Whether to flip the Y axis of the MVP, which flips the output vertically.
+    /// This applies to the default MVP if no MVP is provided. Default is false.
+    pub flip_mvp_y: bool,
+    /// The kernel used to scale the input to the viewport when there are no enabled
+    /// shader passes, and to scale the output of the final pass to the viewport if
+    /// `scale_final_pass` is set. 0 = nearest-neighbour, 1 = bilinear, 2 = bicubic,
+    /// 3 = Lanczos. Default is 0.
+    pub identity_scaling: u32,
+    /// Whether to draw the final pass at the size given by its scaling, and scale its output
+    /// to the viewport with the `identity_scaling` kernel. Default is false, which draws the
+    /// final pass directly to the viewport.
+    pub scale_final_pass: bool,
+    /// The sequence of sub-pixel offsets bound to the `FrameJitter` uniform.
+    /// 0 = no jitter, 1 = Halton (2, 3), 2 = `jitter_offset`. Default is 0.
+    pub jitter_sequence: u32,
+    /// The sub-pixel offset of the current frame in pixels, if `jitter_sequence` is 2.
+    /// Default is (0, 0).
+    pub jitter_offset: [f32; 2],
 }
 
 config_struct! {
@@ -63,6 +91,7 @@ config_struct! {
         0 => [clear_history, frame_direction];
         1 => [rotation, total_subframes, current_subframe];
         2 => [aspect_ratio, frames_per_second, frametime_delta];
+        3 => [transpose_mvp, flip_mvp_y, (into identity_scaling), scale_final_pass, jitter_sequence, jitter_offset];
     }
 }
 
@@ -74,11 +103,66 @@ pub struct filter_chain_mtl_opt_t {
     pub version: LIBRASHADER_API_VERSION,
     /// Whether or not to explicitly disable mipmap generation regardless of shader preset settings.
     pub force_no_mipmaps: bool,
+    /// The maximum number of history frames to keep. If zero, the number of history frames
+    /// is not capped. Shader passes that sample older frames receive the oldest kept frame.
+    pub max_history: u32,
+    /// Disable history and feedback, for latency-sensitive modes such as runahead or netplay,
+    /// where frames are invalidated frequently. No history frames are kept, and shader passes
+    /// that sample history or feedback textures receive black textures instead.
+    pub disable_temporal: bool,
+    /// Freeze the `FrameCount` uniform while the `frame_direction` frame option is negative,
+    /// as RetroArch does while rewinding. Frontends that keep counting frames while rewinding
+    /// should enable this, so that shaders animated by the frame count do not jump.
+    pub freeze_frame_count_on_rewind: bool,
+    /// Record CPU-side statistics of the commands recorded for every frame, such as the number
+    /// of draw calls, which can be read with `libra_*_filter_chain_get_frame_statistics` after
+    /// the frame is drawn. This is meant for performance tuning and regression tracking, and
+    /// has a small cost.
+    pub collect_statistics: bool,
+    /// A callback that is called as every shader pass of the preset is preprocessed, compiled,
+    /// and has its pipeline created, so that frontends can show the progress of loading
+    /// large presets. If the callback is null, progress is not reported.
+    pub progress: libra_progress_t,
+    /// A token to cancel filter chain creation with from another thread, or null. If cancelled,
+    /// creation stops between shader passes and fails. The token must stay valid until
+    /// filter chain creation returns.
+    pub cancel: libra_cancel_token_t,
+    /// A device context to share samplers and LUT textures with other filter chains, or null.
+    /// The device context must be created for the same device as the filter chain, and must
+    /// stay valid until filter chain creation returns.
+    pub device_context: libra_mtl_device_context_t,
+    /// Append a built-in pass after the passes of the preset that simulates or corrects for
+    /// colour vision deficiencies. The pass is controlled at runtime with the
+    /// `LIBRA_COLOR_ASSIST_MODE` (0 = off, 1 = protanopia, 2 = deuteranopia, 3 = tritanopia),
+    /// `LIBRA_COLOR_ASSIST_SIMULATE` and `LIBRA_COLOR_ASSIST_STRENGTH` parameters.
+    pub color_assist: bool,
+    /// Append a built-in pass after the passes of the preset that inserts black frames between
+    /// the subframes of every frame, for motion clarity on high refresh rate displays.
+    /// The pass follows the `current_subframe` and `total_subframes` frame options, and is
+    /// controlled at runtime with the `LIBRA_BFI_ENABLE`, `LIBRA_BFI_LIT_SUBFRAMES`,
+    /// `LIBRA_BFI_DARK_LEVEL` and `LIBRA_BFI_COMPENSATION` parameters.
+    pub black_frame_insertion: bool,
+    /// A librashader instance to create the filter chain from, or null. Shaders are compiled on
+    /// the thread pool of the instance, with the cache directory and log callback of the instance.
+    /// The instance must stay valid until filter chain creation returns.
+    pub instance: libra_instance_handle_t,
 }
 
 config_struct! {
     impl FilterChainOptions => filter_chain_mtl_opt_t {
         0 => [force_no_mipmaps];
+        3 => [
+            max_history,
+            disable_temporal,
+            freeze_frame_count_on_rewind,
+            collect_statistics,
+            (into progress),
+            (into cancel),
+            (into device_context),
+            color_assist,
+            black_frame_insertion,
+            (into instance)
+        ];
     }
 }
 
@@ -87,6 +171,10 @@ extern_fn! {
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
+    ///
+    /// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+    /// compute pass fail to load with an error with code `REFLECT_ERROR`.
     ///
     /// ## Safety:
     /// - `queue` must be valid for the command buffers
@@ -128,12 +216,224 @@ extern_fn! {
     }
 }
 
+extern_fn! {
+    /// Start compiling the shaders of the filter chain given the shader preset on a worker thread.
+    ///
+    /// Shaders are compiled without blocking the calling thread. The filter chain is taken from
+    /// the pending handle written to `out` with `libra_mtl_filter_chain_poll_pending` or
+    /// `libra_mtl_filter_chain_wait_pending`, which create the rest of the filter chain with
+    /// `queue`.
+    /// The progress callback of `options` is called on the worker thread while shaders are
+    /// compiled, and on the thread that takes the filter chain afterwards.
+    ///
+    /// The shader preset is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
+    ///
+    /// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+    /// compute pass fail to load with an error with code `REFLECT_ERROR`.
+    ///
+    /// ## Safety:
+    /// - `queue` must be valid for the command buffers
+    ///     that `libra_mtl_filter_chain_frame` will write to.
+    /// - `queue` must be a reference to a `id<MTLCommandQueue>`.
+    /// - The progress callback and its userdata, the cancel token and the instance of `options`
+    ///   must stay valid until the filter chain is taken from the pending handle. Freeing the
+    ///   pending handle does not stop the worker thread, so to release them before, cancel
+    ///   creation with the cancel token and take the result with
+    ///   `libra_mtl_filter_chain_wait_pending`.
+    /// - `preset` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_mtl_filter_chain_create_async(
+        preset: *mut libra_shader_preset_t,
+        queue: PMTLCommandQueue,
+        options: *const MaybeUninit<filter_chain_mtl_opt_t>,
+        out: *mut MaybeUninit<libra_mtl_pending_filter_chain_t>
+    ) |queue| {
+        assert_non_null!(preset);
+
+        let preset = unsafe {
+            let preset_ptr = &mut *preset;
+            let preset = preset_ptr.take();
+            Box::from_raw(preset.unwrap().as_ptr())
+        };
+
+        let options = if options.is_null() {
+            None
+        } else {
+            Some(unsafe { options.read() })
+        };
+
+        // SAFETY: the queue was checked to be non-null.
+        let queue = unsafe { Retained::retain(ptr::from_ref(queue).cast_mut()) };
+        let Some(queue) = queue else {
+            return Err(LibrashaderError::InvalidParameter("queue"));
+        };
+        let options = options.map(FromUninit::from_uninit);
+
+        unsafe {
+            let pending = FilterChain::load_from_preset_async(*preset, &queue, options)?;
+
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                pending,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Take the filter chain from a pending handle if its shaders have finished compiling, without
+    /// blocking on shader compilation.
+    ///
+    /// The rest of the filter chain is created on the calling thread once shaders are compiled,
+    /// which waits for its LUTs to be uploaded with the command queue.
+    ///
+    /// If its shaders have finished compiling, `ready` is set to true and the resulting value in
+    /// `pending` becomes null. The filter chain is then written to `out`, or the error of filter chain
+    /// creation is returned. Otherwise, `ready` is set to false and `out` is unchanged.
+    ///
+    /// ## Safety
+    /// - `pending` must be a valid and aligned pointer to an initialized `libra_mtl_pending_filter_chain_t`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    /// - `ready` must be a valid and aligned pointer to a `bool`.
+    fn libra_mtl_filter_chain_poll_pending(
+        pending: *mut libra_mtl_pending_filter_chain_t,
+        out: *mut MaybeUninit<libra_mtl_filter_chain_t>,
+        ready: *mut bool
+    ) {
+        assert_non_null!(pending);
+        assert_non_null!(out);
+        assert_non_null!(ready);
+
+        let pending_ptr = unsafe { &mut *pending };
+        let Some(handle) = pending_ptr else {
+            return Err(LibrashaderError::InvalidParameter("pending"));
+        };
+
+        let result = unsafe { handle.as_mut().try_take() };
+        unsafe {
+            ready.write(result.is_some());
+        }
+
+        let Some(result) = result else {
+            return Ok(());
+        };
+
+        unsafe {
+            drop(Box::from_raw(pending_ptr.take().unwrap().as_ptr()));
+        }
+
+        let chain = result?;
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Block until the filter chain of a pending handle has finished creating, and take it.
+    ///
+    /// The rest of the filter chain is created on the calling thread once shaders are compiled,
+    /// which waits for its LUTs to be uploaded with the command queue.
+    ///
+    /// The resulting value in `pending` becomes null. The filter chain is then written to `out`,
+    /// or the error of filter chain creation is returned.
+    ///
+    /// ## Safety
+    /// - `pending` must be a valid and aligned pointer to an initialized `libra_mtl_pending_filter_chain_t`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_mtl_filter_chain_wait_pending(
+        pending: *mut libra_mtl_pending_filter_chain_t,
+        out: *mut MaybeUninit<libra_mtl_filter_chain_t>
+    ) {
+        assert_non_null!(pending);
+        assert_non_null!(out);
+
+        let Some(handle) = (unsafe { &mut *pending }).take() else {
+            return Err(LibrashaderError::InvalidParameter("pending"));
+        };
+
+        let pending = unsafe { Box::from_raw(handle.as_ptr()) };
+        let chain = pending.wait()?;
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Free a pending filter chain handle without taking the filter chain.
+    ///
+    /// Shader compilation is not stopped, but its result is discarded once it is done.
+    /// Compilation can be stopped early with the cancellation token of the filter chain options.
+    /// The resulting value in `pending` then becomes null.
+    ///
+    /// ## Safety
+    /// - `pending` must be either null or a valid and aligned pointer to an initialized `libra_mtl_pending_filter_chain_t`.
+    fn libra_mtl_filter_chain_free_pending(
+        pending: *mut libra_mtl_pending_filter_chain_t
+    ) {
+        assert_non_null!(pending);
+        unsafe {
+            let pending_ptr = &mut *pending;
+            let pending = pending_ptr.take();
+            drop(Box::from_raw(pending.unwrap().as_ptr()))
+        };
+    }
+}
+
+extern_fn! {
+    /// Create a filter chain without any shader passes, that only scales the input
+    /// to the viewport.
+    ///
+    /// This allows the filter chain to be used to present frames even when no
+    /// shader preset is active.
+    ///
+    /// ## Safety:
+    /// - `queue` must be valid for the command buffers
+    ///     that `libra_mtl_filter_chain_frame` will write to.
+    /// - `queue` must be a reference to a `id<MTLCommandQueue>`.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_mtl_filter_chain_create_identity(
+        queue: PMTLCommandQueue,
+        options: *const MaybeUninit<filter_chain_mtl_opt_t>,
+        out: *mut MaybeUninit<libra_mtl_filter_chain_t>
+    ) |queue| {
+        let options = if options.is_null() {
+            None
+        } else {
+            Some(unsafe { options.read() })
+        };
+
+        let queue = queue.as_ref();
+        let options = options.map(FromUninit::from_uninit);
+
+        unsafe {
+            let chain = FilterChain::new_identity(queue, options.as_ref())?;
+
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
 extern_fn! {
     /// Create the filter chain given the shader preset deferring and GPU-side initialization
     /// to the caller. This function therefore requires no external synchronization of the device queue.
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
+    ///
+    /// Compute passes are only supported by the Vulkan runtime. Presets that contain a
+    /// compute pass fail to load with an error with code `REFLECT_ERROR`.
     ///
     /// ## Safety:
     /// - `queue` must be valid for the command buffers
@@ -308,6 +608,106 @@ extern_fn! {
     }
 }
 
+extern_fn! {
+    /// Gets a handle to a parameter of the filter chain.
+    ///
+    /// The handle can be used to get or set the parameter with
+    /// `libra_mtl_filter_chain_get_param_by_handle` and `libra_mtl_filter_chain_set_param_by_handle`,
+    /// without looking up the parameter by name.
+    ///
+    /// If the parameter does not exist, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+    /// - `param_name` must be either null or a null terminated string.
+    fn libra_mtl_filter_chain_get_param_handle(
+        chain: *const libra_mtl_filter_chain_t,
+        param_name: *const c_char,
+        out: *mut MaybeUninit<libra_param_handle_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        assert_non_null!(param_name);
+        unsafe {
+            let name = CStr::from_ptr(param_name);
+            let name = name.to_str()?;
+
+            let Some(handle) = chain.parameters().handle(name) else {
+                return Err(LibrashaderError::UnknownShaderParameter(param_name))
+            };
+
+            out.write(MaybeUninit::new(handle.into()));
+        }
+    }
+}
+
+extern_fn! {
+    /// Sets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+    fn libra_mtl_filter_chain_set_param_by_handle(
+        chain: *mut libra_mtl_filter_chain_t,
+        handle: libra_param_handle_t,
+        value: f32
+    ) |chain| {
+        assert_some_ptr!(chain);
+        if chain.parameters().set_parameter_by_handle(handle.into(), value).is_none() {
+            return Err(LibrashaderError::UnknownShaderParameterHandle(handle))
+        }
+    }
+}
+
+extern_fn! {
+    /// Gets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+    fn libra_mtl_filter_chain_get_param_by_handle(
+        chain: *const libra_mtl_filter_chain_t,
+        handle: libra_param_handle_t,
+        out: *mut MaybeUninit<f32>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let Some(value) = chain.parameters().parameter_value_by_handle(handle.into()) else {
+            return Err(LibrashaderError::UnknownShaderParameterHandle(handle))
+        };
+
+        unsafe {
+            out.write(MaybeUninit::new(value));
+        }
+    }
+}
+
+extern_fn! {
+    /// Get a list of the runtime parameters of the filter chain.
+    ///
+    /// Parameters are listed in the order of their handles, so the handle of a parameter is its
+    /// index in the list. The initial value of a parameter is the value declared in the shader,
+    /// which may differ from the value set by the preset. Parameters that are set by the preset
+    /// but not declared by any pass have an empty description.
+    ///
+    /// The output struct must be freed with `libra_preset_free_runtime_params`.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_param_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    fn libra_mtl_filter_chain_get_runtime_params(
+        chain: *const libra_mtl_filter_chain_t,
+        out: *mut MaybeUninit<libra_preset_param_list_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        assert_non_null!(out);
+        let list = crate::presets::runtime_param_list(chain.parameters())?;
+        unsafe {
+            out.write(MaybeUninit::new(list));
+        }
+    }
+}
+
 extern_fn! {
     /// Sets the number of active passes for this chain.
     ///
@@ -339,6 +739,123 @@ extern_fn! {
     }
 }
 
+extern_fn! {
+    /// Gets the number of history frames kept by this chain, and the number of history
+    /// frames requested by the shader passes.
+    ///
+    /// If the history depth was capped with `max_history`, fewer frames may be kept than
+    /// were requested.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+    /// - `requested` and `kept` must be valid and aligned.
+    fn libra_mtl_filter_chain_get_history_depth(
+        chain: *const libra_mtl_filter_chain_t,
+        requested: *mut MaybeUninit<u32>,
+        kept: *mut MaybeUninit<u32>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let depth = chain.history_depth();
+        unsafe {
+            requested.write(MaybeUninit::new(depth.requested as u32));
+            kept.write(MaybeUninit::new(depth.kept as u32));
+        }
+    }
+}
+
+extern_fn! {
+    /// Invalidates the last `frames` frames of history, such as after a rollback in runahead
+    /// or netplay, so that shader passes do not blend frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+    /// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+    fn libra_mtl_filter_chain_invalidate_history(
+        chain: *mut libra_mtl_filter_chain_t,
+        frames: u32
+    ) mut |chain| {
+        assert_some_ptr!(mut chain);
+        chain.invalidate_history(frames as usize);
+    }
+}
+
+extern_fn! {
+    /// Sets the mesh to draw the final pass with, in place of the default quad.
+    ///
+    /// The `count` vertices are drawn as a triangle strip. Positions are in the space of the
+    /// default final pass quad, where `(0, 0)` is the top-left and `(1, 1)` the bottom-right
+    /// corner of the viewport before the MVP is applied. Texture coordinates are in the same
+    /// orientation. If `vertices` is null, the default quad is drawn again.
+    ///
+    /// If `vertices` is not null and `count` is less than 3, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+    /// - `vertices` must be either null or a valid and aligned pointer to an array of `count` `libra_vertex_t`.
+    fn libra_mtl_filter_chain_set_output_mesh(
+        chain: *mut libra_mtl_filter_chain_t,
+        vertices: *const libra_vertex_t,
+        count: usize
+    ) mut |chain| {
+        assert_some_ptr!(mut chain);
+        let mesh = unsafe { crate::runtime::output_mesh(vertices, count)? };
+        chain.set_output_mesh(mesh.as_ref())?;
+    }
+}
+
+extern_fn! {
+    /// Gets information about the shader pass at the given index, including the framebuffer
+    /// format chosen for the pass.
+    ///
+    /// The strings of the output struct must be freed with `libra_pass_info_free`.
+    /// If the index is out of range, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    fn libra_mtl_filter_chain_get_pass_info(
+        chain: *const libra_mtl_filter_chain_t,
+        index: u32,
+        out: *mut MaybeUninit<libra_pass_info_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let Some(info) = chain.pass_info(index as usize) else {
+            return Err(LibrashaderError::InvalidParameter("index"));
+        };
+        let info = crate::runtime::pass_info(info)?;
+        unsafe {
+            out.write(MaybeUninit::new(info));
+        }
+    }
+}
+
+extern_fn! {
+    /// Gets the CPU-side statistics of the commands recorded for the last frame, such as the
+    /// number of draw calls.
+    ///
+    /// If the filter chain was not created with the `collect_statistics` option, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    fn libra_mtl_filter_chain_get_frame_statistics(
+        chain: *const libra_mtl_filter_chain_t,
+        out: *mut MaybeUninit<libra_frame_statistics_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let Some(statistics) = chain.frame_statistics() else {
+            return Err(LibrashaderError::InvalidParameter("chain"));
+        };
+        unsafe {
+            out.write(MaybeUninit::new(libra_frame_statistics_t {
+                draw_calls: statistics.draw_calls,
+                barriers: statistics.barriers,
+                uploads: statistics.uploads,
+                descriptor_writes: statistics.descriptor_writes,
+            }));
+        }
+    }
+}
+
 extern_fn! {
     /// Free a Metal filter chain.
     ///
@@ -356,3 +873,68 @@ extern_fn! {
         };
     }
 }
+
+thread_local! {
+    // Metal filter chains can not be sent across threads, so every thread has its own queue.
+    static DEFERRED: DeferredQueue<FilterChain, Retained<ProtocolObject<dyn MTLCommandBuffer>>> =
+        const { DeferredQueue::new() };
+}
+
+/// Free the queued filter chains of this thread whose command buffer has completed.
+fn collect_deferred() {
+    DEFERRED.with(|deferred| {
+        deferred.collect(|_, command_buffer| {
+            matches!(
+                command_buffer.status(),
+                MTLCommandBufferStatus::Completed | MTLCommandBufferStatus::Error
+            )
+        })
+    });
+}
+
+extern_fn! {
+    /// Free a Metal filter chain once `command_buffer` has completed, without waiting for the
+    /// frames that were recorded with it to complete.
+    ///
+    /// The filter chain is queued, and freed by a later call to `libra_mtl_filter_chain_free_deferred`
+    /// or `libra_mtl_filter_chain_collect_deferred` on the same thread after `command_buffer`
+    /// has completed.
+    ///
+    /// The resulting value in `chain` then becomes null.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_mtl_filter_chain_t`.
+    /// - `command_buffer` must be the last command buffer that the filter chain recorded into,
+    ///   and must be committed before the filter chain is freed.
+    fn libra_mtl_filter_chain_free_deferred(
+        chain: *mut libra_mtl_filter_chain_t,
+        command_buffer: PMTLCommandBuffer
+    ) |command_buffer| {
+        assert_non_null!(chain);
+
+        // SAFETY: the command buffer was checked to be non-null.
+        let command_buffer = unsafe { Retained::retain(ptr::from_ref(command_buffer).cast_mut()) };
+        let Some(command_buffer) = command_buffer else {
+            return Err(LibrashaderError::InvalidParameter("command_buffer"));
+        };
+
+        let chain = unsafe {
+            let chain_ptr = &mut *chain;
+            let chain = chain_ptr.take();
+            Box::from_raw(chain.unwrap().as_ptr())
+        };
+
+        DEFERRED.with(|deferred| deferred.push(chain, command_buffer));
+        collect_deferred();
+    }
+}
+
+extern_fn! {
+    /// Free the Metal filter chains queued with `libra_mtl_filter_chain_free_deferred` on
+    /// this thread whose command buffer has completed.
+    ///
+    /// Frontends should call this before releasing the device, after waiting for the
+    /// command buffers to complete, so that every queued filter chain is freed.
+    fn libra_mtl_filter_chain_collect_deferred() {
+        collect_deferred();
+    }
+}
@@ -1,5 +1,7 @@
 //! C API for the librashader Metal Runtime (`libra_mtl_*`).
 
+mod device_context;
 mod filter_chain;
 
+pub use device_context::*;
 pub use filter_chain::*;
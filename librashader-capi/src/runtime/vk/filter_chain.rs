@@ -1,8 +1,13 @@
 use crate::ctypes::{
-    config_struct, libra_shader_preset_t, libra_viewport_t, libra_vk_filter_chain_t, FromUninit,
+    config_struct, libra_cancel_token_t, libra_frame_statistics_t, libra_instance_handle_t,
+    libra_latency_marker_t, libra_param_handle_t, libra_pass_info_t, libra_progress_t,
+    libra_shader_preset_t, libra_vertex_t, libra_viewport_t, libra_vk_device_context_t,
+    libra_vk_filter_chain_t, libra_vk_pending_filter_chain_t, FromUninit,
 };
 use crate::error::{assert_non_null, assert_some_ptr, LibrashaderError};
 use crate::ffi::extern_fn;
+use crate::presets::libra_preset_param_list_t;
+use crate::runtime::deferred::DeferredQueue;
 use librashader::runtime::vk::{
     FilterChain, FilterChainOptions, FrameOptions, VulkanImage, VulkanInstance,
 };
@@ -111,6 +116,50 @@ pub struct frame_vk_opt_t {
     pub frames_per_second: f32,
     /// Time in milliseconds between the current and previous frame. Default is 0.
     pub frametime_delta: u32,
+    /// Whether the provided MVP is in row-major order and needs to be transposed.
+    /// Default is false.
+    pub transpose_mvp: bool,
+    /// Whether to flip the Y axis of the MVP, which flips the output vertically.
+    /// This applies to the default MVP if no MVP is provided. Default is false.
+    pub flip_mvp_y: bool,
+    /// The kernel used to scale the input to the viewport when there are no enabled
+    /// shader passes, and to scale the output of the final pass to the viewport if
+    /// `scale_final_pass` is set. 0 = nearest-neighbour, 1 = bilinear, 2 = bicubic,
+    /// 3 = Lanczos. Default is 0.
+    pub identity_scaling: u32,
+    /// Whether to draw the final pass at the size given by its scaling, and scale its output
+    /// to the viewport with the `identity_scaling` kernel. Default is false, which draws the
+    /// final pass directly to the viewport.
+    pub scale_final_pass: bool,
+    /// The sequence of sub-pixel offsets bound to the `FrameJitter` uniform.
+    /// 0 = no jitter, 1 = Halton (2, 3), 2 = `jitter_offset`. Default is 0.
+    pub jitter_sequence: u32,
+    /// The sub-pixel offset of the current frame in pixels, if `jitter_sequence` is 2.
+    /// Default is (0, 0).
+    pub jitter_offset: [f32; 2],
+    /// The layout of the output image when the frame is recorded. If not
+    /// `VK_IMAGE_LAYOUT_UNDEFINED`, the output image is transitioned from this layout to
+    /// `VK_IMAGE_LAYOUT_COLOR_ATTACHMENT_OPTIMAL` before it is drawn to. Otherwise, the output image
+    /// must already be in `VK_IMAGE_LAYOUT_COLOR_ATTACHMENT_OPTIMAL`.
+    pub output_layout: vk::ImageLayout,
+    /// The layout to transition the output image to after the final pass, such as
+    /// `VK_IMAGE_LAYOUT_PRESENT_SRC_KHR`. If `VK_IMAGE_LAYOUT_UNDEFINED`, the output image is
+    /// left in `VK_IMAGE_LAYOUT_COLOR_ATTACHMENT_OPTIMAL`.
+    pub final_output_layout: vk::ImageLayout,
+    /// A `VkImageView` of the output image for the final pass to draw to, or null for the filter
+    /// chain to create a view for the frame. The view must be a 2D view of the first mip level and
+    /// array layer of the output image, in the format of the output image.
+    pub output_view: vk::ImageView,
+    /// A `VkRenderPass` owned by the caller to draw the final pass in, or null for the filter
+    /// chain to draw the final pass in its own rendering. If not null, the final pass is recorded
+    /// to `final_command_buffer`, and `output_layout` and `final_output_layout` are ignored.
+    pub final_render_pass: vk::RenderPass,
+    /// The index of the subpass of `final_render_pass` to draw the final pass in.
+    pub final_subpass: u32,
+    /// A secondary `VkCommandBuffer` to record the final pass to if `final_render_pass` is not
+    /// null. It must be recording, begun with `VK_COMMAND_BUFFER_USAGE_RENDER_PASS_CONTINUE_BIT`
+    /// for `final_render_pass` and `final_subpass`, and executed after the frame command buffer.
+    pub final_command_buffer: vk::CommandBuffer,
 }
 
 config_struct! {
@@ -118,6 +167,7 @@ config_struct! {
         0 => [clear_history, frame_direction];
         1 => [rotation, total_subframes, current_subframe];
         2 => [aspect_ratio, frames_per_second, frametime_delta];
+        3 => [transpose_mvp, flip_mvp_y, (into identity_scaling), scale_final_pass, jitter_sequence, jitter_offset, output_layout, final_output_layout, output_view, final_render_pass, final_subpass, final_command_buffer];
     }
 }
 
@@ -134,15 +184,105 @@ pub struct filter_chain_vk_opt_t {
     /// Use dynamic rendering over explicit render pass objects.
     /// It is recommended if possible to use dynamic rendering,
     /// because render-pass mode will create new framebuffers per pass.
+    /// Dynamic rendering requires the `dynamicRendering` feature of Vulkan 1.3, or of
+    /// `VK_KHR_dynamic_rendering` on Vulkan 1.2, in which case the device must be created
+    /// with the extension enabled. If the device does not support dynamic rendering,
+    /// render pass objects are used instead.
     pub use_dynamic_rendering: bool,
     /// Disable the shader object cache. Shaders will be
     /// recompiled rather than loaded from the cache.
     pub disable_cache: bool,
+    /// The maximum number of history frames to keep. If zero, the number of history frames
+    /// is not capped. Shader passes that sample older frames receive the oldest kept frame.
+    pub max_history: u32,
+    /// A sampler Y'CbCr conversion for inputs in a Y'CbCr format, such as NV12 frames from
+    /// video decoders. If not null, input images are converted to RGB in an internal pass
+    /// before the shader passes run. The conversion must match the format of the input images.
+    pub ycbcr_conversion: vk::SamplerYcbcrConversion,
+    /// A sampler created with `ycbcr_conversion`. Must not be null if `ycbcr_conversion`
+    /// is not null.
+    pub ycbcr_sampler: vk::Sampler,
+    /// The policy to follow when the framebuffer format of a shader pass is not supported.
+    /// 0 = fall back to a wider format, 1 = fall back to the smallest supported format,
+    /// 2 = fail filter chain creation. Default is 0.
+    pub format_fallback: u32,
+    /// Disable history and feedback, for latency-sensitive modes such as runahead or netplay,
+    /// where frames are invalidated frequently. No history frames are kept, and shader passes
+    /// that sample history or feedback textures receive black textures instead.
+    pub disable_temporal: bool,
+    /// Freeze the `FrameCount` uniform while the `frame_direction` frame option is negative,
+    /// as RetroArch does while rewinding. Frontends that keep counting frames while rewinding
+    /// should enable this, so that shaders animated by the frame count do not jump.
+    pub freeze_frame_count_on_rewind: bool,
+    /// Build pipelines for new output formats on a worker thread instead of stalling the
+    /// frame that needs them, to keep frame pacing smooth when the output format changes.
+    /// Until the pipeline is built, the final pass is not drawn to outputs of that format,
+    /// which are left unchanged, and `libra_vk_filter_chain_has_pending_pipelines` reports
+    /// `true` after the frame is recorded. The pipeline is swapped in at the start of a later frame.
+    pub async_pipeline_rebuild: bool,
+    /// Record CPU-side statistics of the commands recorded for every frame, such as the number
+    /// of draw calls, which can be read with `libra_*_filter_chain_get_frame_statistics` after
+    /// the frame is drawn. This is meant for performance tuning and regression tracking, and
+    /// has a small cost.
+    pub collect_statistics: bool,
+    /// A callback that is called as every shader pass of the preset is preprocessed, compiled,
+    /// and has its pipeline created, so that frontends can show the progress of loading
+    /// large presets. If the callback is null, progress is not reported.
+    pub progress: libra_progress_t,
+    /// A token to cancel filter chain creation with from another thread, or null. If cancelled,
+    /// creation stops between shader passes and fails. The token must stay valid until
+    /// filter chain creation returns.
+    pub cancel: libra_cancel_token_t,
+    /// Enable behaviour that is safe on implementations of `VK_KHR_portability_subset`,
+    /// such as MoltenVK. Framebuffer formats that can not be blitted or linearly filtered
+    /// by the device fall back to formats that can, as mipmap generation requires.
+    pub portability: bool,
+    /// A device context to share samplers and LUT textures with other filter chains, or null.
+    /// The device context must be created for the same device as the filter chain, and must
+    /// stay valid until filter chain creation returns.
+    pub device_context: libra_vk_device_context_t,
+    /// Append a built-in pass after the passes of the preset that simulates or corrects for
+    /// colour vision deficiencies. The pass is controlled at runtime with the
+    /// `LIBRA_COLOR_ASSIST_MODE` (0 = off, 1 = protanopia, 2 = deuteranopia, 3 = tritanopia),
+    /// `LIBRA_COLOR_ASSIST_SIMULATE` and `LIBRA_COLOR_ASSIST_STRENGTH` parameters.
+    pub color_assist: bool,
+    /// Append a built-in pass after the passes of the preset that inserts black frames between
+    /// the subframes of every frame, for motion clarity on high refresh rate displays.
+    /// The pass follows the `current_subframe` and `total_subframes` frame options, and is
+    /// controlled at runtime with the `LIBRA_BFI_ENABLE`, `LIBRA_BFI_LIT_SUBFRAMES`,
+    /// `LIBRA_BFI_DARK_LEVEL` and `LIBRA_BFI_COMPENSATION` parameters.
+    pub black_frame_insertion: bool,
+    /// A callback that is called before and after the commands of every frame are recorded,
+    /// to forward to latency SDKs such as NVIDIA Reflex or AMD Anti-Lag. If the callback is
+    /// null, latency markers are not reported.
+    pub latency_markers: libra_latency_marker_t,
+    /// A librashader instance to create the filter chain from, or null. Shaders are compiled on
+    /// the thread pool of the instance, with the cache directory and log callback of the instance.
+    /// The instance must stay valid until filter chain creation returns.
+    pub instance: libra_instance_handle_t,
 }
 
 config_struct! {
     impl FilterChainOptions => filter_chain_vk_opt_t {
         0 => [frames_in_flight, force_no_mipmaps, use_dynamic_rendering, disable_cache];
+        3 => [
+            max_history,
+            ycbcr_conversion,
+            ycbcr_sampler,
+            format_fallback,
+            disable_temporal,
+            freeze_frame_count_on_rewind,
+            async_pipeline_rebuild,
+            collect_statistics,
+            (into progress),
+            (into cancel),
+            portability,
+            (into device_context),
+            color_assist,
+            black_frame_insertion,
+            (into latency_markers),
+            (into instance)
+        ];
     }
 }
 
@@ -151,11 +291,11 @@ extern_fn! {
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
     ///
     /// ## Safety:
     /// - The handles provided in `vulkan` must be valid for the command buffers that
     ///   `libra_vk_filter_chain_frame` will write to.
-    ///    created with the `VK_KHR_dynamic_rendering` extension.
     /// - `preset` must be either null, or valid and aligned.
     /// - `options` must be either null, or valid and aligned.
     /// - `out` must be aligned, but may be null, invalid, or uninitialized.
@@ -191,12 +331,205 @@ extern_fn! {
     }
 }
 
+extern_fn! {
+    /// Start creating the filter chain given the shader preset on a worker thread.
+    ///
+    /// Shaders are compiled without blocking the calling thread. The filter chain is taken from
+    /// the pending handle written to `out` with `libra_vk_filter_chain_poll_pending` or
+    /// `libra_vk_filter_chain_wait_pending`.
+    /// The progress callback of `options` is called on the worker thread.
+    ///
+    /// The shader preset is immediately invalidated and must be recreated after
+    /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
+    ///
+    /// ## Safety:
+    /// - The handles provided in `vulkan` must be valid for the command buffers that
+    ///   `libra_vk_filter_chain_frame` will write to.
+    /// - The progress callback and its userdata, the cancel token and the instance of `options`,
+    ///   and the handles of `vulkan`, must stay valid until the filter chain is taken from the
+    ///   pending handle. Freeing the pending handle does not stop the worker thread, so to
+    ///   release them before, cancel creation with the cancel token and take the result with
+    ///   `libra_vk_filter_chain_wait_pending`.
+    /// - LUTs are uploaded by submitting to the queue of `vulkan` on the worker thread. The queue
+    ///   must not be used by any other thread until the filter chain is taken from the pending handle,
+    ///   so a queue that the frontend does not submit to, such as a second graphics queue, should be given.
+    /// - `preset` must be either null, or valid and aligned.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_vk_filter_chain_create_async(
+        preset: *mut libra_shader_preset_t,
+        vulkan: libra_device_vk_t,
+        options: *const MaybeUninit<filter_chain_vk_opt_t>,
+        out: *mut MaybeUninit<libra_vk_pending_filter_chain_t>
+    ) {
+        assert_non_null!(preset);
+        let preset = unsafe {
+            let preset_ptr = &mut *preset;
+            let preset = preset_ptr.take();
+            Box::from_raw(preset.unwrap().as_ptr())
+        };
+
+        let options = if options.is_null() {
+            None
+        } else {
+            Some(unsafe { options.read() })
+        };
+
+        let vulkan: VulkanInstance = vulkan.into();
+        let options = options.map(FromUninit::from_uninit);
+
+        unsafe {
+            let pending = FilterChain::load_from_preset_async(*preset, vulkan, options)?;
+
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                pending,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Take the filter chain from a pending handle if it has finished creating, without blocking.
+    ///
+    /// If the filter chain has finished creating, `ready` is set to true and the resulting value in
+    /// `pending` becomes null. The filter chain is then written to `out`, or the error of filter chain
+    /// creation is returned. Otherwise, `ready` is set to false and `out` is unchanged.
+    ///
+    /// ## Safety
+    /// - `pending` must be a valid and aligned pointer to an initialized `libra_vk_pending_filter_chain_t`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    /// - `ready` must be a valid and aligned pointer to a `bool`.
+    fn libra_vk_filter_chain_poll_pending(
+        pending: *mut libra_vk_pending_filter_chain_t,
+        out: *mut MaybeUninit<libra_vk_filter_chain_t>,
+        ready: *mut bool
+    ) {
+        assert_non_null!(pending);
+        assert_non_null!(out);
+        assert_non_null!(ready);
+
+        let pending_ptr = unsafe { &mut *pending };
+        let Some(handle) = pending_ptr else {
+            return Err(LibrashaderError::InvalidParameter("pending"));
+        };
+
+        let result = unsafe { handle.as_mut().try_take() };
+        unsafe {
+            ready.write(result.is_some());
+        }
+
+        let Some(result) = result else {
+            return Ok(());
+        };
+
+        unsafe {
+            drop(Box::from_raw(pending_ptr.take().unwrap().as_ptr()));
+        }
+
+        let chain = result?;
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Block until the filter chain of a pending handle has finished creating, and take it.
+    ///
+    /// The resulting value in `pending` becomes null. The filter chain is then written to `out`,
+    /// or the error of filter chain creation is returned.
+    ///
+    /// ## Safety
+    /// - `pending` must be a valid and aligned pointer to an initialized `libra_vk_pending_filter_chain_t`.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_vk_filter_chain_wait_pending(
+        pending: *mut libra_vk_pending_filter_chain_t,
+        out: *mut MaybeUninit<libra_vk_filter_chain_t>
+    ) {
+        assert_non_null!(pending);
+        assert_non_null!(out);
+
+        let Some(handle) = (unsafe { &mut *pending }).take() else {
+            return Err(LibrashaderError::InvalidParameter("pending"));
+        };
+
+        let pending = unsafe { Box::from_raw(handle.as_ptr()) };
+        let chain = pending.wait()?;
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
+extern_fn! {
+    /// Free a pending filter chain handle without taking the filter chain.
+    ///
+    /// Filter chain creation is not stopped, but the filter chain is freed once it is created.
+    /// Creation can be stopped early with the cancellation token of the filter chain options.
+    /// The resulting value in `pending` then becomes null.
+    ///
+    /// ## Safety
+    /// - `pending` must be either null or a valid and aligned pointer to an initialized `libra_vk_pending_filter_chain_t`.
+    fn libra_vk_filter_chain_free_pending(
+        pending: *mut libra_vk_pending_filter_chain_t
+    ) {
+        assert_non_null!(pending);
+        unsafe {
+            let pending_ptr = &mut *pending;
+            let pending = pending_ptr.take();
+            drop(Box::from_raw(pending.unwrap().as_ptr()))
+        };
+    }
+}
+
+extern_fn! {
+    /// Create a filter chain without any shader passes, that only scales the input
+    /// to the viewport.
+    ///
+    /// This allows the filter chain to be used to present frames even when no
+    /// shader preset is active.
+    ///
+    /// ## Safety:
+    /// - The handles provided in `vulkan` must be valid for the command buffers that
+    ///   `libra_vk_filter_chain_frame` will write to.
+    /// - `options` must be either null, or valid and aligned.
+    /// - `out` must be aligned, but may be null, invalid, or uninitialized.
+    fn libra_vk_filter_chain_create_identity(
+        vulkan: libra_device_vk_t,
+        options: *const MaybeUninit<filter_chain_vk_opt_t>,
+        out: *mut MaybeUninit<libra_vk_filter_chain_t>
+    ) {
+        let options = if options.is_null() {
+            None
+        } else {
+            Some(unsafe { options.read() })
+        };
+
+        let vulkan: VulkanInstance = vulkan.into();
+        let options = options.map(FromUninit::from_uninit);
+
+        unsafe {
+            let chain = FilterChain::new_identity(vulkan, options.as_ref())?;
+
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                chain,
+            )))))
+        }
+    }
+}
+
 extern_fn! {
     /// Create the filter chain given the shader preset deferring and GPU-side initialization
     /// to the caller. This function therefore requires no external synchronization of the device queue.
     ///
     /// The shader preset is immediately invalidated and must be recreated after
     /// the filter chain is created.
+    /// Use `libra_preset_clone` to keep a copy of the preset for later use.
     ///
     /// ## Safety:
     /// - The handles provided in `vulkan` must be valid for the command buffers that
@@ -248,9 +581,10 @@ extern_fn! {
     /// Records rendering commands for a frame with the given parameters for the given filter chain
     /// to the input command buffer.
     ///
-    /// A pipeline barrier **will not** be created for the final pass. The output image must be
-    /// in `VK_COLOR_ATTACHMENT_OPTIMAL`, and will remain so after all shader passes. The caller must transition
-    /// the output image to the final layout.
+    /// Unless `final_output_layout` is set in `opt`, a pipeline barrier **will not** be created for the
+    /// final pass. The output image must be in `VK_COLOR_ATTACHMENT_OPTIMAL` unless its current layout is
+    /// given in the `output_layout` option, and will remain so after all shader passes. The caller must
+    /// transition the output image to the final layout.
     ///
     /// ## Parameters
     ///
@@ -387,6 +721,106 @@ extern_fn! {
     }
 }
 
+extern_fn! {
+    /// Gets a handle to a parameter of the filter chain.
+    ///
+    /// The handle can be used to get or set the parameter with
+    /// `libra_vk_filter_chain_get_param_by_handle` and `libra_vk_filter_chain_set_param_by_handle`,
+    /// without looking up the parameter by name.
+    ///
+    /// If the parameter does not exist, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+    /// - `param_name` must be either null or a null terminated string.
+    fn libra_vk_filter_chain_get_param_handle(
+        chain: *const libra_vk_filter_chain_t,
+        param_name: *const c_char,
+        out: *mut MaybeUninit<libra_param_handle_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        assert_non_null!(param_name);
+        unsafe {
+            let name = CStr::from_ptr(param_name);
+            let name = name.to_str()?;
+
+            let Some(handle) = chain.parameters().handle(name) else {
+                return Err(LibrashaderError::UnknownShaderParameter(param_name))
+            };
+
+            out.write(MaybeUninit::new(handle.into()));
+        }
+    }
+}
+
+extern_fn! {
+    /// Sets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+    fn libra_vk_filter_chain_set_param_by_handle(
+        chain: *mut libra_vk_filter_chain_t,
+        handle: libra_param_handle_t,
+        value: f32
+    ) |chain| {
+        assert_some_ptr!(chain);
+        if chain.parameters().set_parameter_by_handle(handle.into(), value).is_none() {
+            return Err(LibrashaderError::UnknownShaderParameterHandle(handle))
+        }
+    }
+}
+
+extern_fn! {
+    /// Gets a parameter for the filter chain by its handle.
+    ///
+    /// If the handle is invalid, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+    fn libra_vk_filter_chain_get_param_by_handle(
+        chain: *const libra_vk_filter_chain_t,
+        handle: libra_param_handle_t,
+        out: *mut MaybeUninit<f32>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let Some(value) = chain.parameters().parameter_value_by_handle(handle.into()) else {
+            return Err(LibrashaderError::UnknownShaderParameterHandle(handle))
+        };
+
+        unsafe {
+            out.write(MaybeUninit::new(value));
+        }
+    }
+}
+
+extern_fn! {
+    /// Get a list of the runtime parameters of the filter chain.
+    ///
+    /// Parameters are listed in the order of their handles, so the handle of a parameter is its
+    /// index in the list. The initial value of a parameter is the value declared in the shader,
+    /// which may differ from the value set by the preset. Parameters that are set by the preset
+    /// but not declared by any pass have an empty description.
+    ///
+    /// The output struct must be freed with `libra_preset_free_runtime_params`.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+    /// - `out` must be an aligned pointer to a `libra_preset_param_list_t`.
+    /// - The output struct should be treated as immutable. Mutating any struct fields
+    ///   in the returned struct may at best cause memory leaks, and at worse
+    ///   cause undefined behaviour when later freed.
+    fn libra_vk_filter_chain_get_runtime_params(
+        chain: *const libra_vk_filter_chain_t,
+        out: *mut MaybeUninit<libra_preset_param_list_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        assert_non_null!(out);
+        let list = crate::presets::runtime_param_list(chain.parameters())?;
+        unsafe {
+            out.write(MaybeUninit::new(list));
+        }
+    }
+}
+
 extern_fn! {
     /// Sets the number of active passes for this chain.
     ///
@@ -418,6 +852,164 @@ extern_fn! {
     }
 }
 
+extern_fn! {
+    /// Gets the number of history frames kept by this chain, and the number of history
+    /// frames requested by the shader passes.
+    ///
+    /// If the history depth was capped with `max_history`, fewer frames may be kept than
+    /// were requested.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+    /// - `requested` and `kept` must be valid and aligned.
+    fn libra_vk_filter_chain_get_history_depth(
+        chain: *const libra_vk_filter_chain_t,
+        requested: *mut MaybeUninit<u32>,
+        kept: *mut MaybeUninit<u32>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let depth = chain.history_depth();
+        unsafe {
+            requested.write(MaybeUninit::new(depth.requested as u32));
+            kept.write(MaybeUninit::new(depth.kept as u32));
+        }
+    }
+}
+
+extern_fn! {
+    /// Invalidates the last `frames` frames of history, such as after a rollback in runahead
+    /// or netplay, so that shader passes do not blend frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+    /// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+    fn libra_vk_filter_chain_invalidate_history(
+        chain: *mut libra_vk_filter_chain_t,
+        frames: u32
+    ) mut |chain| {
+        assert_some_ptr!(mut chain);
+        chain.invalidate_history(frames as usize);
+    }
+}
+
+extern_fn! {
+    /// Sets the mesh to draw the final pass with, in place of the default quad.
+    ///
+    /// The `count` vertices are drawn as a triangle strip. Positions are in the space of the
+    /// default final pass quad, where `(0, 0)` is the top-left and `(1, 1)` the bottom-right
+    /// corner of the viewport before the MVP is applied. Texture coordinates are in the same
+    /// orientation. If `vertices` is null, the default quad is drawn again.
+    ///
+    /// If `vertices` is not null and `count` is less than 3, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+    /// - `vertices` must be either null or a valid and aligned pointer to an array of `count` `libra_vertex_t`.
+    /// - The command buffers recorded with the previous mesh must have completed execution.
+    fn libra_vk_filter_chain_set_output_mesh(
+        chain: *mut libra_vk_filter_chain_t,
+        vertices: *const libra_vertex_t,
+        count: usize
+    ) mut |chain| {
+        assert_some_ptr!(mut chain);
+        let mesh = unsafe { crate::runtime::output_mesh(vertices, count)? };
+        unsafe { chain.set_output_mesh(mesh.as_ref())? };
+    }
+}
+
+extern_fn! {
+    /// Writes GPU timestamps around the commands of the next frame to the given query pool.
+    ///
+    /// The timestamp at `first_query` is written before the first command of the frame, and the
+    /// timestamp at `first_query + 1` after the last command. The queries are only written for
+    /// the next frame, so that frontends can rotate queries between frames in flight.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+    /// - `pool` must be a `VK_QUERY_TYPE_TIMESTAMP` query pool with at least `first_query + 2` queries.
+    /// - Both queries must be reset before the command buffer of the next frame executes.
+    fn libra_vk_filter_chain_set_timestamp_queries(
+        chain: *mut libra_vk_filter_chain_t,
+        pool: vk::QueryPool,
+        first_query: u32
+    ) mut |chain| {
+        assert_some_ptr!(mut chain);
+        unsafe { chain.set_timestamp_queries(pool, first_query) };
+    }
+}
+
+extern_fn! {
+    /// Gets information about the shader pass at the given index, including the framebuffer
+    /// format chosen for the pass.
+    ///
+    /// The strings of the output struct must be freed with `libra_pass_info_free`.
+    /// If the index is out of range, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    fn libra_vk_filter_chain_get_pass_info(
+        chain: *const libra_vk_filter_chain_t,
+        index: u32,
+        out: *mut MaybeUninit<libra_pass_info_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let Some(info) = chain.pass_info(index as usize) else {
+            return Err(LibrashaderError::InvalidParameter("index"));
+        };
+        let info = crate::runtime::pass_info(info)?;
+        unsafe {
+            out.write(MaybeUninit::new(info));
+        }
+    }
+}
+
+extern_fn! {
+    /// Gets the CPU-side statistics of the commands recorded for the last frame, such as the
+    /// number of draw calls.
+    ///
+    /// If the filter chain was not created with the `collect_statistics` option, returns an error.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    fn libra_vk_filter_chain_get_frame_statistics(
+        chain: *const libra_vk_filter_chain_t,
+        out: *mut MaybeUninit<libra_frame_statistics_t>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let Some(statistics) = chain.frame_statistics() else {
+            return Err(LibrashaderError::InvalidParameter("chain"));
+        };
+        unsafe {
+            out.write(MaybeUninit::new(libra_frame_statistics_t {
+                draw_calls: statistics.draw_calls,
+                barriers: statistics.barriers,
+                uploads: statistics.uploads,
+                descriptor_writes: statistics.descriptor_writes,
+            }));
+        }
+    }
+}
+
+extern_fn! {
+    /// Gets whether the final pass of the last recorded frame was not drawn to some outputs,
+    /// because the pipelines for their formats were still being built with the
+    /// `async_pipeline_rebuild` option. Outputs that were not drawn are left unchanged.
+    ///
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+    /// - `out` must be valid and aligned.
+    fn libra_vk_filter_chain_has_pending_pipelines(
+        chain: *const libra_vk_filter_chain_t,
+        out: *mut MaybeUninit<bool>
+    ) |chain| {
+        assert_some_ptr!(chain);
+        let value = chain.has_pending_pipelines();
+        unsafe {
+            out.write(MaybeUninit::new(value))
+        }
+    }
+}
+
 extern_fn! {
     /// Free a Vulkan filter chain.
     ///
@@ -435,3 +1027,63 @@ extern_fn! {
         };
     }
 }
+
+static DEFERRED: DeferredQueue<FilterChain, vk::Fence> = DeferredQueue::new();
+
+/// Free the queued filter chains whose fence is signaled.
+fn collect_deferred() {
+    DEFERRED.collect(|chain, &fence| {
+        // SAFETY: the fence must stay valid until the filter chain is freed.
+        // If the device was lost, the filter chain is no longer in use.
+        unsafe { chain.device().get_fence_status(fence) }.unwrap_or(true)
+    });
+}
+
+extern_fn! {
+    /// Free a Vulkan filter chain once `fence` is signaled, without waiting for the
+    /// frames that were recorded with it to complete.
+    ///
+    /// The filter chain is queued, and freed by a later call to `libra_vk_filter_chain_free_deferred`
+    /// or `libra_vk_filter_chain_collect_deferred` after `fence` is signaled. If `fence` is
+    /// `VK_NULL_HANDLE`, the filter chain is freed immediately, as with `libra_vk_filter_chain_free`.
+    ///
+    /// The resulting value in `chain` then becomes null.
+    /// ## Safety
+    /// - `chain` must be either null or a valid and aligned pointer to an initialized `libra_vk_filter_chain_t`.
+    /// - `fence` must be `VK_NULL_HANDLE`, or a fence of the device the filter chain was created with
+    ///   that is signaled after the last command buffer that the filter chain recorded into
+    ///   has completed. The fence must stay valid until the filter chain is freed.
+    fn libra_vk_filter_chain_free_deferred(
+        chain: *mut libra_vk_filter_chain_t,
+        fence: vk::Fence
+    ) {
+        assert_non_null!(chain);
+        let chain = unsafe {
+            let chain_ptr = &mut *chain;
+            let chain = chain_ptr.take();
+            Box::from_raw(chain.unwrap().as_ptr())
+        };
+
+        if fence != vk::Fence::null() {
+            DEFERRED.push(chain, fence);
+        } else {
+            drop(chain);
+        }
+
+        collect_deferred();
+    }
+}
+
+extern_fn! {
+    /// Free the Vulkan filter chains queued with `libra_vk_filter_chain_free_deferred`
+    /// whose fence is signaled.
+    ///
+    /// Frontends should call this before destroying the device, after waiting for the device
+    /// to be idle, so that every queued filter chain is freed.
+    ///
+    /// ## Safety
+    /// - The fences of the queued filter chains must still be valid.
+    fn libra_vk_filter_chain_collect_deferred() {
+        collect_deferred();
+    }
+}
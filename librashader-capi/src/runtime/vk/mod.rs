@@ -1,5 +1,7 @@
 //! C API for the librashader Vulkan Runtime (`libra_vk_*`).
 
+mod device_context;
 mod filter_chain;
+pub use device_context::*;
 pub use filter_chain::*;
 const _: () = crate::assert_thread_safe::<librashader::runtime::vk::FilterChain>();
@@ -0,0 +1,83 @@
+//! librashader parameter translations C API (`libra_preset_translations_*`).
+
+use crate::ctypes::libra_preset_translations_t;
+use crate::error::{assert_non_null, assert_some_ptr};
+
+use librashader::presets::ParameterTranslations;
+use std::ffi::{c_char, CStr};
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+use crate::ffi::extern_fn;
+
+const _: () = crate::assert_thread_safe::<ParameterTranslations>();
+
+extern_fn! {
+    /// Create an empty set of parameter translations.
+    ///
+    /// Translations map parameter names to localized descriptions, and can be used with any
+    /// number of presets with `libra_preset_get_runtime_params_localized`.
+    ///
+    /// ## Safety
+    ///  - `out` must be either null, or an aligned pointer to an uninitialized or invalid `libra_preset_translations_t`.
+    /// ## Returns
+    ///  - If any parameters are null, `out` is unchanged, and this function returns `LIBRA_ERR_INVALID_PARAMETER`.
+    fn libra_preset_translations_create(
+        out: *mut MaybeUninit<libra_preset_translations_t>
+    ) {
+        assert_non_null!(out);
+
+        unsafe {
+            out.write(MaybeUninit::new(NonNull::new(Box::into_raw(Box::new(
+                ParameterTranslations::new(),
+            )))));
+        }
+    }
+}
+
+extern_fn! {
+    /// Free the parameter translations.
+    ///
+    /// If `translations` is null, this function does nothing. The resulting value in `translations` then becomes
+    /// null.
+    ///
+    /// ## Safety
+    /// - `translations` must be a valid and aligned pointer to a `libra_preset_translations_t`
+    fn libra_preset_translations_free(translations: *mut libra_preset_translations_t) {
+        assert_non_null!(translations);
+        unsafe {
+            let translations_ptr = &mut *translations;
+            let translations = translations_ptr.take();
+            drop(Box::from_raw(translations.unwrap().as_ptr()));
+        }
+    }
+}
+
+extern_fn! {
+    /// Set the localized description of a parameter.
+    ///
+    /// Subsequent calls with the same parameter name will overwrite the description.
+    ///
+    /// ## Safety
+    /// - `translations` must be null or a valid and aligned pointer to a `libra_preset_translations_t`.
+    /// - `name` must be null or a valid and aligned pointer to a string.
+    /// - `description` must be null or a valid and aligned pointer to a string.
+    fn libra_preset_translations_set(
+        translations: *mut libra_preset_translations_t,
+        name: *const c_char,
+        description: *const c_char,
+    ) |name, description|; mut |translations| {
+        let name = unsafe {
+            CStr::from_ptr(name)
+        };
+        let name = name.to_str()?;
+
+        let description = unsafe {
+            CStr::from_ptr(description)
+        };
+        let description = description.to_str()?;
+
+        assert_some_ptr!(mut translations);
+        translations.insert(name, description);
+    }
+}
@@ -1,5 +1,8 @@
 //! librashader instance version helpers.
 
+use crate::ctypes::LIBRA_RUNTIME;
+use std::ffi::{c_char, CStr};
+
 /// API version type alias.
 pub type LIBRASHADER_API_VERSION = usize;
 /// ABI version type alias.
@@ -21,7 +24,60 @@ pub type LIBRASHADER_ABI_VERSION = usize;
 /// - API version 2: 0.6.0
 ///     - Added original aspect uniforms
 ///     - Added frame time uniforms
-pub const LIBRASHADER_CURRENT_VERSION: LIBRASHADER_API_VERSION = 2;
+/// - API version 3: 0.6.3
+///     - Added fallback textures
+///     - Added preset sandboxing
+///     - Added parameter handles
+///     - Added MVP transpose and Y-flip frame options
+///     - Added bottom-left input origin option for OpenGL
+///     - Added identity pass for filter chains without enabled passes
+///     - Added identity filter chain constructors
+///     - Added history depth cap option and history depth getters
+///     - Added built-in scaling kernels for the identity pass
+///     - Added the `scale_final_pass` frame option
+///     - Added keyed mutex support for Direct3D 11 inputs
+///     - Added Y'CbCr conversion input support for Vulkan
+///     - Added sRGB lookup textures
+///     - Added the FrameJitter uniform and jitter frame options
+///     - Added framebuffer format fallbacks and `libra_*_filter_chain_get_pass_info`
+///     - Added the `format_fallback` filter chain option
+///     - Added external texture input support for OpenGL
+///     - Added the `disable_temporal` filter chain option
+///     - Added `libra_*_filter_chain_invalidate_history`
+///     - Added the `progress` filter chain option
+///     - Added the `cancel` filter chain option and `libra_cancel_token_*`
+///     - Added the `strict` preset option and `libra_preset_get_warnings`
+///     - Added the `portability` Vulkan filter chain option
+///     - Added parameter widget hints and `libra_preset_get_runtime_param_widgets`
+///     - Added parameter translations and `libra_preset_get_runtime_params_localized`
+///     - Added the `freeze_frame_count_on_rewind` filter chain option
+///     - Added per-pass defines and `libra_preset_set_pass_define`
+///     - Added the `async_pipeline_rebuild` filter chain option for Vulkan and Direct3D 12,
+///       and `libra_vk_filter_chain_has_pending_pipelines`
+///     - Added `libra_instance_runtime_supported` and build information getters
+///     - Added `libra_preset_clone`
+///     - Added the `collect_statistics` filter chain option and `libra_*_filter_chain_get_frame_statistics`
+///     - Added `libra_instance_set_allocator`
+///     - Added `libra_error_write_source` and `libra_error_write_backtrace`
+///     - Added `libra_instance_option_supported`
+///     - Added the `device_context` filter chain option and `libra_*_device_context_*`
+///     - Added `libra_*_filter_chain_free_deferred` and `libra_*_filter_chain_collect_deferred` for Vulkan, Direct3D 12 and Metal
+///     - Added `libra_*_filter_chain_set_output_mesh` and `libra_vertex_t`
+///     - Added the `color_assist` filter chain option
+///     - Added the `black_frame_insertion` filter chain option
+///     - Added the `latency_markers` filter chain option and `libra_*_filter_chain_set_timestamp_queries` for Vulkan and Direct3D 12
+///     - Added `libra_instance_create`, `libra_instance_free` and the `instance` filter chain option
+///     - Added the `output_layout`, `final_output_layout` and `output_view` Vulkan frame options
+///     - Added `libra_*_filter_chain_create_async` and the `poll_pending`, `wait_pending` and
+///       `free_pending` functions of the pending filter chain handles
+///     - Added `libra_*_filter_chain_get_runtime_params` to list the parameters of a filter chain
+///     - Added the alias, source path, filter, wrap and scaling of passes to `libra_pass_info_t`, and `libra_pass_info_free`
+///     - Added the `LIBRA_SCALE_TYPE_VIEWPORT_ASPECT` and `LIBRA_SCALE_TYPE_ORIGINAL_ASPECT` scale types
+///     - Vulkan filter chains fall back to render pass objects if `use_dynamic_rendering` is set but the device does not support dynamic rendering
+///     - Added the `final_render_pass`, `final_subpass` and `final_command_buffer` Vulkan frame options
+///     - Added the `resource_heap` and `sampler_heap` Direct3D 12 filter chain options
+///     - Added the `mipmap_handoff` Direct3D 12 frame option
+pub const LIBRASHADER_CURRENT_VERSION: LIBRASHADER_API_VERSION = 3;
 
 /// The current version of the librashader ABI.
 /// Used by the loader to check ABI compatibility.
@@ -58,3 +114,98 @@ pub type PFN_libra_instance_api_version = extern "C" fn() -> LIBRASHADER_API_VER
 pub extern "C" fn libra_instance_api_version() -> LIBRASHADER_API_VERSION {
     LIBRASHADER_CURRENT_VERSION
 }
+
+/// Function pointer definition for libra_instance_runtime_supported
+pub type PFN_libra_instance_runtime_supported = extern "C" fn(runtime: LIBRA_RUNTIME) -> bool;
+/// Get whether the loaded instance was built with support for the given runtime.
+///
+/// A runtime is only supported if its feature was enabled and the instance was built
+/// for a platform where that runtime is available. For example, Direct3D 12 is never
+/// supported by instances built for Linux or macOS.
+#[no_mangle]
+pub extern "C" fn libra_instance_runtime_supported(runtime: LIBRA_RUNTIME) -> bool {
+    match runtime {
+        LIBRA_RUNTIME::GlCore => cfg!(feature = "runtime-opengl"),
+        LIBRA_RUNTIME::Vulkan => cfg!(feature = "runtime-vulkan"),
+        LIBRA_RUNTIME::D3D9 => cfg!(all(target_os = "windows", feature = "runtime-d3d9")),
+        LIBRA_RUNTIME::D3D11 => cfg!(all(target_os = "windows", feature = "runtime-d3d11")),
+        LIBRA_RUNTIME::D3D12 => cfg!(all(target_os = "windows", feature = "runtime-d3d12")),
+        LIBRA_RUNTIME::Metal => cfg!(all(target_vendor = "apple", feature = "runtime-metal")),
+    }
+}
+
+/// Function pointer definition for libra_instance_option_supported
+pub type PFN_libra_instance_option_supported = unsafe extern "C" fn(
+    options: *const c_char,
+    field: *const c_char,
+    version: LIBRASHADER_API_VERSION,
+) -> bool;
+/// Get whether the loaded instance honours a field of an options struct of the given
+/// API version, such as `rotation` of `frame_gl_opt_t` for API version 1.
+///
+/// Options structs are read according to their `version` field. Fields that were added in
+/// a later API version than `version`, or that the loaded instance does not know about
+/// because it is older than the frontend, are not read and take their default value instead.
+/// Frontends can use this to tell which options an instance honours before relying on them.
+///
+/// Returns false if `options` or `field` are null or unknown, or if the runtime of the
+/// options struct is not supported by the loaded instance.
+///
+/// ## Safety
+/// - `options` and `field` must be either null or valid null-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn libra_instance_option_supported(
+    options: *const c_char,
+    field: *const c_char,
+    version: LIBRASHADER_API_VERSION,
+) -> bool {
+    if options.is_null() || field.is_null() {
+        return false;
+    }
+
+    // SAFETY: the caller guarantees that non-null strings are valid.
+    let (options, field) = unsafe { (CStr::from_ptr(options), CStr::from_ptr(field)) };
+    let (Ok(options), Ok(field)) = (options.to_str(), field.to_str()) else {
+        return false;
+    };
+
+    crate::runtime::reads_option(options, field, version)
+}
+
+/// Function pointer definition for libra_instance_version_string
+pub type PFN_libra_instance_version_string = extern "C" fn() -> *const c_char;
+/// Get the version of the loaded instance as a null-terminated string, such as `0.6.2`.
+///
+/// The returned string is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn libra_instance_version_string() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Function pointer definition for libra_instance_commit_string
+pub type PFN_libra_instance_commit_string = extern "C" fn() -> *const c_char;
+/// Get the commit the loaded instance was built from as a null-terminated string.
+///
+/// The commit is taken from the `LIBRASHADER_COMMIT` environment variable at build time if set,
+/// otherwise from the git checkout the instance was built in. If neither is available,
+/// the string is `unknown`.
+///
+/// The returned string is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn libra_instance_commit_string() -> *const c_char {
+    concat!(env!("LIBRASHADER_COMMIT"), "\0").as_ptr().cast()
+}
+
+/// Function pointer definition for libra_instance_features_string
+pub type PFN_libra_instance_features_string = extern "C" fn() -> *const c_char;
+/// Get the Cargo features the loaded instance was built with as a null-terminated,
+/// comma-separated string, such as `runtime-opengl,runtime-vulkan,stable`.
+///
+/// Use `libra_instance_runtime_supported` to check whether a runtime is actually
+/// available, since runtime features are ignored on platforms that do not support them.
+///
+/// The returned string is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn libra_instance_features_string() -> *const c_char {
+    concat!(env!("LIBRASHADER_FEATURES"), "\0").as_ptr().cast()
+}
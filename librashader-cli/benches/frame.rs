@@ -0,0 +1,77 @@
+//! Benchmarks for the per-frame CPU overhead of each runtime.
+//!
+//! Every sample creates a new filter chain and renders a number of frames headlessly.
+//! Linear sampling is used so that the per-frame time is estimated from the slope of
+//! the samples, which excludes the one-time cost of creating the filter chain.
+//!
+//! Runtimes that are enabled but can not create a device are skipped.
+//!
+//! Run with `cargo bench -p librashader-cli --bench frame`.
+
+use criterion::{criterion_group, criterion_main, Criterion, SamplingMode};
+use librashader::presets::{ShaderFeatures, ShaderPreset};
+use librashader::runtime::Size;
+use librashader_test::render::RenderTest;
+use std::time::Instant;
+
+const PRESET: &str = "../test/basic.slangp";
+const SIZE: Size<u32> = Size {
+    width: 256,
+    height: 224,
+};
+
+#[allow(unused)]
+fn bench_runtime<T: RenderTest>(c: &mut Criterion, name: &str) {
+    let image = image::RgbaImage::new(SIZE.width, SIZE.height);
+    let mut test = match T::new_with_image(image) {
+        Ok(test) => test,
+        Err(err) => {
+            eprintln!("skipping {name}: {err}");
+            return;
+        }
+    };
+
+    let preset = ShaderPreset::try_parse(PRESET, ShaderFeatures::NONE).unwrap();
+
+    let mut group = c.benchmark_group("frame");
+    group.sampling_mode(SamplingMode::Linear);
+    group.bench_function(name, |b| {
+        b.iter_custom(|frames| {
+            let start = Instant::now();
+            test.render_sequence_with_preset_and_params(
+                preset.clone(),
+                frames as usize - 1..=frames as usize - 1,
+                Some(SIZE),
+                None,
+                None,
+                None,
+                &mut |_, _| Ok(()),
+            )
+            .unwrap();
+            start.elapsed()
+        })
+    });
+    group.finish();
+}
+
+fn bench_frame(c: &mut Criterion) {
+    #[cfg(feature = "opengl")]
+    bench_runtime::<librashader_test::render::gl::OpenGl3>(c, "opengl3");
+    #[cfg(feature = "opengl")]
+    bench_runtime::<librashader_test::render::gl::OpenGl4>(c, "opengl4");
+    #[cfg(feature = "vulkan")]
+    bench_runtime::<librashader_test::render::vk::Vulkan>(c, "vulkan");
+    #[cfg(feature = "wgpu")]
+    bench_runtime::<librashader_test::render::wgpu::Wgpu>(c, "wgpu");
+    #[cfg(all(windows, feature = "d3d9"))]
+    bench_runtime::<librashader_test::render::d3d9::Direct3D9>(c, "d3d9");
+    #[cfg(all(windows, feature = "d3d11"))]
+    bench_runtime::<librashader_test::render::d3d11::Direct3D11>(c, "d3d11");
+    #[cfg(all(windows, feature = "d3d12"))]
+    bench_runtime::<librashader_test::render::d3d12::Direct3D12>(c, "d3d12");
+    #[cfg(all(target_vendor = "apple", feature = "metal"))]
+    bench_runtime::<librashader_test::render::mtl::Metal>(c, "metal");
+}
+
+criterion_group!(benches, bench_frame);
+criterion_main!(benches);
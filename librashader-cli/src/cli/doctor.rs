@@ -0,0 +1,304 @@
+use image::{Rgba, RgbaImage};
+use librashader::presets::{ShaderFeatures, ShaderPreset};
+use librashader::runtime::Size;
+use std::fmt::{Display, Formatter};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+
+/// The size of the input and output images of the self-test.
+const SIZE: u32 = 8;
+
+/// The colours of the top left, top right, bottom left and bottom right quadrants of the
+/// input image.
+///
+/// These are pure colours, so that the expected output does not depend on whether the
+/// runtime renders in linear or sRGB colour space.
+const INPUT: [[u8; 3]; 4] = [[255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 255]];
+
+/// The colours of the quadrants of the output image, which are the inverse of the input.
+const REFERENCE: [[u8; 3]; 4] = [[0, 255, 255], [255, 0, 255], [255, 255, 0], [0, 0, 0]];
+
+/// The maximum difference of a channel from the reference.
+const TOLERANCE: u8 = 2;
+
+const PRESET: &str = r#"shaders = 1
+shader0 = "doctor.slang"
+filter_linear0 = false
+scale_type0 = source
+scale0 = 1.0
+"#;
+
+const SHADER: &str = r#"#version 450
+
+layout(set = 0, binding = 0, std140) uniform UBO
+{
+   mat4 MVP;
+};
+
+#pragma stage vertex
+layout(location = 0) in vec4 Position;
+layout(location = 1) in vec2 TexCoord;
+layout(location = 0) out vec2 vTexCoord;
+void main()
+{
+   gl_Position = MVP * Position;
+   vTexCoord = TexCoord;
+}
+
+#pragma stage fragment
+layout(location = 0) in vec2 vTexCoord;
+layout(location = 0) out vec4 FragColor;
+layout(binding = 1) uniform sampler2D Source;
+void main()
+{
+   FragColor = vec4(vec3(1.0) - texture(Source, vTexCoord).rgb, 1.0);
+}
+"#;
+
+/// The built-in shader preset of the self-test, written to a temporary directory
+/// that is removed when dropped.
+pub struct BuiltinPreset {
+    dir: PathBuf,
+    pub preset: ShaderPreset,
+}
+
+impl BuiltinPreset {
+    pub fn new() -> anyhow::Result<Self> {
+        let dir = std::env::temp_dir().join(format!("librashader-doctor-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("doctor.slang"), SHADER)?;
+        std::fs::write(dir.join("doctor.slangp"), PRESET)?;
+
+        // the shader sources are loaded when a filter chain is created, so the directory is
+        // kept until the self-test is done.
+        let preset = ShaderPreset::try_parse(dir.join("doctor.slangp"), ShaderFeatures::NONE);
+        let preset = match preset {
+            Ok(preset) => preset,
+            Err(err) => {
+                let _ = std::fs::remove_dir_all(&dir);
+                return Err(err.into());
+            }
+        };
+
+        Ok(Self { dir, preset })
+    }
+}
+
+impl Drop for BuiltinPreset {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn quadrant(x: u32, y: u32) -> usize {
+    (x >= SIZE / 2) as usize + 2 * (y >= SIZE / 2) as usize
+}
+
+/// Get the input image of the self-test.
+pub fn input_image() -> RgbaImage {
+    RgbaImage::from_fn(SIZE, SIZE, |x, y| {
+        let [r, g, b] = INPUT[quadrant(x, y)];
+        Rgba([r, g, b, 255])
+    })
+}
+
+/// Get the size of the output of the self-test.
+pub fn output_size() -> Size<u32> {
+    Size::new(SIZE, SIZE)
+}
+
+/// The step of the self-test that is running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// Creating the device and the test harness.
+    Device,
+    /// Creating the filter chain and rendering a frame.
+    Render,
+}
+
+/// The result of the self-test of a runtime.
+#[derive(Debug)]
+pub enum Status {
+    /// The runtime rendered the expected output.
+    Ok,
+    /// No device could be created for the runtime.
+    Unavailable(String),
+    /// The runtime failed to render, or rendered the wrong output.
+    Failed {
+        stage: &'static str,
+        message: String,
+    },
+}
+
+impl Status {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Unavailable(_) => "unavailable",
+            Status::Failed { .. } => "failed",
+        }
+    }
+}
+
+/// The result of the self-test of a runtime.
+pub struct Report {
+    pub runtime: String,
+    pub status: Status,
+}
+
+impl Report {
+    pub fn to_json(&self) -> serde_json::Value {
+        match &self.status {
+            Status::Ok => serde_json::json!({
+                "runtime": self.runtime,
+                "status": self.status.name(),
+            }),
+            Status::Unavailable(message) => serde_json::json!({
+                "runtime": self.runtime,
+                "status": self.status.name(),
+                "message": message,
+            }),
+            Status::Failed { stage, message } => serde_json::json!({
+                "runtime": self.runtime,
+                "status": self.status.name(),
+                "stage": stage,
+                "message": message,
+            }),
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (runtime, status) = (&self.runtime, self.status.name());
+        match &self.status {
+            Status::Ok => write!(f, "{runtime:<10} {status}"),
+            Status::Unavailable(message) => write!(f, "{runtime:<10} {status:<12}{message}"),
+            Status::Failed { stage, message } => {
+                write!(f, "{runtime:<10} {status:<12}{stage}: {message}")
+            }
+        }
+    }
+}
+
+/// Run the self-test of a runtime.
+///
+/// `render` creates the test harness of the runtime, advancing the stage once the harness
+/// is created, and renders the built-in preset against the input image.
+pub fn check_runtime(
+    runtime: String,
+    render: impl FnOnce(&mut Stage) -> anyhow::Result<RgbaImage>,
+) -> Report {
+    let mut stage = Stage::Device;
+
+    // runtimes may panic when the driver misbehaves, which should be reported
+    // as a failure of that runtime rather than abort the self-test.
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = catch_unwind(AssertUnwindSafe(|| render(&mut stage)));
+    std::panic::set_hook(hook);
+
+    let result = match result {
+        Ok(result) => result.map_err(|err| format!("{err:#}")),
+        Err(panic) => Err(panic_message(panic)),
+    };
+
+    let status = match (result, stage) {
+        (Err(message), Stage::Device) => Status::Unavailable(message),
+        (Err(message), Stage::Render) => Status::Failed {
+            stage: "render",
+            message,
+        },
+        (Ok(image), _) => match check_output(&image) {
+            Ok(()) => Status::Ok,
+            Err(message) => Status::Failed {
+                stage: "output",
+                message,
+            },
+        },
+    };
+
+    Report { runtime, status }
+}
+
+fn panic_message(panic: Box<dyn std::any::Any + Send>) -> String {
+    let message = panic
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    format!("panicked: {message}")
+}
+
+/// Check the output of the self-test against the reference.
+fn check_output(image: &RgbaImage) -> Result<(), String> {
+    if image.dimensions() != (SIZE, SIZE) {
+        return Err(format!(
+            "output is {}x{}, expected {SIZE}x{SIZE}",
+            image.width(),
+            image.height()
+        ));
+    }
+
+    let matches = |flip: bool| {
+        image.enumerate_pixels().all(|(x, y, pixel)| {
+            let y = if flip { SIZE - 1 - y } else { y };
+            matches_reference(pixel, REFERENCE[quadrant(x, y)])
+        })
+    };
+
+    if matches(false) {
+        return Ok(());
+    }
+
+    if image.pixels().all(|pixel| pixel.0[..3] == [0, 0, 0]) {
+        return Err("output is black".to_string());
+    }
+
+    if matches(true) {
+        return Err("output is upside down".to_string());
+    }
+
+    let (x, y, pixel) = image
+        .enumerate_pixels()
+        .find(|(x, y, pixel)| !matches_reference(pixel, REFERENCE[quadrant(*x, *y)]))
+        .expect("a pixel does not match the reference");
+    Err(format!(
+        "pixel ({x}, {y}) is {:?}, expected {:?}",
+        &pixel.0[..3],
+        REFERENCE[quadrant(x, y)]
+    ))
+}
+
+fn matches_reference(pixel: &Rgba<u8>, reference: [u8; 3]) -> bool {
+    pixel.0[..3]
+        .iter()
+        .zip(reference)
+        .all(|(&channel, reference)| channel.abs_diff(reference) <= TOLERANCE)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_output, input_image, INPUT, REFERENCE};
+    use image::imageops;
+
+    #[test]
+    fn reference_is_inverse_of_input() {
+        for (input, reference) in INPUT.iter().zip(REFERENCE) {
+            assert_eq!(input.map(|channel| 255 - channel), reference);
+        }
+    }
+
+    #[test]
+    fn output() {
+        let mut image = input_image();
+        imageops::invert(&mut image);
+        assert_eq!(check_output(&image), Ok(()));
+
+        assert_eq!(
+            check_output(&imageops::flip_vertical(&image)),
+            Err("output is upside down".to_string())
+        );
+        assert!(check_output(&input_image()).is_err());
+    }
+}
@@ -1,26 +1,49 @@
 use anyhow::anyhow;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use image::codecs::png::PngEncoder;
+use image::{DynamicImage, ImageFormat, RgbaImage};
+use librashader::preprocess::PreprocessError;
 use librashader::presets::context::ContextItem;
-use librashader::presets::{ShaderFeatures, ShaderPreset, ShaderPresetPack, WildcardContext};
+use librashader::presets::{
+    ParsePresetError, ShaderFeatures, ShaderPreset, ShaderPresetPack, WildcardContext,
+};
 use librashader::reflect::cross::{GlslVersion, HlslShaderModel, MslVersion, SpirvCross};
 use librashader::reflect::naga::{Naga, NagaLoweringOptions};
 use librashader::reflect::semantics::ShaderSemantics;
-use librashader::reflect::{CompileShader, FromCompilation, ReflectShader, SpirvCompilation};
+use librashader::reflect::{
+    CompileShader, FromCompilation, ReflectShader, ShaderCompileError, ShaderReflectError,
+    ShaderReflection, SpirvCompilation,
+};
 use librashader::runtime::Size;
-use librashader::{FastHashMap, ShortString};
+use librashader::{FastHashMap, ScalingKernel, ShortString};
 use librashader_runtime::parameters::RuntimeParameters;
 use librashader_test::render::{CommonFrameOptions, RenderTest};
+use librashader_test::{compare, golden};
 use std::fs::File;
-use std::io::Write;
+use std::io::{BufWriter, Cursor, Write};
 use std::path::{Path, PathBuf};
 
+mod doctor;
+mod validate;
+#[cfg(feature = "ffmpeg")]
+mod video;
+mod y4m;
+
 /// Helpers and utilities to reflect and debug 'slang' shaders and presets.
 #[derive(Parser, Debug)]
 #[command(version, about)]
 struct Args {
     #[command(subcommand)]
     command: Commands,
+    /// The format to write results and errors in.
+    ///
+    /// With `json`, every command writes its result to stdout as a JSON object, and errors are written to stdout
+    /// as a JSON object with a stable error code.
+    ///
+    /// Commands that write an image, stream, video or preset pack write a record of the written output instead,
+    /// and can not write their output to stdout. `reflect` writes a record for every reflected pass.
+    #[arg(value_enum, long, global = true, default_value = "text")]
+    output_format: OutputFormat,
 }
 
 #[derive(clap::Args, Debug)]
@@ -88,6 +111,15 @@ impl From<FrameOptionsArgs> for CommonFrameOptions {
             aspect_ratio: value.aspect_ratio.unwrap_or(0.0),
             frametime_delta: value.frametime_delta.unwrap_or(0),
             frames_per_second: value.frames_per_second.unwrap_or(1.0),
+            transpose_mvp: false,
+            flip_mvp_y: value.flip_mvp_y,
+            identity_scaling: ScalingKernel::from(value.identity_scaling),
+            scale_final_pass: value.scale_final_pass,
+            jitter_sequence: value.jitter_sequence,
+            jitter_offset: value
+                .jitter_offset
+                .and_then(|offset| offset.try_into().ok())
+                .unwrap_or([0.0, 0.0]),
         }
     }
 }
@@ -133,6 +165,50 @@ struct FrameOptionsArgs {
     /// The time between the previous and current frame. The default is 0.
     #[arg(long)]
     pub frametime_delta: Option<u32>,
+    /// Flip the output vertically by flipping the Y axis of the MVP.
+    #[arg(long)]
+    pub flip_mvp_y: bool,
+    /// The kernel used to scale the input to the viewport when there are no enabled passes,
+    /// or to scale the output of the final pass with `--scale-final-pass`.
+    /// 0 = nearest-neighbour, 1 = bilinear, 2 = bicubic, 3 = Lanczos. The default is 0.
+    #[arg(long, default_value_t = 0)]
+    pub identity_scaling: u32,
+    /// Draw the final pass at the size given by its scaling, and scale its output to the
+    /// viewport with the identity scaling kernel.
+    #[arg(long)]
+    pub scale_final_pass: bool,
+    /// The sequence of sub-pixel offsets bound to the `FrameJitter` uniform.
+    /// 0 = no jitter, 1 = Halton (2, 3), 2 = `--jitter-offset`. The default is 0.
+    #[arg(long, default_value_t = 0)]
+    pub jitter_sequence: u32,
+    /// The sub-pixel offset of the frame in pixels, if the jitter sequence is 2.
+    #[arg(long, num_args = 2, value_names = ["X", "Y"], allow_hyphen_values = true)]
+    pub jitter_offset: Option<Vec<f32>>,
+}
+
+#[derive(clap::Args, Debug)]
+struct ImageOutputArgs {
+    /// The image format to write the output in.
+    ///
+    /// If not specified, the format is inferred from the extension of the output path,
+    /// or PNG if writing to stdout.
+    #[arg(value_enum, long)]
+    image_format: Option<ImageOutputFormat>,
+    /// The bit depth of each channel of the output image.
+    ///
+    /// `16` is only supported with PNG, and `float` is only supported with EXR.
+    /// If not specified, `float` is used for EXR, and `8` for every other format.
+    ///
+    /// Note that the frame is read back from the runtime with 8 bits per channel,
+    /// higher bit depths do not increase the precision of the rendered image.
+    #[arg(value_enum, long)]
+    bit_depth: Option<ImageBitDepth>,
+    /// Discard the alpha channel of the rendered image.
+    ///
+    /// By default the alpha channel is kept. JPEG does not support alpha, so it is
+    /// always discarded for JPEG output.
+    #[arg(long)]
+    strip_alpha: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -145,7 +221,73 @@ enum Commands {
         render: RenderArgs,
         /// The path to the output image
         ///
-        /// If `-`, writes the image in PNG format to stdout.
+        /// If `-`, writes the image to stdout.
+        #[arg(short, long)]
+        out: PathBuf,
+        #[clap(flatten)]
+        output: ImageOutputArgs,
+        /// The runtime to use to render the shader preset.
+        #[arg(value_enum, short, long)]
+        runtime: Runtime,
+    },
+    /// Render a shader preset against an image for a sequence of frames, and write
+    /// the frames as an uncompressed YUV4MPEG2 (Y4M) video stream.
+    ///
+    /// The stream can be piped directly into ffmpeg or other video tools.
+    Stream {
+        #[clap(flatten)]
+        preset: PresetArgs,
+        #[clap(flatten)]
+        render: RenderArgs,
+        /// The first frame to write to the stream.
+        ///
+        /// Every frame up to and including `--frame` is rendered, but only the
+        /// frames from this frame onwards are written to the stream.
+        #[arg(long, default_value_t = 0)]
+        start_frame: usize,
+        /// The frame rate of the stream, as either an integer or a fraction `NUM/DEN`.
+        #[arg(long, default_value = "60")]
+        framerate: String,
+        /// The path to write the stream to.
+        ///
+        /// If `-`, writes the stream to stdout.
+        #[arg(short, long)]
+        out: PathBuf,
+        /// The runtime to use to render the shader preset.
+        #[arg(value_enum, short, long)]
+        runtime: Runtime,
+    },
+    /// Render a shader preset against every frame of a video, and encode
+    /// the output as a video.
+    ///
+    /// Only the video stream of the input is processed, any audio is discarded.
+    #[cfg(feature = "ffmpeg")]
+    Video {
+        #[clap(flatten)]
+        preset: PresetArgs,
+        /// The path to the input video.
+        #[arg(short, long)]
+        input: PathBuf,
+        /// The dimensions of the output video.
+        ///
+        /// This is given in either explicit dimensions `WIDTHxHEIGHT`, or a
+        /// percentage of the input video in `SCALE%`.
+        #[arg(short, long)]
+        dimensions: Option<String>,
+        /// Parameters to pass to the shader preset, comma separated with equals signs.
+        ///
+        /// For example, crt_gamma=2.5,halation_weight=0.001
+        #[arg(long, value_delimiter = ',', num_args = 1..)]
+        params: Option<Vec<String>>,
+        /// Set the number of passes enabled for the preset.
+        #[arg(long)]
+        passes_enabled: Option<usize>,
+        #[clap(flatten)]
+        options: Option<FrameOptionsArgs>,
+        /// The path to the output video.
+        ///
+        /// The container is inferred from the extension, and the video is encoded
+        /// with the default video codec of the container.
         #[arg(short, long)]
         out: PathBuf,
         /// The runtime to use to render the shader preset.
@@ -165,12 +307,39 @@ enum Commands {
         /// The runtime to compare to
         #[arg(value_enum, short, long)]
         right: Runtime,
+        /// The metric to compare the rendered frames with.
+        #[arg(value_enum, short, long, default_value = "hybrid")]
+        metric: compare::Metric,
         /// The path to write the similarity image.
         ///
         /// If `-`, writes the image to stdout.
         #[arg(short, long)]
         out: Option<PathBuf>,
     },
+    /// Render every shader preset of a reference image corpus with a runtime, and check
+    /// the rendered frames against the golden images of the runtime.
+    ///
+    /// Exits with status code 0 if every rendered frame is within its tolerance, 1 if the
+    /// corpus could not be loaded or the runtime could not be created, and 3 if any preset
+    /// failed to render, deviated from its golden image, or has no golden image.
+    Golden {
+        /// The path to the manifest of the corpus.
+        #[arg(short, long)]
+        corpus: PathBuf,
+        /// The runtime to render the corpus with.
+        #[arg(value_enum, short, long)]
+        runtime: Runtime,
+        /// Write the rendered frames as the golden images of the runtime instead of
+        /// checking them.
+        #[arg(long)]
+        update: bool,
+        /// The names of the presets to render, comma separated. Defaults to every preset.
+        #[arg(long, value_delimiter = ',', num_args = 1..)]
+        only: Option<Vec<String>>,
+        /// The directory to write the rendered frames of presets that failed the check to.
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+    },
     /// Parse a preset and get a JSON representation of the data.
     Parse {
         #[clap(flatten)]
@@ -244,12 +413,51 @@ enum Commands {
         flags: ShaderFeatureArgs,
 
         /// The pass index to use.
+        ///
+        /// If not specified, every pass of the preset is reflected.
         #[arg(short, long)]
-        index: usize,
+        index: Option<usize>,
 
         #[arg(value_enum, short, long, default_value = "cross")]
         backend: ReflectionBackend,
     },
+    /// Validate that every pass of a shader preset can be preprocessed, compiled and reflected,
+    /// without creating a runtime.
+    ///
+    /// Exits with status code 0 if the preset is valid, 1 if validation could not be run,
+    /// 2 if the arguments are invalid, and 3 if any errors were found, or any warnings were
+    /// found with `--warnings-as-errors`.
+    Validate {
+        #[clap(flatten)]
+        preset: PresetArgs,
+
+        #[clap(flatten)]
+        flags: ShaderFeatureArgs,
+
+        /// Treat warnings as errors when determining the exit status.
+        #[arg(short = 'W', long)]
+        warnings_as_errors: bool,
+
+        /// Categories of diagnostics to ignore, comma separated.
+        #[arg(value_enum, long, value_delimiter = ',', num_args = 1..)]
+        ignore: Vec<validate::Category>,
+    },
+    /// Check that each runtime works with the local GPU and driver, by rendering a built-in
+    /// shader preset and comparing the output against a reference.
+    ///
+    /// Runtimes for which no device could be created are reported as unavailable.
+    ///
+    /// Exits with status code 0 if every available runtime rendered the expected output,
+    /// 1 if the self-test could not be run, and 3 if any available runtime failed.
+    Doctor,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    #[clap(name = "text")]
+    Text,
+    #[clap(name = "json")]
+    Json,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -296,6 +504,28 @@ enum PackFormat {
     MsgPack,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum ImageOutputFormat {
+    #[clap(name = "png")]
+    Png,
+    #[clap(name = "jpeg")]
+    Jpeg,
+    #[clap(name = "webp")]
+    WebP,
+    #[clap(name = "exr")]
+    OpenExr,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum ImageBitDepth {
+    #[clap(name = "8")]
+    Eight,
+    #[clap(name = "16")]
+    Sixteen,
+    #[clap(name = "float")]
+    Float,
+}
+
 #[derive(clap::ValueEnum, Clone, Debug)]
 enum Runtime {
     #[cfg(feature = "opengl")]
@@ -332,7 +562,7 @@ enum ShaderDefinesEnums {
     FrametimeUniforms,
 }
 
-#[derive(clap::ValueEnum, Clone, Debug)]
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
 enum ReflectionBackend {
     #[clap(name = "cross")]
     SpirvCross,
@@ -342,40 +572,62 @@ enum ReflectionBackend {
 
 macro_rules! get_runtime {
     ($rt:ident, $image:ident) => {
+        get_runtime!($rt, new($image.as_path()))
+    };
+    ($rt:ident, $constructor:ident($($arg:expr),*)) => {
         match $rt {
             #[cfg(feature = "opengl")]
-            Runtime::OpenGL3 => &mut librashader_test::render::gl::OpenGl3::new($image.as_path())?,
+            Runtime::OpenGL3 => &mut librashader_test::render::gl::OpenGl3::$constructor($($arg),*)?,
             #[cfg(feature = "opengl")]
-            Runtime::OpenGL4 => &mut librashader_test::render::gl::OpenGl4::new($image.as_path())?,
+            Runtime::OpenGL4 => &mut librashader_test::render::gl::OpenGl4::$constructor($($arg),*)?,
             #[cfg(feature = "vulkan")]
-            Runtime::Vulkan => &mut librashader_test::render::vk::Vulkan::new($image.as_path())?,
+            Runtime::Vulkan => &mut librashader_test::render::vk::Vulkan::$constructor($($arg),*)?,
             #[cfg(feature = "wgpu")]
-            Runtime::Wgpu => &mut librashader_test::render::wgpu::Wgpu::new($image.as_path())?,
+            Runtime::Wgpu => &mut librashader_test::render::wgpu::Wgpu::$constructor($($arg),*)?,
             #[cfg(all(windows, feature = "d3d9"))]
             Runtime::Direct3D9 => {
-                &mut librashader_test::render::d3d9::Direct3D9::new($image.as_path())?
+                &mut librashader_test::render::d3d9::Direct3D9::$constructor($($arg),*)?
             }
             #[cfg(all(windows, feature = "d3d11"))]
             Runtime::Direct3D11 => {
-                &mut librashader_test::render::d3d11::Direct3D11::new($image.as_path())?
+                &mut librashader_test::render::d3d11::Direct3D11::$constructor($($arg),*)?
             }
             #[cfg(all(windows, feature = "d3d12"))]
             Runtime::Direct3D12 => {
-                &mut librashader_test::render::d3d12::Direct3D12::new($image.as_path())?
+                &mut librashader_test::render::d3d12::Direct3D12::$constructor($($arg),*)?
             }
             #[cfg(all(target_vendor = "apple", feature = "metal"))]
-            Runtime::Metal => &mut librashader_test::render::mtl::Metal::new($image.as_path())?,
+            Runtime::Metal => &mut librashader_test::render::mtl::Metal::$constructor($($arg),*)?,
         }
     };
 }
 pub fn main() -> Result<(), anyhow::Error> {
     let args = Args::parse();
+    let output_format = args.output_format;
+
+    match run(args.command, output_format) {
+        Err(err) if output_format == OutputFormat::Json => {
+            let error = serde_json::json!({
+                "error": {
+                    "code": error_code(&err),
+                    "message": err.to_string(),
+                    "causes": err.chain().skip(1).map(|cause| cause.to_string()).collect::<Vec<_>>(),
+                }
+            });
+            println!("{}", serde_json::to_string_pretty(&error)?);
+            std::process::exit(1);
+        }
+        result => result,
+    }
+}
 
-    match args.command {
+fn run(command: Commands, output_format: OutputFormat) -> anyhow::Result<()> {
+    match command {
         Commands::Render {
             preset,
             render,
             out,
+            output,
             runtime,
         } => {
             let PresetArgs { preset, wildcards } = preset;
@@ -388,6 +640,13 @@ pub fn main() -> Result<(), anyhow::Error> {
                 options,
             } = render;
 
+            if output_format == OutputFormat::Json && out.as_path() == Path::new("-") {
+                return Err(anyhow!(
+                    "The image can not be written to stdout with JSON output"
+                ));
+            }
+
+            let runtime_name = value_name(&runtime);
             let test: &mut dyn RenderTest = get_runtime!(runtime, image);
             let dimensions = parse_dimension(dimensions, test.image_size())?;
 
@@ -420,11 +679,192 @@ pub fn main() -> Result<(), anyhow::Error> {
                 options.map(CommonFrameOptions::from),
             )?;
 
-            if out.as_path() == Path::new("-") {
-                let out = std::io::stdout();
-                image.write_with_encoder(PngEncoder::new(out))?;
+            write_image(image, out.as_path(), &output)?;
+
+            if output_format == OutputFormat::Json {
+                let result = serde_json::json!({
+                    "runtime": runtime_name,
+                    "frame": frame,
+                    "width": dimensions.width,
+                    "height": dimensions.height,
+                    "output": out,
+                });
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            }
+        }
+        Commands::Stream {
+            preset,
+            render,
+            start_frame,
+            framerate,
+            out,
+            runtime,
+        } => {
+            let PresetArgs { preset, wildcards } = preset;
+            let RenderArgs {
+                frame,
+                dimensions,
+                params,
+                passes_enabled,
+                image,
+                options,
+            } = render;
+
+            if start_frame > frame {
+                return Err(anyhow!(
+                    "The start frame {start_frame} is after the last frame {frame}"
+                ));
+            }
+
+            if output_format == OutputFormat::Json && out.as_path() == Path::new("-") {
+                return Err(anyhow!(
+                    "The stream can not be written to stdout with JSON output"
+                ));
+            }
+
+            let framerate = y4m::parse_framerate(&framerate)?;
+            let runtime_name = value_name(&runtime);
+            let test: &mut dyn RenderTest = get_runtime!(runtime, image);
+            let dimensions = parse_dimension(dimensions, test.image_size())?;
+
+            let mut features = ShaderFeatures::NONE;
+            if options
+                .as_ref()
+                .is_some_and(|args| args.aspect_ratio.is_some())
+            {
+                features |= ShaderFeatures::ORIGINAL_ASPECT_UNIFORMS;
+            }
+
+            if options
+                .as_ref()
+                .is_some_and(|args| args.frames_per_second.is_some())
+                || options
+                    .as_ref()
+                    .is_some_and(|args| args.frametime_delta.is_some())
+            {
+                features |= ShaderFeatures::FRAMETIME_UNIFORMS;
+            }
+
+            let preset = get_shader_preset(preset, wildcards, features)?;
+            let params = parse_params(params)?;
+
+            let writer: Box<dyn Write> = if out.as_path() == Path::new("-") {
+                Box::new(std::io::stdout().lock())
             } else {
-                image.save(out)?;
+                Box::new(File::create(out.as_path())?)
+            };
+            let mut writer = y4m::Y4mWriter::new(BufWriter::new(writer), framerate);
+
+            let mut frames = 0usize;
+            test.render_sequence_with_preset_and_params(
+                preset,
+                start_frame..=frame,
+                Some(dimensions),
+                Some(&|rp| set_params(rp, &params, passes_enabled)),
+                options.map(CommonFrameOptions::from),
+                None,
+                &mut |_, image| {
+                    frames += 1;
+                    writer.write_frame(&image)
+                },
+            )?;
+
+            writer.flush()?;
+
+            if output_format == OutputFormat::Json {
+                let result = serde_json::json!({
+                    "runtime": runtime_name,
+                    "start_frame": start_frame,
+                    "end_frame": frame,
+                    "frames": frames,
+                    "width": dimensions.width,
+                    "height": dimensions.height,
+                    "output": out,
+                });
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            }
+        }
+        #[cfg(feature = "ffmpeg")]
+        Commands::Video {
+            preset,
+            input,
+            dimensions,
+            params,
+            passes_enabled,
+            options,
+            out,
+            runtime,
+        } => {
+            let PresetArgs { preset, wildcards } = preset;
+
+            let mut decoder = video::VideoDecoder::open(input.as_path())?;
+            let first_frame = decoder
+                .next_frame()?
+                .ok_or_else(|| anyhow!("The input video does not contain any frames"))?;
+
+            let runtime_name = value_name(&runtime);
+            let test: &mut dyn RenderTest =
+                get_runtime!(runtime, new_with_image(first_frame.clone()));
+            let dimensions = parse_dimension(dimensions, test.image_size())?;
+
+            let mut features = ShaderFeatures::NONE;
+            if options
+                .as_ref()
+                .is_some_and(|args| args.aspect_ratio.is_some())
+            {
+                features |= ShaderFeatures::ORIGINAL_ASPECT_UNIFORMS;
+            }
+
+            if options
+                .as_ref()
+                .is_some_and(|args| args.frames_per_second.is_some())
+                || options
+                    .as_ref()
+                    .is_some_and(|args| args.frametime_delta.is_some())
+            {
+                features |= ShaderFeatures::FRAMETIME_UNIFORMS;
+            }
+
+            let preset = get_shader_preset(preset, wildcards, features)?;
+            let params = parse_params(params)?;
+
+            let mut encoder = video::VideoEncoder::create(
+                out.as_path(),
+                dimensions.width,
+                dimensions.height,
+                decoder.frame_rate(),
+            )?;
+
+            let mut first_frame = Some(first_frame);
+            let mut frames = 0usize;
+            test.render_sequence_with_preset_and_params(
+                preset,
+                0..=usize::MAX,
+                Some(dimensions),
+                Some(&|rp| set_params(rp, &params, passes_enabled)),
+                options.map(CommonFrameOptions::from),
+                Some(&mut |_| match first_frame.take() {
+                    Some(frame) => Ok(Some(frame)),
+                    None => decoder.next_frame(),
+                }),
+                &mut |_, image| {
+                    frames += 1;
+                    encoder.write_frame(&image)
+                },
+            )?;
+
+            encoder.finish()?;
+
+            if output_format == OutputFormat::Json {
+                let result = serde_json::json!({
+                    "runtime": runtime_name,
+                    "input": input,
+                    "frames": frames,
+                    "width": dimensions.width,
+                    "height": dimensions.height,
+                    "output": out,
+                });
+                println!("{}", serde_json::to_string_pretty(&result)?);
             }
         }
         Commands::Compare {
@@ -432,6 +872,7 @@ pub fn main() -> Result<(), anyhow::Error> {
             render,
             left,
             right,
+            metric,
             out,
         } => {
             let PresetArgs { preset, wildcards } = preset;
@@ -444,6 +885,18 @@ pub fn main() -> Result<(), anyhow::Error> {
                 options,
             } = render;
 
+            if output_format == OutputFormat::Json
+                && out
+                    .as_ref()
+                    .is_some_and(|out| out.as_path() == Path::new("-"))
+            {
+                return Err(anyhow!(
+                    "The similarity image can not be written to stdout with JSON output"
+                ));
+            }
+
+            let left_name = value_name(&left);
+            let right_name = value_name(&right);
             let left: &mut dyn RenderTest = get_runtime!(left, image);
             let right: &mut dyn RenderTest = get_runtime!(right, image);
 
@@ -486,11 +939,25 @@ pub fn main() -> Result<(), anyhow::Error> {
                 options.map(CommonFrameOptions::from),
             )?;
 
-            let similarity = image_compare::rgba_hybrid_compare(&left_image, &right_image)?;
-            print!("{}", similarity.score);
+            let similarity = compare::compare(metric, &left_image, &right_image)?;
+            match output_format {
+                OutputFormat::Text => print!("{}", similarity.score),
+                OutputFormat::Json => {
+                    let result = serde_json::json!({
+                        "left": left_name,
+                        "right": right_name,
+                        "metric": metric,
+                        "frame": frame,
+                        "width": dimensions.width,
+                        "height": dimensions.height,
+                        "score": similarity.score,
+                    });
+                    print!("{}", serde_json::to_string_pretty(&result)?);
+                }
+            }
 
             if let Some(out) = out {
-                let image = similarity.image.to_color_map();
+                let image = similarity.image;
                 if out.as_path() == Path::new("-") {
                     let out = std::io::stdout();
                     image.write_with_encoder(PngEncoder::new(out))?;
@@ -499,6 +966,141 @@ pub fn main() -> Result<(), anyhow::Error> {
                 }
             }
         }
+        Commands::Golden {
+            corpus,
+            runtime,
+            update,
+            only,
+            out,
+        } => {
+            let corpus = golden::Corpus::load(&corpus)
+                .map_err(|err| anyhow!("Failed to load corpus {}: {err}", corpus.display()))?;
+            if let Some(only) = &only {
+                if let Some(name) = only
+                    .iter()
+                    .find(|name| !corpus.presets.iter().any(|entry| &entry.name == *name))
+                {
+                    return Err(anyhow!("The corpus has no preset named {name}"));
+                }
+            }
+
+            let runtime_name = value_name(&runtime);
+            let image = corpus.image_path();
+            let test: &mut dyn RenderTest = get_runtime!(runtime, image);
+
+            if let Some(out) = &out {
+                std::fs::create_dir_all(out)?;
+            }
+
+            let mut failed = false;
+            let mut results = Vec::new();
+            for entry in &corpus.presets {
+                if only
+                    .as_ref()
+                    .is_some_and(|only| !only.contains(&entry.name))
+                {
+                    continue;
+                }
+
+                let golden_path = corpus.golden_path(entry, &runtime_name);
+                let tolerance = corpus.tolerance(entry, &runtime_name);
+                let rendered = render_golden(test, &corpus, entry);
+
+                let (status, comparison, message) = match rendered {
+                    Err(err) => ("error", None, Some(format!("{err:#}"))),
+                    Ok(rendered) if update => {
+                        if let Some(parent) = golden_path.parent() {
+                            std::fs::create_dir_all(parent)?;
+                        }
+                        rendered.save(&golden_path)?;
+                        ("updated", None, None)
+                    }
+                    Ok(rendered) => {
+                        let result = if golden_path.exists() {
+                            image::open(&golden_path)
+                                .map_err(anyhow::Error::from)
+                                .and_then(|golden| {
+                                    golden::Comparison::new(
+                                        &golden.to_rgba8(),
+                                        &rendered,
+                                        &tolerance,
+                                        &entry.masks,
+                                        &runtime_name,
+                                    )
+                                })
+                                .map(|comparison| {
+                                    let status = if comparison.passes(&tolerance) {
+                                        "ok"
+                                    } else {
+                                        "failed"
+                                    };
+                                    (status, Some(comparison), None)
+                                })
+                                .unwrap_or_else(|err| ("failed", None, Some(err.to_string())))
+                        } else {
+                            ("missing", None, None)
+                        };
+
+                        if result.0 != "ok" {
+                            if let Some(out) = &out {
+                                rendered.save(out.join(&entry.name).with_extension("png"))?;
+                            }
+                        }
+                        result
+                    }
+                };
+
+                failed |= !matches!(status, "ok" | "updated");
+                results.push((entry, status, tolerance, comparison, message));
+            }
+
+            match output_format {
+                OutputFormat::Text => {
+                    for (entry, status, tolerance, comparison, message) in &results {
+                        print!("{:<24} {status:<8}", entry.name);
+                        match (comparison, tolerance.min_ssim) {
+                            (Some(comparison), Some(min_ssim)) => {
+                                print!("ssim {:.5} (min {min_ssim:.5})", comparison.ssim)
+                            }
+                            (Some(comparison), None) => print!(
+                                "rmse {:.5} (max {:.5}), {} mismatched pixel(s) (max {})",
+                                comparison.rmse,
+                                tolerance.max_rmse,
+                                comparison.mismatched_pixels,
+                                tolerance.max_mismatched_pixels
+                            ),
+                            (None, _) => {}
+                        }
+                        if let Some(message) = message {
+                            print!("{message}");
+                        }
+                        println!();
+                    }
+                }
+                OutputFormat::Json => {
+                    let result = serde_json::json!({
+                        "runtime": runtime_name,
+                        "presets": results
+                            .iter()
+                            .map(|(entry, status, tolerance, comparison, message)| {
+                                serde_json::json!({
+                                    "name": entry.name,
+                                    "status": status,
+                                    "tolerance": tolerance,
+                                    "comparison": comparison,
+                                    "message": message,
+                                })
+                            })
+                            .collect::<Vec<_>>(),
+                    });
+                    println!("{}", serde_json::to_string_pretty(&result)?);
+                }
+            }
+
+            if failed {
+                std::process::exit(3);
+            }
+        }
         Commands::Parse { preset, flags } => {
             let PresetArgs { preset, wildcards } = preset;
 
@@ -513,6 +1115,23 @@ pub fn main() -> Result<(), anyhow::Error> {
         } => {
             let source =
                 librashader::preprocess::ShaderSource::load(shader.as_path(), flags.into())?;
+            if output_format == OutputFormat::Json {
+                let value = match output {
+                    PreprocessOutput::Fragment => serde_json::json!(source.fragment),
+                    PreprocessOutput::Vertex => serde_json::json!(source.vertex),
+                    PreprocessOutput::Params => serde_json::to_value(&source.parameters)?,
+                    PreprocessOutput::Format => serde_json::json!(format!("{:?}", source.format)),
+                    PreprocessOutput::Json => serde_json::to_value(&source)?,
+                };
+                let result = serde_json::json!({
+                    "shader": shader,
+                    "output": value_name(&output),
+                    "value": value,
+                });
+                println!("{}", serde_json::to_string_pretty(&result)?);
+                return Ok(());
+            }
+
             match output {
                 PreprocessOutput::Fragment => print!("{}", source.fragment),
                 PreprocessOutput::Vertex => print!("{}", source.vertex),
@@ -543,7 +1162,7 @@ pub fn main() -> Result<(), anyhow::Error> {
                         .map(|s| parse_glsl_version(&s))
                         .unwrap_or(Ok(GlslVersion::Glsl330))?;
 
-                    let output = compilation.compile(version)?;
+                    let output = compilation.compile(version.into())?;
                     TranspileOutput {
                         vertex: output.vertex,
                         fragment: output.fragment,
@@ -618,7 +1237,18 @@ pub fn main() -> Result<(), anyhow::Error> {
                 TranspileStage::Vertex => output.vertex,
             };
 
-            print!("{print}")
+            match output_format {
+                OutputFormat::Text => print!("{print}"),
+                OutputFormat::Json => {
+                    let result = serde_json::json!({
+                        "shader": shader,
+                        "stage": value_name(&stage),
+                        "format": value_name(&format),
+                        "source": print,
+                    });
+                    println!("{}", serde_json::to_string_pretty(&result)?);
+                }
+            }
         }
         Commands::Reflect {
             preset,
@@ -629,39 +1259,43 @@ pub fn main() -> Result<(), anyhow::Error> {
             let PresetArgs { preset, wildcards } = preset;
 
             let preset = get_shader_preset(preset, wildcards, flags.into())?;
-            let Some(shader) = preset.passes.get(index) else {
-                return Err(anyhow!("Invalid pass index for the preset"));
+            let indices = match index {
+                Some(index) if index >= preset.passes.len() => {
+                    return Err(anyhow!("Invalid pass index for the preset"));
+                }
+                Some(index) => index..index + 1,
+                None => 0..preset.passes.len(),
             };
 
-            let source = librashader::preprocess::ShaderSource::load(
-                shader.path.as_path(),
-                preset.features,
-            )?;
-            let compilation = SpirvCompilation::try_from(&source)?;
-
-            let semantics =
-                ShaderSemantics::create_pass_semantics::<anyhow::Error>(&preset, index)?;
-
-            let reflection = match backend {
-                ReflectionBackend::SpirvCross => {
-                    let mut compilation =
-                        <librashader::reflect::targets::SPIRV as FromCompilation<
-                            SpirvCompilation,
-                            SpirvCross,
-                        >>::from_compilation(compilation)?;
-                    compilation.reflect(index, &semantics)?
+            let passes = indices
+                .map(|index| {
+                    let shader = &preset.passes[index];
+                    let reflection = reflect_pass(&preset, index, backend)?;
+                    Ok(serde_json::json!({
+                        "pass": index,
+                        "path": shader.path,
+                        "reflection": reflection,
+                    }))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            match output_format {
+                OutputFormat::Text if index.is_some() => {
+                    print!(
+                        "{}",
+                        serde_json::to_string_pretty(&passes[0]["reflection"])?
+                    )
                 }
-                ReflectionBackend::Naga => {
-                    let mut compilation =
-                        <librashader::reflect::targets::SPIRV as FromCompilation<
-                            SpirvCompilation,
-                            Naga,
-                        >>::from_compilation(compilation)?;
-                    compilation.reflect(index, &semantics)?
+                OutputFormat::Text => {
+                    let reflections: Vec<_> =
+                        passes.iter().map(|pass| &pass["reflection"]).collect();
+                    print!("{}", serde_json::to_string_pretty(&reflections)?)
                 }
-            };
-
-            print!("{}", serde_json::to_string_pretty(&reflection)?);
+                OutputFormat::Json => {
+                    let result = serde_json::json!({ "passes": passes });
+                    print!("{}", serde_json::to_string_pretty(&result)?);
+                }
+            }
         }
         Commands::Pack {
             preset,
@@ -670,8 +1304,15 @@ pub fn main() -> Result<(), anyhow::Error> {
             format,
         } => {
             let PresetArgs { preset, wildcards } = preset;
+            if output_format == OutputFormat::Json && out.as_path() == Path::new("-") {
+                return Err(anyhow!(
+                    "The preset pack can not be written to stdout with JSON output"
+                ));
+            }
+
             let preset = get_shader_preset(preset, wildcards, flags.into())?;
             let preset = ShaderPresetPack::load_from_preset::<anyhow::Error>(preset)?;
+            let passes = preset.passes.len();
             let output_bytes = match format {
                 PackFormat::JSON => serde_json::to_vec_pretty(&preset)?,
                 PackFormat::MsgPack => rmp_serde::to_vec(&preset)?,
@@ -684,6 +1325,104 @@ pub fn main() -> Result<(), anyhow::Error> {
                 let mut file = File::create(out.as_path())?;
                 file.write_all(output_bytes.as_slice())?;
             }
+
+            if output_format == OutputFormat::Json {
+                let result = serde_json::json!({
+                    "format": value_name(&format),
+                    "passes": passes,
+                    "size": output_bytes.len(),
+                    "output": out,
+                });
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            }
+        }
+        Commands::Validate {
+            preset,
+            flags,
+            warnings_as_errors,
+            ignore,
+        } => {
+            let PresetArgs { preset, wildcards } = preset;
+
+            let mut diagnostics = match get_shader_preset(preset.clone(), wildcards, flags.into()) {
+                Ok(preset) => validate::validate_preset(&preset),
+                Err(err) => vec![validate::Diagnostic::new(
+                    validate::Category::Preset,
+                    None,
+                    Some(preset),
+                    err,
+                )],
+            };
+            diagnostics.retain(|diagnostic| !ignore.contains(&diagnostic.category));
+
+            let errors = diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.severity() == validate::Severity::Error)
+                .count();
+            let warnings = diagnostics.len() - errors;
+            let valid = errors == 0 && (!warnings_as_errors || warnings == 0);
+
+            match output_format {
+                OutputFormat::Text => {
+                    for diagnostic in &diagnostics {
+                        println!("{diagnostic}");
+                    }
+                    println!("{errors} error(s), {warnings} warning(s)");
+                }
+                OutputFormat::Json => {
+                    let result = serde_json::json!({
+                        "valid": valid,
+                        "errors": errors,
+                        "warnings": warnings,
+                        "diagnostics": diagnostics
+                            .iter()
+                            .map(validate::Diagnostic::to_json)
+                            .collect::<Vec<_>>(),
+                    });
+                    println!("{}", serde_json::to_string_pretty(&result)?);
+                }
+            }
+
+            if !valid {
+                std::process::exit(3);
+            }
+        }
+        Commands::Doctor => {
+            let builtin = doctor::BuiltinPreset::new()?;
+            let reports: Vec<doctor::Report> = Runtime::value_variants()
+                .iter()
+                .map(|runtime| {
+                    doctor::check_runtime(value_name(runtime), |stage| {
+                        doctor_render(runtime, builtin.preset.clone(), stage)
+                    })
+                })
+                .collect();
+            drop(builtin);
+
+            match output_format {
+                OutputFormat::Text => {
+                    println!("{:<10} {:<12}details", "runtime", "status");
+                    for report in &reports {
+                        println!("{report}");
+                    }
+                }
+                OutputFormat::Json => {
+                    let result = serde_json::json!({
+                        "runtimes": reports
+                            .iter()
+                            .map(doctor::Report::to_json)
+                            .collect::<Vec<_>>(),
+                    });
+                    println!("{}", serde_json::to_string_pretty(&result)?);
+                }
+            }
+
+            if reports
+                .iter()
+                .any(|report| matches!(report.status, doctor::Status::Failed { .. }))
+            {
+                std::process::exit(3);
+            }
         }
     }
 
@@ -695,6 +1434,181 @@ struct TranspileOutput {
     fragment: String,
 }
 
+/// Reflect a pass of a shader preset with the given reflection backend.
+fn reflect_pass(
+    preset: &ShaderPreset,
+    index: usize,
+    backend: ReflectionBackend,
+) -> anyhow::Result<ShaderReflection> {
+    let shader = &preset.passes[index];
+    let source =
+        librashader::preprocess::ShaderSource::load(shader.path.as_path(), preset.features)?;
+    let compilation = SpirvCompilation::try_from(&source)?;
+
+    let semantics = ShaderSemantics::create_pass_semantics::<anyhow::Error>(preset, index)?;
+
+    let reflection = match backend {
+        ReflectionBackend::SpirvCross => {
+            let mut compilation = <librashader::reflect::targets::SPIRV as FromCompilation<
+                SpirvCompilation,
+                SpirvCross,
+            >>::from_compilation(compilation)?;
+            compilation.reflect(index, &semantics)?
+        }
+        ReflectionBackend::Naga => {
+            let mut compilation = <librashader::reflect::targets::SPIRV as FromCompilation<
+                SpirvCompilation,
+                Naga,
+            >>::from_compilation(compilation)?;
+            compilation.reflect(index, &semantics)?
+        }
+    };
+    Ok(reflection)
+}
+
+/// The name of a value of an argument, as given on the command line.
+fn value_name(value: &impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+/// Render a shader preset of a reference image corpus.
+fn render_golden(
+    test: &mut dyn RenderTest,
+    corpus: &golden::Corpus,
+    entry: &golden::CorpusEntry,
+) -> anyhow::Result<RgbaImage> {
+    let preset = get_shader_preset(corpus.preset_path(entry), None, ShaderFeatures::NONE)?;
+    let dimensions = parse_dimension(entry.dimensions.clone(), test.image_size())?;
+    let params = (!entry.params.is_empty()).then(|| {
+        entry
+            .params
+            .iter()
+            .map(|(name, value)| (ShortString::from(name.as_str()), *value))
+            .collect()
+    });
+
+    test.render_with_preset_and_params(
+        preset,
+        entry.frame,
+        Some(dimensions),
+        Some(&|rp| set_params(rp, &params, None)),
+        None,
+    )
+}
+
+/// Render the built-in preset of `doctor` with a runtime, advancing the stage once the
+/// test harness of the runtime is created.
+fn doctor_render(
+    runtime: &Runtime,
+    preset: ShaderPreset,
+    stage: &mut doctor::Stage,
+) -> anyhow::Result<RgbaImage> {
+    let test: &mut dyn RenderTest = get_runtime!(runtime, new_with_image(doctor::input_image()));
+    *stage = doctor::Stage::Render;
+    test.render_with_preset(preset, 0, Some(doctor::output_size()))
+}
+
+/// Get a stable error code for the first known error in the chain of an error.
+///
+/// These codes are part of the JSON output, and should not be changed.
+fn error_code(err: &anyhow::Error) -> &'static str {
+    for cause in err.chain() {
+        if cause.is::<ParsePresetError>() {
+            return "preset-parse";
+        }
+        if cause.is::<PreprocessError>() {
+            return "preprocess";
+        }
+        if cause.is::<ShaderCompileError>() {
+            return "shader-compile";
+        }
+        if cause.is::<ShaderReflectError>() {
+            return "shader-reflect";
+        }
+        if cause.is::<image::ImageError>() {
+            return "image";
+        }
+        if cause.is::<std::io::Error>() {
+            return "io";
+        }
+    }
+
+    "other"
+}
+
+fn write_image(image: RgbaImage, out: &Path, args: &ImageOutputArgs) -> anyhow::Result<()> {
+    let to_stdout = out == Path::new("-");
+    let format = match args.image_format {
+        Some(format) => format,
+        None if to_stdout => ImageOutputFormat::Png,
+        None => match ImageFormat::from_path(out) {
+            Ok(ImageFormat::Png) => ImageOutputFormat::Png,
+            Ok(ImageFormat::Jpeg) => ImageOutputFormat::Jpeg,
+            Ok(ImageFormat::WebP) => ImageOutputFormat::WebP,
+            Ok(ImageFormat::OpenExr) => ImageOutputFormat::OpenExr,
+            _ => {
+                return Err(anyhow!(
+                    "Unable to infer image format from output path, specify one with --image-format"
+                ))
+            }
+        },
+    };
+
+    let depth = args.bit_depth.unwrap_or(match format {
+        ImageOutputFormat::OpenExr => ImageBitDepth::Float,
+        _ => ImageBitDepth::Eight,
+    });
+
+    let image = DynamicImage::ImageRgba8(image);
+    let (image, format) = match (format, depth, !args.strip_alpha) {
+        (ImageOutputFormat::Png, ImageBitDepth::Eight, true) => (image, ImageFormat::Png),
+        (ImageOutputFormat::Png, ImageBitDepth::Eight, false) => {
+            (image.to_rgb8().into(), ImageFormat::Png)
+        }
+        (ImageOutputFormat::Png, ImageBitDepth::Sixteen, true) => {
+            (image.to_rgba16().into(), ImageFormat::Png)
+        }
+        (ImageOutputFormat::Png, ImageBitDepth::Sixteen, false) => {
+            (image.to_rgb16().into(), ImageFormat::Png)
+        }
+        (ImageOutputFormat::Jpeg, ImageBitDepth::Eight, _) => {
+            (image.to_rgb8().into(), ImageFormat::Jpeg)
+        }
+        (ImageOutputFormat::WebP, ImageBitDepth::Eight, true) => (image, ImageFormat::WebP),
+        (ImageOutputFormat::WebP, ImageBitDepth::Eight, false) => {
+            (image.to_rgb8().into(), ImageFormat::WebP)
+        }
+        (ImageOutputFormat::OpenExr, ImageBitDepth::Float, true) => {
+            (image.to_rgba32f().into(), ImageFormat::OpenExr)
+        }
+        (ImageOutputFormat::OpenExr, ImageBitDepth::Float, false) => {
+            (image.to_rgb32f().into(), ImageFormat::OpenExr)
+        }
+        (format, depth, _) => {
+            return Err(anyhow!(
+                "Bit depth {depth:?} is not supported for image format {format:?}"
+            ))
+        }
+    };
+
+    // Some encoders require a seekable writer, so the image is always encoded in memory first.
+    let mut bytes = Cursor::new(Vec::new());
+    image.write_to(&mut bytes, format)?;
+
+    if to_stdout {
+        let mut out = std::io::stdout();
+        out.write_all(bytes.get_ref())?;
+    } else {
+        let mut file = File::create(out)?;
+        file.write_all(bytes.get_ref())?;
+    }
+
+    Ok(())
+}
+
 fn get_shader_preset(
     preset: PathBuf,
     wildcards: Option<Vec<String>>,
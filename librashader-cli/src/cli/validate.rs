@@ -0,0 +1,283 @@
+use librashader::preprocess::{ShaderSource, KNOWN_PRAGMAS};
+use librashader::presets::{PassResource, ShaderPreset};
+use librashader::reflect::cross::SpirvCross;
+use librashader::reflect::targets::SPIRV;
+use librashader::reflect::{CompilePresetTarget, ReflectShader, SpirvCompilation};
+use librashader::{FastHashMap, ShortString};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// The pragmas defined by GLSL. Pragmas starting with `STDGL` are also reserved by GLSL.
+const GLSL_PRAGMAS: &[&str] = &["optimize", "debug"];
+
+/// The category of a validation diagnostic.
+///
+/// The names of the categories are part of the output of `validate`, and should not be changed.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Category {
+    /// The shader preset could not be parsed.
+    #[clap(name = "preset")]
+    Preset,
+    /// A shader could not be loaded or preprocessed.
+    #[clap(name = "preprocess")]
+    Preprocess,
+    /// A shader could not be compiled.
+    #[clap(name = "compile")]
+    Compile,
+    /// A shader could not be reflected against the semantics of the preset.
+    #[clap(name = "reflect")]
+    Reflect,
+    /// A shader contains a `#pragma` that is not understood by librashader.
+    #[clap(name = "unknown-pragma")]
+    UnknownPragma,
+    /// The preset sets a parameter that is not declared by any shader.
+    #[clap(name = "unknown-parameter")]
+    UnknownParameter,
+    /// The value of a parameter is outside of its declared range.
+    #[clap(name = "parameter-range")]
+    ParameterRange,
+}
+
+impl Category {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::Preset => "preset",
+            Category::Preprocess => "preprocess",
+            Category::Compile => "compile",
+            Category::Reflect => "reflect",
+            Category::UnknownPragma => "unknown-pragma",
+            Category::UnknownParameter => "unknown-parameter",
+            Category::ParameterRange => "parameter-range",
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            Category::Preset | Category::Preprocess | Category::Compile | Category::Reflect => {
+                Severity::Error
+            }
+            Category::UnknownPragma | Category::UnknownParameter | Category::ParameterRange => {
+                Severity::Warning
+            }
+        }
+    }
+}
+
+/// The severity of a validation diagnostic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// A single problem found when validating a shader preset.
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub category: Category,
+    /// The index of the pass the diagnostic applies to, if any.
+    pub pass: Option<usize>,
+    /// The path of the file the diagnostic applies to, if any.
+    pub path: Option<PathBuf>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(
+        category: Category,
+        pass: Option<usize>,
+        path: Option<PathBuf>,
+        message: impl Display,
+    ) -> Self {
+        Self {
+            category,
+            pass,
+            path,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.category.severity()
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "severity": self.severity().name(),
+            "category": self.category.name(),
+            "pass": self.pass,
+            "path": self.path,
+            "message": self.message,
+        })
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}]: ", self.severity().name(), self.category.name())?;
+        match (self.pass, &self.path) {
+            (Some(pass), Some(path)) => write!(f, "pass {pass} ({}): ", path.display())?,
+            (Some(pass), None) => write!(f, "pass {pass}: ")?,
+            (None, Some(path)) => write!(f, "{}: ", path.display())?,
+            (None, None) => {}
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Validate every pass of a shader preset without creating a runtime.
+///
+/// Every pass is preprocessed and compiled independently so that all errors are reported,
+/// and passes are only reflected once every pass compiles.
+pub fn validate_preset(preset: &ShaderPreset) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let mut declared = FastHashMap::<ShortString, (f32, f32)>::default();
+    let mut resources = Vec::new();
+    let mut compiled = true;
+
+    for (index, pass) in preset.passes.iter().enumerate() {
+        let path = Some(pass.path.clone());
+        let source = match ShaderSource::load_with_defines(
+            &pass.path,
+            preset.features,
+            &pass.meta.defines,
+            None,
+        ) {
+            Ok(source) => source,
+            Err(err) => {
+                diagnostics.push(Diagnostic::new(
+                    Category::Preprocess,
+                    Some(index),
+                    path,
+                    err,
+                ));
+                compiled = false;
+                continue;
+            }
+        };
+
+        // pragmas that are neither understood by the preprocessor nor defined by GLSL are
+        // passed through to the compiler, which silently ignores them.
+        let pragmas: BTreeSet<&str> = source
+            .vertex
+            .lines()
+            .chain(source.fragment.lines())
+            .chain(source.compute.iter().flat_map(|compute| compute.lines()))
+            .map(str::trim)
+            .filter(|line| {
+                let Some(pragma) = line.strip_prefix("#pragma") else {
+                    return false;
+                };
+                let name = pragma
+                    .trim_start()
+                    .split(|c: char| c.is_whitespace() || c == '"' || c == '(')
+                    .next()
+                    .unwrap_or_default();
+                !KNOWN_PRAGMAS.contains(&name)
+                    && !GLSL_PRAGMAS.contains(&name)
+                    && !name.starts_with("STDGL")
+            })
+            .collect();
+
+        for pragma in pragmas {
+            diagnostics.push(Diagnostic::new(
+                Category::UnknownPragma,
+                Some(index),
+                path.clone(),
+                format!("Unknown pragma `{pragma}`"),
+            ));
+        }
+
+        let mut parameters: Vec<_> = source.parameters.values().collect();
+        parameters.sort_by(|left, right| left.id.cmp(&right.id));
+        for parameter in parameters {
+            if !(parameter.minimum..=parameter.maximum).contains(&parameter.initial) {
+                diagnostics.push(Diagnostic::new(
+                    Category::ParameterRange,
+                    Some(index),
+                    path.clone(),
+                    format!(
+                        "The initial value {} of parameter {} is outside of its range {} to {}",
+                        parameter.initial, parameter.id, parameter.minimum, parameter.maximum
+                    ),
+                ));
+            }
+            declared.insert(parameter.id.clone(), (parameter.minimum, parameter.maximum));
+        }
+
+        if let Err(err) = SpirvCompilation::try_from(&source) {
+            diagnostics.push(Diagnostic::new(Category::Compile, Some(index), path, err));
+            compiled = false;
+            continue;
+        }
+
+        resources.push(PassResource {
+            data: source,
+            meta: pass.meta.clone(),
+            path: Some(pass.path.clone()),
+        });
+    }
+
+    for parameter in &preset.parameters {
+        match declared.get(&parameter.name) {
+            None => diagnostics.push(Diagnostic::new(
+                Category::UnknownParameter,
+                None,
+                None,
+                format!(
+                    "The preset sets parameter {}, which is not declared by any pass",
+                    parameter.name
+                ),
+            )),
+            Some(&(minimum, maximum)) if !(minimum..=maximum).contains(&parameter.value) => {
+                diagnostics.push(Diagnostic::new(
+                    Category::ParameterRange,
+                    None,
+                    None,
+                    format!(
+                        "The preset sets parameter {} to {}, which is outside of its range {} to {}",
+                        parameter.name, parameter.value, minimum, maximum
+                    ),
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+
+    // Reflection needs the semantics of the entire preset, which requires every pass.
+    if !compiled {
+        return diagnostics;
+    }
+
+    let paths: Vec<_> = preset.passes.iter().map(|pass| pass.path.clone()).collect();
+    match SPIRV::compile_preset_passes::<SpirvCompilation, SpirvCross, anyhow::Error>(
+        resources,
+        preset.textures.iter().map(|texture| &texture.meta),
+    ) {
+        Ok((passes, semantics)) => {
+            for (index, (_, mut reflect)) in passes.into_iter().enumerate() {
+                if let Err(err) = reflect.reflect(index, &semantics) {
+                    diagnostics.push(Diagnostic::new(
+                        Category::Reflect,
+                        Some(index),
+                        paths.get(index).cloned(),
+                        err,
+                    ));
+                }
+            }
+        }
+        Err(err) => diagnostics.push(Diagnostic::new(Category::Compile, None, None, err)),
+    }
+
+    diagnostics
+}
@@ -0,0 +1,247 @@
+use anyhow::anyhow;
+use ffmpeg_next::format::Pixel;
+use ffmpeg_next::software::scaling;
+use ffmpeg_next::util::frame;
+use ffmpeg_next::{codec, encoder, format, media, Packet, Rational};
+use image::RgbaImage;
+use std::path::Path;
+
+/// Decodes the frames of the best video stream of a file into RGBA images.
+///
+/// Any other streams, including audio, are ignored.
+pub struct VideoDecoder {
+    input: format::context::Input,
+    decoder: ffmpeg_next::decoder::Video,
+    scaler: scaling::Context,
+    stream_index: usize,
+    frame_rate: Rational,
+    eof: bool,
+}
+
+impl VideoDecoder {
+    /// Open the video file at the given path.
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        ffmpeg_next::init()?;
+
+        let input = format::input(path)?;
+        let stream = input
+            .streams()
+            .best(media::Type::Video)
+            .ok_or_else(|| anyhow!("No video stream found in {}", path.display()))?;
+
+        let stream_index = stream.index();
+        let frame_rate = match stream.avg_frame_rate() {
+            Rational(0, _) | Rational(_, 0) => stream.rate(),
+            rate => rate,
+        };
+
+        let decoder = codec::context::Context::from_parameters(stream.parameters())?
+            .decoder()
+            .video()?;
+
+        let scaler = scaling::Context::get(
+            decoder.format(),
+            decoder.width(),
+            decoder.height(),
+            Pixel::RGBA,
+            decoder.width(),
+            decoder.height(),
+            scaling::Flags::BILINEAR,
+        )?;
+
+        Ok(Self {
+            input,
+            decoder,
+            scaler,
+            stream_index,
+            frame_rate,
+            eof: false,
+        })
+    }
+
+    /// The average frame rate of the video stream.
+    pub fn frame_rate(&self) -> Rational {
+        self.frame_rate
+    }
+
+    /// Decode the next frame of the video, or `None` if there are no more frames.
+    pub fn next_frame(&mut self) -> anyhow::Result<Option<RgbaImage>> {
+        let mut decoded = frame::Video::empty();
+        loop {
+            if self.decoder.receive_frame(&mut decoded).is_ok() {
+                let mut rgba = frame::Video::empty();
+                self.scaler.run(&decoded, &mut rgba)?;
+                return Ok(Some(to_image(&rgba)?));
+            }
+
+            if self.eof {
+                return Ok(None);
+            }
+
+            let mut packet = Packet::empty();
+            match packet.read(&mut self.input) {
+                Ok(()) if packet.stream() == self.stream_index => {
+                    self.decoder.send_packet(&packet)?
+                }
+                Ok(()) => {}
+                Err(ffmpeg_next::Error::Eof) => {
+                    self.decoder.send_eof()?;
+                    self.eof = true;
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+}
+
+/// Encodes RGBA images into a video file.
+///
+/// The container is inferred from the extension of the output path, and the video is
+/// encoded with the default video codec for that container.
+pub struct VideoEncoder {
+    output: format::context::Output,
+    encoder: encoder::video::Encoder,
+    scaler: scaling::Context,
+    stream_index: usize,
+    encoder_time_base: Rational,
+    stream_time_base: Rational,
+    frame_count: i64,
+}
+
+impl VideoEncoder {
+    /// Create the video file at the given path.
+    pub fn create(
+        path: &Path,
+        width: u32,
+        height: u32,
+        frame_rate: Rational,
+    ) -> anyhow::Result<Self> {
+        ffmpeg_next::init()?;
+
+        let mut output = format::output(path)?;
+        let codec = encoder::find(output.format().codec(path, media::Type::Video))
+            .ok_or_else(|| anyhow!("No video encoder available for {}", path.display()))?;
+
+        // Prefer 4:2:0 for compatibility with most players.
+        let formats: Vec<Pixel> = codec
+            .video()?
+            .formats()
+            .map(|formats| formats.collect())
+            .unwrap_or_default();
+        let pixel_format = if formats.is_empty() || formats.contains(&Pixel::YUV420P) {
+            Pixel::YUV420P
+        } else {
+            formats[0]
+        };
+
+        let global_header = output
+            .format()
+            .flags()
+            .contains(format::Flags::GLOBAL_HEADER);
+        let encoder_time_base = frame_rate.invert();
+
+        let mut context = codec::context::Context::new_with_codec(codec)
+            .encoder()
+            .video()?;
+        context.set_width(width);
+        context.set_height(height);
+        context.set_format(pixel_format);
+        context.set_frame_rate(Some(frame_rate));
+        context.set_time_base(encoder_time_base);
+        if global_header {
+            context.set_flags(codec::Flags::GLOBAL_HEADER);
+        }
+
+        let encoder = context.open_as(codec)?;
+
+        let stream_index = {
+            let mut stream = output.add_stream(codec)?;
+            stream.set_parameters(&encoder);
+            stream.set_time_base(encoder_time_base);
+            stream.index()
+        };
+
+        output.write_header()?;
+
+        // The muxer may change the time base of the stream when writing the header.
+        let stream_time_base = output
+            .stream(stream_index)
+            .ok_or_else(|| anyhow!("Output stream is missing"))?
+            .time_base();
+
+        let scaler = scaling::Context::get(
+            Pixel::RGBA,
+            width,
+            height,
+            pixel_format,
+            width,
+            height,
+            scaling::Flags::BILINEAR,
+        )?;
+
+        Ok(Self {
+            output,
+            encoder,
+            scaler,
+            stream_index,
+            encoder_time_base,
+            stream_time_base,
+            frame_count: 0,
+        })
+    }
+
+    /// Encode an image as the next frame of the video.
+    pub fn write_frame(&mut self, image: &RgbaImage) -> anyhow::Result<()> {
+        let mut rgba = frame::Video::new(Pixel::RGBA, image.width(), image.height());
+        let stride = rgba.stride(0);
+        let row_length = image.width() as usize * 4;
+        for (dst, src) in rgba
+            .data_mut(0)
+            .chunks_mut(stride)
+            .zip(image.as_raw().chunks(row_length))
+        {
+            dst[..row_length].copy_from_slice(src);
+        }
+
+        let mut converted = frame::Video::empty();
+        self.scaler.run(&rgba, &mut converted)?;
+        converted.set_pts(Some(self.frame_count));
+        self.frame_count += 1;
+
+        self.encoder.send_frame(&converted)?;
+        self.write_packets()
+    }
+
+    /// Flush any remaining frames and finish writing the video file.
+    pub fn finish(mut self) -> anyhow::Result<()> {
+        self.encoder.send_eof()?;
+        self.write_packets()?;
+        self.output.write_trailer()?;
+        Ok(())
+    }
+
+    fn write_packets(&mut self) -> anyhow::Result<()> {
+        let mut packet = Packet::empty();
+        while self.encoder.receive_packet(&mut packet).is_ok() {
+            packet.set_stream(self.stream_index);
+            packet.rescale_ts(self.encoder_time_base, self.stream_time_base);
+            packet.write_interleaved(&mut self.output)?;
+        }
+        Ok(())
+    }
+}
+
+fn to_image(frame: &frame::Video) -> anyhow::Result<RgbaImage> {
+    let row_length = frame.width() as usize * 4;
+    let mut bytes = Vec::with_capacity(row_length * frame.height() as usize);
+    for row in frame
+        .data(0)
+        .chunks(frame.stride(0))
+        .take(frame.height() as usize)
+    {
+        bytes.extend_from_slice(&row[..row_length]);
+    }
+
+    RgbaImage::from_raw(frame.width(), frame.height(), bytes)
+        .ok_or_else(|| anyhow!("Unable to create image from decoded frame"))
+}
@@ -0,0 +1,97 @@
+use anyhow::anyhow;
+use image::RgbaImage;
+use std::io::Write;
+
+/// Writes frames as an uncompressed YUV4MPEG2 stream.
+///
+/// Frames are converted to 4:4:4 Y'CbCr with BT.601 coefficients in limited range,
+/// which is what ffmpeg assumes for Y4M input without colour metadata. The alpha channel
+/// is discarded.
+pub struct Y4mWriter<W: Write> {
+    writer: W,
+    framerate: (u32, u32),
+    size: Option<(u32, u32)>,
+    planes: Vec<u8>,
+}
+
+impl<W: Write> Y4mWriter<W> {
+    /// Create a new writer with the given frame rate as a `(numerator, denominator)` pair.
+    ///
+    /// The stream header is written along with the first frame.
+    pub fn new(writer: W, framerate: (u32, u32)) -> Self {
+        Self {
+            writer,
+            framerate,
+            size: None,
+            planes: Vec::new(),
+        }
+    }
+
+    /// Write a frame to the stream.
+    ///
+    /// All frames in a stream must have the same dimensions.
+    pub fn write_frame(&mut self, frame: &RgbaImage) -> anyhow::Result<()> {
+        let dimensions = frame.dimensions();
+        match self.size {
+            None => {
+                let (width, height) = dimensions;
+                let (num, den) = self.framerate;
+                writeln!(
+                    self.writer,
+                    "YUV4MPEG2 W{width} H{height} F{num}:{den} Ip A1:1 C444"
+                )?;
+                self.size = Some(dimensions);
+            }
+            Some(size) if size != dimensions => {
+                return Err(anyhow!(
+                    "Frame dimensions changed from {}x{} to {}x{} within the stream",
+                    size.0,
+                    size.1,
+                    dimensions.0,
+                    dimensions.1
+                ));
+            }
+            Some(_) => {}
+        }
+
+        let pixels = frame.pixels().len();
+        self.planes.resize(pixels * 3, 0);
+        let (y_plane, chroma) = self.planes.split_at_mut(pixels);
+        let (cb_plane, cr_plane) = chroma.split_at_mut(pixels);
+
+        for (index, pixel) in frame.pixels().enumerate() {
+            let [r, g, b, _] = pixel.0.map(|c| c as f32 / 255.0);
+            y_plane[index] = to_u8(16.0 + 65.481 * r + 128.553 * g + 24.966 * b);
+            cb_plane[index] = to_u8(128.0 - 37.797 * r - 74.203 * g + 112.0 * b);
+            cr_plane[index] = to_u8(128.0 + 112.0 * r - 93.786 * g - 18.214 * b);
+        }
+
+        self.writer.write_all(b"FRAME\n")?;
+        self.writer.write_all(&self.planes)?;
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> anyhow::Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+fn to_u8(value: f32) -> u8 {
+    value.round().clamp(0.0, 255.0) as u8
+}
+
+/// Parse a frame rate given as either an integer or a fraction `NUM/DEN`.
+pub fn parse_framerate(framerate: &str) -> anyhow::Result<(u32, u32)> {
+    let (num, den) = framerate.split_once('/').unwrap_or((framerate, "1"));
+    let (Ok(num), Ok(den)) = (num.trim().parse::<u32>(), den.trim().parse::<u32>()) else {
+        return Err(anyhow!("Invalid frame rate {framerate}"));
+    };
+
+    if num == 0 || den == 0 {
+        return Err(anyhow!("Frame rate must be larger than 0"));
+    }
+
+    Ok((num, den))
+}
@@ -0,0 +1,215 @@
+//! Image comparison for render tests.
+//!
+//! Runtimes and GPUs do not render bit-identical frames, so comparing frames pixel by pixel
+//! flags harmless differences in precision. The structural similarity index (SSIM) instead
+//! compares the local luminance, contrast and structure of two frames, which better matches
+//! whether a difference is visible.
+use anyhow::anyhow;
+use image::{DynamicImage, GrayImage, ImageBuffer, Luma, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// The metric to compare two frames with.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Metric {
+    /// SSIM of the luma channel and the root mean square error of the chroma and alpha
+    /// channels, taking the lowest similarity of each pixel.
+    #[clap(name = "hybrid")]
+    Hybrid,
+    /// The mean structural similarity index of the red, green and blue channels, over a
+    /// Gaussian window.
+    #[clap(name = "ssim")]
+    Ssim,
+}
+
+/// The similarity of two frames.
+pub struct Similarity {
+    /// The similarity score, where `1.0` means the frames are identical.
+    pub score: f64,
+    /// An image of where the frames differ, which is black where the frames are identical.
+    pub image: DynamicImage,
+}
+
+/// Compare two frames with the given metric.
+pub fn compare(metric: Metric, left: &RgbaImage, right: &RgbaImage) -> anyhow::Result<Similarity> {
+    match metric {
+        Metric::Hybrid => {
+            let similarity = image_compare::rgba_hybrid_compare(left, right)?;
+            Ok(Similarity {
+                score: similarity.score,
+                image: similarity.image.to_color_map(),
+            })
+        }
+        Metric::Ssim => {
+            let map = ssim_map(left, right)?;
+            let score = mean(map.pixels().map(|pixel| pixel.0[0]));
+            let image = GrayImage::from_fn(map.width(), map.height(), |x, y| {
+                let dissimilarity = 1.0 - map.get_pixel(x, y).0[0];
+                Luma([(dissimilarity.clamp(0.0, 1.0) * 255.0).round() as u8])
+            });
+
+            Ok(Similarity {
+                score,
+                image: DynamicImage::ImageLuma8(image),
+            })
+        }
+    }
+}
+
+/// Get the mean of an iterator of values, or `1.0` if it is empty.
+pub(crate) fn mean(values: impl Iterator<Item = f32>) -> f64 {
+    let (sum, count) = values.fold((0f64, 0usize), |(sum, count), value| {
+        (sum + value as f64, count + 1)
+    });
+
+    if count == 0 {
+        1.0
+    } else {
+        sum / count as f64
+    }
+}
+
+/// The standard deviation of the Gaussian window.
+const SIGMA: f32 = 1.5;
+/// The radius of the Gaussian window, giving the usual 11x11 window.
+const RADIUS: i64 = 5;
+/// Stabilizes the luminance term for dark regions.
+const C1: f32 = 0.01 * 0.01;
+/// Stabilizes the contrast term for flat regions.
+const C2: f32 = 0.03 * 0.03;
+
+type Plane = ImageBuffer<Luma<f32>, Vec<f32>>;
+
+/// Get the SSIM of each pixel of two frames, averaged over the red, green and blue channels.
+///
+/// Alpha is ignored, since rendered frames are opaque.
+pub fn ssim_map(left: &RgbaImage, right: &RgbaImage) -> anyhow::Result<Plane> {
+    if left.dimensions() != right.dimensions() {
+        return Err(anyhow!(
+            "Can not compare a {}x{} frame with a {}x{} frame",
+            left.width(),
+            left.height(),
+            right.width(),
+            right.height()
+        ));
+    }
+
+    let (width, height) = left.dimensions();
+    let kernel = gaussian_kernel();
+    let mut map = Plane::new(width, height);
+
+    for channel in 0..3 {
+        let plane = |image: &RgbaImage| {
+            Plane::from_fn(width, height, |x, y| {
+                Luma([image.get_pixel(x, y).0[channel] as f32 / 255.0])
+            })
+        };
+
+        let x = plane(left);
+        let y = plane(right);
+        let product = |a: &Plane, b: &Plane| {
+            Plane::from_fn(width, height, |i, j| {
+                Luma([a.get_pixel(i, j).0[0] * b.get_pixel(i, j).0[0]])
+            })
+        };
+
+        let mean_x = blur(&x, &kernel);
+        let mean_y = blur(&y, &kernel);
+        let mean_xx = blur(&product(&x, &x), &kernel);
+        let mean_yy = blur(&product(&y, &y), &kernel);
+        let mean_xy = blur(&product(&x, &y), &kernel);
+
+        for (i, j, ssim) in map.enumerate_pixels_mut() {
+            let mu_x = mean_x.get_pixel(i, j).0[0];
+            let mu_y = mean_y.get_pixel(i, j).0[0];
+            let sigma_xx = mean_xx.get_pixel(i, j).0[0] - mu_x * mu_x;
+            let sigma_yy = mean_yy.get_pixel(i, j).0[0] - mu_y * mu_y;
+            let sigma_xy = mean_xy.get_pixel(i, j).0[0] - mu_x * mu_y;
+
+            let value = ((2.0 * mu_x * mu_y + C1) * (2.0 * sigma_xy + C2))
+                / ((mu_x * mu_x + mu_y * mu_y + C1) * (sigma_xx + sigma_yy + C2));
+            ssim.0[0] += value / 3.0;
+        }
+    }
+
+    Ok(map)
+}
+
+fn gaussian_kernel() -> Vec<f32> {
+    let kernel: Vec<f32> = (-RADIUS..=RADIUS)
+        .map(|offset| (-((offset * offset) as f32) / (2.0 * SIGMA * SIGMA)).exp())
+        .collect();
+    let sum: f32 = kernel.iter().sum();
+    kernel.into_iter().map(|weight| weight / sum).collect()
+}
+
+/// Apply a separable blur to a plane, clamping samples to the edges.
+fn blur(plane: &Plane, kernel: &[f32]) -> Plane {
+    let (width, height) = plane.dimensions();
+    let sample = |plane: &Plane, x: i64, y: i64| {
+        let x = x.clamp(0, width as i64 - 1) as u32;
+        let y = y.clamp(0, height as i64 - 1) as u32;
+        plane.get_pixel(x, y).0[0]
+    };
+
+    let convolve = |plane: &Plane, horizontal: bool| {
+        Plane::from_fn(width, height, |x, y| {
+            let value = (-RADIUS..=RADIUS)
+                .zip(kernel)
+                .map(|(offset, weight)| {
+                    let (x, y) = if horizontal {
+                        (x as i64 + offset, y as i64)
+                    } else {
+                        (x as i64, y as i64 + offset)
+                    };
+                    sample(plane, x, y) * weight
+                })
+                .sum();
+            Luma([value])
+        })
+    };
+
+    convolve(&convolve(plane, true), false)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{compare, Metric};
+    use image::{Rgba, RgbaImage};
+
+    fn checkerboard(offset: u8) -> RgbaImage {
+        RgbaImage::from_fn(32, 32, |x, y| {
+            let value = if (x / 4 + y / 4) % 2 == 0 { 200 } else { 40 };
+            Rgba([value + offset, value, value - offset, 255])
+        })
+    }
+
+    #[test]
+    fn ssim_identical() {
+        let image = checkerboard(0);
+        let similarity = compare(Metric::Ssim, &image, &image).unwrap();
+        assert!((similarity.score - 1.0).abs() < 1e-6);
+        assert!(similarity
+            .image
+            .to_luma8()
+            .pixels()
+            .all(|pixel| pixel.0[0] == 0));
+    }
+
+    #[test]
+    fn ssim_precision() {
+        // differences in precision between GPUs should barely affect the score,
+        // while a structural difference should.
+        let similarity = compare(Metric::Ssim, &checkerboard(0), &checkerboard(1)).unwrap();
+        assert!(similarity.score > 0.99);
+
+        let flat = RgbaImage::from_pixel(32, 32, Rgba([120, 120, 120, 255]));
+        let similarity = compare(Metric::Ssim, &checkerboard(0), &flat).unwrap();
+        assert!(similarity.score < 0.5);
+    }
+
+    #[test]
+    fn ssim_dimensions() {
+        assert!(compare(Metric::Ssim, &RgbaImage::new(4, 4), &RgbaImage::new(4, 2)).is_err());
+    }
+}
@@ -0,0 +1,399 @@
+//! Reference image corpus for render tests.
+//!
+//! A corpus is described by a JSON manifest listing shader presets to render against an input
+//! image, along with the tolerance each rendered frame is allowed to deviate from the golden
+//! image of a runtime. Golden images are stored next to the manifest, at
+//! `golden/<runtime>/<name>.png`.
+use crate::compare;
+use anyhow::anyhow;
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// How far a rendered frame may deviate from its golden image.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Tolerance {
+    /// The maximum root mean square error over every channel of all unmasked pixels,
+    /// normalized to the range `0.0` to `1.0`.
+    pub max_rmse: f64,
+    /// The maximum absolute difference of the red, green, blue and alpha channels of a pixel
+    /// before the pixel counts as mismatched.
+    pub channels: [u8; 4],
+    /// The maximum number of unmasked pixels that may be mismatched.
+    pub max_mismatched_pixels: usize,
+    /// The minimum mean structural similarity of all unmasked pixels.
+    ///
+    /// If set, the rendered frame is checked by its structural similarity instead of
+    /// per-pixel differences, which tolerates differences in precision between GPUs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_ssim: Option<f64>,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            max_rmse: 0.005,
+            channels: [4; 4],
+            max_mismatched_pixels: 0,
+            min_ssim: None,
+        }
+    }
+}
+
+/// A region of a frame that is known to differ, and is excluded from comparisons.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Mask {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// The runtimes the mask applies to. If empty, the mask applies to every runtime.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runtimes: Vec<String>,
+}
+
+impl Mask {
+    fn applies_to(&self, runtime: &str) -> bool {
+        self.runtimes.is_empty() || self.runtimes.iter().any(|name| name == runtime)
+    }
+
+    fn contains(&self, x: u32, y: u32) -> bool {
+        (self.x..self.x.saturating_add(self.width)).contains(&x)
+            && (self.y..self.y.saturating_add(self.height)).contains(&y)
+    }
+}
+
+/// A shader preset in a corpus.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CorpusEntry {
+    /// The name of the golden images of the preset, which must be unique within the corpus.
+    pub name: String,
+    /// The path to the shader preset, relative to the manifest.
+    pub preset: PathBuf,
+    /// The frame to render.
+    #[serde(default)]
+    pub frame: usize,
+    /// The dimensions of the rendered frame, in the same format as the `--dimensions` option
+    /// of the CLI. Defaults to the dimensions of the input image.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<String>,
+    /// Parameters to set on the shader preset.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, f32>,
+    /// The tolerance of the preset, replacing the default tolerance of the corpus.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<Tolerance>,
+    /// Tolerances of the preset for specific runtimes, replacing any other tolerance.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub runtime_tolerance: BTreeMap<String, Tolerance>,
+    /// Regions of the rendered frame that are known to differ.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub masks: Vec<Mask>,
+}
+
+/// A reference image corpus.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Corpus {
+    /// The path to the input image, relative to the manifest.
+    pub image: PathBuf,
+    /// The default tolerance of every preset in the corpus.
+    #[serde(default)]
+    pub tolerance: Tolerance,
+    /// The shader presets in the corpus.
+    pub presets: Vec<CorpusEntry>,
+    /// The directory containing the manifest.
+    #[serde(skip)]
+    root: PathBuf,
+}
+
+impl Corpus {
+    /// Load a corpus from the path to its manifest.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let manifest = std::fs::read_to_string(path)?;
+        let mut corpus: Corpus = serde_json::from_str(&manifest)?;
+        corpus.root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        for (index, entry) in corpus.presets.iter().enumerate() {
+            if entry.name.is_empty()
+                || entry
+                    .name
+                    .contains(|c: char| std::path::is_separator(c) || c == '.')
+            {
+                return Err(anyhow!("Invalid preset name {:?}", entry.name));
+            }
+
+            if corpus.presets[..index]
+                .iter()
+                .any(|other| other.name == entry.name)
+            {
+                return Err(anyhow!("Duplicate preset name {:?}", entry.name));
+            }
+        }
+
+        Ok(corpus)
+    }
+
+    /// Get the path to the input image.
+    pub fn image_path(&self) -> PathBuf {
+        self.root.join(&self.image)
+    }
+
+    /// Get the path to the shader preset of an entry.
+    pub fn preset_path(&self, entry: &CorpusEntry) -> PathBuf {
+        self.root.join(&entry.preset)
+    }
+
+    /// Get the path to the golden image of an entry for a runtime.
+    pub fn golden_path(&self, entry: &CorpusEntry, runtime: &str) -> PathBuf {
+        self.root
+            .join("golden")
+            .join(runtime)
+            .join(&entry.name)
+            .with_extension("png")
+    }
+
+    /// Get the tolerance of an entry for a runtime.
+    pub fn tolerance(&self, entry: &CorpusEntry, runtime: &str) -> Tolerance {
+        entry
+            .runtime_tolerance
+            .get(runtime)
+            .or(entry.tolerance.as_ref())
+            .copied()
+            .unwrap_or(self.tolerance)
+    }
+}
+
+/// The difference between a rendered frame and its golden image.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Comparison {
+    /// The root mean square error over every channel of all unmasked pixels.
+    pub rmse: f64,
+    /// The largest difference of each channel over all unmasked pixels.
+    pub max_difference: [u8; 4],
+    /// The number of unmasked pixels that exceed the channel tolerance.
+    pub mismatched_pixels: usize,
+    /// The mean structural similarity of all unmasked pixels.
+    pub ssim: f64,
+    /// The number of pixels excluded by masks.
+    pub masked_pixels: usize,
+}
+
+impl Comparison {
+    /// Compare a rendered frame against its golden image, excluding the masks that apply
+    /// to the runtime.
+    pub fn new(
+        golden: &RgbaImage,
+        rendered: &RgbaImage,
+        tolerance: &Tolerance,
+        masks: &[Mask],
+        runtime: &str,
+    ) -> anyhow::Result<Self> {
+        if golden.dimensions() != rendered.dimensions() {
+            return Err(anyhow!(
+                "Rendered frame is {}x{}, but the golden image is {}x{}",
+                rendered.width(),
+                rendered.height(),
+                golden.width(),
+                golden.height()
+            ));
+        }
+
+        let masks: Vec<&Mask> = masks
+            .iter()
+            .filter(|mask| mask.applies_to(runtime))
+            .collect();
+
+        let ssim_map = compare::ssim_map(golden, rendered)?;
+        let mut squared_error = 0f64;
+        let mut samples = 0usize;
+        let mut max_difference = [0u8; 4];
+        let mut mismatched_pixels = 0;
+        let mut masked_pixels = 0;
+
+        for (x, y, rendered) in rendered.enumerate_pixels() {
+            if masks.iter().any(|mask| mask.contains(x, y)) {
+                masked_pixels += 1;
+                continue;
+            }
+
+            let golden = golden.get_pixel(x, y);
+            let mut mismatched = false;
+            for (channel, max_difference) in max_difference.iter_mut().enumerate() {
+                let difference = golden.0[channel].abs_diff(rendered.0[channel]);
+                squared_error += (difference as f64 / 255.0).powi(2);
+                *max_difference = (*max_difference).max(difference);
+                mismatched |= difference > tolerance.channels[channel];
+            }
+
+            samples += 4;
+            mismatched_pixels += mismatched as usize;
+        }
+
+        let rmse = if samples == 0 {
+            0.0
+        } else {
+            (squared_error / samples as f64).sqrt()
+        };
+
+        let ssim = compare::mean(
+            ssim_map
+                .enumerate_pixels()
+                .filter(|(x, y, _)| !masks.iter().any(|mask| mask.contains(*x, *y)))
+                .map(|(_, _, ssim)| ssim.0[0]),
+        );
+
+        Ok(Self {
+            rmse,
+            max_difference,
+            mismatched_pixels,
+            ssim,
+            masked_pixels,
+        })
+    }
+
+    /// Whether the comparison is within the tolerance.
+    pub fn passes(&self, tolerance: &Tolerance) -> bool {
+        if let Some(min_ssim) = tolerance.min_ssim {
+            return self.ssim >= min_ssim;
+        }
+
+        self.rmse <= tolerance.max_rmse && self.mismatched_pixels <= tolerance.max_mismatched_pixels
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Comparison, Corpus, Mask, Tolerance};
+    use image::{Rgba, RgbaImage};
+    use std::path::Path;
+
+    #[test]
+    fn compare_tolerance() {
+        let golden = RgbaImage::from_pixel(4, 4, Rgba([100, 100, 100, 255]));
+        let mut rendered = golden.clone();
+        rendered.put_pixel(1, 1, Rgba([110, 100, 100, 255]));
+
+        let tolerance = Tolerance::default();
+        let comparison = Comparison::new(&golden, &rendered, &tolerance, &[], "vulkan").unwrap();
+        assert_eq!(comparison.max_difference, [10, 0, 0, 0]);
+        assert_eq!(comparison.mismatched_pixels, 1);
+        assert!(!comparison.passes(&tolerance));
+
+        let tolerance = Tolerance {
+            max_rmse: 0.01,
+            max_mismatched_pixels: 1,
+            ..Tolerance::default()
+        };
+        assert!(comparison.passes(&tolerance));
+    }
+
+    #[test]
+    fn compare_ssim() {
+        let golden = RgbaImage::from_fn(16, 16, |x, y| {
+            Rgba([(x * 16) as u8, (y * 16) as u8, 0, 255])
+        });
+        let rendered = RgbaImage::from_fn(16, 16, |x, y| {
+            Rgba([(x * 16) as u8 + 1, (y * 16) as u8, 0, 255])
+        });
+
+        let tolerance = Tolerance {
+            channels: [0; 4],
+            ..Tolerance::default()
+        };
+        let comparison = Comparison::new(&golden, &rendered, &tolerance, &[], "vulkan").unwrap();
+        assert_eq!(comparison.mismatched_pixels, 256);
+        assert!(comparison.ssim > 0.99);
+        assert!(!comparison.passes(&tolerance));
+
+        let tolerance = Tolerance {
+            min_ssim: Some(0.99),
+            ..tolerance
+        };
+        assert!(comparison.passes(&tolerance));
+    }
+
+    #[test]
+    fn compare_masks() {
+        let golden = RgbaImage::from_pixel(4, 4, Rgba([0, 0, 0, 255]));
+        let mut rendered = golden.clone();
+        rendered.put_pixel(3, 0, Rgba([255, 255, 255, 255]));
+
+        let masks = [Mask {
+            x: 2,
+            y: 0,
+            width: 2,
+            height: 1,
+            runtimes: vec!["wgpu".to_string()],
+        }];
+
+        let tolerance = Tolerance::default();
+        let comparison = Comparison::new(&golden, &rendered, &tolerance, &masks, "wgpu").unwrap();
+        assert_eq!(comparison.masked_pixels, 2);
+        assert_eq!(comparison.rmse, 0.0);
+        assert!(comparison.passes(&tolerance));
+
+        let comparison = Comparison::new(&golden, &rendered, &tolerance, &masks, "vulkan").unwrap();
+        assert_eq!(comparison.masked_pixels, 0);
+        assert!(!comparison.passes(&tolerance));
+    }
+
+    #[test]
+    fn compare_dimensions() {
+        let golden = RgbaImage::new(4, 4);
+        let rendered = RgbaImage::new(4, 2);
+        assert!(Comparison::new(&golden, &rendered, &Tolerance::default(), &[], "wgpu").is_err());
+    }
+
+    #[test]
+    fn corpus_tolerance() {
+        let manifest = r#"{
+            "image": "input.png",
+            "tolerance": { "max_rmse": 0.02 },
+            "presets": [
+                {
+                    "name": "crt-royale",
+                    "preset": "crt/crt-royale.slangp",
+                    "frame": 10,
+                    "tolerance": { "channels": [8, 8, 8, 0] },
+                    "runtime_tolerance": { "opengl3": { "max_mismatched_pixels": 16 } }
+                },
+                { "name": "nearest", "preset": "stock.slangp" }
+            ]
+        }"#;
+
+        let path =
+            std::env::temp_dir().join(format!("librashader-corpus-{}.json", std::process::id()));
+        std::fs::write(&path, manifest).unwrap();
+        let corpus = Corpus::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        let corpus = corpus.unwrap();
+
+        let [royale, nearest] = &corpus.presets[..] else {
+            panic!("expected two presets");
+        };
+
+        assert_eq!(corpus.tolerance(nearest, "vulkan").max_rmse, 0.02);
+        assert_eq!(corpus.tolerance(royale, "vulkan").channels, [8, 8, 8, 0]);
+        assert_eq!(corpus.tolerance(royale, "vulkan").max_rmse, 0.005);
+        assert_eq!(
+            corpus.tolerance(royale, "opengl3").max_mismatched_pixels,
+            16
+        );
+        assert_eq!(
+            corpus.golden_path(royale, "vulkan"),
+            std::env::temp_dir()
+                .join("golden")
+                .join("vulkan")
+                .join("crt-royale.png")
+        );
+        assert_eq!(
+            corpus.preset_path(royale),
+            std::env::temp_dir().join(Path::new("crt/crt-royale.slangp"))
+        );
+    }
+}
@@ -1,2 +1,8 @@
 /// Render tests
 pub mod render;
+
+/// Reference image corpus for render tests
+pub mod golden;
+
+/// Image comparison for render tests
+pub mod compare;
@@ -1,33 +1,34 @@
-use crate::render::{CommonFrameOptions, RenderTest};
+use crate::render::{check_input_size, CommonFrameOptions, RenderTest};
 use anyhow::anyhow;
 use image::RgbaImage;
 use librashader::runtime::d3d11::*;
 use librashader::runtime::{FilterChainParameters, RuntimeParameters};
 use librashader::runtime::{Size, Viewport};
 use std::io::{Cursor, Write};
-use std::ops::DerefMut;
-use std::path::Path;
+use std::ops::{DerefMut, RangeInclusive};
 
 impl RenderTest for Direct3D11 {
-    fn new(path: &Path) -> anyhow::Result<Self>
+    fn new_with_image(image: RgbaImage) -> anyhow::Result<Self>
     where
         Self: Sized,
     {
-        Direct3D11::new(path)
+        Direct3D11::new_with_image(image)
     }
 
     fn image_size(&self) -> Size<u32> {
         self.image_bytes.size
     }
 
-    fn render_with_preset_and_params(
+    fn render_sequence_with_preset_and_params(
         &mut self,
         preset: ShaderPreset,
-        frame_count: usize,
+        frames: RangeInclusive<usize>,
         output_size: Option<Size<u32>>,
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
-    ) -> anyhow::Result<image::RgbaImage> {
+        mut frame_source: Option<&mut dyn FnMut(usize) -> anyhow::Result<Option<RgbaImage>>>,
+        frame_sink: &mut dyn FnMut(usize, RgbaImage) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
         let output_size = output_size.unwrap_or(self.image_bytes.size);
         let (renderbuffer, rtv) = self.create_renderbuffer(output_size)?;
 
@@ -38,6 +39,21 @@ impl RenderTest for Direct3D11 {
                 Some(&FilterChainOptions {
                     force_no_mipmaps: false,
                     disable_cache: false,
+                    max_history: 0,
+                    format_fallback: 0,
+                    use_keyed_mutex: false,
+                    keyed_mutex_acquire_key: 0,
+                    keyed_mutex_release_key: 0,
+                    disable_temporal: false,
+                    freeze_frame_count_on_rewind: false,
+                    collect_statistics: false,
+                    progress: None,
+                    cancel: None,
+                    lut_bundle: None,
+                    device_context: None,
+                    color_assist: false,
+                    black_frame_insertion: false,
+                    instance: None,
                 }),
             )?;
 
@@ -54,14 +70,15 @@ impl RenderTest for Direct3D11 {
                 aspect_ratio: options.aspect_ratio,
                 frametime_delta: options.frametime_delta,
                 frames_per_second: options.frames_per_second,
+                transpose_mvp: options.transpose_mvp,
+                flip_mvp_y: options.flip_mvp_y,
+                identity_scaling: options.identity_scaling,
+                scale_final_pass: options.scale_final_pass,
+                jitter_sequence: options.jitter_sequence,
+                jitter_offset: options.jitter_offset,
             });
 
-            for frame in 0..=frame_count {
-                filter_chain.frame(None, &self.image_srv, &viewport, frame, options.as_ref())?;
-            }
-
             let mut renderbuffer_desc = Default::default();
-            self.immediate_context.Flush();
             renderbuffer.GetDesc(&mut renderbuffer_desc);
 
             let mut staging = None;
@@ -80,33 +97,51 @@ impl RenderTest for Direct3D11 {
 
             let staging = staging.ok_or(anyhow!("Unable to create staging texture"))?;
 
-            self.immediate_context.CopyResource(&staging, &renderbuffer);
+            for frame in 0..=*frames.end() {
+                if let Some(source) = frame_source.as_mut() {
+                    let Some(image) = source(frame)? else {
+                        break;
+                    };
+                    self.update_image(image)?;
+                }
 
-            let mut pixels: Vec<u8> = Vec::new();
+                filter_chain.frame(None, &self.image_srv, &viewport, frame, options.as_ref())?;
 
-            let mut map_info = Default::default();
-            self.immediate_context
-                .Map(&staging, 0, D3D11_MAP_READ, 0, Some(&mut map_info))?;
+                if !frames.contains(&frame) {
+                    continue;
+                }
 
-            let slice = std::slice::from_raw_parts(
-                map_info.pData as *const u8,
-                (renderbuffer_desc.Height * map_info.RowPitch) as usize,
-            );
-            pixels.resize(
-                (renderbuffer_desc.Height * renderbuffer_desc.Width * 4) as usize,
-                0,
-            );
+                self.immediate_context.Flush();
+                self.immediate_context.CopyResource(&staging, &renderbuffer);
 
-            let mut cursor = Cursor::new(pixels.deref_mut());
-            for chunk in slice.chunks(map_info.RowPitch as usize) {
-                cursor.write_all(&chunk[..(renderbuffer_desc.Width * 4) as usize])?
-            }
+                let mut pixels: Vec<u8> = Vec::new();
+
+                let mut map_info = Default::default();
+                self.immediate_context
+                    .Map(&staging, 0, D3D11_MAP_READ, 0, Some(&mut map_info))?;
 
-            let image = RgbaImage::from_raw(output_size.width, output_size.height, pixels)
-                .ok_or(anyhow!("Unable to create image from data"))?;
-            self.immediate_context.Unmap(&staging, 0);
+                let slice = std::slice::from_raw_parts(
+                    map_info.pData as *const u8,
+                    (renderbuffer_desc.Height * map_info.RowPitch) as usize,
+                );
+                pixels.resize(
+                    (renderbuffer_desc.Height * renderbuffer_desc.Width * 4) as usize,
+                    0,
+                );
 
-            Ok(image)
+                let mut cursor = Cursor::new(pixels.deref_mut());
+                for chunk in slice.chunks(map_info.RowPitch as usize) {
+                    cursor.write_all(&chunk[..(renderbuffer_desc.Width * 4) as usize])?
+                }
+
+                let image = RgbaImage::from_raw(output_size.width, output_size.height, pixels)
+                    .ok_or(anyhow!("Unable to create image from data"))?;
+                self.immediate_context.Unmap(&staging, 0);
+
+                frame_sink(frame, image)?;
+            }
+
+            Ok(())
         }
     }
 }
@@ -121,7 +156,7 @@ use windows::{
 pub struct Direct3D11 {
     device: ID3D11Device,
     immediate_context: ID3D11DeviceContext,
-    _image_tex: ID3D11Texture2D,
+    image_tex: ID3D11Texture2D,
     image_srv: ID3D11ShaderResourceView,
     image_bytes: Image,
 }
@@ -151,24 +186,24 @@ impl Direct3D11 {
         Ok((dxgi_factory, out_device.unwrap(), out_context.unwrap()))
     }
 
-    pub fn new(image_path: &Path) -> anyhow::Result<Self> {
+    pub fn new_with_image(image: RgbaImage) -> anyhow::Result<Self> {
         let (_factory, device, imm_context) = Self::create_device()?;
 
-        let (image, image_tex, srv) = Self::load_image(&device, image_path)?;
+        let (image, image_tex, srv) = Self::load_image(&device, image)?;
         Ok(Self {
             device,
             immediate_context: imm_context,
             image_bytes: image,
-            _image_tex: image_tex,
+            image_tex,
             image_srv: srv,
         })
     }
 
     fn load_image(
         device: &ID3D11Device,
-        image_path: &Path,
+        image: RgbaImage,
     ) -> anyhow::Result<(Image, ID3D11Texture2D, ID3D11ShaderResourceView)> {
-        let image = Image::load(image_path, UVDirection::TopLeft)?;
+        let image = Image::load_from_buffer(image.into(), UVDirection::TopLeft)?;
         let desc = D3D11_TEXTURE2D_DESC {
             Width: image.size.width,
             Height: image.size.height,
@@ -221,6 +256,24 @@ impl Direct3D11 {
         }
     }
 
+    fn update_image(&self, image: RgbaImage) -> anyhow::Result<()> {
+        check_input_size(&image, self.image_bytes.size)?;
+        let image: Image = Image::load_from_buffer(image.into(), UVDirection::TopLeft)?;
+
+        unsafe {
+            self.immediate_context.UpdateSubresource(
+                &self.image_tex,
+                0,
+                None,
+                image.bytes.as_ptr().cast(),
+                image.pitch as u32,
+                0,
+            );
+        }
+
+        Ok(())
+    }
+
     fn create_renderbuffer(
         &self,
         size: Size<u32>,
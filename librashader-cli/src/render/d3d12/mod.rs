@@ -2,7 +2,7 @@ mod descriptor_heap;
 mod util;
 
 use crate::render::d3d12::descriptor_heap::{CpuStagingHeap, RenderTargetHeap};
-use crate::render::{CommonFrameOptions, RenderTest};
+use crate::render::{check_input_size, CommonFrameOptions, RenderTest};
 use anyhow::anyhow;
 use d3d12_descriptor_heap::{D3D12DescriptorHeap, D3D12DescriptorHeapSlot};
 use image::RgbaImage;
@@ -11,7 +11,7 @@ use librashader::runtime::d3d12::{D3D12OutputView, FilterChain, FilterChainOptio
 use librashader::runtime::{FilterChainParameters, RuntimeParameters};
 use librashader::runtime::{Size, Viewport};
 use librashader_runtime::image::{Image, PixelFormat, UVDirection, BGRA8};
-use std::path::Path;
+use std::ops::RangeInclusive;
 use windows::core::Interface;
 use windows::Win32::Foundation::CloseHandle;
 use windows::Win32::Graphics::Direct3D::{D3D_FEATURE_LEVEL_11_0, D3D_FEATURE_LEVEL_12_1};
@@ -51,24 +51,26 @@ pub struct Direct3D12 {
 }
 
 impl RenderTest for Direct3D12 {
-    fn new(path: &Path) -> anyhow::Result<Self>
+    fn new_with_image(image: RgbaImage) -> anyhow::Result<Self>
     where
         Self: Sized,
     {
-        Direct3D12::new(path)
+        Direct3D12::new_with_image(image)
     }
 
     fn image_size(&self) -> Size<u32> {
         self.image.size
     }
-    fn render_with_preset_and_params(
+    fn render_sequence_with_preset_and_params(
         &mut self,
         preset: ShaderPreset,
-        frame_count: usize,
+        frames: RangeInclusive<usize>,
         output_size: Option<Size<u32>>,
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
-    ) -> anyhow::Result<image::RgbaImage> {
+        mut frame_source: Option<&mut dyn FnMut(usize) -> anyhow::Result<Option<RgbaImage>>>,
+        frame_sink: &mut dyn FnMut(usize, RgbaImage) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
         unsafe {
             let descriptor = self.rtv_heap.allocate_descriptor()?;
 
@@ -89,6 +91,18 @@ impl RenderTest for Direct3D12 {
                     force_hlsl_pipeline: false,
                     force_no_mipmaps: false,
                     disable_cache: false,
+                    max_history: 0,
+                    format_fallback: 0,
+                    disable_temporal: false,
+                    freeze_frame_count_on_rewind: false,
+                    async_pipeline_rebuild: false,
+                    collect_statistics: false,
+                    progress: None,
+                    cancel: None,
+                    color_assist: false,
+                    black_frame_insertion: false,
+                    latency_markers: None,
+                    instance: None,
                 }),
             )?;
 
@@ -155,47 +169,90 @@ impl RenderTest for Direct3D12 {
                 aspect_ratio: options.aspect_ratio,
                 frametime_delta: options.frametime_delta,
                 frames_per_second: options.frames_per_second,
+                transpose_mvp: options.transpose_mvp,
+                flip_mvp_y: options.flip_mvp_y,
+                identity_scaling: options.identity_scaling,
+                scale_final_pass: options.scale_final_pass,
+                jitter_sequence: options.jitter_sequence,
+                jitter_offset: options.jitter_offset,
+                ..Default::default()
             });
 
             let image = self.texture.to_ref();
 
-            for frame in 0..=frame_count {
-                filter_chain.frame(&cmd, image.into(), &viewport, frame, options.as_ref())?;
-            }
+            let has_source = frame_source.is_some();
+            let mut fence_value = 0;
+            for frame in 0..=*frames.end() {
+                let upload = match frame_source.as_mut() {
+                    Some(source) => {
+                        let Some(input) = source(frame)? else {
+                            break;
+                        };
+                        Some(self.record_image_update(&cmd, input)?)
+                    }
+                    None => None,
+                };
 
-            cmd.Close()?;
-            self.queue.ExecuteCommandLists(&[Some(cmd.cast()?)]);
-            self.queue.Signal(&fence, 1)?;
+                filter_chain.frame(&cmd, image.into(), &viewport, frame, options.as_ref())?;
 
-            if fence.GetCompletedValue() < 1 {
-                fence.SetEventOnCompletion(1, fence_event)?;
-                WaitForSingleObject(fence_event, INFINITE);
-                CloseHandle(fence_event)?;
-            };
+                let captured = frames.contains(&frame);
+
+                // The input image changes every frame with a frame source, so every frame
+                // needs to finish executing before the upload buffer can be released.
+                if !captured && !has_source {
+                    continue;
+                }
+
+                cmd.Close()?;
+                self.queue.ExecuteCommandLists(&[Some(cmd.cast()?)]);
+                fence_value += 1;
+                self.queue.Signal(&fence, fence_value)?;
+
+                if fence.GetCompletedValue() < fence_value {
+                    fence.SetEventOnCompletion(fence_value, fence_event)?;
+                    WaitForSingleObject(fence_event, INFINITE);
+                };
+
+                drop(upload);
+
+                if captured {
+                    let mut buffer =
+                        vec![0u8; (output_size.height * output_size.width) as usize * 4];
+
+                    output_texture.ReadFromSubresource(
+                        buffer.as_mut_ptr().cast(),
+                        4 * output_size.width,
+                        0,
+                        0,
+                        None,
+                    )?;
+
+                    BGRA8::convert(&mut buffer);
+
+                    let image = RgbaImage::from_raw(
+                        output_size.width,
+                        output_size.height,
+                        Vec::from(buffer),
+                    )
+                    .ok_or(anyhow!("Unable to create image from data"))?;
 
-            let mut buffer = vec![0u8; (output_size.height * output_size.width) as usize * 4];
+                    frame_sink(frame, image)?;
+                }
 
-            output_texture.ReadFromSubresource(
-                buffer.as_mut_ptr().cast(),
-                4 * output_size.width,
-                0,
-                0,
-                None,
-            )?;
-
-            BGRA8::convert(&mut buffer);
+                // The GPU is idle here, so the command list can be recorded into again.
+                self.command_pool.Reset()?;
+                cmd.Reset(&self.command_pool, None)?;
+            }
 
-            let image =
-                RgbaImage::from_raw(output_size.width, output_size.height, Vec::from(buffer))
-                    .ok_or(anyhow!("Unable to create image from data"))?;
+            CloseHandle(fence_event)?;
 
-            Ok(image)
+            Ok(())
         }
     }
 }
 
 impl Direct3D12 {
-    pub fn new(image_path: &Path) -> anyhow::Result<Self> {
+    pub fn new_with_image(image: RgbaImage) -> anyhow::Result<Self> {
         let device = Self::create_device()?;
         let mut heap = unsafe { D3D12DescriptorHeap::new(&device, 8)? };
         let rtv_heap = unsafe { D3D12DescriptorHeap::new(&device, 16)? };
@@ -212,7 +269,7 @@ impl Direct3D12 {
                     NodeMask: 0,
                 })?;
             let (image, texture, heap_slot) =
-                Self::load_image(&device, &command_pool, &queue, &mut heap, image_path)?;
+                Self::load_image(&device, &command_pool, &queue, &mut heap, image)?;
 
             Ok(Self {
                 device,
@@ -245,14 +302,14 @@ impl Direct3D12 {
         command_pool: &ID3D12CommandAllocator,
         queue: &ID3D12CommandQueue,
         heap: &mut D3D12DescriptorHeap<CpuStagingHeap>,
-        path: &Path,
+        image: RgbaImage,
     ) -> anyhow::Result<(
         Image<BGRA8>,
         ID3D12Resource,
         D3D12DescriptorHeapSlot<CpuStagingHeap>,
     )> {
         // 1 time queue infrastructure for lut uploads
-        let image: Image<BGRA8> = Image::load(path, UVDirection::TopLeft)?;
+        let image: Image<BGRA8> = Image::load_from_buffer(image.into(), UVDirection::TopLeft)?;
 
         let desc = D3D12_RESOURCE_DESC {
             Dimension: D3D12_RESOURCE_DIMENSION_TEXTURE2D,
@@ -314,6 +371,44 @@ impl Direct3D12 {
 
             device.CreateShaderResourceView(&resource, Some(&srv_desc), *descriptor.as_ref());
 
+            let upload = Self::record_image_upload(device, &cmd, &resource, &image)?;
+
+            cmd.Close()?;
+            queue.ExecuteCommandLists(&[Some(cmd.cast()?)]);
+            queue.Signal(&fence, 1)?;
+
+            if fence.GetCompletedValue() < 1 {
+                fence.SetEventOnCompletion(1, fence_event)?;
+                WaitForSingleObject(fence_event, INFINITE);
+                CloseHandle(fence_event)?;
+            }
+
+            drop(upload);
+
+            Ok((image, resource, descriptor))
+        }
+    }
+
+    /// Record a command to replace the input image, returning the upload buffer that
+    /// must be kept alive until the command list has finished executing.
+    fn record_image_update(
+        &self,
+        cmd: &ID3D12GraphicsCommandList,
+        image: RgbaImage,
+    ) -> anyhow::Result<ID3D12Resource> {
+        check_input_size(&image, self.image.size)?;
+        let image: Image<BGRA8> = Image::load_from_buffer(image.into(), UVDirection::TopLeft)?;
+        Self::record_image_upload(&self.device, cmd, &self.texture, &image)
+    }
+
+    fn record_image_upload(
+        device: &ID3D12Device,
+        cmd: &ID3D12GraphicsCommandList,
+        resource: &ID3D12Resource,
+        image: &Image<BGRA8>,
+    ) -> anyhow::Result<ID3D12Resource> {
+        unsafe {
+            let desc = resource.GetDesc();
             let mut buffer_desc = D3D12_RESOURCE_DESC {
                 Dimension: D3D12_RESOURCE_DIMENSION_BUFFER,
                 ..Default::default()
@@ -366,32 +461,22 @@ impl Direct3D12 {
             }];
 
             util::d3d12_resource_transition(
-                &cmd,
-                &resource,
+                cmd,
+                resource,
                 D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
                 D3D12_RESOURCE_STATE_COPY_DEST,
             );
 
-            util::d3d12_update_subresources(&cmd, &resource, &upload, 0, 0, 1, &subresource)?;
+            util::d3d12_update_subresources(cmd, resource, &upload, 0, 0, 1, &subresource)?;
 
             util::d3d12_resource_transition(
-                &cmd,
-                &resource,
+                cmd,
+                resource,
                 D3D12_RESOURCE_STATE_COPY_DEST,
                 D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
             );
 
-            cmd.Close()?;
-            queue.ExecuteCommandLists(&[Some(cmd.cast()?)]);
-            queue.Signal(&fence, 1)?;
-
-            if fence.GetCompletedValue() < 1 {
-                fence.SetEventOnCompletion(1, fence_event)?;
-                WaitForSingleObject(fence_event, INFINITE);
-                CloseHandle(fence_event)?;
-            }
-
-            Ok((image, resource, descriptor))
+            Ok(upload)
         }
     }
 
@@ -1,4 +1,4 @@
-use crate::render::{CommonFrameOptions, RenderTest};
+use crate::render::{check_input_size, CommonFrameOptions, RenderTest};
 use anyhow::anyhow;
 use image::RgbaImage;
 use librashader::presets::ShaderPreset;
@@ -6,7 +6,7 @@ use librashader::runtime::d3d9::{FilterChain, FilterChainOptions, FrameOptions};
 use librashader::runtime::{FilterChainParameters, RuntimeParameters};
 use librashader::runtime::{Size, Viewport};
 use librashader_runtime::image::{Image, PixelFormat, UVDirection, BGRA8};
-use std::path::Path;
+use std::ops::RangeInclusive;
 use windows::Win32::Foundation::{HWND, TRUE};
 use windows::Win32::Graphics::Direct3D9::{
     Direct3DCreate9, IDirect3D9, IDirect3DDevice9, IDirect3DTexture9, D3DADAPTER_DEFAULT,
@@ -24,25 +24,27 @@ pub struct Direct3D9 {
 }
 
 impl RenderTest for Direct3D9 {
-    fn new(path: &Path) -> anyhow::Result<Self>
+    fn new_with_image(image: RgbaImage) -> anyhow::Result<Self>
     where
         Self: Sized,
     {
-        Direct3D9::new(path)
+        Direct3D9::new_with_image(image)
     }
 
     fn image_size(&self) -> Size<u32> {
         self.image.size
     }
 
-    fn render_with_preset_and_params(
+    fn render_sequence_with_preset_and_params(
         &mut self,
         preset: ShaderPreset,
-        frame_count: usize,
+        frames: RangeInclusive<usize>,
         output_size: Option<Size<u32>>,
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
-    ) -> anyhow::Result<image::RgbaImage> {
+        mut frame_source: Option<&mut dyn FnMut(usize) -> anyhow::Result<Option<RgbaImage>>>,
+        frame_sink: &mut dyn FnMut(usize, RgbaImage) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
         unsafe {
             let mut filter_chain = FilterChain::load_from_preset(
                 preset,
@@ -50,6 +52,18 @@ impl RenderTest for Direct3D9 {
                 Some(&FilterChainOptions {
                     force_no_mipmaps: false,
                     disable_cache: false,
+                    max_history: 0,
+                    format_fallback: 0,
+                    disable_temporal: false,
+                    freeze_frame_count_on_rewind: false,
+                    collect_statistics: false,
+                    progress: None,
+                    cancel: None,
+                    lut_bundle: None,
+                    device_context: None,
+                    color_assist: false,
+                    black_frame_insertion: false,
+                    instance: None,
                 }),
             )?;
 
@@ -99,44 +113,63 @@ impl RenderTest for Direct3D9 {
                 aspect_ratio: options.aspect_ratio,
                 frametime_delta: options.frametime_delta,
                 frames_per_second: options.frames_per_second,
+                transpose_mvp: options.transpose_mvp,
+                flip_mvp_y: options.flip_mvp_y,
+                identity_scaling: options.identity_scaling,
+                scale_final_pass: options.scale_final_pass,
+                jitter_sequence: options.jitter_sequence,
+                jitter_offset: options.jitter_offset,
             });
 
             let viewport = Viewport::new_render_target_sized_origin(&surface, None)?;
 
-            for frame in 0..=frame_count {
+            let mut desc = D3DSURFACE_DESC::default();
+            surface.GetDesc(&mut desc)?;
+
+            for frame in 0..=*frames.end() {
+                if let Some(source) = frame_source.as_mut() {
+                    let Some(image) = source(frame)? else {
+                        break;
+                    };
+                    self.update_image(image)?;
+                }
+
                 filter_chain.frame(&self.texture, &viewport, frame, options.as_ref())?;
-            }
 
-            self.device.GetRenderTargetData(&surface, &copy_texture)?;
+                if !frames.contains(&frame) {
+                    continue;
+                }
 
-            let mut desc = D3DSURFACE_DESC::default();
-            surface.GetDesc(&mut desc)?;
+                self.device.GetRenderTargetData(&surface, &copy_texture)?;
 
-            let mut lock = D3DLOCKED_RECT::default();
-            copy_texture.LockRect(&mut lock, std::ptr::null_mut(), 0)?;
-            let mut buffer = vec![0u8; desc.Height as usize * lock.Pitch as usize];
+                let mut lock = D3DLOCKED_RECT::default();
+                copy_texture.LockRect(&mut lock, std::ptr::null_mut(), 0)?;
+                let mut buffer = vec![0u8; desc.Height as usize * lock.Pitch as usize];
+
+                std::ptr::copy_nonoverlapping(lock.pBits.cast(), buffer.as_mut_ptr(), buffer.len());
+                copy_texture.UnlockRect()?;
 
-            std::ptr::copy_nonoverlapping(lock.pBits.cast(), buffer.as_mut_ptr(), buffer.len());
-            copy_texture.UnlockRect()?;
+                BGRA8::convert(&mut buffer);
 
-            BGRA8::convert(&mut buffer);
+                let image = RgbaImage::from_raw(output_size.width, output_size.height, buffer)
+                    .ok_or(anyhow!("Unable to create image from data"))?;
 
-            let image = RgbaImage::from_raw(output_size.width, output_size.height, buffer)
-                .ok_or(anyhow!("Unable to create image from data"))?;
+                frame_sink(frame, image)?;
+            }
 
-            Ok(image)
+            Ok(())
         }
     }
 }
 
 impl Direct3D9 {
-    pub fn new(image_path: impl AsRef<Path>) -> anyhow::Result<Self> {
+    pub fn new_with_image(image: RgbaImage) -> anyhow::Result<Self> {
         let direct3d = unsafe {
             Direct3DCreate9(D3D_SDK_VERSION)
                 .ok_or_else(|| anyhow!("Unable to create Direct3D 9 device"))?
         };
 
-        let image = Image::<BGRA8>::load(image_path, UVDirection::TopLeft)?;
+        let image = Image::<BGRA8>::load_from_buffer(image.into(), UVDirection::TopLeft)?;
 
         let mut present_params: D3DPRESENT_PARAMETERS = Default::default();
         present_params.BackBufferWidth = image.size.width;
@@ -175,6 +208,23 @@ impl Direct3D9 {
             texture.ok_or_else(|| anyhow!("Unable to create Direct3D 9 texture"))?
         };
 
+        Self::write_image(&texture, &image)?;
+
+        Ok(Self {
+            texture,
+            image,
+            direct3d,
+            device,
+        })
+    }
+
+    fn update_image(&self, image: RgbaImage) -> anyhow::Result<()> {
+        check_input_size(&image, self.image.size)?;
+        let image = Image::<BGRA8>::load_from_buffer(image.into(), UVDirection::TopLeft)?;
+        Self::write_image(&self.texture, &image)
+    }
+
+    fn write_image(texture: &IDirect3DTexture9, image: &Image<BGRA8>) -> anyhow::Result<()> {
         unsafe {
             let mut lock = D3DLOCKED_RECT::default();
             texture.LockRect(0, &mut lock, std::ptr::null_mut(), 0)?;
@@ -186,11 +236,6 @@ impl Direct3D9 {
             texture.UnlockRect(0)?;
         }
 
-        Ok(Self {
-            texture,
-            image,
-            direct3d,
-            device,
-        })
+        Ok(())
     }
 }
@@ -1,7 +1,7 @@
 mod context;
 
 use crate::render::gl::context::{GLVersion, GlfwContext};
-use crate::render::{CommonFrameOptions, RenderTest};
+use crate::render::{check_input_size, CommonFrameOptions, RenderTest};
 use anyhow::anyhow;
 use glow::{HasContext, PixelPackData, PixelUnpackData};
 use image::RgbaImage;
@@ -10,7 +10,7 @@ use librashader::runtime::gl::{FilterChain, FilterChainOptions, FrameOptions, GL
 use librashader::runtime::{FilterChainParameters, RuntimeParameters};
 use librashader::runtime::{Size, Viewport};
 use librashader_runtime::image::{Image, UVDirection, RGBA8};
-use std::path::Path;
+use std::ops::RangeInclusive;
 use std::sync::Arc;
 
 struct OpenGl {
@@ -23,25 +23,27 @@ pub struct OpenGl3(OpenGl);
 pub struct OpenGl4(OpenGl);
 
 impl RenderTest for OpenGl3 {
-    fn new(path: &Path) -> anyhow::Result<Self>
+    fn new_with_image(image: RgbaImage) -> anyhow::Result<Self>
     where
         Self: Sized,
     {
-        OpenGl3::new(path)
+        OpenGl3::new_with_image(image)
     }
 
     fn image_size(&self) -> Size<u32> {
         self.0.image_bytes.size
     }
 
-    fn render_with_preset_and_params(
+    fn render_sequence_with_preset_and_params(
         &mut self,
         preset: ShaderPreset,
-        frame_count: usize,
+        frames: RangeInclusive<usize>,
         output_size: Option<Size<u32>>,
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
-    ) -> anyhow::Result<image::RgbaImage> {
+        frame_source: Option<&mut dyn FnMut(usize) -> anyhow::Result<Option<RgbaImage>>>,
+        frame_sink: &mut dyn FnMut(usize, RgbaImage) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
         let mut filter_chain = unsafe {
             FilterChain::load_from_preset(
                 preset,
@@ -51,6 +53,20 @@ impl RenderTest for OpenGl3 {
                     use_dsa: false,
                     force_no_mipmaps: false,
                     disable_cache: false,
+                    input_bottom_left: false,
+                    input_external: false,
+                    max_history: 0,
+                    format_fallback: 0,
+                    disable_temporal: false,
+                    freeze_frame_count_on_rewind: false,
+                    collect_statistics: false,
+                    progress: None,
+                    cancel: None,
+                    lut_bundle: None,
+                    device_context: None,
+                    color_assist: false,
+                    black_frame_insertion: false,
+                    instance: None,
                 }),
             )
         }?;
@@ -59,9 +75,9 @@ impl RenderTest for OpenGl3 {
             setter(filter_chain.parameters());
         }
 
-        Ok(self.0.render(
+        self.0.render(
             &mut filter_chain,
-            frame_count,
+            frames,
             output_size,
             frame_options
                 .map(|options| FrameOptions {
@@ -73,32 +89,42 @@ impl RenderTest for OpenGl3 {
                     aspect_ratio: options.aspect_ratio,
                     frametime_delta: options.frametime_delta,
                     frames_per_second: options.frames_per_second,
+                    transpose_mvp: options.transpose_mvp,
+                    flip_mvp_y: options.flip_mvp_y,
+                    identity_scaling: options.identity_scaling,
+                    scale_final_pass: options.scale_final_pass,
+                    jitter_sequence: options.jitter_sequence,
+                    jitter_offset: options.jitter_offset,
                 })
                 .as_ref(),
-        )?)
+            frame_source,
+            frame_sink,
+        )
     }
 }
 
 impl RenderTest for OpenGl4 {
-    fn new(path: &Path) -> anyhow::Result<Self>
+    fn new_with_image(image: RgbaImage) -> anyhow::Result<Self>
     where
         Self: Sized,
     {
-        OpenGl4::new(path)
+        OpenGl4::new_with_image(image)
     }
 
     fn image_size(&self) -> Size<u32> {
         self.0.image_bytes.size
     }
 
-    fn render_with_preset_and_params(
+    fn render_sequence_with_preset_and_params(
         &mut self,
         preset: ShaderPreset,
-        frame_count: usize,
+        frames: RangeInclusive<usize>,
         output_size: Option<Size<u32>>,
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
-    ) -> anyhow::Result<image::RgbaImage> {
+        frame_source: Option<&mut dyn FnMut(usize) -> anyhow::Result<Option<RgbaImage>>>,
+        frame_sink: &mut dyn FnMut(usize, RgbaImage) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
         let mut filter_chain = unsafe {
             FilterChain::load_from_preset(
                 preset,
@@ -108,6 +134,20 @@ impl RenderTest for OpenGl4 {
                     use_dsa: true,
                     force_no_mipmaps: false,
                     disable_cache: true,
+                    input_bottom_left: false,
+                    input_external: false,
+                    max_history: 0,
+                    format_fallback: 0,
+                    disable_temporal: false,
+                    freeze_frame_count_on_rewind: false,
+                    collect_statistics: false,
+                    progress: None,
+                    cancel: None,
+                    lut_bundle: None,
+                    device_context: None,
+                    color_assist: false,
+                    black_frame_insertion: false,
+                    instance: None,
                 }),
             )
         }?;
@@ -116,9 +156,9 @@ impl RenderTest for OpenGl4 {
             setter(filter_chain.parameters());
         }
 
-        Ok(self.0.render(
+        self.0.render(
             &mut filter_chain,
-            frame_count,
+            frames,
             output_size,
             frame_options
                 .map(|options| FrameOptions {
@@ -130,27 +170,35 @@ impl RenderTest for OpenGl4 {
                     aspect_ratio: options.aspect_ratio,
                     frametime_delta: options.frametime_delta,
                     frames_per_second: options.frames_per_second,
+                    transpose_mvp: options.transpose_mvp,
+                    flip_mvp_y: options.flip_mvp_y,
+                    identity_scaling: options.identity_scaling,
+                    scale_final_pass: options.scale_final_pass,
+                    jitter_sequence: options.jitter_sequence,
+                    jitter_offset: options.jitter_offset,
                 })
                 .as_ref(),
-        )?)
+            frame_source,
+            frame_sink,
+        )
     }
 }
 
 impl OpenGl3 {
-    pub fn new(image_path: &Path) -> anyhow::Result<Self> {
-        Ok(Self(OpenGl::new(image_path, false)?))
+    pub fn new_with_image(image: RgbaImage) -> anyhow::Result<Self> {
+        Ok(Self(OpenGl::new(image, false)?))
     }
 }
 
 impl OpenGl4 {
-    pub fn new(image_path: &Path) -> anyhow::Result<Self> {
-        Ok(Self(OpenGl::new(image_path, true)?))
+    pub fn new_with_image(image: RgbaImage) -> anyhow::Result<Self> {
+        Ok(Self(OpenGl::new(image, true)?))
     }
 }
 
 impl OpenGl {
-    pub fn new(image_path: &Path, use_dsa: bool) -> anyhow::Result<Self> {
-        let image: Image<RGBA8> = Image::load(image_path, UVDirection::TopLeft)?;
+    pub fn new(image: RgbaImage, use_dsa: bool) -> anyhow::Result<Self> {
+        let image: Image<RGBA8> = Image::load_from_buffer(image.into(), UVDirection::TopLeft)?;
         let height = image.size.height;
         let width = image.size.width;
         let version = if use_dsa {
@@ -171,12 +219,42 @@ impl OpenGl {
                 image.size.width as i32,
                 image.size.height as i32,
             );
+            context.gl.bind_texture(glow::TEXTURE_2D, None);
+            tex
+        };
 
-            context.gl.pixel_store_i32(glow::UNPACK_ROW_LENGTH, 0);
-            context.gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 4);
-            context.gl.bind_buffer(glow::PIXEL_UNPACK_BUFFER, None);
+        let gl = Self {
+            context,
+            texture: GLImage {
+                handle: Some(texture),
+                format: glow::RGBA8,
+                size: image.size,
+            },
+            image_bytes: image,
+        };
 
-            context.gl.tex_sub_image_2d(
+        gl.write_image(&gl.image_bytes);
+        Ok(gl)
+    }
+
+    fn update_image(&self, image: RgbaImage) -> anyhow::Result<()> {
+        check_input_size(&image, self.image_bytes.size)?;
+        let image: Image<RGBA8> = Image::load_from_buffer(image.into(), UVDirection::TopLeft)?;
+        self.write_image(&image);
+        Ok(())
+    }
+
+    fn write_image(&self, image: &Image<RGBA8>) {
+        unsafe {
+            self.context
+                .gl
+                .bind_texture(glow::TEXTURE_2D, self.texture.handle);
+
+            self.context.gl.pixel_store_i32(glow::UNPACK_ROW_LENGTH, 0);
+            self.context.gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 4);
+            self.context.gl.bind_buffer(glow::PIXEL_UNPACK_BUFFER, None);
+
+            self.context.gl.tex_sub_image_2d(
                 glow::TEXTURE_2D,
                 0,
                 0,
@@ -188,28 +266,19 @@ impl OpenGl {
                 PixelUnpackData::Slice(&image.bytes),
             );
 
-            context.gl.bind_texture(glow::TEXTURE_2D, None);
-            tex
-        };
-
-        Ok(Self {
-            context,
-            texture: GLImage {
-                handle: Some(texture),
-                format: glow::RGBA8,
-                size: image.size,
-            },
-            image_bytes: image,
-        })
+            self.context.gl.bind_texture(glow::TEXTURE_2D, None);
+        }
     }
 
     pub fn render(
         &self,
         chain: &mut FilterChain,
-        frame_count: usize,
+        frames: RangeInclusive<usize>,
         output_size: Option<Size<u32>>,
         options: Option<&FrameOptions>,
-    ) -> Result<RgbaImage, anyhow::Error> {
+        mut frame_source: Option<&mut dyn FnMut(usize) -> anyhow::Result<Option<RgbaImage>>>,
+        frame_sink: &mut dyn FnMut(usize, RgbaImage) -> anyhow::Result<()>,
+    ) -> Result<(), anyhow::Error> {
         let output_size = output_size.unwrap_or(self.image_bytes.size);
 
         let render_texture = unsafe {
@@ -237,29 +306,42 @@ impl OpenGl {
         };
 
         let viewport = Viewport::new_render_target_sized_origin(&output, None)?;
-        for frame in 0..=frame_count {
+        for frame in 0..=*frames.end() {
+            if let Some(source) = frame_source.as_mut() {
+                let Some(image) = source(frame)? else {
+                    break;
+                };
+                self.update_image(image)?;
+            }
+
             unsafe {
                 chain.frame(&self.texture, &viewport, frame, options)?;
             }
-        }
 
-        let mut data = vec![0u8; output_size.width as usize * output_size.height as usize * 4];
+            if !frames.contains(&frame) {
+                continue;
+            }
 
-        unsafe {
-            self.context
-                .gl
-                .bind_texture(glow::TEXTURE_2D, output.handle);
-            self.context.gl.get_tex_image(
-                glow::TEXTURE_2D,
-                0,
-                glow::RGBA,
-                glow::UNSIGNED_BYTE,
-                PixelPackData::Slice(&mut data),
-            )
+            let mut data = vec![0u8; output_size.width as usize * output_size.height as usize * 4];
+
+            unsafe {
+                self.context
+                    .gl
+                    .bind_texture(glow::TEXTURE_2D, output.handle);
+                self.context.gl.get_tex_image(
+                    glow::TEXTURE_2D,
+                    0,
+                    glow::RGBA,
+                    glow::UNSIGNED_BYTE,
+                    PixelPackData::Slice(&mut data),
+                )
+            }
+
+            let image = RgbaImage::from_raw(output_size.width, output_size.height, data)
+                .ok_or(anyhow!("failed to create image from slice"))?;
+            frame_sink(frame, image)?;
         }
-        Ok(
-            RgbaImage::from_raw(output_size.width, output_size.height, data)
-                .ok_or(anyhow!("failed to create image from slice"))?,
-        )
+
+        Ok(())
     }
 }
@@ -19,16 +19,26 @@ pub mod wgpu;
 #[cfg(all(target_vendor = "apple", feature = "metal"))]
 pub mod mtl;
 
+use anyhow::anyhow;
 use librashader::presets::{ShaderFeatures, ShaderPreset};
 use librashader::runtime::Size;
 use librashader_runtime::impl_default_frame_options;
 use librashader_runtime::parameters::RuntimeParameters;
+use std::ops::RangeInclusive;
 use std::path::Path;
 
 /// Test harness to set up a device, render a triangle, and apply a shader
 pub trait RenderTest {
     /// Create a new instance of the test harness.
     fn new(path: &Path) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::new_with_image(image::open(path)?.to_rgba8())
+    }
+
+    /// Create a new instance of the test harness from an already loaded image.
+    fn new_with_image(image: image::RgbaImage) -> anyhow::Result<Self>
     where
         Self: Sized;
 
@@ -86,14 +96,68 @@ pub trait RenderTest {
         output_size: Option<Size<u32>>,
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
-    ) -> anyhow::Result<image::RgbaImage>;
+    ) -> anyhow::Result<image::RgbaImage> {
+        let mut image = None;
+        self.render_sequence_with_preset_and_params(
+            preset,
+            frame_count..=frame_count,
+            output_size,
+            param_setter,
+            frame_options,
+            None,
+            &mut |_, frame| {
+                image = Some(frame);
+                Ok(())
+            },
+        )?;
+
+        image.ok_or_else(|| anyhow!("No frame was rendered"))
+    }
+
+    /// Render a shader onto a sequence of image buffers, applying the provided shader.
+    ///
+    /// Every frame from 0 up to and including the end of `frames` is rendered to ensure
+    /// feedback and history, but only the frames within `frames` are read back and passed
+    /// to `frame_sink` in order, along with their frame count.
+    ///
+    /// If `frame_source` is provided, it is called with the frame count before each frame is
+    /// rendered, and the image it returns replaces the input image of the test harness. The
+    /// image must have the same dimensions as the image the harness was created with. The
+    /// sequence ends early once `frame_source` returns `None`.
+    #[allow(clippy::too_many_arguments)]
+    fn render_sequence_with_preset_and_params(
+        &mut self,
+        preset: ShaderPreset,
+        frames: RangeInclusive<usize>,
+        output_size: Option<Size<u32>>,
+        param_setter: Option<&dyn Fn(&RuntimeParameters)>,
+        frame_options: Option<CommonFrameOptions>,
+        frame_source: Option<&mut dyn FnMut(usize) -> anyhow::Result<Option<image::RgbaImage>>>,
+        frame_sink: &mut dyn FnMut(usize, image::RgbaImage) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()>;
 }
 
 impl_default_frame_options!(CommonFrameOptions);
 
+/// Ensure that an image from a frame source can replace the input image of a test harness.
+fn check_input_size(image: &image::RgbaImage, size: Size<u32>) -> anyhow::Result<()> {
+    if image.width() != size.width || image.height() != size.height {
+        return Err(anyhow!(
+            "Input frame is {}x{}, but the test harness was created with a {}x{} image",
+            image.width(),
+            image.height(),
+            size.width,
+            size.height
+        ));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
 
+    use crate::compare::Metric;
     use crate::render::RenderTest;
     use image::codecs::png::PngEncoder;
     use librashader::presets::ShaderFeatures;
@@ -169,7 +233,7 @@ mod test {
         let a_image = a.render(FILTER_PATH.as_ref(), ShaderFeatures::NONE, 100)?;
         let b_image = b.render(FILTER_PATH.as_ref(), ShaderFeatures::NONE, 100)?;
 
-        let similarity = image_compare::rgba_hybrid_compare(&a_image, &b_image)?;
+        let similarity = crate::compare::compare(Metric::Ssim, &a_image, &b_image)?;
         assert!(similarity.score > 0.95);
         Ok(())
     }
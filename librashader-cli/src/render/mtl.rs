@@ -1,4 +1,4 @@
-use crate::render::{CommonFrameOptions, RenderTest};
+use crate::render::{check_input_size, CommonFrameOptions, RenderTest};
 use anyhow::anyhow;
 use image::RgbaImage;
 use librashader::presets::ShaderPreset;
@@ -14,7 +14,7 @@ use objc2_metal::{
     MTLPixelFormat, MTLRegion, MTLSize, MTLStorageMode, MTLTexture, MTLTextureDescriptor,
     MTLTextureUsage,
 };
-use std::path::Path;
+use std::ops::RangeInclusive;
 use std::ptr::NonNull;
 
 pub struct Metal {
@@ -24,39 +24,48 @@ pub struct Metal {
 }
 
 impl RenderTest for Metal {
-    fn new(path: &Path) -> anyhow::Result<Self>
+    fn new_with_image(image: RgbaImage) -> anyhow::Result<Self>
     where
         Self: Sized,
     {
-        Metal::new(path)
+        Metal::new_with_image(image)
     }
 
     fn image_size(&self) -> Size<u32> {
         self.image_bytes.size
     }
 
-    fn render_with_preset_and_params(
+    fn render_sequence_with_preset_and_params(
         &mut self,
         preset: ShaderPreset,
-        frame_count: usize,
+        frames: RangeInclusive<usize>,
         output_size: Option<Size<u32>>,
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
-    ) -> anyhow::Result<image::RgbaImage> {
+        mut frame_source: Option<&mut dyn FnMut(usize) -> anyhow::Result<Option<RgbaImage>>>,
+        frame_sink: &mut dyn FnMut(usize, RgbaImage) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
         let queue = self
             .device
             .newCommandQueue()
             .ok_or_else(|| anyhow!("Unable to create command queue"))?;
 
-        let cmd = queue
-            .commandBuffer()
-            .ok_or_else(|| anyhow!("Unable to create command buffer"))?;
-
         let mut filter_chain = FilterChain::load_from_preset(
             preset,
             &queue,
             Some(&FilterChainOptions {
                 force_no_mipmaps: false,
+                max_history: 0,
+                disable_temporal: false,
+                freeze_frame_count_on_rewind: false,
+                collect_statistics: false,
+                progress: None,
+                cancel: None,
+                lut_bundle: None,
+                device_context: None,
+                color_assist: false,
+                black_frame_insertion: false,
+                instance: None,
             }),
         )?;
 
@@ -103,9 +112,46 @@ impl RenderTest for Metal {
             aspect_ratio: options.aspect_ratio,
             frametime_delta: options.frametime_delta,
             frames_per_second: options.frames_per_second,
+            transpose_mvp: options.transpose_mvp,
+            flip_mvp_y: options.flip_mvp_y,
+            identity_scaling: options.identity_scaling,
+            scale_final_pass: options.scale_final_pass,
+            jitter_sequence: options.jitter_sequence,
+            jitter_offset: options.jitter_offset,
         });
 
-        for frame in 0..=frame_count {
+        let region = MTLRegion {
+            origin: MTLOrigin { x: 0, y: 0, z: 0 },
+            size: MTLSize {
+                width: output_size.width as usize,
+                height: output_size.height as usize,
+                depth: 1,
+            },
+        };
+
+        let mut cmd = queue
+            .commandBuffer()
+            .ok_or_else(|| anyhow!("Unable to create command buffer"))?;
+
+        for frame in 0..=*frames.end() {
+            if let Some(source) = frame_source.as_mut() {
+                let Some(image) = source(frame)? else {
+                    break;
+                };
+
+                // Frames that have already been recorded need to finish before
+                // the input texture is overwritten.
+                cmd.commit();
+                unsafe {
+                    cmd.waitUntilCompleted();
+                }
+                cmd = queue
+                    .commandBuffer()
+                    .ok_or_else(|| anyhow!("Unable to create command buffer"))?;
+
+                self.update_image(image)?;
+            }
+
             filter_chain.frame(
                 &self.texture,
                 &viewport,
@@ -113,51 +159,52 @@ impl RenderTest for Metal {
                 frame,
                 options.as_ref(),
             )?;
-        }
 
-        cmd.commit();
-        unsafe {
-            cmd.waitUntilCompleted();
-        }
+            if !frames.contains(&frame) {
+                continue;
+            }
 
-        let region = MTLRegion {
-            origin: MTLOrigin { x: 0, y: 0, z: 0 },
-            size: MTLSize {
-                width: output_size.width as usize,
-                height: output_size.height as usize,
-                depth: 1,
-            },
-        };
+            cmd.commit();
+            unsafe {
+                cmd.waitUntilCompleted();
+            }
 
-        unsafe {
-            // should be the same size
-            let mut buffer =
-                vec![0u8; output_size.width as usize * output_size.height as usize * 4];
-            render_texture.getBytes_bytesPerRow_fromRegion_mipmapLevel(
-                NonNull::new(buffer.as_mut_ptr().cast()).unwrap(),
-                4 * output_size.width as usize,
-                region,
-                0,
-            );
+            let image = unsafe {
+                // should be the same size
+                let mut buffer =
+                    vec![0u8; output_size.width as usize * output_size.height as usize * 4];
+                render_texture.getBytes_bytesPerRow_fromRegion_mipmapLevel(
+                    NonNull::new(buffer.as_mut_ptr().cast()).unwrap(),
+                    4 * output_size.width as usize,
+                    region,
+                    0,
+                );
 
-            // swap the BGRA back to RGBA.
-            BGRA8::convert(&mut buffer);
+                // swap the BGRA back to RGBA.
+                BGRA8::convert(&mut buffer);
+
+                RgbaImage::from_raw(
+                    render_texture.width() as u32,
+                    render_texture.height() as u32,
+                    Vec::from(buffer),
+                )
+                .ok_or(anyhow!("Unable to create image from data"))?
+            };
 
-            let image = RgbaImage::from_raw(
-                render_texture.width() as u32,
-                render_texture.height() as u32,
-                Vec::from(buffer),
-            )
-            .ok_or(anyhow!("Unable to create image from data"))?;
+            frame_sink(frame, image)?;
 
-            Ok(image)
+            cmd = queue
+                .commandBuffer()
+                .ok_or_else(|| anyhow!("Unable to create command buffer"))?;
         }
+
+        Ok(())
     }
 }
 
 impl Metal {
-    pub fn new(image_path: impl AsRef<Path>) -> anyhow::Result<Self> {
-        let image: Image<BGRA8> = Image::load(image_path, UVDirection::TopLeft)?;
+    pub fn new_with_image(image: RgbaImage) -> anyhow::Result<Self> {
+        let image: Image<BGRA8> = Image::load_from_buffer(image.into(), UVDirection::TopLeft)?;
 
         unsafe {
             let device = Retained::from_raw(MTLCreateSystemDefaultDevice())
@@ -185,14 +232,34 @@ impl Metal {
                 .newTextureWithDescriptor(&texture_descriptor)
                 .ok_or_else(|| anyhow!("Failed to create texture"))?;
 
-            let region = MTLRegion {
-                origin: MTLOrigin { x: 0, y: 0, z: 0 },
-                size: MTLSize {
-                    width: image.size.width as usize,
-                    height: image.size.height as usize,
-                    depth: 1,
-                },
-            };
+            Self::write_image(&texture, &image);
+
+            Ok(Self {
+                device,
+                texture,
+                image_bytes: image,
+            })
+        }
+    }
+
+    fn update_image(&self, image: RgbaImage) -> anyhow::Result<()> {
+        check_input_size(&image, self.image_bytes.size)?;
+        let image: Image<BGRA8> = Image::load_from_buffer(image.into(), UVDirection::TopLeft)?;
+        Self::write_image(&self.texture, &image);
+        Ok(())
+    }
+
+    fn write_image(texture: &ProtocolObject<dyn MTLTexture>, image: &Image<BGRA8>) {
+        let region = MTLRegion {
+            origin: MTLOrigin { x: 0, y: 0, z: 0 },
+            size: MTLSize {
+                width: image.size.width as usize,
+                height: image.size.height as usize,
+                depth: 1,
+            },
+        };
+
+        unsafe {
             texture.replaceRegion_mipmapLevel_withBytes_bytesPerRow(
                 region,
                 0,
@@ -200,12 +267,6 @@ impl Metal {
                 NonNull::new_unchecked(image.bytes.as_slice().as_ptr() as *mut _),
                 4 * image.size.width as usize,
             );
-
-            Ok(Self {
-                device,
-                texture,
-                image_bytes: image,
-            })
         }
     }
 }
@@ -19,6 +19,8 @@ impl From<&VulkanBase> for VulkanObjects {
             device: Arc::clone(&value.device),
             alloc: Arc::clone(&value.allocator),
             queue: value.graphics_queue.clone(),
+            physical_device: None,
+            instance_version: None,
         }
     }
 }
@@ -37,10 +39,21 @@ impl VulkanBase {
             .application_version(0)
             .api_version(vk::make_api_version(0, 1, 3, 0));
 
+        // MoltenVK is only enumerated with the portability enumeration extension.
+        let (extensions, flags) = if cfg!(target_vendor = "apple") {
+            (
+                vec![ash::khr::portability_enumeration::NAME.as_ptr()],
+                vk::InstanceCreateFlags::ENUMERATE_PORTABILITY_KHR,
+            )
+        } else {
+            (vec![], vk::InstanceCreateFlags::empty())
+        };
+
         let create_info = vk::InstanceCreateInfo::default()
             .application_info(&app_info)
             .enabled_layer_names(&layers)
-            .enabled_extension_names(&[]);
+            .enabled_extension_names(&extensions)
+            .flags(flags);
 
         let instance = unsafe { entry.create_instance(&create_info, None) }?;
 
@@ -95,7 +108,17 @@ impl VulkanBase {
         let mut physical_device_features =
             vk::PhysicalDeviceVulkan13Features::default().dynamic_rendering(true);
 
-        let extensions = [ash::khr::dynamic_rendering::NAME.as_ptr()];
+        let mut extensions = vec![ash::khr::dynamic_rendering::NAME.as_ptr()];
+
+        // portability implementations require the portability subset to be enabled.
+        let supported_extensions =
+            unsafe { instance.enumerate_device_extension_properties(*physical_device)? };
+        if supported_extensions
+            .iter()
+            .any(|ext| ext.extension_name_as_c_str() == Ok(ash::khr::portability_subset::NAME))
+        {
+            extensions.push(ash::khr::portability_subset::NAME.as_ptr());
+        }
 
         let device_create_info = vk::DeviceCreateInfo::default()
             .queue_create_infos(&queue_info)
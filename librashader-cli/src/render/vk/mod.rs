@@ -1,6 +1,6 @@
 use crate::render::vk::base::VulkanBase;
 use crate::render::vk::memory::{VulkanBuffer, VulkanImageMemory};
-use crate::render::{CommonFrameOptions, RenderTest};
+use crate::render::{check_input_size, CommonFrameOptions, RenderTest};
 use anyhow::anyhow;
 use ash::vk;
 use gpu_allocator::MemoryLocation;
@@ -10,7 +10,7 @@ use librashader::runtime::vk::{FilterChain, FilterChainOptions, FrameOptions, Vu
 use librashader::runtime::{FilterChainParameters, RuntimeParameters};
 use librashader::runtime::{Size, Viewport};
 use librashader_runtime::image::{Image, UVDirection, BGRA8};
-use std::path::Path;
+use std::ops::RangeInclusive;
 
 mod base;
 mod memory;
@@ -25,25 +25,27 @@ pub struct Vulkan {
 }
 
 impl RenderTest for Vulkan {
-    fn new(path: &Path) -> anyhow::Result<Self>
+    fn new_with_image(image: RgbaImage) -> anyhow::Result<Self>
     where
         Self: Sized,
     {
-        Vulkan::new(path)
+        Vulkan::new_with_image(image)
     }
 
     fn image_size(&self) -> Size<u32> {
         self.image_bytes.size
     }
 
-    fn render_with_preset_and_params(
+    fn render_sequence_with_preset_and_params(
         &mut self,
         preset: ShaderPreset,
-        frame_count: usize,
+        frames: RangeInclusive<usize>,
         output_size: Option<Size<u32>>,
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
-    ) -> anyhow::Result<image::RgbaImage> {
+        mut frame_source: Option<&mut dyn FnMut(usize) -> anyhow::Result<Option<RgbaImage>>>,
+        frame_sink: &mut dyn FnMut(usize, RgbaImage) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
         unsafe {
             let mut filter_chain = FilterChain::load_from_preset(
                 preset,
@@ -53,6 +55,23 @@ impl RenderTest for Vulkan {
                     force_no_mipmaps: false,
                     use_dynamic_rendering: false,
                     disable_cache: false,
+                    max_history: 0,
+                    format_fallback: 0,
+                    ycbcr_conversion: vk::SamplerYcbcrConversion::null(),
+                    ycbcr_sampler: vk::Sampler::null(),
+                    disable_temporal: false,
+                    freeze_frame_count_on_rewind: false,
+                    async_pipeline_rebuild: false,
+                    collect_statistics: false,
+                    progress: None,
+                    cancel: None,
+                    portability: cfg!(target_vendor = "apple"),
+                    lut_bundle: None,
+                    device_context: None,
+                    color_assist: false,
+                    black_frame_insertion: false,
+                    latency_markers: None,
+                    instance: None,
                 }),
             )?;
 
@@ -147,41 +166,74 @@ impl RenderTest for Vulkan {
                     vk::QUEUE_FAMILY_IGNORED,
                 );
 
-                let options = frame_options.map(|options| FrameOptions {
-                    clear_history: options.clear_history,
-                    frame_direction: options.frame_direction,
-                    rotation: options.rotation,
-                    total_subframes: options.total_subframes,
-                    current_subframe: options.current_subframe,
-                    aspect_ratio: options.aspect_ratio,
-                    frametime_delta: options.frametime_delta,
-                    frames_per_second: options.frames_per_second,
-                });
-
-                let viewport = Viewport::new_render_target_sized_origin(
-                    VulkanImage {
-                        image: render_texture,
-                        size: self.image_bytes.size.into(),
-                        format: vk::Format::B8G8R8A8_UNORM,
-                    },
-                    None,
-                )?;
-
-                for frame in 0..=frame_count {
-                    filter_chain.frame(
-                        &VulkanImage {
-                            image: self.image,
-                            size: self.image_bytes.size,
-                            format: vk::Format::B8G8R8A8_UNORM,
-                        },
-                        &viewport,
-                        cmd,
-                        frame,
-                        options.as_ref(),
-                    )?;
+                Ok::<_, anyhow::Error>(())
+            })??;
+
+            let options = frame_options.map(|options| FrameOptions {
+                clear_history: options.clear_history,
+                frame_direction: options.frame_direction,
+                rotation: options.rotation,
+                total_subframes: options.total_subframes,
+                current_subframe: options.current_subframe,
+                aspect_ratio: options.aspect_ratio,
+                frametime_delta: options.frametime_delta,
+                frames_per_second: options.frames_per_second,
+                transpose_mvp: options.transpose_mvp,
+                flip_mvp_y: options.flip_mvp_y,
+                identity_scaling: options.identity_scaling,
+                scale_final_pass: options.scale_final_pass,
+                jitter_sequence: options.jitter_sequence,
+                jitter_offset: options.jitter_offset,
+                ..Default::default()
+            });
+
+            let viewport = Viewport::new_render_target_sized_origin(
+                VulkanImage {
+                    image: render_texture,
+                    size: self.image_bytes.size.into(),
+                    format: vk::Format::B8G8R8A8_UNORM,
+                },
+                None,
+            )?;
+
+            let last_frame = *frames.end();
+            let has_source = frame_source.is_some();
+            let mut frame = 0;
+            while frame <= last_frame {
+                if let Some(source) = frame_source.as_mut() {
+                    let Some(image) = source(frame)? else {
+                        break;
+                    };
+                    self.update_image(image)?;
                 }
 
-                {
+                // Frames that are not read back are recorded into the same submission
+                // as the next frame that is, unless the input image changes every frame.
+                let captured = self.vk.queue_work(|cmd| {
+                    loop {
+                        filter_chain.frame(
+                            &VulkanImage {
+                                image: self.image,
+                                size: self.image_bytes.size,
+                                format: vk::Format::B8G8R8A8_UNORM,
+                            },
+                            &viewport,
+                            cmd,
+                            frame,
+                            options.as_ref(),
+                        )?;
+
+                        if frames.contains(&frame) {
+                            break;
+                        }
+
+                        if frame == last_frame || has_source {
+                            return Ok::<_, anyhow::Error>(false);
+                        }
+
+                        frame += 1;
+                    }
+
                     util::vulkan_image_layout_transition_levels(
                         &self.vk.device(),
                         cmd,
@@ -195,78 +247,97 @@ impl RenderTest for Vulkan {
                         vk::PipelineStageFlags::TRANSFER,
                         vk::QUEUE_FAMILY_IGNORED,
                         vk::QUEUE_FAMILY_IGNORED,
-                    )
-                }
+                    );
+
+                    let offsets = [
+                        vk::Offset3D { x: 0, y: 0, z: 0 },
+                        vk::Offset3D {
+                            x: self.image_bytes.size.width as i32,
+                            y: self.image_bytes.size.height as i32,
+                            z: 1,
+                        },
+                    ];
 
-                let offsets = [
-                    vk::Offset3D { x: 0, y: 0, z: 0 },
-                    vk::Offset3D {
-                        x: self.image_bytes.size.width as i32,
-                        y: self.image_bytes.size.height as i32,
-                        z: 1,
-                    },
-                ];
-
-                let subresource = vk::ImageSubresourceLayers::default()
-                    .aspect_mask(vk::ImageAspectFlags::COLOR)
-                    .base_array_layer(0)
-                    .layer_count(1);
-
-                let image_blit = vk::ImageBlit::default()
-                    .src_subresource(subresource.clone())
-                    .src_offsets(offsets.clone())
-                    .dst_subresource(subresource)
-                    .dst_offsets(offsets);
-
-                self.vk.device().cmd_blit_image(
-                    cmd,
-                    render_texture,
-                    vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
-                    transfer_texture,
-                    vk::ImageLayout::GENERAL,
-                    &[image_blit],
-                    vk::Filter::NEAREST,
-                );
+                    let subresource = vk::ImageSubresourceLayers::default()
+                        .aspect_mask(vk::ImageAspectFlags::COLOR)
+                        .base_array_layer(0)
+                        .layer_count(1);
 
-                Ok::<_, anyhow::Error>(())
-            })??;
+                    let image_blit = vk::ImageBlit::default()
+                        .src_subresource(subresource.clone())
+                        .src_offsets(offsets.clone())
+                        .dst_subresource(subresource)
+                        .dst_offsets(offsets);
 
-            // should have read now.
-            let mut memory = transfer_memory
-                .allocation
-                .mapped_slice_mut()
-                .ok_or(anyhow!("readback buffer was not mapped"))?;
-
-            let layout = self.vk.device().get_image_subresource_layout(
-                transfer_texture,
-                vk::ImageSubresource::default()
-                    .aspect_mask(vk::ImageAspectFlags::COLOR)
-                    .array_layer(0),
-            );
-            memory = &mut memory[layout.offset as usize..];
+                    self.vk.device().cmd_blit_image(
+                        cmd,
+                        render_texture,
+                        vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                        transfer_texture,
+                        vk::ImageLayout::GENERAL,
+                        &[image_blit],
+                        vk::Filter::NEAREST,
+                    );
 
-            // let mut pixels = Vec::with_capacity(layout.size as usize);
+                    util::vulkan_image_layout_transition_levels(
+                        &self.vk.device(),
+                        cmd,
+                        render_texture,
+                        vk::REMAINING_MIP_LEVELS,
+                        vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                        vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+                        vk::AccessFlags::TRANSFER_READ,
+                        vk::AccessFlags::COLOR_ATTACHMENT_WRITE,
+                        vk::PipelineStageFlags::TRANSFER,
+                        vk::PipelineStageFlags::ALL_GRAPHICS,
+                        vk::QUEUE_FAMILY_IGNORED,
+                        vk::QUEUE_FAMILY_IGNORED,
+                    );
+
+                    Ok::<_, anyhow::Error>(true)
+                })??;
+
+                if captured {
+                    // should have read now.
+                    let mut memory = transfer_memory
+                        .allocation
+                        .mapped_slice_mut()
+                        .ok_or(anyhow!("readback buffer was not mapped"))?;
+
+                    let layout = self.vk.device().get_image_subresource_layout(
+                        transfer_texture,
+                        vk::ImageSubresource::default()
+                            .aspect_mask(vk::ImageAspectFlags::COLOR)
+                            .array_layer(0),
+                    );
+                    memory = &mut memory[layout.offset as usize..];
+
+                    let image = RgbaImage::from_raw(
+                        self.image_bytes.size.width,
+                        self.image_bytes.size.height,
+                        Vec::from(memory),
+                    )
+                    .ok_or(anyhow!("failed to create image from slice"))?;
 
-            let image = RgbaImage::from_raw(
-                self.image_bytes.size.width,
-                self.image_bytes.size.height,
-                Vec::from(memory),
-            )
-            .ok_or(anyhow!("failed to create image from slice"));
+                    frame_sink(frame, image)?;
+                }
+
+                frame += 1;
+            }
 
             self.vk.device().destroy_image(transfer_texture, None);
             self.vk.device().destroy_image(render_texture, None);
 
-            Ok(image?)
+            Ok(())
         }
     }
 }
 
 impl Vulkan {
-    pub fn new(image_path: &Path) -> anyhow::Result<Self> {
+    pub fn new_with_image(image: RgbaImage) -> anyhow::Result<Self> {
         let vk = VulkanBase::new()?;
 
-        let (image_bytes, image_alloc, image, _view) = Self::load_image(&vk, image_path)?;
+        let (image_bytes, image_alloc, image, _view) = Self::load_image(&vk, image)?;
 
         Ok(Self {
             vk,
@@ -278,9 +349,9 @@ impl Vulkan {
 
     pub fn load_image(
         vk: &VulkanBase,
-        image_path: &Path,
+        image: RgbaImage,
     ) -> anyhow::Result<(Image<BGRA8>, VulkanImageMemory, vk::Image, vk::ImageView)> {
-        let image: Image<BGRA8> = Image::load(image_path, UVDirection::TopLeft)?;
+        let image: Image<BGRA8> = Image::load_from_buffer(image.into(), UVDirection::TopLeft)?;
 
         let image_info = vk::ImageCreateInfo::default()
             .image_type(vk::ImageType::TYPE_2D)
@@ -330,6 +401,22 @@ impl Vulkan {
 
         let texture_view = unsafe { vk.device().create_image_view(&view_info, None)? };
 
+        Self::write_image(vk, texture, &image)?;
+
+        Ok((image, memory, texture, texture_view))
+    }
+
+    fn update_image(&self, image: RgbaImage) -> anyhow::Result<()> {
+        check_input_size(&image, self.image_bytes.size)?;
+        let image: Image<BGRA8> = Image::load_from_buffer(image.into(), UVDirection::TopLeft)?;
+        Self::write_image(&self.vk, self.image, &image)
+    }
+
+    fn write_image(
+        vk: &VulkanBase,
+        texture: vk::Image,
+        image: &Image<BGRA8>,
+    ) -> anyhow::Result<()> {
         let mut staging = VulkanBuffer::new(
             &vk.device(),
             &vk.allocator(),
@@ -389,6 +476,6 @@ impl Vulkan {
             );
         })?;
 
-        Ok((image, memory, texture, texture_view))
+        Ok(())
     }
 }
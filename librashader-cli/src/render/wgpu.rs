@@ -1,12 +1,11 @@
-use crate::render::{CommonFrameOptions, RenderTest};
+use crate::render::{check_input_size, CommonFrameOptions, RenderTest};
 use anyhow::anyhow;
 use image::RgbaImage;
 use librashader::runtime::wgpu::*;
 use librashader::runtime::{Size, Viewport};
 use librashader_runtime::image::{Image, UVDirection};
 use std::io::{Cursor, Write};
-use std::ops::DerefMut;
-use std::path::Path;
+use std::ops::{DerefMut, RangeInclusive};
 use std::sync::Arc;
 use wgpu::{Adapter, Device, Instance, Queue, Texture};
 use wgpu_types::{
@@ -27,6 +26,7 @@ pub struct Wgpu {
     texture: Arc<Texture>,
 }
 
+#[derive(Clone, Copy)]
 struct BufferDimensions {
     height: usize,
     unpadded_bytes_per_row: usize,
@@ -49,25 +49,27 @@ impl BufferDimensions {
 }
 
 impl RenderTest for Wgpu {
-    fn new(path: &Path) -> anyhow::Result<Self>
+    fn new_with_image(image: RgbaImage) -> anyhow::Result<Self>
     where
         Self: Sized,
     {
-        Wgpu::new(path)
+        Wgpu::new_with_image(image)
     }
 
     fn image_size(&self) -> Size<u32> {
         self.image.size
     }
 
-    fn render_with_preset_and_params(
+    fn render_sequence_with_preset_and_params(
         &mut self,
         preset: ShaderPreset,
-        frame_count: usize,
+        frames: RangeInclusive<usize>,
         output_size: Option<Size<u32>>,
         param_setter: Option<&dyn Fn(&RuntimeParameters)>,
         frame_options: Option<CommonFrameOptions>,
-    ) -> anyhow::Result<image::RgbaImage> {
+        mut frame_source: Option<&mut dyn FnMut(usize) -> anyhow::Result<Option<RgbaImage>>>,
+        frame_sink: &mut dyn FnMut(usize, RgbaImage) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
         let mut chain = FilterChain::load_from_preset(
             preset,
             Arc::clone(&self.device),
@@ -76,14 +78,23 @@ impl RenderTest for Wgpu {
                 force_no_mipmaps: false,
                 enable_cache: true,
                 adapter_info: None,
+                max_history: 0,
+                format_fallback: 0,
+                disable_temporal: false,
+                freeze_frame_count_on_rewind: false,
+                collect_statistics: false,
+                progress: None,
+                cancel: None,
+                lut_bundle: None,
+                device_context: None,
+                color_assist: false,
+                black_frame_insertion: false,
+                instance: None,
             }),
         )?;
         if let Some(setter) = param_setter {
             setter(&chain.parameters());
         }
-        let mut cmd = self
-            .device
-            .create_command_encoder(&CommandEncoderDescriptor { label: None });
 
         let output_tex = self.device.create_texture(&TextureDescriptor {
             label: None,
@@ -123,9 +134,35 @@ impl RenderTest for Wgpu {
             aspect_ratio: options.aspect_ratio,
             frametime_delta: options.frametime_delta,
             frames_per_second: options.frames_per_second,
+            transpose_mvp: options.transpose_mvp,
+            flip_mvp_y: options.flip_mvp_y,
+            identity_scaling: options.identity_scaling,
+            scale_final_pass: options.scale_final_pass,
+            jitter_sequence: options.jitter_sequence,
+            jitter_offset: options.jitter_offset,
         });
 
-        for frame in 0..=frame_count {
+        let mut cmd = self
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor { label: None });
+
+        for frame in 0..=*frames.end() {
+            if let Some(source) = frame_source.as_mut() {
+                let Some(image) = source(frame)? else {
+                    break;
+                };
+
+                // Frames that have already been recorded need to be submitted before
+                // the input texture is overwritten.
+                let recorded = std::mem::replace(
+                    &mut cmd,
+                    self.device
+                        .create_command_encoder(&CommandEncoderDescriptor { label: None }),
+                );
+                self.queue.submit([recorded.finish()]);
+                self.update_image(image)?;
+            }
+
             chain.frame(
                 Arc::clone(&self.texture),
                 &viewport,
@@ -133,68 +170,80 @@ impl RenderTest for Wgpu {
                 frame,
                 options.as_ref(),
             )?;
-        }
 
-        cmd.copy_texture_to_buffer(
-            output_tex.as_image_copy(),
-            ImageCopyBuffer {
-                buffer: &output_buf,
-                layout: ImageDataLayout {
-                    offset: 0,
-                    bytes_per_row: Some(buffer_dimensions.padded_bytes_per_row as u32),
-                    rows_per_image: None,
+            if !frames.contains(&frame) {
+                continue;
+            }
+
+            cmd.copy_texture_to_buffer(
+                output_tex.as_image_copy(),
+                ImageCopyBuffer {
+                    buffer: &output_buf,
+                    layout: ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: Some(buffer_dimensions.padded_bytes_per_row as u32),
+                        rows_per_image: None,
+                    },
                 },
-            },
-            output_tex.size(),
-        );
+                output_tex.size(),
+            );
 
-        let si = self.queue.submit([cmd.finish()]);
-        self.device.poll(Maintain::WaitForSubmissionIndex(si));
+            let recorded = std::mem::replace(
+                &mut cmd,
+                self.device
+                    .create_command_encoder(&CommandEncoderDescriptor { label: None }),
+            );
+
+            let si = self.queue.submit([recorded.finish()]);
+            self.device.poll(Maintain::WaitForSubmissionIndex(si));
 
-        let capturable = Arc::clone(&output_buf);
+            let capturable = Arc::clone(&output_buf);
 
-        let pixels = Arc::new(Mutex::new(Vec::new()));
+            let pixels = Arc::new(Mutex::new(Vec::new()));
 
-        let pixels_async = Arc::clone(&pixels);
-        output_buf
-            .slice(..)
-            .map_async(wgpu::MapMode::Read, move |r| {
-                if r.is_ok() {
-                    let buffer = capturable.slice(..).get_mapped_range();
-                    let mut pixels = pixels_async.lock();
-                    pixels.resize(buffer.len(), 0);
+            let pixels_async = Arc::clone(&pixels);
+            output_buf
+                .slice(..)
+                .map_async(wgpu::MapMode::Read, move |r| {
+                    if r.is_ok() {
+                        let buffer = capturable.slice(..).get_mapped_range();
+                        let mut pixels = pixels_async.lock();
+                        pixels.resize(buffer.len(), 0);
 
-                    let mut cursor = Cursor::new(pixels.deref_mut());
-                    for chunk in buffer.chunks(buffer_dimensions.padded_bytes_per_row) {
-                        cursor
-                            .write_all(&chunk[..buffer_dimensions.unpadded_bytes_per_row])
-                            .unwrap()
+                        let mut cursor = Cursor::new(pixels.deref_mut());
+                        for chunk in buffer.chunks(buffer_dimensions.padded_bytes_per_row) {
+                            cursor
+                                .write_all(&chunk[..buffer_dimensions.unpadded_bytes_per_row])
+                                .unwrap()
+                        }
+
+                        cursor.into_inner();
                     }
+                    capturable.unmap();
+                });
 
-                    cursor.into_inner();
-                }
-                capturable.unmap();
-            });
+            self.device.poll(Maintain::Wait);
 
-        self.device.poll(Maintain::Wait);
+            if pixels.lock().len() == 0 {
+                return Err(anyhow!("failed to copy pixels from buffer"));
+            }
 
-        if pixels.lock().len() == 0 {
-            return Err(anyhow!("failed to copy pixels from buffer"));
-        }
+            let image = RgbaImage::from_raw(
+                output_tex.width(),
+                output_tex.height(),
+                pixels.lock().to_vec(),
+            )
+            .ok_or(anyhow!("Unable to create image from data"))?;
 
-        let image = RgbaImage::from_raw(
-            output_tex.width(),
-            output_tex.height(),
-            pixels.lock().to_vec(),
-        )
-        .ok_or(anyhow!("Unable to create image from data"))?;
+            frame_sink(frame, image)?;
+        }
 
-        Ok(image)
+        Ok(())
     }
 }
 
 impl Wgpu {
-    pub fn new(image: &Path) -> anyhow::Result<Self> {
+    pub fn new_with_image(image: RgbaImage) -> anyhow::Result<Self> {
         pollster::block_on(async {
             let instance = wgpu::Instance::default();
             let adapter = instance
@@ -233,8 +282,12 @@ impl Wgpu {
         })
     }
 
-    fn load_image(device: &Device, queue: &Queue, path: &Path) -> anyhow::Result<(Image, Texture)> {
-        let image = Image::load(path, UVDirection::TopLeft)?;
+    fn load_image(
+        device: &Device,
+        queue: &Queue,
+        image: RgbaImage,
+    ) -> anyhow::Result<(Image, Texture)> {
+        let image = Image::load_from_buffer(image.into(), UVDirection::TopLeft)?;
         let texture = device.create_texture(&TextureDescriptor {
             size: image.size.into(),
             mip_level_count: 1,
@@ -246,9 +299,22 @@ impl Wgpu {
             label: None,
         });
 
+        Self::write_image(device, queue, &texture, &image);
+
+        Ok((image, texture))
+    }
+
+    fn update_image(&self, image: RgbaImage) -> anyhow::Result<()> {
+        check_input_size(&image, self.image.size)?;
+        let image = Image::load_from_buffer(image.into(), UVDirection::TopLeft)?;
+        Self::write_image(&self.device, &self.queue, &self.texture, &image);
+        Ok(())
+    }
+
+    fn write_image(device: &Device, queue: &Queue, texture: &Texture, image: &Image) {
         queue.write_texture(
             wgpu::ImageCopyTexture {
-                texture: &texture,
+                texture,
                 mip_level: 0,
                 origin: wgpu::Origin3d::ZERO,
                 aspect: wgpu::TextureAspect::All,
@@ -265,7 +331,5 @@ impl Wgpu {
         let si = queue.submit([]);
 
         device.poll(Maintain::WaitForSubmissionIndex(si));
-
-        Ok((image, texture))
     }
 }
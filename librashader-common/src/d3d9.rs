@@ -22,6 +22,8 @@ impl From<ImageFormat> for Direct3D9::D3DFORMAT {
             ImageFormat::R16G16B16A16Sint => Direct3D9::D3DFMT_A16B16G16R16,
             ImageFormat::R16G16B16A16Sfloat => Direct3D9::D3DFMT_A16B16G16R16F,
             ImageFormat::R32Sfloat => Direct3D9::D3DFMT_R32F,
+            ImageFormat::R32G32Sfloat => Direct3D9::D3DFMT_G32R32F,
+            ImageFormat::R32G32B32A32Sfloat => Direct3D9::D3DFMT_A32B32G32R32F,
             _ => Direct3D9::D3DFMT_UNKNOWN,
         }
     }
@@ -39,6 +41,8 @@ impl From<Direct3D9::D3DFORMAT> for ImageFormat {
             Direct3D9::D3DFMT_A16B16G16R16 => ImageFormat::R16G16B16A16Uint,
             Direct3D9::D3DFMT_A16B16G16R16F => ImageFormat::R16G16B16A16Sfloat,
             Direct3D9::D3DFMT_R32F => ImageFormat::R32Sfloat,
+            Direct3D9::D3DFMT_G32R32F => ImageFormat::R32G32Sfloat,
+            Direct3D9::D3DFMT_A32B32G32R32F => ImageFormat::R32G32B32A32Sfloat,
             _ => ImageFormat::Unknown,
         }
     }
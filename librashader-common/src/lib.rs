@@ -35,7 +35,9 @@ mod viewport;
 
 #[doc(hidden)]
 pub mod map;
+pub mod sandbox;
 pub mod shader_features;
+pub mod text;
 
 pub use viewport::Viewport;
 
@@ -146,6 +148,43 @@ pub enum WrapMode {
     MirroredRepeat,
 }
 
+#[repr(u32)]
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A built-in kernel to scale the input to the viewport with when there are no shader passes.
+pub enum ScalingKernel {
+    #[default]
+    /// Nearest-neighbour scaling.
+    Nearest = 0,
+    /// Bilinear scaling.
+    Bilinear,
+    /// Bicubic (Catmull-Rom) scaling.
+    Bicubic,
+    /// Lanczos-3 scaling.
+    Lanczos,
+}
+
+impl ScalingKernel {
+    /// Every built-in scaling kernel, in order of their discriminants.
+    pub const ALL: [ScalingKernel; 4] = [
+        ScalingKernel::Nearest,
+        ScalingKernel::Bilinear,
+        ScalingKernel::Bicubic,
+        ScalingKernel::Lanczos,
+    ];
+}
+
+impl From<u32> for ScalingKernel {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => ScalingKernel::Bilinear,
+            2 => ScalingKernel::Bicubic,
+            3 => ScalingKernel::Lanczos,
+            _ => ScalingKernel::Nearest,
+        }
+    }
+}
+
 impl FromStr for ImageFormat {
     type Err = Infallible;
 
@@ -157,7 +196,7 @@ impl FromStr for ImageFormat {
             "R8_UINT" => Self::R8Uint,
             "R8_SINT" => Self::R8Sint,
             "R8G8_UNORM" => Self::R8G8Unorm,
-            "R8G8_UINT" => Self::R8Uint,
+            "R8G8_UINT" => Self::R8G8Uint,
             "R8G8_SINT" => Self::R8G8Sint,
             "R8G8B8A8_UNORM" => Self::R8G8B8A8Unorm,
             "R8G8B8A8_UINT" => Self::R8G8B8A8Uint,
@@ -191,6 +230,151 @@ impl FromStr for ImageFormat {
     }
 }
 
+#[repr(u32)]
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The policy to follow when the framebuffer format of a shader pass is not supported.
+///
+/// Fallback formats always keep the component type and the channels of the requested format.
+pub enum FormatFallback {
+    #[default]
+    /// Fall back to a wider format, never losing precision when a wider format is supported.
+    PreferPrecision = 0,
+    /// Fall back to the smallest supported format. Floating point formats may fall back
+    /// to a floating point format of lower precision.
+    PreferPerformance,
+    /// Fail filter chain creation if the requested format is not supported.
+    Error,
+}
+
+impl From<u32> for FormatFallback {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => FormatFallback::PreferPerformance,
+            2 => FormatFallback::Error,
+            _ => FormatFallback::PreferPrecision,
+        }
+    }
+}
+
+impl ImageFormat {
+    /// Get the formats to try in order of preference when creating a framebuffer of this format
+    /// under the given fallback policy, starting with the format itself.
+    pub fn fallback_formats(self, policy: FormatFallback) -> &'static [ImageFormat] {
+        use ImageFormat::*;
+        match policy {
+            FormatFallback::PreferPrecision => self.precision_fallback_formats(),
+            // every list of fallbacks starts with the format itself.
+            FormatFallback::Error => &self.precision_fallback_formats()[..1],
+            FormatFallback::PreferPerformance => match self {
+                R8G8B8A8Srgb => &[R8G8B8A8Srgb, R8G8B8A8Unorm, R16G16B16A16Sfloat],
+                A2B10G10R10UnormPack32 => {
+                    &[A2B10G10R10UnormPack32, R8G8B8A8Unorm, R16G16B16A16Sfloat]
+                }
+                R16Sfloat => &[
+                    R16Sfloat,
+                    R16G16Sfloat,
+                    R32Sfloat,
+                    R16G16B16A16Sfloat,
+                    R32G32B32A32Sfloat,
+                ],
+                R32Sfloat => &[
+                    R32Sfloat,
+                    R16Sfloat,
+                    R16G16Sfloat,
+                    R32G32Sfloat,
+                    R16G16B16A16Sfloat,
+                    R32G32B32A32Sfloat,
+                ],
+                R32G32Sfloat => &[
+                    R32G32Sfloat,
+                    R16G16Sfloat,
+                    R16G16B16A16Sfloat,
+                    R32G32B32A32Sfloat,
+                ],
+                // integer formats can not lose precision without changing the result,
+                // and the remaining fallbacks are already the smallest wider formats.
+                _ => self.precision_fallback_formats(),
+            },
+        }
+    }
+
+    /// Fallbacks that never drop channels or precision when a wider format is available.
+    fn precision_fallback_formats(self) -> &'static [ImageFormat] {
+        use ImageFormat::*;
+        match self {
+            Unknown => &[Unknown],
+
+            R8Unorm => &[R8Unorm, R8G8Unorm, R8G8B8A8Unorm],
+            R8Uint => &[R8Uint, R8G8Uint, R8G8B8A8Uint],
+            R8Sint => &[R8Sint, R8G8Sint, R8G8B8A8Sint],
+            R8G8Unorm => &[R8G8Unorm, R8G8B8A8Unorm],
+            R8G8Uint => &[R8G8Uint, R8G8B8A8Uint],
+            R8G8Sint => &[R8G8Sint, R8G8B8A8Sint],
+            R8G8B8A8Unorm => &[R8G8B8A8Unorm],
+            R8G8B8A8Uint => &[R8G8B8A8Uint],
+            R8G8B8A8Sint => &[R8G8B8A8Sint],
+            R8G8B8A8Srgb => &[R8G8B8A8Srgb, R16G16B16A16Sfloat, R8G8B8A8Unorm],
+
+            A2B10G10R10UnormPack32 => &[A2B10G10R10UnormPack32, R16G16B16A16Sfloat, R8G8B8A8Unorm],
+            A2B10G10R10UintPack32 => &[A2B10G10R10UintPack32, R16G16B16A16Uint],
+
+            R16Uint => &[R16Uint, R16G16Uint, R16G16B16A16Uint],
+            R16Sint => &[R16Sint, R16G16Sint, R16G16B16A16Sint],
+            R16Sfloat => &[
+                R16Sfloat,
+                R16G16Sfloat,
+                R16G16B16A16Sfloat,
+                R32Sfloat,
+                R32G32B32A32Sfloat,
+            ],
+            R16G16Uint => &[R16G16Uint, R16G16B16A16Uint],
+            R16G16Sint => &[R16G16Sint, R16G16B16A16Sint],
+            R16G16Sfloat => &[
+                R16G16Sfloat,
+                R16G16B16A16Sfloat,
+                R32G32Sfloat,
+                R32G32B32A32Sfloat,
+            ],
+            R16G16B16A16Uint => &[R16G16B16A16Uint, R32G32B32A32Uint],
+            R16G16B16A16Sint => &[R16G16B16A16Sint, R32G32B32A32Sint],
+            R16G16B16A16Sfloat => &[R16G16B16A16Sfloat, R32G32B32A32Sfloat],
+
+            R32Uint => &[R32Uint, R32G32Uint, R32G32B32A32Uint],
+            R32Sint => &[R32Sint, R32G32Sint, R32G32B32A32Sint],
+            R32Sfloat => &[R32Sfloat, R32G32Sfloat, R32G32B32A32Sfloat],
+            R32G32Uint => &[R32G32Uint, R32G32B32A32Uint],
+            R32G32Sint => &[R32G32Sint, R32G32B32A32Sint],
+            R32G32Sfloat => &[R32G32Sfloat, R32G32B32A32Sfloat],
+            R32G32B32A32Uint => &[R32G32B32A32Uint],
+            R32G32B32A32Sint => &[R32G32B32A32Sint],
+            R32G32B32A32Sfloat => &[R32G32B32A32Sfloat, R16G16B16A16Sfloat],
+        }
+    }
+
+    /// Select the first of the [fallback formats](ImageFormat::fallback_formats) of this format
+    /// that is supported under the given policy.
+    ///
+    /// If none of the fallback formats are supported, the format itself is returned, unless the
+    /// policy is [`FormatFallback::Error`], in which case `None` is returned.
+    pub fn select_supported(
+        self,
+        policy: FormatFallback,
+        is_supported: impl Fn(ImageFormat) -> bool,
+    ) -> Option<ImageFormat> {
+        let selected = self
+            .fallback_formats(policy)
+            .iter()
+            .copied()
+            .find(|&format| is_supported(format));
+
+        match policy {
+            FormatFallback::Error => selected,
+            _ => Some(selected.unwrap_or(self)),
+        }
+    }
+}
+
 /// A size with a width and height.
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -291,3 +475,36 @@ impl<T: GetSize<u32>> GetSize<f32> for T {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{FormatFallback, ImageFormat};
+
+    #[test]
+    fn selects_fallback_by_policy() {
+        let unsupported = |format| format != ImageFormat::R32Sfloat;
+
+        assert_eq!(
+            ImageFormat::R32Sfloat.select_supported(FormatFallback::PreferPrecision, unsupported),
+            Some(ImageFormat::R32G32Sfloat)
+        );
+        assert_eq!(
+            ImageFormat::R32Sfloat.select_supported(FormatFallback::PreferPerformance, unsupported),
+            Some(ImageFormat::R16Sfloat)
+        );
+        assert_eq!(
+            ImageFormat::R32Sfloat.select_supported(FormatFallback::Error, unsupported),
+            None
+        );
+        assert_eq!(
+            ImageFormat::R32Sfloat.select_supported(FormatFallback::Error, |_| true),
+            Some(ImageFormat::R32Sfloat)
+        );
+
+        // without any supported fallback, the format itself is used.
+        assert_eq!(
+            ImageFormat::R8Unorm.select_supported(FormatFallback::PreferPrecision, |_| false),
+            Some(ImageFormat::R8Unorm)
+        );
+    }
+}
@@ -4,5 +4,11 @@
 pub type FastHashMap<K, V> =
     halfbrown::SizedHashMap<K, V, core::hash::BuildHasherDefault<rustc_hash::FxHasher>, 32>;
 
+/// A hashmap with a fast hash implementation that iterates in insertion order.
+///
+/// Used where the iteration order is observable, such as the parameters declared by a shader.
+pub type OrderedHashMap<K, V> =
+    indexmap::IndexMap<K, V, core::hash::BuildHasherDefault<rustc_hash::FxHasher>>;
+
 /// A string with small string optimizations up to 23 bytes.
 pub type ShortString = smartstring::SmartString<smartstring::LazyCompact>;
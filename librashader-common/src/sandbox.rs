@@ -0,0 +1,61 @@
+//! Path restrictions for untrusted shader presets.
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+/// A set of root directories that every file referenced by a shader preset must reside in.
+///
+/// Paths are fully resolved before they are checked, so neither `..` components nor
+/// symbolic links can be used to escape the roots of the sandbox.
+#[derive(Debug, Clone, Default)]
+pub struct PathSandbox {
+    roots: Arc<[PathBuf]>,
+}
+
+impl PathSandbox {
+    /// Create a sandbox that allows files within the given root directories.
+    ///
+    /// Returns an error if any of the root directories could not be resolved.
+    pub fn new<P: AsRef<Path>>(roots: impl IntoIterator<Item = P>) -> std::io::Result<Self> {
+        let roots = roots
+            .into_iter()
+            .map(|root| root.as_ref().canonicalize())
+            .collect::<std::io::Result<Vec<_>>>()?;
+        Ok(Self {
+            roots: Arc::from(roots),
+        })
+    }
+
+    /// The resolved root directories of the sandbox.
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Returns whether the path resides within any of the root directories of the sandbox.
+    ///
+    /// The path does not need to exist, but must be absolute.
+    pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+        let Some(path) = resolve(path.as_ref()) else {
+            return false;
+        };
+        self.roots.iter().any(|root| path.starts_with(root))
+    }
+}
+
+// Canonicalize the closest ancestor of the path that exists, then lexically
+// resolve the rest of the components.
+fn resolve(path: &Path) -> Option<PathBuf> {
+    if let Ok(path) = path.canonicalize() {
+        return Some(path);
+    }
+
+    let mut resolved = resolve(path.parent()?)?;
+    match path.components().next_back()? {
+        Component::ParentDir => {
+            resolved.pop();
+        }
+        Component::Normal(name) => resolved.push(name),
+        _ => {}
+    }
+    Some(resolved)
+}
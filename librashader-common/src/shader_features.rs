@@ -1,3 +1,4 @@
+use crate::map::ShortString;
 use bitflags::bitflags;
 
 bitflags! {
@@ -19,5 +20,50 @@ bitflags! {
         /// Note that this flag only enables the `_HAS_FRAMETIME_UNIFORMS` define.
         /// The uniforms will be bound unconditionally if found in reflection.
         const FRAMETIME_UNIFORMS = 0b00000010;
+        /// Substitute a placeholder texture for lookup textures that are missing or fail to load.
+        ///
+        /// Note that this flag does not enable any defines. A warning is logged for every
+        /// substituted texture instead of failing to load the preset.
+        const FALLBACK_TEXTURES = 0b00000100;
+        /// Fail to parse presets that contain unknown keys.
+        ///
+        /// Note that this flag does not enable any defines. Without it, unknown keys are
+        /// ignored and collected as warnings on the parsed preset.
+        const STRICT_PRESET = 0b00001000;
+    }
+}
+
+/// A preprocessor define injected into the source of a shader pass.
+///
+/// Defines are inserted after the `#version` header, so they can toggle features of
+/// shaders that are compile-time switches. Passes with different defines are distinct
+/// shaders, and are compiled and cached separately.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShaderDefine {
+    /// The name of the macro.
+    pub name: ShortString,
+    /// The replacement text of the macro, which may be empty.
+    pub value: String,
+}
+
+impl ShaderDefine {
+    /// Create a define with the given name and replacement text.
+    pub fn new(name: impl Into<ShortString>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Whether the name of the define is a valid identifier and the replacement text
+    /// fits on a single line.
+    pub fn is_valid(&self) -> bool {
+        let mut chars = self.name.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !self.value.contains(['\n', '\r'])
     }
 }
@@ -0,0 +1,86 @@
+//! Decoding of shader and preset files saved by different editors.
+
+use encoding_rs::{Encoding, WINDOWS_1252};
+use std::borrow::Cow;
+
+/// Decode the contents of a shader or preset file to a string with `\n` line endings.
+///
+/// Files that start with a UTF-8, UTF-16LE or UTF-16BE byte order mark are decoded with
+/// that encoding, and the byte order mark is removed. Other files are decoded as UTF-8,
+/// falling back to Windows-1252 if they are not valid UTF-8. `\r\n` and lone `\r` line
+/// endings are converted to `\n`.
+///
+/// If the contents can not be decoded, the bytes are returned as the error.
+pub fn decode_text(bytes: Vec<u8>) -> Result<String, Vec<u8>> {
+    match decode_text_borrowed(&bytes) {
+        // Reuse the allocation if the whole file is already valid text.
+        Some(Cow::Borrowed(text)) if text.len() == bytes.len() => {
+            String::from_utf8(bytes).map_err(|e| e.into_bytes())
+        }
+        Some(text) => Ok(text.into_owned()),
+        None => Err(bytes),
+    }
+}
+
+/// Decode the contents of a shader or preset file like [`decode_text`], without copying
+/// the contents if they are already UTF-8 text with `\n` line endings.
+///
+/// Returns `None` if the contents can not be decoded.
+pub fn decode_text_borrowed(bytes: &[u8]) -> Option<Cow<'_, str>> {
+    let text = if let Some((encoding, bom_length)) = Encoding::for_bom(bytes) {
+        let (text, malformed) = encoding.decode_without_bom_handling(&bytes[bom_length..]);
+        if malformed {
+            return None;
+        }
+        text
+    } else {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => Cow::Borrowed(text),
+            Err(_) => WINDOWS_1252.decode_without_bom_handling_and_without_replacement(bytes)?,
+        }
+    };
+
+    if !text.contains('\r') {
+        return Some(text);
+    }
+
+    Some(Cow::Owned(text.replace("\r\n", "\n").replace('\r', "\n")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode_text, decode_text_borrowed};
+    use std::borrow::Cow;
+
+    #[test]
+    fn decodes_editor_encodings() {
+        let expected = "#version 450\n#pragma stage vertex\n";
+
+        let utf8_bom = b"\xEF\xBB\xBF#version 450\r\n#pragma stage vertex\r\n".to_vec();
+        assert_eq!(decode_text(utf8_bom).as_deref(), Ok(expected));
+
+        let mut utf16le = vec![0xFF, 0xFE];
+        utf16le.extend(
+            "#version 450\r\n#pragma stage vertex\n"
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes),
+        );
+        assert_eq!(decode_text(utf16le).as_deref(), Ok(expected));
+
+        let mut utf16be = vec![0xFE, 0xFF];
+        utf16be.extend(
+            "#version 450\r#pragma stage vertex\r\n"
+                .encode_utf16()
+                .flat_map(u16::to_be_bytes),
+        );
+        assert_eq!(decode_text(utf16be).as_deref(), Ok(expected));
+
+        let latin1 = b"// \xE9\n".to_vec();
+        assert_eq!(decode_text(latin1).as_deref(), Ok("// é\n"));
+
+        assert!(matches!(
+            decode_text_borrowed(expected.as_bytes()),
+            Some(Cow::Borrowed(text)) if text == expected
+        ));
+    }
+}
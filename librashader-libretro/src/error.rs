@@ -0,0 +1,23 @@
+use librashader::runtime::gl::error::FilterChainError;
+use thiserror::Error;
+
+/// Error type for libretro filter chains.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum LibretroError {
+    /// An error occurred when creating the filter chain or processing a frame.
+    #[error("filter chain error")]
+    FilterChainError(#[from] FilterChainError),
+    /// A frame was processed before `context_reset` was called, or after `context_destroy`.
+    #[error("the hardware context has not been reset")]
+    ContextNotReady,
+    /// The output framebuffer could not be created.
+    #[error("fbo initialization error {0:x}")]
+    FramebufferInit(u32),
+    /// An OpenGL object could not be created.
+    #[error("opengl error: {0}")]
+    GlError(String),
+}
+
+/// Result type for libretro filter chains.
+pub type Result<T> = std::result::Result<T, LibretroError>;
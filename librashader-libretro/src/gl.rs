@@ -0,0 +1,244 @@
+use crate::error::{LibretroError, Result};
+use crate::{retro_hw_get_current_framebuffer_t, retro_hw_get_proc_address_t};
+use glow::HasContext;
+use librashader::presets::ShaderPreset;
+use librashader::presets::ShaderPresetPack;
+use librashader::runtime::gl::error::FilterChainError;
+use librashader::runtime::gl::{FilterChain, FilterChainOptions, FrameOptions, GLImage};
+use librashader::runtime::{Size, Viewport};
+use std::ffi::c_void;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+/// An OpenGL filter chain that follows the lifecycle of a libretro hardware context.
+///
+/// The filter chain is created when the context is reset, and released when the context is
+/// destroyed. Frames are drawn to the framebuffer of the frontend.
+pub struct LibretroFilterChain {
+    preset: ShaderPresetPack,
+    options: FilterChainOptions,
+    state: Option<ContextState>,
+}
+
+struct ContextState {
+    context: Arc<glow::Context>,
+    get_current_framebuffer: retro_hw_get_current_framebuffer_t,
+    chain: FilterChain,
+    output: Option<OutputTexture>,
+}
+
+struct OutputTexture {
+    texture: glow::Texture,
+    framebuffer: glow::Framebuffer,
+    size: Size<u32>,
+}
+
+impl LibretroFilterChain {
+    /// Load the shaders and textures of a preset, to create filter chains with when the
+    /// hardware context is reset.
+    pub fn new(preset: ShaderPreset, options: Option<&FilterChainOptions>) -> Result<Self> {
+        let preset = ShaderPresetPack::load_from_preset::<FilterChainError>(preset)?;
+        Ok(Self {
+            preset,
+            options: options.cloned().unwrap_or_default(),
+            state: None,
+        })
+    }
+
+    /// Create the filter chain for a new hardware context.
+    ///
+    /// This should be called from the `context_reset` callback. If the filter chain of a previous
+    /// context was not released with [`context_destroy`](Self::context_destroy), it is released
+    /// first.
+    ///
+    /// ## Safety
+    /// The hardware context must be current on the calling thread, and `get_proc_address` and
+    /// `get_current_framebuffer` must be the callbacks of that context.
+    pub unsafe fn context_reset(
+        &mut self,
+        get_proc_address: retro_hw_get_proc_address_t,
+        get_current_framebuffer: retro_hw_get_current_framebuffer_t,
+    ) -> Result<()> {
+        self.context_destroy();
+
+        let context = unsafe {
+            glow::Context::from_loader_function_cstr(|name| {
+                get_proc_address(name.as_ptr()).map_or(std::ptr::null(), |f| f as *const c_void)
+            })
+        };
+        let context = Arc::new(context);
+
+        let chain = unsafe {
+            FilterChain::load_from_pack(
+                self.preset.clone(),
+                Arc::clone(&context),
+                Some(&self.options),
+            )?
+        };
+
+        self.state = Some(ContextState {
+            context,
+            get_current_framebuffer,
+            chain,
+            output: None,
+        });
+        Ok(())
+    }
+
+    /// Release the filter chain of the hardware context.
+    ///
+    /// This should be called from the `context_destroy` callback, while the context is still
+    /// current. Does nothing if there is no filter chain.
+    pub fn context_destroy(&mut self) {
+        let Some(state) = self.state.take() else {
+            return;
+        };
+
+        if let Some(output) = state.output {
+            output.delete(&state.context);
+        }
+    }
+
+    /// Whether a hardware context has been reset, and frames can be processed.
+    pub fn is_ready(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Get the filter chain of the current hardware context, to set runtime parameters with.
+    pub fn filter_chain(&mut self) -> Option<&mut FilterChain> {
+        self.state.as_mut().map(|state| &mut state.chain)
+    }
+
+    /// Process a frame with the input image, and draw it to the framebuffer of the frontend
+    /// with the given size, starting from the origin.
+    ///
+    /// When this function returns, `GL_FRAMEBUFFER` is bound to the framebuffer of the frontend.
+    ///
+    /// ## Safety
+    /// The hardware context must be current on the calling thread.
+    pub unsafe fn frame(
+        &mut self,
+        input: &GLImage,
+        size: Size<u32>,
+        frame_count: usize,
+        options: Option<&FrameOptions>,
+    ) -> Result<()> {
+        let Some(state) = self.state.as_mut() else {
+            return Err(LibretroError::ContextNotReady);
+        };
+
+        if state
+            .output
+            .as_ref()
+            .is_some_and(|output| output.size != size)
+        {
+            if let Some(output) = state.output.take() {
+                output.delete(&state.context);
+            }
+        }
+
+        let output = match &state.output {
+            Some(output) => output,
+            None => state
+                .output
+                .insert(unsafe { OutputTexture::new(&state.context, size)? }),
+        };
+
+        let image = GLImage {
+            handle: Some(output.texture),
+            format: glow::RGBA8,
+            size,
+        };
+        let viewport = Viewport {
+            x: 0.0,
+            y: 0.0,
+            mvp: None,
+            output: &image,
+            size,
+        };
+
+        unsafe {
+            state.chain.frame(input, &viewport, frame_count, options)?;
+        }
+
+        let target = unsafe { (state.get_current_framebuffer)() };
+        let target = NonZeroU32::new(target as u32).map(glow::NativeFramebuffer);
+
+        let gl = &state.context;
+        unsafe {
+            gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(output.framebuffer));
+            gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, target);
+            gl.blit_framebuffer(
+                0,
+                0,
+                size.width as i32,
+                size.height as i32,
+                0,
+                0,
+                size.width as i32,
+                size.height as i32,
+                glow::COLOR_BUFFER_BIT,
+                glow::NEAREST,
+            );
+            gl.bind_framebuffer(glow::FRAMEBUFFER, target);
+        }
+
+        Ok(())
+    }
+}
+
+impl OutputTexture {
+    unsafe fn new(context: &glow::Context, size: Size<u32>) -> Result<Self> {
+        unsafe {
+            let texture = context.create_texture().map_err(LibretroError::GlError)?;
+            context.bind_texture(glow::TEXTURE_2D, Some(texture));
+            context.tex_storage_2d(
+                glow::TEXTURE_2D,
+                1,
+                glow::RGBA8,
+                size.width as i32,
+                size.height as i32,
+            );
+            context.bind_texture(glow::TEXTURE_2D, None);
+
+            let framebuffer = match context.create_framebuffer() {
+                Ok(framebuffer) => framebuffer,
+                Err(error) => {
+                    context.delete_texture(texture);
+                    return Err(LibretroError::GlError(error));
+                }
+            };
+
+            let output = Self {
+                texture,
+                framebuffer,
+                size,
+            };
+
+            context.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(framebuffer));
+            context.framebuffer_texture_2d(
+                glow::READ_FRAMEBUFFER,
+                glow::COLOR_ATTACHMENT0,
+                glow::TEXTURE_2D,
+                Some(texture),
+                0,
+            );
+            let status = context.check_framebuffer_status(glow::READ_FRAMEBUFFER);
+            context.bind_framebuffer(glow::READ_FRAMEBUFFER, None);
+
+            if status != glow::FRAMEBUFFER_COMPLETE {
+                output.delete(context);
+                return Err(LibretroError::FramebufferInit(status));
+            }
+
+            Ok(output)
+        }
+    }
+
+    fn delete(self, context: &glow::Context) {
+        unsafe {
+            context.delete_framebuffer(self.framebuffer);
+            context.delete_texture(self.texture);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Glue between librashader and the libretro hardware rendering callbacks.
+//!
+//! libretro cores and thin frontends that render with `RETRO_HW_CONTEXT_OPENGL_CORE` do not own
+//! their OpenGL context. Function pointers have to be loaded with the `get_proc_address`
+//! callback, the context can be lost and recreated at any time, and every frame has to be drawn
+//! to the framebuffer returned by the `get_current_framebuffer` callback.
+//!
+//! [`LibretroFilterChain`] follows this lifecycle. The preset is loaded from disk once, and the
+//! filter chain is created in `context_reset` and released in `context_destroy`.
+//!
+//! ```ignore
+//! extern "C" fn context_reset() {
+//!     let hw = HW_RENDER.get();
+//!     unsafe { CHAIN.context_reset(hw.get_proc_address, hw.get_current_framebuffer) }
+//!         .expect("failed to create filter chain");
+//! }
+//!
+//! extern "C" fn context_destroy() {
+//!     CHAIN.context_destroy();
+//! }
+//!
+//! fn retro_run() {
+//!     // draw the frame of the core into `frame`, then
+//!     unsafe { CHAIN.frame(&frame, viewport_size, frame_count, None) }.unwrap();
+//!     video_refresh(RETRO_HW_FRAME_BUFFER_VALID, viewport_size.width, viewport_size.height, 0);
+//! }
+//! ```
+//!
+//! Only OpenGL hardware contexts are supported.
+#![forbid(missing_docs)]
+#![allow(non_camel_case_types)]
+#![deny(unsafe_op_in_unsafe_fn)]
+
+/// libretro error types.
+pub mod error;
+
+mod gl;
+
+pub use gl::LibretroFilterChain;
+
+use std::ffi::c_char;
+
+/// A function pointer returned by the `get_proc_address` callback of the frontend.
+pub type retro_proc_address_t = Option<unsafe extern "C" fn()>;
+
+/// The `get_proc_address` callback of `retro_hw_render_callback`.
+pub type retro_hw_get_proc_address_t =
+    unsafe extern "C" fn(sym: *const c_char) -> retro_proc_address_t;
+
+/// The `get_current_framebuffer` callback of `retro_hw_render_callback`.
+pub type retro_hw_get_current_framebuffer_t = unsafe extern "C" fn() -> usize;
@@ -5,9 +5,13 @@
 //! Also defines abstractly the `.slangpack` shader format implemented via serde derives on [`ShaderPresetPack`].
 //!
 use image::{ImageError, RgbaImage};
-use librashader_preprocess::{PreprocessError, ShaderSource};
-use librashader_presets::{ParameterMeta, PassMeta, ShaderFeatures, ShaderPreset, TextureMeta};
-use std::path::Path;
+use librashader_common::{FilterMode, ScalingKernel, WrapMode};
+use librashader_preprocess::{ParameterWidget, PreprocessError, ShaderSource};
+use librashader_presets::{
+    ParameterMeta, PassMeta, Scale2D, ScaleFactor, ScaleType, Scaling, ShaderFeatures,
+    ShaderPreset, TextureMeta,
+};
+use std::path::{Path, PathBuf};
 
 #[cfg(not(target_arch = "wasm32"))]
 use rayon::prelude::*;
@@ -22,6 +26,28 @@ pub struct TextureBuffer {
     height: u32,
 }
 
+impl TextureBuffer {
+    /// The width and height of the placeholder texture.
+    const PLACEHOLDER_SIZE: u32 = 64;
+    /// The width and height of a single cell of the placeholder checkerboard.
+    const PLACEHOLDER_CELL: u32 = 8;
+
+    /// Create a magenta and black checkerboard to substitute for a texture that failed to load.
+    pub fn placeholder() -> TextureBuffer {
+        TextureBuffer::from(RgbaImage::from_fn(
+            Self::PLACEHOLDER_SIZE,
+            Self::PLACEHOLDER_SIZE,
+            |x, y| {
+                if (x / Self::PLACEHOLDER_CELL + y / Self::PLACEHOLDER_CELL) % 2 == 0 {
+                    image::Rgba([255, 0, 255, 255])
+                } else {
+                    image::Rgba([0, 0, 0, 255])
+                }
+            },
+        ))
+    }
+}
+
 impl From<TextureBuffer> for Option<RgbaImage> {
     fn from(value: TextureBuffer) -> Self {
         RgbaImage::from_raw(value.width, value.height, value.image)
@@ -54,6 +80,11 @@ pub struct LoadedResource<M: LoadableResource> {
     pub data: M::ResourceType,
     /// Meta information about the texture.
     pub meta: M,
+    /// The path the resource was loaded from, if it was loaded from a file.
+    ///
+    /// This is not kept when the resource is serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub path: Option<PathBuf>,
 }
 
 /// Trait for a resource that is loadable from disk.
@@ -91,6 +122,75 @@ impl LoadableResource for TextureMeta {
 /// The loaded resource information for the source code of a shader pass.
 pub type PassResource = LoadedResource<PassMeta>;
 
+/// Scaling to the size of the viewport.
+fn viewport_scaling() -> Scale2D {
+    let scaling = Scaling {
+        scale_type: ScaleType::Viewport,
+        factor: ScaleFactor::Float(1.0),
+    };
+
+    Scale2D {
+        valid: true,
+        x: scaling.clone(),
+        y: scaling,
+    }
+}
+
+impl PassResource {
+    /// A built-in pass that draws the source at the size of the viewport.
+    fn viewport_pass(id: i32, data: ShaderSource, filter: FilterMode) -> PassResource {
+        PassResource {
+            data,
+            meta: PassMeta {
+                id,
+                alias: None,
+                filter,
+                wrap_mode: WrapMode::ClampToEdge,
+                frame_count_mod: 0,
+                srgb_framebuffer: false,
+                float_framebuffer: false,
+                mipmap_input: false,
+                defines: Vec::new(),
+                scaling: viewport_scaling(),
+            },
+            path: None,
+        }
+    }
+
+    /// A pass that scales the source image to the viewport with the given kernel
+    /// without any other processing.
+    ///
+    /// See [`ShaderSource::identity`].
+    pub fn identity(kernel: ScalingKernel) -> PassResource {
+        let filter = if kernel == ScalingKernel::Bilinear {
+            FilterMode::Linear
+        } else {
+            FilterMode::Nearest
+        };
+
+        Self::viewport_pass(0, ShaderSource::identity(kernel), filter)
+    }
+
+    /// A pass that simulates or corrects for colour vision deficiencies, drawn after
+    /// the passes of a preset.
+    ///
+    /// See [`ShaderSource::color_assist`].
+    pub fn color_assist(id: i32) -> PassResource {
+        Self::viewport_pass(id, ShaderSource::color_assist(), FilterMode::Nearest)
+    }
+
+    /// A pass that inserts black frames between subframes, drawn after the passes of a preset.
+    ///
+    /// See [`ShaderSource::black_frame_insertion`].
+    pub fn black_frame_insertion(id: i32) -> PassResource {
+        Self::viewport_pass(
+            id,
+            ShaderSource::black_frame_insertion(),
+            FilterMode::Nearest,
+        )
+    }
+}
+
 /// The loaded texture resource for a shader preset.
 pub type TextureResource = LoadedResource<TextureMeta>;
 
@@ -117,7 +217,57 @@ pub struct ShaderPresetPack {
 }
 
 impl ShaderPresetPack {
+    /// Create a preset without any passes, textures or parameters.
+    ///
+    /// A filter chain loaded from an empty preset only scales the input to the viewport.
+    pub fn empty() -> ShaderPresetPack {
+        ShaderPresetPack {
+            #[cfg(feature = "parse_legacy_glsl")]
+            feedback_pass: 0,
+
+            pass_count: 0,
+            passes: Vec::new(),
+            textures: Vec::new(),
+            parameters: Vec::new(),
+        }
+    }
+
+    /// Append a built-in pass after the passes of the preset.
+    ///
+    /// The previous final pass is scaled to the viewport if it does not declare its own
+    /// scaling, as it would be as the final pass. The appended pass is the last enabled pass,
+    /// so lowering the number of enabled passes of the filter chain disables it.
+    fn append_pass(&mut self, pass: impl FnOnce(i32) -> PassResource) {
+        if let Some(last) = self.passes.last_mut() {
+            if !last.meta.scaling.valid {
+                last.meta.scaling = viewport_scaling();
+            }
+        }
+
+        self.passes.push(pass(self.passes.len() as i32));
+        self.pass_count = self.passes.len() as i32;
+    }
+
+    /// Append the [colour assistance pass](PassResource::color_assist) after the passes of
+    /// the preset.
+    ///
+    /// Lowering the number of enabled passes of the filter chain disables the pass.
+    pub fn append_color_assist(&mut self) {
+        self.append_pass(PassResource::color_assist);
+    }
+
+    /// Append the [black frame insertion pass](PassResource::black_frame_insertion) after the
+    /// passes of the preset. This should be the last pass to be appended.
+    ///
+    /// Lowering the number of enabled passes of the filter chain disables the pass.
+    pub fn append_black_frame_insertion(&mut self) {
+        self.append_pass(PassResource::black_frame_insertion);
+    }
+
     /// Load a `ShaderPack` from a [`ShaderPreset`].
+    ///
+    /// If the preset was parsed with [`ShaderFeatures::FALLBACK_TEXTURES`], textures that fail
+    /// to load are replaced with [`TextureBuffer::placeholder`] and a warning is logged.
     pub fn load_from_preset<E>(preset: ShaderPreset) -> Result<ShaderPresetPack, E>
     where
         E: From<PreprocessError>,
@@ -144,17 +294,36 @@ impl ShaderPresetPack {
             passes: shaders_iter
                 .map(|v| {
                     Ok::<_, E>(PassResource {
-                        data: PassMeta::load(v.path.as_path(), preset.features)?,
+                        data: ShaderSource::load_with_defines(
+                            &v.path,
+                            preset.features,
+                            &v.meta.defines,
+                            preset.sandbox.as_ref(),
+                        )?,
                         meta: v.meta,
+                        path: Some(v.path),
                     })
                 })
                 .collect::<Result<Vec<_>, _>>()?,
             textures: textures_iter
                 .into_par_iter()
                 .map(|t| {
+                    let data = match TextureMeta::load(t.path.as_path(), ()) {
+                        Ok(data) => data,
+                        Err(err) if preset.features.contains(ShaderFeatures::FALLBACK_TEXTURES) => {
+                            log::warn!(
+                                "substituting placeholder for texture {} ({}): {err}",
+                                t.meta.name,
+                                t.path.display()
+                            );
+                            TextureBuffer::placeholder()
+                        }
+                        Err(err) => return Err(err.into()),
+                    };
                     Ok::<_, E>(TextureResource {
-                        data: TextureMeta::load(t.path.as_path(), ())?,
+                        data,
                         meta: t.meta,
+                        path: Some(t.path),
                     })
                 })
                 .collect::<Result<Vec<_>, _>>()?,
@@ -163,6 +332,146 @@ impl ShaderPresetPack {
     }
 }
 
+impl ShaderPresetPack {
+    /// Compute a stable content hash of the preset and all of its resources.
+    ///
+    /// The hash covers the preprocessed source of every pass, which includes the contents
+    /// of every included file, the name, format and declared parameters of every pass,
+    /// the decoded pixels of every texture, and the configuration of every pass, texture
+    /// and parameter. Paths are not hashed, so the same preset
+    /// installed in different locations has the same hash.
+    ///
+    /// The hash is stable across platforms, and can be used as a cache key or to
+    /// deduplicate and verify preset packs.
+    pub fn content_hash(&self) -> [u8; 32] {
+        let mut hasher = ContentHasher(blake3::Hasher::new());
+        hasher.bytes(b"librashader-pack content hash v2");
+
+        hasher.u32(self.pass_count as u32);
+        hasher.u32(self.passes.len() as u32);
+        for pass in &self.passes {
+            let meta = &pass.meta;
+            hasher.str(&pass.data.vertex);
+            hasher.str(&pass.data.fragment);
+            hasher.str(pass.data.compute.as_deref().unwrap_or(""));
+            hasher.bool(pass.data.compute.is_some());
+            hasher.str(pass.data.name.as_deref().unwrap_or(""));
+            hasher.bool(pass.data.name.is_some());
+            hasher.u32(pass.data.format as u32);
+            hasher.u32(pass.data.parameters.len() as u32);
+            for parameter in pass.data.parameters.values() {
+                hasher.str(&parameter.id);
+                hasher.str(&parameter.description);
+                hasher.u32(parameter.initial.to_bits());
+                hasher.u32(parameter.minimum.to_bits());
+                hasher.u32(parameter.maximum.to_bits());
+                hasher.u32(parameter.step.to_bits());
+                match &parameter.widget {
+                    ParameterWidget::Slider => hasher.u32(0),
+                    ParameterWidget::Checkbox => hasher.u32(1),
+                    ParameterWidget::Combo(labels) => {
+                        hasher.u32(2);
+                        hasher.u32(labels.len() as u32);
+                        for label in labels {
+                            hasher.str(label);
+                        }
+                    }
+                }
+            }
+            hasher.u32(meta.id as u32);
+            hasher.str(meta.alias.as_deref().unwrap_or(""));
+            hasher.bool(meta.alias.is_some());
+            hasher.u32(meta.filter as u32);
+            hasher.u32(meta.wrap_mode as u32);
+            hasher.u32(meta.frame_count_mod);
+            hasher.bool(meta.srgb_framebuffer);
+            hasher.bool(meta.float_framebuffer);
+            hasher.bool(meta.mipmap_input);
+            hasher.bool(meta.scaling.valid);
+            for scaling in [&meta.scaling.x, &meta.scaling.y] {
+                hasher.u32(scaling.scale_type as u32);
+                match scaling.factor {
+                    ScaleFactor::Float(factor) => {
+                        hasher.u32(0);
+                        hasher.u32(factor.to_bits());
+                    }
+                    ScaleFactor::Absolute(factor) => {
+                        hasher.u32(1);
+                        hasher.u32(factor as u32);
+                    }
+                }
+            }
+        }
+
+        hasher.u32(self.textures.len() as u32);
+        for texture in &self.textures {
+            let meta = &texture.meta;
+            hasher.str(&meta.name);
+            hasher.u32(meta.wrap_mode as u32);
+            hasher.u32(meta.filter_mode as u32);
+            hasher.bool(meta.mipmap);
+            hasher.bool(meta.srgb);
+            hasher.u32(texture.data.width);
+            hasher.u32(texture.data.height);
+            hasher.bytes(texture.data.as_ref());
+        }
+
+        hasher.u32(self.parameters.len() as u32);
+        for parameter in &self.parameters {
+            hasher.str(&parameter.name);
+            hasher.u32(parameter.value.to_bits());
+        }
+
+        *hasher.0.finalize().as_bytes()
+    }
+}
+
+impl TextureResource {
+    /// Compute a stable content hash of the pixels of the texture and the configuration that
+    /// affects how it is sampled.
+    ///
+    /// The name of the texture is not hashed, so textures with the same hash are
+    /// interchangeable even if different presets bind them under different names.
+    pub fn content_hash(&self) -> [u8; 32] {
+        let mut hasher = ContentHasher(blake3::Hasher::new());
+        hasher.bytes(b"librashader-pack texture hash v1");
+
+        let meta = &self.meta;
+        hasher.u32(meta.wrap_mode as u32);
+        hasher.u32(meta.filter_mode as u32);
+        hasher.bool(meta.mipmap);
+        hasher.bool(meta.srgb);
+        hasher.u32(self.data.width);
+        hasher.u32(self.data.height);
+        hasher.bytes(self.data.as_ref());
+
+        *hasher.0.finalize().as_bytes()
+    }
+}
+
+// Every value is written with a fixed width or a length prefix so that
+// the boundaries between values can not be ambiguous.
+struct ContentHasher(blake3::Hasher);
+
+impl ContentHasher {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.0.update(&(bytes.len() as u64).to_le_bytes());
+        self.0.update(bytes);
+    }
+
+    fn str(&mut self, str: &str) {
+        self.bytes(str.as_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.0.update(&value.to_le_bytes());
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.0.update(&[value as u8]);
+    }
+}
+
 #[cfg(feature = "serde")]
 mod serde_base64_or_bytes {
     use base64::display::Base64Display;
@@ -227,8 +536,10 @@ mod serde_base64_or_bytes {
 
 #[cfg(test)]
 mod test {
-    use crate::ShaderPresetPack;
-    use librashader_presets::{ShaderFeatures, ShaderPreset};
+    use crate::{ShaderPresetPack, TextureBuffer};
+    use librashader_presets::{
+        ParsePresetError, PathSandbox, ShaderFeatures, ShaderPreset, WildcardContext,
+    };
     use std::fs::File;
     use std::io::Write;
 
@@ -245,6 +556,104 @@ mod test {
             .unwrap();
     }
 
+    #[test]
+    fn test_fallback_texture() {
+        assert!(
+            ShaderPreset::try_parse("../test/missing_lut.slangp", ShaderFeatures::NONE).is_err()
+        );
+
+        let preset = ShaderPreset::try_parse(
+            "../test/missing_lut.slangp",
+            ShaderFeatures::FALLBACK_TEXTURES,
+        )
+        .unwrap();
+        let resolved = ShaderPresetPack::load_from_preset::<anyhow::Error>(preset).unwrap();
+        assert_eq!(
+            resolved.textures[0].data.as_ref(),
+            TextureBuffer::placeholder().as_ref()
+        );
+    }
+
+    #[test]
+    fn test_sandbox() {
+        let sandbox = PathSandbox::new(["../test/include_optional"]).unwrap();
+        assert!(matches!(
+            ShaderPreset::try_parse_with_sandbox(
+                "../test/basic.slangp",
+                ShaderFeatures::NONE,
+                WildcardContext::new(),
+                sandbox,
+            ),
+            Err(ParsePresetError::PathOutsideSandbox(_))
+        ));
+
+        let sandbox = PathSandbox::new(["../test"]).unwrap();
+        let preset = ShaderPreset::try_parse_with_sandbox(
+            "../test/basic.slangp",
+            ShaderFeatures::NONE,
+            WildcardContext::new(),
+            sandbox,
+        )
+        .unwrap();
+        assert!(ShaderPresetPack::load_from_preset::<anyhow::Error>(preset).is_ok());
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let load = |path| {
+            let preset = ShaderPreset::try_parse(path, ShaderFeatures::NONE).unwrap();
+            ShaderPresetPack::load_from_preset::<anyhow::Error>(preset).unwrap()
+        };
+
+        let basic = load("../test/basic.slangp");
+        assert_eq!(
+            basic.content_hash(),
+            load("../test/basic.slangp").content_hash()
+        );
+        assert_ne!(
+            basic.content_hash(),
+            load("../test/null.slangp").content_hash()
+        );
+
+        let mut modified = basic.clone();
+        modified.passes[0].meta.mipmap_input = !modified.passes[0].meta.mipmap_input;
+        assert_ne!(basic.content_hash(), modified.content_hash());
+    }
+
+    #[test]
+    fn test_color_assist() {
+        let preset = ShaderPreset::try_parse("../test/basic.slangp", ShaderFeatures::NONE).unwrap();
+        let mut pack = ShaderPresetPack::load_from_preset::<anyhow::Error>(preset).unwrap();
+        let passes = pack.passes.len();
+        pack.append_color_assist();
+
+        assert_eq!(pack.passes.len(), passes + 1);
+        assert_eq!(pack.pass_count as usize, passes + 1);
+        assert_eq!(
+            pack.passes[passes].data.name.as_deref(),
+            Some("ColorAssist")
+        );
+        assert!(pack.passes[passes - 1].meta.scaling.valid);
+        assert!(pack.passes[passes - 1].path.is_some());
+        assert!(pack.passes[passes].path.is_none());
+    }
+
+    #[test]
+    fn test_black_frame_insertion() {
+        let preset = ShaderPreset::try_parse("../test/basic.slangp", ShaderFeatures::NONE).unwrap();
+        let mut pack = ShaderPresetPack::load_from_preset::<anyhow::Error>(preset).unwrap();
+        let passes = pack.passes.len();
+        pack.append_color_assist();
+        pack.append_black_frame_insertion();
+
+        assert_eq!(pack.pass_count as usize, passes + 2);
+        assert_eq!(
+            pack.passes[passes + 1].data.name.as_deref(),
+            Some("BlackFrameInsertion")
+        );
+        assert_eq!(pack.passes[passes + 1].meta.id as usize, passes + 1);
+    }
+
     #[test]
     fn test_rmp() {
         let preset = ShaderPreset::try_parse(
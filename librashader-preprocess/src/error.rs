@@ -12,9 +12,12 @@ pub enum PreprocessError {
     /// An IO error occurred when reading the source file.
     #[error("the file was not found during resolution")]
     IOError(PathBuf, std::io::Error),
+    /// The file resides outside of the roots of the sandbox.
+    #[error("the file {0:?} is outside of the sandbox")]
+    PathOutsideSandbox(PathBuf),
     /// A known encoding was not found for the file.
     #[error(
-        "a known encoding was not found for the file. supported encodings are UTF-8 and Latin-1"
+        "a known encoding was not found for the file. supported encodings are UTF-8, UTF-16 with a byte order mark, and Latin-1"
     )]
     EncodingError(PathBuf),
     /// Unexpected EOF when reading the source file.
@@ -32,9 +35,15 @@ pub enum PreprocessError {
     /// The image format requested by the shader was unknown or not supported.
     #[error("shader format is unknown or not found")]
     UnknownImageFormat,
-    /// The stage declared by the shader source was not `vertex` or `fragment`.
-    #[error("stage must be either vertex or fragment")]
+    /// The stage declared by the shader source was not `vertex`, `fragment` or `compute`.
+    #[error("stage must be either vertex, fragment or compute")]
     InvalidStage,
+    /// The shader source declared a `compute` stage along with a `vertex` or `fragment` stage.
+    #[error("a compute stage can not be declared along with a vertex or fragment stage")]
+    MixedComputeStage,
+    /// A define injected into the shader had an invalid name or replacement text.
+    #[error("the define {0} is not valid")]
+    InvalidDefine(ShortString),
 }
 
 impl From<Infallible> for PreprocessError {
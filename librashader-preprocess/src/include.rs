@@ -1,8 +1,11 @@
 use crate::{PreprocessError, SourceOutput};
-use encoding_rs::{DecoderResult, WINDOWS_1252};
-use librashader_common::shader_features::ShaderFeatures;
+use librashader_common::sandbox::PathSandbox;
+use librashader_common::shader_features::{ShaderDefine, ShaderFeatures};
+use librashader_common::text::decode_text_borrowed;
+use std::borrow::Cow;
 use std::fs::File;
 use std::io::Read;
+use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::str::Lines;
 
@@ -14,46 +17,67 @@ const DEFINE_HAS_ORIGINALASPECT_UNIFORM: &str = "#define _HAS_ORIGINALASPECT_UNI
 
 const DEFINE_HAS_FRAMETIME_UNIFORMS: &str = "#define _HAS_FRAMETIME_UNIFORMS";
 
-fn read_file(path: impl AsRef<Path>) -> Result<String, PreprocessError> {
-    let path = path.as_ref();
-    let mut buf = Vec::new();
-    File::open(path)
-        .and_then(|mut f| {
-            f.read_to_end(&mut buf)?;
-            Ok(())
-        })
-        .map_err(|e| PreprocessError::IOError(path.to_path_buf(), e))?;
-
-    match String::from_utf8(buf) {
-        Ok(s) => Ok(s),
-        Err(e) => {
-            let buf = e.into_bytes();
-            let decoder = WINDOWS_1252.new_decoder();
-            let Some(len) = decoder.max_utf8_buffer_length_without_replacement(buf.len()) else {
-                return Err(PreprocessError::EncodingError(path.to_path_buf()));
-            };
+/// The contents of a shader source file.
+enum SourceBytes {
+    /// The file is memory-mapped.
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+    /// The file was read into memory.
+    Owned(Vec<u8>),
+}
 
-            let mut latin1_string = String::with_capacity(len);
+impl Deref for SourceBytes {
+    type Target = [u8];
 
-            let (result, _) = WINDOWS_1252
-                .new_decoder()
-                .decode_to_string_without_replacement(&buf, &mut latin1_string, true);
-            if result == DecoderResult::InputEmpty {
-                Ok(latin1_string)
-            } else {
-                Err(PreprocessError::EncodingError(path.to_path_buf()))
-            }
+    fn deref(&self) -> &Self::Target {
+        match self {
+            #[cfg(feature = "mmap")]
+            SourceBytes::Mapped(map) => map,
+            SourceBytes::Owned(buf) => buf,
         }
     }
 }
 
+fn read_file(
+    path: impl AsRef<Path>,
+    sandbox: Option<&PathSandbox>,
+) -> Result<SourceBytes, PreprocessError> {
+    let path = path.as_ref();
+    if sandbox.is_some_and(|sandbox| !sandbox.contains(path)) {
+        return Err(PreprocessError::PathOutsideSandbox(path.to_path_buf()));
+    }
+
+    let mut file = File::open(path).map_err(|e| PreprocessError::IOError(path.to_path_buf(), e))?;
+
+    // SAFETY: Shader sources must not be modified while they are loaded, which is
+    // documented on the `mmap` feature. If the file can not be mapped, it is read instead.
+    #[cfg(feature = "mmap")]
+    if let Ok(map) = unsafe { memmap2::Mmap::map(&file) } {
+        return Ok(SourceBytes::Mapped(map));
+    }
+
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)
+        .map_err(|e| PreprocessError::IOError(path.to_path_buf(), e))?;
+    Ok(SourceBytes::Owned(buf))
+}
+
+/// Decode the contents of a shader source file, borrowing them if they are already
+/// UTF-8 text with `\n` line endings.
+fn decode_file<'a>(path: &Path, bytes: &'a SourceBytes) -> Result<Cow<'a, str>, PreprocessError> {
+    decode_text_borrowed(bytes).ok_or_else(|| PreprocessError::EncodingError(path.to_path_buf()))
+}
+
 pub fn read_source(
     path: impl AsRef<Path>,
     features: ShaderFeatures,
+    defines: &[ShaderDefine],
+    sandbox: Option<&PathSandbox>,
 ) -> Result<String, PreprocessError> {
     let path = path.as_ref();
-    let source = read_file(path)?;
-    let mut output = String::new();
+    let bytes = read_file(path, sandbox)?;
+    let source = decode_file(path, &bytes)?;
+    let mut output = String::with_capacity(source.len());
 
     let source = source.trim();
     let mut lines = source.lines();
@@ -78,16 +102,72 @@ pub fn read_source(
         output.push_line(DEFINE_HAS_FRAMETIME_UNIFORMS);
     }
 
+    for define in defines {
+        if !define.is_valid() {
+            return Err(PreprocessError::InvalidDefine(define.name.clone()));
+        }
+        output.push_line(&format!("#define {} {}", define.name, define.value));
+    }
+
     output.mark_line(2, path.file_name().and_then(|f| f.to_str()).unwrap_or(""));
-    preprocess(lines, path, &mut output)?;
+    preprocess(lines, path, &mut output, sandbox)?;
 
     Ok(output)
 }
 
+/// Collect the paths of the source file and every file it includes, in the order they are
+/// first included. Optional includes are collected even if they do not exist.
+pub fn source_files(
+    path: impl AsRef<Path>,
+    sandbox: Option<&PathSandbox>,
+) -> Result<Vec<PathBuf>, PreprocessError> {
+    fn collect(
+        path: PathBuf,
+        optional: bool,
+        files: &mut Vec<PathBuf>,
+        sandbox: Option<&PathSandbox>,
+    ) -> Result<(), PreprocessError> {
+        if files.contains(&path) {
+            return Ok(());
+        }
+        files.push(path.clone());
+
+        let bytes = match read_file(&path, sandbox) {
+            Ok(bytes) => bytes,
+            Err(PreprocessError::IOError(..)) if optional => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let source = decode_file(&path, &bytes)?;
+        let include_path = path.parent().unwrap();
+
+        for (line_no, line) in source.lines().enumerate() {
+            let (include_file, optional) = if let Some(file) = line.strip_prefix("#include ") {
+                (file, false)
+            } else if let Some(file) = line.strip_prefix("#pragma include_optional") {
+                (file, true)
+            } else {
+                continue;
+            };
+
+            let include_file = include_file.trim().trim_matches('"');
+            if include_file.is_empty() {
+                return Err(PreprocessError::UnexpectedEol(line_no));
+            }
+            collect(include_path.join(include_file), optional, files, sandbox)?;
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    collect(path.as_ref().to_path_buf(), false, &mut files, sandbox)?;
+    Ok(files)
+}
+
 fn preprocess(
     lines: Lines,
     file_name: impl AsRef<Path>,
     output: &mut String,
+    sandbox: Option<&PathSandbox>,
 ) -> Result<(), PreprocessError> {
     let file_name = file_name.as_ref();
     let include_path = file_name.parent().unwrap();
@@ -95,10 +175,11 @@ fn preprocess(
 
     fn include_callback(
         output: &mut String,
-        source: String,
+        source: &str,
         include_path: PathBuf,
         file_name: &str,
         line_no: usize,
+        sandbox: Option<&PathSandbox>,
     ) -> Result<(), PreprocessError> {
         let source = source.trim();
         let lines = source.lines();
@@ -108,7 +189,7 @@ fn preprocess(
             .and_then(|f| f.to_str())
             .unwrap_or("");
         output.mark_line(1, include_file);
-        preprocess(lines, include_path, output)?;
+        preprocess(lines, include_path, output, sandbox)?;
         output.mark_line(line_no + 1, file_name);
         Ok(())
     }
@@ -123,8 +204,9 @@ fn preprocess(
             let mut include_path = include_path.to_path_buf();
             include_path.push(include_file);
 
-            let source = read_file(&include_path)?;
-            include_callback(output, source, include_path, file_name, line_no)?;
+            let bytes = read_file(&include_path, sandbox)?;
+            let source = decode_file(&include_path, &bytes)?;
+            include_callback(output, &source, include_path, file_name, line_no, sandbox)?;
 
             continue;
         }
@@ -139,8 +221,11 @@ fn preprocess(
             let mut include_path = include_path.to_path_buf();
             include_path.push(include_file);
 
-            match read_file(&include_path) {
-                Ok(source) => include_callback(output, source, include_path, file_name, line_no)?,
+            match read_file(&include_path, sandbox) {
+                Ok(bytes) => {
+                    let source = decode_file(&include_path, &bytes)?;
+                    include_callback(output, &source, include_path, file_name, line_no, sandbox)?
+                }
                 // ioerror indicates that the file is not found.
                 Err(PreprocessError::IOError(..)) => {
                     output.push_line(&format!("// include_optional not found: {include_file}"));
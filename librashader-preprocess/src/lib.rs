@@ -13,12 +13,18 @@ mod include;
 mod pragma;
 mod stage;
 
-use crate::include::read_source;
+use crate::include::{read_source, source_files};
 pub use error::*;
-use librashader_common::map::{FastHashMap, ShortString};
-use librashader_common::shader_features::ShaderFeatures;
-use librashader_common::ImageFormat;
-use std::path::Path;
+use librashader_common::map::{OrderedHashMap, ShortString};
+use librashader_common::sandbox::PathSandbox;
+use librashader_common::shader_features::{ShaderDefine, ShaderFeatures};
+use librashader_common::{ImageFormat, ScalingKernel};
+use std::path::{Path, PathBuf};
+
+/// The names of the `#pragma` directives that are understood by the preprocessor.
+///
+/// Other pragmas are passed through to the shader compiler, which ignores them.
+pub const KNOWN_PRAGMAS: &[&str] = &["parameter", "format", "name", "stage", "include_optional"];
 
 /// The source file for a single shader pass.
 #[derive(Debug, Clone, PartialEq)]
@@ -30,11 +36,19 @@ pub struct ShaderSource {
     /// The source contents for the fragment shader.
     pub fragment: String,
 
+    /// The source contents for the compute shader, if this is a compute pass.
+    ///
+    /// A compute pass is declared with `#pragma stage compute`, and writes its output
+    /// to a storage image instead of rendering a quad. The vertex and fragment sources
+    /// of a compute pass are empty.
+    pub compute: Option<String>,
+
     /// The alias of the shader if available.
     pub name: Option<ShortString>,
 
-    /// The list of shader parameters found in the shader source.
-    pub parameters: FastHashMap<ShortString, ShaderParameter>,
+    /// The list of shader parameters found in the shader source,
+    /// in the order they are declared.
+    pub parameters: OrderedHashMap<ShortString, ShaderParameter>,
 
     /// The image format the shader expects.
     pub format: ImageFormat,
@@ -56,6 +70,24 @@ pub struct ShaderParameter {
     pub maximum: f32,
     /// The step by which this parameter can be incremented or decremented.
     pub step: f32,
+    /// The widget that settings UIs should show for the parameter.
+    pub widget: ParameterWidget,
+}
+
+/// A hint for the widget that settings UIs should show for a shader parameter.
+///
+/// Widget hints are declared after the step of a `#pragma parameter`, which RetroArch ignores,
+/// for example `#pragma parameter MODE "Mode" 0.0 0.0 2.0 1.0 combo "Off" "Low" "High"`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParameterWidget {
+    /// A slider from the minimum to the maximum. This is the default.
+    #[default]
+    Slider,
+    /// A checkbox that toggles between the minimum and the maximum.
+    Checkbox,
+    /// A combo box with a label for every step from the minimum to the maximum.
+    Combo(Vec<String>),
 }
 
 impl ShaderSource {
@@ -65,7 +97,85 @@ impl ShaderSource {
         path: impl AsRef<Path>,
         features: ShaderFeatures,
     ) -> Result<ShaderSource, PreprocessError> {
-        load_shader_source(path, features)
+        load_shader_source(path, features, &[], None)
+    }
+
+    /// Load the source file at the given path, resolving includes relative to the location of the
+    /// source file.
+    ///
+    /// The source file and every file it includes must reside within the roots of the sandbox.
+    pub fn load_sandboxed(
+        path: impl AsRef<Path>,
+        features: ShaderFeatures,
+        sandbox: &PathSandbox,
+    ) -> Result<ShaderSource, PreprocessError> {
+        load_shader_source(path, features, &[], Some(sandbox))
+    }
+
+    /// Load the source file at the given path with the given defines injected after the
+    /// version header, resolving includes relative to the location of the source file.
+    ///
+    /// If a sandbox is given, the source file and every file it includes must reside
+    /// within the roots of the sandbox.
+    pub fn load_with_defines(
+        path: impl AsRef<Path>,
+        features: ShaderFeatures,
+        defines: &[ShaderDefine],
+        sandbox: Option<&PathSandbox>,
+    ) -> Result<ShaderSource, PreprocessError> {
+        load_shader_source(path, features, defines, sandbox)
+    }
+
+    /// The paths of the source file at the given path and every file it includes, in the order
+    /// they are first included.
+    ///
+    /// Files included with `#pragma include_optional` are listed even if they do not exist,
+    /// since creating them changes the loaded source. This does not preprocess the source.
+    pub fn source_files(
+        path: impl AsRef<Path>,
+        sandbox: Option<&PathSandbox>,
+    ) -> Result<Vec<PathBuf>, PreprocessError> {
+        source_files(path, sandbox)
+    }
+
+    /// The source of a pass that scales the source image with the given kernel without
+    /// any other processing.
+    ///
+    /// Filter chains render this pass to scale the input to the viewport when there
+    /// are no shader passes to run. The nearest-neighbour and bilinear kernels share a
+    /// source that relies on the filter mode of the sampler.
+    pub fn identity(kernel: ScalingKernel) -> ShaderSource {
+        let source = match kernel {
+            ScalingKernel::Nearest | ScalingKernel::Bilinear => include_str!("identity.slang"),
+            ScalingKernel::Bicubic => include_str!("identity_bicubic.slang"),
+            ScalingKernel::Lanczos => include_str!("identity_lanczos.slang"),
+        };
+
+        parse_shader_source(source).expect("the identity shader should be valid")
+    }
+
+    /// The source of a pass that simulates or corrects for colour vision deficiencies.
+    ///
+    /// The pass is controlled by the `LIBRA_COLOR_ASSIST_MODE` parameter, where 0 is off,
+    /// 1 is protanopia, 2 is deuteranopia and 3 is tritanopia. If the
+    /// `LIBRA_COLOR_ASSIST_SIMULATE` parameter is 1, the deficiency is simulated instead of
+    /// corrected for, and `LIBRA_COLOR_ASSIST_STRENGTH` blends the result with the source image.
+    pub fn color_assist() -> ShaderSource {
+        parse_shader_source(include_str!("color_assist.slang"))
+            .expect("the color assist shader should be valid")
+    }
+
+    /// The source of a pass that inserts black frames between the subframes of a frame,
+    /// to improve motion clarity on high refresh rate displays.
+    ///
+    /// The pass is enabled with the `LIBRA_BFI_ENABLE` parameter. The first
+    /// `LIBRA_BFI_LIT_SUBFRAMES` subframes of every frame show the source image, and the rest
+    /// are drawn at the `LIBRA_BFI_DARK_LEVEL` brightness, where a level above 0 reduces
+    /// flicker. `LIBRA_BFI_COMPENSATION` brightens the lit subframes to make up for the dark
+    /// subframes. The pass has no effect if there is only one subframe.
+    pub fn black_frame_insertion() -> ShaderSource {
+        parse_shader_source(include_str!("black_frame_insertion.slang"))
+            .expect("the black frame insertion shader should be valid")
     }
 }
 
@@ -87,16 +197,24 @@ impl SourceOutput for String {
 pub(crate) fn load_shader_source(
     path: impl AsRef<Path>,
     features: ShaderFeatures,
+    defines: &[ShaderDefine],
+    sandbox: Option<&PathSandbox>,
 ) -> Result<ShaderSource, PreprocessError> {
-    let source = read_source(path, features)?;
-    let meta = pragma::parse_pragma_meta(&source)?;
+    let source = read_source(path, features, defines, sandbox)?;
+    parse_shader_source(&source)
+}
 
-    let text = stage::process_stages(&source)?;
-    let parameters = FastHashMap::from_iter(meta.parameters.into_iter().map(|p| (p.id.clone(), p)));
+fn parse_shader_source(source: &str) -> Result<ShaderSource, PreprocessError> {
+    let meta = pragma::parse_pragma_meta(source)?;
+
+    let text = stage::process_stages(source)?;
+    let parameters =
+        OrderedHashMap::from_iter(meta.parameters.into_iter().map(|p| (p.id.clone(), p)));
 
     Ok(ShaderSource {
         vertex: text.vertex,
         fragment: text.fragment,
+        compute: text.compute,
         name: meta.name,
         parameters,
         format: meta.format,
@@ -106,14 +224,18 @@ pub(crate) fn load_shader_source(
 #[cfg(test)]
 mod test {
     use crate::include::read_source;
-    use crate::{load_shader_source, pragma};
-    use librashader_common::shader_features::ShaderFeatures;
+    use crate::{load_shader_source, pragma, PreprocessError, ShaderSource};
+    use librashader_common::sandbox::PathSandbox;
+    use librashader_common::shader_features::{ShaderDefine, ShaderFeatures};
+    use librashader_common::ScalingKernel;
 
     #[test]
     pub fn load_file() {
         let result = load_shader_source(
             "../test/shaders_slang/blurs/shaders/royale/blur3x3-last-pass.slang",
             ShaderFeatures::NONE,
+            &[],
+            None,
         )
         .unwrap();
         eprintln!("{:#}", result.vertex)
@@ -124,6 +246,8 @@ mod test {
         let result = read_source(
             "../test/slang-shaders/blurs/shaders/royale/blur3x3-last-pass.slang",
             ShaderFeatures::NONE,
+            &[],
+            None,
         )
         .unwrap();
         eprintln!("{result}")
@@ -134,6 +258,8 @@ mod test {
         let result = read_source(
             "../test/slang-shaders/crt/shaders/crt-maximus-royale/src/ntsc_pass1.slang",
             ShaderFeatures::NONE,
+            &[],
+            None,
         )
         .unwrap();
 
@@ -141,11 +267,163 @@ mod test {
         eprintln!("{params:?}")
     }
 
+    #[test]
+    pub fn identity() {
+        let source = ShaderSource::identity(ScalingKernel::Nearest);
+        assert_eq!(source.name.as_deref(), Some("Identity"));
+
+        for kernel in ScalingKernel::ALL {
+            let source = ShaderSource::identity(kernel);
+            assert!(source.parameters.is_empty());
+            assert!(source.vertex.contains("gl_Position"));
+            assert!(source.fragment.contains("FragColor"));
+        }
+    }
+
+    #[test]
+    pub fn color_assist() {
+        let source = ShaderSource::color_assist();
+        assert_eq!(source.name.as_deref(), Some("ColorAssist"));
+        assert_eq!(
+            source
+                .parameters
+                .get("LIBRA_COLOR_ASSIST_MODE")
+                .map(|param| param.initial),
+            Some(0.0)
+        );
+        assert!(source
+            .parameters
+            .contains_key("LIBRA_COLOR_ASSIST_SIMULATE"));
+        assert!(source
+            .parameters
+            .contains_key("LIBRA_COLOR_ASSIST_STRENGTH"));
+    }
+
+    #[test]
+    pub fn black_frame_insertion() {
+        let source = ShaderSource::black_frame_insertion();
+        assert_eq!(source.name.as_deref(), Some("BlackFrameInsertion"));
+        assert_eq!(
+            source
+                .parameters
+                .get("LIBRA_BFI_ENABLE")
+                .map(|param| param.initial),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    pub fn compute_stage() {
+        let source = ShaderSource::load("../test/compute.slang", ShaderFeatures::NONE).unwrap();
+        let compute = source.compute.expect("compute source should be present");
+        assert!(compute.contains("local_size_x"));
+        assert!(compute.contains("uniform UBO"));
+        assert!(source.vertex.is_empty());
+        assert!(source.fragment.is_empty());
+
+        let source = ShaderSource::load("../test/basic.slang", ShaderFeatures::NONE).unwrap();
+        assert!(source.compute.is_none());
+    }
+
+    #[test]
+    pub fn mixed_compute_stage() {
+        let result = super::parse_shader_source(
+            "#version 450\n#pragma stage vertex\nvoid main() {}\n#pragma stage compute\nvoid main() {}\n",
+        );
+        assert!(matches!(result, Err(PreprocessError::MixedComputeStage)));
+    }
+
+    #[test]
+    pub fn parameters_in_declaration_order() {
+        let mut source = String::from("#version 450\n");
+        for index in (0..40).rev() {
+            source.push_str(&format!(
+                "#pragma parameter PARAM_{index} \"Param {index}\" 0.0 0.0 1.0 0.1\n"
+            ));
+        }
+        source.push_str("#pragma stage vertex\nvoid main() {}\n");
+
+        let source = super::parse_shader_source(&source).unwrap();
+        let names: Vec<String> = source.parameters.keys().map(|id| id.to_string()).collect();
+        let expected: Vec<String> = (0..40)
+            .rev()
+            .map(|index| format!("PARAM_{index}"))
+            .collect();
+        assert_eq!(names, expected);
+    }
+
     #[test]
     pub fn include_optional() {
-        let result =
-            read_source("../test/include_optional/pass.slang", ShaderFeatures::NONE).unwrap();
+        let result = read_source(
+            "../test/include_optional/pass.slang",
+            ShaderFeatures::NONE,
+            &[],
+            None,
+        )
+        .unwrap();
 
         eprintln!("{result}")
     }
+
+    #[test]
+    pub fn list_source_files() {
+        let files =
+            ShaderSource::source_files("../test/include_optional/pass.slang", None).unwrap();
+        let names: Vec<_> = files
+            .iter()
+            .map(|file| file.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            ["pass.slang", "myinclude.inc", "myinclude.doesntexist.inc"]
+        );
+    }
+
+    #[test]
+    pub fn include_sandboxed() {
+        let sandbox = PathSandbox::new(["../test/include_optional"]).unwrap();
+        let result = read_source(
+            "../test/include_optional/pass.slang",
+            ShaderFeatures::NONE,
+            &[],
+            Some(&sandbox),
+        );
+        assert!(result.is_ok());
+
+        let sandbox = PathSandbox::new(["../test/include_optional/includes"]).unwrap();
+        let result = read_source(
+            "../test/include_optional/pass.slang",
+            ShaderFeatures::NONE,
+            &[],
+            Some(&sandbox),
+        );
+        assert!(matches!(
+            result,
+            Err(PreprocessError::PathOutsideSandbox(_))
+        ));
+    }
+
+    #[test]
+    pub fn inject_defines() {
+        let defines = [
+            ShaderDefine::new("CURVATURE", "0"),
+            ShaderDefine::new("USE_LUT", ""),
+        ];
+        let result =
+            read_source("../test/basic.slang", ShaderFeatures::NONE, &defines, None).unwrap();
+        let mut lines = result
+            .lines()
+            .filter(|l| !l.starts_with("#line") && !l.starts_with("#extension"));
+        assert!(lines.next().unwrap().starts_with("#version"));
+        assert_eq!(lines.next(), Some("#define CURVATURE 0"));
+        assert_eq!(lines.next(), Some("#define USE_LUT "));
+
+        let result = read_source(
+            "../test/basic.slang",
+            ShaderFeatures::NONE,
+            &[ShaderDefine::new("1BAD", "")],
+            None,
+        );
+        assert!(matches!(result, Err(PreprocessError::InvalidDefine(_))));
+    }
 }
@@ -1,12 +1,14 @@
-use crate::{PreprocessError, ShaderParameter};
+use crate::{ParameterWidget, PreprocessError, ShaderParameter};
 use librashader_common::ImageFormat;
 use nom::bytes::complete::{is_not, tag, take_while};
 
 use librashader_common::map::ShortString;
-use nom::character::complete::{multispace0, multispace1};
-use nom::combinator::opt;
+use nom::branch::alt;
+use nom::character::complete::{multispace0, multispace1, satisfy};
+use nom::combinator::{map, not, opt, value};
+use nom::multi::many0;
 use nom::number::complete::float;
-use nom::sequence::delimited;
+use nom::sequence::{delimited, preceded, terminated};
 use nom::IResult;
 use std::str::FromStr;
 
@@ -17,6 +19,27 @@ pub(crate) struct ShaderMeta {
     pub(crate) name: Option<ShortString>,
 }
 
+// Match a whole word, so that hints are not matched as the prefix of an unknown hint.
+fn keyword<'a>(word: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
+    terminated(
+        tag(word),
+        not(satisfy(|c: char| c.is_alphanumeric() || c == '_')),
+    )
+}
+
+fn parse_parameter_widget(input: &str) -> IResult<&str, ParameterWidget> {
+    let label = delimited(tag("\""), is_not("\""), tag("\""));
+    let combo = preceded(keyword("combo"), many0(preceded(multispace1, label)));
+
+    alt((
+        value(ParameterWidget::Slider, keyword("slider")),
+        value(ParameterWidget::Checkbox, keyword("checkbox")),
+        map(combo, |labels: Vec<&str>| {
+            ParameterWidget::Combo(labels.into_iter().map(String::from).collect())
+        }),
+    ))(input)
+}
+
 fn parse_parameter_string(input: &str) -> Result<ShaderParameter, PreprocessError> {
     fn parse_parameter_string_name(input: &str) -> IResult<&str, (&str, &str)> {
         let (input, _) = tag("#pragma parameter ")(input)?;
@@ -47,6 +70,11 @@ fn parse_parameter_string(input: &str) -> Result<ShaderParameter, PreprocessErro
         // https://github.com/libretro/slang-shaders/blob/0e2939787076e4a8a83be89175557fde23abe837/crt/shaders/crt-slangtest/parameters.inc#L1
         let (input, _) = multispace0(input)?;
         let (input, step) = opt(float)(input)?;
+
+        // Widget hints follow the step, where they are ignored by RetroArch.
+        // Unknown hints are ignored as well, rather than failing the whole parameter.
+        let (input, _) = multispace0(input)?;
+        let (input, widget) = opt(parse_parameter_widget)(input)?;
         Ok((
             input,
             ShaderParameter {
@@ -56,6 +84,7 @@ fn parse_parameter_string(input: &str) -> Result<ShaderParameter, PreprocessErro
                 minimum,
                 maximum,
                 step: step.unwrap_or(0.02),
+                widget: widget.unwrap_or_default(),
             },
         ))
     }
@@ -76,6 +105,7 @@ fn parse_parameter_string(input: &str) -> Result<ShaderParameter, PreprocessErro
             minimum: 0f32,
             maximum: 0f32,
             step: 0f32,
+            widget: ParameterWidget::default(),
         })
     }
 }
@@ -128,8 +158,26 @@ pub(crate) fn parse_pragma_meta(source: impl AsRef<str>) -> Result<ShaderMeta, P
 
 #[cfg(test)]
 mod test {
-    use crate::pragma::parse_parameter_string;
-    use crate::ShaderParameter;
+    use crate::pragma::{parse_parameter_string, parse_pragma_meta};
+    use crate::{ParameterWidget, ShaderParameter};
+    use librashader_common::ImageFormat;
+
+    #[test]
+    fn parses_format_pragma() {
+        for (name, format) in [
+            ("R8G8_UINT", ImageFormat::R8G8Uint),
+            ("R8G8B8A8_SRGB", ImageFormat::R8G8B8A8Srgb),
+            ("A2B10G10R10_UNORM_PACK32", ImageFormat::A2B10G10R10UnormPack32),
+            ("R16_SFLOAT", ImageFormat::R16Sfloat),
+            ("R16G16_SFLOAT", ImageFormat::R16G16Sfloat),
+            ("R32G32B32A32_SFLOAT", ImageFormat::R32G32B32A32Sfloat),
+        ] {
+            let meta = parse_pragma_meta(format!("#pragma format {name}")).unwrap();
+            assert_eq!(meta.format, format, "{name}");
+        }
+
+        assert!(parse_pragma_meta("#pragma format R16F").is_err());
+    }
 
     #[test]
     fn parses_parameter_pragma() {
@@ -139,7 +187,8 @@ mod test {
             initial: 0.0,
             minimum: -10.0,
             maximum: 10.0,
-            step: 0.25
+            step: 0.25,
+            widget: ParameterWidget::Slider,
         }, parse_parameter_string(r#"#pragma parameter exc "orizontal correction hack (games where players stay at center)" 0.0 -10.0 10.0 0.25"#).unwrap())
     }
 
@@ -151,7 +200,8 @@ mod test {
             initial: 100.0,
             minimum: 25.0,
             maximum: 1600.0,
-            step: 25.0
+            step: 25.0,
+            widget: ParameterWidget::Slider,
         }, parse_parameter_string(r#"#pragma parameter HSM_CORE_RES_SAMPLING_MULT_SCANLINE_DIR			"          Scanline Dir Multiplier"  100 25 1600 25"#).unwrap())
     }
 
@@ -164,7 +214,8 @@ mod test {
                 initial: 2.2,
                 minimum: 1.8,
                 maximum: 2.4,
-                step: 0.02
+                step: 0.02,
+                widget: ParameterWidget::Slider,
             },
             parse_parameter_string(
                 r#"#pragma parameter OUT_GAMMA "Monitor Output Gamma" 2.2 1.8 2.4"#
@@ -172,4 +223,33 @@ mod test {
             .unwrap()
         )
     }
+
+    #[test]
+    fn parses_parameter_pragma_widget() {
+        let checkbox =
+            parse_parameter_string(r#"#pragma parameter BLUR "Blur" 0.0 0.0 1.0 1.0 checkbox"#)
+                .unwrap();
+        assert_eq!(checkbox.widget, ParameterWidget::Checkbox);
+
+        let combo = parse_parameter_string(
+            r#"#pragma parameter MODE "Mode" 0.0 0.0 2.0 1.0 combo "Off" "Low" "High""#,
+        )
+        .unwrap();
+        assert_eq!(combo.step, 1.0);
+        assert_eq!(
+            combo.widget,
+            ParameterWidget::Combo(vec!["Off".into(), "Low".into(), "High".into()])
+        );
+
+        let unknown =
+            parse_parameter_string(r#"#pragma parameter GAMMA "Gamma" 2.2 1.8 2.4 0.1 knob"#)
+                .unwrap();
+        assert_eq!(unknown.step, 0.1);
+        assert_eq!(unknown.widget, ParameterWidget::Slider);
+
+        let prefixed =
+            parse_parameter_string(r#"#pragma parameter BLUR "Blur" 0.0 0.0 1.0 1.0 checkboxes"#)
+                .unwrap();
+        assert_eq!(prefixed.widget, ParameterWidget::Slider);
+    }
 }
@@ -1,10 +1,12 @@
 use crate::{PreprocessError, SourceOutput};
 use std::str::FromStr;
 
+#[derive(Copy, Clone, PartialEq, Eq)]
 enum ActiveStage {
     Both,
     Fragment,
     Vertex,
+    Compute,
 }
 
 impl FromStr for ActiveStage {
@@ -14,6 +16,7 @@ impl FromStr for ActiveStage {
         match s {
             "vertex" => Ok(ActiveStage::Vertex),
             "fragment" => Ok(ActiveStage::Fragment),
+            "compute" => Ok(ActiveStage::Compute),
             _ => Err(PreprocessError::InvalidStage),
         }
     }
@@ -23,16 +26,30 @@ impl FromStr for ActiveStage {
 pub(crate) struct ShaderOutput {
     pub(crate) fragment: String,
     pub(crate) vertex: String,
+    pub(crate) compute: Option<String>,
 }
 
 pub(crate) fn process_stages(source: &str) -> Result<ShaderOutput, PreprocessError> {
     let mut active_stage = ActiveStage::Both;
     let mut output = ShaderOutput::default();
+    let mut has_graphics_stage = false;
 
     for line in source.lines() {
         if let Some(stage) = line.strip_prefix("#pragma stage ") {
             let stage = stage.trim();
             active_stage = ActiveStage::from_str(stage)?;
+
+            // a compute pass replaces the vertex and fragment stages entirely.
+            if active_stage == ActiveStage::Compute {
+                if has_graphics_stage {
+                    return Err(PreprocessError::MixedComputeStage);
+                }
+                output.compute.get_or_insert_with(|| output.vertex.clone());
+            } else if output.compute.is_some() {
+                return Err(PreprocessError::MixedComputeStage);
+            } else {
+                has_graphics_stage = true;
+            }
             continue;
         }
 
@@ -52,8 +69,19 @@ pub(crate) fn process_stages(source: &str) -> Result<ShaderOutput, PreprocessErr
                 output.fragment.push_line(line);
             }
             ActiveStage::Vertex => output.vertex.push_line(line),
+            ActiveStage::Compute => {
+                if let Some(compute) = &mut output.compute {
+                    compute.push_line(line);
+                }
+            }
         }
     }
 
+    // compute passes have no vertex or fragment source.
+    if output.compute.is_some() {
+        output.vertex.clear();
+        output.fragment.clear();
+    }
+
     Ok(output)
 }
@@ -0,0 +1,324 @@
+//! Structured differences between two versions of a shader preset.
+use crate::{PassConfig, ShaderPreset, TextureConfig};
+use librashader_common::map::ShortString;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// A single change between two versions of a shader preset.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PresetChange {
+    /// A shader pass was added at the given index of the new preset.
+    PassAdded { index: usize, path: PathBuf },
+    /// The shader pass at the given index of the old preset was removed.
+    PassRemoved { index: usize, path: PathBuf },
+    /// The configuration of a shader pass changed.
+    ///
+    /// The fields are named after the preset keys that changed, such as `filter_linear`
+    /// or `scale`.
+    PassChanged {
+        old_index: usize,
+        new_index: usize,
+        path: PathBuf,
+        fields: Vec<&'static str>,
+    },
+    /// A parameter was set by the new preset but not by the old preset.
+    ParameterAdded { name: ShortString, value: f32 },
+    /// A parameter was set by the old preset but not by the new preset.
+    ParameterRemoved { name: ShortString, value: f32 },
+    /// The value a parameter is set to changed.
+    ParameterChanged {
+        name: ShortString,
+        old: f32,
+        new: f32,
+    },
+    /// A lookup texture was added.
+    TextureAdded { name: ShortString, path: PathBuf },
+    /// A lookup texture was removed.
+    TextureRemoved { name: ShortString, path: PathBuf },
+    /// The path or the configuration of a lookup texture changed.
+    ///
+    /// The fields are named after the preset keys that changed, such as `path` or `mipmap`.
+    TextureChanged {
+        name: ShortString,
+        path: PathBuf,
+        fields: Vec<&'static str>,
+    },
+}
+
+/// The changes between two versions of a shader preset.
+///
+/// Pass changes come first in the order of the new preset, followed by parameter and
+/// texture changes in the order they are declared. Passes are matched by their path, so a
+/// pass inserted in the middle of a preset is reported as a single added pass. Paths are
+/// compared as resolved when the presets were parsed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PresetDiff {
+    /// The list of changes.
+    pub changes: Vec<PresetChange>,
+}
+
+impl PresetDiff {
+    /// Whether the presets are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl ShaderPreset {
+    /// Get the changes from this preset to a newer version of it.
+    pub fn diff(&self, new: &ShaderPreset) -> PresetDiff {
+        let mut changes = Vec::new();
+        diff_passes(&self.passes, &new.passes, &mut changes);
+
+        for param in &new.parameters {
+            match self.parameters.iter().find(|p| p.name == param.name) {
+                None => changes.push(PresetChange::ParameterAdded {
+                    name: param.name.clone(),
+                    value: param.value,
+                }),
+                Some(old) if old.value != param.value => {
+                    changes.push(PresetChange::ParameterChanged {
+                        name: param.name.clone(),
+                        old: old.value,
+                        new: param.value,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        for param in &self.parameters {
+            if !new.parameters.iter().any(|p| p.name == param.name) {
+                changes.push(PresetChange::ParameterRemoved {
+                    name: param.name.clone(),
+                    value: param.value,
+                })
+            }
+        }
+
+        for texture in &new.textures {
+            match self
+                .textures
+                .iter()
+                .find(|t| t.meta.name == texture.meta.name)
+            {
+                None => changes.push(PresetChange::TextureAdded {
+                    name: texture.meta.name.clone(),
+                    path: texture.path.clone(),
+                }),
+                Some(old) => {
+                    let fields = texture_fields(old, texture);
+                    if !fields.is_empty() {
+                        changes.push(PresetChange::TextureChanged {
+                            name: texture.meta.name.clone(),
+                            path: texture.path.clone(),
+                            fields,
+                        })
+                    }
+                }
+            }
+        }
+
+        for texture in &self.textures {
+            if !new
+                .textures
+                .iter()
+                .any(|t| t.meta.name == texture.meta.name)
+            {
+                changes.push(PresetChange::TextureRemoved {
+                    name: texture.meta.name.clone(),
+                    path: texture.path.clone(),
+                })
+            }
+        }
+
+        PresetDiff { changes }
+    }
+}
+
+/// Match passes by the longest common subsequence of their paths.
+fn diff_passes(old: &[PassConfig], new: &[PassConfig], changes: &mut Vec<PresetChange>) {
+    // lengths[i][j] is the length of the common subsequence of old[i..] and new[j..].
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i].path == new[j].path {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i].path == new[j].path {
+            let fields = pass_fields(&old[i], &new[j]);
+            if !fields.is_empty() {
+                changes.push(PresetChange::PassChanged {
+                    old_index: i,
+                    new_index: j,
+                    path: new[j].path.clone(),
+                    fields,
+                });
+            }
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lengths[i][j + 1] > lengths[i + 1][j]) {
+            changes.push(PresetChange::PassAdded {
+                index: j,
+                path: new[j].path.clone(),
+            });
+            j += 1;
+        } else {
+            changes.push(PresetChange::PassRemoved {
+                index: i,
+                path: old[i].path.clone(),
+            });
+            i += 1;
+        }
+    }
+}
+
+fn pass_fields(old: &PassConfig, new: &PassConfig) -> Vec<&'static str> {
+    let (old, new) = (&old.meta, &new.meta);
+    [
+        ("alias", old.alias != new.alias),
+        ("filter_linear", old.filter != new.filter),
+        ("wrap_mode", old.wrap_mode != new.wrap_mode),
+        (
+            "frame_count_mod",
+            old.frame_count_mod != new.frame_count_mod,
+        ),
+        (
+            "srgb_framebuffer",
+            old.srgb_framebuffer != new.srgb_framebuffer,
+        ),
+        (
+            "float_framebuffer",
+            old.float_framebuffer != new.float_framebuffer,
+        ),
+        ("mipmap_input", old.mipmap_input != new.mipmap_input),
+        ("scale", old.scaling != new.scaling),
+        ("defines", old.defines != new.defines),
+    ]
+    .into_iter()
+    .filter_map(|(field, changed)| changed.then_some(field))
+    .collect()
+}
+
+fn texture_fields(old: &TextureConfig, new: &TextureConfig) -> Vec<&'static str> {
+    [
+        ("path", old.path != new.path),
+        ("wrap_mode", old.meta.wrap_mode != new.meta.wrap_mode),
+        ("linear", old.meta.filter_mode != new.meta.filter_mode),
+        ("mipmap", old.meta.mipmap != new.meta.mipmap),
+        ("srgb", old.meta.srgb != new.meta.srgb),
+    ]
+    .into_iter()
+    .filter_map(|(field, changed)| changed.then_some(field))
+    .collect()
+}
+
+impl Display for PresetChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PresetChange::PassAdded { index, path } => {
+                write!(f, "+ pass {index}: {}", path.display())
+            }
+            PresetChange::PassRemoved { index, path } => {
+                write!(f, "- pass {index}: {}", path.display())
+            }
+            PresetChange::PassChanged {
+                new_index,
+                path,
+                fields,
+                ..
+            } => write!(
+                f,
+                "~ pass {new_index}: {} ({})",
+                path.display(),
+                fields.join(", ")
+            ),
+            PresetChange::ParameterAdded { name, value } => {
+                write!(f, "+ parameter {name} = {value}")
+            }
+            PresetChange::ParameterRemoved { name, value } => {
+                write!(f, "- parameter {name} = {value}")
+            }
+            PresetChange::ParameterChanged { name, old, new } => {
+                write!(f, "~ parameter {name}: {old} -> {new}")
+            }
+            PresetChange::TextureAdded { name, path } => {
+                write!(f, "+ texture {name}: {}", path.display())
+            }
+            PresetChange::TextureRemoved { name, path } => {
+                write!(f, "- texture {name}: {}", path.display())
+            }
+            PresetChange::TextureChanged { name, path, fields } => write!(
+                f,
+                "~ texture {name}: {} ({})",
+                path.display(),
+                fields.join(", ")
+            ),
+        }
+    }
+}
+
+/// Formats the diff as a changelog with one change per line.
+impl Display for PresetDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ParameterMeta, PresetChange, ShaderDefine, ShaderFeatures, ShaderPreset};
+
+    #[test]
+    pub fn diff() {
+        let old = ShaderPreset::try_parse("../test/defines.slangp", ShaderFeatures::NONE).unwrap();
+        assert!(old.diff(&old).is_empty());
+
+        let mut new = old.clone();
+        new.passes[0]
+            .meta
+            .set_define(ShaderDefine::new("CURVATURE", "1"));
+        let mut pass = new.passes[0].clone();
+        pass.path.set_file_name("compute.slang");
+        new.passes.insert(0, pass);
+        new.parameters.push(ParameterMeta {
+            name: "CURVATURE_STRENGTH".into(),
+            value: 0.5,
+        });
+
+        let diff = old.diff(&new);
+        assert!(matches!(
+            &diff.changes[..],
+            [
+                PresetChange::PassAdded { index: 0, .. },
+                PresetChange::PassChanged {
+                    old_index: 0,
+                    new_index: 1,
+                    fields,
+                    ..
+                },
+                PresetChange::ParameterAdded { value: 0.5, .. },
+            ] if fields == &["defines"]
+        ));
+
+        let diff = new.diff(&old);
+        assert!(matches!(
+            &diff.changes[..],
+            [
+                PresetChange::PassRemoved { index: 0, .. },
+                PresetChange::PassChanged { .. },
+                PresetChange::ParameterRemoved { .. },
+            ]
+        ));
+    }
+}
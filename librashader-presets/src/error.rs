@@ -21,15 +21,37 @@ pub enum ParsePresetError {
     /// The preset reference depth exceeded 16.
     #[error("exceeded maximum reference depth (16)")]
     ExceededReferenceDepth,
+    /// A preset references itself, directly or through the presets it references.
+    #[error("the preset {0:?} references itself")]
+    CyclicReference(PathBuf),
     /// An absolute path could not be found to resolve the shader preset against.
     #[error("shader presets must be resolved against an absolute path")]
     RootPathWasNotAbsolute,
     /// An IO error occurred when reading the shader preset.
     #[error("io error on file {0:?}: {1}")]
     IOError(PathBuf, std::io::Error),
-    /// The shader preset did not contain valid UTF-8 bytes.
+    /// A file referenced by the shader preset resides outside of the roots of the sandbox.
+    #[error("the file {0:?} is outside of the sandbox")]
+    PathOutsideSandbox(PathBuf),
+    /// The shader preset could not be decoded as UTF-8, UTF-16 or Latin-1 text.
     #[error("expected utf8 bytes but got invalid utf8")]
     Utf8Error(Vec<u8>),
+    /// The shader preset had a problem that is only a warning outside of strict mode.
+    #[error("{0}")]
+    Strict(ParsePresetWarning),
+}
+
+/// A problem with a shader preset that was ignored when parsing.
+#[derive(Error, Debug, Clone)]
+pub enum ParsePresetWarning {
+    /// The key was not recognized, and its value was ignored.
+    #[error("unknown key `{key}` in {path:?} at line {row}, column {col}")]
+    UnknownKey {
+        path: PathBuf,
+        key: String,
+        row: u32,
+        col: usize,
+    },
 }
 
 /// The kind of error that may occur in parsing.
@@ -45,4 +67,6 @@ pub enum ParseErrorKind {
     Float,
     /// Expected a boolean.
     Bool,
+    /// Expected a list of defines (i.e. `CURVATURE=0;USE_LUT`)
+    Define,
 }
@@ -9,10 +9,15 @@
 //! Re-exported as [`librashader::presets`](https://docs.rs/librashader/latest/librashader/presets/index.html).
 
 pub mod context;
+mod diff;
 mod error;
 mod parse;
 mod preset;
+mod variant;
+mod write;
 
 pub use context::WildcardContext;
+pub use diff::{PresetChange, PresetDiff};
 pub use error::*;
 pub use preset::*;
+pub use variant::VariantMatrix;
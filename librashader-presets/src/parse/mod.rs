@@ -14,7 +14,7 @@ use crate::context::{VideoDriver, WildcardContext};
 use crate::error::ParsePresetError;
 use crate::parse::preset::resolve_values;
 use crate::parse::value::parse_preset;
-use crate::{ShaderFeatures, ShaderPreset};
+use crate::{PathSandbox, ShaderFeatures, ShaderPreset};
 
 pub(crate) fn remove_if<T>(values: &mut Vec<T>, f: impl FnMut(&T) -> bool) -> Option<T> {
     values.iter().position(f).map(|idx| values.remove(idx))
@@ -30,7 +30,7 @@ impl ShaderPreset {
     ) -> Result<ShaderPreset, ParsePresetError> {
         let mut context = WildcardContext::new();
         context.add_path_defaults(path.as_ref());
-        let values = parse_preset(path, context)?;
+        let values = parse_preset(path, context, shader_features, None)?;
         Ok(resolve_values(values, shader_features))
     }
 
@@ -45,7 +45,7 @@ impl ShaderPreset {
         let mut context = WildcardContext::new();
         context.add_path_defaults(path.as_ref());
         context.add_video_driver_defaults(driver);
-        let values = parse_preset(path, context)?;
+        let values = parse_preset(path, context, shader_features, None)?;
         Ok(resolve_values(values, shader_features))
     }
 
@@ -58,9 +58,27 @@ impl ShaderPreset {
         shader_features: ShaderFeatures,
         context: WildcardContext,
     ) -> Result<ShaderPreset, ParsePresetError> {
-        let values = parse_preset(path, context)?;
+        let values = parse_preset(path, context, shader_features, None)?;
         Ok(resolve_values(values, shader_features))
     }
+
+    /// Try to parse the shader preset at the given path, with the exact provided context,
+    /// restricting every file referenced by the preset to the roots of the sandbox.
+    ///
+    /// This should be used to load presets from untrusted sources. Shaders, textures and
+    /// referenced presets are checked when parsing, and includes are checked when the
+    /// shader sources are loaded.
+    pub fn try_parse_with_sandbox(
+        path: impl AsRef<Path>,
+        shader_features: ShaderFeatures,
+        context: WildcardContext,
+        sandbox: PathSandbox,
+    ) -> Result<ShaderPreset, ParsePresetError> {
+        let values = parse_preset(path, context, shader_features, Some(&sandbox))?;
+        let mut preset = resolve_values(values, shader_features);
+        preset.sandbox = Some(sandbox);
+        Ok(preset)
+    }
 }
 
 #[cfg(test)]
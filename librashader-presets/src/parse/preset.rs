@@ -1,9 +1,11 @@
+use crate::error::ParsePresetWarning;
 use crate::parse::remove_if;
 use crate::parse::value::Value;
 use crate::{
     ParameterMeta, PassConfig, PassMeta, Scale2D, Scaling, ShaderFeatures, ShaderPreset,
     TextureConfig, TextureMeta,
 };
+use std::path::PathBuf;
 use vec_extract_if_polyfill::MakeExtractIf;
 
 pub fn resolve_values(mut values: Vec<Value>, features: ShaderFeatures) -> ShaderPreset {
@@ -15,6 +17,7 @@ pub fn resolve_values(mut values: Vec<Value>, features: ShaderFeatures) -> Shade
                     filter_mode,
                     wrap_mode,
                     mipmap,
+                    srgb,
                     path,
                 } = value
                 {
@@ -25,6 +28,7 @@ pub fn resolve_values(mut values: Vec<Value>, features: ShaderFeatures) -> Shade
                             wrap_mode,
                             filter_mode,
                             mipmap,
+                            srgb,
                         },
                     }
                 } else {
@@ -43,6 +47,28 @@ pub fn resolve_values(mut values: Vec<Value>, features: ShaderFeatures) -> Shade
             })
             .collect();
 
+    let warnings: Vec<ParsePresetWarning> =
+        MakeExtractIf::extract_if(&mut values, |f| matches!(*f, Value::Warning(_)))
+            .map(|value| {
+                if let Value::Warning(warning) = value {
+                    warning
+                } else {
+                    unreachable!("values should be all of type warning")
+                }
+            })
+            .collect();
+
+    let references: Vec<PathBuf> =
+        MakeExtractIf::extract_if(&mut values, |f| matches!(*f, Value::Reference(_)))
+            .map(|value| {
+                if let Value::Reference(path) = value {
+                    path
+                } else {
+                    unreachable!("values should be all of type reference")
+                }
+            })
+            .collect();
+
     let mut shaders = Vec::new();
     let shader_count =
         remove_if(&mut values, |v| matches!(*v, Value::ShaderCount(_))).map_or(0, |value| {
@@ -168,6 +194,13 @@ pub fn resolve_values(mut values: Vec<Value>, features: ShaderFeatures) -> Shade
                             _ => None,
                         })
                         .unwrap_or(false),
+                    defines: shader_values
+                        .iter()
+                        .find_map(|f| match f {
+                            Value::Defines(_, value) => Some(value.clone()),
+                            _ => None,
+                        })
+                        .unwrap_or_default(),
                     scaling: Scale2D {
                         valid: scale_valid,
                         x: Scaling {
@@ -194,5 +227,8 @@ pub fn resolve_values(mut values: Vec<Value>, features: ShaderFeatures) -> Shade
         textures,
         parameters,
         features,
+        references,
+        sandbox: None,
+        warnings,
     }
 }
@@ -1,19 +1,22 @@
-use crate::error::{ParseErrorKind, ParsePresetError};
+use crate::error::{ParseErrorKind, ParsePresetError, ParsePresetWarning};
 use crate::parse::{remove_if, Span, Token};
-use crate::{ScaleFactor, ScaleType};
+use crate::{ScaleFactor, ScaleType, ShaderDefine, ShaderFeatures};
 use nom::bytes::complete::tag;
 use nom::character::complete::digit1;
 use nom::combinator::{eof, map_res};
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 
 use nom::IResult;
 use num_traits::cast::ToPrimitive;
 
 use crate::parse::token::do_lex;
 use librashader_common::map::{FastHashMap, ShortString};
+use librashader_common::sandbox::PathSandbox;
+use librashader_common::text::decode_text;
 use librashader_common::{FilterMode, WrapMode};
 use std::fs::File;
 use std::io::Read;
+use std::num::ParseFloatError;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
@@ -38,14 +41,18 @@ pub enum Value {
     SrgbFramebuffer(i32, bool),
     MipmapInput(i32, bool),
     Alias(i32, ShortString),
+    Defines(i32, Vec<ShaderDefine>),
     Parameter(ShortString, f32),
     Texture {
         name: ShortString,
         filter_mode: FilterMode,
         wrap_mode: WrapMode,
         mipmap: bool,
+        srgb: bool,
         path: PathBuf,
     },
+    Warning(ParsePresetWarning),
+    Reference(PathBuf),
 }
 
 impl Value {
@@ -65,6 +72,7 @@ impl Value {
             Value::SrgbFramebuffer(i, _) => Some(*i),
             Value::MipmapInput(i, _) => Some(*i),
             Value::Alias(i, _) => Some(*i),
+            Value::Defines(i, _) => Some(*i),
             _ => None,
         }
     }
@@ -84,7 +92,7 @@ fn from_int(input: Span) -> Result<i32, ParsePresetError> {
         })
         .or_else(|e| {
             // An even more egregious ✨CRIME✨ is using a float as a shader index.
-            let result = f32::from_str(to_parse).map_err(|_| e)?;
+            let result = parse_float(to_parse).map_err(|_| e)?;
             let result = result
                 .trunc()
                 .to_i32()
@@ -110,11 +118,22 @@ fn from_ul(input: Span) -> Result<u32, ParsePresetError> {
     })
 }
 
+// Parsing floats with `f32::from_str` does not depend on the system locale, but presets
+// saved by frontends running in some locales use `,` as the decimal separator.
+fn parse_float(input: &str) -> Result<f32, ParseFloatError> {
+    f32::from_str(input).or_else(|e| match input.split_once(',') {
+        Some((whole, fraction)) if !whole.contains('.') && !fraction.contains([',', '.']) => {
+            f32::from_str(&format!("{whole}.{fraction}")).map_err(|_| e)
+        }
+        _ => Err(e),
+    })
+}
+
 fn from_float(input: Span) -> Result<f32, ParsePresetError> {
     // Presets like to commit ✨CRIMES✨ and end their lines with a ";".
     // It's too hard to put this in the lexer because we can't tell between
     // semicolon crimes or a valid param/texture name listing.
-    f32::from_str(input.trim().trim_end_matches(";")).map_err(|_| ParsePresetError::ParserError {
+    parse_float(input.trim().trim_end_matches(";")).map_err(|_| ParsePresetError::ParserError {
         offset: input.location_offset(),
         row: input.location_line(),
         col: input.get_column(),
@@ -143,6 +162,28 @@ fn from_bool(input: Span) -> Result<bool, ParsePresetError> {
     })
 }
 
+// Defines are separated by ";", and take the form "NAME" or "NAME=VALUE".
+fn from_defines(input: Span) -> Result<Vec<ShaderDefine>, ParsePresetError> {
+    input
+        .split(';')
+        .map(str::trim)
+        .filter(|define| !define.is_empty())
+        .map(|define| {
+            let (name, value) = define.split_once('=').unwrap_or((define, ""));
+            let define = ShaderDefine::new(name.trim(), value.trim());
+            if !define.is_valid() {
+                return Err(ParsePresetError::ParserError {
+                    offset: input.location_offset(),
+                    row: input.location_line(),
+                    col: input.get_column(),
+                    kind: ParseErrorKind::Define,
+                });
+            }
+            Ok(define)
+        })
+        .collect()
+}
+
 fn parse_indexed_key<'a>(key: &'static str, input: Span<'a>) -> IResult<Span<'a>, i32> {
     let (input, _) = tag(key)(input)?;
     let (input, idx) = map_res(digit1, from_int)(input)?;
@@ -152,20 +193,33 @@ fn parse_indexed_key<'a>(key: &'static str, input: Span<'a>) -> IResult<Span<'a>
 
 pub const SHADER_MAX_REFERENCE_DEPTH: usize = 16;
 
+/// The directories and contents of referenced presets.
+type ReferenceStrings = Vec<(PathBuf, String)>;
+
 // prereq: root_path must be contextualized
 fn load_child_reference_strings(
     root_references: Vec<PathBuf>,
     root_path: impl AsRef<Path>,
+    root_file: Option<PathBuf>,
     context: &FastHashMap<String, String>,
-) -> Result<Vec<(PathBuf, String)>, ParsePresetError> {
+    sandbox: Option<&PathSandbox>,
+) -> Result<(ReferenceStrings, Vec<PathBuf>), ParsePresetError> {
     let root_path = root_path.as_ref();
 
     let mut reference_depth = 0;
     let mut reference_strings: VecDeque<(PathBuf, String)> = VecDeque::new();
-    let root_references = vec![(root_path.to_path_buf(), root_references)];
+    let mut reference_files = Vec::new();
+    // every root keeps the chain of presets that led to it, to detect presets that reference themselves.
+    let root_file = root_file.and_then(|file| file.canonicalize().ok());
+    let root_references = vec![(
+        root_path.to_path_buf(),
+        root_references,
+        Vec::from_iter(root_file),
+    )];
     let mut root_references = VecDeque::from(root_references);
     // search needs to be depth first to allow for overrides.
-    while let Some((mut reference_root, referenced_paths)) = root_references.pop_front() {
+    while let Some((mut reference_root, referenced_paths, ancestors)) = root_references.pop_front()
+    {
         if reference_depth > SHADER_MAX_REFERENCE_DEPTH {
             return Err(ParsePresetError::ExceededReferenceDepth);
         }
@@ -187,12 +241,12 @@ fn load_child_reference_strings(
             let mut path = path
                 .canonicalize()
                 .map_err(|e| ParsePresetError::IOError(path.clone(), e))?;
+            check_sandbox(&path, sandbox)?;
+            if ancestors.contains(&path) {
+                return Err(ParsePresetError::CyclicReference(path));
+            }
             // println!("Opening {:?}", path);
-            let mut reference_contents = String::new();
-            File::open(&path)
-                .map_err(|e| ParsePresetError::IOError(path.clone(), e))?
-                .read_to_string(&mut reference_contents)
-                .map_err(|e| ParsePresetError::IOError(path.clone(), e))?;
+            let reference_contents = read_preset(&path)?;
 
             let mut new_tokens = do_lex(&reference_contents)?;
             let new_references: Vec<PathBuf> =
@@ -202,20 +256,26 @@ fn load_child_reference_strings(
                 .map(|value| PathBuf::from(*value.value.fragment()))
                 .collect();
 
+            let mut path_ancestors = ancestors.clone();
+            path_ancestors.push(path.clone());
+            reference_files.push(path.clone());
+
             path.pop();
             reference_strings.push_front((path.clone(), reference_contents));
             if !new_references.is_empty() {
-                root_references.push_front((path, new_references));
+                root_references.push_front((path, new_references, path_ancestors));
             }
         }
     }
 
-    Ok(reference_strings.into())
+    Ok((reference_strings.into(), reference_files))
 }
 
 pub(crate) fn parse_preset(
     path: impl AsRef<Path>,
     context: WildcardContext,
+    features: ShaderFeatures,
+    sandbox: Option<&PathSandbox>,
 ) -> Result<Vec<Value>, ParsePresetError> {
     let path = path.as_ref();
     let mut path = path.to_path_buf();
@@ -226,14 +286,43 @@ pub(crate) fn parse_preset(
     let path = path
         .canonicalize()
         .map_err(|e| ParsePresetError::IOError(path.to_path_buf(), e))?;
+    check_sandbox(&path, sandbox)?;
 
-    let mut contents = String::new();
-    File::open(&path)
-        .and_then(|mut f| f.read_to_string(&mut contents))
-        .map_err(|e| ParsePresetError::IOError(path.to_path_buf(), e))?;
+    let contents = read_preset(&path)?;
 
     let tokens = super::token::do_lex(&contents)?;
-    parse_values(tokens, path, context)
+    parse_values(tokens, path, context, features, sandbox)
+}
+
+fn read_preset(path: &Path) -> Result<String, ParsePresetError> {
+    let mut buf = Vec::new();
+    File::open(path)
+        .and_then(|mut f| f.read_to_end(&mut buf))
+        .map_err(|e| ParsePresetError::IOError(path.to_path_buf(), e))?;
+
+    decode_text(buf).map_err(ParsePresetError::Utf8Error)
+}
+
+fn check_sandbox(path: &Path, sandbox: Option<&PathSandbox>) -> Result<(), ParsePresetError> {
+    if sandbox.is_some_and(|sandbox| !sandbox.contains(path)) {
+        return Err(ParsePresetError::PathOutsideSandbox(path.to_path_buf()));
+    }
+    Ok(())
+}
+
+// Missing textures are allowed if they will be substituted when the preset is loaded.
+fn check_texture_path(
+    path: &Path,
+    features: ShaderFeatures,
+    sandbox: Option<&PathSandbox>,
+) -> Result<(), ParsePresetError> {
+    check_sandbox(path, sandbox)?;
+    match path.canonicalize() {
+        Err(e) if !features.contains(ShaderFeatures::FALLBACK_TEXTURES) => {
+            Err(ParsePresetError::IOError(path.to_path_buf(), e))
+        }
+        _ => Ok(()),
+    }
 }
 
 // prereq: root_path must be contextualized
@@ -241,11 +330,14 @@ pub fn parse_values(
     mut tokens: Vec<Token>,
     root_path: impl AsRef<Path>,
     context: FastHashMap<String, String>,
+    features: ShaderFeatures,
+    sandbox: Option<&PathSandbox>,
 ) -> Result<Vec<Value>, ParsePresetError> {
     let mut root_path = root_path.as_ref().to_path_buf();
     if root_path.is_relative() {
         return Err(ParsePresetError::RootPathWasNotAbsolute);
     }
+    let root_file = root_path.is_file().then(|| root_path.clone());
     if !root_path.is_dir() {
         // we don't really care if this doesn't do anything because a non-canonical root path will
         // fail at a later stage during resolution.
@@ -260,7 +352,8 @@ pub fn parse_values(
     // unfortunately we need to lex twice because there's no way to know the references ahead of time.
     // the returned references should have context applied
 
-    let child_strings = load_child_reference_strings(references, &root_path, &context)?;
+    let (child_strings, reference_files) =
+        load_child_reference_strings(references, &root_path, root_file, &context, sandbox)?;
     let mut all_tokens: Vec<(&Path, Vec<Token>)> = Vec::new();
 
     for (path, string) in child_strings.iter() {
@@ -299,7 +392,21 @@ pub fn parse_values(
         }
     }
 
-    let mut values = Vec::new();
+    // later presets override the keys of the presets they reference, and later keys override
+    // earlier keys in the same preset, like in RetroArch. the names of parameters and textures
+    // were already collected from every preset.
+    let mut seen_keys = HashSet::new();
+    for (_, tokens) in all_tokens.iter_mut().rev() {
+        let keep: Vec<bool> = tokens
+            .iter()
+            .rev()
+            .map(|token| seen_keys.insert(token.key.fragment().trim()))
+            .collect();
+        let mut keep = keep.into_iter().rev();
+        tokens.retain(|_| keep.next().unwrap_or(true));
+    }
+
+    let mut values: Vec<Value> = reference_files.into_iter().map(Value::Reference).collect();
     // resolve shader paths.
     for (path, tokens) in all_tokens.iter_mut() {
         for token in MakeExtractIf::extract_if(tokens, |token| {
@@ -328,6 +435,7 @@ pub fn parse_values(
             relative_path
                 .canonicalize()
                 .map_err(|e| ParsePresetError::IOError(relative_path.clone(), e))?;
+            check_sandbox(&relative_path, sandbox)?;
             values.push(Value::Shader(index, relative_path))
         }
     }
@@ -341,9 +449,7 @@ pub fn parse_values(
             let mut relative_path = path.to_path_buf();
             // Don't trim paths
             relative_path.push(*token.value.fragment());
-            relative_path
-                .canonicalize()
-                .map_err(|e| ParsePresetError::IOError(relative_path.clone(), e))?;
+            check_texture_path(&relative_path, features, sandbox)?;
             textures.push((token.key, relative_path))
         }
     }
@@ -368,6 +474,13 @@ pub fn parse_values(
         })
         .map_or_else(|| Ok(false), |(_, v)| from_bool(v.value))?;
 
+        let srgb = remove_if(&mut tokens, |(_, t)| {
+            t.key.starts_with(*texture)
+                && t.key.ends_with("_srgb")
+                && t.key.len() == texture.len() + "_srgb".len()
+        })
+        .map_or_else(|| Ok(false), |(_, v)| from_bool(v.value))?;
+
         let wrap_mode = remove_if(&mut tokens, |(_, t)| {
             t.key.starts_with(*texture)
                 && (t.key.ends_with("_wrap_mode") || t.key.ends_with("_repeat_mode"))
@@ -398,6 +511,7 @@ pub fn parse_values(
             }),
             wrap_mode,
             mipmap,
+            srgb,
             path,
         })
     }
@@ -500,6 +614,11 @@ pub fn parse_values(
             ));
             continue;
         }
+
+        if let Ok((_, idx)) = parse_indexed_key("defines", token.key) {
+            values.push(Value::Defines(idx, from_defines(token.value)?));
+            continue;
+        }
         if let Ok((_, idx)) = parse_indexed_key("scale_type", token.key) {
             let scale_type = ScaleType::from_str(token.value.trim())?;
             values.push(Value::ScaleType(idx, scale_type));
@@ -519,6 +638,7 @@ pub fn parse_values(
     }
 
     let mut undeclared_textures = Vec::new();
+    let mut unknown_keys = Vec::new();
     for (path, token) in &rest_tokens {
         if let Ok((_, idx)) = parse_indexed_key("scale", token.key) {
             let scale = if values.iter().any(|t| matches!(*t, Value::ScaleType(match_idx, ScaleType::Absolute) if match_idx == idx)) {
@@ -566,20 +686,42 @@ pub fn parse_values(
         }
         // very last resort, assume undeclared texture (must have extension)
         else if Path::new(token.value.fragment()).extension().is_some()
-            && ["_mipmap", "_linear", "_wrap_mode", "_repeat_mode"]
+            && ["_mipmap", "_linear", "_srgb", "_wrap_mode", "_repeat_mode"]
                 .iter()
                 .all(|k| !token.key.ends_with(k))
         {
             let mut relative_path = path.to_path_buf();
             // Don't trim paths.
             relative_path.push(*token.value.fragment());
-            relative_path
-                .canonicalize()
-                .map_err(|e| ParsePresetError::IOError(relative_path.clone(), e))?;
+            check_texture_path(&relative_path, features, sandbox)?;
             undeclared_textures.push((token.key, relative_path));
         }
-
         // we tried our best
+        else {
+            unknown_keys.push((*path, token.key));
+        }
+    }
+
+    // Options of undeclared textures are handled below, so they are not unknown.
+    unknown_keys.retain(|(_, key)| {
+        !undeclared_textures.iter().any(|(texture, _)| {
+            key.strip_prefix(*texture.fragment()).is_some_and(|suffix| {
+                ["_mipmap", "_linear", "_srgb", "_wrap_mode", "_repeat_mode"].contains(&suffix)
+            })
+        })
+    });
+
+    for (path, key) in unknown_keys {
+        let warning = ParsePresetWarning::UnknownKey {
+            path: path.to_path_buf(),
+            key: key.fragment().trim().to_string(),
+            row: key.location_line(),
+            col: key.get_column(),
+        };
+        if features.contains(ShaderFeatures::STRICT_PRESET) {
+            return Err(ParsePresetError::Strict(warning));
+        }
+        values.push(Value::Warning(warning));
     }
 
     // Since there are undeclared textures we need to deal with potential mipmap information.
@@ -598,6 +740,13 @@ pub fn parse_values(
         })
         .map_or_else(|| Ok(false), |(_, v)| from_bool(v.value))?;
 
+        let srgb = remove_if(&mut rest_tokens, |(_, t)| {
+            t.key.starts_with(*texture)
+                && t.key.ends_with("_srgb")
+                && t.key.len() == texture.len() + "_srgb".len()
+        })
+        .map_or_else(|| Ok(false), |(_, v)| from_bool(v.value))?;
+
         let wrap_mode = remove_if(&mut rest_tokens, |(_, t)| {
             t.key.starts_with(*texture)
                 && (t.key.ends_with("_wrap_mode") || t.key.ends_with("_repeat_mode"))
@@ -618,6 +767,7 @@ pub fn parse_values(
             },
             wrap_mode,
             mipmap,
+            srgb,
             path,
         })
     }
@@ -628,16 +778,97 @@ pub fn parse_values(
 
 #[cfg(test)]
 mod test {
-    use crate::parse::value::parse_preset;
-    use crate::WildcardContext;
+    use crate::parse::value::{parse_float, parse_preset};
+    use crate::{
+        ParsePresetError, ParsePresetWarning, ShaderDefine, ShaderFeatures, ShaderPreset,
+        WildcardContext,
+    };
+    use librashader_common::FilterMode;
     use std::path::PathBuf;
 
     #[test]
     pub fn parse_basic() {
         let root =
             PathBuf::from("../test/shaders_slang/bezel/Mega_Bezel/Presets/Base_CRT_Presets/MBZ__3__STD__MEGATRON-NTSC.slangp");
-        let basic = parse_preset(root, WildcardContext::new());
+        let basic = parse_preset(root, WildcardContext::new(), ShaderFeatures::NONE, None);
         eprintln!("{basic:?}");
         assert!(basic.is_ok());
     }
+
+    #[test]
+    pub fn parse_float_separators() {
+        assert_eq!(parse_float("1.5"), Ok(1.5));
+        assert_eq!(parse_float("1,5"), Ok(1.5));
+        assert_eq!(parse_float("-0,25"), Ok(-0.25));
+        assert_eq!(parse_float(",5"), Ok(0.5));
+        assert!(parse_float("1,5,0").is_err());
+        assert!(parse_float("1.5,0").is_err());
+    }
+
+    #[test]
+    pub fn parse_unknown_key() {
+        let root = PathBuf::from("../test/unknown_key.slangp");
+        let preset = ShaderPreset::try_parse(&root, ShaderFeatures::NONE).unwrap();
+        assert!(matches!(
+            &preset.warnings[..],
+            [ParsePresetWarning::UnknownKey { key, row: 3, .. }] if key == "filter_lienar0"
+        ));
+
+        let strict = ShaderPreset::try_parse(&root, ShaderFeatures::STRICT_PRESET);
+        assert!(matches!(strict, Err(ParsePresetError::Strict(_))));
+    }
+
+    #[test]
+    pub fn parse_cyclic_reference() {
+        let root = PathBuf::from("../test/reference_cycle_a.slangp");
+        let preset = ShaderPreset::try_parse(&root, ShaderFeatures::NONE);
+        assert!(matches!(
+            preset,
+            Err(ParsePresetError::CyclicReference(path)) if path.ends_with("reference_cycle_a.slangp")
+        ));
+    }
+
+    #[test]
+    pub fn parse_references() {
+        let root = PathBuf::from("../test/reference.slangp");
+        let preset = ShaderPreset::try_parse(&root, ShaderFeatures::NONE).unwrap();
+        assert_eq!(preset.references.len(), 1);
+        assert!(preset.references[0].ends_with("null.slangp"));
+    }
+
+    #[test]
+    pub fn parse_reference_overrides() {
+        let root = PathBuf::from("../test/reference_override.slangp");
+        let preset = ShaderPreset::try_parse(&root, ShaderFeatures::STRICT_PRESET).unwrap();
+        assert_eq!(preset.passes.len(), 1);
+
+        let parameters: Vec<_> = preset
+            .parameters
+            .iter()
+            .map(|param| (param.name.as_str(), param.value))
+            .collect();
+        assert_eq!(parameters, [("A", 1.0), ("B", 2.0)]);
+
+        assert_eq!(preset.textures.len(), 2);
+        let lut = preset
+            .textures
+            .iter()
+            .find(|texture| texture.meta.name == "LUT")
+            .unwrap();
+        assert!(lut.path.ends_with("advwars.jpg"));
+        assert_eq!(lut.meta.filter_mode, FilterMode::Linear);
+    }
+
+    #[test]
+    pub fn parse_defines() {
+        let root = PathBuf::from("../test/defines.slangp");
+        let preset = ShaderPreset::try_parse(&root, ShaderFeatures::STRICT_PRESET).unwrap();
+        assert_eq!(
+            preset.passes[0].meta.defines,
+            [
+                ShaderDefine::new("CURVATURE", "0"),
+                ShaderDefine::new("USE_LUT", "")
+            ]
+        );
+    }
 }
@@ -1,11 +1,12 @@
-use crate::error::ParsePresetError;
+use crate::error::{ParsePresetError, ParsePresetWarning};
 use librashader_common::map::ShortString;
 use librashader_common::{FilterMode, ImageFormat, WrapMode};
 use std::ops::Mul;
 use std::path::PathBuf;
 use std::str::FromStr;
 
-pub use librashader_common::shader_features::ShaderFeatures;
+pub use librashader_common::sandbox::PathSandbox;
+pub use librashader_common::shader_features::{ShaderDefine, ShaderFeatures};
 
 /// The configuration for a single shader pass.
 pub type PassConfig = PathReference<PassMeta>;
@@ -45,6 +46,12 @@ pub struct PassMeta {
     pub mipmap_input: bool,
     /// Specifies the scaling of the output framebuffer for this shader pass.
     pub scaling: Scale2D,
+    /// Preprocessor defines to inject into the source of this shader pass.
+    ///
+    /// Defines are declared in presets with `defines0 = "CURVATURE=0;USE_LUT"`, and
+    /// frontends may add their own before creating a filter chain.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub defines: Vec<ShaderDefine>,
 }
 
 impl PassMeta {
@@ -60,6 +67,15 @@ impl PassMeta {
         None
     }
 
+    /// Set a define to inject into the source of this shader pass, replacing any define
+    /// with the same name.
+    pub fn set_define(&mut self, define: ShaderDefine) {
+        match self.defines.iter_mut().find(|d| d.name == define.name) {
+            Some(existing) => *existing = define,
+            None => self.defines.push(define),
+        }
+    }
+
     #[inline(always)]
     pub fn get_frame_count(&self, count: usize) -> u32 {
         (if self.frame_count_mod > 0 {
@@ -71,7 +87,7 @@ impl PassMeta {
 }
 
 #[repr(i32)]
-#[derive(Default, Copy, Clone, Debug)]
+#[derive(Default, Copy, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The scaling type for the shader pass.
 pub enum ScaleType {
@@ -84,10 +100,20 @@ pub enum ScaleType {
     Viewport,
     /// Scale by the size of the original input quad.
     Original,
+    /// Scale by the size of the largest rectangle with the aspect ratio of the original
+    /// input quad that fits in the viewport.
+    ViewportAspect,
+    /// Scale by the size of the other axis, corrected by the aspect ratio of the original
+    /// input quad.
+    ///
+    /// This is used with an absolute or viewport scale on the other axis to keep the aspect
+    /// ratio of the original image. If both axes use this scale type, the framebuffer is
+    /// scaled by the size of the original input quad.
+    OriginalAspect,
 }
 
 /// The scaling factor for framebuffer scaling.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScaleFactor {
     /// Scale by a fractional float factor.
@@ -128,7 +154,7 @@ impl Mul<ScaleFactor> for u32 {
     fn mul(self, rhs: ScaleFactor) -> Self::Output {
         match rhs {
             ScaleFactor::Float(f) => f * self as f32,
-            ScaleFactor::Absolute(f) => (f as u32 * self) as f32,
+            ScaleFactor::Absolute(f) => f as f32 * self as f32,
         }
     }
 }
@@ -142,13 +168,15 @@ impl FromStr for ScaleType {
             "viewport" => Ok(ScaleType::Viewport),
             "absolute" => Ok(ScaleType::Absolute),
             "original" => Ok(ScaleType::Original),
+            "viewport_aspect" => Ok(ScaleType::ViewportAspect),
+            "original_aspect" => Ok(ScaleType::OriginalAspect),
             _ => Err(ParsePresetError::InvalidScaleType(s.to_string())),
         }
     }
 }
 
 /// Framebuffer scaling parameters.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Scaling {
     /// The method to scale the framebuffer with.
@@ -158,7 +186,7 @@ pub struct Scaling {
 }
 
 /// 2D quad scaling parameters.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Scale2D {
     /// Whether or not this combination of scaling factors is valid.
@@ -181,6 +209,12 @@ pub struct TextureMeta {
     pub filter_mode: FilterMode,
     /// Whether to generate mipmaps for this texture.
     pub mipmap: bool,
+    /// Whether the texture data is sRGB encoded.
+    ///
+    /// If true, the texture is sampled through an sRGB view, so filtering happens in linear
+    /// space and the shader receives linear values. Otherwise, the texture is sampled as-is.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub srgb: bool,
 }
 
 /// Configuration options for a shader parameter.
@@ -219,4 +253,22 @@ pub struct ShaderPreset {
 
     /// Shader features to enable.
     pub features: ShaderFeatures,
+
+    /// The paths of the presets included with `#reference`, directly or through other
+    /// referenced presets.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub references: Vec<PathBuf>,
+
+    /// The sandbox that files referenced by the preset are restricted to, if any.
+    ///
+    /// Includes are resolved when the shader sources are loaded, so the sandbox is
+    /// kept to be checked against at that point.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub sandbox: Option<PathSandbox>,
+
+    /// Problems with the preset that were ignored when parsing, such as unknown keys.
+    ///
+    /// Parse the preset with [`ShaderFeatures::STRICT_PRESET`] to fail on these instead.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub warnings: Vec<ParsePresetWarning>,
 }
@@ -0,0 +1,116 @@
+//! Matrices of shader variants that differ by their injected defines.
+use crate::{ShaderDefine, ShaderPreset};
+use librashader_common::map::ShortString;
+
+/// A matrix of shader variants of a preset, declared as a set of defines and the
+/// values each define can take.
+///
+/// The variants of a preset are every combination of the declared values. For example,
+/// declaring `CURVATURE` with the values `0` and `1`, and `MASK_TYPE` with the values
+/// `0`, `1` and `2` results in six variants.
+#[derive(Debug, Clone, Default)]
+pub struct VariantMatrix {
+    axes: Vec<VariantAxis>,
+}
+
+#[derive(Debug, Clone)]
+struct VariantAxis {
+    pass: Option<usize>,
+    name: ShortString,
+    values: Vec<String>,
+}
+
+impl VariantMatrix {
+    /// Create an empty matrix, which only has the preset itself as a variant.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a define that is injected into every pass, with the values it can take.
+    pub fn define(
+        &mut self,
+        name: impl Into<ShortString>,
+        values: impl IntoIterator<Item = impl Into<String>>,
+    ) {
+        self.axes.push(VariantAxis {
+            pass: None,
+            name: name.into(),
+            values: values.into_iter().map(Into::into).collect(),
+        });
+    }
+
+    /// Declare a define that is injected into the pass at the given index, with the
+    /// values it can take.
+    pub fn define_pass(
+        &mut self,
+        pass: usize,
+        name: impl Into<ShortString>,
+        values: impl IntoIterator<Item = impl Into<String>>,
+    ) {
+        self.axes.push(VariantAxis {
+            pass: Some(pass),
+            name: name.into(),
+            values: values.into_iter().map(Into::into).collect(),
+        });
+    }
+
+    /// The number of variants in the matrix.
+    ///
+    /// If a define was declared without any values, the matrix has no variants.
+    pub fn variant_count(&self) -> usize {
+        self.axes.iter().map(|axis| axis.values.len()).product()
+    }
+
+    /// Get every variant of the preset in the matrix.
+    ///
+    /// Each variant is a copy of the preset with the defines of the variant set on its passes,
+    /// replacing any define with the same name declared by the preset.
+    pub fn variants<'a>(
+        &'a self,
+        preset: &'a ShaderPreset,
+    ) -> impl Iterator<Item = ShaderPreset> + 'a {
+        (0..self.variant_count()).map(move |mut index| {
+            let mut variant = preset.clone();
+            for axis in &self.axes {
+                let value = &axis.values[index % axis.values.len()];
+                index /= axis.values.len();
+
+                for (pass_index, pass) in variant.passes.iter_mut().enumerate() {
+                    if axis.pass.is_some_and(|p| p != pass_index) {
+                        continue;
+                    }
+                    pass.meta
+                        .set_define(ShaderDefine::new(axis.name.clone(), value.clone()));
+                }
+            }
+            variant
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ShaderDefine, ShaderFeatures, ShaderPreset, VariantMatrix};
+
+    #[test]
+    pub fn variants() {
+        let preset =
+            ShaderPreset::try_parse("../test/defines.slangp", ShaderFeatures::NONE).unwrap();
+
+        let mut matrix = VariantMatrix::new();
+        matrix.define("CURVATURE", ["0", "1"]);
+        matrix.define_pass(0, "MASK_TYPE", ["0", "1", "2"]);
+        assert_eq!(matrix.variant_count(), 6);
+
+        let variants: Vec<_> = matrix.variants(&preset).collect();
+        assert_eq!(variants.len(), 6);
+        assert_eq!(
+            variants[5].passes[0].meta.defines,
+            [
+                ShaderDefine::new("CURVATURE", "1"),
+                ShaderDefine::new("USE_LUT", ""),
+                ShaderDefine::new("MASK_TYPE", "2"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Serialization of shader presets back to `.slangp` syntax.
+use crate::{ScaleFactor, ScaleType, Scaling, ShaderPreset};
+use librashader_common::{FilterMode, WrapMode};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+fn wrap_mode(wrap_mode: WrapMode) -> &'static str {
+    match wrap_mode {
+        WrapMode::ClampToBorder => "clamp_to_border",
+        WrapMode::ClampToEdge => "clamp_to_edge",
+        WrapMode::Repeat => "repeat",
+        WrapMode::MirroredRepeat => "mirrored_repeat",
+    }
+}
+
+fn scale_type(scale_type: ScaleType) -> &'static str {
+    match scale_type {
+        ScaleType::Input => "source",
+        ScaleType::Absolute => "absolute",
+        ScaleType::Viewport => "viewport",
+        ScaleType::Original => "original",
+        ScaleType::ViewportAspect => "viewport_aspect",
+        ScaleType::OriginalAspect => "original_aspect",
+    }
+}
+
+fn scale_factor(factor: ScaleFactor) -> String {
+    match factor {
+        ScaleFactor::Float(factor) => factor.to_string(),
+        ScaleFactor::Absolute(factor) => factor.to_string(),
+    }
+}
+
+fn write_scaling(
+    f: &mut Formatter<'_>,
+    index: usize,
+    suffix: &str,
+    scaling: &Scaling,
+) -> std::fmt::Result {
+    writeln!(
+        f,
+        "scale_type{suffix}{index} = \"{}\"",
+        scale_type(scaling.scale_type)
+    )?;
+    writeln!(
+        f,
+        "scale{suffix}{index} = \"{}\"",
+        scale_factor(scaling.factor)
+    )
+}
+
+// Relative paths are relative to the working directory, not to the written preset.
+fn path(path: &Path) -> String {
+    std::path::absolute(path)
+        .as_deref()
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+impl ShaderPreset {
+    /// Write the preset in `.slangp` syntax to the given writer.
+    ///
+    /// The paths of passes and textures are written as absolute paths, so the written preset
+    /// can be placed in any directory. Keys of referenced presets are written inline, so the
+    /// written preset does not contain `#reference` directives. The shader features and
+    /// sandbox of the preset are not part of the preset syntax, and are not written.
+    pub fn write_to(&self, mut writer: impl std::io::Write) -> std::io::Result<()> {
+        write!(writer, "{self}")
+    }
+}
+
+/// Formats the preset in `.slangp` syntax, as written by [`ShaderPreset::write_to`].
+impl Display for ShaderPreset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "shaders = \"{}\"", self.passes.len())?;
+        #[cfg(feature = "parse_legacy_glsl")]
+        if self.feedback_pass >= 0 {
+            writeln!(f, "feedback_pass = \"{}\"", self.feedback_pass)?;
+        }
+
+        for (index, pass) in self.passes.iter().enumerate() {
+            let meta = &pass.meta;
+            writeln!(f)?;
+            writeln!(f, "shader{index} = \"{}\"", path(&pass.path))?;
+            if let Some(alias) = &meta.alias {
+                writeln!(f, "alias{index} = \"{alias}\"")?;
+            }
+            writeln!(
+                f,
+                "filter_linear{index} = \"{}\"",
+                meta.filter == FilterMode::Linear
+            )?;
+            writeln!(f, "wrap_mode{index} = \"{}\"", wrap_mode(meta.wrap_mode))?;
+            if meta.frame_count_mod != 0 {
+                writeln!(f, "frame_count_mod{index} = \"{}\"", meta.frame_count_mod)?;
+            }
+            writeln!(f, "srgb_framebuffer{index} = \"{}\"", meta.srgb_framebuffer)?;
+            writeln!(
+                f,
+                "float_framebuffer{index} = \"{}\"",
+                meta.float_framebuffer
+            )?;
+            writeln!(f, "mipmap_input{index} = \"{}\"", meta.mipmap_input)?;
+
+            if meta.scaling.valid {
+                if meta.scaling.x == meta.scaling.y {
+                    write_scaling(f, index, "", &meta.scaling.x)?;
+                } else {
+                    write_scaling(f, index, "_x", &meta.scaling.x)?;
+                    write_scaling(f, index, "_y", &meta.scaling.y)?;
+                }
+            }
+
+            if !meta.defines.is_empty() {
+                let defines: Vec<String> = meta
+                    .defines
+                    .iter()
+                    .map(|define| {
+                        if define.value.is_empty() {
+                            define.name.to_string()
+                        } else {
+                            format!("{}={}", define.name, define.value)
+                        }
+                    })
+                    .collect();
+                writeln!(f, "defines{index} = \"{}\"", defines.join(";"))?;
+            }
+        }
+
+        if !self.textures.is_empty() {
+            let names: Vec<&str> = self
+                .textures
+                .iter()
+                .map(|texture| texture.meta.name.as_str())
+                .collect();
+            writeln!(f)?;
+            writeln!(f, "textures = \"{}\"", names.join(";"))?;
+            for texture in &self.textures {
+                let meta = &texture.meta;
+                writeln!(f, "{} = \"{}\"", meta.name, path(&texture.path))?;
+                writeln!(
+                    f,
+                    "{}_linear = \"{}\"",
+                    meta.name,
+                    meta.filter_mode == FilterMode::Linear
+                )?;
+                writeln!(
+                    f,
+                    "{}_wrap_mode = \"{}\"",
+                    meta.name,
+                    wrap_mode(meta.wrap_mode)
+                )?;
+                writeln!(f, "{}_mipmap = \"{}\"", meta.name, meta.mipmap)?;
+                writeln!(f, "{}_srgb = \"{}\"", meta.name, meta.srgb)?;
+            }
+        }
+
+        if !self.parameters.is_empty() {
+            let names: Vec<&str> = self
+                .parameters
+                .iter()
+                .map(|param| param.name.as_str())
+                .collect();
+            writeln!(f)?;
+            writeln!(f, "parameters = \"{}\"", names.join(";"))?;
+            for param in &self.parameters {
+                writeln!(f, "{} = \"{}\"", param.name, param.value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ShaderFeatures, ShaderPreset};
+
+    #[test]
+    pub fn write_round_trip() {
+        let dir = std::env::temp_dir().join(format!("librashader-write-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        for name in ["defines.slangp", "reference_override.slangp"] {
+            let mut preset =
+                ShaderPreset::try_parse(format!("../test/{name}"), ShaderFeatures::NONE).unwrap();
+            if let Some(param) = preset.parameters.first_mut() {
+                param.value = 0.25;
+            }
+
+            let path = dir.join(name);
+            preset
+                .write_to(std::fs::File::create(&path).unwrap())
+                .unwrap();
+            let written = ShaderPreset::try_parse(&path, ShaderFeatures::STRICT_PRESET).unwrap();
+            assert!(
+                preset.diff(&written).is_empty(),
+                "{}",
+                preset.diff(&written)
+            );
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,4 +1,4 @@
-use crate::back::spirv::WriteSpirV;
+use crate::back::spirv::{SpirvReflect, WriteSpirV};
 use crate::back::targets::{OutputTarget, DXIL};
 use crate::back::{
     CompileReflectShader, CompileShader, CompilerBackend, FromCompilation, ShaderCompilerOutput,
@@ -31,9 +31,10 @@ impl FromCompilation<SpirvCompilation, SpirvCross> for DXIL {
         Ok(CompilerBackend {
             // we can just reuse WriteSpirV as the backend.
             backend: WriteSpirV {
-                reflect,
+                reflect: SpirvReflect::Graphics(reflect),
                 vertex: compile.vertex,
                 fragment: compile.fragment,
+                compute: None,
             },
         })
     }
@@ -53,9 +54,10 @@ impl FromCompilation<SpirvCompilation, SpirvCross> for DXIL {
         Ok(CompilerBackend {
             // we can just reuse WriteSpirV as the backend.
             backend: Box::new(WriteSpirV {
-                reflect,
+                reflect: SpirvReflect::Graphics(reflect),
                 vertex: compile.vertex,
                 fragment: compile.fragment,
+                compute: None,
             }),
         })
     }
@@ -108,6 +110,7 @@ impl CompileShader<DXIL> for WriteSpirV {
         Ok(ShaderCompilerOutput {
             vertex,
             fragment,
+            compute: None,
             context: (),
         })
     }
@@ -9,9 +9,48 @@ pub use spirv_cross2::compile::glsl::GlslVersion;
 
 use crate::reflect::cross::glsl::GlslReflect;
 
+/// Options for a GLSL compilation via spirv-cross.
+#[derive(Debug, Copy, Clone)]
+pub struct GlslOptions {
+    /// The GLSL version to target.
+    pub version: GlslVersion,
+    /// Flatten the UBO and push constant blocks into `vec4` arrays instead of emitting
+    /// uniform blocks.
+    ///
+    /// The arrays are named after the blocks (`LIBRA_UBO_VERTEX`, `LIBRA_PUSH_FRAGMENT`, etc.),
+    /// and reflected offsets are byte offsets into the array. Flattening fails if a block
+    /// mixes float and integer members.
+    pub flatten_buffers: bool,
+}
+
+impl GlslOptions {
+    /// Options for WebGL2 frontends, targeting ESSL 3.00.
+    ///
+    /// Uniform blocks are kept; set [`GlslOptions::flatten_buffers`] for frontends that
+    /// upload uniforms with `uniform4fv` instead of uniform buffers.
+    pub const fn webgl2() -> Self {
+        GlslOptions {
+            version: GlslVersion::Glsl300Es,
+            flatten_buffers: false,
+        }
+    }
+}
+
+impl From<GlslVersion> for GlslOptions {
+    fn from(version: GlslVersion) -> Self {
+        GlslOptions {
+            version,
+            flatten_buffers: false,
+        }
+    }
+}
+
 /// The context for a GLSL compilation via spirv-cross.
 pub struct CrossGlslContext {
     /// A map of bindings of sampler names to binding locations.
+    ///
+    /// The emitted GLSL has no explicit binding locations, so the texture unit of each
+    /// sampler must be assigned by name.
     pub sampler_bindings: Vec<(String, u32)>,
     /// The compiled program artifact after compilation.
     pub artifact: CompiledProgram<spirv_cross2::targets::Glsl>,
@@ -20,7 +59,7 @@ pub struct CrossGlslContext {
 #[cfg(not(feature = "stable"))]
 impl FromCompilation<SpirvCompilation, SpirvCross> for GLSL {
     type Target = GLSL;
-    type Options = GlslVersion;
+    type Options = GlslOptions;
     type Context = CrossGlslContext;
     type Output = impl CompileReflectShader<Self::Target, SpirvCompilation, SpirvCross>;
 
@@ -36,7 +75,7 @@ impl FromCompilation<SpirvCompilation, SpirvCross> for GLSL {
 #[cfg(feature = "stable")]
 impl FromCompilation<SpirvCompilation, SpirvCross> for GLSL {
     type Target = GLSL;
-    type Options = GlslVersion;
+    type Options = GlslOptions;
     type Context = CrossGlslContext;
     type Output = Box<dyn CompileReflectShader<Self::Target, SpirvCompilation, SpirvCross> + Send>;
 
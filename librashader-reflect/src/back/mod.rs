@@ -20,6 +20,10 @@ pub struct ShaderCompilerOutput<T, Context = ()> {
     pub vertex: T,
     /// The output for the fragment shader.
     pub fragment: T,
+    /// The output for the compute shader, if this is a compute pass.
+    ///
+    /// If present, `vertex` and `fragment` are empty.
+    pub compute: Option<T>,
     /// Additional context provided by the shader compiler.
     pub context: Context,
 }
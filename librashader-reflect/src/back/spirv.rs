@@ -4,18 +4,38 @@ use crate::back::{
 };
 use crate::error::{ShaderCompileError, ShaderReflectError};
 use crate::front::SpirvCompilation;
+use crate::reflect::cross::compute::CrossComputeReflect;
 use crate::reflect::cross::glsl::GlslReflect;
 use crate::reflect::cross::SpirvCross;
 use crate::reflect::naga::{Naga, NagaLoweringOptions, NagaReflect};
 use crate::reflect::semantics::ShaderSemantics;
 use crate::reflect::{ReflectShader, ShaderReflection};
 use naga::Module;
+use spirv_cross2::targets;
+
+pub(crate) enum SpirvReflect {
+    Graphics(GlslReflect),
+    Compute(CrossComputeReflect<targets::Glsl>),
+}
+
+impl SpirvReflect {
+    fn try_from_compilation(compile: &SpirvCompilation) -> Result<Self, ShaderReflectError> {
+        if compile.is_compute() {
+            Ok(SpirvReflect::Compute(CrossComputeReflect::try_from(
+                compile,
+            )?))
+        } else {
+            Ok(SpirvReflect::Graphics(GlslReflect::try_from(compile)?))
+        }
+    }
+}
 
 pub(crate) struct WriteSpirV {
     // rely on GLSL to provide out reflection but we don't actually need the AST.
-    pub(crate) reflect: GlslReflect,
+    pub(crate) reflect: SpirvReflect,
     pub(crate) vertex: Vec<u32>,
     pub(crate) fragment: Vec<u32>,
+    pub(crate) compute: Option<Vec<u32>>,
 }
 
 #[cfg(not(feature = "stable"))]
@@ -28,14 +48,16 @@ impl FromCompilation<SpirvCompilation, SpirvCross> for SPIRV {
     fn from_compilation(
         compile: SpirvCompilation,
     ) -> Result<CompilerBackend<Self::Output>, ShaderReflectError> {
-        let reflect = GlslReflect::try_from(&compile)?;
+        let reflect = SpirvReflect::try_from_compilation(&compile)?;
         let vertex = compile.vertex;
         let fragment = compile.fragment;
+        let compute = compile.compute;
         Ok(CompilerBackend {
             backend: WriteSpirV {
                 reflect,
                 vertex,
                 fragment,
+                compute,
             },
         })
     }
@@ -51,14 +73,16 @@ impl FromCompilation<SpirvCompilation, SpirvCross> for SPIRV {
     fn from_compilation(
         compile: SpirvCompilation,
     ) -> Result<CompilerBackend<Self::Output>, ShaderReflectError> {
-        let reflect = GlslReflect::try_from(&compile)?;
+        let reflect = SpirvReflect::try_from_compilation(&compile)?;
         let vertex = compile.vertex;
         let fragment = compile.fragment;
+        let compute = compile.compute;
         Ok(CompilerBackend {
             backend: Box::new(WriteSpirV {
                 reflect,
                 vertex,
                 fragment,
+                compute,
             }),
         })
     }
@@ -70,11 +94,17 @@ impl ReflectShader for WriteSpirV {
         pass_number: usize,
         semantics: &ShaderSemantics,
     ) -> Result<ShaderReflection, ShaderReflectError> {
-        self.reflect.reflect(pass_number, semantics)
+        match &mut self.reflect {
+            SpirvReflect::Graphics(reflect) => reflect.reflect(pass_number, semantics),
+            SpirvReflect::Compute(reflect) => reflect.reflect(pass_number, semantics),
+        }
     }
 
     fn validate(&mut self) -> Result<(), ShaderReflectError> {
-        self.reflect.validate()
+        match &mut self.reflect {
+            SpirvReflect::Graphics(reflect) => reflect.validate(),
+            SpirvReflect::Compute(reflect) => reflect.validate(),
+        }
     }
 }
 
@@ -89,6 +119,7 @@ impl CompileShader<SPIRV> for WriteSpirV {
         Ok(ShaderCompilerOutput {
             vertex: self.vertex,
             fragment: self.fragment,
+            compute: self.compute,
             context: (),
         })
     }
@@ -100,6 +131,7 @@ impl CompileShader<SPIRV> for WriteSpirV {
         Ok(ShaderCompilerOutput {
             vertex: self.vertex,
             fragment: self.fragment,
+            compute: self.compute,
             context: (),
         })
     }
@@ -75,6 +75,8 @@ pub enum SemanticsErrorKind {
     InvalidRange(u32),
     /// The number of entry points in the shader was invalid.
     InvalidEntryPointCount(usize),
+    /// The workgroup size of a compute shader was missing or not declared with literals.
+    InvalidWorkgroupSize,
     /// The requested uniform or texture name was not provided semantics.
     UnknownSemantics(String),
     /// The type of the requested uniform was not compatible with the provided semantics.
@@ -94,6 +96,13 @@ pub enum ShaderReflectError {
     /// Error when validating fragment shader semantics.
     #[error("error when verifying texture semantics {0:?}")]
     FragmentSemanticError(SemanticsErrorKind),
+    /// Error when validating compute shader semantics.
+    #[error("error when verifying compute semantics: {0:?}")]
+    ComputeSemanticError(SemanticsErrorKind),
+    /// The shader is a compute pass, but the target or reflection backend does not
+    /// support compute passes.
+    #[error("compute passes are not supported by this target")]
+    UnsupportedComputePass,
     /// The vertex and fragment shader must have the same UBO binding location.
     #[error("vertex and fragment shader must have same UBO binding. declared {vertex} in vertex, got {fragment} in fragment")]
     MismatchedUniformBuffer { vertex: u32, fragment: u32 },
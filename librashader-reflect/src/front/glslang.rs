@@ -28,6 +28,24 @@ pub(crate) fn compile_spirv(source: &ShaderSource) -> Result<SpirvCompilation, S
         messages: ShaderMessage::DEFAULT,
     };
 
+    if let Some(compute) = &source.compute {
+        let compute = glslang::ShaderSource::from(compute.as_str());
+        let compute = ShaderInput::new(
+            &compute,
+            glslang::ShaderStage::Compute,
+            &options,
+            None,
+            None,
+        )?;
+        let compute = compiler.create_shader(compute)?.compile()?;
+
+        return Ok(SpirvCompilation {
+            vertex: Vec::new(),
+            fragment: Vec::new(),
+            compute: Some(compute),
+        });
+    }
+
     let vertex = glslang::ShaderSource::from(source.vertex.as_str());
     let vertex = ShaderInput::new(&vertex, glslang::ShaderStage::Vertex, &options, None, None)?;
     let vertex = compiler.create_shader(vertex)?;
@@ -56,7 +74,11 @@ pub(crate) fn compile_spirv(source: &ShaderSource) -> Result<SpirvCompilation, S
     let vertex = vertex.module().assemble();
     let fragment = fragment.module().assemble();
 
-    Ok(SpirvCompilation { vertex, fragment })
+    Ok(SpirvCompilation {
+        vertex,
+        fragment,
+        compute: None,
+    })
 }
 
 #[cfg(test)]
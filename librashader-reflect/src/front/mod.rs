@@ -29,6 +29,15 @@ impl ShaderReflectObject for SpirvCompilation {
 pub struct SpirvCompilation {
     pub(crate) vertex: Vec<u32>,
     pub(crate) fragment: Vec<u32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) compute: Option<Vec<u32>>,
+}
+
+impl SpirvCompilation {
+    /// Whether this is the compilation of a compute pass.
+    pub fn is_compute(&self) -> bool {
+        self.compute.is_some()
+    }
 }
 
 impl TryFrom<&ShaderSource> for SpirvCompilation {
@@ -43,6 +43,24 @@
 //! librashader-reflect is designed to be compiler-agnostic. [naga](https://docs.rs/naga/latest/naga/index.html),
 //! a pure-Rust shader compiler, as well as SPIRV-Cross via [SpirvCompilation](crate::front::SpirvCompilation)
 //! is supported.
+//!
+//! ## Stability of reflection output
+//! Custom runtimes depend on the exact output of reflection, not only on its types. For a given
+//! shader, reflection backend and set of [`ShaderFeatures`](librashader_common::shader_features::ShaderFeatures),
+//! the following are considered part of the public API:
+//!
+//! * which uniforms, push constants and textures are assigned to which semantics,
+//! * the binding points, sizes and stage masks of the UBO and push constant block,
+//! * the offsets and sizes of uniform members, and
+//! * the binding points of textures.
+//!
+//! Changes to any of these are breaking changes, and are only made in a release that bumps
+//! the minor version while librashader is below 1.0. Patch releases never change reflection output.
+//! Both backends are held to this independently; they are not guaranteed to agree with each other
+//! (naga assigns a binding to the push constant block, for example).
+//!
+//! The snapshot tests in `tests/reflection_snapshots.rs` record the reflection output for a corpus
+//! of shaders and fail on any change.
 #![cfg_attr(not(feature = "stable"), feature(impl_trait_in_assoc_type))]
 /// Shader codegen backends.
 pub mod back;
@@ -0,0 +1,248 @@
+use crate::error::{SemanticsErrorKind, ShaderReflectError};
+use crate::front::SpirvCompilation;
+use crate::reflect::cross::CrossReflect;
+use crate::reflect::helper::SemanticErrorBlame;
+use crate::reflect::semantics::{
+    BindingMeta, BindingStage, BufferReflection, ComputeReflection, ShaderReflection,
+    ShaderSemantics, UniformMemberBlock, MAX_BINDINGS_COUNT,
+};
+use crate::reflect::{align_uniform_size, ReflectShader};
+use spirv_cross2::reflect::{AllResources, ExecutionModeArguments};
+use spirv_cross2::spirv::{Decoration, ExecutionMode};
+use spirv_cross2::{Compiler, Module};
+
+/// Reflect a compute pass under SPIRV-Cross semantics.
+///
+/// Compute passes share the uniform and texture semantics of graphics passes, but
+/// write their output to a single storage image instead of a fragment output.
+pub(crate) struct CrossComputeReflect<T>
+where
+    T: spirv_cross2::compile::CompilableTarget,
+{
+    compute: Compiler<T>,
+}
+
+impl<T> TryFrom<&SpirvCompilation> for CrossComputeReflect<T>
+where
+    T: spirv_cross2::compile::CompilableTarget,
+{
+    type Error = ShaderReflectError;
+
+    fn try_from(value: &SpirvCompilation) -> Result<Self, Self::Error> {
+        let Some(compute) = &value.compute else {
+            return Err(ShaderReflectError::ComputeSemanticError(
+                SemanticsErrorKind::InvalidEntryPointCount(0),
+            ));
+        };
+
+        let compute = Compiler::new(Module::from_words(compute))?;
+        Ok(CrossComputeReflect { compute })
+    }
+}
+
+impl<T> CrossComputeReflect<T>
+where
+    T: spirv_cross2::compile::CompilableTarget,
+{
+    fn validate_semantics(&self, res: &AllResources) -> Result<(), ShaderReflectError> {
+        let blame = SemanticErrorBlame::Compute;
+
+        let entry_points = self.compute.entry_points()?;
+        if entry_points.len() != 1 {
+            return Err(blame.error(SemanticsErrorKind::InvalidEntryPointCount(
+                entry_points.len(),
+            )));
+        }
+
+        if !res.storage_buffers.is_empty()
+            || !res.subpass_inputs.is_empty()
+            || !res.atomic_counters.is_empty()
+        {
+            return Err(blame.error(SemanticsErrorKind::InvalidResourceType));
+        }
+
+        if res.storage_images.len() != 1 {
+            return Err(blame.error(SemanticsErrorKind::InvalidOutputCount(
+                res.storage_images.len(),
+            )));
+        }
+
+        if res.uniform_buffers.len() > 1 {
+            return Err(blame.error(SemanticsErrorKind::InvalidUniformBufferCount(
+                res.uniform_buffers.len(),
+            )));
+        }
+
+        if res.push_constant_buffers.len() > 1 {
+            return Err(blame.error(SemanticsErrorKind::InvalidPushBufferCount(
+                res.push_constant_buffers.len(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn reflect_compute(&self, res: &AllResources) -> Result<ComputeReflection, ShaderReflectError> {
+        let blame = SemanticErrorBlame::Compute;
+
+        let Some(ExecutionModeArguments::LocalSize { x, y, z }) = self
+            .compute
+            .execution_mode_arguments(ExecutionMode::LocalSize)?
+        else {
+            return Err(blame.error(SemanticsErrorKind::InvalidWorkgroupSize));
+        };
+
+        let output = &res.storage_images[0];
+        let Some(descriptor_set) = self
+            .compute
+            .decoration(output.id, Decoration::DescriptorSet)?
+            .and_then(|l| l.as_literal())
+        else {
+            return Err(blame.error(SemanticsErrorKind::MissingBinding));
+        };
+        let Some(binding) = self
+            .compute
+            .decoration(output.id, Decoration::Binding)?
+            .and_then(|l| l.as_literal())
+        else {
+            return Err(blame.error(SemanticsErrorKind::MissingBinding));
+        };
+
+        if descriptor_set != 0 {
+            return Err(blame.error(SemanticsErrorKind::InvalidDescriptorSet(descriptor_set)));
+        }
+        if binding >= MAX_BINDINGS_COUNT {
+            return Err(blame.error(SemanticsErrorKind::InvalidBinding(binding)));
+        }
+
+        Ok(ComputeReflection {
+            workgroup_size: [x, y, z],
+            output_binding: binding,
+        })
+    }
+
+    fn reflect_ubo(
+        &mut self,
+        res: &AllResources,
+    ) -> Result<Option<BufferReflection<u32>>, ShaderReflectError> {
+        let Some(ubo) = res.uniform_buffers.first() else {
+            return Ok(None);
+        };
+
+        self.compute
+            .set_decoration(ubo.id, Decoration::Binding, Some(0))?;
+        let ubo = CrossReflect::get_ubo_data(&self.compute, ubo, SemanticErrorBlame::Compute)?;
+        Ok(Some(BufferReflection {
+            binding: ubo.binding,
+            size: align_uniform_size(ubo.size),
+            stage_mask: BindingStage::COMPUTE,
+        }))
+    }
+
+    fn reflect_push_constant_buffer(
+        &mut self,
+        res: &AllResources,
+    ) -> Result<Option<BufferReflection<Option<u32>>>, ShaderReflectError> {
+        let Some(push) = res.push_constant_buffers.first() else {
+            return Ok(None);
+        };
+
+        self.compute
+            .set_decoration(push.id, Decoration::Binding, Some(1))?;
+        let size = CrossReflect::get_push_size(&self.compute, push, SemanticErrorBlame::Compute)?;
+        Ok(Some(BufferReflection {
+            binding: None,
+            size: align_uniform_size(size),
+            stage_mask: BindingStage::COMPUTE,
+        }))
+    }
+}
+
+impl<T> ReflectShader for CrossComputeReflect<T>
+where
+    T: spirv_cross2::compile::CompilableTarget,
+{
+    fn reflect(
+        &mut self,
+        pass_number: usize,
+        semantics: &ShaderSemantics,
+    ) -> Result<ShaderReflection, ShaderReflectError> {
+        let res = self.compute.shader_resources()?.all_resources()?;
+        self.validate_semantics(&res)?;
+
+        let ubo = self.reflect_ubo(&res)?;
+        let push_constant = self.reflect_push_constant_buffer(&res)?;
+        let compute = self.reflect_compute(&res)?;
+
+        let mut meta = BindingMeta::default();
+        if let Some(ubo) = res.uniform_buffers.first() {
+            CrossReflect::reflect_buffer_range_metas(
+                &self.compute,
+                ubo,
+                pass_number,
+                semantics,
+                &mut meta,
+                UniformMemberBlock::Ubo,
+                SemanticErrorBlame::Compute,
+            )?;
+        }
+
+        if let Some(push) = res.push_constant_buffers.first() {
+            CrossReflect::reflect_buffer_range_metas(
+                &self.compute,
+                push,
+                pass_number,
+                semantics,
+                &mut meta,
+                UniformMemberBlock::PushConstant,
+                SemanticErrorBlame::Compute,
+            )?;
+        }
+
+        let mut bindings = 0u16;
+        if let Some(ubo) = &ubo {
+            bindings |= 1 << ubo.binding;
+        }
+
+        if bindings & (1 << compute.output_binding) != 0 {
+            return Err(ShaderReflectError::BindingInUse(compute.output_binding));
+        }
+        bindings |= 1 << compute.output_binding;
+
+        for sampled_image in &res.sampled_images {
+            let texture_data = CrossReflect::reflect_texture(
+                &self.compute,
+                sampled_image,
+                SemanticErrorBlame::Compute,
+            )?;
+            if bindings & (1 << texture_data.binding) != 0 {
+                return Err(ShaderReflectError::BindingInUse(texture_data.binding));
+            }
+            bindings |= 1 << texture_data.binding;
+
+            CrossReflect::<T>::reflect_texture_metas(
+                texture_data,
+                pass_number,
+                semantics,
+                &mut meta,
+                SemanticErrorBlame::Compute,
+            )?;
+        }
+
+        Ok(ShaderReflection {
+            ubo,
+            push_constant,
+            meta,
+            compute: Some(compute),
+        })
+    }
+
+    fn validate(&mut self) -> Result<(), ShaderReflectError> {
+        let res = self.compute.shader_resources()?.all_resources()?;
+        self.validate_semantics(&res)?;
+        self.reflect_ubo(&res)?;
+        self.reflect_push_constant_buffer(&res)?;
+        self.reflect_compute(&res)?;
+        Ok(())
+    }
+}
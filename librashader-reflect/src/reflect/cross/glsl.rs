@@ -1,4 +1,4 @@
-use crate::back::glsl::CrossGlslContext;
+use crate::back::glsl::{CrossGlslContext, GlslOptions};
 use crate::back::targets::GLSL;
 use crate::back::{CompileShader, ShaderCompilerOutput};
 use crate::error::ShaderCompileError;
@@ -12,16 +12,16 @@ use spirv_cross2::{targets, SpirvCrossError};
 pub(crate) type GlslReflect = CrossReflect<targets::Glsl>;
 
 impl CompileShader<GLSL> for CrossReflect<targets::Glsl> {
-    type Options = spirv_cross2::compile::glsl::GlslVersion;
+    type Options = GlslOptions;
     type Context = CrossGlslContext;
 
     fn compile(
         mut self,
-        version: Self::Options,
+        glsl_options: Self::Options,
     ) -> Result<ShaderCompilerOutput<String, Self::Context>, ShaderCompileError> {
         let mut options = targets::Glsl::options();
 
-        options.version = version;
+        options.version = glsl_options.version;
 
         options.es_default_float_precision_highp = true;
         options.es_default_int_precision_highp = true;
@@ -45,6 +45,8 @@ impl CompileShader<GLSL> for CrossReflect<targets::Glsl> {
                 .set_decoration(res.id, Decoration::Location, DecorationValue::unset())?;
         }
 
+        let flatten = glsl_options.flatten_buffers;
+
         let vertex_pcb = vertex_resources.resources_for_type(ResourceType::PushConstant)?;
         if vertex_pcb.len() > 1 {
             return Err(ShaderCompileError::SpirvCrossCompileError(
@@ -54,15 +56,15 @@ impl CompileShader<GLSL> for CrossReflect<targets::Glsl> {
             ));
         }
         for res in vertex_pcb {
+            if flatten {
+                self.vertex.flatten_buffer_block(res.id)?;
+            }
             self.vertex
                 .set_name(res.id, c"LIBRA_PUSH_VERTEX_INSTANCE")?;
             self.vertex
                 .set_name(res.base_type_id, c"LIBRA_PUSH_VERTEX")?;
         }
 
-        // todo: options
-        let _flatten = false;
-
         let vertex_ubo = vertex_resources.resources_for_type(ResourceType::UniformBuffer)?;
         if vertex_ubo.len() > 1 {
             return Err(ShaderCompileError::SpirvCrossCompileError(
@@ -72,9 +74,9 @@ impl CompileShader<GLSL> for CrossReflect<targets::Glsl> {
             ));
         }
         for res in vertex_ubo {
-            // if flatten {
-            //     self.vertex.flatten_buffer_block(res.id)?;
-            // }
+            if flatten {
+                self.vertex.flatten_buffer_block(res.id)?;
+            }
             self.vertex.set_name(res.id, c"LIBRA_UBO_VERTEX_INSTANCE")?;
             self.vertex
                 .set_name(res.base_type_id, c"LIBRA_UBO_VERTEX")?;
@@ -97,6 +99,9 @@ impl CompileShader<GLSL> for CrossReflect<targets::Glsl> {
         }
 
         for res in fragment_pcb {
+            if flatten {
+                self.fragment.flatten_buffer_block(res.id)?;
+            }
             self.fragment
                 .set_name(res.id, c"LIBRA_PUSH_FRAGMENT_INSTANCE")?;
             self.fragment
@@ -113,9 +118,9 @@ impl CompileShader<GLSL> for CrossReflect<targets::Glsl> {
         }
 
         for res in fragment_ubo {
-            // if flatten {
-            //     self.fragment.flatten_buffer_block(res.id)?;
-            // }
+            if flatten {
+                self.fragment.flatten_buffer_block(res.id)?;
+            }
             self.fragment
                 .set_name(res.id, c"LIBRA_UBO_FRAGMENT_INSTANCE")?;
             self.fragment
@@ -155,6 +160,7 @@ impl CompileShader<GLSL> for CrossReflect<targets::Glsl> {
         Ok(ShaderCompilerOutput {
             vertex: vertex_compiled.to_string(),
             fragment: fragment_compiled.to_string(),
+            compute: None,
             context: CrossGlslContext {
                 sampler_bindings: texture_fixups,
                 artifact: CompiledProgram {
@@ -117,6 +117,7 @@ impl CompileShader<HLSL> for CrossReflect<targets::Hlsl> {
         Ok(ShaderCompilerOutput {
             vertex: vertex_compiled.to_string(),
             fragment: fragment_compiled.to_string(),
+            compute: None,
             context: CrossHlslContext {
                 artifact: CompiledProgram {
                     vertex: vertex_compiled,
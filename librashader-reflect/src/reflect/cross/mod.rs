@@ -7,9 +7,14 @@ pub mod hlsl;
 #[doc(hidden)]
 pub mod msl;
 
+pub(crate) mod compute;
+
 use crate::error::{SemanticsErrorKind, ShaderReflectError};
 use crate::front::SpirvCompilation;
-use crate::reflect::helper::{SemanticErrorBlame, TextureData, UboData};
+use crate::reflect::helper::{
+    insert_parameter_array_meta, insert_parameter_meta, ParameterArrayLayout, SemanticErrorBlame,
+    TextureData, UboData,
+};
 use crate::reflect::semantics::{
     BindingMeta, BindingStage, BufferReflection, MemberOffset, ShaderReflection, ShaderSemantics,
     TextureBinding, TextureSemanticMap, TextureSemantics, TextureSizeMeta, TypeInfo,
@@ -20,7 +25,8 @@ use crate::reflect::{align_uniform_size, ReflectShader};
 use librashader_common::map::ShortString;
 use spirv_cross2::compile::CompiledArtifact;
 use spirv_cross2::reflect::{
-    AllResources, BitWidth, DecorationValue, Resource, Scalar, ScalarKind, TypeInner,
+    AllResources, ArrayDimension, BitWidth, DecorationValue, Resource, Scalar, ScalarKind,
+    StructMember, TypeInner,
 };
 use spirv_cross2::spirv::Decoration;
 use spirv_cross2::Compiler;
@@ -94,6 +100,7 @@ impl ValidateTypeSemantics<TypeInner<'_>> for UniqueSemantics {
                 }
             }
             UniqueSemantics::FloatParameter
+            | UniqueSemantics::SceneLuminance
             | UniqueSemantics::OriginalAspectRotated
             | UniqueSemantics::OriginalAspect
             | UniqueSemantics::OriginalFPS => {
@@ -150,6 +157,10 @@ where
     type Error = ShaderReflectError;
 
     fn try_from(value: &SpirvCompilation) -> Result<Self, Self::Error> {
+        if value.compute.is_some() {
+            return Err(ShaderReflectError::UnsupportedComputePass);
+        }
+
         let vertex_module = Module::from_words(&value.vertex);
         let fragment_module = Module::from_words(&value.fragment);
 
@@ -365,16 +376,14 @@ where
             };
 
             let ubo_type = ast.type_description(resource.base_type_id)?;
-            let range_type = match ubo_type.inner {
-                TypeInner::Struct(struct_def) => {
-                    let range_type = struct_def
-                        .members
-                        .get(range.index as usize)
-                        .ok_or(blame.error(SemanticsErrorKind::InvalidRange(range.index)))?;
-                    ast.type_description(range_type.id)?
-                }
-                _ => return Err(blame.error(SemanticsErrorKind::InvalidResourceType)),
+            let TypeInner::Struct(struct_def) = ubo_type.inner else {
+                return Err(blame.error(SemanticsErrorKind::InvalidResourceType));
             };
+            let member = struct_def
+                .members
+                .get(range.index as usize)
+                .ok_or(blame.error(SemanticsErrorKind::InvalidRange(range.index)))?;
+            let range_type = ast.type_description(member.id)?;
 
             if let Some(parameter) = semantics.uniform_semantics.unique_semantic(&name) {
                 let Some(typeinfo) = parameter.semantics.validate_type(&range_type.inner) else {
@@ -385,42 +394,14 @@ where
 
                 match &parameter.semantics {
                     UniqueSemantics::FloatParameter => {
-                        let offset = range.offset;
-                        if let Some(meta) = meta.parameter_meta.get_mut::<str>(&name.as_ref()) {
-                            if let Some(expected) = meta
-                                .offset
-                                .offset(offset_type)
-                                .filter(|expected| *expected != offset)
-                            {
-                                return Err(ShaderReflectError::MismatchedOffset {
-                                    semantic: name.to_string(),
-                                    expected,
-                                    received: offset,
-                                    ty: offset_type,
-                                    pass: pass_number,
-                                });
-                            }
-                            if meta.size != typeinfo.size {
-                                return Err(ShaderReflectError::MismatchedSize {
-                                    semantic: name.to_string(),
-                                    vertex: meta.size,
-                                    fragment: typeinfo.size,
-                                    pass: pass_number,
-                                });
-                            }
-
-                            *meta.offset.offset_mut(offset_type) = Some(offset);
-                        } else {
-                            let name = ShortString::from(name.as_ref());
-                            meta.parameter_meta.insert(
-                                name.clone(),
-                                VariableMeta {
-                                    id: name,
-                                    offset: MemberOffset::new(offset, offset_type),
-                                    size: typeinfo.size,
-                                },
-                            );
-                        }
+                        insert_parameter_meta(
+                            meta,
+                            &name,
+                            range.offset,
+                            typeinfo.size,
+                            offset_type,
+                            pass_number,
+                        )?;
                     }
                     semantics => {
                         let offset = range.offset;
@@ -495,6 +476,7 @@ where
                     meta.stage_mask.insert(match blame {
                         SemanticErrorBlame::Vertex => BindingStage::VERTEX,
                         SemanticErrorBlame::Fragment => BindingStage::FRAGMENT,
+                        SemanticErrorBlame::Compute => BindingStage::COMPUTE,
                     });
 
                     *meta.offset.offset_mut(offset_type) = Some(offset);
@@ -506,11 +488,23 @@ where
                             stage_mask: match blame {
                                 SemanticErrorBlame::Vertex => BindingStage::VERTEX,
                                 SemanticErrorBlame::Fragment => BindingStage::FRAGMENT,
+                                SemanticErrorBlame::Compute => BindingStage::COMPUTE,
                             },
                             id: ShortString::from(name.as_ref()),
                         },
                     );
                 }
+            } else if let Some(layout) = Self::parameter_array_layout(ast, member)? {
+                insert_parameter_array_meta(
+                    meta,
+                    semantics,
+                    &name,
+                    layout,
+                    range.offset,
+                    offset_type,
+                    pass_number,
+                    blame,
+                )?;
             } else {
                 return Err(blame.error(SemanticsErrorKind::UnknownSemantics(name.to_string())));
             }
@@ -518,6 +512,57 @@ where
         Ok(())
     }
 
+    /// Get the layout of a member that binds an array of float parameters, if it can.
+    fn parameter_array_layout(
+        ast: &Compiler<T>,
+        member: &StructMember,
+    ) -> Result<Option<ParameterArrayLayout>, ShaderReflectError> {
+        const FLOAT: Scalar = Scalar {
+            kind: ScalarKind::Float,
+            size: BitWidth::Word,
+        };
+
+        match ast.type_description(member.id)?.inner {
+            TypeInner::Matrix {
+                columns,
+                rows,
+                scalar: FLOAT,
+            } => Ok(member.matrix_stride.map(|stride| ParameterArrayLayout {
+                elements: columns,
+                components: rows,
+                stride,
+            })),
+            TypeInner::Array {
+                base,
+                dimensions,
+                stride,
+                ..
+            } => {
+                let ([ArrayDimension::Literal(elements)], Some(stride)) =
+                    (dimensions.as_slice(), stride.or(member.array_stride))
+                else {
+                    return Ok(None);
+                };
+
+                let components = match ast.type_description(base)?.inner {
+                    TypeInner::Scalar(FLOAT) => 1,
+                    TypeInner::Vector {
+                        width,
+                        scalar: FLOAT,
+                    } => width,
+                    _ => return Ok(None),
+                };
+
+                Ok(Some(ParameterArrayLayout {
+                    elements: *elements,
+                    components,
+                    stride,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
     fn reflect_ubos(
         &mut self,
         vertex_ubo: Option<&Resource>,
@@ -576,18 +621,16 @@ where
     }
 
     fn reflect_texture_metas(
-        &self,
         texture: TextureData,
         pass_number: usize,
         semantics: &ShaderSemantics,
         meta: &mut BindingMeta,
+        blame: SemanticErrorBlame,
     ) -> Result<(), ShaderReflectError> {
         let Some(semantic) = semantics.texture_semantics.texture_semantic(texture.name) else {
-            return Err(
-                SemanticErrorBlame::Fragment.error(SemanticsErrorKind::UnknownSemantics(
-                    texture.name.to_string(),
-                )),
-            );
+            return Err(blame.error(SemanticsErrorKind::UnknownSemantics(
+                texture.name.to_string(),
+            )));
         };
 
         if semantic.semantics == TextureSemantics::PassOutput && semantic.index >= pass_number {
@@ -607,37 +650,28 @@ where
     }
 
     fn reflect_texture<'a>(
-        &'a self,
+        ast: &Compiler<T>,
         texture: &'a Resource,
+        blame: SemanticErrorBlame,
     ) -> Result<TextureData<'a>, ShaderReflectError> {
-        let Some(descriptor_set) = self
-            .fragment
+        let Some(descriptor_set) = ast
             .decoration(texture.id, Decoration::DescriptorSet)?
             .and_then(|l| l.as_literal())
         else {
-            return Err(ShaderReflectError::FragmentSemanticError(
-                SemanticsErrorKind::MissingBinding,
-            ));
+            return Err(blame.error(SemanticsErrorKind::MissingBinding));
         };
-        let Some(binding) = self
-            .fragment
+        let Some(binding) = ast
             .decoration(texture.id, Decoration::Binding)?
             .and_then(|l| l.as_literal())
         else {
-            return Err(ShaderReflectError::FragmentSemanticError(
-                SemanticsErrorKind::MissingBinding,
-            ));
+            return Err(blame.error(SemanticsErrorKind::MissingBinding));
         };
 
         if descriptor_set != 0 {
-            return Err(ShaderReflectError::FragmentSemanticError(
-                SemanticsErrorKind::InvalidDescriptorSet(descriptor_set),
-            ));
+            return Err(blame.error(SemanticsErrorKind::InvalidDescriptorSet(descriptor_set)));
         }
         if binding >= MAX_BINDINGS_COUNT {
-            return Err(ShaderReflectError::FragmentSemanticError(
-                SemanticsErrorKind::InvalidBinding(binding),
-            ));
+            return Err(blame.error(SemanticsErrorKind::InvalidBinding(binding)));
         }
 
         Ok(TextureData {
@@ -786,19 +820,27 @@ where
         }
 
         for sampled_image in &fragment_res.sampled_images {
-            let texture_data = self.reflect_texture(sampled_image)?;
+            let texture_data =
+                Self::reflect_texture(&self.fragment, sampled_image, SemanticErrorBlame::Fragment)?;
             if ubo_bindings & (1 << texture_data.binding) != 0 {
                 return Err(ShaderReflectError::BindingInUse(texture_data.binding));
             }
             ubo_bindings |= 1 << texture_data.binding;
 
-            self.reflect_texture_metas(texture_data, pass_number, semantics, &mut meta)?;
+            Self::reflect_texture_metas(
+                texture_data,
+                pass_number,
+                semantics,
+                &mut meta,
+                SemanticErrorBlame::Fragment,
+            )?;
         }
 
         Ok(ShaderReflection {
             ubo,
             push_constant,
             meta,
+            compute: None,
         })
     }
 
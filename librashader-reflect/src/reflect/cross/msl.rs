@@ -93,6 +93,7 @@ impl CompileShader<MSL> for CrossReflect<targets::Msl> {
         Ok(ShaderCompilerOutput {
             vertex: vertex_compiled.to_string(),
             fragment: fragment_compiled.to_string(),
+            compute: None,
             context: CrossMslContext {
                 artifact: CompiledProgram {
                     vertex: vertex_compiled,
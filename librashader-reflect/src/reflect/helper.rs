@@ -1,4 +1,9 @@
 use crate::error::{SemanticsErrorKind, ShaderReflectError};
+use crate::reflect::semantics::{
+    BindingMeta, MemberOffset, Semantic, ShaderSemantics, UniformMemberBlock, UniqueSemanticMap,
+    UniqueSemantics, VariableMeta,
+};
+use librashader_common::map::ShortString;
 
 pub struct UboData {
     // id: u32,
@@ -20,6 +25,7 @@ pub struct TextureData<'a> {
 pub enum SemanticErrorBlame {
     Vertex,
     Fragment,
+    Compute,
 }
 
 impl SemanticErrorBlame {
@@ -27,6 +33,122 @@ impl SemanticErrorBlame {
         match self {
             SemanticErrorBlame::Vertex => ShaderReflectError::VertexSemanticError(kind),
             SemanticErrorBlame::Fragment => ShaderReflectError::FragmentSemanticError(kind),
+            SemanticErrorBlame::Compute => ShaderReflectError::ComputeSemanticError(kind),
         }
     }
 }
+
+/// The layout of a uniform member that binds an array of float parameters.
+///
+/// A `float NAME[N]`, `vecM NAME[N]` or `matCxR NAME` member that is not itself a semantic
+/// binds the parameters `NAME0`, `NAME1`, ... to its components in declaration order. Matrices
+/// are column-major, so the components of each column are consecutive parameters.
+#[derive(Copy, Clone)]
+pub struct ParameterArrayLayout {
+    /// The number of array elements, or matrix columns.
+    pub elements: u32,
+    /// The number of float components in each element.
+    pub components: u32,
+    /// The stride in bytes between the start of each element.
+    pub stride: u32,
+}
+
+impl ParameterArrayLayout {
+    /// Get the name of the parameter bound to each component, with the byte offset of the component
+    /// relative to the start of the member.
+    pub fn parameters<'a>(&self, name: &'a str) -> impl Iterator<Item = (String, usize)> + 'a {
+        let layout = *self;
+        (0..layout.elements).flat_map(move |element| {
+            (0..layout.components).map(move |component| {
+                (
+                    format!("{name}{}", element * layout.components + component),
+                    (element * layout.stride + component * 4) as usize,
+                )
+            })
+        })
+    }
+}
+
+/// Insert or validate the metadata of a float parameter bound at `offset` in a uniform block.
+pub fn insert_parameter_meta(
+    meta: &mut BindingMeta,
+    name: &str,
+    offset: usize,
+    size: u32,
+    offset_type: UniformMemberBlock,
+    pass_number: usize,
+) -> Result<(), ShaderReflectError> {
+    if let Some(meta) = meta.parameter_meta.get_mut::<str>(name) {
+        if let Some(expected) = meta
+            .offset
+            .offset(offset_type)
+            .filter(|expected| *expected != offset)
+        {
+            return Err(ShaderReflectError::MismatchedOffset {
+                semantic: name.to_string(),
+                expected,
+                received: offset,
+                ty: offset_type,
+                pass: pass_number,
+            });
+        }
+        if meta.size != size {
+            return Err(ShaderReflectError::MismatchedSize {
+                semantic: name.to_string(),
+                vertex: meta.size,
+                fragment: size,
+                pass: pass_number,
+            });
+        }
+
+        *meta.offset.offset_mut(offset_type) = Some(offset);
+    } else {
+        let name = ShortString::from(name);
+        meta.parameter_meta.insert(
+            name.clone(),
+            VariableMeta {
+                id: name,
+                offset: MemberOffset::new(offset, offset_type),
+                size,
+            },
+        );
+    }
+    Ok(())
+}
+
+/// Insert the metadata of every parameter bound by a parameter array member.
+///
+/// Every component of the member must be bound to a parameter declared by the shader.
+#[allow(clippy::too_many_arguments)]
+pub fn insert_parameter_array_meta(
+    meta: &mut BindingMeta,
+    semantics: &ShaderSemantics,
+    name: &str,
+    layout: ParameterArrayLayout,
+    offset: usize,
+    offset_type: UniformMemberBlock,
+    pass_number: usize,
+    blame: SemanticErrorBlame,
+) -> Result<(), ShaderReflectError> {
+    for (parameter, component_offset) in layout.parameters(name) {
+        if !matches!(
+            semantics.uniform_semantics.unique_semantic(&parameter),
+            Some(Semantic {
+                semantics: UniqueSemantics::FloatParameter,
+                ..
+            })
+        ) {
+            return Err(blame.error(SemanticsErrorKind::UnknownSemantics(parameter)));
+        }
+
+        insert_parameter_meta(
+            meta,
+            &parameter,
+            offset + component_offset,
+            1,
+            offset_type,
+            pass_number,
+        )?;
+    }
+    Ok(())
+}
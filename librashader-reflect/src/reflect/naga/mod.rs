@@ -12,7 +12,10 @@ use std::fmt::Debug;
 
 use crate::front::spirv_passes::lower_samplers;
 use crate::front::SpirvCompilation;
-use crate::reflect::helper::{SemanticErrorBlame, TextureData, UboData};
+use crate::reflect::helper::{
+    insert_parameter_array_meta, insert_parameter_meta, ParameterArrayLayout, SemanticErrorBlame,
+    TextureData, UboData,
+};
 use crate::reflect::semantics::{
     BindingMeta, BindingStage, BufferReflection, MemberOffset, ShaderSemantics, TextureBinding,
     TextureSemanticMap, TextureSemantics, TextureSizeMeta, TypeInfo, UniformMemberBlock,
@@ -22,8 +25,8 @@ use crate::reflect::semantics::{
 use crate::reflect::{align_uniform_size, ReflectShader, ShaderReflection};
 use librashader_common::map::ShortString;
 use naga::{
-    AddressSpace, Binding, Expression, GlobalVariable, Handle, ImageClass, Module, ResourceBinding,
-    Scalar, ScalarKind, StructMember, TypeInner, VectorSize,
+    AddressSpace, ArraySize, Binding, Expression, GlobalVariable, Handle, ImageClass, Module,
+    ResourceBinding, Scalar, ScalarKind, StructMember, TypeInner, VectorSize,
 };
 use rspirv::binary::Assemble;
 use rspirv::dr::Builder;
@@ -122,6 +125,10 @@ impl TryFrom<&SpirvCompilation> for NagaReflect {
     type Error = ShaderReflectError;
 
     fn try_from(compile: &SpirvCompilation) -> Result<Self, Self::Error> {
+        if compile.compute.is_some() {
+            return Err(ShaderReflectError::UnsupportedComputePass);
+        }
+
         fn lower_fragment_shader(builder: &mut Builder) {
             let mut pass = lower_samplers::LowerCombinedImageSamplerPass::new(builder);
             pass.ensure_op_type_sampler();
@@ -193,6 +200,7 @@ impl ValidateTypeSemantics<&TypeInner> for UniqueSemantics {
                 }
             }
             UniqueSemantics::FloatParameter
+            | UniqueSemantics::SceneLuminance
             | UniqueSemantics::OriginalFPS
             | UniqueSemantics::OriginalAspectRotated
             | UniqueSemantics::OriginalAspect => {
@@ -681,42 +689,14 @@ impl NagaReflect {
 
                 match &parameter.semantics {
                     UniqueSemantics::FloatParameter => {
-                        let offset = member.offset;
-                        if let Some(meta) = meta.parameter_meta.get_mut::<str>(name.as_ref()) {
-                            if let Some(expected) = meta
-                                .offset
-                                .offset(offset_type)
-                                .filter(|expected| *expected != offset as usize)
-                            {
-                                return Err(ShaderReflectError::MismatchedOffset {
-                                    semantic: name,
-                                    expected,
-                                    received: offset as usize,
-                                    ty: offset_type,
-                                    pass: pass_number,
-                                });
-                            }
-                            if meta.size != typeinfo.size {
-                                return Err(ShaderReflectError::MismatchedSize {
-                                    semantic: name,
-                                    vertex: meta.size,
-                                    fragment: typeinfo.size,
-                                    pass: pass_number,
-                                });
-                            }
-
-                            *meta.offset.offset_mut(offset_type) = Some(offset as usize);
-                        } else {
-                            let name = ShortString::from(name);
-                            meta.parameter_meta.insert(
-                                name.clone(),
-                                VariableMeta {
-                                    id: name,
-                                    offset: MemberOffset::new(offset as usize, offset_type),
-                                    size: typeinfo.size,
-                                },
-                            );
-                        }
+                        insert_parameter_meta(
+                            meta,
+                            &name,
+                            member.offset as usize,
+                            typeinfo.size,
+                            offset_type,
+                            pass_number,
+                        )?;
                     }
                     semantics => {
                         let offset = member.offset;
@@ -789,6 +769,7 @@ impl NagaReflect {
                     meta.stage_mask.insert(match blame {
                         SemanticErrorBlame::Vertex => BindingStage::VERTEX,
                         SemanticErrorBlame::Fragment => BindingStage::FRAGMENT,
+                        SemanticErrorBlame::Compute => BindingStage::COMPUTE,
                     });
 
                     *meta.offset.offset_mut(offset_type) = Some(offset as usize);
@@ -800,11 +781,23 @@ impl NagaReflect {
                             stage_mask: match blame {
                                 SemanticErrorBlame::Vertex => BindingStage::VERTEX,
                                 SemanticErrorBlame::Fragment => BindingStage::FRAGMENT,
+                                SemanticErrorBlame::Compute => BindingStage::COMPUTE,
                             },
                             id: ShortString::from(name),
                         },
                     );
                 }
+            } else if let Some(layout) = Self::parameter_array_layout(module, member_type) {
+                insert_parameter_array_meta(
+                    meta,
+                    semantics,
+                    &name,
+                    layout,
+                    member.offset as usize,
+                    offset_type,
+                    pass_number,
+                    blame,
+                )?;
             } else {
                 return Err(blame.error(SemanticsErrorKind::UnknownSemantics(name)));
             }
@@ -812,6 +805,51 @@ impl NagaReflect {
         Ok(())
     }
 
+    /// Get the layout of a member that binds an array of float parameters, if it can.
+    fn parameter_array_layout(module: &Module, ty: &TypeInner) -> Option<ParameterArrayLayout> {
+        const FLOAT: Scalar = Scalar {
+            kind: ScalarKind::Float,
+            width: 4,
+        };
+
+        match *ty {
+            TypeInner::Matrix {
+                columns,
+                rows,
+                scalar: FLOAT,
+            } => Some(ParameterArrayLayout {
+                elements: columns as u32,
+                components: rows as u32,
+                // columns are aligned like vectors of the same size.
+                stride: match rows {
+                    VectorSize::Bi => 8,
+                    VectorSize::Tri | VectorSize::Quad => 16,
+                },
+            }),
+            TypeInner::Array {
+                base,
+                size: ArraySize::Constant(elements),
+                stride,
+            } => {
+                let components = match module.types[base].inner {
+                    TypeInner::Scalar(FLOAT) => 1,
+                    TypeInner::Vector {
+                        size,
+                        scalar: FLOAT,
+                    } => size as u32,
+                    _ => return None,
+                };
+
+                Some(ParameterArrayLayout {
+                    elements: elements.get(),
+                    components,
+                    stride,
+                })
+            }
+            _ => None,
+        }
+    }
+
     fn reflect_texture<'a>(
         &'a self,
         texture: &'a GlobalVariable,
@@ -1014,6 +1052,7 @@ impl ReflectShader for NagaReflect {
             ubo,
             push_constant,
             meta,
+            compute: None,
         })
     }
 
@@ -45,6 +45,7 @@ impl CompileShader<SPIRV> for NagaReflect {
         Ok(ShaderCompilerOutput {
             vertex,
             fragment,
+            compute: None,
             context: NagaSpirvContext {
                 fragment: self.fragment,
                 vertex: self.vertex,
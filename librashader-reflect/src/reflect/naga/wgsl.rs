@@ -32,6 +32,7 @@ impl CompileShader<WGSL> for NagaReflect {
         Ok(ShaderCompilerOutput {
             vertex,
             fragment,
+            compute: None,
             context: NagaWgslContext {
                 fragment: self.fragment,
                 vertex: self.vertex,
@@ -236,7 +236,12 @@ impl ShaderSemantics {
             .get(index)
             .ok_or_else(|| PreprocessError::InvalidStage)?;
 
-        let source = ShaderSource::load(&config.path, preset.features)?;
+        let source = ShaderSource::load_with_defines(
+            &config.path,
+            preset.features,
+            &config.meta.defines,
+            None,
+        )?;
 
         for parameter in source.parameters.values() {
             uniform_semantics.insert(
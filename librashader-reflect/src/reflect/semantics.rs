@@ -1,5 +1,6 @@
 use bitflags::bitflags;
 use librashader_common::map::{FastHashMap, ShortString};
+use librashader_common::Size;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
@@ -66,6 +67,18 @@ pub enum UniqueSemantics {
     /// A user defined float parameter.
     // float, user defined parameter, array
     FloatParameter = 12,
+    // float, read back from the scene analysis of a previous frame
+    /// The log-average luminance of the original input.
+    ///
+    /// This is read back from the built-in scene analysis of a previous frame, so it lags
+    /// behind the input by the number of frames in flight. Runtimes that do not support
+    /// scene analysis bind a constant middle grey.
+    SceneLuminance = 13,
+    // vec4, jitter offset of the current frame
+    /// The sub-pixel jitter offset of the original input for the current frame.
+    ///
+    /// `xy` is the offset in pixels, and `zw` is the offset in normalized texture coordinates.
+    FrameJitter = 14,
 }
 
 impl UniqueSemantics {
@@ -89,6 +102,8 @@ impl UniqueSemantics {
             UniqueSemantics::TotalSubFrames => UniformType::Unsigned,
             UniqueSemantics::CurrentSubFrame => UniformType::Unsigned,
             UniqueSemantics::FloatParameter => UniformType::Float,
+            UniqueSemantics::SceneLuminance => UniformType::Float,
+            UniqueSemantics::FrameJitter => UniformType::Vec4,
             UniqueSemantics::FrameTimeDelta => UniformType::Unsigned,
             UniqueSemantics::OriginalFPS => UniformType::Float,
             UniqueSemantics::OriginalAspect => UniformType::Float,
@@ -108,6 +123,8 @@ impl UniqueSemantics {
             UniqueSemantics::TotalSubFrames => "TotalSubFrames",
             UniqueSemantics::CurrentSubFrame => "CurrentSubFrame",
             UniqueSemantics::FloatParameter => "FloatParameter",
+            UniqueSemantics::SceneLuminance => "SceneLuminance",
+            UniqueSemantics::FrameJitter => "FrameJitter",
             UniqueSemantics::FrameTimeDelta => "FrameTimeDelta",
             UniqueSemantics::OriginalFPS => "OriginalFPS",
             UniqueSemantics::OriginalAspect => "OriginalAspect",
@@ -223,6 +240,7 @@ bitflags! {
         const NONE = 0b00000000;
         const VERTEX = 0b00000001;
         const FRAGMENT = 0b00000010;
+        const COMPUTE = 0b00000100;
     }
 }
 
@@ -338,6 +356,32 @@ pub struct ShaderReflection {
     pub push_constant: Option<BufferReflection<Option<u32>>>,
     /// Metadata about the bindings required for this shader.
     pub meta: BindingMeta,
+    /// Reflection information about the compute stage, if this is a compute pass.
+    pub compute: Option<ComputeReflection>,
+}
+
+/// Reflection information about a compute pass.
+///
+/// Compute passes write their output to a storage image instead of rendering a quad,
+/// and are dispatched with enough workgroups to cover the output.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComputeReflection {
+    /// The number of invocations in a workgroup along the X, Y and Z axes.
+    pub workgroup_size: [u32; 3],
+    /// The binding index of the storage image the pass writes its output to.
+    pub output_binding: u32,
+}
+
+impl ComputeReflection {
+    /// The number of workgroups to dispatch to cover an output of the given size.
+    pub fn workgroup_count(&self, size: Size<u32>) -> [u32; 3] {
+        [
+            size.width.div_ceil(self.workgroup_size[0]),
+            size.height.div_ceil(self.workgroup_size[1]),
+            1,
+        ]
+    }
 }
 
 /// Metadata about a uniform variable.
@@ -495,6 +539,14 @@ impl UniqueSemanticMap for FastHashMap<ShortString, UniformSemantic> {
                     semantics: UniqueSemantics::FrameTimeDelta,
                     index: (),
                 }),
+                "SceneLuminance" => Some(Semantic {
+                    semantics: UniqueSemantics::SceneLuminance,
+                    index: (),
+                }),
+                "FrameJitter" => Some(Semantic {
+                    semantics: UniqueSemantics::FrameJitter,
+                    index: (),
+                }),
                 _ => None,
             },
             Some(UniformSemantic::Unique(variable)) => Some(*variable),
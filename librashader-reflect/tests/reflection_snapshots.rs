@@ -0,0 +1,276 @@
+//! Snapshot tests for reflection output.
+//!
+//! Reflection output is part of the public API of librashader-reflect (see the crate
+//! documentation), so every change to a snapshot in `tests/snapshots` must be deliberate.
+//!
+//! Set `LIBRASHADER_UPDATE_SNAPSHOTS=1` to rewrite the snapshots from the current output.
+use std::error::Error;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use librashader_common::ScalingKernel;
+use librashader_pack::{PassResource, ShaderPresetPack};
+use librashader_presets::{ShaderFeatures, ShaderPreset};
+use librashader_reflect::back::targets::SPIRV;
+use librashader_reflect::back::FromCompilation;
+use librashader_reflect::error::ShaderReflectError;
+use librashader_reflect::front::SpirvCompilation;
+use librashader_reflect::reflect::cross::SpirvCross;
+use librashader_reflect::reflect::naga::Naga;
+use librashader_reflect::reflect::presets::CompilePresetTarget;
+use librashader_reflect::reflect::semantics::{MemberOffset, ShaderSemantics};
+use librashader_reflect::reflect::{ReflectShader, ShaderReflection};
+
+type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
+
+const UPDATE_SNAPSHOTS: &str = "LIBRASHADER_UPDATE_SNAPSHOTS";
+
+fn load_preset(path: &str, features: ShaderFeatures) -> Result<Vec<PassResource>> {
+    let preset = ShaderPreset::try_parse(path, features)?;
+    let pack = ShaderPresetPack::load_from_preset::<Box<dyn Error + Send + Sync>>(preset)?;
+    Ok(pack.passes)
+}
+
+fn identity_passes() -> Vec<PassResource> {
+    ScalingKernel::ALL.map(PassResource::identity).to_vec()
+}
+
+fn reflect_passes<R>(passes: Vec<PassResource>) -> Result<Vec<ShaderReflection>>
+where
+    SPIRV: FromCompilation<SpirvCompilation, R>,
+    <SPIRV as FromCompilation<SpirvCompilation, R>>::Output: ReflectShader,
+{
+    let (passes, semantics): (_, ShaderSemantics) = SPIRV::compile_preset_passes::<
+        SpirvCompilation,
+        R,
+        Box<dyn Error + Send + Sync>,
+    >(passes, std::iter::empty())?;
+
+    let mut reflections = Vec::new();
+    for (index, (_, mut reflect)) in passes.into_iter().enumerate() {
+        reflections.push(reflect.reflect(index, &semantics)?);
+    }
+    Ok(reflections)
+}
+
+fn offset(offset: &MemberOffset) -> String {
+    match (offset.ubo, offset.push) {
+        (Some(ubo), Some(push)) => format!("ubo+{ubo} push+{push}"),
+        (Some(ubo), None) => format!("ubo+{ubo}"),
+        (None, Some(push)) => format!("push+{push}"),
+        (None, None) => String::from("unbound"),
+    }
+}
+
+/// Render the reflection of each pass in a stable, sorted, line-based format.
+fn render(reflections: &[ShaderReflection]) -> String {
+    let mut out = String::new();
+    for (index, reflection) in reflections.iter().enumerate() {
+        writeln!(out, "pass {index}").unwrap();
+        if let Some(ubo) = &reflection.ubo {
+            writeln!(
+                out,
+                "  ubo binding={} size={} stages={:?}",
+                ubo.binding, ubo.size, ubo.stage_mask
+            )
+            .unwrap();
+        }
+        if let Some(push) = &reflection.push_constant {
+            writeln!(
+                out,
+                "  push binding={:?} size={} stages={:?}",
+                push.binding, push.size, push.stage_mask
+            )
+            .unwrap();
+        }
+        if let Some(compute) = &reflection.compute {
+            writeln!(
+                out,
+                "  compute workgroup_size={:?} output_binding={}",
+                compute.workgroup_size, compute.output_binding
+            )
+            .unwrap();
+        }
+
+        let meta = &reflection.meta;
+
+        let mut params: Vec<_> = meta.parameter_meta.values().collect();
+        params.sort_by(|a, b| a.id.cmp(&b.id));
+        for param in params {
+            writeln!(
+                out,
+                "  param {} {} size={}",
+                param.id,
+                offset(&param.offset),
+                param.size
+            )
+            .unwrap();
+        }
+
+        let mut unique: Vec<_> = meta.unique_meta.iter().collect();
+        unique.sort_by_key(|(semantics, _)| **semantics);
+        for (semantics, variable) in unique {
+            writeln!(
+                out,
+                "  unique {semantics:?} {} {} size={}",
+                variable.id,
+                offset(&variable.offset),
+                variable.size
+            )
+            .unwrap();
+        }
+
+        let mut textures: Vec<_> = meta.texture_meta.iter().collect();
+        textures.sort_by_key(|(semantic, _)| (semantic.semantics, semantic.index));
+        for (semantic, texture) in textures {
+            writeln!(
+                out,
+                "  texture {:?}[{}] binding={}",
+                semantic.semantics, semantic.index, texture.binding
+            )
+            .unwrap();
+        }
+
+        let mut sizes: Vec<_> = meta.texture_size_meta.iter().collect();
+        sizes.sort_by_key(|(semantic, _)| (semantic.semantics, semantic.index));
+        for (semantic, size) in sizes {
+            writeln!(
+                out,
+                "  texture_size {:?}[{}] {} {} stages={:?}",
+                semantic.semantics,
+                semantic.index,
+                size.id,
+                offset(&size.offset),
+                size.stage_mask
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{name}.snap"))
+}
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    if std::env::var_os(UPDATE_SNAPSHOTS).is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "could not read snapshot {}: {e}; run with {UPDATE_SNAPSHOTS}=1 to create it",
+            path.display()
+        )
+    });
+
+    assert_eq!(
+        expected.replace("\r\n", "\n"),
+        actual,
+        "reflection output for {name} changed; if this is intended, run with {UPDATE_SNAPSHOTS}=1 \
+         and note the change in the release notes"
+    );
+}
+
+/// Snapshot the reflection of the passes with both reflection backends.
+///
+/// The backends are snapshotted separately because they do not agree on every detail,
+/// for example naga assigns a binding to the push constant block.
+fn check(name: &str, passes: impl Fn() -> Result<Vec<PassResource>>) {
+    let cross = render(&reflect_passes::<SpirvCross>(passes().unwrap()).unwrap());
+    assert_snapshot(&format!("{name}.cross"), &cross);
+
+    let naga = render(&reflect_passes::<Naga>(passes().unwrap()).unwrap());
+    assert_snapshot(&format!("{name}.naga"), &naga);
+}
+
+#[test]
+fn basic() {
+    check("basic", || {
+        load_preset("../test/basic.slangp", ShaderFeatures::NONE)
+    });
+}
+
+#[test]
+fn null() {
+    check("null", || {
+        load_preset("../test/null.slangp", ShaderFeatures::NONE)
+    });
+}
+
+#[test]
+fn aspect() {
+    check("aspect", || {
+        load_preset("../test/aspect.slangp", ShaderFeatures::NONE)
+    });
+}
+
+#[test]
+fn aspect_with_features() {
+    check("aspect_features", || {
+        load_preset(
+            "../test/aspect.slangp",
+            ShaderFeatures::ORIGINAL_ASPECT_UNIFORMS | ShaderFeatures::FRAMETIME_UNIFORMS,
+        )
+    });
+}
+
+#[test]
+fn scene_luminance() {
+    check("scene_luminance", || {
+        load_preset("../test/scene_luminance.slangp", ShaderFeatures::NONE)
+    });
+}
+
+#[test]
+fn jitter() {
+    check("jitter", || {
+        load_preset("../test/jitter.slangp", ShaderFeatures::NONE)
+    });
+}
+
+#[test]
+fn parameter_arrays() {
+    check("parameter_arrays", || {
+        load_preset("../test/parameter_arrays.slangp", ShaderFeatures::NONE)
+    });
+}
+
+#[test]
+fn identity() {
+    check("identity", || Ok(identity_passes()));
+}
+
+#[test]
+fn color_assist() {
+    check("color_assist", || Ok(vec![PassResource::color_assist(0)]));
+}
+
+#[test]
+fn black_frame_insertion() {
+    check("black_frame_insertion", || {
+        Ok(vec![PassResource::black_frame_insertion(0)])
+    });
+}
+
+/// Compute passes are only supported with SPIRV-Cross reflection.
+#[test]
+fn compute() {
+    let passes = || load_preset("../test/compute.slangp", ShaderFeatures::NONE);
+    let cross = render(&reflect_passes::<SpirvCross>(passes().unwrap()).unwrap());
+    assert_snapshot("compute.cross", &cross);
+
+    let Err(err) = reflect_passes::<Naga>(passes().unwrap()) else {
+        panic!("naga reflection of a compute pass should fail");
+    };
+    assert!(err
+        .downcast_ref::<ShaderReflectError>()
+        .is_some_and(|err| matches!(err, ShaderReflectError::UnsupportedComputePass)));
+}
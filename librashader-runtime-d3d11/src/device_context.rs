@@ -0,0 +1,43 @@
+use crate::error;
+use crate::luts::LutBundleD3D11;
+use crate::samplers::SamplerSet;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use windows::Win32::Graphics::Direct3D11::ID3D11Device;
+
+/// Device-level resources that can be shared between filter chains created on the same device
+/// with the `device_context` option.
+///
+/// A device context owns the sampler states of the filter chains, and a [`LutBundleD3D11`] for
+/// their LUT textures, so that frontends that run several filter chains at once do not create
+/// duplicate objects for each of them. Clones of a device context share the same resources.
+#[derive(Clone)]
+pub struct DeviceContextD3D11 {
+    pub(crate) samplers: Arc<SamplerSet>,
+    pub(crate) luts: LutBundleD3D11,
+}
+
+impl DeviceContextD3D11 {
+    /// Create a device context for the given device.
+    pub fn new(device: &ID3D11Device) -> error::Result<Self> {
+        Ok(Self {
+            samplers: Arc::new(SamplerSet::new(device)?),
+            luts: LutBundleD3D11::new(),
+        })
+    }
+
+    /// The bundle of LUT textures shared by filter chains created with this device context.
+    ///
+    /// Textures can be preloaded into the bundle before any filter chain is created.
+    pub fn lut_bundle(&self) -> &LutBundleD3D11 {
+        &self.luts
+    }
+}
+
+impl Debug for DeviceContextD3D11 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeviceContextD3D11")
+            .field("luts", &self.luts)
+            .finish_non_exhaustive()
+    }
+}
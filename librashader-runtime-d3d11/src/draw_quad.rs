@@ -2,6 +2,7 @@ use crate::error;
 use crate::error::assume_d3d11_init;
 use array_concat::concat_arrays;
 use bytemuck::offset_of;
+use librashader_runtime::mesh::OutputMesh;
 use librashader_runtime::quad::{QuadType, VertexInput};
 use windows::core::PCSTR;
 use windows::Win32::Graphics::Direct3D::D3D11_PRIMITIVE_TOPOLOGY_TRIANGLESTRIP;
@@ -55,6 +56,7 @@ static VBO_DATA: &[VertexInput; 8] = &concat_arrays!(OFFSCREEN_VBO_DATA, FINAL_V
 pub(crate) struct DrawQuad {
     stride: u32,
     vbo: ID3D11Buffer,
+    mesh: Option<(ID3D11Buffer, u32)>,
 }
 
 impl DrawQuad {
@@ -82,10 +84,49 @@ impl DrawQuad {
             Ok(DrawQuad {
                 vbo,
                 stride: std::mem::size_of::<VertexInput>() as u32,
+                mesh: None,
             })
         }
     }
 
+    /// Set the mesh to draw the final pass with, or reset it to the default quad.
+    pub fn set_mesh(
+        &mut self,
+        device: &ID3D11Device,
+        mesh: Option<&OutputMesh>,
+    ) -> error::Result<()> {
+        let Some(mesh) = mesh else {
+            self.mesh = None;
+            return Ok(());
+        };
+
+        // the clip space of Direct3D points up.
+        let vertices = mesh.flipped_vertices(false);
+        unsafe {
+            let mut vbo = None;
+            device.CreateBuffer(
+                &D3D11_BUFFER_DESC {
+                    ByteWidth: std::mem::size_of_val(vertices.as_slice()) as u32,
+                    Usage: D3D11_USAGE_IMMUTABLE,
+                    BindFlags: D3D11_BIND_VERTEX_BUFFER.0 as u32,
+                    CPUAccessFlags: Default::default(),
+                    MiscFlags: Default::default(),
+                    StructureByteStride: 0,
+                },
+                Some(&D3D11_SUBRESOURCE_DATA {
+                    pSysMem: vertices.as_ptr().cast(),
+                    SysMemPitch: 0,
+                    SysMemSlicePitch: 0,
+                }),
+                Some(&mut vbo),
+            )?;
+            assume_d3d11_init!(vbo, "CreateBuffer");
+            self.mesh = Some((vbo, mesh.vertex_count()));
+        }
+
+        Ok(())
+    }
+
     pub fn bind_vbo_for_frame(&self, context: &ID3D11DeviceContext) {
         unsafe {
             context.IASetPrimitiveTopology(D3D11_PRIMITIVE_TOPOLOGY_TRIANGLESTRIP);
@@ -101,6 +142,22 @@ impl DrawQuad {
     }
 
     pub fn draw_quad(&self, context: &ID3D11DeviceContext, vbo_type: QuadType) {
+        if let (QuadType::Final, Some((mesh, count))) = (&vbo_type, &self.mesh) {
+            unsafe {
+                context.IASetVertexBuffers(
+                    0,
+                    1,
+                    Some(&Some(mesh.clone())),
+                    Some(&self.stride),
+                    Some(&0),
+                );
+                context.Draw(*count, 0);
+            }
+            // the quad is bound again for the passes drawn after the final pass.
+            self.bind_vbo_for_frame(context);
+            return;
+        }
+
         let offset = match vbo_type {
             QuadType::Offscreen => 0,
             QuadType::Final => 4,
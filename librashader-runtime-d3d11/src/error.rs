@@ -1,10 +1,12 @@
 //! Direct3D 11 shader runtime errors.
 //!
+use librashader_common::ImageFormat;
 use librashader_preprocess::PreprocessError;
 use librashader_presets::ParsePresetError;
 use librashader_reflect::error::{ShaderCompileError, ShaderReflectError};
 use librashader_runtime::image::ImageError;
 use thiserror::Error;
+use windows::Win32::Graphics::Direct3D::D3D_SRV_DIMENSION;
 
 /// Cumulative error type for Direct3D11 filter chains.
 #[derive(Error, Debug)]
@@ -24,6 +26,18 @@ pub enum FilterChainError {
     ShaderReflectError(#[from] ShaderReflectError),
     #[error("lut loading error")]
     LutLoadError(#[from] ImageError),
+    #[error("invalid input view dimension {0:?}, expected D3D11_SRV_DIMENSION_TEXTURE2D")]
+    InvalidViewDimensionError(D3D_SRV_DIMENSION),
+    #[error("input texture was not created with D3D11_BIND_SHADER_RESOURCE")]
+    InvalidBindFlagsError,
+    #[error("the framebuffer format {0:?} is not supported by the device")]
+    UnsupportedFormat(ImageFormat),
+    #[error("filter chain creation was cancelled")]
+    Cancelled,
+    #[error("could not spawn the worker thread to create the filter chain on")]
+    WorkerThread(#[source] std::io::Error),
+    #[error("the keyed mutex of the input can not be acquired on a deferred context")]
+    KeyedMutexOnDeferredContext,
 }
 
 macro_rules! assume_d3d11_init {
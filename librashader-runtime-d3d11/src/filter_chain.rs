@@ -1,5 +1,5 @@
-use crate::texture::InputTexture;
-use librashader_common::{ImageFormat, Size, Viewport};
+use crate::texture::{InputTexture, KeyedMutexGuard};
+use librashader_common::{FormatFallback, ImageFormat, ScalingKernel, Size, Viewport};
 
 use librashader_common::map::FastHashMap;
 use librashader_presets::{ShaderFeatures, ShaderPreset};
@@ -8,69 +8,98 @@ use librashader_reflect::back::{CompileReflectShader, CompileShader};
 use librashader_reflect::front::SpirvCompilation;
 use librashader_reflect::reflect::semantics::ShaderSemantics;
 use librashader_reflect::reflect::ReflectShader;
-use librashader_runtime::image::{ImageError, LoadedTexture, UVDirection, RGBA8};
+use librashader_runtime::instance::{self, LibrashaderInstance};
+use librashader_runtime::jitter;
+use librashader_runtime::pending::LocalPendingFilterChain;
+use librashader_runtime::progress::{CancellationToken, LoadStage, PassProgress, ProgressCallback};
+use librashader_runtime::rewind::RewindFrameCount;
 use std::collections::VecDeque;
 
 use std::path::Path;
+use std::sync::Arc;
 
 use crate::draw_quad::DrawQuad;
 use crate::error::{assume_d3d11_init, FilterChainError};
 use crate::filter_pass::{ConstantBufferBinding, FilterPass};
 use crate::framebuffer::OwnedImage;
 use crate::graphics_pipeline::D3D11State;
-use crate::luts::LutTexture;
+use crate::luts::{self, LutTexture};
 use crate::options::{FilterChainOptionsD3D11, FrameOptionsD3D11};
 use crate::samplers::SamplerSet;
 use crate::util::d3d11_compile_bound_shader;
-use crate::{error, util};
+use crate::{error, util, DeviceContextD3D11};
 use librashader_cache::cache_shader_object;
 use librashader_cache::CachedCompilation;
 use librashader_common::GetSize;
 use librashader_presets::context::VideoDriver;
 use librashader_reflect::reflect::cross::SpirvCross;
 use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
-use librashader_runtime::binding::{BindingUtil, TextureInput};
-use librashader_runtime::framebuffer::FramebufferInit;
+use librashader_runtime::binding::{BindingUtil, TextureInput, UniformBindings};
+use librashader_runtime::filter_pass::{
+    requested_format, FilterPassMeta, IdentityPasses, PassInfo,
+};
+use librashader_runtime::framebuffer::{FramebufferInit, HistoryDepth};
+use librashader_runtime::mesh::OutputMesh;
+use librashader_runtime::mvp;
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
 use librashader_runtime::scaling::ScaleFramebuffer;
+use librashader_runtime::statistics::{FrameStatistics, FrameStatisticsRecorder};
 use librashader_runtime::uniforms::UniformStorage;
+use librashader_runtime::warnings::OptionWarning;
 use rayon::prelude::*;
 use windows::Win32::Graphics::Direct3D11::{
     ID3D11Buffer, ID3D11Device, ID3D11DeviceContext, ID3D11RenderTargetView,
     ID3D11ShaderResourceView, D3D11_BIND_CONSTANT_BUFFER, D3D11_BUFFER_DESC,
-    D3D11_CPU_ACCESS_WRITE, D3D11_CREATE_DEVICE_SINGLETHREADED, D3D11_RESOURCE_MISC_GENERATE_MIPS,
-    D3D11_TEXTURE2D_DESC, D3D11_USAGE_DEFAULT, D3D11_USAGE_DYNAMIC,
+    D3D11_CPU_ACCESS_WRITE, D3D11_CREATE_DEVICE_SINGLETHREADED, D3D11_DEVICE_CONTEXT_DEFERRED,
+    D3D11_USAGE_DYNAMIC,
 };
-use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_R8G8B8A8_UNORM;
 
 /// A Direct3D 11 filter chain.
 pub struct FilterChainD3D11 {
     pub(crate) common: FilterCommon,
     passes: Vec<FilterPass>,
+    identity: IdentityPasses<FilterPass>,
+    pass_options: PassOptions,
     output_framebuffers: Box<[OwnedImage]>,
     feedback_framebuffers: Box<[OwnedImage]>,
     history_framebuffers: VecDeque<OwnedImage>,
+    history_depth: HistoryDepth,
+    option_warnings: Box<[OptionWarning]>,
     state: D3D11State,
     default_options: FrameOptionsD3D11,
     draw_last_pass_feedback: bool,
+    disable_temporal: bool,
+    rewind: RewindFrameCount,
+    invalidated_history: usize,
+    keyed_mutex: Option<(u64, u64)>,
+}
+
+/// The options that the shader passes were created with, to create the identity passes with
+/// when they are first used.
+#[derive(Clone, Copy)]
+struct PassOptions {
+    disable_cache: bool,
+    format_fallback: FormatFallback,
 }
 
 pub(crate) struct Direct3D11 {
-    pub(crate) _device: ID3D11Device,
+    pub(crate) device: ID3D11Device,
     pub(crate) immediate_context: ID3D11DeviceContext,
 }
 
 pub(crate) struct FilterCommon {
     pub(crate) d3d11: Direct3D11,
-    pub(crate) luts: FastHashMap<usize, LutTexture>,
-    pub samplers: SamplerSet,
+    pub(crate) luts: FastHashMap<usize, Arc<LutTexture>>,
+    pub samplers: Arc<SamplerSet>,
     pub output_textures: Box<[Option<InputTexture>]>,
     pub feedback_textures: Box<[Option<InputTexture>]>,
     pub history_textures: Box<[Option<InputTexture>]>,
     pub config: RuntimeParameters,
     pub disable_mipmaps: bool,
     pub(crate) draw_quad: DrawQuad,
+    pub(crate) frame_jitter: [f32; 2],
+    pub statistics: FrameStatisticsRecorder,
 }
 
 mod compile {
@@ -109,10 +138,64 @@ mod compile {
 }
 
 use compile::{compile_passes, ShaderPassMeta};
-use librashader_pack::{ShaderPresetPack, TextureResource};
+use librashader_pack::{PassResource, ShaderPresetPack};
 use librashader_runtime::parameters::RuntimeParameters;
 
+/// A shader preset with its passes compiled, to create a filter chain from on the thread that
+/// uses the immediate context.
+struct CompiledPreset {
+    preset: ShaderPresetPack,
+    passes: Vec<ShaderPassMeta>,
+    semantics: ShaderSemantics,
+}
+
+impl CompiledPreset {
+    /// Append the requested built-in passes to the preset, and compile its passes.
+    ///
+    /// This does not use the device, so it can be done on any thread.
+    fn compile(
+        mut preset: ShaderPresetPack,
+        disable_cache: bool,
+        color_assist: bool,
+        black_frame_insertion: bool,
+        progress: Option<&ProgressCallback>,
+        cancel: Option<&CancellationToken>,
+    ) -> error::Result<Self> {
+        if color_assist {
+            preset.append_color_assist();
+        }
+        if black_frame_insertion {
+            preset.append_black_frame_insertion();
+        }
+
+        let progress = PassProgress::new(progress, preset.passes.len()).with_cancellation(cancel);
+
+        let shaders = std::mem::take(&mut preset.passes);
+        let (passes, semantics) = compile_passes(shaders, &preset.textures, disable_cache)?;
+        progress.report_all(LoadStage::Preprocessing);
+        if progress.is_cancelled() {
+            return Err(FilterChainError::Cancelled);
+        }
+
+        Ok(CompiledPreset {
+            preset,
+            passes,
+            semantics,
+        })
+    }
+}
+
 impl FilterChainD3D11 {
+    /// Create a filter chain without any shader passes, that only scales the input to the viewport.
+    ///
+    /// This is equivalent to loading a preset without any shader passes.
+    pub unsafe fn new_identity(
+        device: &ID3D11Device,
+        options: Option<&FilterChainOptionsD3D11>,
+    ) -> error::Result<FilterChainD3D11> {
+        unsafe { Self::load_from_pack(ShaderPresetPack::empty(), device, options) }
+    }
+
     /// Load the shader preset at the given path into a filter chain.
     pub unsafe fn load_from_path(
         path: impl AsRef<Path>,
@@ -147,6 +230,54 @@ impl FilterChainD3D11 {
         unsafe { Self::load_from_pack_deferred(preset, device, &immediate_context, options) }
     }
 
+    /// Load a filter chain from a pre-parsed `ShaderPreset`, compiling its shaders on a worker
+    /// thread.
+    ///
+    /// Shaders are compiled without blocking the calling thread. The rest of the filter chain is
+    /// created on the thread that takes it from the returned [`LocalPendingFilterChain`], and
+    /// LUTs are uploaded on the immediate context of `device`. The progress callback of the
+    /// options is called on the worker thread while shaders are compiled, and on the taking
+    /// thread afterwards.
+    ///
+    /// Returns an error if the worker thread could not be spawned.
+    ///
+    /// ## Safety
+    /// Access to the immediate context when taking the filter chain requires external
+    /// synchronization, as for [`load_from_preset`](Self::load_from_preset).
+    pub unsafe fn load_from_preset_async(
+        preset: ShaderPreset,
+        device: &ID3D11Device,
+        options: Option<FilterChainOptionsD3D11>,
+    ) -> error::Result<LocalPendingFilterChain<error::Result<FilterChainD3D11>>> {
+        let disable_cache = options.as_ref().map_or(false, |o| o.disable_cache);
+        let color_assist = options.as_ref().map_or(false, |o| o.color_assist);
+        let black_frame_insertion = options.as_ref().map_or(false, |o| o.black_frame_insertion);
+        let progress = options.as_ref().and_then(|o| o.progress.clone());
+        let cancel = options.as_ref().and_then(|o| o.cancel.clone());
+        let instance = options.as_ref().and_then(|o| o.instance.clone());
+        let device = device.clone();
+
+        LocalPendingFilterChain::spawn(
+            move || {
+                let _instance = instance.as_ref().map(LibrashaderInstance::enter);
+                let preset = ShaderPresetPack::load_from_preset::<FilterChainError>(preset)?;
+                CompiledPreset::compile(
+                    preset,
+                    disable_cache,
+                    color_assist,
+                    black_frame_insertion,
+                    progress.as_ref(),
+                    cancel.as_ref(),
+                )
+            },
+            move |compiled: error::Result<CompiledPreset>| unsafe {
+                let immediate_context = device.GetImmediateContext()?;
+                Self::load_from_compiled(compiled?, &device, &immediate_context, options.as_ref())
+            },
+        )
+        .map_err(FilterChainError::WorkerThread)
+    }
+
     /// Load a filter chain from a pre-parsed `ShaderPreset`, deferring and GPU-side initialization
     /// to the caller. This function is therefore requires no external synchronization of the
     /// immediate context, as long as the immediate context is not used as the input context,
@@ -193,19 +324,84 @@ impl FilterChainD3D11 {
         ctx: &ID3D11DeviceContext,
         options: Option<&FilterChainOptionsD3D11>,
     ) -> error::Result<FilterChainD3D11> {
+        let _instance = options
+            .and_then(|o| o.instance.as_ref())
+            .map(LibrashaderInstance::enter);
+        let compiled = CompiledPreset::compile(
+            preset,
+            options.map_or(false, |o| o.disable_cache),
+            options.map_or(false, |o| o.color_assist),
+            options.map_or(false, |o| o.black_frame_insertion),
+            options.and_then(|o| o.progress.as_ref()),
+            options.and_then(|o| o.cancel.as_ref()),
+        )?;
+        unsafe { Self::load_from_compiled(compiled, device, ctx, options) }
+    }
+
+    /// Load a filter chain from a compiled `ShaderPresetPack`, recording the upload of LUTs
+    /// onto `ctx` as for [`load_from_pack_deferred`](Self::load_from_pack_deferred).
+    unsafe fn load_from_compiled(
+        compiled: CompiledPreset,
+        device: &ID3D11Device,
+        ctx: &ID3D11DeviceContext,
+        options: Option<&FilterChainOptionsD3D11>,
+    ) -> error::Result<FilterChainD3D11> {
+        let CompiledPreset {
+            preset,
+            passes,
+            semantics,
+        } = compiled;
         let disable_cache = options.map_or(false, |o| o.disable_cache);
+        let instance = options.and_then(|o| o.instance.as_ref());
+        let _instance = instance.map(LibrashaderInstance::enter);
+
+        let progress = PassProgress::new(options.and_then(|o| o.progress.as_ref()), passes.len())
+            .with_cancellation(options.and_then(|o| o.cancel.as_ref()));
+
+        let device_context = options.and_then(|o| o.device_context.as_ref());
+        let samplers = match device_context {
+            Some(context) => Arc::clone(&context.samplers),
+            None => Arc::new(SamplerSet::new(device)?),
+        };
 
-        let (passes, semantics) = compile_passes(preset.passes, &preset.textures, disable_cache)?;
+        let parameters = RuntimeParameters::new(
+            preset.pass_count as usize,
+            preset.parameters,
+            passes
+                .iter()
+                .flat_map(|(pass, _)| pass.data.parameters.values()),
+        );
 
-        let samplers = SamplerSet::new(device)?;
+        let pass_options = PassOptions {
+            disable_cache,
+            format_fallback: options.map_or(FormatFallback::default(), |o| {
+                FormatFallback::from(o.format_fallback)
+            }),
+        };
 
         // initialize passes
-        let filters = FilterChainD3D11::init_passes(device, passes, &semantics, disable_cache)?;
+        let filters = FilterChainD3D11::init_passes(
+            device,
+            passes,
+            &semantics,
+            &parameters,
+            disable_cache,
+            pass_options.format_fallback,
+            progress,
+            instance,
+        )?;
 
         let immediate_context = unsafe { device.GetImmediateContext()? };
 
         // load luts
-        let luts = FilterChainD3D11::load_luts(device, &ctx, preset.textures)?;
+        let luts = luts::load_luts(
+            device,
+            ctx,
+            preset.textures,
+            options
+                .and_then(|o| o.lut_bundle.as_ref())
+                .or(device_context.map(DeviceContextD3D11::lut_bundle)),
+        )?;
 
         let framebuffer_gen =
             || OwnedImage::new(device, Size::new(1, 1), ImageFormat::R8G8B8A8Unorm, false);
@@ -214,7 +410,15 @@ impl FilterChainD3D11 {
             filters.iter().map(|f| &f.reflection.meta),
             &framebuffer_gen,
             &input_gen,
-        );
+        )
+        .with_max_history(options.map_or(0, |o| o.max_history as usize))
+        .with_temporal_disabled(options.map_or(false, |o| o.disable_temporal));
+        let history_depth = framebuffer_init.history_depth();
+        let mut option_warnings = framebuffer_init.option_warnings();
+        option_warnings.extend(OptionWarning::check_mipmap_input(
+            options.map_or(false, |o| o.force_no_mipmaps),
+            filters.iter().map(|f| f.meta.mipmap_input),
+        ));
 
         // initialize output framebuffers
         let (output_framebuffers, output_textures) = framebuffer_init.init_output_framebuffers()?;
@@ -230,16 +434,28 @@ impl FilterChainD3D11 {
         let state = D3D11State::new(device)?;
         Ok(FilterChainD3D11 {
             draw_last_pass_feedback: framebuffer_init.uses_final_pass_as_feedback(),
+            disable_temporal: options.map_or(false, |o| o.disable_temporal),
+            rewind: RewindFrameCount::new(
+                options.map_or(false, |o| o.freeze_frame_count_on_rewind),
+            ),
+            invalidated_history: 0,
             passes: filters,
+            identity: IdentityPasses::default(),
+            pass_options,
             output_framebuffers,
             feedback_framebuffers,
             history_framebuffers,
+            history_depth,
+            option_warnings: option_warnings.into_boxed_slice(),
             common: FilterCommon {
+                statistics: FrameStatisticsRecorder::new(
+                    options.map_or(false, |o| o.collect_statistics),
+                ),
                 d3d11: Direct3D11 {
-                    _device: device.clone(),
+                    device: device.clone(),
                     immediate_context,
                 },
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                config: parameters,
                 disable_mipmaps: options.map_or(false, |o| o.force_no_mipmaps),
                 luts,
                 samplers,
@@ -247,9 +463,13 @@ impl FilterChainD3D11 {
                 feedback_textures,
                 history_textures,
                 draw_quad,
+                frame_jitter: [0.0, 0.0],
             },
             state,
             default_options: Default::default(),
+            keyed_mutex: options
+                .filter(|o| o.use_keyed_mutex)
+                .map(|o| (o.keyed_mutex_acquire_key, o.keyed_mutex_release_key)),
         })
     }
 }
@@ -275,18 +495,55 @@ impl FilterChainD3D11 {
         }
     }
 
+    /// Create the identity pass that scales its input to the viewport with the given kernel.
+    fn init_identity(
+        device: &ID3D11Device,
+        parameters: &RuntimeParameters,
+        options: PassOptions,
+        kernel: ScalingKernel,
+    ) -> error::Result<FilterPass> {
+        let (passes, semantics) = compile_passes(
+            vec![PassResource::identity(kernel)],
+            &[],
+            options.disable_cache,
+        )?;
+
+        let pass = Self::init_passes(
+            device,
+            passes,
+            &semantics,
+            parameters,
+            options.disable_cache,
+            options.format_fallback,
+            PassProgress::default(),
+            None,
+        )?
+        .remove(0);
+
+        Ok(pass)
+    }
+
     fn init_passes(
         device: &ID3D11Device,
         passes: Vec<ShaderPassMeta>,
         semantics: &ShaderSemantics,
+        parameters: &RuntimeParameters,
         disable_cache: bool,
+        format_fallback: FormatFallback,
+        progress: PassProgress,
+        instance: Option<&LibrashaderInstance>,
     ) -> error::Result<Vec<FilterPass>> {
         let device_is_singlethreaded =
             unsafe { (device.GetCreationFlags() & D3D11_CREATE_DEVICE_SINGLETHREADED.0) == 1 };
 
         let builder_fn = |(index, (config, mut reflect)): (usize, ShaderPassMeta)| {
+            if progress.is_cancelled() {
+                return Err(FilterChainError::Cancelled);
+            }
+
             let reflection = reflect.reflect(index, semantics)?;
             let hlsl = reflect.compile(None)?;
+            progress.report(LoadStage::Compiling, index);
 
             let (vs, vertex_dxbc) = cache_shader_object(
                 "dxbc",
@@ -318,6 +575,7 @@ impl FilterChainD3D11 {
                 },
                 disable_cache,
             )?;
+            progress.report(LoadStage::Pipeline, index);
 
             let ubo_cbuffer =
                 if let Some(ubo) = &reflection.ubo.as_ref().filter(|ubo| ubo.size != 0) {
@@ -356,7 +614,15 @@ impl FilterChainD3D11 {
                     .map_or(0, |push| push.size as usize),
             );
 
-            let uniform_bindings = reflection.meta.create_binding_map(|param| param.offset());
+            let uniform_bindings = UniformBindings::new(
+                reflection.meta.create_binding_map(|param| param.offset()),
+                parameters,
+            );
+
+            let requested_format = requested_format(&config.meta, config.data.format);
+            let format =
+                util::d3d11_select_supported_format(device, requested_format, format_fallback)
+                    .ok_or(FilterChainError::UnsupportedFormat(requested_format))?;
 
             Ok(FilterPass {
                 reflection,
@@ -369,6 +635,8 @@ impl FilterChainD3D11 {
                 push_buffer: push_cbuffer,
                 source: config.data,
                 meta: config.meta,
+                path: config.path,
+                format,
             })
         };
 
@@ -377,7 +645,9 @@ impl FilterChainD3D11 {
             passes.into_iter().enumerate().map(builder_fn).collect()
         } else {
             // D3D11Device is thread safe
-            passes.into_par_iter().enumerate().map(builder_fn).collect()
+            instance::install(instance, || {
+                passes.into_par_iter().enumerate().map(builder_fn).collect()
+            })
         };
 
         let filters: error::Result<Vec<FilterPass>> = filters.into_iter().collect();
@@ -398,45 +668,56 @@ impl FilterChainD3D11 {
         Ok(())
     }
 
-    fn load_luts(
-        device: &ID3D11Device,
-        context: &ID3D11DeviceContext,
-        textures: Vec<TextureResource>,
-    ) -> error::Result<FastHashMap<usize, LutTexture>> {
-        let mut luts = FastHashMap::default();
-        let textures = textures
-            .into_par_iter()
-            .map(|texture| LoadedTexture::from_texture(texture, UVDirection::TopLeft))
-            .collect::<Result<Vec<LoadedTexture<RGBA8>>, ImageError>>()?;
-
-        for (index, LoadedTexture { meta, image }) in textures.iter().enumerate() {
-            let desc = D3D11_TEXTURE2D_DESC {
-                Width: image.size.width,
-                Height: image.size.height,
-                Format: DXGI_FORMAT_R8G8B8A8_UNORM,
-                Usage: D3D11_USAGE_DEFAULT,
-                MiscFlags: if meta.mipmap {
-                    D3D11_RESOURCE_MISC_GENERATE_MIPS.0 as u32
-                } else {
-                    0
-                },
-                ..Default::default()
-            };
+    /// Get the depth of the history kept by the filter chain, and the depth
+    /// requested by the shader passes.
+    pub fn history_depth(&self) -> HistoryDepth {
+        self.history_depth
+    }
 
-            let texture = LutTexture::new(
-                device,
-                context,
-                &image,
-                desc,
-                meta.filter_mode,
-                meta.wrap_mode,
-            )?;
-            luts.insert(index, texture);
-        }
-        Ok(luts)
+    /// Get the warnings about options of the filter chain that could not be honoured as
+    /// given, such as mipmaps being forced off for shader passes that request them.
+    pub fn option_warnings(&self) -> &[OptionWarning] {
+        &self.option_warnings
+    }
+
+    /// Invalidate the last `frames` frames of history, such as after a rollback in runahead
+    /// or netplay, so that shader passes do not blend frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+    /// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+    pub fn invalidate_history(&mut self, frames: usize) {
+        self.invalidated_history = std::cmp::max(self.invalidated_history, frames);
+    }
+
+    /// Get information about the shader pass at the given index, including the
+    /// framebuffer format chosen for the pass.
+    pub fn pass_info(&self, index: usize) -> Option<PassInfo> {
+        self.passes.get(index).map(FilterPassMeta::pass_info)
+    }
+
+    /// Get the statistics of the commands recorded for the last frame, or `None` if the
+    /// filter chain was not created with the `collect_statistics` option.
+    pub fn frame_statistics(&self) -> Option<FrameStatistics> {
+        self.common.statistics.statistics()
+    }
+
+    /// Set the mesh to draw the final pass with, or reset it to the default quad if `None`.
+    pub fn set_output_mesh(&mut self, mesh: Option<&OutputMesh>) -> error::Result<()> {
+        self.common
+            .draw_quad
+            .set_mesh(&self.common.d3d11.device, mesh)
     }
 
     /// Process a frame with the input image.
+    ///
+    /// The input must be a `D3D11_SRV_DIMENSION_TEXTURE2D` view of a texture created with
+    /// `D3D11_BIND_SHADER_RESOURCE`, otherwise an error is returned.
+    ///
+    /// If `ctx` is `None`, commands are recorded onto the immediate context of the device.
+    /// `ctx` may also be a deferred context, in which case the command list is not finished.
+    /// The caller must call `FinishCommandList` and execute the command lists of each frame on
+    /// the immediate context in the order they were recorded, since history and feedback
+    /// carry over between frames. Only one frame may be recorded at a time.
     pub unsafe fn frame(
         &mut self,
         ctx: Option<&ID3D11DeviceContext>,
@@ -445,13 +726,41 @@ impl FilterChainD3D11 {
         frame_count: usize,
         options: Option<&FrameOptionsD3D11>,
     ) -> error::Result<()> {
+        unsafe {
+            self.frame_broadcast(
+                ctx,
+                input,
+                std::slice::from_ref(viewport),
+                frame_count,
+                options,
+            )
+        }
+    }
+
+    /// Process a frame with the input image, drawing the final pass to every viewport.
+    ///
+    /// Intermediate passes are scaled relative to the first viewport and are only run once.
+    /// If `viewports` is empty, nothing is drawn. Deferred contexts are handled as in
+    /// [`FilterChainD3D11::frame`].
+    pub unsafe fn frame_broadcast(
+        &mut self,
+        ctx: Option<&ID3D11DeviceContext>,
+        input: &ID3D11ShaderResourceView,
+        viewports: &[Viewport<&ID3D11RenderTargetView>],
+        frame_count: usize,
+        options: Option<&FrameOptionsD3D11>,
+    ) -> error::Result<()> {
+        // passes are scaled relative to the first viewport.
+        let Some(viewport) = viewports.first() else {
+            return Ok(());
+        };
+
         let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
 
         // Need to clone this because pushing history needs a mutable borrow.
         let immediate_context = &self.common.d3d11.immediate_context.clone();
         let ctx = ctx.unwrap_or(immediate_context);
 
-        let passes = &mut self.passes[0..max];
         if let Some(options) = options {
             if options.clear_history {
                 for framebuffer in &mut self.history_framebuffers {
@@ -460,11 +769,57 @@ impl FilterChainD3D11 {
             }
         }
 
-        if passes.is_empty() {
-            return Ok(());
+        // with history and feedback disabled, the history is kept black.
+        if self.disable_temporal {
+            for framebuffer in &mut self.history_framebuffers {
+                framebuffer.clear(ctx)?;
+            }
+        }
+
+        // clear the history frames invalidated since the last frame.
+        let invalidated_history = std::mem::take(&mut self.invalidated_history);
+        for framebuffer in self
+            .history_framebuffers
+            .iter_mut()
+            .take(invalidated_history)
+        {
+            framebuffer.clear(ctx)?;
         }
 
         let options = options.unwrap_or(&self.default_options);
+        self.common.statistics.reset();
+        let frame_count = self
+            .rewind
+            .frame_count(frame_count, options.frame_direction);
+        self.common.frame_jitter = jitter::resolve(
+            options.jitter_sequence.into(),
+            options.jitter_offset,
+            frame_count,
+        );
+
+        // the identity pass draws to the viewport instead of the final pass if there are no
+        // enabled passes, or if the output of the final pass is scaled to the viewport.
+        let identity = if max == 0 || options.scale_final_pass {
+            Some(
+                self.identity
+                    .get_or_try_init(options.identity_scaling, |kernel| {
+                        Self::init_identity(
+                            &self.common.d3d11.device,
+                            &self.common.config,
+                            self.pass_options,
+                            kernel,
+                        )
+                    })?,
+            )
+        } else {
+            None
+        };
+
+        let scale_final_pass = max > 0 && identity.is_some();
+        let (passes, final_scaling) = match identity {
+            Some(identity) if max == 0 => (std::slice::from_mut(identity), None),
+            identity => (&mut self.passes[0..max], identity),
+        };
         let filter = passes[0].meta.filter;
         let wrap_mode = passes[0].meta.wrap_mode;
 
@@ -477,10 +832,18 @@ impl FilterChainD3D11 {
             *texture = Some(InputTexture::from_framebuffer(fbo, wrap_mode, filter)?);
         }
 
-        let original = InputTexture {
-            view: input.clone(),
-            filter,
-            wrap_mode,
+        let original = InputTexture::from_view(input, wrap_mode, filter)?;
+
+        // the keyed mutex is held until the input has been copied into the history.
+        let _keyed_mutex = match self.keyed_mutex {
+            // the mutex would be held while recording, not while the command list executes.
+            Some(_) if unsafe { ctx.GetType() } == D3D11_DEVICE_CONTEXT_DEFERRED => {
+                return Err(FilterChainError::KeyedMutexOnDeferredContext);
+            }
+            Some((acquire_key, release_key)) => {
+                KeyedMutexGuard::acquire(input, acquire_key, release_key)?
+            }
+            None => None,
         };
 
         let mut source = original.clone();
@@ -496,6 +859,14 @@ impl FilterChainD3D11 {
             None,
         )?;
 
+        // feedback is kept black, including after the feedback framebuffers are resized.
+        // the feedback of the previous frame is also cleared if it was invalidated.
+        if self.disable_temporal || invalidated_history > 0 {
+            for framebuffer in &mut self.feedback_framebuffers {
+                framebuffer.clear(ctx)?;
+            }
+        }
+
         // Refresh inputs for feedback textures.
         // Don't need to do this for outputs because they are yet to be bound.
         for ((texture, fbo), pass) in self
@@ -513,7 +884,10 @@ impl FilterChainD3D11 {
         }
 
         let passes_len = passes.len();
-        let (pass, last) = passes.split_at_mut(passes_len - 1);
+        let (pass, last) = match final_scaling {
+            Some(identity) => (passes, std::slice::from_mut(identity)),
+            None => passes.split_at_mut(passes_len - 1),
+        };
 
         let state_guard = self.state.enter_filter_state(ctx);
         self.common.draw_quad.bind_vbo_for_frame(ctx);
@@ -552,8 +926,11 @@ impl FilterChainD3D11 {
 
             // Draw to output_framebuffers for proper handling of feedback.
 
+            // the output of the final pass is already drawn if it is scaled to the viewport.
             let feedback_target = &self.output_framebuffers[index];
-            if self.draw_last_pass_feedback {
+            if self.draw_last_pass_feedback && !scale_final_pass {
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
                 pass.draw(
                     &ctx,
                     index,
@@ -566,33 +943,42 @@ impl FilterChainD3D11 {
                     RenderTarget::viewport_with_output(
                         &feedback_target.create_render_target_view()?,
                         viewport,
+                        &final_mvp,
                     ),
                     QuadType::Final,
                 )?;
             }
 
-            pass.draw(
-                &ctx,
-                index,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                RenderTarget::viewport(viewport),
-                QuadType::Final,
-            )?;
+            for viewport in viewports {
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
+                pass.draw(
+                    &ctx,
+                    index,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    RenderTarget::viewport(viewport, &final_mvp),
+                    QuadType::Final,
+                )?;
+            }
         }
 
-        std::mem::swap(
-            &mut self.output_framebuffers,
-            &mut self.feedback_framebuffers,
-        );
+        if !self.disable_temporal {
+            std::mem::swap(
+                &mut self.output_framebuffers,
+                &mut self.feedback_framebuffers,
+            );
+        }
 
         drop(state_guard);
 
-        self.push_history(ctx, &input)?;
+        if !self.disable_temporal {
+            self.push_history(ctx, &input)?;
+        }
 
         Ok(())
     }
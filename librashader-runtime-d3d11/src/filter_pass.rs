@@ -3,16 +3,15 @@ use crate::options::FrameOptionsD3D11;
 use crate::texture::InputTexture;
 use windows::Win32::Foundation::RECT;
 
-use librashader_common::map::FastHashMap;
 use librashader_common::{ImageFormat, Size, Viewport};
 use librashader_preprocess::ShaderSource;
 use librashader_presets::PassMeta;
-use librashader_reflect::reflect::semantics::{
-    BindingStage, MemberOffset, TextureBinding, UniformBinding,
-};
+use librashader_reflect::reflect::semantics::{BindingStage, MemberOffset, TextureBinding};
 use librashader_reflect::reflect::ShaderReflection;
 
-use librashader_runtime::binding::{BindSemantics, TextureInput, UniformInputs};
+use librashader_runtime::binding::{
+    BindSemantics, TextureInput, UniformBindings, UniformInputs, DEFAULT_SCENE_LUMINANCE,
+};
 use librashader_runtime::filter_pass::FilterPassMeta;
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
@@ -26,6 +25,7 @@ use crate::error;
 use crate::samplers::SamplerSet;
 use librashader_common::GetSize;
 use librashader_runtime::uniforms::{UniformStorage, UniformStorageAccess};
+use std::path::{Path, PathBuf};
 
 pub struct ConstantBufferBinding {
     pub binding: u32,
@@ -41,13 +41,17 @@ pub struct FilterPass {
     pub vertex_layout: ID3D11InputLayout,
     pub pixel_shader: ID3D11PixelShader,
 
-    pub uniform_bindings: FastHashMap<UniformBinding, MemberOffset>,
+    pub uniform_bindings: UniformBindings<MemberOffset>,
 
     pub uniform_storage: UniformStorage,
     pub uniform_buffer: Option<ConstantBufferBinding>,
     pub push_buffer: Option<ConstantBufferBinding>,
     pub source: ShaderSource,
     pub meta: PassMeta,
+    pub path: Option<PathBuf>,
+    /// The framebuffer format of the pass, after falling back to a format supported
+    /// by the device.
+    pub format: ImageFormat,
 }
 
 // https://doc.rust-lang.org/nightly/core/array/fn.from_fn.html is not ~const :(
@@ -93,6 +97,14 @@ impl FilterPassMeta for FilterPass {
     fn meta(&self) -> &PassMeta {
         &self.meta
     }
+
+    fn source_path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn get_supported_format(&self) -> ImageFormat {
+        self.format
+    }
 }
 
 // slang_process.cpp 229
@@ -131,6 +143,8 @@ impl FilterPass {
                 frametime_delta: options.frametime_delta,
                 framebuffer_size: fb_size,
                 viewport_size,
+                scene_luminance: DEFAULT_SCENE_LUMINANCE,
+                frame_jitter: parent.frame_jitter,
             },
             original,
             source,
@@ -142,7 +156,6 @@ impl FilterPass {
             parent.feedback_textures.iter().map(|o| o.as_ref()),
             parent.history_textures.iter().map(|o| o.as_ref()),
             parent.luts.iter().map(|(u, i)| (*u, i.as_ref())),
-            &self.source.parameters,
             &parent.config,
         );
     }
@@ -160,6 +173,8 @@ impl FilterPass {
         output: RenderTarget<ID3D11RenderTargetView>,
         vbo_type: QuadType,
     ) -> error::Result<()> {
+        parent.statistics.record_pass(&self.reflection);
+
         if self.meta.mipmap_input && !parent.disable_mipmaps {
             unsafe {
                 ctx.GenerateMips(&source.view);
@@ -191,6 +206,8 @@ impl FilterPass {
             source,
         );
 
+        // buffers are mapped with discard on every draw, which is the only map type
+        // deferred contexts allow, and gives every draw in a command list its own contents.
         if let Some(ubo) = &self.uniform_buffer {
             // upload uniforms
             unsafe {
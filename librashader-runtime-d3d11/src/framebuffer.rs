@@ -105,9 +105,15 @@ impl OwnedImage {
     }
 
     pub fn init(&mut self, size: Size<u32>, format: ImageFormat) -> error::Result<()> {
+        self.init_format(size, DXGI_FORMAT::from(format))
+    }
+
+    /// Initialize the image with a DXGI format, which may not have an [`ImageFormat`] equivalent
+    /// (such as `DXGI_FORMAT_B8G8R8A8_UNORM`).
+    fn init_format(&mut self, size: Size<u32>, format: DXGI_FORMAT) -> error::Result<()> {
         let format = d3d11_get_closest_format(
             &self.device,
-            DXGI_FORMAT::from(format),
+            format,
             D3D11_FORMAT_SUPPORT_TEXTURE2D.0
                 | D3D11_FORMAT_SUPPORT_SHADER_SAMPLE.0
                 | D3D11_FORMAT_SUPPORT_RENDER_TARGET.0,
@@ -180,15 +186,22 @@ impl OwnedImage {
             resource.cast()?
         };
 
-        let (format, image_size) = unsafe {
+        let image_size = unsafe {
             let mut desc = Default::default();
             original_resource.GetDesc(&mut desc);
-            (desc.Format, Size::new(desc.Width, desc.Height))
+            Size::new(desc.Width, desc.Height)
+        };
+
+        // use the format of the view rather than the texture, which may be typeless.
+        let format = unsafe {
+            let mut desc = Default::default();
+            image.GetDesc(&mut desc);
+            desc.Format
         };
 
         if self.size != image_size || format != self.format {
             // eprintln!("[history] resizing");
-            self.init(image_size, ImageFormat::from(format))?;
+            self.init_format(image_size, format)?;
         }
 
         unsafe {
@@ -7,6 +7,7 @@
 
 #![cfg_attr(not(feature = "stable"), feature(type_alias_impl_trait))]
 
+mod device_context;
 mod draw_quad;
 mod filter_chain;
 mod filter_pass;
@@ -23,4 +24,6 @@ pub mod options;
 use librashader_runtime::impl_filter_chain_parameters;
 impl_filter_chain_parameters!(FilterChainD3D11);
 
+pub use device_context::DeviceContextD3D11;
 pub use filter_chain::FilterChainD3D11;
+pub use luts::LutBundleD3D11;
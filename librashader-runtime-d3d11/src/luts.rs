@@ -1,18 +1,25 @@
 use crate::error;
 use crate::error::assume_d3d11_init;
 use crate::texture::InputTexture;
+use librashader_common::map::FastHashMap;
 use librashader_common::{FilterMode, WrapMode};
-use librashader_runtime::image::Image;
+use librashader_pack::TextureResource;
+use librashader_runtime::image::{Image, ImageError, LoadedTexture, UVDirection, RGBA8};
+use librashader_runtime::luts::LutBundle;
 use librashader_runtime::scaling::MipmapSize;
+use rayon::prelude::*;
+use std::sync::Arc;
 use windows::Win32::Graphics::Direct3D::D3D_SRV_DIMENSION_TEXTURE2D;
 use windows::Win32::Graphics::Direct3D11::{
     ID3D11Device, ID3D11DeviceContext, ID3D11Texture2D, D3D11_BIND_RENDER_TARGET,
     D3D11_BIND_SHADER_RESOURCE, D3D11_BOX, D3D11_CPU_ACCESS_WRITE,
     D3D11_RESOURCE_MISC_GENERATE_MIPS, D3D11_SHADER_RESOURCE_VIEW_DESC,
     D3D11_SHADER_RESOURCE_VIEW_DESC_0, D3D11_SUBRESOURCE_DATA, D3D11_TEX2D_SRV,
-    D3D11_TEXTURE2D_DESC, D3D11_USAGE_DYNAMIC, D3D11_USAGE_STAGING,
+    D3D11_TEXTURE2D_DESC, D3D11_USAGE_DEFAULT, D3D11_USAGE_DYNAMIC, D3D11_USAGE_STAGING,
+};
+use windows::Win32::Graphics::Dxgi::Common::{
+    DXGI_FORMAT_R8G8B8A8_UNORM, DXGI_FORMAT_R8G8B8A8_UNORM_SRGB, DXGI_SAMPLE_DESC,
 };
-use windows::Win32::Graphics::Dxgi::Common::DXGI_SAMPLE_DESC;
 
 #[derive(Debug, Clone)]
 pub(crate) struct LutTexture {
@@ -69,7 +76,7 @@ impl LutTexture {
         }
 
         // Don't need to determine format support because LUTs are always DXGI_FORMAT_R8G8B8A8_UNORM
-        // since we load them with the Image module.
+        // or DXGI_FORMAT_R8G8B8A8_UNORM_SRGB since we load them with the Image module.
 
         unsafe {
             let mut handle = None;
@@ -147,3 +154,101 @@ impl LutTexture {
         }
     }
 }
+
+/// A set of LUT textures uploaded to a Direct3D 11 device, that can be shared between filter
+/// chains created on the same device with the `lut_bundle` option.
+///
+/// Frontends that switch between presets sharing texture packs can keep a bundle around, so
+/// that textures are only uploaded once. Clones of a bundle share the same textures.
+#[derive(Clone, Debug, Default)]
+pub struct LutBundleD3D11(LutBundle<LutTexture>);
+
+impl LutBundleD3D11 {
+    /// Create an empty bundle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of textures in the bundle.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the bundle has no textures.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Remove every texture from the bundle.
+    ///
+    /// Textures in use by filter chains are released once those filter chains are dropped.
+    pub fn clear(&self) {
+        self.0.clear()
+    }
+
+    /// Upload LUT textures to the bundle ahead of filter chain creation, such as the textures
+    /// of a `ShaderPresetPack`.
+    ///
+    /// Textures that are already in the bundle are not uploaded again.
+    ///
+    /// ## Safety
+    /// The provided context must either be immediate, or immediately submitted after this
+    /// function returns, **before drawing frames** with any filter chain using the textures,
+    /// as with [`FilterChainD3D11::load_from_pack_deferred`](crate::FilterChainD3D11::load_from_pack_deferred).
+    pub unsafe fn preload_deferred(
+        &self,
+        textures: Vec<TextureResource>,
+        device: &ID3D11Device,
+        ctx: &ID3D11DeviceContext,
+    ) -> error::Result<()> {
+        load_luts(device, ctx, textures, Some(self))?;
+        Ok(())
+    }
+}
+
+/// Load the LUT textures of a preset, reusing the textures in the bundle if one is given.
+pub(crate) fn load_luts(
+    device: &ID3D11Device,
+    context: &ID3D11DeviceContext,
+    textures: Vec<TextureResource>,
+    bundle: Option<&LutBundleD3D11>,
+) -> error::Result<FastHashMap<usize, Arc<LutTexture>>> {
+    LutBundle::load(
+        bundle.map(|bundle| &bundle.0),
+        textures,
+        |textures| {
+            textures
+                .into_par_iter()
+                .map(|texture| LoadedTexture::from_texture(texture, UVDirection::TopLeft))
+                .collect::<Result<Vec<LoadedTexture<RGBA8>>, ImageError>>()
+        },
+        |LoadedTexture { meta, image }| {
+            let desc = D3D11_TEXTURE2D_DESC {
+                Width: image.size.width,
+                Height: image.size.height,
+                // sRGB textures are decoded to linear when sampled, so filtering happens in linear space.
+                Format: if meta.srgb {
+                    DXGI_FORMAT_R8G8B8A8_UNORM_SRGB
+                } else {
+                    DXGI_FORMAT_R8G8B8A8_UNORM
+                },
+                Usage: D3D11_USAGE_DEFAULT,
+                MiscFlags: if meta.mipmap {
+                    D3D11_RESOURCE_MISC_GENERATE_MIPS.0 as u32
+                } else {
+                    0
+                },
+                ..Default::default()
+            };
+
+            LutTexture::new(
+                device,
+                context,
+                &image,
+                desc,
+                meta.filter_mode,
+                meta.wrap_mode,
+            )
+        },
+    )
+}
@@ -1,6 +1,9 @@
 //! Direct3D 11 shader runtime options.
 
+use crate::{DeviceContextD3D11, LutBundleD3D11};
 use librashader_runtime::impl_default_frame_options;
+use librashader_runtime::instance::LibrashaderInstance;
+use librashader_runtime::progress::{CancellationToken, ProgressCallback};
 impl_default_frame_options!(FrameOptionsD3D11);
 
 /// Options for Direct3D 11 filter chain creation.
@@ -13,4 +16,65 @@ pub struct FilterChainOptionsD3D11 {
     /// Disable the shader object cache. Shaders will be
     /// recompiled rather than loaded from the cache.
     pub disable_cache: bool,
+    /// The maximum number of history frames to keep. If zero, the number of history frames
+    /// is not capped. Shader passes that sample older frames receive the oldest kept frame.
+    pub max_history: u32,
+    /// The policy to follow when the framebuffer format of a shader pass is not supported.
+    /// 0 = fall back to a wider format, 1 = fall back to the smallest supported format,
+    /// 2 = fail filter chain creation. Default is 0.
+    pub format_fallback: u32,
+    /// Acquire the keyed mutex of the input texture before sampling it, and release it once
+    /// the frame has been recorded. Inputs without a keyed mutex are sampled as is.
+    ///
+    /// This is needed for shared textures created with `D3D11_RESOURCE_MISC_SHARED_KEYEDMUTEX`,
+    /// and is only supported when frames are recorded on the immediate context. Recording a
+    /// frame on a deferred context with this option enabled returns an error.
+    pub use_keyed_mutex: bool,
+    /// The key to acquire the keyed mutex of the input texture with.
+    pub keyed_mutex_acquire_key: u64,
+    /// The key to release the keyed mutex of the input texture with.
+    pub keyed_mutex_release_key: u64,
+    /// Disable history and feedback, for latency-sensitive modes such as runahead or netplay,
+    /// where frames are invalidated frequently. No history frames are kept, and shader passes
+    /// that sample history or feedback textures receive black textures instead.
+    pub disable_temporal: bool,
+    /// Freeze the `FrameCount` uniform while the `frame_direction` frame option is negative,
+    /// as RetroArch does while rewinding. Frontends that keep counting frames while rewinding
+    /// should enable this, so that shaders animated by the frame count do not jump.
+    pub freeze_frame_count_on_rewind: bool,
+    /// Record CPU-side statistics of the commands recorded for every frame, such as the number
+    /// of draw calls, which can be read with `frame_statistics` after the frame is drawn.
+    /// This is meant for performance tuning and regression tracking, and has a small cost.
+    pub collect_statistics: bool,
+    /// A callback that is called as every shader pass of the preset is preprocessed, compiled,
+    /// and has its pipeline created, so that frontends can show the progress of loading
+    /// large presets.
+    pub progress: Option<ProgressCallback>,
+    /// A token to cancel filter chain creation with from another thread. If cancelled,
+    /// creation stops between shader passes and fails with
+    /// [`FilterChainError::Cancelled`](crate::error::FilterChainError::Cancelled).
+    pub cancel: Option<CancellationToken>,
+    /// A bundle of LUT textures to share with other filter chains on the same device.
+    /// LUT textures of the preset that are in the bundle are reused instead of uploaded again,
+    /// and the rest are uploaded and added to the bundle.
+    pub lut_bundle: Option<LutBundleD3D11>,
+    /// A device context to share sampler states and LUT textures with other filter chains on
+    /// the same device. The device context must have been created for the same device.
+    /// If `lut_bundle` is also set, LUT textures are shared through `lut_bundle` instead.
+    pub device_context: Option<DeviceContextD3D11>,
+    /// Append a built-in pass after the passes of the preset that simulates or corrects for
+    /// colour vision deficiencies. The pass is controlled at runtime with the
+    /// `LIBRA_COLOR_ASSIST_MODE` (0 = off, 1 = protanopia, 2 = deuteranopia, 3 = tritanopia),
+    /// `LIBRA_COLOR_ASSIST_SIMULATE` and `LIBRA_COLOR_ASSIST_STRENGTH` parameters.
+    pub color_assist: bool,
+    /// Append a built-in pass after the passes of the preset that inserts black frames between
+    /// the subframes of every frame, for motion clarity on high refresh rate displays.
+    /// The pass follows the `current_subframe` and `total_subframes` frame options, and is
+    /// controlled at runtime with the `LIBRA_BFI_ENABLE`, `LIBRA_BFI_LIT_SUBFRAMES`,
+    /// `LIBRA_BFI_DARK_LEVEL` and `LIBRA_BFI_COMPENSATION` parameters.
+    pub black_frame_insertion: bool,
+    /// The librashader instance to create the filter chain from. Shaders are compiled on the
+    /// thread pool of the instance, with the cache directory and log callback of the instance.
+    /// If `None`, the global thread pool, default cache directory and global logger are used.
+    pub instance: Option<LibrashaderInstance>,
 }
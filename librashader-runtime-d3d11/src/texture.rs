@@ -1,7 +1,14 @@
-use crate::error::Result;
+use crate::error::{FilterChainError, Result};
 use crate::framebuffer::OwnedImage;
 use librashader_common::{FilterMode, WrapMode};
-use windows::Win32::Graphics::Direct3D11::ID3D11ShaderResourceView;
+use windows::core::Interface;
+use windows::Win32::Graphics::Direct3D::D3D_SRV_DIMENSION_TEXTURE2D;
+use windows::Win32::Graphics::Direct3D11::{
+    ID3D11ShaderResourceView, ID3D11Texture2D, D3D11_BIND_SHADER_RESOURCE,
+    D3D11_SHADER_RESOURCE_VIEW_DESC, D3D11_TEXTURE2D_DESC,
+};
+use windows::Win32::Graphics::Dxgi::IDXGIKeyedMutex;
+use windows::Win32::System::Threading::INFINITE;
 
 #[derive(Debug, Clone)]
 pub struct InputTexture {
@@ -22,6 +29,43 @@ impl InputTexture {
             wrap_mode,
         })
     }
+
+    /// Create an input texture from a shader resource view provided by the caller.
+    ///
+    /// The view must be a `D3D11_SRV_DIMENSION_TEXTURE2D` view of a texture that was
+    /// created with `D3D11_BIND_SHADER_RESOURCE`.
+    pub(crate) fn from_view(
+        view: &ID3D11ShaderResourceView,
+        wrap_mode: WrapMode,
+        filter: FilterMode,
+    ) -> Result<Self> {
+        let mut view_desc = D3D11_SHADER_RESOURCE_VIEW_DESC::default();
+        unsafe {
+            view.GetDesc(&mut view_desc);
+        }
+
+        if view_desc.ViewDimension != D3D_SRV_DIMENSION_TEXTURE2D {
+            return Err(FilterChainError::InvalidViewDimensionError(
+                view_desc.ViewDimension,
+            ));
+        }
+
+        let texture: ID3D11Texture2D = unsafe { view.GetResource()?.cast()? };
+        let mut texture_desc = D3D11_TEXTURE2D_DESC::default();
+        unsafe {
+            texture.GetDesc(&mut texture_desc);
+        }
+
+        if texture_desc.BindFlags & D3D11_BIND_SHADER_RESOURCE.0 as u32 == 0 {
+            return Err(FilterChainError::InvalidBindFlagsError);
+        }
+
+        Ok(InputTexture {
+            view: view.clone(),
+            filter,
+            wrap_mode,
+        })
+    }
 }
 
 impl AsRef<InputTexture> for InputTexture {
@@ -29,3 +73,39 @@ impl AsRef<InputTexture> for InputTexture {
         self
     }
 }
+
+/// Holds the keyed mutex of a shared input texture, releasing it when dropped.
+pub(crate) struct KeyedMutexGuard {
+    mutex: IDXGIKeyedMutex,
+    release_key: u64,
+}
+
+impl KeyedMutexGuard {
+    /// Acquire the keyed mutex of the texture behind the view.
+    ///
+    /// Returns `None` if the texture does not have a keyed mutex.
+    pub(crate) fn acquire(
+        view: &ID3D11ShaderResourceView,
+        acquire_key: u64,
+        release_key: u64,
+    ) -> Result<Option<Self>> {
+        let resource = unsafe { view.GetResource()? };
+        let Ok(mutex) = resource.cast::<IDXGIKeyedMutex>() else {
+            return Ok(None);
+        };
+
+        unsafe {
+            mutex.AcquireSync(acquire_key, INFINITE)?;
+        }
+
+        Ok(Some(KeyedMutexGuard { mutex, release_key }))
+    }
+}
+
+impl Drop for KeyedMutexGuard {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.mutex.ReleaseSync(self.release_key);
+        }
+    }
+}
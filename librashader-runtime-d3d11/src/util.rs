@@ -1,5 +1,6 @@
 use crate::error;
 use crate::error::assume_d3d11_init;
+use librashader_common::{FormatFallback, ImageFormat};
 use std::slice;
 use windows::core::PCSTR;
 use windows::Win32::Graphics::Direct3D::Fxc::{
@@ -109,6 +110,26 @@ pub fn d3d11_get_closest_format(
     DXGI_FORMAT_UNKNOWN
 }
 
+/// Select the first fallback of the format under the fallback policy that the device can
+/// render to and sample from.
+pub fn d3d11_select_supported_format(
+    device: &ID3D11Device,
+    format: ImageFormat,
+    policy: FormatFallback,
+) -> Option<ImageFormat> {
+    let format_support_mask = (D3D11_FORMAT_SUPPORT_TEXTURE2D.0
+        | D3D11_FORMAT_SUPPORT_SHADER_SAMPLE.0
+        | D3D11_FORMAT_SUPPORT_RENDER_TARGET.0) as u32;
+
+    format.select_supported(policy, |format| unsafe {
+        device
+            .CheckFormatSupport(DXGI_FORMAT::from(format))
+            .is_ok_and(|supported_format| {
+                (supported_format & format_support_mask) == format_support_mask
+            })
+    })
+}
+
 pub fn d3d_compile_shader(source: &[u8], entry: &[u8], version: &[u8]) -> error::Result<ID3DBlob> {
     unsafe {
         let mut blob = None;
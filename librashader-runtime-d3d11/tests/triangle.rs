@@ -36,6 +36,21 @@ fn triangle_d3d11_args() {
         Some(&FilterChainOptionsD3D11 {
             force_no_mipmaps: false,
             disable_cache: false,
+            max_history: 0,
+            format_fallback: 0,
+            use_keyed_mutex: false,
+            keyed_mutex_acquire_key: 0,
+            keyed_mutex_release_key: 0,
+            disable_temporal: false,
+            freeze_frame_count_on_rewind: false,
+            collect_statistics: false,
+            progress: None,
+            cancel: None,
+            lut_bundle: None,
+            device_context: None,
+            color_assist: false,
+            black_frame_insertion: false,
+            instance: None,
         }),
         // replace below with 'None' for the triangle
         Some(image),
@@ -61,6 +76,21 @@ fn triangle_d3d11() {
         Some(&FilterChainOptionsD3D11 {
             force_no_mipmaps: false,
             disable_cache: true,
+            max_history: 0,
+            format_fallback: 0,
+            use_keyed_mutex: false,
+            keyed_mutex_acquire_key: 0,
+            keyed_mutex_release_key: 0,
+            disable_temporal: false,
+            freeze_frame_count_on_rewind: false,
+            collect_statistics: false,
+            progress: None,
+            cancel: None,
+            lut_bundle: None,
+            device_context: None,
+            color_assist: false,
+            black_frame_insertion: false,
+            instance: None,
         }),
         // replace below with 'None' for the triangle
         // None,
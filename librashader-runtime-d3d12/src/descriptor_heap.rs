@@ -1,10 +1,21 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
 use windows::Win32::Graphics::Direct3D12::{
-    D3D12_DESCRIPTOR_HEAP_DESC, D3D12_DESCRIPTOR_HEAP_FLAG_NONE,
-    D3D12_DESCRIPTOR_HEAP_FLAG_SHADER_VISIBLE, D3D12_DESCRIPTOR_HEAP_TYPE_CBV_SRV_UAV,
+    ID3D12DescriptorHeap, ID3D12Device, D3D12_CPU_DESCRIPTOR_HANDLE, D3D12_DESCRIPTOR_HEAP_DESC,
+    D3D12_DESCRIPTOR_HEAP_FLAG_NONE, D3D12_DESCRIPTOR_HEAP_FLAG_SHADER_VISIBLE,
+    D3D12_DESCRIPTOR_HEAP_TYPE, D3D12_DESCRIPTOR_HEAP_TYPE_CBV_SRV_UAV,
     D3D12_DESCRIPTOR_HEAP_TYPE_RTV, D3D12_DESCRIPTOR_HEAP_TYPE_SAMPLER,
+    D3D12_GPU_DESCRIPTOR_HANDLE,
+};
+
+use d3d12_descriptor_heap::{
+    D3D12DescriptorHeapError, D3D12DescriptorHeapType, D3D12ShaderVisibleDescriptorHeapType,
 };
 
-use d3d12_descriptor_heap::{D3D12DescriptorHeapType, D3D12ShaderVisibleDescriptorHeapType};
+use crate::error::{self, FilterChainError};
+use crate::options::D3D12DescriptorHeapRange;
 
 #[derive(Clone)]
 pub struct SamplerPaletteHeap;
@@ -82,3 +93,191 @@ impl D3D12DescriptorHeapType for SamplerWorkHeap {
         }
     }
 }
+
+struct WorkHeapInner {
+    device: ID3D12Device,
+    heap: ID3D12DescriptorHeap,
+    ty: D3D12_DESCRIPTOR_HEAP_TYPE,
+    cpu_start: D3D12_CPU_DESCRIPTOR_HANDLE,
+    gpu_start: D3D12_GPU_DESCRIPTOR_HANDLE,
+    handle_size: usize,
+    len: usize,
+    free: Mutex<Vec<usize>>,
+}
+
+/// A range of a shader visible heap that work descriptors are sub-allocated from.
+///
+/// Unlike `D3D12DescriptorHeap`, the range can be part of a heap owned by the caller,
+/// so that frames do not need to switch descriptor heaps.
+pub struct WorkHeap<T>(Arc<WorkHeapInner>, PhantomData<T>);
+
+// clones share the same range, so a derive would needlessly require `T: Clone`.
+impl<T> Clone for WorkHeap<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0), PhantomData)
+    }
+}
+
+/// An allocated slot on a work heap range.
+///
+/// The slot is returned to the range when the last clone is dropped.
+#[derive(Clone)]
+pub struct WorkHeapSlot<T>(Arc<WorkHeapSlotInner<T>>);
+
+struct WorkHeapSlotInner<T> {
+    heap: Arc<WorkHeapInner>,
+    index: usize,
+    cpu_handle: D3D12_CPU_DESCRIPTOR_HANDLE,
+    gpu_handle: D3D12_GPU_DESCRIPTOR_HANDLE,
+    _pd: PhantomData<T>,
+}
+
+/// Ensure a heap range supplied by the caller is a range of a shader visible heap of the given
+/// type, with at least `required` descriptors.
+pub fn validate_heap_range(
+    range: &D3D12DescriptorHeapRange,
+    ty: D3D12_DESCRIPTOR_HEAP_TYPE,
+    required: usize,
+) -> error::Result<()> {
+    let desc = unsafe { range.heap.GetDesc() };
+    if desc.Type != ty || (desc.Flags & D3D12_DESCRIPTOR_HEAP_FLAG_SHADER_VISIBLE).0 == 0 {
+        return Err(FilterChainError::InvalidDescriptorHeap(ty));
+    }
+
+    // the part of the range past the end of the heap can not be used.
+    let available = std::cmp::min(
+        range.count as usize,
+        (desc.NumDescriptors as usize).saturating_sub(range.offset as usize),
+    );
+    if available < required {
+        return Err(FilterChainError::DescriptorHeapRangeTooSmall {
+            required,
+            available,
+        });
+    }
+
+    Ok(())
+}
+
+impl<T: D3D12ShaderVisibleDescriptorHeapType> WorkHeap<T> {
+    /// Create a new shader visible heap, returning the heap to be split into ranges.
+    pub unsafe fn create_heap(
+        device: &ID3D12Device,
+        size: usize,
+    ) -> windows::core::Result<ID3D12DescriptorHeap> {
+        unsafe { device.CreateDescriptorHeap(&T::create_desc(size)) }
+    }
+}
+
+impl<T> WorkHeap<T> {
+    /// Create a work heap over `len` descriptors of `heap`, starting at the descriptor at `offset`.
+    ///
+    /// SAFETY:
+    ///   - `heap` must be a shader visible heap of the heap type of `T`.
+    ///   - The range must lie within the heap, and must not overlap any other work heap.
+    pub unsafe fn new(
+        device: &ID3D12Device,
+        heap: &ID3D12DescriptorHeap,
+        offset: usize,
+        len: usize,
+    ) -> WorkHeap<T> {
+        unsafe {
+            let ty = heap.GetDesc().Type;
+            let handle_size = device.GetDescriptorHandleIncrementSize(ty) as usize;
+            let cpu_start = heap.GetCPUDescriptorHandleForHeapStart();
+            let gpu_start = heap.GetGPUDescriptorHandleForHeapStart();
+
+            WorkHeap(
+                Arc::new(WorkHeapInner {
+                    device: device.clone(),
+                    heap: heap.clone(),
+                    ty,
+                    cpu_start: D3D12_CPU_DESCRIPTOR_HANDLE {
+                        ptr: cpu_start.ptr + offset * handle_size,
+                    },
+                    gpu_start: D3D12_GPU_DESCRIPTOR_HANDLE {
+                        ptr: gpu_start.ptr + (offset * handle_size) as u64,
+                    },
+                    handle_size,
+                    len,
+                    // slots are handed out in order, so a fresh range allocates contiguously.
+                    free: Mutex::new((0..len).rev().collect()),
+                }),
+                PhantomData,
+            )
+        }
+    }
+
+    /// Allocate a descriptor.
+    ///
+    /// If there are no more free descriptors, returns an error with the number of
+    /// descriptors in this range.
+    pub fn allocate_descriptor(&mut self) -> Result<WorkHeapSlot<T>, D3D12DescriptorHeapError> {
+        let inner = &self.0;
+        let Some(index) = inner.free.lock().pop() else {
+            return Err(D3D12DescriptorHeapError::HeapOverflow(inner.len));
+        };
+
+        let offset = index * inner.handle_size;
+        Ok(WorkHeapSlot(Arc::new(WorkHeapSlotInner {
+            heap: Arc::clone(inner),
+            index,
+            cpu_handle: D3D12_CPU_DESCRIPTOR_HANDLE {
+                ptr: inner.cpu_start.ptr + offset,
+            },
+            gpu_handle: D3D12_GPU_DESCRIPTOR_HANDLE {
+                ptr: inner.gpu_start.ptr + offset as u64,
+            },
+            _pd: PhantomData,
+        })))
+    }
+
+    /// Allocate a contiguous range of descriptors from a fresh range.
+    pub fn allocate_descriptor_range<const NUM_DESC: usize>(
+        &mut self,
+    ) -> Result<[WorkHeapSlot<T>; NUM_DESC], D3D12DescriptorHeapError> {
+        let slots = (0..NUM_DESC)
+            .map(|_| self.allocate_descriptor())
+            .collect::<Result<Vec<_>, _>>()?;
+        match slots.try_into() {
+            Ok(slots) => Ok(slots),
+            Err(_) => unreachable!("exactly NUM_DESC slots were allocated"),
+        }
+    }
+
+    /// Gets a cloned handle to the heap of the range.
+    pub fn handle(&self) -> ID3D12DescriptorHeap {
+        self.0.heap.clone()
+    }
+}
+
+impl<T> WorkHeapSlot<T> {
+    /// Copy a descriptor from the given CPU handle into this slot.
+    pub unsafe fn copy_descriptor(&self, source: D3D12_CPU_DESCRIPTOR_HANDLE) {
+        let inner = &self.0;
+        unsafe {
+            inner
+                .heap
+                .device
+                .CopyDescriptorsSimple(1, inner.cpu_handle, source, inner.heap.ty);
+        }
+    }
+}
+
+impl<T> AsRef<D3D12_CPU_DESCRIPTOR_HANDLE> for WorkHeapSlot<T> {
+    fn as_ref(&self) -> &D3D12_CPU_DESCRIPTOR_HANDLE {
+        &self.0.cpu_handle
+    }
+}
+
+impl<T> AsRef<D3D12_GPU_DESCRIPTOR_HANDLE> for WorkHeapSlot<T> {
+    fn as_ref(&self) -> &D3D12_GPU_DESCRIPTOR_HANDLE {
+        &self.0.gpu_handle
+    }
+}
+
+impl<T> Drop for WorkHeapSlotInner<T> {
+    fn drop(&mut self) {
+        self.heap.free.lock().push(self.index);
+    }
+}
@@ -3,6 +3,7 @@ use crate::error;
 use array_concat::concat_arrays;
 use bytemuck::offset_of;
 use gpu_allocator::d3d12::Allocator;
+use librashader_runtime::mesh::OutputMesh;
 use librashader_runtime::quad::{QuadType, VertexInput};
 use parking_lot::Mutex;
 use std::sync::Arc;
@@ -57,6 +58,7 @@ static VBO_DATA: &[VertexInput; 8] = &concat_arrays!(OFFSCREEN_VBO_DATA, FINAL_V
 pub(crate) struct DrawQuad {
     _buffer: D3D12Buffer,
     view: D3D12_VERTEX_BUFFER_VIEW,
+    mesh: Option<(D3D12Buffer, D3D12_VERTEX_BUFFER_VIEW, u32)>,
 }
 
 impl DrawQuad {
@@ -78,9 +80,40 @@ impl DrawQuad {
         Ok(DrawQuad {
             _buffer: buffer,
             view,
+            mesh: None,
         })
     }
 
+    /// Set the mesh to draw the final pass with, or reset it to the default quad.
+    pub fn set_mesh(
+        &mut self,
+        allocator: &Arc<Mutex<Allocator>>,
+        mesh: Option<&OutputMesh>,
+    ) -> error::Result<()> {
+        let Some(mesh) = mesh else {
+            self.mesh = None;
+            return Ok(());
+        };
+
+        // the clip space of Direct3D points up.
+        let vertices = mesh.flipped_vertices(false);
+        let size = std::mem::size_of_val(vertices.as_slice());
+        let mut buffer = D3D12Buffer::new(allocator, size)?;
+        buffer
+            .map(None)?
+            .slice
+            .copy_from_slice(bytemuck::cast_slice(&vertices));
+
+        let view = D3D12_VERTEX_BUFFER_VIEW {
+            BufferLocation: buffer.gpu_address(),
+            SizeInBytes: size as u32,
+            StrideInBytes: std::mem::size_of::<VertexInput>() as u32,
+        };
+
+        self.mesh = Some((buffer, view, mesh.vertex_count()));
+        Ok(())
+    }
+
     pub fn bind_vertices_for_frame(&self, cmd: &ID3D12GraphicsCommandList) {
         unsafe {
             cmd.IASetPrimitiveTopology(D3D_PRIMITIVE_TOPOLOGY_TRIANGLESTRIP);
@@ -90,6 +123,16 @@ impl DrawQuad {
 
     // frame uses ID3D12GraphicsCommandList4 for renderpasses, don't need to bother with the cast.
     pub fn draw_quad(&self, cmd: &ID3D12GraphicsCommandList4, vbo_type: QuadType) {
+        if let (QuadType::Final, Some((_, view, count))) = (&vbo_type, &self.mesh) {
+            unsafe {
+                cmd.IASetVertexBuffers(0, Some(&[*view]));
+                cmd.DrawInstanced(*count, 1, 0, 0);
+                // the quad is bound again for the passes drawn after the final pass.
+                cmd.IASetVertexBuffers(0, Some(&[self.view]));
+            }
+            return;
+        }
+
         let offset = match vbo_type {
             QuadType::Offscreen => 0,
             QuadType::Final => 4,
@@ -2,8 +2,9 @@
 //!
 
 use d3d12_descriptor_heap::D3D12DescriptorHeapError;
+use librashader_common::ImageFormat;
 use thiserror::Error;
-use windows::Win32::Graphics::Direct3D12::D3D12_RESOURCE_DIMENSION;
+use windows::Win32::Graphics::Direct3D12::{D3D12_DESCRIPTOR_HEAP_TYPE, D3D12_RESOURCE_DIMENSION};
 
 /// Cumulative error type for Direct3D12 filter chains.
 #[derive(Error, Debug)]
@@ -29,6 +30,16 @@ pub enum FilterChainError {
     AllocationError(#[from] gpu_allocator::AllocationError),
     #[error("invalid resource dimension {0:?}")]
     InvalidDimensionError(D3D12_RESOURCE_DIMENSION),
+    #[error("the framebuffer format {0:?} is not supported by the device")]
+    UnsupportedFormat(ImageFormat),
+    #[error("filter chain creation was cancelled")]
+    Cancelled,
+    #[error("could not spawn the worker thread to create the filter chain on")]
+    WorkerThread(#[source] std::io::Error),
+    #[error("the descriptor heap is not a shader visible heap of type {0:?}")]
+    InvalidDescriptorHeap(D3D12_DESCRIPTOR_HEAP_TYPE),
+    #[error("the descriptor heap range has {available} descriptors, but {required} are required")]
+    DescriptorHeapRangeTooSmall { required: usize, available: usize },
     #[error("unreachable")]
     Infallible(#[from] std::convert::Infallible),
 }
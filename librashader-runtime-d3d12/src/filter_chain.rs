@@ -1,5 +1,8 @@
 use crate::buffer::{D3D12Buffer, RawD3D12Buffer};
-use crate::descriptor_heap::{CpuStagingHeap, RenderTargetHeap, ResourceWorkHeap};
+use crate::descriptor_heap::{
+    self, CpuStagingHeap, RenderTargetHeap, ResourceWorkHeap, SamplerWorkHeap, WorkHeap,
+    WorkHeapSlot,
+};
 use crate::draw_quad::DrawQuad;
 use crate::error::FilterChainError;
 use crate::filter_pass::FilterPass;
@@ -7,16 +10,14 @@ use crate::framebuffer::OwnedImage;
 use crate::graphics_pipeline::{D3D12GraphicsPipeline, D3D12RootSignature};
 use crate::luts::LutTexture;
 use crate::mipmap::D3D12MipmapGen;
-use crate::options::{FilterChainOptionsD3D12, FrameOptionsD3D12};
+use crate::options::{D3D12DescriptorHeapRange, FilterChainOptionsD3D12, FrameOptionsD3D12};
 use crate::samplers::SamplerSet;
 use crate::texture::{D3D12InputImage, D3D12OutputView, InputTexture, OutputDescriptor};
 use crate::{error, util};
-use d3d12_descriptor_heap::{
-    D3D12DescriptorHeap, D3D12DescriptorHeapSlot, D3D12PartitionableHeap, D3D12PartitionedHeap,
-};
+use d3d12_descriptor_heap::D3D12DescriptorHeap;
 use gpu_allocator::d3d12::{Allocator, AllocatorCreateDesc, ID3D12DeviceVersion};
 use librashader_common::map::FastHashMap;
-use librashader_common::{ImageFormat, Size, Viewport};
+use librashader_common::{FormatFallback, ImageFormat, ScalingKernel, Size, Viewport};
 use librashader_presets::{ShaderFeatures, ShaderPreset};
 use librashader_reflect::back::targets::{DXIL, HLSL};
 use librashader_reflect::back::{CompileReflectShader, CompileShader};
@@ -24,9 +25,17 @@ use librashader_reflect::front::SpirvCompilation;
 use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
 use librashader_reflect::reflect::semantics::{ShaderSemantics, MAX_BINDINGS_COUNT};
 use librashader_reflect::reflect::ReflectShader;
-use librashader_runtime::binding::{BindingUtil, TextureInput};
+use librashader_runtime::binding::{BindingUtil, TextureInput, UniformBindings};
 use librashader_runtime::image::{ImageError, LoadedTexture, UVDirection};
+use librashader_runtime::instance::{self, LibrashaderInstance};
+use librashader_runtime::jitter;
+use librashader_runtime::latency::{LatencyMarker, LatencyMarkerCallback};
+use librashader_runtime::mesh::OutputMesh;
+use librashader_runtime::mvp;
+use librashader_runtime::pending::PendingFilterChain;
+use librashader_runtime::progress::{LoadStage, PassProgress};
 use librashader_runtime::quad::QuadType;
+use librashader_runtime::rewind::RewindFrameCount;
 use librashader_runtime::uniforms::UniformStorage;
 use parking_lot::Mutex;
 use std::collections::VecDeque;
@@ -41,10 +50,12 @@ use windows::Win32::Graphics::Direct3D::Dxc::{
 };
 use windows::Win32::Graphics::Direct3D12::{
     ID3D12CommandAllocator, ID3D12CommandQueue, ID3D12DescriptorHeap, ID3D12Device, ID3D12Fence,
-    ID3D12GraphicsCommandList, ID3D12Resource, D3D12_COMMAND_LIST_TYPE_DIRECT,
-    D3D12_COMMAND_QUEUE_DESC, D3D12_COMMAND_QUEUE_FLAG_NONE, D3D12_FENCE_FLAG_NONE,
-    D3D12_RESOURCE_BARRIER, D3D12_RESOURCE_BARRIER_TYPE_TRANSITION,
-    D3D12_RESOURCE_BARRIER_TYPE_UAV, D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
+    ID3D12GraphicsCommandList, ID3D12QueryHeap, ID3D12Resource, D3D12_COMMAND_LIST_TYPE_DIRECT,
+    D3D12_COMMAND_QUEUE_DESC, D3D12_COMMAND_QUEUE_FLAG_NONE,
+    D3D12_DESCRIPTOR_HEAP_TYPE_CBV_SRV_UAV, D3D12_DESCRIPTOR_HEAP_TYPE_SAMPLER,
+    D3D12_FENCE_FLAG_NONE, D3D12_QUERY_TYPE_TIMESTAMP, D3D12_RESOURCE_BARRIER,
+    D3D12_RESOURCE_BARRIER_TYPE_TRANSITION, D3D12_RESOURCE_BARRIER_TYPE_UAV,
+    D3D12_RESOURCE_STATE_NON_PIXEL_SHADER_RESOURCE, D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
     D3D12_RESOURCE_STATE_RENDER_TARGET,
 };
 use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_UNKNOWN;
@@ -53,9 +64,14 @@ use windows::Win32::System::Threading::{CreateEventA, WaitForSingleObject, INFIN
 use librashader_cache::CachedCompilation;
 use librashader_presets::context::VideoDriver;
 use librashader_reflect::reflect::cross::SpirvCross;
-use librashader_runtime::framebuffer::FramebufferInit;
+use librashader_runtime::filter_pass::{
+    requested_format, FilterPassMeta, IdentityPasses, PassInfo,
+};
+use librashader_runtime::framebuffer::{FramebufferInit, HistoryDepth};
 use librashader_runtime::render_target::RenderTarget;
 use librashader_runtime::scaling::ScaleFramebuffer;
+use librashader_runtime::statistics::{FrameStatistics, FrameStatisticsRecorder};
+use librashader_runtime::warnings::OptionWarning;
 use rayon::prelude::*;
 
 const MIPMAP_RESERVED_WORKHEAP_DESCRIPTORS: usize = 4096;
@@ -64,24 +80,50 @@ const MIPMAP_RESERVED_WORKHEAP_DESCRIPTORS: usize = 4096;
 pub struct FilterChainD3D12 {
     pub(crate) common: FilterCommon,
     pub(crate) passes: Vec<FilterPass>,
+    pub(crate) identity: IdentityPasses<FilterPass>,
+    /// The heap ranges reserved for the identity pass of every scaling kernel.
+    identity_heaps: Box<[PassHeaps]>,
+    pass_options: PassOptions,
     pub(crate) output_framebuffers: Box<[OwnedImage]>,
     pub(crate) feedback_framebuffers: Box<[OwnedImage]>,
     pub(crate) history_framebuffers: VecDeque<OwnedImage>,
+    history_depth: HistoryDepth,
+    option_warnings: Box<[OptionWarning]>,
     pub(crate) staging_heap: D3D12DescriptorHeap<CpuStagingHeap>,
     pub(crate) rtv_heap: D3D12DescriptorHeap<RenderTargetHeap>,
 
     work_heap: ID3D12DescriptorHeap,
     sampler_heap: ID3D12DescriptorHeap,
+    // the caller binds both heaps when they are owned by the caller.
+    external_heaps: bool,
 
     residuals: FrameResiduals,
-    mipmap_heap: D3D12DescriptorHeap<ResourceWorkHeap>,
+    mipmap_heap: WorkHeap<ResourceWorkHeap>,
 
     disable_mipmaps: bool,
 
     default_options: FrameOptionsD3D12,
     draw_last_pass_feedback: bool,
+    disable_temporal: bool,
+    rewind: RewindFrameCount,
+    async_pipeline_rebuild: bool,
+    invalidated_history: usize,
+    latency_markers: Option<LatencyMarkerCallback>,
+    timestamp_queries: Option<(ID3D12QueryHeap, u32)>,
+}
+
+/// The options that the shader passes were created with, to create the identity passes with
+/// when they are first used.
+#[derive(Clone, Copy)]
+struct PassOptions {
+    force_hlsl: bool,
+    disable_cache: bool,
+    format_fallback: FormatFallback,
 }
 
+/// The heap ranges that the descriptors of a shader pass are allocated from.
+type PassHeaps = (WorkHeap<ResourceWorkHeap>, WorkHeap<SamplerWorkHeap>);
+
 pub(crate) struct FilterCommon {
     pub(crate) d3d12: ID3D12Device,
     pub samplers: SamplerSet,
@@ -95,11 +137,13 @@ pub(crate) struct FilterCommon {
     pub root_signature: D3D12RootSignature,
     pub draw_quad: DrawQuad,
     allocator: Arc<Mutex<Allocator>>,
+    pub(crate) frame_jitter: [f32; 2],
+    pub statistics: FrameStatisticsRecorder,
 }
 
 pub(crate) struct FrameResiduals {
     outputs: Vec<OutputDescriptor>,
-    mipmaps: Vec<D3D12DescriptorHeapSlot<ResourceWorkHeap>>,
+    mipmaps: Vec<WorkHeapSlot<ResourceWorkHeap>>,
     mipmap_luts: Vec<D3D12MipmapGen>,
     resources: Vec<ManuallyDrop<Option<ID3D12Resource>>>,
     resource_barriers: Vec<D3D12_RESOURCE_BARRIER>,
@@ -124,10 +168,7 @@ impl FrameResiduals {
         self.outputs.push(descriptor)
     }
 
-    pub fn dispose_mipmap_handles(
-        &mut self,
-        handles: Vec<D3D12DescriptorHeapSlot<ResourceWorkHeap>>,
-    ) {
+    pub fn dispose_mipmap_handles(&mut self, handles: Vec<WorkHeapSlot<ResourceWorkHeap>>) {
         self.mipmaps.extend(handles)
     }
 
@@ -240,10 +281,20 @@ mod compile {
 
 use crate::resource::OutlivesFrame;
 use compile::{compile_passes_dxil, compile_passes_hlsl, DxilShaderPassMeta, HlslShaderPassMeta};
-use librashader_pack::{ShaderPresetPack, TextureResource};
+use librashader_pack::{PassResource, ShaderPresetPack, TextureResource};
 use librashader_runtime::parameters::RuntimeParameters;
 
 impl FilterChainD3D12 {
+    /// Create a filter chain without any shader passes, that only scales the input to the viewport.
+    ///
+    /// This is equivalent to loading a preset without any shader passes.
+    pub unsafe fn new_identity(
+        device: &ID3D12Device,
+        options: Option<&FilterChainOptionsD3D12>,
+    ) -> error::Result<FilterChainD3D12> {
+        unsafe { Self::load_from_pack(ShaderPresetPack::empty(), device, options) }
+    }
+
     /// Load the shader preset at the given path into a filter chain.
     pub unsafe fn load_from_path(
         path: impl AsRef<Path>,
@@ -268,6 +319,26 @@ impl FilterChainD3D12 {
         unsafe { Self::load_from_pack(preset, device, options) }
     }
 
+    /// Load a filter chain from a pre-parsed `ShaderPreset` on a worker thread.
+    ///
+    /// Shaders are compiled without blocking the calling thread, and the returned
+    /// [`PendingFilterChain`] can be polled or awaited for the filter chain. LUTs are
+    /// uploaded on a command queue owned by the worker thread. The progress callback of the
+    /// options is called on the worker thread.
+    ///
+    /// Returns an error if the worker thread could not be spawned.
+    pub unsafe fn load_from_preset_async(
+        preset: ShaderPreset,
+        device: &ID3D12Device,
+        options: Option<FilterChainOptionsD3D12>,
+    ) -> error::Result<PendingFilterChain<error::Result<FilterChainD3D12>>> {
+        let device = device.clone();
+        PendingFilterChain::spawn(move || unsafe {
+            Self::load_from_preset(preset, &device, options.as_ref())
+        })
+        .map_err(FilterChainError::WorkerThread)
+    }
+
     /// Load a filter chain from a pre-parsed `ShaderPreset`.
     pub unsafe fn load_from_pack(
         preset: ShaderPresetPack,
@@ -332,20 +403,43 @@ impl FilterChainD3D12 {
     /// The caller is responsible for ending the command list and immediately submitting it to a
     /// graphics queue. The command list must be completely executed before calling [`frame`](Self::frame).
     pub unsafe fn load_from_pack_deferred(
-        preset: ShaderPresetPack,
+        mut preset: ShaderPresetPack,
         device: &ID3D12Device,
         cmd: &ID3D12GraphicsCommandList,
         options: Option<&FilterChainOptionsD3D12>,
     ) -> error::Result<FilterChainD3D12> {
-        let shader_count = preset.passes.len();
+        let instance = options.and_then(|o| o.instance.as_ref());
+        let _instance = instance.map(LibrashaderInstance::enter);
+
+        if options.map_or(false, |o| o.color_assist) {
+            preset.append_color_assist();
+        }
+        if options.map_or(false, |o| o.black_frame_insertion) {
+            preset.append_black_frame_insertion();
+        }
+
+        let progress = PassProgress::new(
+            options.and_then(|o| o.progress.as_ref()),
+            preset.passes.len(),
+        )
+        .with_cancellation(options.and_then(|o| o.cancel.as_ref()));
+
+        let shaders = preset.passes;
+
+        // heap ranges are reserved for the identity passes, which are only compiled when used.
+        let preset_shader_count = shaders.len();
+        let shader_count = preset_shader_count + ScalingKernel::ALL.len();
         let lut_count = preset.textures.len();
 
-        let shader_copy = preset.passes.clone();
+        let shader_copy = shaders.clone();
         let disable_cache = options.map_or(false, |o| o.disable_cache);
 
-        let (passes, semantics) =
-            compile_passes_dxil(preset.passes, &preset.textures, disable_cache)?;
+        let (passes, semantics) = compile_passes_dxil(shaders, &preset.textures, disable_cache)?;
         let (hlsl_passes, _) = compile_passes_hlsl(shader_copy, &preset.textures, disable_cache)?;
+        progress.report_all(LoadStage::Preprocessing);
+        if progress.is_cancelled() {
+            return Err(FilterChainError::Cancelled);
+        }
 
         let samplers = SamplerSet::new(device)?;
         let mipmap_gen = D3D12MipmapGen::new(device, false)?;
@@ -377,15 +471,43 @@ impl FilterChainD3D12 {
 
         let root_signature = D3D12RootSignature::new(device)?;
 
-        let (texture_heap, sampler_heap, filters, mut mipmap_heap) = FilterChainD3D12::init_passes(
+        let parameters = RuntimeParameters::new(
+            preset.pass_count as usize,
+            preset.parameters,
+            passes
+                .iter()
+                .flat_map(|(pass, _)| pass.data.parameters.values()),
+        );
+
+        let pass_options = PassOptions {
+            force_hlsl: options.map_or(false, |o| o.force_hlsl_pipeline),
+            disable_cache,
+            format_fallback: options.map_or(FormatFallback::default(), |o| {
+                FormatFallback::from(o.format_fallback)
+            }),
+        };
+
+        let (texture_heap, sampler_heap, mut pass_heaps, mut mipmap_heap) =
+            FilterChainD3D12::init_heaps(
+                device,
+                shader_count,
+                options.and_then(|o| o.resource_heap.as_ref()),
+                options.and_then(|o| o.sampler_heap.as_ref()),
+            )?;
+        let identity_heaps = pass_heaps.split_off(preset_shader_count);
+
+        let filters = FilterChainD3D12::init_passes(
             device,
             &root_signature,
             &allocator,
             passes,
             hlsl_passes,
+            pass_heaps,
             &semantics,
-            options.map_or(false, |o| o.force_hlsl_pipeline),
-            disable_cache,
+            &parameters,
+            pass_options,
+            progress,
+            instance,
         )?;
 
         let mut residuals = FrameResiduals::new();
@@ -414,7 +536,15 @@ impl FilterChainD3D12 {
             filters.iter().map(|f| &f.reflection.meta),
             &framebuffer_gen,
             &input_gen,
-        );
+        )
+        .with_max_history(options.map_or(0, |o| o.max_history as usize))
+        .with_temporal_disabled(options.map_or(false, |o| o.disable_temporal));
+        let history_depth = framebuffer_init.history_depth();
+        let mut option_warnings = framebuffer_init.option_warnings();
+        option_warnings.extend(OptionWarning::check_mipmap_input(
+            options.map_or(false, |o| o.force_no_mipmaps),
+            filters.iter().map(|f| f.meta.mipmap_input),
+        ));
 
         // initialize output framebuffers
         let (output_framebuffers, output_textures) = framebuffer_init.init_output_framebuffers()?;
@@ -428,7 +558,18 @@ impl FilterChainD3D12 {
 
         Ok(FilterChainD3D12 {
             draw_last_pass_feedback: framebuffer_init.uses_final_pass_as_feedback(),
+            disable_temporal: options.map_or(false, |o| o.disable_temporal),
+            rewind: RewindFrameCount::new(
+                options.map_or(false, |o| o.freeze_frame_count_on_rewind),
+            ),
+            async_pipeline_rebuild: options.map_or(false, |o| o.async_pipeline_rebuild),
+            invalidated_history: 0,
+            latency_markers: options.and_then(|o| o.latency_markers.clone()),
+            timestamp_queries: None,
             common: FilterCommon {
+                statistics: FrameStatisticsRecorder::new(
+                    options.map_or(false, |o| o.collect_statistics),
+                ),
                 d3d12: device.clone(),
                 samplers,
                 allocator,
@@ -438,17 +579,25 @@ impl FilterChainD3D12 {
                 mipmap_gen,
                 root_signature,
                 draw_quad,
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                config: parameters,
                 history_textures,
+                frame_jitter: [0.0, 0.0],
             },
             staging_heap,
             rtv_heap,
             passes: filters,
+            identity: IdentityPasses::default(),
+            identity_heaps: identity_heaps.into_boxed_slice(),
+            pass_options,
             output_framebuffers,
             feedback_framebuffers,
             history_framebuffers,
+            history_depth,
+            option_warnings: option_warnings.into_boxed_slice(),
             work_heap: texture_heap,
             sampler_heap,
+            external_heaps: options
+                .is_some_and(|o| o.resource_heap.is_some() && o.sampler_heap.is_some()),
             mipmap_heap,
             disable_mipmaps: options.map_or(false, |o| o.force_no_mipmaps),
             residuals,
@@ -461,7 +610,7 @@ impl FilterChainD3D12 {
         cmd: &ID3D12GraphicsCommandList,
         allocator: &Arc<Mutex<Allocator>>,
         staging_heap: &mut D3D12DescriptorHeap<CpuStagingHeap>,
-        mipmap_heap: &mut D3D12DescriptorHeap<ResourceWorkHeap>,
+        mipmap_heap: &mut WorkHeap<ResourceWorkHeap>,
         gc: &mut FrameResiduals,
         textures: Vec<TextureResource>,
     ) -> error::Result<FastHashMap<usize, LutTexture>> {
@@ -484,19 +633,24 @@ impl FilterChainD3D12 {
                 meta.filter_mode,
                 meta.wrap_mode,
                 meta.mipmap,
+                meta.srgb,
                 gc,
             )?;
             luts.insert(index, texture);
         }
 
-        let (residual_mipmap, residual_barrier) =
-            mipmap_gen.mipmapping_context(cmd, mipmap_heap, |context| {
+        let (residual_mipmap, residual_barrier) = mipmap_gen.mipmapping_context(
+            cmd,
+            mipmap_heap,
+            D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
+            |context| {
                 for lut in luts.values() {
                     lut.generate_mipmaps(context)?;
                 }
 
                 Ok::<(), FilterChainError>(())
-            })?;
+            },
+        )?;
 
         gc.dispose_mipmap_handles(residual_mipmap);
         gc.dispose_mipmap_gen(mipmap_gen);
@@ -508,166 +662,288 @@ impl FilterChainD3D12 {
         Ok(luts)
     }
 
-    fn init_passes(
+    /// Create the heaps that the descriptors of shader passes are allocated from, with a range
+    /// for each of `shader_count` passes.
+    fn init_heaps(
         device: &ID3D12Device,
-        root_signature: &D3D12RootSignature,
-        allocator: &Arc<Mutex<Allocator>>,
-        passes: Vec<DxilShaderPassMeta>,
-        hlsl_passes: Vec<HlslShaderPassMeta>,
-        semantics: &ShaderSemantics,
-        force_hlsl: bool,
-        disable_cache: bool,
+        shader_count: usize,
+        resource_heap: Option<&D3D12DescriptorHeapRange>,
+        sampler_heap: Option<&D3D12DescriptorHeapRange>,
     ) -> error::Result<(
         ID3D12DescriptorHeap,
         ID3D12DescriptorHeap,
-        Vec<FilterPass>,
-        D3D12DescriptorHeap<ResourceWorkHeap>,
+        Vec<PassHeaps>,
+        WorkHeap<ResourceWorkHeap>,
     )> {
-        let shader_count = passes.len();
-        let D3D12PartitionedHeap {
-            partitioned: work_heaps,
-            reserved: mipmap_heap,
-            handle: texture_heap_handle,
-        } = unsafe {
-            let work_heap = D3D12PartitionableHeap::<ResourceWorkHeap>::new(
-                device,
-                (MAX_BINDINGS_COUNT as usize) * shader_count + MIPMAP_RESERVED_WORKHEAP_DESCRIPTORS,
-            )?;
+        let bindings_count = MAX_BINDINGS_COUNT as usize;
+
+        // heap ranges of the caller are sub-allocated from, otherwise heaps are created to fit.
+        let texture_heap_size =
+            bindings_count * shader_count + MIPMAP_RESERVED_WORKHEAP_DESCRIPTORS;
+        let (texture_heap_handle, texture_heap_offset) = match resource_heap {
+            Some(range) => {
+                descriptor_heap::validate_heap_range(
+                    range,
+                    D3D12_DESCRIPTOR_HEAP_TYPE_CBV_SRV_UAV,
+                    texture_heap_size,
+                )?;
+                (range.heap.clone(), range.offset as usize)
+            }
+            None => unsafe {
+                (
+                    WorkHeap::<ResourceWorkHeap>::create_heap(device, texture_heap_size)?,
+                    0,
+                )
+            },
+        };
 
-            work_heap.into_partitioned(
-                MAX_BINDINGS_COUNT as usize,
-                MIPMAP_RESERVED_WORKHEAP_DESCRIPTORS,
-            )?
+        let sampler_heap_size = bindings_count * shader_count;
+        let (sampler_heap_handle, sampler_heap_offset) = match sampler_heap {
+            Some(range) => {
+                descriptor_heap::validate_heap_range(
+                    range,
+                    D3D12_DESCRIPTOR_HEAP_TYPE_SAMPLER,
+                    sampler_heap_size,
+                )?;
+                (range.heap.clone(), range.offset as usize)
+            }
+            None => unsafe {
+                (
+                    WorkHeap::<SamplerWorkHeap>::create_heap(device, sampler_heap_size)?,
+                    0,
+                )
+            },
         };
 
-        let D3D12PartitionedHeap {
-            partitioned: sampler_work_heaps,
-            reserved: _,
-            handle: sampler_heap_handle,
-        } = unsafe {
-            let sampler_heap =
-                D3D12PartitionableHeap::new(device, (MAX_BINDINGS_COUNT as usize) * shader_count)?;
-            sampler_heap.into_partitioned(MAX_BINDINGS_COUNT as usize, 0)?
+        // every pass gets its own range of bindings, followed by the range for mipmaps.
+        let work_heaps: Vec<WorkHeap<ResourceWorkHeap>> = (0..shader_count)
+            .map(|index| unsafe {
+                WorkHeap::new(
+                    device,
+                    &texture_heap_handle,
+                    texture_heap_offset + index * bindings_count,
+                    bindings_count,
+                )
+            })
+            .collect();
+        let mipmap_heap = unsafe {
+            WorkHeap::new(
+                device,
+                &texture_heap_handle,
+                texture_heap_offset + shader_count * bindings_count,
+                MIPMAP_RESERVED_WORKHEAP_DESCRIPTORS,
+            )
         };
 
-        let filters: Vec<error::Result<_>> = passes
-            .into_par_iter()
-            .zip(hlsl_passes)
-            .zip(work_heaps)
-            .zip(sampler_work_heaps)
-            .enumerate()
-            .map_init(
-                || {
-                    let validator: IDxcValidator =
-                        unsafe { DxcCreateInstance(&CLSID_DxcValidator)? };
-                    let library: IDxcUtils = unsafe { DxcCreateInstance(&CLSID_DxcLibrary)? };
-                    let compiler: IDxcCompiler = unsafe { DxcCreateInstance(&CLSID_DxcCompiler)? };
-                    Ok::<_, FilterChainError>((validator, library, compiler))
-                },
-                |dxc,
-                 (
-                    index,
-                    ((((config, mut dxil), (_, mut hlsl)), mut texture_heap), mut sampler_heap),
-                )| {
-                    let Ok((validator, library, compiler)) = dxc else {
-                        return Err(FilterChainError::Direct3DOperationError(
-                            "Could not initialize DXC for thread",
-                        ));
-                    };
-
-                    let dxil_reflection = dxil.reflect(index, semantics)?;
-                    let dxil = dxil.compile(Some(
-                        librashader_reflect::back::dxil::ShaderModel::ShaderModel6_0,
-                    ))?;
-
-                    let render_format = if let Some(format) = config.meta.get_format_override() {
-                        format
-                    } else if config.data.format != ImageFormat::Unknown {
-                        config.data.format
-                    } else {
-                        ImageFormat::R8G8B8A8Unorm
-                    }
-                    .into();
+        let sampler_work_heaps: Vec<WorkHeap<SamplerWorkHeap>> = (0..shader_count)
+            .map(|index| unsafe {
+                WorkHeap::new(
+                    device,
+                    &sampler_heap_handle,
+                    sampler_heap_offset + index * bindings_count,
+                    bindings_count,
+                )
+            })
+            .collect();
+
+        let pass_heaps = work_heaps.into_iter().zip(sampler_work_heaps).collect();
+
+        Ok((
+            texture_heap_handle,
+            sampler_heap_handle,
+            pass_heaps,
+            mipmap_heap,
+        ))
+    }
+
+    /// Create the identity pass that scales its input to the viewport with the given kernel.
+    fn init_identity(
+        common: &FilterCommon,
+        heaps: &PassHeaps,
+        options: PassOptions,
+        kernel: ScalingKernel,
+    ) -> error::Result<FilterPass> {
+        let shaders = vec![PassResource::identity(kernel)];
+        let (passes, semantics) = compile_passes_dxil(shaders.clone(), &[], options.disable_cache)?;
+        let (hlsl_passes, _) = compile_passes_hlsl(shaders, &[], options.disable_cache)?;
+
+        // the reserved ranges are shared, so that the descriptors of a pass are returned to them.
+        let (texture_heap, sampler_heap) = heaps;
+        let pass = Self::init_passes(
+            &common.d3d12,
+            &common.root_signature,
+            &common.allocator,
+            passes,
+            hlsl_passes,
+            vec![(texture_heap.clone(), sampler_heap.clone())],
+            &semantics,
+            &common.config,
+            options,
+            PassProgress::default(),
+            None,
+        )?
+        .remove(0);
+
+        Ok(pass)
+    }
 
-                    // incredibly cursed.
-                    let (reflection, graphics_pipeline) = 'pipeline: {
-                        'dxil: {
-                            if force_hlsl {
-                                break 'dxil;
+    #[allow(clippy::too_many_arguments)]
+    fn init_passes(
+        device: &ID3D12Device,
+        root_signature: &D3D12RootSignature,
+        allocator: &Arc<Mutex<Allocator>>,
+        passes: Vec<DxilShaderPassMeta>,
+        hlsl_passes: Vec<HlslShaderPassMeta>,
+        pass_heaps: Vec<PassHeaps>,
+        semantics: &ShaderSemantics,
+        parameters: &RuntimeParameters,
+        options: PassOptions,
+        progress: PassProgress,
+        instance: Option<&LibrashaderInstance>,
+    ) -> error::Result<Vec<FilterPass>> {
+        let PassOptions {
+            force_hlsl,
+            disable_cache,
+            format_fallback,
+        } = options;
+
+        let filters: Vec<error::Result<_>> = instance::install(instance, || {
+            passes
+                .into_par_iter()
+                .zip(hlsl_passes)
+                .zip(pass_heaps)
+                .enumerate()
+                .map_init(
+                    || {
+                        let validator: IDxcValidator =
+                            unsafe { DxcCreateInstance(&CLSID_DxcValidator)? };
+                        let library: IDxcUtils = unsafe { DxcCreateInstance(&CLSID_DxcLibrary)? };
+                        let compiler: IDxcCompiler =
+                            unsafe { DxcCreateInstance(&CLSID_DxcCompiler)? };
+                        Ok::<_, FilterChainError>((validator, library, compiler))
+                    },
+                    |dxc,
+                     (
+                        index,
+                        (((config, mut dxil), (_, mut hlsl)), (mut texture_heap, mut sampler_heap)),
+                    )| {
+                        let Ok((validator, library, compiler)) = dxc else {
+                            return Err(FilterChainError::Direct3DOperationError(
+                                "Could not initialize DXC for thread",
+                            ));
+                        };
+
+                        if progress.is_cancelled() {
+                            return Err(FilterChainError::Cancelled);
+                        }
+
+                        let dxil_reflection = dxil.reflect(index, semantics)?;
+                        let dxil = dxil.compile(Some(
+                            librashader_reflect::back::dxil::ShaderModel::ShaderModel6_0,
+                        ))?;
+                        progress.report(LoadStage::Compiling, index);
+
+                        let requested_format = requested_format(&config.meta, config.data.format);
+                        let format = util::d3d12_select_supported_format(
+                            device,
+                            requested_format,
+                            format_fallback,
+                        )
+                        .ok_or(FilterChainError::UnsupportedFormat(requested_format))?;
+                        let render_format = format.into();
+
+                        // incredibly cursed.
+                        let (reflection, graphics_pipeline) = 'pipeline: {
+                            'dxil: {
+                                if force_hlsl {
+                                    break 'dxil;
+                                }
+
+                                if let Ok(graphics_pipeline) = D3D12GraphicsPipeline::new_from_dxil(
+                                    device,
+                                    library,
+                                    validator,
+                                    &dxil,
+                                    root_signature,
+                                    render_format,
+                                    disable_cache,
+                                ) {
+                                    break 'pipeline (dxil_reflection, graphics_pipeline);
+                                }
                             }
 
-                            if let Ok(graphics_pipeline) = D3D12GraphicsPipeline::new_from_dxil(
+                            let hlsl_reflection = hlsl.reflect(index, semantics)?;
+                            let hlsl = hlsl.compile(Some(
+                                librashader_reflect::back::hlsl::HlslShaderModel::ShaderModel6_0,
+                            ))?;
+
+                            let graphics_pipeline = D3D12GraphicsPipeline::new_from_hlsl(
                                 device,
                                 library,
-                                validator,
-                                &dxil,
+                                compiler,
+                                &hlsl,
                                 root_signature,
                                 render_format,
                                 disable_cache,
-                            ) {
-                                break 'pipeline (dxil_reflection, graphics_pipeline);
-                            }
-                        }
-
-                        let hlsl_reflection = hlsl.reflect(index, semantics)?;
-                        let hlsl = hlsl.compile(Some(
-                            librashader_reflect::back::hlsl::HlslShaderModel::ShaderModel6_0,
-                        ))?;
-
-                        let graphics_pipeline = D3D12GraphicsPipeline::new_from_hlsl(
-                            device,
-                            library,
-                            compiler,
-                            &hlsl,
-                            root_signature,
-                            render_format,
-                            disable_cache,
-                        )?;
-                        (hlsl_reflection, graphics_pipeline)
-                    };
-
-                    // minimum size here has to be 1 byte.
-                    let ubo_size = reflection.ubo.as_ref().map_or(1, |ubo| ubo.size as usize);
-                    let push_size = reflection
-                        .push_constant
-                        .as_ref()
-                        .map_or(1, |push| push.size as usize);
-
-                    let uniform_storage = UniformStorage::new_with_storage(
-                        RawD3D12Buffer::new(D3D12Buffer::new(allocator, ubo_size)?)?,
-                        RawD3D12Buffer::new(D3D12Buffer::new(allocator, push_size)?)?,
-                    );
-
-                    let uniform_bindings =
-                        reflection.meta.create_binding_map(|param| param.offset());
-
-                    let texture_heap = texture_heap.allocate_descriptor_range()?;
-                    let sampler_heap = sampler_heap.allocate_descriptor_range()?;
-
-                    Ok(FilterPass {
-                        reflection,
-                        uniform_bindings,
-                        uniform_storage,
-                        pipeline: graphics_pipeline,
-                        meta: config.meta,
-                        texture_heap,
-                        sampler_heap,
-                        source: config.data,
-                    })
-                },
-            )
-            .collect();
+                            )?;
+                            (hlsl_reflection, graphics_pipeline)
+                        };
+                        progress.report(LoadStage::Pipeline, index);
+
+                        // minimum size here has to be 1 byte.
+                        let ubo_size = reflection.ubo.as_ref().map_or(1, |ubo| ubo.size as usize);
+                        let push_size = reflection
+                            .push_constant
+                            .as_ref()
+                            .map_or(1, |push| push.size as usize);
+
+                        let uniform_storage = UniformStorage::new_with_storage(
+                            RawD3D12Buffer::new(D3D12Buffer::new(allocator, ubo_size)?)?,
+                            RawD3D12Buffer::new(D3D12Buffer::new(allocator, push_size)?)?,
+                        );
+
+                        let uniform_bindings = UniformBindings::new(
+                            reflection.meta.create_binding_map(|param| param.offset()),
+                            parameters,
+                        );
+
+                        let texture_heap = texture_heap.allocate_descriptor_range()?;
+                        let sampler_heap = sampler_heap.allocate_descriptor_range()?;
+
+                        Ok(FilterPass {
+                            reflection,
+                            uniform_bindings,
+                            uniform_storage,
+                            pipeline: graphics_pipeline,
+                            meta: config.meta,
+                            path: config.path,
+                            format,
+                            texture_heap,
+                            sampler_heap,
+                            source: config.data,
+                        })
+                    },
+                )
+                .collect()
+        });
 
-        let filters: error::Result<Vec<_>> = filters.into_iter().collect();
-        let filters = filters?;
+        filters.into_iter().collect()
+    }
 
-        // Panic SAFETY: mipmap_heap is always 1024 descriptors.
-        Ok((
-            texture_heap_handle,
-            sampler_heap_handle,
-            filters,
-            mipmap_heap.unwrap(),
-        ))
+    // bind the state that shader passes expect on a graphics command list of the frame.
+    unsafe fn bind_pass_state(
+        cmd: &ID3D12GraphicsCommandList,
+        common: &FilterCommon,
+        heaps: Option<&[Option<ID3D12DescriptorHeap>; 2]>,
+    ) {
+        unsafe {
+            if let Some(heaps) = heaps {
+                cmd.SetDescriptorHeaps(heaps);
+            }
+            cmd.SetGraphicsRootSignature(&common.root_signature.handle);
+        }
+        common.mipmap_gen.pin_root_signature(cmd);
+        common.draw_quad.bind_vertices_for_frame(cmd);
     }
 
     fn push_history(
@@ -695,12 +971,56 @@ impl FilterChainD3D12 {
             unsafe {
                 back.copy_from(cmd, input)?;
             }
+
+            // the copy transitions the input and the history frame, and back again.
+            self.common.statistics.record_barriers(4);
             self.history_framebuffers.push_front(back);
         }
 
         Ok(())
     }
 
+    /// Get the depth of the history kept by the filter chain, and the depth
+    /// requested by the shader passes.
+    pub fn history_depth(&self) -> HistoryDepth {
+        self.history_depth
+    }
+
+    /// Get the warnings about options of the filter chain that could not be honoured as
+    /// given, such as mipmaps being forced off for shader passes that request them.
+    pub fn option_warnings(&self) -> &[OptionWarning] {
+        &self.option_warnings
+    }
+
+    /// Invalidate the last `frames` frames of history, such as after a rollback in runahead
+    /// or netplay, so that shader passes do not blend frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+    /// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+    pub fn invalidate_history(&mut self, frames: usize) {
+        self.invalidated_history = std::cmp::max(self.invalidated_history, frames);
+    }
+
+    /// Get information about the shader pass at the given index, including the
+    /// framebuffer format chosen for the pass.
+    pub fn pass_info(&self, index: usize) -> Option<PassInfo> {
+        self.passes.get(index).map(FilterPassMeta::pass_info)
+    }
+
+    /// Get the statistics of the commands recorded for the last frame, or `None` if the
+    /// filter chain was not created with the `collect_statistics` option.
+    pub fn frame_statistics(&self) -> Option<FrameStatistics> {
+        self.common.statistics.statistics()
+    }
+
+    /// Set the mesh to draw the final pass with, or reset it to the default quad if `None`.
+    ///
+    /// ## Safety
+    /// The command lists recorded with the previous mesh must have completed execution.
+    pub unsafe fn set_output_mesh(&mut self, mesh: Option<&OutputMesh>) -> error::Result<()> {
+        self.common.draw_quad.set_mesh(&self.common.allocator, mesh)
+    }
+
     /// Records shader rendering commands to the provided command list.
     ///
     /// * The input image must be in the `D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE` resource state.
@@ -711,6 +1031,10 @@ impl FilterChainD3D12 {
     /// the output image to the final resource state.
     ///
     /// The input and output images must stay alive until the command list is submitted and work is complete.
+    ///
+    /// If the `mipmap_handoff` frame option is set, the frame may continue on the command lists
+    /// returned by the callback, in which case the final pass is recorded to the last graphics
+    /// command list that was returned.
     pub unsafe fn frame(
         &mut self,
         cmd: &ID3D12GraphicsCommandList,
@@ -719,14 +1043,96 @@ impl FilterChainD3D12 {
         frame_count: usize,
         options: Option<&FrameOptionsD3D12>,
     ) -> error::Result<()> {
+        unsafe {
+            self.frame_broadcast(
+                cmd,
+                input,
+                std::slice::from_ref(viewport),
+                frame_count,
+                options,
+            )
+        }
+    }
+
+    /// Write GPU timestamps around the commands of the next frame to the given query heap.
+    ///
+    /// The timestamp at `first_query` is written before the first command of the frame, and the
+    /// timestamp at `first_query + 1` after the last command, so that latency-focused frontends
+    /// can measure the GPU time of the filter chain within their frame. The queries are only
+    /// written for the next frame, so that frontends can rotate queries between frames in flight.
+    ///
+    /// If the `mipmap_handoff` frame option hands the frame off to other command lists, the query
+    /// pair spans several command lists. The timestamp at `first_query` is written to the command
+    /// list given to [`frame`](Self::frame), and the timestamp at `first_query + 1` to the last
+    /// graphics command list returned by the callback. The measured time then includes any time
+    /// the graphics queue waits for the compute command lists.
+    ///
+    /// ## Safety
+    /// The query heap must be a `D3D12_QUERY_HEAP_TYPE_TIMESTAMP` heap with at least
+    /// `first_query + 2` queries, and must stay alive until every command list of the next
+    /// frame has completed execution. If the frame is handed off, the graphics command lists
+    /// that contain the queries must be executed on the same queue.
+    pub unsafe fn set_timestamp_queries(&mut self, heap: &ID3D12QueryHeap, first_query: u32) {
+        self.timestamp_queries = Some((heap.clone(), first_query));
+    }
+
+    /// Records shader rendering commands to the provided command list, drawing the final pass
+    /// to every viewport.
+    ///
+    /// Intermediate passes are scaled relative to the first viewport and are only run once.
+    /// If `viewports` is empty, nothing is recorded.
+    ///
+    /// The same resource state requirements as [`FilterChainD3D12::frame`] apply to every
+    /// output image.
+    pub unsafe fn frame_broadcast(
+        &mut self,
+        cmd: &ID3D12GraphicsCommandList,
+        input: D3D12InputImage,
+        viewports: &[Viewport<D3D12OutputView>],
+        frame_count: usize,
+        options: Option<&FrameOptionsD3D12>,
+    ) -> error::Result<()> {
+        if let Some(markers) = &self.latency_markers {
+            markers.mark(LatencyMarker::RecordBegin, frame_count);
+        }
+
+        let timestamp_queries = self.timestamp_queries.take();
+        if let Some((heap, query)) = &timestamp_queries {
+            unsafe { cmd.EndQuery(heap, D3D12_QUERY_TYPE_TIMESTAMP, *query) };
+        }
+
+        // the rest of the frame moves to another command list after a mipmap handoff.
+        let mut cmd = cmd.clone();
+        let result = unsafe { self.record_frame(&mut cmd, input, viewports, frame_count, options) };
+
+        if let Some((heap, query)) = &timestamp_queries {
+            unsafe { cmd.EndQuery(heap, D3D12_QUERY_TYPE_TIMESTAMP, *query + 1) };
+        }
+
+        if let Some(markers) = &self.latency_markers {
+            markers.mark(LatencyMarker::RecordEnd, frame_count);
+        }
+
+        result
+    }
+
+    unsafe fn record_frame(
+        &mut self,
+        cmd: &mut ID3D12GraphicsCommandList,
+        input: D3D12InputImage,
+        viewports: &[Viewport<D3D12OutputView>],
+        frame_count: usize,
+        options: Option<&FrameOptionsD3D12>,
+    ) -> error::Result<()> {
+        // passes are scaled relative to the first viewport.
+        let Some(viewport) = viewports.first() else {
+            return Ok(());
+        };
+
         self.residuals.dispose();
 
         // limit number of passes to those enabled.
         let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
-        let passes = &mut self.passes[0..max];
-        if passes.is_empty() {
-            return Ok(());
-        }
 
         if let Some(options) = options {
             if options.clear_history {
@@ -736,7 +1142,57 @@ impl FilterChainD3D12 {
             }
         }
 
+        // with history and feedback disabled, the history is kept black.
+        if self.disable_temporal {
+            for framebuffer in &mut self.history_framebuffers {
+                framebuffer.clear(cmd, &mut self.rtv_heap)?;
+            }
+        }
+
+        // clear the history frames invalidated since the last frame.
+        let invalidated_history = std::mem::take(&mut self.invalidated_history);
+        for framebuffer in self
+            .history_framebuffers
+            .iter_mut()
+            .take(invalidated_history)
+        {
+            framebuffer.clear(cmd, &mut self.rtv_heap)?;
+        }
+
         let options = options.unwrap_or(&self.default_options);
+        self.common.statistics.reset();
+        let frame_count = self
+            .rewind
+            .frame_count(frame_count, options.frame_direction);
+        self.common.frame_jitter = jitter::resolve(
+            options.jitter_sequence.into(),
+            options.jitter_offset,
+            frame_count,
+        );
+
+        // the identity pass draws to the viewport instead of the final pass if there are no
+        // enabled passes, or if the output of the final pass is scaled to the viewport.
+        let identity = if max == 0 || options.scale_final_pass {
+            Some(
+                self.identity
+                    .get_or_try_init(options.identity_scaling, |kernel| {
+                        Self::init_identity(
+                            &self.common,
+                            &self.identity_heaps[kernel as usize],
+                            self.pass_options,
+                            kernel,
+                        )
+                    })?,
+            )
+        } else {
+            None
+        };
+
+        let scale_final_pass = max > 0 && identity.is_some();
+        let (passes, final_scaling) = match identity {
+            Some(identity) if max == 0 => (std::slice::from_mut(identity), None),
+            identity => (&mut self.passes[0..max], identity),
+        };
 
         let filter = passes[0].meta.filter;
         let wrap_mode = passes[0].meta.wrap_mode;
@@ -784,10 +1240,12 @@ impl FilterChainD3D12 {
         let mut source = original.clone();
 
         // swap output and feedback **before** recording command buffers
-        std::mem::swap(
-            &mut self.output_framebuffers,
-            &mut self.feedback_framebuffers,
-        );
+        if !self.disable_temporal {
+            std::mem::swap(
+                &mut self.output_framebuffers,
+                &mut self.feedback_framebuffers,
+            );
+        }
 
         // rescale render buffers to ensure all bindings are valid.
         OwnedImage::scale_framebuffers(
@@ -814,20 +1272,27 @@ impl FilterChainD3D12 {
             }),
         )?;
 
+        // feedback is kept black, including after the feedback framebuffers are resized.
+        // the feedback of the previous frame is also cleared if it was invalidated.
+        if self.disable_temporal || invalidated_history > 0 {
+            for framebuffer in &self.feedback_framebuffers {
+                framebuffer.clear(cmd, &mut self.rtv_heap)?;
+            }
+        }
+
         let passes_len = passes.len();
-        let (pass, last) = passes.split_at_mut(passes_len - 1);
+        let (pass, last) = match final_scaling {
+            Some(identity) => (passes, std::slice::from_mut(identity)),
+            None => passes.split_at_mut(passes_len - 1),
+        };
 
-        unsafe {
-            let heaps = [
+        let heaps = (!self.external_heaps).then(|| {
+            [
                 Some(self.work_heap.clone()),
                 Some(self.sampler_heap.clone()),
-            ];
-            cmd.SetDescriptorHeaps(&heaps);
-            cmd.SetGraphicsRootSignature(&self.common.root_signature.handle);
-            self.common.mipmap_gen.pin_root_signature(cmd);
-        }
-
-        self.common.draw_quad.bind_vertices_for_frame(cmd);
+            ]
+        });
+        unsafe { Self::bind_pass_state(cmd, &self.common, heaps.as_ref()) };
 
         for (index, pass) in pass.iter_mut().enumerate() {
             source.filter = pass.meta.filter;
@@ -867,18 +1332,66 @@ impl FilterChainD3D12 {
                 QuadType::Offscreen,
             )?;
 
+            let generate_mipmaps = target.max_mipmap > 1 && !self.disable_mipmaps;
+            let handoff = match &options.mipmap_handoff {
+                Some(callback) if generate_mipmaps => callback.handoff(index),
+                _ => None,
+            };
+
+            // outputs handed off to a compute list need a state that is valid on compute queues.
+            let output_state = if handoff.is_some() {
+                D3D12_RESOURCE_STATE_NON_PIXEL_SHADER_RESOURCE
+            } else {
+                D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE
+            };
             util::d3d12_resource_transition::<OutlivesFrame, _>(
                 cmd,
                 &target.resource,
                 D3D12_RESOURCE_STATE_RENDER_TARGET,
-                D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
+                output_state,
             );
+            self.common.statistics.record_barriers(2);
 
-            if target.max_mipmap > 1 && !self.disable_mipmaps {
+            if let Some(handoff) = handoff {
+                unsafe {
+                    if let Some(heaps) = &heaps {
+                        handoff.compute.SetDescriptorHeaps(heaps);
+                    }
+                }
+                self.common.mipmap_gen.pin_root_signature(&handoff.compute);
+
+                let (residuals, _residual_barriers) = self.common.mipmap_gen.mipmapping_context(
+                    &handoff.compute,
+                    &mut self.mipmap_heap,
+                    D3D12_RESOURCE_STATE_NON_PIXEL_SHADER_RESOURCE,
+                    |ctx| {
+                        ctx.generate_mipmaps::<OutlivesFrame, _>(
+                            &target.resource,
+                            target.max_mipmap,
+                            target.size,
+                            target.format.into(),
+                        )?;
+                        Ok::<(), FilterChainError>(())
+                    },
+                )?;
+                self.residuals.dispose_mipmap_handles(residuals);
+
+                util::d3d12_resource_transition::<OutlivesFrame, _>(
+                    &handoff.graphics,
+                    &target.resource,
+                    D3D12_RESOURCE_STATE_NON_PIXEL_SHADER_RESOURCE,
+                    D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
+                );
+                self.common.statistics.record_barriers(1);
+
+                unsafe { Self::bind_pass_state(&handoff.graphics, &self.common, heaps.as_ref()) };
+                *cmd = handoff.graphics;
+            } else if generate_mipmaps {
                 // barriers don't get disposed because the context is OutlivesFrame
                 let (residuals, _residual_barriers) = self.common.mipmap_gen.mipmapping_context(
                     cmd,
                     &mut self.mipmap_heap,
+                    D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
                     |ctx| {
                         ctx.generate_mipmaps::<OutlivesFrame, _>(
                             &target.resource,
@@ -905,7 +1418,8 @@ impl FilterChainD3D12 {
             source.filter = pass.meta.filter;
             source.wrap_mode = pass.meta.wrap_mode;
 
-            if self.draw_last_pass_feedback {
+            // the output of the final pass is already drawn if it is scaled to the viewport.
+            if self.draw_last_pass_feedback && !scale_final_pass {
                 let feedback_target = &self.output_framebuffers[index];
 
                 if !pass.pipeline.has_format(feedback_target.format) {
@@ -924,8 +1438,10 @@ impl FilterChainD3D12 {
                     D3D12_RESOURCE_STATE_RENDER_TARGET,
                 );
 
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
                 let view = feedback_target.create_render_target_view(&mut self.rtv_heap)?;
-                let out = RenderTarget::viewport_with_output(&view, viewport);
+                let out = RenderTarget::viewport_with_output(&view, viewport, &final_mvp);
                 pass.draw(
                     cmd,
                     index,
@@ -945,33 +1461,50 @@ impl FilterChainD3D12 {
                     D3D12_RESOURCE_STATE_RENDER_TARGET,
                     D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
                 );
+                self.common.statistics.record_barriers(2);
             }
 
-            if !pass.pipeline.has_format(viewport.output.format) {
-                // eprintln!("recompiling final pipeline");
-                pass.pipeline.recompile(
-                    viewport.output.format,
-                    &self.common.root_signature,
-                    &self.common.d3d12,
+            pass.pipeline.swap_finished()?;
+            for viewport in viewports {
+                if !pass.pipeline.has_format(viewport.output.format) {
+                    // eprintln!("recompiling final pipeline");
+                    if self.async_pipeline_rebuild {
+                        pass.pipeline.recompile_async(
+                            viewport.output.format,
+                            &self.common.root_signature,
+                            &self.common.d3d12,
+                        );
+                        continue;
+                    }
+
+                    pass.pipeline.recompile(
+                        viewport.output.format,
+                        &self.common.root_signature,
+                        &self.common.d3d12,
+                    )?;
+                }
+
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
+                let out = RenderTarget::viewport(viewport, &final_mvp);
+                pass.draw(
+                    cmd,
+                    passes_len - 1,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    &out,
+                    QuadType::Final,
                 )?;
             }
-
-            let out = RenderTarget::viewport(viewport);
-            pass.draw(
-                cmd,
-                passes_len - 1,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                &out,
-                QuadType::Final,
-            )?;
         }
 
-        self.push_history(cmd, &original)?;
+        if !self.disable_temporal {
+            self.push_history(cmd, &original)?;
+        }
 
         Ok(())
     }
@@ -1,23 +1,24 @@
 use crate::buffer::RawD3D12Buffer;
-use crate::descriptor_heap::{ResourceWorkHeap, SamplerWorkHeap};
+use crate::descriptor_heap::{ResourceWorkHeap, SamplerWorkHeap, WorkHeapSlot};
 use crate::error;
 use crate::filter_chain::FilterCommon;
 use crate::graphics_pipeline::D3D12GraphicsPipeline;
 use crate::options::FrameOptionsD3D12;
 use crate::samplers::SamplerSet;
 use crate::texture::{D3D12OutputView, InputTexture};
-use d3d12_descriptor_heap::D3D12DescriptorHeapSlot;
-use librashader_common::map::FastHashMap;
 use librashader_common::{ImageFormat, Size, Viewport};
 use librashader_preprocess::ShaderSource;
 use librashader_presets::PassMeta;
-use librashader_reflect::reflect::semantics::{MemberOffset, TextureBinding, UniformBinding};
+use librashader_reflect::reflect::semantics::{MemberOffset, TextureBinding};
 use librashader_reflect::reflect::ShaderReflection;
-use librashader_runtime::binding::{BindSemantics, TextureInput, UniformInputs};
+use librashader_runtime::binding::{
+    BindSemantics, TextureInput, UniformBindings, UniformInputs, DEFAULT_SCENE_LUMINANCE,
+};
 use librashader_runtime::filter_pass::FilterPassMeta;
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
 use librashader_runtime::uniforms::{NoUniformBinder, UniformStorage};
+use std::path::{Path, PathBuf};
 use windows::core::Interface;
 use windows::Win32::Foundation::RECT;
 use windows::Win32::Graphics::Direct3D12::{
@@ -31,11 +32,15 @@ pub(crate) struct FilterPass {
     pub(crate) pipeline: D3D12GraphicsPipeline,
     pub(crate) reflection: ShaderReflection,
     pub(crate) meta: PassMeta,
-    pub(crate) uniform_bindings: FastHashMap<UniformBinding, MemberOffset>,
+    pub(crate) path: Option<PathBuf>,
+    /// The framebuffer format of the pass, after falling back to a format supported
+    /// by the device.
+    pub(crate) format: ImageFormat,
+    pub(crate) uniform_bindings: UniformBindings<MemberOffset>,
     pub uniform_storage:
         UniformStorage<NoUniformBinder, Option<()>, RawD3D12Buffer, RawD3D12Buffer>,
-    pub(crate) texture_heap: [D3D12DescriptorHeapSlot<ResourceWorkHeap>; 16],
-    pub(crate) sampler_heap: [D3D12DescriptorHeapSlot<SamplerWorkHeap>; 16],
+    pub(crate) texture_heap: [WorkHeapSlot<ResourceWorkHeap>; 16],
+    pub(crate) sampler_heap: [WorkHeapSlot<SamplerWorkHeap>; 16],
     pub source: ShaderSource,
 }
 
@@ -49,8 +54,8 @@ impl BindSemantics<NoUniformBinder, Option<()>, RawD3D12Buffer, RawD3D12Buffer>
     type InputTexture = InputTexture;
     type SamplerSet = SamplerSet;
     type DescriptorSet<'a> = (
-        &'a mut [D3D12DescriptorHeapSlot<ResourceWorkHeap>; 16],
-        &'a mut [D3D12DescriptorHeapSlot<SamplerWorkHeap>; 16],
+        &'a mut [WorkHeapSlot<ResourceWorkHeap>; 16],
+        &'a mut [WorkHeapSlot<SamplerWorkHeap>; 16],
     );
     type DeviceContext = ();
     type UniformOffset = MemberOffset;
@@ -80,6 +85,14 @@ impl FilterPassMeta for FilterPass {
     fn meta(&self) -> &PassMeta {
         &self.meta
     }
+
+    fn source_path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn get_supported_format(&self) -> ImageFormat {
+        self.format
+    }
 }
 
 impl FilterPass {
@@ -113,6 +126,8 @@ impl FilterPass {
                 frametime_delta: options.frametime_delta,
                 framebuffer_size: fb_size,
                 viewport_size,
+                scene_luminance: DEFAULT_SCENE_LUMINANCE,
+                frame_jitter: parent.frame_jitter,
             },
             original,
             source,
@@ -124,7 +139,6 @@ impl FilterPass {
             parent.feedback_textures.iter().map(|o| o.as_ref()),
             parent.history_textures.iter().map(|o| o.as_ref()),
             parent.luts.iter().map(|(u, i)| (*u, i.as_ref())),
-            &self.source.parameters,
             &parent.config,
         );
     }
@@ -147,6 +161,8 @@ impl FilterPass {
         output: &RenderTarget<D3D12OutputView>,
         vbo_type: QuadType,
     ) -> error::Result<()> {
+        parent.statistics.record_pass(&self.reflection);
+
         unsafe {
             cmd.SetPipelineState(self.pipeline.pipeline_state(output.output.format));
         }
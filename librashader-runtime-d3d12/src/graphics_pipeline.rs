@@ -1,5 +1,6 @@
 use crate::draw_quad::DrawQuad;
 use crate::error::assume_d3d12_init;
+use crate::error::FilterChainError;
 use crate::error::FilterChainError::Direct3DOperationError;
 use crate::{error, util};
 use librashader_cache::{cache_pipeline, cache_shader_object};
@@ -7,6 +8,7 @@ use librashader_common::map::FastHashMap;
 use librashader_reflect::back::dxil::DxilObject;
 use librashader_reflect::back::hlsl::CrossHlslContext;
 use librashader_reflect::back::ShaderCompilerOutput;
+use librashader_runtime::pipeline_build::PendingPipelines;
 use std::hash::{Hash, Hasher};
 use std::mem::ManuallyDrop;
 use std::ops::Deref;
@@ -37,7 +39,7 @@ use windows::Win32::Graphics::Dxgi::Common::{DXGI_FORMAT, DXGI_FORMAT_UNKNOWN, D
 
 // bruh why does DXGI_FORMAT not impl hash
 #[repr(transparent)]
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Clone, Copy)]
 struct HashDxgiFormat(DXGI_FORMAT);
 impl Hash for HashDxgiFormat {
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -50,6 +52,7 @@ pub struct D3D12GraphicsPipeline {
     vertex: Vec<u8>,
     fragment: Vec<u8>,
     cache_disabled: bool,
+    pending: PendingPipelines<HashDxgiFormat, ID3D12PipelineState, FilterChainError>,
 }
 
 const D3D12_SLANG_ROOT_PARAMETERS: &[D3D12_ROOT_PARAMETER1; 4] = &[
@@ -319,39 +322,61 @@ impl D3D12GraphicsPipeline {
                 vertex,
                 fragment,
                 cache_disabled: disable_cache,
+                pending: PendingPipelines::default(),
             })
         }
     }
 
+    /// Get a function that builds the pipeline for the given render target format, which
+    /// can run on any thread.
+    fn builder(
+        &self,
+        format: DXGI_FORMAT,
+        root_sig: &D3D12RootSignature,
+        device: &ID3D12Device,
+    ) -> impl FnOnce() -> error::Result<ID3D12PipelineState> + Send + 'static {
+        let vertex = self.vertex.clone();
+        let fragment = self.fragment.clone();
+        let root_sig = D3D12RootSignature {
+            handle: root_sig.handle.clone(),
+        };
+        let device = device.clone();
+        let cache_disabled = self.cache_disabled;
+
+        move || {
+            let (vertex, fragment) = unsafe {
+                let library: IDxcUtils = DxcCreateInstance(&CLSID_DxcLibrary)?;
+                let vertex = library.CreateBlobFromPinned(
+                    vertex.as_ptr().cast(),
+                    vertex.len() as u32,
+                    DXC_CP(0),
+                )?;
+                let fragment = library.CreateBlobFromPinned(
+                    fragment.as_ptr().cast(),
+                    fragment.len() as u32,
+                    DXC_CP(0),
+                )?;
+                (vertex, fragment)
+            };
+
+            Self::make_pipeline_state(
+                &device,
+                &vertex.cast()?,
+                &fragment.cast()?,
+                &root_sig,
+                format,
+                cache_disabled,
+            )
+        }
+    }
+
     pub fn recompile(
         &mut self,
         format: DXGI_FORMAT,
         root_sig: &D3D12RootSignature,
         device: &ID3D12Device,
     ) -> error::Result<()> {
-        let (vertex, fragment) = unsafe {
-            let library: IDxcUtils = DxcCreateInstance(&CLSID_DxcLibrary)?;
-            let vertex = library.CreateBlobFromPinned(
-                self.vertex.as_ptr().cast(),
-                self.vertex.len() as u32,
-                DXC_CP(0),
-            )?;
-            let fragment = library.CreateBlobFromPinned(
-                self.fragment.as_ptr().cast(),
-                self.fragment.len() as u32,
-                DXC_CP(0),
-            )?;
-            (vertex, fragment)
-        };
-
-        let new_pipeline = Self::make_pipeline_state(
-            device,
-            &vertex.cast()?,
-            &fragment.cast()?,
-            root_sig,
-            format,
-            self.cache_disabled,
-        )?;
+        let new_pipeline = self.builder(format, root_sig, device)()?;
 
         self.render_pipelines
             .insert(HashDxgiFormat(format), new_pipeline);
@@ -359,6 +384,27 @@ impl D3D12GraphicsPipeline {
         Ok(())
     }
 
+    /// Start building the pipeline for the given render target format on a worker thread.
+    ///
+    /// The pipeline is swapped in by [`Self::swap_finished`] once it is built.
+    pub fn recompile_async(
+        &mut self,
+        format: DXGI_FORMAT,
+        root_sig: &D3D12RootSignature,
+        device: &ID3D12Device,
+    ) {
+        let builder = self.builder(format, root_sig, device);
+        self.pending.spawn(HashDxgiFormat(format), builder);
+    }
+
+    /// Swap in the pipelines that finished building on a worker thread.
+    pub fn swap_finished(&mut self) -> error::Result<()> {
+        for (format, result) in self.pending.take_finished() {
+            self.render_pipelines.insert(format, result?);
+        }
+        Ok(())
+    }
+
     pub fn has_format(&self, format: DXGI_FORMAT) -> bool {
         self.render_pipelines.contains_key(&HashDxgiFormat(format))
     }
@@ -35,7 +35,10 @@ use windows::Win32::Graphics::Direct3D12::{
     D3D12_TEXTURE_COPY_TYPE_PLACED_FOOTPRINT, D3D12_TEXTURE_COPY_TYPE_SUBRESOURCE_INDEX,
     D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
 };
-use windows::Win32::Graphics::Dxgi::Common::DXGI_SAMPLE_DESC;
+use windows::Win32::Graphics::Dxgi::Common::{
+    DXGI_FORMAT_R8G8B8A8_TYPELESS, DXGI_FORMAT_R8G8B8A8_UNORM, DXGI_FORMAT_R8G8B8A8_UNORM_SRGB,
+    DXGI_SAMPLE_DESC,
+};
 
 pub struct LutTexture {
     allocator_resource: ManuallyDrop<Resource>,
@@ -50,6 +53,7 @@ pub struct LutTexture {
 }
 
 impl LutTexture {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         device: &ID3D12Device,
         allocator: &Arc<Mutex<Allocator>>,
@@ -59,6 +63,7 @@ impl LutTexture {
         filter: FilterMode,
         wrap_mode: WrapMode,
         mipmap: bool,
+        srgb: bool,
         gc: &mut FrameResiduals,
     ) -> error::Result<LutTexture> {
         let miplevels = source.size.calculate_miplevels() as u16;
@@ -90,6 +95,15 @@ impl LutTexture {
         }
 
         desc.Format = d3d12_get_closest_format(device, format_support);
+
+        // sRGB textures are decoded to linear when sampled, so filtering happens in linear space.
+        // The resource is made typeless so mipmaps can still be generated through a UNORM view.
+        let view_format = if srgb && desc.Format == DXGI_FORMAT_R8G8B8A8_UNORM {
+            desc.Format = DXGI_FORMAT_R8G8B8A8_TYPELESS;
+            DXGI_FORMAT_R8G8B8A8_UNORM_SRGB
+        } else {
+            desc.Format
+        };
         let descriptor = heap.allocate_descriptor()?;
 
         // create handles on GPU
@@ -108,7 +122,7 @@ impl LutTexture {
 
         unsafe {
             let srv_desc = D3D12_SHADER_RESOURCE_VIEW_DESC {
-                Format: desc.Format,
+                Format: view_format,
                 ViewDimension: D3D12_SRV_DIMENSION_TEXTURE2D,
                 Shader4ComponentMapping: D3D12_DEFAULT_SHADER_4_COMPONENT_MAPPING,
                 Anonymous: D3D12_SHADER_RESOURCE_VIEW_DESC_0 {
@@ -205,7 +219,7 @@ impl LutTexture {
             &resource,
             descriptor,
             source.size,
-            ImageFormat::R8G8B8A8Unorm.into(),
+            view_format,
             filter,
             wrap_mode,
         );
@@ -1,9 +1,8 @@
-use crate::descriptor_heap::ResourceWorkHeap;
+use crate::descriptor_heap::{ResourceWorkHeap, WorkHeap, WorkHeapSlot};
 use crate::resource::{ObtainResourceHandle, ResourceHandleStrategy};
 use crate::util::dxc_validate_shader;
 use crate::{error, util};
 use bytemuck::{Pod, Zeroable};
-use d3d12_descriptor_heap::{D3D12DescriptorHeap, D3D12DescriptorHeapSlot};
 use librashader_common::Size;
 use librashader_runtime::scaling::MipmapSize;
 use std::mem::ManuallyDrop;
@@ -12,12 +11,11 @@ use windows::Win32::Graphics::Direct3D::Dxc::{
     CLSID_DxcLibrary, CLSID_DxcValidator, DxcCreateInstance,
 };
 use windows::Win32::Graphics::Direct3D12::{
-    ID3D12DescriptorHeap, ID3D12Device, ID3D12GraphicsCommandList, ID3D12PipelineState,
-    ID3D12RootSignature, D3D12_COMPUTE_PIPELINE_STATE_DESC,
-    D3D12_DEFAULT_SHADER_4_COMPONENT_MAPPING, D3D12_RESOURCE_BARRIER, D3D12_RESOURCE_BARRIER_0,
-    D3D12_RESOURCE_BARRIER_TYPE_UAV, D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
-    D3D12_RESOURCE_STATE_UNORDERED_ACCESS, D3D12_RESOURCE_UAV_BARRIER, D3D12_SHADER_BYTECODE,
-    D3D12_SHADER_RESOURCE_VIEW_DESC, D3D12_SHADER_RESOURCE_VIEW_DESC_0,
+    ID3D12Device, ID3D12GraphicsCommandList, ID3D12PipelineState, ID3D12RootSignature,
+    D3D12_COMPUTE_PIPELINE_STATE_DESC, D3D12_DEFAULT_SHADER_4_COMPONENT_MAPPING,
+    D3D12_RESOURCE_BARRIER, D3D12_RESOURCE_BARRIER_0, D3D12_RESOURCE_BARRIER_TYPE_UAV,
+    D3D12_RESOURCE_STATES, D3D12_RESOURCE_STATE_UNORDERED_ACCESS, D3D12_RESOURCE_UAV_BARRIER,
+    D3D12_SHADER_BYTECODE, D3D12_SHADER_RESOURCE_VIEW_DESC, D3D12_SHADER_RESOURCE_VIEW_DESC_0,
     D3D12_SRV_DIMENSION_TEXTURE2D, D3D12_TEX2D_SRV, D3D12_TEX2D_UAV, D3D12_UAV_DIMENSION_TEXTURE2D,
     D3D12_UNORDERED_ACCESS_VIEW_DESC, D3D12_UNORDERED_ACCESS_VIEW_DESC_0,
 };
@@ -42,8 +40,9 @@ struct MipConstants {
 pub struct MipmapGenContext<'a> {
     gen: &'a D3D12MipmapGen,
     cmd: &'a ID3D12GraphicsCommandList,
-    heap: &'a mut D3D12DescriptorHeap<ResourceWorkHeap>,
-    residuals: Vec<D3D12DescriptorHeapSlot<ResourceWorkHeap>>,
+    heap: &'a mut WorkHeap<ResourceWorkHeap>,
+    resource_state: D3D12_RESOURCE_STATES,
+    residuals: Vec<WorkHeapSlot<ResourceWorkHeap>>,
     residual_barriers: Vec<D3D12_RESOURCE_BARRIER>,
 }
 
@@ -51,12 +50,14 @@ impl<'a> MipmapGenContext<'a> {
     fn new(
         gen: &'a D3D12MipmapGen,
         cmd: &'a ID3D12GraphicsCommandList,
-        heap: &'a mut D3D12DescriptorHeap<ResourceWorkHeap>,
+        heap: &'a mut WorkHeap<ResourceWorkHeap>,
+        resource_state: D3D12_RESOURCE_STATES,
     ) -> MipmapGenContext<'a> {
         Self {
             gen,
             cmd,
             heap,
+            resource_state,
             residuals: Vec::new(),
             residual_barriers: Vec::new(),
         }
@@ -72,9 +73,15 @@ impl<'a> MipmapGenContext<'a> {
         format: DXGI_FORMAT,
     ) -> error::Result<()> {
         unsafe {
-            let (residuals_heap, residual_barriers) = self
-                .gen
-                .generate_mipmaps::<S, T>(self.cmd, resource, miplevels, size, format, self.heap)?;
+            let (residuals_heap, residual_barriers) = self.gen.generate_mipmaps::<S, T>(
+                self.cmd,
+                resource,
+                miplevels,
+                size,
+                format,
+                self.heap,
+                self.resource_state,
+            )?;
 
             // heap slots always need to be disposed
             self.residuals.extend(residuals_heap);
@@ -89,7 +96,7 @@ impl<'a> MipmapGenContext<'a> {
     fn close(
         self,
     ) -> (
-        Vec<D3D12DescriptorHeapSlot<ResourceWorkHeap>>,
+        Vec<WorkHeapSlot<ResourceWorkHeap>>,
         Vec<D3D12_RESOURCE_BARRIER>,
     ) {
         (self.residuals, self.residual_barriers)
@@ -150,15 +157,20 @@ impl D3D12MipmapGen {
     ///
     /// The list of returned descriptors must be kept around until the command list has been
     /// submitted.
+    ///
+    /// Resources must be in `resource_state` when mipmaps are generated, and are returned to
+    /// it afterwards. On compute command lists, this must be a state that is valid on compute
+    /// queues, such as `D3D12_RESOURCE_STATE_NON_PIXEL_SHADER_RESOURCE`.
     #[must_use]
     pub fn mipmapping_context<F, E>(
         &self,
         cmd: &ID3D12GraphicsCommandList,
-        work_heap: &mut D3D12DescriptorHeap<ResourceWorkHeap>,
+        work_heap: &mut WorkHeap<ResourceWorkHeap>,
+        resource_state: D3D12_RESOURCE_STATES,
         mut f: F,
     ) -> Result<
         (
-            Vec<D3D12DescriptorHeapSlot<ResourceWorkHeap>>,
+            Vec<WorkHeapSlot<ResourceWorkHeap>>,
             Vec<D3D12_RESOURCE_BARRIER>,
         ),
         E,
@@ -166,7 +178,7 @@ impl D3D12MipmapGen {
     where
         F: FnMut(&mut MipmapGenContext) -> Result<(), E>,
     {
-        let heap: ID3D12DescriptorHeap = (&(*work_heap)).into();
+        let heap = work_heap.handle();
         unsafe {
             cmd.SetPipelineState(&self.pipeline);
 
@@ -176,7 +188,7 @@ impl D3D12MipmapGen {
             }
         }
 
-        let mut context = MipmapGenContext::new(self, cmd, work_heap);
+        let mut context = MipmapGenContext::new(self, cmd, work_heap, resource_state);
         f(&mut context)?;
         Ok(context.close())
     }
@@ -184,6 +196,7 @@ impl D3D12MipmapGen {
     /// SAFETY:
     ///   - handle must be a CPU handle to an SRV
     ///   - work_heap must have enough descriptors to fit all miplevels.
+    ///   - the resource must be in resource_state.
     unsafe fn generate_mipmaps<S: ResourceHandleStrategy<T>, T: ObtainResourceHandle>(
         &self,
         cmd: &ID3D12GraphicsCommandList,
@@ -191,9 +204,10 @@ impl D3D12MipmapGen {
         miplevels: u16,
         size: Size<u32>,
         format: DXGI_FORMAT,
-        work_heap: &mut D3D12DescriptorHeap<ResourceWorkHeap>,
+        work_heap: &mut WorkHeap<ResourceWorkHeap>,
+        resource_state: D3D12_RESOURCE_STATES,
     ) -> error::Result<(
-        Vec<D3D12DescriptorHeapSlot<ResourceWorkHeap>>,
+        Vec<WorkHeapSlot<ResourceWorkHeap>>,
         Vec<D3D12_RESOURCE_BARRIER>,
     )> {
         // create views for mipmap generation
@@ -259,13 +273,13 @@ impl D3D12MipmapGen {
             let barriers = [
                 util::d3d12_get_resource_transition_subresource::<S, _>(
                     resource,
-                    D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
+                    resource_state,
                     D3D12_RESOURCE_STATE_UNORDERED_ACCESS,
                     i - 1,
                 ),
                 util::d3d12_get_resource_transition_subresource::<S, _>(
                     resource,
-                    D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
+                    resource_state,
                     D3D12_RESOURCE_STATE_UNORDERED_ACCESS,
                     i,
                 ),
@@ -304,13 +318,13 @@ impl D3D12MipmapGen {
                 util::d3d12_get_resource_transition_subresource::<S, _>(
                     resource,
                     D3D12_RESOURCE_STATE_UNORDERED_ACCESS,
-                    D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
+                    resource_state,
                     i,
                 ),
                 util::d3d12_get_resource_transition_subresource::<S, _>(
                     resource,
                     D3D12_RESOURCE_STATE_UNORDERED_ACCESS,
-                    D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE,
+                    resource_state,
                     i - 1,
                 ),
             ];
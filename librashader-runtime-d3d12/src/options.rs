@@ -1,7 +1,82 @@
 //! Direct3D 12 shader runtime options.
 
 use librashader_runtime::impl_default_frame_options;
-impl_default_frame_options!(FrameOptionsD3D12);
+use librashader_runtime::instance::LibrashaderInstance;
+use librashader_runtime::latency::LatencyMarkerCallback;
+use librashader_runtime::progress::{CancellationToken, ProgressCallback};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use windows::Win32::Graphics::Direct3D12::{ID3D12DescriptorHeap, ID3D12GraphicsCommandList};
+impl_default_frame_options!(FrameOptionsD3D12, {
+    /// A callback to record the mipmap generation of intermediate passes onto compute command
+    /// lists with, instead of recording it inline on the graphics command list of the frame.
+    /// If `None`, mipmaps are recorded inline. Default is `None`.
+    mipmap_handoff: Option<D3D12MipmapHandoffCallback> = None,
+});
+
+/// The command lists to hand the output of a shader pass off to for mipmap generation.
+#[derive(Debug, Clone)]
+pub struct D3D12MipmapHandoff {
+    /// A command list of type `D3D12_COMMAND_LIST_TYPE_COMPUTE` to record the mipmap generation
+    /// for the output of the shader pass to. The command list must be ready for recording.
+    pub compute: ID3D12GraphicsCommandList,
+    /// A graphics command list to record the rest of the frame to. The command list must be
+    /// ready for recording, and the filter chain binds the state it needs on it.
+    pub graphics: ID3D12GraphicsCommandList,
+}
+
+/// A callback that is called with the index of every intermediate shader pass whose output
+/// needs mipmaps, to generate the mipmaps on a compute queue so that frontends can overlap
+/// mipmap generation with other graphics work.
+///
+/// If the callback returns `None`, the mipmaps of the pass are recorded inline. Otherwise, the
+/// output is transitioned to `D3D12_RESOURCE_STATE_NON_PIXEL_SHADER_RESOURCE` at the end of the
+/// current graphics command list, its mipmaps are recorded to the compute command list, and it
+/// is transitioned back to `D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE` at the start of the new
+/// graphics command list, which the rest of the frame is recorded to.
+///
+/// If the filter chain was created with descriptor heaps owned by the caller, both heaps must
+/// also be bound to the returned command lists before they are returned.
+///
+/// The filter chain does not close or submit any command list. The compute command list must
+/// be executed after the current graphics command list has completed, and the new graphics
+/// command list after the compute command list has completed, for example by signalling and
+/// waiting on fences between the graphics and compute queues.
+#[derive(Clone)]
+pub struct D3D12MipmapHandoffCallback(
+    Arc<dyn Fn(usize) -> Option<D3D12MipmapHandoff> + Send + Sync>,
+);
+
+impl D3D12MipmapHandoffCallback {
+    /// Create a mipmap handoff callback from a closure.
+    pub fn new(
+        callback: impl Fn(usize) -> Option<D3D12MipmapHandoff> + Send + Sync + 'static,
+    ) -> Self {
+        D3D12MipmapHandoffCallback(Arc::new(callback))
+    }
+
+    /// Get the command lists to hand the output of the pass at the given index off to.
+    pub fn handoff(&self, pass: usize) -> Option<D3D12MipmapHandoff> {
+        (self.0)(pass)
+    }
+}
+
+impl Debug for D3D12MipmapHandoffCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("D3D12MipmapHandoffCallback")
+    }
+}
+
+/// A range of descriptors on a shader visible descriptor heap owned by the caller.
+#[derive(Debug, Clone)]
+pub struct D3D12DescriptorHeapRange {
+    /// The shader visible descriptor heap.
+    pub heap: ID3D12DescriptorHeap,
+    /// The index of the first descriptor of the range in the heap.
+    pub offset: u32,
+    /// The number of descriptors in the range.
+    pub count: u32,
+}
 
 /// Options for Direct3D 12 filter chain creation.
 #[repr(C)]
@@ -18,4 +93,70 @@ pub struct FilterChainOptionsD3D12 {
     /// Disable the shader object cache. Shaders will be
     /// recompiled rather than loaded from the cache.
     pub disable_cache: bool,
+
+    /// The maximum number of history frames to keep. If zero, the number of history frames
+    /// is not capped. Shader passes that sample older frames receive the oldest kept frame.
+    pub max_history: u32,
+
+    /// The policy to follow when the framebuffer format of a shader pass is not supported.
+    /// 0 = fall back to a wider format, 1 = fall back to the smallest supported format,
+    /// 2 = fail filter chain creation. Default is 0.
+    pub format_fallback: u32,
+    /// Disable history and feedback, for latency-sensitive modes such as runahead or netplay,
+    /// where frames are invalidated frequently. No history frames are kept, and shader passes
+    /// that sample history or feedback textures receive black textures instead.
+    pub disable_temporal: bool,
+    /// Freeze the `FrameCount` uniform while the `frame_direction` frame option is negative,
+    /// as RetroArch does while rewinding. Frontends that keep counting frames while rewinding
+    /// should enable this, so that shaders animated by the frame count do not jump.
+    pub freeze_frame_count_on_rewind: bool,
+    /// Build pipelines for new output formats on a worker thread instead of stalling the
+    /// frame that needs them, to keep frame pacing smooth when the output format changes.
+    /// Until the pipeline is built, the final pass is not drawn to outputs of that format,
+    /// which are left unchanged. The pipeline is swapped in at the start of a later frame.
+    pub async_pipeline_rebuild: bool,
+    /// Record CPU-side statistics of the commands recorded for every frame, such as the number
+    /// of draw calls, which can be read with `frame_statistics` after the frame is drawn.
+    /// This is meant for performance tuning and regression tracking, and has a small cost.
+    pub collect_statistics: bool,
+    /// A callback that is called as every shader pass of the preset is preprocessed, compiled,
+    /// and has its pipeline created, so that frontends can show the progress of loading
+    /// large presets.
+    pub progress: Option<ProgressCallback>,
+    /// A token to cancel filter chain creation with from another thread. If cancelled,
+    /// creation stops between shader passes and fails with
+    /// [`FilterChainError::Cancelled`](crate::error::FilterChainError::Cancelled).
+    pub cancel: Option<CancellationToken>,
+    /// Append a built-in pass after the passes of the preset that simulates or corrects for
+    /// colour vision deficiencies. The pass is controlled at runtime with the
+    /// `LIBRA_COLOR_ASSIST_MODE` (0 = off, 1 = protanopia, 2 = deuteranopia, 3 = tritanopia),
+    /// `LIBRA_COLOR_ASSIST_SIMULATE` and `LIBRA_COLOR_ASSIST_STRENGTH` parameters.
+    pub color_assist: bool,
+    /// Append a built-in pass after the passes of the preset that inserts black frames between
+    /// the subframes of every frame, for motion clarity on high refresh rate displays.
+    /// The pass follows the `current_subframe` and `total_subframes` frame options, and is
+    /// controlled at runtime with the `LIBRA_BFI_ENABLE`, `LIBRA_BFI_LIT_SUBFRAMES`,
+    /// `LIBRA_BFI_DARK_LEVEL` and `LIBRA_BFI_COMPENSATION` parameters.
+    pub black_frame_insertion: bool,
+    /// A callback that is called before and after the commands of every frame are recorded,
+    /// to forward to latency SDKs such as NVIDIA Reflex or AMD Anti-Lag.
+    pub latency_markers: Option<LatencyMarkerCallback>,
+    /// The librashader instance to create the filter chain from. Shaders are compiled on the
+    /// thread pool of the instance, with the cache directory and log callback of the instance.
+    /// If `None`, the global thread pool, default cache directory and global logger are used.
+    pub instance: Option<LibrashaderInstance>,
+    /// A range of a shader visible `D3D12_DESCRIPTOR_HEAP_TYPE_CBV_SRV_UAV` heap to sub-allocate
+    /// the shader resource views bound to shader passes from, instead of creating a heap.
+    /// The range must hold 16 descriptors for every shader pass, including the 4 built-in
+    /// identity passes which are reserved even if they are never used, and any appended
+    /// built-in passes, and 4096 descriptors for mipmap generation. The descriptors in the range must not be used by the caller while the
+    /// filter chain is alive.
+    ///
+    /// If both `resource_heap` and `sampler_heap` are set, frames do not switch descriptor heaps,
+    /// and both heaps must be bound to the command list before a frame is recorded.
+    pub resource_heap: Option<D3D12DescriptorHeapRange>,
+    /// A range of a shader visible `D3D12_DESCRIPTOR_HEAP_TYPE_SAMPLER` heap to sub-allocate
+    /// the samplers bound to shader passes from, instead of creating a heap.
+    /// The range must hold 16 descriptors for every shader pass, counted as for `resource_heap`.
+    pub sampler_heap: Option<D3D12DescriptorHeapRange>,
 }
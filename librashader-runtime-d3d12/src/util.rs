@@ -1,4 +1,5 @@
 use crate::error;
+use librashader_common::{FormatFallback, ImageFormat};
 use std::mem::ManuallyDrop;
 use widestring::{u16cstr, U16CStr};
 use windows::core::{Interface, PCWSTR};
@@ -10,10 +11,11 @@ use windows::Win32::Graphics::Direct3D::Dxc::{
 use crate::resource::ResourceHandleStrategy;
 use windows::Win32::Graphics::Direct3D12::{
     ID3D12Device, ID3D12GraphicsCommandList, D3D12_FEATURE_DATA_FORMAT_SUPPORT,
-    D3D12_FEATURE_FORMAT_SUPPORT, D3D12_RESOURCE_BARRIER, D3D12_RESOURCE_BARRIER_0,
-    D3D12_RESOURCE_BARRIER_ALL_SUBRESOURCES, D3D12_RESOURCE_BARRIER_FLAG_NONE,
-    D3D12_RESOURCE_BARRIER_TYPE_TRANSITION, D3D12_RESOURCE_STATES,
-    D3D12_RESOURCE_TRANSITION_BARRIER,
+    D3D12_FEATURE_FORMAT_SUPPORT, D3D12_FORMAT_SUPPORT1_RENDER_TARGET,
+    D3D12_FORMAT_SUPPORT1_SHADER_SAMPLE, D3D12_FORMAT_SUPPORT1_TEXTURE2D, D3D12_RESOURCE_BARRIER,
+    D3D12_RESOURCE_BARRIER_0, D3D12_RESOURCE_BARRIER_ALL_SUBRESOURCES,
+    D3D12_RESOURCE_BARRIER_FLAG_NONE, D3D12_RESOURCE_BARRIER_TYPE_TRANSITION,
+    D3D12_RESOURCE_STATES, D3D12_RESOURCE_TRANSITION_BARRIER,
 };
 use windows::Win32::Graphics::Dxgi::Common::*;
 
@@ -124,6 +126,33 @@ pub fn d3d12_get_closest_format(
     DXGI_FORMAT_UNKNOWN
 }
 
+/// Select the first fallback of the format under the fallback policy that the device can
+/// render to and sample from.
+pub fn d3d12_select_supported_format(
+    device: &ID3D12Device,
+    format: ImageFormat,
+    policy: FormatFallback,
+) -> Option<ImageFormat> {
+    let format_support_mask = D3D12_FORMAT_SUPPORT1_TEXTURE2D
+        | D3D12_FORMAT_SUPPORT1_SHADER_SAMPLE
+        | D3D12_FORMAT_SUPPORT1_RENDER_TARGET;
+
+    format.select_supported(policy, |format| unsafe {
+        let mut support = D3D12_FEATURE_DATA_FORMAT_SUPPORT {
+            Format: format.into(),
+            ..Default::default()
+        };
+        device
+            .CheckFeatureSupport(
+                D3D12_FEATURE_FORMAT_SUPPORT,
+                &mut support as *mut _ as *mut _,
+                std::mem::size_of::<D3D12_FEATURE_DATA_FORMAT_SUPPORT>() as u32,
+            )
+            .is_ok()
+            && (support.Support1 & format_support_mask) == format_support_mask
+    })
+}
+
 pub fn dxc_compile_shader(
     library: &IDxcUtils,
     compiler: &IDxcCompiler,
@@ -0,0 +1,45 @@
+use crate::error;
+use crate::luts::LutBundleD3D9;
+use crate::samplers::SamplerSet;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Device-level resources that can be shared between filter chains created on the same device
+/// with the `device_context` option.
+///
+/// A device context owns the sampler states of the filter chains, and a [`LutBundleD3D9`] for
+/// their LUT textures, so that frontends that run several filter chains at once do not create
+/// duplicate objects for each of them. Clones of a device context share the same resources.
+#[derive(Clone)]
+pub struct DeviceContextD3D9 {
+    pub(crate) samplers: Arc<SamplerSet>,
+    pub(crate) luts: LutBundleD3D9,
+}
+
+impl DeviceContextD3D9 {
+    /// Create a device context.
+    ///
+    /// Direct3D 9 sampler states are set on the device when drawing, so a device context is not
+    /// tied to a device until LUT textures are loaded into its bundle.
+    pub fn new() -> error::Result<Self> {
+        Ok(Self {
+            samplers: Arc::new(SamplerSet::new()?),
+            luts: LutBundleD3D9::new(),
+        })
+    }
+
+    /// The bundle of LUT textures shared by filter chains created with this device context.
+    ///
+    /// Textures can be preloaded into the bundle before any filter chain is created.
+    pub fn lut_bundle(&self) -> &LutBundleD3D9 {
+        &self.luts
+    }
+}
+
+impl Debug for DeviceContextD3D9 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeviceContextD3D9")
+            .field("luts", &self.luts)
+            .finish_non_exhaustive()
+    }
+}
@@ -2,6 +2,7 @@ use crate::error;
 use crate::error::{assume_d3d_init, Result};
 use array_concat::concat_arrays;
 use bytemuck::offset_of;
+use librashader_runtime::mesh::OutputMesh;
 use librashader_runtime::quad::{QuadType, VertexInput};
 
 use windows::Win32::Foundation::FALSE;
@@ -58,6 +59,7 @@ static VBO_DATA: &[VertexInput; 8] = &concat_arrays!(OFFSCREEN_VBO_DATA, FINAL_V
 pub(crate) struct DrawQuad {
     vbo: IDirect3DVertexBuffer9,
     vao: IDirect3DVertexDeclaration9,
+    mesh: Option<(IDirect3DVertexBuffer9, u32)>,
 }
 
 impl DrawQuad {
@@ -86,19 +88,66 @@ impl DrawQuad {
             vbo.Unlock()?;
 
             let vao = device.CreateVertexDeclaration(Self::get_spirv_cross_vbo_desc().as_ptr())?;
-            Ok(DrawQuad { vbo, vao })
+            Ok(DrawQuad {
+                vbo,
+                vao,
+                mesh: None,
+            })
         }
     }
 
+    /// Set the mesh to draw the final pass with, or reset it to the default quad.
+    pub fn set_mesh(
+        &mut self,
+        device: &IDirect3DDevice9,
+        mesh: Option<&OutputMesh>,
+    ) -> error::Result<()> {
+        let Some(mesh) = mesh else {
+            self.mesh = None;
+            return Ok(());
+        };
+
+        // the clip space of Direct3D points up.
+        let vertices = mesh.flipped_vertices(false);
+        let size = std::mem::size_of_val(vertices.as_slice()) as u32;
+        unsafe {
+            let mut vbo = None;
+            device.CreateVertexBuffer(
+                size,
+                0,
+                0,
+                D3DPOOL_DEFAULT,
+                &mut vbo,
+                std::ptr::null_mut(),
+            )?;
+
+            assume_d3d_init!(vbo, "CreateVertexBuffer");
+
+            let mut ptr = std::ptr::null_mut();
+            vbo.Lock(0, size, &mut ptr, 0)?;
+            std::ptr::copy_nonoverlapping(
+                vertices.as_ptr(),
+                ptr.cast::<VertexInput>(),
+                vertices.len(),
+            );
+            vbo.Unlock()?;
+
+            self.mesh = Some((vbo, mesh.vertex_count()));
+        }
+
+        Ok(())
+    }
+
     pub fn draw_quad(
         &self,
         device: &IDirect3DDevice9,
         vbo_type: QuadType,
         mvp: &[f32; 16],
     ) -> Result<()> {
-        let offset = match vbo_type {
-            QuadType::Offscreen => 0,
-            QuadType::Final => 4,
+        let (vbo, offset, vertices) = match (vbo_type, &self.mesh) {
+            (QuadType::Offscreen, _) => (&self.vbo, 0, 4),
+            (QuadType::Final, Some((mesh, count))) => (mesh, 0, *count),
+            (QuadType::Final, None) => (&self.vbo, 4, 4),
         };
 
         unsafe {
@@ -115,11 +164,11 @@ impl DrawQuad {
             device.SetRenderState(D3DRS_COLORWRITEENABLE, 0xfu32)?;
             device.SetRenderState(D3DRS_ALPHABLENDENABLE, FALSE.0 as u32)?;
             device.BeginScene()?;
-            device.SetStreamSource(0, &self.vbo, 0, std::mem::size_of::<VertexInput>() as u32)?;
+            device.SetStreamSource(0, vbo, 0, std::mem::size_of::<VertexInput>() as u32)?;
             // device.SetFVF(D3DFVF_XYZRHW | D3DFVF_TEX1)?;
             device.SetVertexDeclaration(&self.vao)?;
 
-            device.DrawPrimitive(D3DPT_TRIANGLESTRIP, offset, 2)?;
+            device.DrawPrimitive(D3DPT_TRIANGLESTRIP, offset, vertices - 2)?;
             device.EndScene()?;
         }
 
@@ -1,5 +1,6 @@
 //! Direct3D 11 shader runtime errors.
 //!
+use librashader_common::ImageFormat;
 use librashader_preprocess::PreprocessError;
 use librashader_presets::ParsePresetError;
 use librashader_reflect::error::{ShaderCompileError, ShaderReflectError};
@@ -27,6 +28,12 @@ pub enum FilterChainError {
     LutLoadError(#[from] ImageError),
     #[error("invalid hlsl uniform name")]
     UniformNameError(#[from] FromUtf8Error),
+    #[error("the framebuffer format {0:?} is not supported by the device")]
+    UnsupportedFormat(ImageFormat),
+    #[error("filter chain creation was cancelled")]
+    Cancelled,
+    #[error("could not spawn the worker thread to create the filter chain on")]
+    WorkerThread(#[source] std::io::Error),
 }
 
 macro_rules! assume_d3d_init {
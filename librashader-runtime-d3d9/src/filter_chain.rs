@@ -3,14 +3,14 @@ use crate::draw_quad::DrawQuad;
 use crate::error::FilterChainError;
 use crate::filter_pass::FilterPass;
 use crate::graphics_pipeline::D3D9State;
-use crate::luts::LutTexture;
+use crate::luts::{self, LutTexture};
 use crate::options::{FilterChainOptionsD3D9, FrameOptionsD3D9};
 use crate::samplers::SamplerSet;
 use crate::texture::{D3D9InputTexture, D3D9Texture};
-use crate::{error, util};
+use crate::{error, util, DeviceContextD3D9};
 use librashader_cache::{cache_shader_object, CachedCompilation};
 use librashader_common::map::FastHashMap;
-use librashader_common::{ImageFormat, Size, Viewport};
+use librashader_common::{FormatFallback, ImageFormat, ScalingKernel, Size, Viewport};
 use librashader_presets::context::VideoDriver;
 use librashader_presets::{ShaderFeatures, ShaderPreset};
 use librashader_reflect::back::hlsl::HlslShaderModel;
@@ -21,44 +21,71 @@ use librashader_reflect::reflect::cross::SpirvCross;
 use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
 use librashader_reflect::reflect::semantics::ShaderSemantics;
 use librashader_reflect::reflect::ReflectShader;
-use librashader_runtime::binding::{BindingUtil, TextureInput};
-use librashader_runtime::framebuffer::FramebufferInit;
-use librashader_runtime::image::{ImageError, LoadedTexture, UVDirection, BGRA8};
+use librashader_runtime::binding::{BindingUtil, TextureInput, UniformBindings};
+use librashader_runtime::filter_pass::{
+    requested_format, FilterPassMeta, IdentityPasses, PassInfo,
+};
+use librashader_runtime::framebuffer::{FramebufferInit, HistoryDepth};
+use librashader_runtime::instance::LibrashaderInstance;
+use librashader_runtime::jitter;
+use librashader_runtime::mesh::OutputMesh;
+use librashader_runtime::mvp;
+use librashader_runtime::pending::LocalPendingFilterChain;
+use librashader_runtime::progress::{CancellationToken, LoadStage, PassProgress, ProgressCallback};
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
+use librashader_runtime::rewind::RewindFrameCount;
 use librashader_runtime::scaling::ScaleFramebuffer;
+use librashader_runtime::statistics::{FrameStatistics, FrameStatisticsRecorder};
 use librashader_runtime::uniforms::UniformStorage;
+use librashader_runtime::warnings::OptionWarning;
 use std::collections::VecDeque;
 
 use librashader_common::GetSize;
-use rayon::iter::IntoParallelIterator;
-use rayon::iter::ParallelIterator;
 
 use std::path::Path;
+use std::sync::Arc;
 
 use windows::Win32::Graphics::Direct3D9::{IDirect3DDevice9, IDirect3DSurface9, IDirect3DTexture9};
 
 pub(crate) struct FilterCommon {
     pub(crate) d3d9: IDirect3DDevice9,
-    pub(crate) luts: FastHashMap<usize, LutTexture>,
-    pub samplers: SamplerSet,
+    pub(crate) luts: FastHashMap<usize, Arc<LutTexture>>,
+    pub samplers: Arc<SamplerSet>,
     pub output_textures: Box<[Option<D3D9InputTexture>]>,
     pub feedback_textures: Box<[Option<D3D9InputTexture>]>,
     pub history_textures: Box<[Option<D3D9InputTexture>]>,
     pub config: RuntimeParameters,
     pub disable_mipmaps: bool,
     pub(crate) draw_quad: DrawQuad,
+    pub(crate) frame_jitter: [f32; 2],
+    pub statistics: FrameStatisticsRecorder,
+}
+
+/// The options that the shader passes were created with, to create the identity passes with
+/// when they are first used.
+#[derive(Clone, Copy)]
+struct PassOptions {
+    disable_cache: bool,
+    format_fallback: FormatFallback,
 }
 
 /// A Direct3D 9 filter chain.
 pub struct FilterChainD3D9 {
     pub(crate) common: FilterCommon,
     passes: Vec<FilterPass>,
+    identity: IdentityPasses<FilterPass>,
+    pass_options: PassOptions,
     output_framebuffers: Box<[D3D9Texture]>,
     feedback_framebuffers: Box<[D3D9Texture]>,
     history_framebuffers: VecDeque<D3D9Texture>,
+    history_depth: HistoryDepth,
+    option_warnings: Box<[OptionWarning]>,
     default_options: FrameOptionsD3D9,
     draw_last_pass_feedback: bool,
+    disable_temporal: bool,
+    rewind: RewindFrameCount,
+    invalidated_history: usize,
 }
 
 mod compile {
@@ -97,19 +124,98 @@ mod compile {
 }
 
 use compile::{compile_passes, ShaderPassMeta};
-use librashader_pack::{ShaderPresetPack, TextureResource};
+use librashader_pack::{PassResource, ShaderPresetPack};
 use librashader_runtime::parameters::RuntimeParameters;
 
+/// A shader preset with its passes compiled, to create a filter chain from on the thread that
+/// uses the device.
+struct CompiledPreset {
+    preset: ShaderPresetPack,
+    passes: Vec<ShaderPassMeta>,
+    semantics: ShaderSemantics,
+}
+
+impl CompiledPreset {
+    /// Append the requested built-in passes to the preset, and compile its passes.
+    ///
+    /// This does not use the device, so it can be done on any thread.
+    fn compile(
+        mut preset: ShaderPresetPack,
+        disable_cache: bool,
+        color_assist: bool,
+        black_frame_insertion: bool,
+        progress: Option<&ProgressCallback>,
+        cancel: Option<&CancellationToken>,
+    ) -> error::Result<Self> {
+        if color_assist {
+            preset.append_color_assist();
+        }
+        if black_frame_insertion {
+            preset.append_black_frame_insertion();
+        }
+
+        let progress = PassProgress::new(progress, preset.passes.len()).with_cancellation(cancel);
+
+        let shaders = std::mem::take(&mut preset.passes);
+        let (passes, semantics) = compile_passes(shaders, &preset.textures, disable_cache)?;
+        progress.report_all(LoadStage::Preprocessing);
+        if progress.is_cancelled() {
+            return Err(FilterChainError::Cancelled);
+        }
+
+        Ok(CompiledPreset {
+            preset,
+            passes,
+            semantics,
+        })
+    }
+}
+
 impl FilterChainD3D9 {
+    /// Create the identity pass that scales its input to the viewport with the given kernel.
+    fn init_identity(
+        device: &IDirect3DDevice9,
+        parameters: &RuntimeParameters,
+        options: PassOptions,
+        kernel: ScalingKernel,
+    ) -> error::Result<FilterPass> {
+        let (passes, semantics) = compile_passes(
+            vec![PassResource::identity(kernel)],
+            &[],
+            options.disable_cache,
+        )?;
+
+        let pass = Self::init_passes(
+            device,
+            passes,
+            &semantics,
+            parameters,
+            options.disable_cache,
+            options.format_fallback,
+            PassProgress::default(),
+        )?
+        .remove(0);
+
+        Ok(pass)
+    }
+
     fn init_passes(
         device: &IDirect3DDevice9,
         passes: Vec<ShaderPassMeta>,
         semantics: &ShaderSemantics,
+        parameters: &RuntimeParameters,
         disable_cache: bool,
+        format_fallback: FormatFallback,
+        progress: PassProgress,
     ) -> error::Result<Vec<FilterPass>> {
         let builder_fn = |(index, (config, mut reflect)): (usize, ShaderPassMeta)| {
+            if progress.is_cancelled() {
+                return Err(FilterChainError::Cancelled);
+            }
+
             let mut reflection = reflect.reflect(index, semantics)?;
             let hlsl = reflect.compile(Some(HlslShaderModel::ShaderModel3_0))?;
+            progress.report(LoadStage::Compiling, index);
 
             // eprintln!("===vs===\n{}", hlsl.vertex);
 
@@ -140,6 +246,7 @@ impl FilterChainD3D9 {
                 },
                 disable_cache,
             )?;
+            progress.report(LoadStage::Pipeline, index);
 
             let uniform_storage = UniformStorage::new(
                 reflection.ubo.as_ref().map_or(0, |ubo| ubo.size as usize),
@@ -152,14 +259,17 @@ impl FilterChainD3D9 {
             let mut ps_constants = util::d3d_reflect_shader(ps_blob)?;
             let vs_constants = util::d3d_reflect_shader(vs_blob)?;
 
-            let uniform_bindings = reflection.meta.create_binding_map(|param| {
-                ConstantRegister::reflect_register_assignment(
-                    param,
-                    &ps_constants,
-                    &vs_constants,
-                    &hlsl.context,
-                )
-            });
+            let uniform_bindings = UniformBindings::new(
+                reflection.meta.create_binding_map(|param| {
+                    ConstantRegister::reflect_register_assignment(
+                        param,
+                        &ps_constants,
+                        &vs_constants,
+                        &hlsl.context,
+                    )
+                }),
+                parameters,
+            );
 
             let gl_halfpixel = vs_constants.get("gl_HalfPixel").map(|o| o.assignment);
 
@@ -167,6 +277,13 @@ impl FilterChainD3D9 {
 
             update_sampler_bindings(&mut reflection.meta, &ps_constants);
             // eprintln!("{:?}", ps_constants);
+
+            let format = util::d3d9_select_supported_format(
+                device,
+                requested_format(&config.meta, config.data.format),
+                format_fallback,
+            )?;
+
             Ok(FilterPass {
                 reflection,
                 vertex_shader: vs,
@@ -176,6 +293,8 @@ impl FilterChainD3D9 {
                 gl_halfpixel,
                 source: config.data,
                 meta: config.meta,
+                path: config.path,
+                format,
             })
         };
 
@@ -186,26 +305,19 @@ impl FilterChainD3D9 {
         let filters = filters?;
         Ok(filters)
     }
+}
 
-    fn load_luts(
+impl FilterChainD3D9 {
+    /// Create a filter chain without any shader passes, that only scales the input to the viewport.
+    ///
+    /// This is equivalent to loading a preset without any shader passes.
+    pub unsafe fn new_identity(
         device: &IDirect3DDevice9,
-        textures: Vec<TextureResource>,
-    ) -> error::Result<FastHashMap<usize, LutTexture>> {
-        let mut luts = FastHashMap::default();
-        let images = textures
-            .into_par_iter()
-            .map(|texture| LoadedTexture::from_texture(texture, UVDirection::TopLeft))
-            .collect::<Result<Vec<LoadedTexture<BGRA8>>, ImageError>>()?;
-
-        for (index, LoadedTexture { meta, image }) in images.iter().enumerate() {
-            let texture = LutTexture::new(device, &image, &meta)?;
-            luts.insert(index, texture);
-        }
-        Ok(luts)
+        options: Option<&FilterChainOptionsD3D9>,
+    ) -> error::Result<FilterChainD3D9> {
+        unsafe { Self::load_from_pack(ShaderPresetPack::empty(), device, options) }
     }
-}
 
-impl FilterChainD3D9 {
     /// Load the shader preset at the given path into a filter chain.
     pub unsafe fn load_from_path(
         path: impl AsRef<Path>,
@@ -230,23 +342,127 @@ impl FilterChainD3D9 {
         unsafe { Self::load_from_pack(preset, device, options) }
     }
 
+    /// Load a filter chain from a pre-parsed `ShaderPreset`, compiling its shaders on a worker
+    /// thread.
+    ///
+    /// Shaders are compiled without blocking the calling thread. The rest of the filter chain is
+    /// created on the thread that takes it from the returned [`LocalPendingFilterChain`], which
+    /// must be able to use `device`. The progress callback of the options is called on the worker
+    /// thread while shaders are compiled, and on the taking thread afterwards.
+    ///
+    /// Returns an error if the worker thread could not be spawned.
+    pub unsafe fn load_from_preset_async(
+        preset: ShaderPreset,
+        device: &IDirect3DDevice9,
+        options: Option<FilterChainOptionsD3D9>,
+    ) -> error::Result<LocalPendingFilterChain<error::Result<FilterChainD3D9>>> {
+        let disable_cache = options.as_ref().map_or(false, |o| o.disable_cache);
+        let color_assist = options.as_ref().map_or(false, |o| o.color_assist);
+        let black_frame_insertion = options.as_ref().map_or(false, |o| o.black_frame_insertion);
+        let progress = options.as_ref().and_then(|o| o.progress.clone());
+        let cancel = options.as_ref().and_then(|o| o.cancel.clone());
+        let instance = options.as_ref().and_then(|o| o.instance.clone());
+        let device = device.clone();
+
+        LocalPendingFilterChain::spawn(
+            move || {
+                let _instance = instance.as_ref().map(LibrashaderInstance::enter);
+                let preset = ShaderPresetPack::load_from_preset::<FilterChainError>(preset)?;
+                CompiledPreset::compile(
+                    preset,
+                    disable_cache,
+                    color_assist,
+                    black_frame_insertion,
+                    progress.as_ref(),
+                    cancel.as_ref(),
+                )
+            },
+            move |compiled: error::Result<CompiledPreset>| unsafe {
+                Self::load_from_compiled(compiled?, &device, options.as_ref())
+            },
+        )
+        .map_err(FilterChainError::WorkerThread)
+    }
+
     /// Load a filter chain from a pre-parsed `ShaderPreset`.
     pub unsafe fn load_from_pack(
         preset: ShaderPresetPack,
         device: &IDirect3DDevice9,
         options: Option<&FilterChainOptionsD3D9>,
     ) -> error::Result<FilterChainD3D9> {
+        let _instance = options
+            .and_then(|o| o.instance.as_ref())
+            .map(LibrashaderInstance::enter);
+        let compiled = CompiledPreset::compile(
+            preset,
+            options.map_or(false, |o| o.disable_cache),
+            options.map_or(false, |o| o.color_assist),
+            options.map_or(false, |o| o.black_frame_insertion),
+            options.and_then(|o| o.progress.as_ref()),
+            options.and_then(|o| o.cancel.as_ref()),
+        )?;
+        unsafe { Self::load_from_compiled(compiled, device, options) }
+    }
+
+    /// Load a filter chain from a compiled `ShaderPresetPack`.
+    unsafe fn load_from_compiled(
+        compiled: CompiledPreset,
+        device: &IDirect3DDevice9,
+        options: Option<&FilterChainOptionsD3D9>,
+    ) -> error::Result<FilterChainD3D9> {
+        let CompiledPreset {
+            preset,
+            passes,
+            semantics,
+        } = compiled;
         let disable_cache = options.map_or(false, |o| o.disable_cache);
+        let _instance = options
+            .and_then(|o| o.instance.as_ref())
+            .map(LibrashaderInstance::enter);
+
+        let progress = PassProgress::new(options.and_then(|o| o.progress.as_ref()), passes.len())
+            .with_cancellation(options.and_then(|o| o.cancel.as_ref()));
 
-        let (passes, semantics) = compile_passes(preset.passes, &preset.textures, disable_cache)?;
+        let device_context = options.and_then(|o| o.device_context.as_ref());
+        let samplers = match device_context {
+            Some(context) => Arc::clone(&context.samplers),
+            None => Arc::new(SamplerSet::new()?),
+        };
+
+        let parameters = RuntimeParameters::new(
+            preset.pass_count as usize,
+            preset.parameters,
+            passes
+                .iter()
+                .flat_map(|(pass, _)| pass.data.parameters.values()),
+        );
 
-        let samplers = SamplerSet::new()?;
+        let pass_options = PassOptions {
+            disable_cache,
+            format_fallback: options.map_or(FormatFallback::default(), |o| {
+                FormatFallback::from(o.format_fallback)
+            }),
+        };
 
         // initialize passes
-        let filters = FilterChainD3D9::init_passes(device, passes, &semantics, disable_cache)?;
+        let filters = FilterChainD3D9::init_passes(
+            device,
+            passes,
+            &semantics,
+            &parameters,
+            disable_cache,
+            pass_options.format_fallback,
+            progress,
+        )?;
 
         // load luts
-        let luts = FilterChainD3D9::load_luts(device, preset.textures)?;
+        let luts = luts::load_luts(
+            device,
+            preset.textures,
+            options
+                .and_then(|o| o.lut_bundle.as_ref())
+                .or(device_context.map(DeviceContextD3D9::lut_bundle)),
+        )?;
 
         let framebuffer_gen =
             || D3D9Texture::new(device, Size::new(1, 1), ImageFormat::R8G8B8A8Unorm, false);
@@ -255,7 +471,15 @@ impl FilterChainD3D9 {
             filters.iter().map(|f| &f.reflection.meta),
             &framebuffer_gen,
             &input_gen,
-        );
+        )
+        .with_max_history(options.map_or(0, |o| o.max_history as usize))
+        .with_temporal_disabled(options.map_or(false, |o| o.disable_temporal));
+        let history_depth = framebuffer_init.history_depth();
+        let mut option_warnings = framebuffer_init.option_warnings();
+        option_warnings.extend(OptionWarning::check_mipmap_input(
+            options.map_or(false, |o| o.force_no_mipmaps),
+            filters.iter().map(|f| f.meta.mipmap_input),
+        ));
 
         // initialize output framebuffers
         let (output_framebuffers, output_textures) = framebuffer_init.init_output_framebuffers()?;
@@ -271,13 +495,25 @@ impl FilterChainD3D9 {
 
         Ok(FilterChainD3D9 {
             draw_last_pass_feedback: framebuffer_init.uses_final_pass_as_feedback(),
+            disable_temporal: options.map_or(false, |o| o.disable_temporal),
+            rewind: RewindFrameCount::new(
+                options.map_or(false, |o| o.freeze_frame_count_on_rewind),
+            ),
+            invalidated_history: 0,
             passes: filters,
+            identity: IdentityPasses::default(),
+            pass_options,
             output_framebuffers,
             feedback_framebuffers,
             history_framebuffers,
+            history_depth,
+            option_warnings: option_warnings.into_boxed_slice(),
             common: FilterCommon {
+                statistics: FrameStatisticsRecorder::new(
+                    options.map_or(false, |o| o.collect_statistics),
+                ),
                 d3d9: device.clone(),
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                config: parameters,
                 disable_mipmaps: options.map_or(false, |o| o.force_no_mipmaps),
                 luts,
                 samplers,
@@ -285,6 +521,7 @@ impl FilterChainD3D9 {
                 feedback_textures,
                 history_textures,
                 draw_quad,
+                frame_jitter: [0.0, 0.0],
             },
             default_options: Default::default(),
         })
@@ -299,6 +536,44 @@ impl FilterChainD3D9 {
         Ok(())
     }
 
+    /// Get the depth of the history kept by the filter chain, and the depth
+    /// requested by the shader passes.
+    pub fn history_depth(&self) -> HistoryDepth {
+        self.history_depth
+    }
+
+    /// Get the warnings about options of the filter chain that could not be honoured as
+    /// given, such as mipmaps being forced off for shader passes that request them.
+    pub fn option_warnings(&self) -> &[OptionWarning] {
+        &self.option_warnings
+    }
+
+    /// Invalidate the last `frames` frames of history, such as after a rollback in runahead
+    /// or netplay, so that shader passes do not blend frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+    /// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+    pub fn invalidate_history(&mut self, frames: usize) {
+        self.invalidated_history = std::cmp::max(self.invalidated_history, frames);
+    }
+
+    /// Get information about the shader pass at the given index, including the
+    /// framebuffer format chosen for the pass.
+    pub fn pass_info(&self, index: usize) -> Option<PassInfo> {
+        self.passes.get(index).map(FilterPassMeta::pass_info)
+    }
+
+    /// Get the statistics of the commands recorded for the last frame, or `None` if the
+    /// filter chain was not created with the `collect_statistics` option.
+    pub fn frame_statistics(&self) -> Option<FrameStatistics> {
+        self.common.statistics.statistics()
+    }
+
+    /// Set the mesh to draw the final pass with, or reset it to the default quad if `None`.
+    pub fn set_output_mesh(&mut self, mesh: Option<&OutputMesh>) -> error::Result<()> {
+        self.common.draw_quad.set_mesh(&self.common.d3d9, mesh)
+    }
+
     /// Process a frame with the input image.
     ///
     /// ## Safety:
@@ -310,9 +585,30 @@ impl FilterChainD3D9 {
         frame_count: usize,
         options: Option<&FrameOptionsD3D9>,
     ) -> error::Result<()> {
+        unsafe { self.frame_broadcast(input, std::slice::from_ref(viewport), frame_count, options) }
+    }
+
+    /// Process a frame with the input image, drawing the final pass to every viewport.
+    ///
+    /// Intermediate passes are scaled relative to the first viewport and are only run once.
+    /// If `viewports` is empty, nothing is drawn.
+    ///
+    /// ## Safety:
+    ///   * `input` must be in `D3DPOOL_DEFAULT`.
+    pub unsafe fn frame_broadcast(
+        &mut self,
+        input: &IDirect3DTexture9,
+        viewports: &[Viewport<&IDirect3DSurface9>],
+        frame_count: usize,
+        options: Option<&FrameOptionsD3D9>,
+    ) -> error::Result<()> {
+        // passes are scaled relative to the first viewport.
+        let Some(viewport) = viewports.first() else {
+            return Ok(());
+        };
+
         let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
 
-        let passes = &mut self.passes[0..max];
         if let Some(options) = options {
             if options.clear_history {
                 for framebuffer in &mut self.history_framebuffers {
@@ -321,11 +617,57 @@ impl FilterChainD3D9 {
             }
         }
 
-        if passes.is_empty() {
-            return Ok(());
+        // with history and feedback disabled, the history is kept black.
+        if self.disable_temporal {
+            for framebuffer in &mut self.history_framebuffers {
+                framebuffer.clear(&self.common.d3d9)?;
+            }
+        }
+
+        // clear the history frames invalidated since the last frame.
+        let invalidated_history = std::mem::take(&mut self.invalidated_history);
+        for framebuffer in self
+            .history_framebuffers
+            .iter_mut()
+            .take(invalidated_history)
+        {
+            framebuffer.clear(&self.common.d3d9)?;
         }
 
         let options = options.unwrap_or(&self.default_options);
+        self.common.statistics.reset();
+        let frame_count = self
+            .rewind
+            .frame_count(frame_count, options.frame_direction);
+        self.common.frame_jitter = jitter::resolve(
+            options.jitter_sequence.into(),
+            options.jitter_offset,
+            frame_count,
+        );
+
+        // the identity pass draws to the viewport instead of the final pass if there are no
+        // enabled passes, or if the output of the final pass is scaled to the viewport.
+        let identity = if max == 0 || options.scale_final_pass {
+            Some(
+                self.identity
+                    .get_or_try_init(options.identity_scaling, |kernel| {
+                        Self::init_identity(
+                            &self.common.d3d9,
+                            &self.common.config,
+                            self.pass_options,
+                            kernel,
+                        )
+                    })?,
+            )
+        } else {
+            None
+        };
+
+        let scale_final_pass = max > 0 && identity.is_some();
+        let (passes, final_scaling) = match identity {
+            Some(identity) if max == 0 => (std::slice::from_mut(identity), None),
+            identity => (&mut self.passes[0..max], identity),
+        };
         let filter = passes[0].meta.filter;
         let wrap_mode = passes[0].meta.wrap_mode;
 
@@ -359,6 +701,14 @@ impl FilterChainD3D9 {
             None,
         )?;
 
+        // feedback is kept black, including after the feedback framebuffers are resized.
+        // the feedback of the previous frame is also cleared if it was invalidated.
+        if self.disable_temporal || invalidated_history > 0 {
+            for framebuffer in &mut self.feedback_framebuffers {
+                framebuffer.clear(&self.common.d3d9)?;
+            }
+        }
+
         // Refresh inputs for feedback textures.
         // Don't need to do this for outputs because they are yet to be bound.
         for ((texture, fbo), pass) in self
@@ -372,7 +722,10 @@ impl FilterChainD3D9 {
         }
 
         let passes_len = passes.len();
-        let (pass, last) = passes.split_at_mut(passes_len - 1);
+        let (pass, last) = match final_scaling {
+            Some(identity) => (passes, std::slice::from_mut(identity)),
+            None => passes.split_at_mut(passes_len - 1),
+        };
         let state_guard = D3D9State::new(&self.common.d3d9)?;
 
         for (index, pass) in pass.iter_mut().enumerate() {
@@ -412,9 +765,12 @@ impl FilterChainD3D9 {
             source.wrap = pass.meta.wrap_mode;
             source.is_srgb = pass.meta.srgb_framebuffer;
 
-            if self.draw_last_pass_feedback {
+            // the output of the final pass is already drawn if it is scaled to the viewport.
+            if self.draw_last_pass_feedback && !scale_final_pass {
                 let feedback_target = &self.output_framebuffers[index];
                 let feedback_target_rtv = feedback_target.as_output()?;
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
 
                 pass.draw(
                     &self.common.d3d9,
@@ -425,33 +781,41 @@ impl FilterChainD3D9 {
                     viewport,
                     &original,
                     &source,
-                    RenderTarget::viewport_with_output(&feedback_target_rtv, viewport),
+                    RenderTarget::viewport_with_output(&feedback_target_rtv, viewport, &final_mvp),
                     QuadType::Final,
                 )?;
             }
 
-            pass.draw(
-                &self.common.d3d9,
-                index,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                RenderTarget::viewport(viewport),
-                QuadType::Final,
-            )?;
+            for viewport in viewports {
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
+                pass.draw(
+                    &self.common.d3d9,
+                    index,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    RenderTarget::viewport(viewport, &final_mvp),
+                    QuadType::Final,
+                )?;
+            }
         }
 
-        std::mem::swap(
-            &mut self.output_framebuffers,
-            &mut self.feedback_framebuffers,
-        );
+        if !self.disable_temporal {
+            std::mem::swap(
+                &mut self.output_framebuffers,
+                &mut self.feedback_framebuffers,
+            );
+        }
 
         drop(state_guard);
 
-        self.push_history(&input)?;
+        if !self.disable_temporal {
+            self.push_history(&input)?;
+        }
 
         Ok(())
     }
@@ -4,19 +4,21 @@ use crate::filter_chain::FilterCommon;
 use crate::options::FrameOptionsD3D9;
 use crate::samplers::SamplerSet;
 use crate::texture::D3D9InputTexture;
-use librashader_common::map::FastHashMap;
 use librashader_common::GetSize;
 use librashader_common::{ImageFormat, Size, Viewport};
 use librashader_preprocess::ShaderSource;
 use librashader_presets::PassMeta;
-use librashader_reflect::reflect::semantics::{TextureBinding, UniformBinding};
+use librashader_reflect::reflect::semantics::TextureBinding;
 use librashader_reflect::reflect::ShaderReflection;
-use librashader_runtime::binding::{BindSemantics, UniformInputs};
+use librashader_runtime::binding::{
+    BindSemantics, UniformBindings, UniformInputs, DEFAULT_SCENE_LUMINANCE,
+};
 use librashader_runtime::filter_pass::FilterPassMeta;
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
 use windows::Win32::Foundation::{FALSE, TRUE};
 
+use std::path::{Path, PathBuf};
 use windows::Win32::Graphics::Direct3D9::{
     IDirect3DDevice9, IDirect3DPixelShader9, IDirect3DSurface9, IDirect3DVertexShader9,
     D3DCLEAR_TARGET, D3DRS_SRGBWRITEENABLE, D3DSAMP_SRGBTEXTURE, D3DVIEWPORT9,
@@ -26,9 +28,13 @@ pub struct FilterPass {
     pub reflection: ShaderReflection,
     pub vertex_shader: IDirect3DVertexShader9,
     pub pixel_shader: IDirect3DPixelShader9,
-    pub uniform_bindings: FastHashMap<UniformBinding, ConstantRegister>,
+    pub uniform_bindings: UniformBindings<ConstantRegister>,
     pub source: ShaderSource,
     pub meta: PassMeta,
+    pub path: Option<PathBuf>,
+    /// The framebuffer format of the pass, after falling back to a format supported
+    /// by the device.
+    pub format: ImageFormat,
     pub uniform_storage: D3D9UniformStorage,
     pub gl_halfpixel: Option<RegisterAssignment>,
 }
@@ -41,6 +47,14 @@ impl FilterPassMeta for FilterPass {
     fn meta(&self) -> &PassMeta {
         &self.meta
     }
+
+    fn source_path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn get_supported_format(&self) -> ImageFormat {
+        self.format
+    }
 }
 
 impl BindSemantics<D3D9UniformBinder, ConstantRegister> for FilterPass {
@@ -124,6 +138,8 @@ impl FilterPass {
                 frametime_delta: options.frametime_delta,
                 framebuffer_size: fb_size,
                 viewport_size,
+                scene_luminance: DEFAULT_SCENE_LUMINANCE,
+                frame_jitter: parent.frame_jitter,
             },
             original,
             source,
@@ -135,7 +151,6 @@ impl FilterPass {
             parent.feedback_textures.iter().map(|o| o.as_ref()),
             parent.history_textures.iter().map(|o| o.as_ref()),
             parent.luts.iter().map(|(u, i)| (*u, i.as_ref())),
-            &self.source.parameters,
             &parent.config,
         );
     }
@@ -153,6 +168,8 @@ impl FilterPass {
         output: RenderTarget<IDirect3DSurface9>,
         vbo_type: QuadType,
     ) -> error::Result<()> {
+        parent.statistics.record_pass(&self.reflection);
+
         if self.meta.mipmap_input && !parent.disable_mipmaps {
             unsafe {
                 source.handle.GenerateMipSubLevels();
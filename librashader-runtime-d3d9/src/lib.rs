@@ -3,6 +3,7 @@
 
 mod binding;
 mod d3dx;
+mod device_context;
 mod draw_quad;
 pub mod error;
 mod filter_chain;
@@ -17,4 +18,6 @@ mod util;
 use librashader_runtime::impl_filter_chain_parameters;
 impl_filter_chain_parameters!(FilterChainD3D9);
 
+pub use crate::device_context::DeviceContextD3D9;
 pub use crate::filter_chain::FilterChainD3D9;
+pub use crate::luts::LutBundleD3D9;
@@ -2,8 +2,13 @@ use crate::error;
 use crate::error::assume_d3d_init;
 use crate::texture::D3D9InputTexture;
 
+use librashader_common::map::FastHashMap;
+use librashader_pack::TextureResource;
 use librashader_presets::TextureMeta;
-use librashader_runtime::image::{Image, BGRA8};
+use librashader_runtime::image::{Image, ImageError, LoadedTexture, UVDirection, BGRA8};
+use librashader_runtime::luts::LutBundle;
+use rayon::prelude::*;
+use std::sync::Arc;
 
 use windows::Win32::Graphics::Direct3D9::{
     IDirect3DDevice9, D3DFMT_A8R8G8B8, D3DLOCKED_RECT, D3DPOOL_MANAGED,
@@ -59,7 +64,71 @@ impl LutTexture {
             filter: config.filter_mode,
             wrap: config.wrap_mode,
             mipmode: config.filter_mode,
-            is_srgb: false,
+            is_srgb: config.srgb,
         }))
     }
 }
+
+/// A set of LUT textures uploaded to a Direct3D 9 device, that can be shared between filter
+/// chains created on the same device with the `lut_bundle` option.
+///
+/// Frontends that switch between presets sharing texture packs can keep a bundle around, so
+/// that textures are only uploaded once. Clones of a bundle share the same textures.
+#[derive(Clone, Debug, Default)]
+pub struct LutBundleD3D9(LutBundle<LutTexture>);
+
+impl LutBundleD3D9 {
+    /// Create an empty bundle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of textures in the bundle.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the bundle has no textures.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Remove every texture from the bundle.
+    ///
+    /// Textures in use by filter chains are released once those filter chains are dropped.
+    pub fn clear(&self) {
+        self.0.clear()
+    }
+
+    /// Upload LUT textures to the bundle ahead of filter chain creation, such as the textures
+    /// of a `ShaderPresetPack`.
+    ///
+    /// Textures that are already in the bundle are not uploaded again.
+    pub fn preload(
+        &self,
+        textures: Vec<TextureResource>,
+        device: &IDirect3DDevice9,
+    ) -> error::Result<()> {
+        load_luts(device, textures, Some(self))?;
+        Ok(())
+    }
+}
+
+/// Load the LUT textures of a preset, reusing the textures in the bundle if one is given.
+pub(crate) fn load_luts(
+    device: &IDirect3DDevice9,
+    textures: Vec<TextureResource>,
+    bundle: Option<&LutBundleD3D9>,
+) -> error::Result<FastHashMap<usize, Arc<LutTexture>>> {
+    LutBundle::load(
+        bundle.map(|bundle| &bundle.0),
+        textures,
+        |textures| {
+            textures
+                .into_par_iter()
+                .map(|texture| LoadedTexture::from_texture(texture, UVDirection::TopLeft))
+                .collect::<Result<Vec<LoadedTexture<BGRA8>>, ImageError>>()
+        },
+        |LoadedTexture { meta, image }| LutTexture::new(device, &image, &meta),
+    )
+}
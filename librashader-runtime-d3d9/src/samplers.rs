@@ -10,7 +10,7 @@ use windows::Win32::Graphics::Direct3D9::{
 pub struct SamplerSet {
     samplers: FastHashMap<
         (WrapMode, FilterMode, FilterMode),
-        Box<dyn Fn(&IDirect3DDevice9, u32) -> Result<()>>,
+        Box<dyn Fn(&IDirect3DDevice9, u32) -> Result<()> + Send + Sync>,
     >,
 }
 
@@ -43,7 +43,7 @@ impl SamplerSet {
         for wrap_mode in wrap_modes {
             for filter_mode in &[FilterMode::Linear, FilterMode::Nearest] {
                 for mip_filter in &[FilterMode::Linear, FilterMode::Nearest] {
-                    let sampler: Box<dyn Fn(&IDirect3DDevice9, u32) -> Result<()>> =
+                    let sampler: Box<dyn Fn(&IDirect3DDevice9, u32) -> Result<()> + Send + Sync> =
                         Box::new(|device: &IDirect3DDevice9, index| {
                             unsafe {
                                 let wrap_mode = *wrap_mode;
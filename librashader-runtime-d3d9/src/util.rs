@@ -1,120 +1,54 @@
 use crate::error;
-use crate::error::assume_d3d_init;
+use crate::error::{assume_d3d_init, FilterChainError};
 
 use std::mem::MaybeUninit;
 
 use crate::binding::{ConstantDescriptor, RegisterAssignment, RegisterSet};
 use crate::d3dx::{ID3DXConstantTable, D3DXCONSTANT_DESC, D3DXREGISTER_SET};
 use librashader_common::map::FastHashMap;
+use librashader_common::{FormatFallback, ImageFormat};
 use windows::core::PCSTR;
 use windows::Win32::Graphics::Direct3D::Fxc::{D3DCompile, D3DCOMPILE_AVOID_FLOW_CONTROL};
 use windows::Win32::Graphics::Direct3D::ID3DBlob;
+use windows::Win32::Graphics::Direct3D9::{
+    IDirect3DDevice9, D3DFMT_UNKNOWN, D3DFMT_X8R8G8B8, D3DFORMAT, D3DRTYPE_TEXTURE,
+    D3DUSAGE_RENDERTARGET,
+};
 
-// const fn d3d9_format_fallback_list(format: D3DFORMAT) -> Option<&'static [D3DFORMAT]> {
-//     match format {
-//         DXGI_FORMAT_R32G32B32A32_FLOAT => Some(&[
-//             windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_R32G32B32A32_FLOAT,
-//             DXGI_FORMAT_R16G16B16A16_FLOAT,
-//             DXGI_FORMAT_R32G32B32_FLOAT,
-//             DXGI_FORMAT_R11G11B10_FLOAT,
-//             DXGI_FORMAT_UNKNOWN,
-//         ]),
-//         DXGI_FORMAT_R16G16B16A16_FLOAT => Some(&[
-//             windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_R16G16B16A16_FLOAT,
-//             DXGI_FORMAT_R32G32B32A32_FLOAT,
-//             DXGI_FORMAT_R32G32B32_FLOAT,
-//             DXGI_FORMAT_R11G11B10_FLOAT,
-//             DXGI_FORMAT_UNKNOWN,
-//         ]),
-//         DXGI_FORMAT_R8G8B8A8_UNORM => Some(&[
-//             windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_R8G8B8A8_UNORM,
-//             DXGI_FORMAT_B8G8R8A8_UNORM,
-//             DXGI_FORMAT_B8G8R8X8_UNORM,
-//             DXGI_FORMAT_UNKNOWN,
-//         ]),
-//         DXGI_FORMAT_R8G8B8A8_UNORM_SRGB => Some(&[
-//             windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_R8G8B8A8_UNORM_SRGB,
-//             DXGI_FORMAT_R8G8B8A8_UNORM,
-//             DXGI_FORMAT_B8G8R8A8_UNORM,
-//             DXGI_FORMAT_B8G8R8X8_UNORM,
-//             DXGI_FORMAT_UNKNOWN,
-//         ]),
-//         DXGI_FORMAT_B8G8R8A8_UNORM => Some(&[
-//             windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM,
-//             DXGI_FORMAT_R8G8B8A8_UNORM,
-//             DXGI_FORMAT_UNKNOWN,
-//         ]),
-//         DXGI_FORMAT_B8G8R8X8_UNORM => Some(&[
-//             windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8X8_UNORM,
-//             DXGI_FORMAT_B8G8R8A8_UNORM,
-//             DXGI_FORMAT_R8G8B8A8_UNORM,
-//             DXGI_FORMAT_UNKNOWN,
-//         ]),
-//         DXGI_FORMAT_B5G6R5_UNORM => Some(&[
-//             windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B5G6R5_UNORM,
-//             DXGI_FORMAT_B8G8R8X8_UNORM,
-//             DXGI_FORMAT_B8G8R8A8_UNORM,
-//             DXGI_FORMAT_R8G8B8A8_UNORM,
-//             DXGI_FORMAT_UNKNOWN,
-//         ]),
-//         DXGI_FORMAT_EX_A4R4G4B4_UNORM | DXGI_FORMAT_B4G4R4A4_UNORM => Some(&[
-//             windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B4G4R4A4_UNORM,
-//             DXGI_FORMAT_B8G8R8A8_UNORM,
-//             DXGI_FORMAT_R8G8B8A8_UNORM,
-//             DXGI_FORMAT_UNKNOWN,
-//         ]),
-// //         DXGI_FORMAT_A8_UNORM => Some(&[
-// //             windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_A8_UNORM,
-// //             DXGI_FORMAT_R8_UNORM,
-// //             DXGI_FORMAT_R8G8_UNORM,
-// //             DXGI_FORMAT_R8G8B8A8_UNORM,
-// //             DXGI_FORMAT_B8G8R8A8_UNORM,
-// //             DXGI_FORMAT_UNKNOWN,
-// //         ]),
-// //         DXGI_FORMAT_R8_UNORM => Some(&[
-// //             windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_R8_UNORM,
-// //             DXGI_FORMAT_A8_UNORM,
-// //             DXGI_FORMAT_R8G8_UNORM,
-// //             DXGI_FORMAT_R8G8B8A8_UNORM,
-// //             DXGI_FORMAT_B8G8R8A8_UNORM,
-// //             DXGI_FORMAT_UNKNOWN,
-// //         ]),
-// //         _ => None,
-// //     }
-// // }
-//
-// pub fn d3d9_get_closest_format(
-//     device: &IDirect3DDevice9,
-//     format: D3DFORMAT,
-//     restype: D3DRESOURCETYPE,
-//     format_support_mask: i32,
-// ) -> error::Result<D3DFORMAT> {
-//     let d3d9 = unsafe { device.GetDirect3D()? };
-//     let (devtype, ordinal) = unsafe {
-//         let mut params = Default::default();
-//         device.GetCreationParameters(&mut params)?;
-//         (params.DeviceType, params.AdapterOrdinal)
-//     };
-//
-//     let default_list = [format, D3DFMT_UNKNOWN];
-//     let format_support_list = d3d9_format_fallback_list(format).unwrap_or(&default_list);
-//     let format_support_mask = format_support_mask as u32;
-//
-//     for supported in format_support_list {
-//         unsafe {
-//             if let Ok(_) = d3d9.CheckDeviceFormat(
-//                 ordinal,devtype,
-//                 format, format_support_mask,
-//                 restype,
-//                 format)
-//             {
-//                 return Ok(*supported);
-//             }
-//         }
-//     }
-//
-//     Ok(D3DFMT_UNKNOWN)
-// }
+/// Select the first fallback of the format under the fallback policy that the device can
+/// render to and sample from.
+///
+/// Formats that have no Direct3D 9 equivalent are never supported.
+pub fn d3d9_select_supported_format(
+    device: &IDirect3DDevice9,
+    format: ImageFormat,
+    policy: FormatFallback,
+) -> error::Result<ImageFormat> {
+    let d3d9 = unsafe { device.GetDirect3D()? };
+    let (devtype, ordinal) = unsafe {
+        let mut params = Default::default();
+        device.GetCreationParameters(&mut params)?;
+        (params.DeviceType, params.AdapterOrdinal)
+    };
+
+    format
+        .select_supported(policy, |format| {
+            let format = D3DFORMAT::from(format);
+            format != D3DFMT_UNKNOWN
+                && unsafe {
+                    d3d9.CheckDeviceFormat(
+                        ordinal,
+                        devtype,
+                        D3DFMT_X8R8G8B8,
+                        D3DUSAGE_RENDERTARGET as u32,
+                        D3DRTYPE_TEXTURE,
+                        format,
+                    )
+                    .is_ok()
+                }
+        })
+        .ok_or(FilterChainError::UnsupportedFormat(format))
+}
 
 pub fn d3d_compile_shader(source: &[u8], entry: &[u8], version: &[u8]) -> error::Result<ID3DBlob> {
     unsafe {
@@ -263,6 +263,18 @@ pub mod d3d9_hello_triangle {
                     Some(&FilterChainOptionsD3D9 {
                         force_no_mipmaps: false,
                         disable_cache: true,
+                        max_history: 0,
+                        format_fallback: 0,
+                        disable_temporal: false,
+                        freeze_frame_count_on_rewind: false,
+                        collect_statistics: false,
+                        progress: None,
+                        cancel: None,
+                        lut_bundle: None,
+                        device_context: None,
+                        color_assist: false,
+                        black_frame_insertion: false,
+                        instance: None,
                     }),
                 )
                 .unwrap()
@@ -0,0 +1,45 @@
+use crate::error::Result;
+use crate::luts::LutBundleGL;
+use crate::samplers::SamplerSet;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Context-level resources that can be shared between filter chains created on the same
+/// OpenGL context with the `device_context` option.
+///
+/// A device context owns the sampler objects of the filter chains, and a [`LutBundleGL`] for
+/// their LUT textures, so that frontends that run several filter chains at once do not create
+/// duplicate objects for each of them. Clones of a device context share the same resources.
+#[derive(Clone)]
+pub struct DeviceContextGL {
+    pub(crate) samplers: Arc<SamplerSet>,
+    pub(crate) luts: LutBundleGL,
+}
+
+impl DeviceContextGL {
+    /// Create a device context for the given OpenGL context.
+    ///
+    /// ## Safety
+    /// The OpenGL context must be current on the calling thread.
+    pub unsafe fn new(context: &glow::Context) -> Result<Self> {
+        Ok(Self {
+            samplers: Arc::new(SamplerSet::new(context)?),
+            luts: LutBundleGL::new(),
+        })
+    }
+
+    /// The bundle of LUT textures shared by filter chains created with this device context.
+    ///
+    /// Textures can be preloaded into the bundle before any filter chain is created.
+    pub fn lut_bundle(&self) -> &LutBundleGL {
+        &self.luts
+    }
+}
+
+impl Debug for DeviceContextGL {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeviceContextGL")
+            .field("luts", &self.luts)
+            .finish_non_exhaustive()
+    }
+}
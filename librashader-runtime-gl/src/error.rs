@@ -1,5 +1,6 @@
 //! OpenGL shader runtime errors.
 
+use librashader_common::ImageFormat;
 use librashader_preprocess::PreprocessError;
 use librashader_presets::ParsePresetError;
 use librashader_reflect::error::{ShaderCompileError, ShaderReflectError};
@@ -38,6 +39,19 @@ pub enum FilterChainError {
     GlInvalidFramebuffer,
     #[error("opengl error: {0}")]
     GlError(String),
+    #[error("opengl does not support external images (GL_OES_EGL_image_external)")]
+    GlExternalImageUnsupported,
+    #[error("the framebuffer format {0:?} is not supported by the context")]
+    UnsupportedFormat(ImageFormat),
+    #[error("filter chain creation was cancelled")]
+    Cancelled,
+    #[error("could not spawn the worker thread to create the filter chain on")]
+    WorkerThread(#[source] std::io::Error),
+    #[error("the filter chain requires {required}: missing {missing}")]
+    GlUnsupported {
+        required: &'static str,
+        missing: &'static str,
+    },
     #[error("unreachable")]
     Infallible(#[from] std::convert::Infallible),
 }
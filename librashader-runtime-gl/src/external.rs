@@ -0,0 +1,205 @@
+use crate::error;
+use crate::error::FilterChainError;
+use crate::framebuffer::GLImage;
+use crate::gl::{DrawQuad, FramebufferInterface, GLFramebuffer, GLInterface};
+use crate::util;
+use glow::HasContext;
+use librashader_common::ImageFormat;
+use librashader_runtime::quad::QuadType;
+
+/// `GL_TEXTURE_EXTERNAL_OES` from `GL_OES_EGL_image_external`.
+const TEXTURE_EXTERNAL_OES: u32 = 0x8D65;
+
+const EXTERNAL_INPUT_VS: &str = r#"#version 330
+layout(location = 0) in vec4 Position;
+layout(location = 1) in vec2 TexCoord;
+out vec2 vTexCoord;
+
+void main() {
+    gl_Position = Position;
+    vTexCoord = TexCoord;
+}
+"#;
+
+const EXTERNAL_INPUT_FS: &str = r#"#version 330
+#extension GL_OES_EGL_image_external : require
+uniform samplerExternalOES Source;
+in vec2 vTexCoord;
+out vec4 FragColor;
+
+void main() {
+    FragColor = texture(Source, vTexCoord);
+}
+"#;
+
+const EXTERNAL_INPUT_VS_ES: &str = r#"#version 300 es
+layout(location = 0) in vec4 Position;
+layout(location = 1) in vec2 TexCoord;
+out vec2 vTexCoord;
+
+void main() {
+    gl_Position = Position;
+    vTexCoord = TexCoord;
+}
+"#;
+
+const EXTERNAL_INPUT_FS_ES: &str = r#"#version 300 es
+#extension GL_OES_EGL_image_external_essl3 : require
+precision mediump float;
+uniform samplerExternalOES Source;
+in vec2 vTexCoord;
+out vec4 FragColor;
+
+void main() {
+    FragColor = texture(Source, vTexCoord);
+}
+"#;
+
+/// The built-in copy of external (`GL_TEXTURE_EXTERNAL_OES`) input images into an internal
+/// texture, so that shader passes can sample them as regular 2D textures.
+///
+/// External images can not be attached to a framebuffer to be blitted, so they are drawn
+/// with a program that samples them through `samplerExternalOES`.
+pub(crate) struct ExternalInput {
+    program: glow::Program,
+    framebuffer: GLFramebuffer,
+}
+
+impl ExternalInput {
+    pub fn new(context: &glow::Context, framebuffer: GLFramebuffer) -> error::Result<Self> {
+        // GLSL ES 3.00 shaders need the ESSL3 variant of the extension to use samplerExternalOES.
+        let (extension, vertex_source, fragment_source) = if context.version().is_embedded {
+            (
+                "GL_OES_EGL_image_external_essl3",
+                EXTERNAL_INPUT_VS_ES,
+                EXTERNAL_INPUT_FS_ES,
+            )
+        } else {
+            (
+                "GL_OES_EGL_image_external",
+                EXTERNAL_INPUT_VS,
+                EXTERNAL_INPUT_FS,
+            )
+        };
+
+        if !context.supported_extensions().contains(extension) {
+            return Err(FilterChainError::GlExternalImageUnsupported);
+        }
+
+        unsafe {
+            let vertex = util::gl_compile_shader(context, glow::VERTEX_SHADER, vertex_source)?;
+            let fragment =
+                util::gl_compile_shader(context, glow::FRAGMENT_SHADER, fragment_source)?;
+
+            let program = context
+                .create_program()
+                .map_err(|_| FilterChainError::GlProgramError)?;
+
+            context.attach_shader(program, vertex);
+            context.attach_shader(program, fragment);
+            context.link_program(program);
+            context.delete_shader(vertex);
+            context.delete_shader(fragment);
+
+            if !context.get_program_link_status(program) {
+                return Err(FilterChainError::GLLinkError);
+            }
+
+            Ok(ExternalInput {
+                program,
+                framebuffer,
+            })
+        }
+    }
+
+    /// Copy the external input image into the internal texture, returning the internal texture.
+    ///
+    /// When this returns, GL_FRAMEBUFFER is bound to 0.
+    pub fn copy<T: GLInterface>(
+        &mut self,
+        context: &glow::Context,
+        draw_quad: &T::DrawQuad,
+        input: &GLImage,
+    ) -> error::Result<GLImage> {
+        if input.handle.is_none() {
+            return Ok(*input);
+        }
+
+        // the format of an external image is usually not known to the frontend.
+        let format = if input.format == 0 {
+            ImageFormat::R8G8B8A8Unorm.into()
+        } else {
+            input.format
+        };
+
+        if self.framebuffer.size != input.size || self.framebuffer.format != format {
+            T::FramebufferInterface::init(&mut self.framebuffer, input.size, format)?;
+        }
+
+        unsafe {
+            self.framebuffer.bind::<T::FramebufferInterface>()?;
+            context.use_program(Some(self.program));
+
+            // the sampler uniform defaults to texture unit 0.
+            context.active_texture(glow::TEXTURE0);
+            context.bind_texture(TEXTURE_EXTERNAL_OES, input.handle);
+            if util::gl_supports_sampler_objects(context) {
+                context.bind_sampler(0, None);
+            }
+
+            context.viewport(
+                0,
+                0,
+                self.framebuffer.size.width as i32,
+                self.framebuffer.size.height as i32,
+            );
+            if util::gl_supports_framebuffer_srgb(context) {
+                context.disable(glow::FRAMEBUFFER_SRGB);
+            }
+            context.disable(glow::CULL_FACE);
+            context.disable(glow::BLEND);
+            context.disable(glow::DEPTH_TEST);
+
+            draw_quad.bind_vertices(context, QuadType::Offscreen);
+            context.draw_arrays(glow::TRIANGLE_STRIP, 0, 4);
+            draw_quad.unbind_vertices(context);
+
+            context.bind_texture(TEXTURE_EXTERNAL_OES, None);
+            context.bind_framebuffer(glow::FRAMEBUFFER, None);
+        }
+
+        Ok(GLImage {
+            handle: self.framebuffer.image,
+            format: self.framebuffer.format,
+            size: self.framebuffer.size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use glslang::{
+        Compiler, CompilerOptions, ShaderInput, ShaderMessage, ShaderSource, ShaderStage,
+        SourceLanguage, Target,
+    };
+
+    fn parse(source: &str, stage: ShaderStage) {
+        let compiler = Compiler::acquire().unwrap();
+        let options = CompilerOptions {
+            source_language: SourceLanguage::GLSL,
+            target: Target::None(None),
+            version_profile: None,
+            messages: ShaderMessage::DEFAULT,
+        };
+        let source = ShaderSource::from(source.to_string());
+        let input = ShaderInput::new(&source, stage, &options, None, None).unwrap();
+        compiler.create_shader(input).unwrap();
+    }
+
+    #[test]
+    pub fn parse_es_external_input() {
+        parse(EXTERNAL_INPUT_VS_ES, ShaderStage::Vertex);
+        parse(EXTERNAL_INPUT_FS_ES, ShaderStage::Fragment);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::binding::{GlUniformStorage, UniformLocation, VariableLocation};
 use crate::error::FilterChainError;
+use crate::external::ExternalInput;
 use crate::filter_pass::{FilterPass, UniformOffset};
 use crate::gl::{
     CompileProgram, DrawQuad, FramebufferInterface, GLFramebuffer, GLInterface, LoadLut,
@@ -8,9 +9,11 @@ use crate::gl::{
 use crate::options::{FilterChainOptionsGL, FrameOptionsGL};
 use crate::samplers::SamplerSet;
 use crate::texture::InputTexture;
-use crate::util::{gl_get_version, gl_u16_to_version};
-use crate::{error, GLImage};
-use librashader_common::Viewport;
+use crate::util::{
+    gl_get_version, gl_select_supported_format, gl_u16_to_version, gl_version_warning,
+};
+use crate::{error, DeviceContextGL, GLImage};
+use librashader_common::{FormatFallback, ScalingKernel, Viewport};
 
 use librashader_reflect::back::glsl::GlslVersion;
 use librashader_reflect::back::targets::GLSL;
@@ -25,37 +28,68 @@ use librashader_pack::{PassResource, ShaderPresetPack, TextureResource};
 use librashader_reflect::reflect::cross::SpirvCross;
 use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
 use librashader_reflect::reflect::ReflectShader;
-use librashader_runtime::binding::BindingUtil;
-use librashader_runtime::framebuffer::FramebufferInit;
+use librashader_runtime::binding::{BindingUtil, UniformBindings};
+use librashader_runtime::filter_pass::{requested_format, IdentityPasses};
+use librashader_runtime::framebuffer::{FramebufferInit, HistoryDepth};
+use librashader_runtime::instance::LibrashaderInstance;
+use librashader_runtime::jitter;
+use librashader_runtime::mesh::OutputMesh;
+use librashader_runtime::mvp;
+use librashader_runtime::progress::{CancellationToken, LoadStage, PassProgress, ProgressCallback};
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
+use librashader_runtime::rewind::RewindFrameCount;
 use librashader_runtime::scaling::ScaleFramebuffer;
+use librashader_runtime::statistics::FrameStatisticsRecorder;
+use librashader_runtime::warnings::OptionWarning;
 
 use std::collections::VecDeque;
 use std::sync::Arc;
 
 pub(crate) struct FilterChainImpl<T: GLInterface> {
     pub(crate) common: FilterCommon,
-    passes: Box<[FilterPass<T>]>,
+    pub(crate) passes: Box<[FilterPass<T>]>,
+    identity: IdentityPasses<FilterPass<T>>,
+    pass_options: PassOptions,
     draw_quad: T::DrawQuad,
     output_framebuffers: Box<[GLFramebuffer]>,
     feedback_framebuffers: Box<[GLFramebuffer]>,
     history_framebuffers: VecDeque<GLFramebuffer>,
+    pub(crate) history_depth: HistoryDepth,
+    pub(crate) option_warnings: Box<[OptionWarning]>,
+    flipped_input: Option<GLFramebuffer>,
+    external_input: Option<ExternalInput>,
     render_target: OutputFramebuffer,
     default_options: FrameOptionsGL,
     draw_last_pass_feedback: bool,
+    disable_temporal: bool,
+    rewind: RewindFrameCount,
+    invalidated_history: usize,
+}
+
+/// The options that the shader passes were created with, to create the identity passes with
+/// when they are first used.
+#[derive(Clone, Copy)]
+struct PassOptions {
+    version: GlslVersion,
+    disable_cache: bool,
+    format_fallback: FormatFallback,
 }
 
 pub(crate) struct FilterCommon {
     // semantics: ReflectSemantics,
     pub config: RuntimeParameters,
-    pub luts: FastHashMap<usize, InputTexture>,
-    pub samplers: SamplerSet,
+    pub luts: FastHashMap<usize, Arc<InputTexture>>,
+    pub samplers: Arc<SamplerSet>,
     pub output_textures: Box<[InputTexture]>,
     pub feedback_textures: Box<[InputTexture]>,
     pub history_textures: Box<[InputTexture]>,
     pub disable_mipmaps: bool,
     pub context: Arc<glow::Context>,
+    pub(crate) frame_jitter: [f32; 2],
+    /// The number of vertices of the bound quad or output mesh.
+    pub(crate) vertex_count: i32,
+    pub statistics: FrameStatisticsRecorder,
 }
 
 impl<T: GLInterface> FilterChainImpl<T> {
@@ -100,7 +134,7 @@ mod compile {
 
     #[cfg(not(feature = "stable"))]
     pub type ShaderPassMeta =
-        ShaderPassArtifact<impl CompileReflectShader<GLSL, SpirvCompilation, SpirvCross>>;
+        ShaderPassArtifact<impl CompileReflectShader<GLSL, SpirvCompilation, SpirvCross> + Send>;
 
     #[cfg(feature = "stable")]
     pub type ShaderPassMeta = ShaderPassArtifact<
@@ -132,22 +166,102 @@ mod compile {
 use compile::{compile_passes, ShaderPassMeta};
 use librashader_runtime::parameters::RuntimeParameters;
 
+/// A shader preset with its passes compiled, to create a filter chain from on the thread that
+/// the context is current on.
+pub(crate) struct CompiledPreset {
+    preset: ShaderPresetPack,
+    passes: Vec<ShaderPassMeta>,
+    semantics: ShaderSemantics,
+}
+
+impl CompiledPreset {
+    /// Append the requested built-in passes to the preset, and compile its passes.
+    ///
+    /// This does not use the context, so it can be done on any thread.
+    pub(crate) fn compile(
+        mut preset: ShaderPresetPack,
+        disable_cache: bool,
+        color_assist: bool,
+        black_frame_insertion: bool,
+        progress: Option<&ProgressCallback>,
+        cancel: Option<&CancellationToken>,
+    ) -> error::Result<Self> {
+        if color_assist {
+            preset.append_color_assist();
+        }
+        if black_frame_insertion {
+            preset.append_black_frame_insertion();
+        }
+
+        let progress = PassProgress::new(progress, preset.passes.len()).with_cancellation(cancel);
+
+        let shaders = std::mem::take(&mut preset.passes);
+        let (passes, semantics) = compile_passes(shaders, &preset.textures, disable_cache)?;
+        progress.report_all(LoadStage::Preprocessing);
+        if progress.is_cancelled() {
+            return Err(FilterChainError::Cancelled);
+        }
+
+        Ok(CompiledPreset {
+            preset,
+            passes,
+            semantics,
+        })
+    }
+}
+
 impl<T: GLInterface> FilterChainImpl<T> {
-    /// Load a filter chain from a pre-parsed `ShaderPreset`.
-    pub(crate) unsafe fn load_from_pack(
-        preset: ShaderPresetPack,
+    /// Load a filter chain from a compiled `ShaderPresetPack`.
+    pub(crate) unsafe fn load_from_compiled(
+        compiled: CompiledPreset,
         context: Arc<glow::Context>,
         options: Option<&FilterChainOptionsGL>,
     ) -> error::Result<Self> {
+        let CompiledPreset {
+            preset,
+            passes,
+            semantics,
+        } = compiled;
         let disable_cache = options.map_or(false, |o| o.disable_cache);
-        let (passes, semantics) = compile_passes(preset.passes, &preset.textures, disable_cache)?;
+        let _instance = options
+            .and_then(|o| o.instance.as_ref())
+            .map(LibrashaderInstance::enter);
+
+        let progress = PassProgress::new(options.and_then(|o| o.progress.as_ref()), passes.len())
+            .with_cancellation(options.and_then(|o| o.cancel.as_ref()));
+
         let version = options.map_or_else(
             || gl_get_version(&context),
             |o| gl_u16_to_version(&context, o.glsl_version),
         );
 
+        let parameters = RuntimeParameters::new(
+            preset.pass_count as usize,
+            preset.parameters,
+            passes
+                .iter()
+                .flat_map(|(pass, _)| pass.data.parameters.values()),
+        );
+
+        let pass_options = PassOptions {
+            version,
+            disable_cache,
+            format_fallback: options.map_or(FormatFallback::default(), |o| {
+                FormatFallback::from(o.format_fallback)
+            }),
+        };
+
         // initialize passes
-        let filters = Self::init_passes(&context, version, passes, &semantics, disable_cache)?;
+        let filters = Self::init_passes(
+            &context,
+            version,
+            passes,
+            &semantics,
+            &parameters,
+            disable_cache,
+            pass_options.format_fallback,
+            progress,
+        )?;
 
         let default_filter = filters.first().map(|f| f.meta.filter).unwrap_or_default();
         let default_wrap = filters
@@ -155,10 +269,21 @@ impl<T: GLInterface> FilterChainImpl<T> {
             .map(|f| f.meta.wrap_mode)
             .unwrap_or_default();
 
-        let samplers = SamplerSet::new(&context)?;
+        let device_context = options.and_then(|o| o.device_context.as_ref());
+        let samplers = match device_context {
+            Some(device_context) => Arc::clone(&device_context.samplers),
+            None => Arc::new(SamplerSet::new(&context)?),
+        };
 
         // load luts
-        let luts = T::LoadLut::load_luts(&context, preset.textures)?;
+        let luts = T::LoadLut::load_luts(
+            &context,
+            preset.textures,
+            options
+                .and_then(|o| o.lut_bundle.as_ref())
+                .or(device_context.map(DeviceContextGL::lut_bundle))
+                .map(|b| &b.0),
+        )?;
 
         let framebuffer_gen = || T::FramebufferInterface::new(&context, 1);
         let input_gen = || InputTexture {
@@ -172,7 +297,19 @@ impl<T: GLInterface> FilterChainImpl<T> {
             filters.iter().map(|f| &f.reflection.meta),
             &framebuffer_gen,
             &input_gen,
-        );
+        )
+        .with_max_history(options.map_or(0, |o| o.max_history as usize))
+        .with_temporal_disabled(options.map_or(false, |o| o.disable_temporal));
+        let history_depth = framebuffer_init.history_depth();
+        let mut option_warnings: Vec<OptionWarning> = options
+            .and_then(|o| gl_version_warning(o.glsl_version))
+            .into_iter()
+            .collect();
+        option_warnings.extend(framebuffer_init.option_warnings());
+        option_warnings.extend(OptionWarning::check_mipmap_input(
+            options.map_or(false, |o| o.force_no_mipmaps),
+            filters.iter().map(|f| f.meta.mipmap_input),
+        ));
 
         // initialize output framebuffers
         let (output_framebuffers, output_textures) = framebuffer_init.init_output_framebuffers()?;
@@ -184,6 +321,20 @@ impl<T: GLInterface> FilterChainImpl<T> {
         // initialize history
         let (history_framebuffers, history_textures) = framebuffer_init.init_history()?;
 
+        // initialize the framebuffer to flip the input into
+        let flipped_input = if options.map_or(false, |o| o.input_bottom_left) {
+            Some(framebuffer_gen()?)
+        } else {
+            None
+        };
+
+        // initialize the copy of external input images
+        let external_input = if options.map_or(false, |o| o.input_external) {
+            Some(ExternalInput::new(&context, framebuffer_gen()?)?)
+        } else {
+            None
+        };
+
         // create vertex objects
         let draw_quad = T::DrawQuad::new(&context)?;
 
@@ -191,13 +342,27 @@ impl<T: GLInterface> FilterChainImpl<T> {
 
         Ok(FilterChainImpl {
             draw_last_pass_feedback: framebuffer_init.uses_final_pass_as_feedback(),
+            disable_temporal: options.map_or(false, |o| o.disable_temporal),
+            rewind: RewindFrameCount::new(
+                options.map_or(false, |o| o.freeze_frame_count_on_rewind),
+            ),
+            invalidated_history: 0,
             passes: filters,
+            identity: IdentityPasses::default(),
+            pass_options,
             output_framebuffers,
             feedback_framebuffers,
             history_framebuffers,
+            history_depth,
+            option_warnings: option_warnings.into_boxed_slice(),
+            flipped_input,
+            external_input,
             draw_quad,
             common: FilterCommon {
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                statistics: FrameStatisticsRecorder::new(
+                    options.map_or(false, |o| o.collect_statistics),
+                ),
+                config: parameters,
                 disable_mipmaps: options.map_or(false, |o| o.force_no_mipmaps),
                 luts,
                 samplers,
@@ -205,28 +370,73 @@ impl<T: GLInterface> FilterChainImpl<T> {
                 feedback_textures,
                 history_textures,
                 context,
+                frame_jitter: [0.0, 0.0],
+                vertex_count: 4,
             },
             default_options: Default::default(),
             render_target: output,
         })
     }
 
+    /// Create the identity pass that scales its input to the viewport with the given kernel.
+    fn init_identity(
+        context: &glow::Context,
+        parameters: &RuntimeParameters,
+        options: PassOptions,
+        kernel: ScalingKernel,
+    ) -> error::Result<FilterPass<T>> {
+        let (passes, semantics) = compile_passes(
+            vec![PassResource::identity(kernel)],
+            &[],
+            options.disable_cache,
+        )?;
+
+        let pass = Self::init_passes(
+            context,
+            options.version,
+            passes,
+            &semantics,
+            parameters,
+            options.disable_cache,
+            options.format_fallback,
+            PassProgress::default(),
+        )?
+        .into_vec()
+        .remove(0);
+
+        Ok(pass)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn init_passes(
         context: &glow::Context,
         version: GlslVersion,
         passes: Vec<ShaderPassMeta>,
         semantics: &ShaderSemantics,
+        parameters: &RuntimeParameters,
         disable_cache: bool,
+        format_fallback: FormatFallback,
+        progress: PassProgress,
     ) -> error::Result<Box<[FilterPass<T>]>> {
-        let mut filters = Vec::new();
+        let mut filters: Vec<FilterPass<T>> = Vec::new();
 
         // initialize passes
         for (index, (config, mut reflect)) in passes.into_iter().enumerate() {
+            if progress.is_cancelled() {
+                // programs are not deleted when a pass is dropped.
+                for filter in filters {
+                    unsafe { context.delete_program(filter.program) };
+                }
+                return Err(FilterChainError::Cancelled);
+            }
+
             let reflection = reflect.reflect(index, semantics)?;
-            let glsl = reflect.compile(version)?;
+            let glsl = reflect.compile(version.into())?;
+            progress.report(LoadStage::Compiling, index);
 
             let (program, ubo_location) =
                 T::CompileShader::compile_program(context, glsl, !disable_cache)?;
+            progress.report(LoadStage::Pipeline, index);
 
             let ubo_ring = if let Some(ubo) = &reflection.ubo {
                 let ring = T::UboRing::new(&context, ubo.size)?;
@@ -243,12 +453,19 @@ impl<T: GLInterface> FilterChainImpl<T> {
                     .map_or(0, |push| push.size as usize),
             );
 
-            let uniform_bindings = reflection.meta.create_binding_map(|param| {
-                UniformOffset::new(
-                    Self::reflect_uniform_location(&context, program, param),
-                    param.offset(),
-                )
-            });
+            let uniform_bindings = UniformBindings::new(
+                reflection.meta.create_binding_map(|param| {
+                    UniformOffset::new(
+                        Self::reflect_uniform_location(&context, program, param),
+                        param.offset(),
+                    )
+                }),
+                parameters,
+            );
+
+            let requested_format = requested_format(&config.meta, config.data.format);
+            let format = gl_select_supported_format(context, requested_format, format_fallback)?
+                .ok_or(FilterChainError::UnsupportedFormat(requested_format))?;
 
             filters.push(FilterPass {
                 reflection,
@@ -259,12 +476,39 @@ impl<T: GLInterface> FilterChainImpl<T> {
                 uniform_bindings,
                 source: config.data,
                 meta: config.meta,
+                path: config.path,
+                format,
             });
         }
 
         Ok(filters.into_boxed_slice())
     }
 
+    fn flip_input(flipped: &mut Option<GLFramebuffer>, input: &GLImage) -> error::Result<GLImage> {
+        let Some(flipped) = flipped else {
+            return Ok(*input);
+        };
+
+        if flipped.size != input.size || (input.format != 0 && input.format != flipped.format) {
+            T::FramebufferInterface::init(flipped, input.size, input.format)?;
+        }
+
+        flipped.copy_from::<T::FramebufferInterface>(input, true)?;
+        Ok(GLImage {
+            handle: flipped.image,
+            format: flipped.format,
+            size: flipped.size,
+        })
+    }
+
+    pub(crate) fn set_output_mesh(&mut self, mesh: Option<&OutputMesh>) -> error::Result<()> {
+        self.draw_quad.set_mesh(&self.common.context, mesh)
+    }
+
+    pub(crate) fn invalidate_history(&mut self, frames: usize) {
+        self.invalidated_history = std::cmp::max(self.invalidated_history, frames);
+    }
+
     fn push_history(&mut self, input: &GLImage) -> error::Result<()> {
         if let Some(mut back) = self.history_framebuffers.pop_back() {
             if back.size != input.size || (input.format != 0 && input.format != back.format) {
@@ -272,7 +516,7 @@ impl<T: GLInterface> FilterChainImpl<T> {
                 T::FramebufferInterface::init(&mut back, input.size, input.format)?;
             }
 
-            back.copy_from::<T::FramebufferInterface>(input)?;
+            back.copy_from::<T::FramebufferInterface>(input, false)?;
             self.history_framebuffers.push_front(back)
         }
 
@@ -285,13 +529,17 @@ impl<T: GLInterface> FilterChainImpl<T> {
     pub unsafe fn frame(
         &mut self,
         frame_count: usize,
-        viewport: &Viewport<&GLImage>,
+        viewports: &[Viewport<&GLImage>],
         input: &GLImage,
         options: Option<&FrameOptionsGL>,
     ) -> error::Result<()> {
+        // passes are scaled relative to the first viewport.
+        let Some(viewport) = viewports.first() else {
+            return Ok(());
+        };
+
         // limit number of passes to those enabled.
         let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
-        let passes = &mut self.passes[0..max];
 
         if let Some(options) = options {
             if options.clear_history {
@@ -301,10 +549,58 @@ impl<T: GLInterface> FilterChainImpl<T> {
             }
         }
 
-        if passes.is_empty() {
-            return Ok(());
+        // with history and feedback disabled, the history is kept black.
+        if self.disable_temporal {
+            for framebuffer in &self.history_framebuffers {
+                framebuffer.clear::<T::FramebufferInterface, true>()
+            }
+        }
+
+        // clear the history frames invalidated since the last frame.
+        let invalidated_history = std::mem::take(&mut self.invalidated_history);
+        for framebuffer in self.history_framebuffers.iter().take(invalidated_history) {
+            framebuffer.clear::<T::FramebufferInterface, true>()
         }
+
         let options = options.unwrap_or(&self.default_options);
+        self.common.statistics.reset();
+        let frame_count = self
+            .rewind
+            .frame_count(frame_count, options.frame_direction);
+        self.common.frame_jitter = jitter::resolve(
+            options.jitter_sequence.into(),
+            options.jitter_offset,
+            frame_count,
+        );
+
+        // the identity pass draws to the viewport instead of the final pass if there are no
+        // enabled passes, or if the output of the final pass is scaled to the viewport.
+        let identity = if max == 0 || options.scale_final_pass {
+            Some(
+                self.identity
+                    .get_or_try_init(options.identity_scaling, |kernel| {
+                        Self::init_identity(
+                            &self.common.context,
+                            &self.common.config,
+                            self.pass_options,
+                            kernel,
+                        )
+                    })?,
+            )
+        } else {
+            None
+        };
+
+        let scale_final_pass = max > 0 && identity.is_some();
+        let (passes, final_scaling) = match identity {
+            Some(identity) if max == 0 => (std::slice::from_mut(identity), None),
+            identity => (&mut self.passes[0..max], identity),
+        };
+        let input = match &mut self.external_input {
+            Some(external) => external.copy::<T>(&self.common.context, &self.draw_quad, input)?,
+            None => *input,
+        };
+        let input = Self::flip_input(&mut self.flipped_input, &input)?;
 
         // do not need to rebind FBO 0 here since first `draw` will
         // bind automatically.
@@ -326,7 +622,7 @@ impl<T: GLInterface> FilterChainImpl<T> {
 
         // shader_gl3: 2067
         let original = InputTexture {
-            image: *input,
+            image: input,
             filter,
             mip_filter: filter,
             wrap_mode,
@@ -345,6 +641,14 @@ impl<T: GLInterface> FilterChainImpl<T> {
             None,
         )?;
 
+        // feedback is kept black, including after the feedback framebuffers are resized.
+        // the feedback of the previous frame is also cleared if it was invalidated.
+        if self.disable_temporal || invalidated_history > 0 {
+            for framebuffer in &self.feedback_framebuffers {
+                framebuffer.clear::<T::FramebufferInterface, true>()
+            }
+        }
+
         // Refresh inputs for feedback textures.
         // Don't need to do this for outputs because they are yet to be bound.
         for ((texture, fbo), pass) in self
@@ -358,10 +662,14 @@ impl<T: GLInterface> FilterChainImpl<T> {
         }
 
         let passes_len = passes.len();
-        let (pass, last) = passes.split_at_mut(passes_len - 1);
+        let (pass, last) = match final_scaling {
+            Some(identity) => (passes, std::slice::from_mut(identity)),
+            None => passes.split_at_mut(passes_len - 1),
+        };
 
         self.draw_quad
             .bind_vertices(&self.common.context, QuadType::Offscreen);
+        self.common.vertex_count = self.draw_quad.vertex_count(QuadType::Offscreen);
         for (index, pass) in pass.iter_mut().enumerate() {
             let target = &self.output_framebuffers[index];
             source.filter = pass.meta.filter;
@@ -386,19 +694,19 @@ impl<T: GLInterface> FilterChainImpl<T> {
 
         self.draw_quad
             .bind_vertices(&self.common.context, QuadType::Final);
+        self.common.vertex_count = self.draw_quad.vertex_count(QuadType::Final);
         // try to hint the optimizer
         assert_eq!(last.len(), 1);
         if let Some(pass) = last.iter_mut().next() {
             let index = passes_len - 1;
-            let final_viewport = self
-                .render_target
-                .ensure::<T::FramebufferInterface>(viewport.output)?;
-
             source.filter = pass.meta.filter;
             source.mip_filter = pass.meta.filter;
             source.wrap_mode = pass.meta.wrap_mode;
 
-            if self.draw_last_pass_feedback {
+            // the output of the final pass is already drawn if it is scaled to the viewport.
+            if self.draw_last_pass_feedback && !scale_final_pass {
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
                 let target = &self.output_framebuffers[index];
                 pass.draw(
                     index,
@@ -408,32 +716,44 @@ impl<T: GLInterface> FilterChainImpl<T> {
                     viewport,
                     &original,
                     &source,
-                    RenderTarget::viewport_with_output(target, viewport),
+                    RenderTarget::viewport_with_output(target, viewport, &final_mvp),
                 )?;
             }
 
-            pass.draw(
-                index,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                RenderTarget::viewport_with_output(final_viewport, viewport),
-            )?;
-            self.common.output_textures[passes_len - 1] = viewport
-                .output
-                .as_texture(pass.meta.filter, pass.meta.wrap_mode);
+            for viewport in viewports {
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
+                let final_viewport = self
+                    .render_target
+                    .ensure::<T::FramebufferInterface>(viewport.output)?;
+
+                pass.draw(
+                    index,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    RenderTarget::viewport_with_output(final_viewport, viewport, &final_mvp),
+                )?;
+            }
+            if !scale_final_pass {
+                self.common.output_textures[index] = viewport
+                    .output
+                    .as_texture(pass.meta.filter, pass.meta.wrap_mode);
+            }
         }
 
-        // swap feedback framebuffers with output
-        std::mem::swap(
-            &mut self.output_framebuffers,
-            &mut self.feedback_framebuffers,
-        );
+        if !self.disable_temporal {
+            // swap feedback framebuffers with output
+            std::mem::swap(
+                &mut self.output_framebuffers,
+                &mut self.feedback_framebuffers,
+            );
 
-        self.push_history(input)?;
+            self.push_history(&input)?;
+        }
 
         self.draw_quad.unbind_vertices(&self.common.context);
 
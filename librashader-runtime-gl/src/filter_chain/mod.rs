@@ -1,10 +1,11 @@
 use crate::error::{FilterChainError, Result};
-use crate::filter_chain::chain::FilterChainImpl;
+use crate::filter_chain::chain::{CompiledPreset, FilterChainImpl};
 use crate::filter_chain::inner::FilterChainDispatch;
 use crate::options::{FilterChainOptionsGL, FrameOptionsGL};
+use crate::util;
 use crate::GLImage;
 use librashader_presets::{ShaderFeatures, ShaderPreset};
-use std::panic::catch_unwind;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::Path;
 use std::sync::Arc;
 
@@ -16,8 +17,21 @@ pub(crate) use chain::FilterCommon;
 use librashader_common::Viewport;
 use librashader_pack::ShaderPresetPack;
 use librashader_presets::context::VideoDriver;
+use librashader_runtime::filter_pass::{FilterPassMeta, PassInfo};
+use librashader_runtime::framebuffer::HistoryDepth;
+use librashader_runtime::instance::LibrashaderInstance;
+use librashader_runtime::mesh::OutputMesh;
+use librashader_runtime::pending::LocalPendingFilterChain;
+use librashader_runtime::statistics::FrameStatistics;
+use librashader_runtime::warnings::OptionWarning;
 
 /// An OpenGL filter chain.
+///
+/// The filter chain requires a desktop OpenGL 3.0 context with uniform buffer objects
+/// (OpenGL 3.1 or `GL_ARB_uniform_buffer_object`) and immutable texture storage
+/// (OpenGL 4.2 or `GL_ARB_texture_storage`). Sampler objects are emulated if the context
+/// does not have OpenGL 3.3 or `GL_ARB_sampler_objects`. Creating a filter chain on a context
+/// without these fails with [`FilterChainError::GlUnsupported`].
 pub struct FilterChainGL {
     pub(in crate::filter_chain) filter: FilterChainDispatch,
 }
@@ -33,29 +47,107 @@ impl FilterChainGL {
         unsafe { Self::load_from_pack(preset, ctx, options) }
     }
 
+    /// Load a filter chain from a pre-parsed `ShaderPreset`, compiling its shaders on a worker
+    /// thread.
+    ///
+    /// Shaders are compiled without blocking the calling thread. The rest of the filter chain is
+    /// created on the thread that takes it from the returned [`LocalPendingFilterChain`], which
+    /// must have `ctx` current. The progress callback of the options is called on the worker
+    /// thread while shaders are compiled, and on the taking thread afterwards.
+    ///
+    /// Returns an error if the worker thread could not be spawned.
+    pub unsafe fn load_from_preset_async(
+        preset: ShaderPreset,
+        ctx: Arc<glow::Context>,
+        options: Option<FilterChainOptionsGL>,
+    ) -> Result<LocalPendingFilterChain<Result<Self>>> {
+        let disable_cache = options.as_ref().map_or(false, |o| o.disable_cache);
+        let color_assist = options.as_ref().map_or(false, |o| o.color_assist);
+        let black_frame_insertion = options.as_ref().map_or(false, |o| o.black_frame_insertion);
+        let progress = options.as_ref().and_then(|o| o.progress.clone());
+        let cancel = options.as_ref().and_then(|o| o.cancel.clone());
+        let instance = options.as_ref().and_then(|o| o.instance.clone());
+
+        LocalPendingFilterChain::spawn(
+            move || {
+                let _instance = instance.as_ref().map(LibrashaderInstance::enter);
+                let preset = ShaderPresetPack::load_from_preset::<FilterChainError>(preset)?;
+                CompiledPreset::compile(
+                    preset,
+                    disable_cache,
+                    color_assist,
+                    black_frame_insertion,
+                    progress.as_ref(),
+                    cancel.as_ref(),
+                )
+            },
+            move |compiled: Result<CompiledPreset>| unsafe {
+                Self::load_from_compiled(compiled?, ctx, options.as_ref())
+            },
+        )
+        .map_err(FilterChainError::WorkerThread)
+    }
+
     /// Load a filter chain from a pre-parsed and loaded `ShaderPresetPack`.
     pub unsafe fn load_from_pack(
         preset: ShaderPresetPack,
         ctx: Arc<glow::Context>,
         options: Option<&FilterChainOptionsGL>,
     ) -> Result<Self> {
-        let result = catch_unwind(|| {
-            if options.is_some_and(|options| options.use_dsa) {
+        let _instance = options
+            .and_then(|o| o.instance.as_ref())
+            .map(LibrashaderInstance::enter);
+        let compiled = CompiledPreset::compile(
+            preset,
+            options.map_or(false, |o| o.disable_cache),
+            options.map_or(false, |o| o.color_assist),
+            options.map_or(false, |o| o.black_frame_insertion),
+            options.and_then(|o| o.progress.as_ref()),
+            options.and_then(|o| o.cancel.as_ref()),
+        )?;
+        unsafe { Self::load_from_compiled(compiled, ctx, options) }
+    }
+
+    unsafe fn load_from_compiled(
+        compiled: CompiledPreset,
+        ctx: Arc<glow::Context>,
+        options: Option<&FilterChainOptionsGL>,
+    ) -> Result<Self> {
+        // the progress callback is only called, and is not observed after a panic.
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let use_dsa = options.is_some_and(|options| options.use_dsa);
+            util::gl_check_capabilities(&ctx, use_dsa)?;
+
+            if use_dsa {
                 return Ok(Self {
                     filter: FilterChainDispatch::DirectStateAccess(unsafe {
-                        FilterChainImpl::load_from_pack(preset, ctx, options)?
+                        FilterChainImpl::load_from_compiled(compiled, ctx, options)?
                     }),
                 });
             }
             Ok(Self {
                 filter: FilterChainDispatch::Compatibility(unsafe {
-                    FilterChainImpl::load_from_pack(preset, ctx, options)?
+                    FilterChainImpl::load_from_compiled(compiled, ctx, options)?
                 }),
             })
-        });
+        }));
         result.unwrap_or_else(|_| Err(FilterChainError::GLLoadError))
     }
 
+    /// Create a filter chain without any shader passes, that only scales the input to the viewport.
+    ///
+    /// This is equivalent to loading a preset without any shader passes.
+    ///
+    /// ## Safety
+    /// `ctx` must be current on the calling thread, and must be the context that
+    /// [`frame`](Self::frame) is called with.
+    pub unsafe fn new_identity(
+        ctx: Arc<glow::Context>,
+        options: Option<&FilterChainOptionsGL>,
+    ) -> Result<Self> {
+        unsafe { Self::load_from_pack(ShaderPresetPack::empty(), ctx, options) }
+    }
+
     /// Load the shader preset at the given path into a filter chain.
     pub unsafe fn load_from_path(
         path: impl AsRef<Path>,
@@ -79,17 +171,105 @@ impl FilterChainGL {
         viewport: &Viewport<&GLImage>,
         frame_count: usize,
         options: Option<&FrameOptionsGL>,
+    ) -> Result<()> {
+        unsafe { self.frame_broadcast(input, std::slice::from_ref(viewport), frame_count, options) }
+    }
+
+    /// Process a frame with the input image, drawing the final pass to every viewport.
+    ///
+    /// Intermediate passes are scaled relative to the first viewport and are only run once.
+    /// This allows the processed frame to be presented and captured without an extra copy.
+    /// If `viewports` is empty, nothing is drawn.
+    ///
+    /// When this frame returns, `GL_FRAMEBUFFER` is bound to 0 if not using Direct State Access.
+    /// Otherwise, it is untouched.
+    ///
+    /// ## Safety
+    /// - The context the filter chain was created with must be current on the calling thread.
+    /// - The input texture and the output texture of every viewport must be textures of that
+    ///   context, and must not be deleted until this function returns. Viewports may draw to
+    ///   different regions of the same output texture, but no output texture may be the input
+    ///   texture.
+    /// - Draws are submitted to the current context, so later commands on that context see every
+    ///   output. Other contexts that share an output texture must synchronize with the current
+    ///   context, for example with a fence sync, before reading it.
+    pub unsafe fn frame_broadcast(
+        &mut self,
+        input: &GLImage,
+        viewports: &[Viewport<&GLImage>],
+        frame_count: usize,
+        options: Option<&FrameOptionsGL>,
     ) -> Result<()> {
         match &mut self.filter {
             FilterChainDispatch::DirectStateAccess(p) => unsafe {
-                p.frame(frame_count, viewport, input, options)
+                p.frame(frame_count, viewports, input, options)
             },
             FilterChainDispatch::Compatibility(p) => unsafe {
-                p.frame(frame_count, viewport, input, options)
+                p.frame(frame_count, viewports, input, options)
             },
         }
     }
 
+    /// Get the depth of the history kept by the filter chain, and the depth
+    /// requested by the shader passes.
+    pub fn history_depth(&self) -> HistoryDepth {
+        match &self.filter {
+            FilterChainDispatch::DirectStateAccess(p) => p.history_depth,
+            FilterChainDispatch::Compatibility(p) => p.history_depth,
+        }
+    }
+
+    /// Get the warnings about options of the filter chain that could not be honoured as
+    /// given, such as mipmaps being forced off for shader passes that request them.
+    pub fn option_warnings(&self) -> &[OptionWarning] {
+        match &self.filter {
+            FilterChainDispatch::DirectStateAccess(p) => &p.option_warnings,
+            FilterChainDispatch::Compatibility(p) => &p.option_warnings,
+        }
+    }
+
+    /// Invalidate the last `frames` frames of history, such as after a rollback in runahead
+    /// or netplay, so that shader passes do not blend frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+    /// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+    pub fn invalidate_history(&mut self, frames: usize) {
+        match &mut self.filter {
+            FilterChainDispatch::DirectStateAccess(p) => p.invalidate_history(frames),
+            FilterChainDispatch::Compatibility(p) => p.invalidate_history(frames),
+        }
+    }
+
+    /// Set the mesh to draw the final pass with, or reset it to the default quad if `None`.
+    pub fn set_output_mesh(&mut self, mesh: Option<&OutputMesh>) -> Result<()> {
+        match &mut self.filter {
+            FilterChainDispatch::DirectStateAccess(p) => p.set_output_mesh(mesh),
+            FilterChainDispatch::Compatibility(p) => p.set_output_mesh(mesh),
+        }
+    }
+
+    /// Get information about the shader pass at the given index, including the
+    /// framebuffer format chosen for the pass.
+    pub fn pass_info(&self, index: usize) -> Option<PassInfo> {
+        match &self.filter {
+            FilterChainDispatch::DirectStateAccess(p) => {
+                p.passes.get(index).map(FilterPassMeta::pass_info)
+            }
+            FilterChainDispatch::Compatibility(p) => {
+                p.passes.get(index).map(FilterPassMeta::pass_info)
+            }
+        }
+    }
+
+    /// Get the statistics of the commands recorded for the last frame, or `None` if the
+    /// filter chain was not created with the `collect_statistics` option.
+    pub fn frame_statistics(&self) -> Option<FrameStatistics> {
+        match &self.filter {
+            FilterChainDispatch::DirectStateAccess(p) => p.common.statistics.statistics(),
+            FilterChainDispatch::Compatibility(p) => p.common.statistics.statistics(),
+        }
+    }
+
     /// Get the GL context associated with this filter chain
     pub fn get_context(&self) -> &Arc<glow::Context> {
         match &self.filter {
@@ -1,12 +1,14 @@
 use glow::HasContext;
 use librashader_reflect::reflect::ShaderReflection;
 
-use librashader_common::map::FastHashMap;
 use librashader_common::{ImageFormat, Size, Viewport};
 use librashader_preprocess::ShaderSource;
 use librashader_presets::PassMeta;
-use librashader_reflect::reflect::semantics::{MemberOffset, TextureBinding, UniformBinding};
-use librashader_runtime::binding::{BindSemantics, ContextOffset, TextureInput, UniformInputs};
+use librashader_reflect::reflect::semantics::{MemberOffset, TextureBinding};
+use librashader_runtime::binding::{
+    BindSemantics, ContextOffset, TextureInput, UniformBindings, UniformInputs,
+    DEFAULT_SCENE_LUMINANCE,
+};
 use librashader_runtime::filter_pass::FilterPassMeta;
 use librashader_runtime::render_target::RenderTarget;
 
@@ -15,9 +17,10 @@ use crate::filter_chain::FilterCommon;
 use crate::gl::{BindTexture, GLFramebuffer, GLInterface, UboRing};
 use crate::options::FrameOptionsGL;
 use crate::samplers::SamplerSet;
-use crate::{error, GLImage};
+use crate::{error, util, GLImage};
 
 use crate::texture::InputTexture;
+use std::path::{Path, PathBuf};
 
 pub struct UniformOffset {
     pub location: VariableLocation,
@@ -36,9 +39,13 @@ pub(crate) struct FilterPass<T: GLInterface> {
     pub ubo_location: UniformLocation<Option<u32>>,
     pub ubo_ring: Option<T::UboRing>,
     pub(crate) uniform_storage: GlUniformStorage,
-    pub uniform_bindings: FastHashMap<UniformBinding, UniformOffset>,
+    pub uniform_bindings: UniformBindings<UniformOffset>,
     pub source: ShaderSource,
     pub meta: PassMeta,
+    pub path: Option<PathBuf>,
+    /// The framebuffer format of the pass, after falling back to a format supported
+    /// by the context.
+    pub format: ImageFormat,
 }
 
 impl TextureInput for InputTexture {
@@ -87,6 +94,8 @@ impl<T: GLInterface> FilterPass<T> {
         source: &InputTexture,
         output: RenderTarget<GLFramebuffer, i32>,
     ) -> error::Result<()> {
+        parent.statistics.record_pass(&self.reflection);
+
         let framebuffer = output.output;
 
         if self.meta.mipmap_input && !parent.disable_mipmaps {
@@ -138,18 +147,25 @@ impl<T: GLInterface> FilterPass<T> {
                 output.size.height as i32,
             );
 
-            if framebuffer.format == glow::SRGB8_ALPHA8 {
-                parent.context.enable(glow::FRAMEBUFFER_SRGB);
-            } else {
-                parent.context.disable(glow::FRAMEBUFFER_SRGB);
+            let toggle_srgb = util::gl_supports_framebuffer_srgb(&parent.context);
+            if toggle_srgb {
+                if framebuffer.format == glow::SRGB8_ALPHA8 {
+                    parent.context.enable(glow::FRAMEBUFFER_SRGB);
+                } else {
+                    parent.context.disable(glow::FRAMEBUFFER_SRGB);
+                }
             }
 
             parent.context.disable(glow::CULL_FACE);
             parent.context.disable(glow::BLEND);
             parent.context.disable(glow::DEPTH_TEST);
 
-            parent.context.draw_arrays(glow::TRIANGLE_STRIP, 0, 4);
-            parent.context.disable(glow::FRAMEBUFFER_SRGB);
+            parent
+                .context
+                .draw_arrays(glow::TRIANGLE_STRIP, 0, parent.vertex_count);
+            if toggle_srgb {
+                parent.context.disable(glow::FRAMEBUFFER_SRGB);
+            }
             parent.context.bind_framebuffer(glow::FRAMEBUFFER, None);
         }
 
@@ -165,6 +181,14 @@ impl<T: GLInterface> FilterPassMeta for FilterPass<T> {
     fn meta(&self) -> &PassMeta {
         &self.meta
     }
+
+    fn source_path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn get_supported_format(&self) -> ImageFormat {
+        self.format
+    }
 }
 
 impl<T: GLInterface> FilterPass<T> {
@@ -198,6 +222,8 @@ impl<T: GLInterface> FilterPass<T> {
                 frametime_delta: options.frametime_delta,
                 framebuffer_size: fb_size,
                 viewport_size: viewport.output.size,
+                scene_luminance: DEFAULT_SCENE_LUMINANCE,
+                frame_jitter: parent.frame_jitter,
             },
             original,
             source,
@@ -209,7 +235,6 @@ impl<T: GLInterface> FilterPass<T> {
             parent.feedback_textures.iter().map(|o| o.bound()),
             parent.history_textures.iter().map(|o| o.bound()),
             parent.luts.iter().map(|(u, i)| (*u, i)),
-            &self.source.parameters,
             &parent.config,
         );
     }
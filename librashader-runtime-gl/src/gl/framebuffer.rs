@@ -71,8 +71,12 @@ impl GLFramebuffer {
         )
     }
 
-    pub(crate) fn copy_from<T: FramebufferInterface>(&mut self, image: &GLImage) -> Result<()> {
-        T::copy_from(self, image)
+    pub(crate) fn copy_from<T: FramebufferInterface>(
+        &mut self,
+        image: &GLImage,
+        flip_y: bool,
+    ) -> Result<()> {
+        T::copy_from(self, image, flip_y)
     }
 
     pub(crate) fn as_texture(&self, filter: FilterMode, wrap_mode: WrapMode) -> InputTexture {
@@ -3,11 +3,13 @@ use crate::error::FilterChainError;
 use crate::gl::{DrawQuad, FINAL_VBO_DATA, OFFSCREEN_VBO_DATA};
 use bytemuck::offset_of;
 use glow::HasContext;
+use librashader_runtime::mesh::OutputMesh;
 use librashader_runtime::quad::{QuadType, VertexInput};
 
 pub struct Gl3DrawQuad {
     vbo: [glow::Buffer; 2],
     vao: glow::VertexArray,
+    mesh: Option<(glow::Buffer, i32)>,
 }
 
 impl DrawQuad for Gl3DrawQuad {
@@ -43,13 +45,18 @@ impl DrawQuad for Gl3DrawQuad {
                 .map_err(FilterChainError::GlError)?;
         }
 
-        Ok(Self { vbo, vao })
+        Ok(Self {
+            vbo,
+            vao,
+            mesh: None,
+        })
     }
 
     fn bind_vertices(&self, ctx: &glow::Context, quad_type: QuadType) {
-        let buffer_index = match quad_type {
-            QuadType::Offscreen => 0,
-            QuadType::Final => 1,
+        let buffer = match (quad_type, &self.mesh) {
+            (QuadType::Offscreen, _) => self.vbo[0],
+            (QuadType::Final, Some((mesh, _))) => *mesh,
+            (QuadType::Final, None) => self.vbo[1],
         };
 
         unsafe {
@@ -57,7 +64,7 @@ impl DrawQuad for Gl3DrawQuad {
             ctx.enable_vertex_attrib_array(0);
             ctx.enable_vertex_attrib_array(1);
 
-            ctx.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo[buffer_index]));
+            ctx.bind_buffer(glow::ARRAY_BUFFER, Some(buffer));
 
             ctx.vertex_attrib_pointer_f32(
                 0,
@@ -87,6 +94,39 @@ impl DrawQuad for Gl3DrawQuad {
             ctx.bind_buffer(glow::ARRAY_BUFFER, None);
         }
     }
+
+    fn set_mesh(&mut self, ctx: &glow::Context, mesh: Option<&OutputMesh>) -> error::Result<()> {
+        if let Some((buffer, _)) = self.mesh.take() {
+            unsafe { ctx.delete_buffer(buffer) }
+        }
+
+        let Some(mesh) = mesh else {
+            return Ok(());
+        };
+
+        // the clip space and texture origin of OpenGL are at the bottom.
+        let vertices = mesh.flipped_vertices(true);
+        unsafe {
+            let buffer = ctx.create_buffer().map_err(FilterChainError::GlError)?;
+            ctx.bind_buffer(glow::ARRAY_BUFFER, Some(buffer));
+            ctx.buffer_data_u8_slice(
+                glow::ARRAY_BUFFER,
+                bytemuck::cast_slice(&vertices),
+                glow::STATIC_DRAW,
+            );
+            ctx.bind_buffer(glow::ARRAY_BUFFER, None);
+            self.mesh = Some((buffer, mesh.vertex_count() as i32));
+        }
+
+        Ok(())
+    }
+
+    fn vertex_count(&self, quad_type: QuadType) -> i32 {
+        match (quad_type, &self.mesh) {
+            (QuadType::Final, Some((_, count))) => *count,
+            _ => 4,
+        }
+    }
 }
 
 // impl Drop for Gl3DrawQuad {
@@ -106,7 +106,7 @@ impl FramebufferInterface for Gl3Framebuffer {
             }
         }
     }
-    fn copy_from(fb: &mut GLFramebuffer, image: &GLImage) -> Result<()> {
+    fn copy_from(fb: &mut GLFramebuffer, image: &GLImage, flip_y: bool) -> Result<()> {
         // todo: may want to use a shader and draw a quad to be faster.
         if image.size != fb.size || image.format != fb.format {
             Self::init(fb, image.size, image.format)?;
@@ -131,7 +131,15 @@ impl FramebufferInterface for Gl3Framebuffer {
             );
 
             fb.ctx.read_buffer(glow::COLOR_ATTACHMENT0);
-            fb.ctx.draw_buffer(glow::COLOR_ATTACHMENT1);
+            // glDrawBuffer is not available on OpenGL ES.
+            fb.ctx.draw_buffers(&[glow::NONE, glow::COLOR_ATTACHMENT1]);
+
+            // blitting to a destination rectangle with swapped y coordinates flips the image.
+            let (dst_y0, dst_y1) = if flip_y {
+                (fb.size.height as i32, 0)
+            } else {
+                (0, fb.size.height as i32)
+            };
 
             fb.ctx.blit_framebuffer(
                 0,
@@ -139,9 +147,9 @@ impl FramebufferInterface for Gl3Framebuffer {
                 fb.size.width as i32,
                 fb.size.height as i32,
                 0,
-                0,
+                dst_y0,
                 fb.size.width as i32,
-                fb.size.height as i32,
+                dst_y1,
                 glow::COLOR_BUFFER_BIT,
                 glow::NEAREST,
             );
@@ -17,9 +17,12 @@ impl BindTexture for Gl3BindTexture {
             // eprintln!("setting {} to texunit {}", texture.image.handle, binding.binding);;
             ctx.active_texture(glow::TEXTURE0 + binding.binding);
             ctx.bind_texture(glow::TEXTURE_2D, texture.image.handle);
-            ctx.bind_sampler(
+            samplers.bind(
+                ctx,
                 binding.binding,
-                Some(samplers.get(texture.wrap_mode, texture.filter, texture.mip_filter)),
+                texture.wrap_mode,
+                texture.filter,
+                texture.mip_filter,
             );
         }
     }
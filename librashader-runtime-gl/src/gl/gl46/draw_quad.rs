@@ -4,11 +4,13 @@ use crate::gl::DrawQuad;
 use crate::gl::{FINAL_VBO_DATA, OFFSCREEN_VBO_DATA};
 use bytemuck::offset_of;
 use glow::HasContext;
+use librashader_runtime::mesh::OutputMesh;
 use librashader_runtime::quad::{QuadType, VertexInput};
 
 pub struct Gl46DrawQuad {
     vbo: [glow::Buffer; 2],
     vao: glow::VertexArray,
+    mesh: Option<(glow::Buffer, i32)>,
 }
 
 impl DrawQuad for Gl46DrawQuad {
@@ -65,20 +67,25 @@ impl DrawQuad for Gl46DrawQuad {
             context.vertex_array_attrib_binding_f32(vao, 1, 0);
         }
 
-        Ok(Self { vbo, vao })
+        Ok(Self {
+            vbo,
+            vao,
+            mesh: None,
+        })
     }
 
     fn bind_vertices(&self, context: &glow::Context, quad_type: QuadType) {
-        let buffer_index = match quad_type {
-            QuadType::Offscreen => 0,
-            QuadType::Final => 1,
+        let buffer = match (quad_type, &self.mesh) {
+            (QuadType::Offscreen, _) => self.vbo[0],
+            (QuadType::Final, Some((mesh, _))) => *mesh,
+            (QuadType::Final, None) => self.vbo[1],
         };
 
         unsafe {
             context.vertex_array_vertex_buffer(
                 self.vao,
                 0,
-                Some(self.vbo[buffer_index]),
+                Some(buffer),
                 0,
                 std::mem::size_of::<VertexInput>() as i32,
             );
@@ -92,4 +99,41 @@ impl DrawQuad for Gl46DrawQuad {
             context.bind_vertex_array(None);
         }
     }
+
+    fn set_mesh(
+        &mut self,
+        context: &glow::Context,
+        mesh: Option<&OutputMesh>,
+    ) -> error::Result<()> {
+        if let Some((buffer, _)) = self.mesh.take() {
+            unsafe { context.delete_buffer(buffer) }
+        }
+
+        let Some(mesh) = mesh else {
+            return Ok(());
+        };
+
+        // the clip space and texture origin of OpenGL are at the bottom.
+        let vertices = mesh.flipped_vertices(true);
+        unsafe {
+            let buffer = context
+                .create_named_buffer()
+                .map_err(FilterChainError::GlError)?;
+            context.named_buffer_data_u8_slice(
+                buffer,
+                bytemuck::cast_slice(&vertices),
+                glow::STATIC_DRAW,
+            );
+            self.mesh = Some((buffer, mesh.vertex_count() as i32));
+        }
+
+        Ok(())
+    }
+
+    fn vertex_count(&self, quad_type: QuadType) -> i32 {
+        match (quad_type, &self.mesh) {
+            (QuadType::Final, Some((_, count))) => *count,
+            _ => 4,
+        }
+    }
 }
@@ -93,7 +93,7 @@ impl FramebufferInterface for Gl46Framebuffer {
             );
         }
     }
-    fn copy_from(fb: &mut GLFramebuffer, image: &GLImage) -> Result<()> {
+    fn copy_from(fb: &mut GLFramebuffer, image: &GLImage, flip_y: bool) -> Result<()> {
         // todo: confirm this behaviour for unbound image.
         if image.handle == None {
             return Ok(());
@@ -120,6 +120,13 @@ impl FramebufferInterface for Gl46Framebuffer {
             fb.ctx
                 .named_framebuffer_texture(Some(fb.fbo), glow::COLOR_ATTACHMENT1, fb.image, 0);
 
+            // blitting to a destination rectangle with swapped y coordinates flips the image.
+            let (dst_y0, dst_y1) = if flip_y {
+                (fb.size.height as i32, 0)
+            } else {
+                (0, fb.size.height as i32)
+            };
+
             fb.ctx.blit_named_framebuffer(
                 Some(fb.fbo),
                 Some(fb.fbo),
@@ -128,9 +135,9 @@ impl FramebufferInterface for Gl46Framebuffer {
                 image.size.width as i32,
                 image.size.height as i32,
                 0,
-                0,
+                dst_y0,
                 fb.size.width as i32,
-                fb.size.height as i32,
+                dst_y1,
                 glow::COLOR_BUFFER_BIT,
                 glow::NEAREST,
             );
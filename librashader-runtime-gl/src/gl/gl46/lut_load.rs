@@ -6,31 +6,41 @@ use glow::{HasContext, PixelUnpackData};
 use librashader_common::map::FastHashMap;
 use librashader_pack::TextureResource;
 use librashader_runtime::image::{ImageError, LoadedTexture, UVDirection};
+use librashader_runtime::luts::LutBundle;
 use librashader_runtime::scaling::MipmapSize;
 use rayon::prelude::*;
+use std::sync::Arc;
 
 pub struct Gl46LutLoad;
 impl LoadLut for Gl46LutLoad {
     fn load_luts(
         context: &glow::Context,
         textures: Vec<TextureResource>,
-    ) -> Result<FastHashMap<usize, InputTexture>> {
-        let mut luts = FastHashMap::default();
-
+        bundle: Option<&LutBundle<InputTexture>>,
+    ) -> Result<FastHashMap<usize, Arc<InputTexture>>> {
         // don't need this for texture DSA api.
 
-        let textures = textures
-            .into_par_iter()
-            .map(|texture| LoadedTexture::from_texture(texture, UVDirection::TopLeft))
-            .collect::<std::result::Result<Vec<LoadedTexture>, ImageError>>()?;
+        let decode = |textures: Vec<TextureResource>| {
+            textures
+                .into_par_iter()
+                .map(|texture| LoadedTexture::from_texture(texture, UVDirection::TopLeft))
+                .collect::<std::result::Result<Vec<LoadedTexture>, ImageError>>()
+        };
 
-        for (index, LoadedTexture { meta, image }) in textures.iter().enumerate() {
+        let luts = LutBundle::load(bundle, textures, decode, |LoadedTexture { meta, image }| {
             let levels = if meta.mipmap {
                 image.size.calculate_miplevels()
             } else {
                 1u32
             };
 
+            // sRGB textures are decoded to linear when sampled, so filtering happens in linear space.
+            let format = if meta.srgb {
+                glow::SRGB8_ALPHA8
+            } else {
+                glow::RGBA8
+            };
+
             let handle = unsafe {
                 let handle = context
                     .create_named_texture(glow::TEXTURE_2D)
@@ -39,7 +49,7 @@ impl LoadLut for Gl46LutLoad {
                 context.texture_storage_2d(
                     handle,
                     levels as i32,
-                    glow::RGBA8,
+                    format,
                     image.size.width as i32,
                     image.size.height as i32,
                 );
@@ -67,24 +77,21 @@ impl LoadLut for Gl46LutLoad {
                 handle
             };
 
-            luts.insert(
-                index,
-                InputTexture {
-                    image: GLImage {
-                        handle: Some(handle),
-                        format: glow::RGBA8,
-                        size: image.size,
-                    },
-                    filter: meta.filter_mode,
-                    mip_filter: meta.filter_mode,
-                    wrap_mode: meta.wrap_mode,
+            Ok::<_, FilterChainError>(InputTexture {
+                image: GLImage {
+                    handle: Some(handle),
+                    format,
+                    size: image.size,
                 },
-            );
-        }
+                filter: meta.filter_mode,
+                mip_filter: meta.filter_mode,
+                wrap_mode: meta.wrap_mode,
+            })
+        });
 
         // unsafe {
         //     context.bind_buffer(glow::PIXEL_UNPACK_BUFFER, pixel_unpack);
         // };
-        Ok(luts)
+        luts
     }
 }
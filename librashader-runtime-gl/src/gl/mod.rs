@@ -14,6 +14,8 @@ use librashader_presets::Scale2D;
 use librashader_reflect::back::glsl::CrossGlslContext;
 use librashader_reflect::back::ShaderCompilerOutput;
 use librashader_reflect::reflect::semantics::{BufferReflection, TextureBinding};
+use librashader_runtime::luts::LutBundle;
+use librashader_runtime::mesh::OutputMesh;
 use librashader_runtime::quad::{QuadType, VertexInput};
 use librashader_runtime::scaling::ViewportSize;
 use librashader_runtime::uniforms::UniformStorageAccess;
@@ -61,7 +63,8 @@ pub(crate) trait LoadLut {
     fn load_luts(
         context: &glow::Context,
         textures: Vec<TextureResource>,
-    ) -> Result<FastHashMap<usize, InputTexture>>;
+        bundle: Option<&LutBundle<InputTexture>>,
+    ) -> Result<FastHashMap<usize, Arc<InputTexture>>>;
 }
 
 pub(crate) trait CompileProgram {
@@ -78,6 +81,8 @@ pub(crate) trait DrawQuad {
         Self: Sized;
     fn bind_vertices(&self, context: &glow::Context, quad_type: QuadType);
     fn unbind_vertices(&self, context: &glow::Context);
+    fn set_mesh(&mut self, context: &glow::Context, mesh: Option<&OutputMesh>) -> Result<()>;
+    fn vertex_count(&self, quad_type: QuadType) -> i32;
 }
 
 pub(crate) trait UboRing<const SIZE: usize> {
@@ -147,7 +152,7 @@ pub(crate) trait FramebufferInterface {
     }
 
     fn clear<const REBIND: bool>(fb: &GLFramebuffer);
-    fn copy_from(fb: &mut GLFramebuffer, image: &GLImage) -> Result<()>;
+    fn copy_from(fb: &mut GLFramebuffer, image: &GLImage, flip_y: bool) -> Result<()>;
     fn init(fb: &mut GLFramebuffer, size: Size<u32>, format: impl Into<u32>) -> Result<()>;
     fn bind(fb: &GLFramebuffer) -> Result<()>;
 }
@@ -6,17 +6,22 @@
 #![cfg_attr(not(feature = "stable"), feature(type_alias_impl_trait))]
 
 mod binding;
+mod device_context;
+mod external;
 mod filter_chain;
 mod filter_pass;
 mod framebuffer;
 mod util;
 
 mod gl;
+mod luts;
 mod samplers;
 mod texture;
 
 pub mod error;
 pub mod options;
 
+pub use device_context::DeviceContextGL;
 pub use filter_chain::FilterChainGL;
 pub use framebuffer::GLImage;
+pub use luts::LutBundleGL;
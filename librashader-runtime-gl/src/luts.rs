@@ -0,0 +1,66 @@
+use crate::error::Result;
+use crate::gl::gl3::CompatibilityGL;
+use crate::gl::gl46::DirectStateAccessGL;
+use crate::gl::{GLInterface, LoadLut};
+use crate::texture::InputTexture;
+use librashader_pack::TextureResource;
+use librashader_runtime::luts::LutBundle;
+
+/// A set of LUT textures uploaded to an OpenGL context, that can be shared between filter chains
+/// created on the same context with the `lut_bundle` option.
+///
+/// Frontends that switch between presets sharing texture packs can keep a bundle around, so
+/// that textures are only uploaded once. Clones of a bundle share the same textures.
+#[derive(Clone, Debug, Default)]
+pub struct LutBundleGL(pub(crate) LutBundle<InputTexture>);
+
+impl LutBundleGL {
+    /// Create an empty bundle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of textures in the bundle.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the bundle has no textures.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Remove every texture from the bundle.
+    ///
+    /// OpenGL textures are not deleted when released, so textures removed from the bundle
+    /// stay allocated until the context is destroyed.
+    pub fn clear(&self) {
+        self.0.clear()
+    }
+
+    /// Upload LUT textures to the bundle ahead of filter chain creation, such as the textures
+    /// of a `ShaderPresetPack`.
+    ///
+    /// Textures that are already in the bundle are not uploaded again. `use_dsa` should match
+    /// the option that filter chains using the bundle are created with.
+    ///
+    /// ## Safety
+    /// The OpenGL context must be current on the calling thread.
+    pub unsafe fn preload(
+        &self,
+        textures: Vec<TextureResource>,
+        context: &glow::Context,
+        use_dsa: bool,
+    ) -> Result<()> {
+        if use_dsa {
+            <DirectStateAccessGL as GLInterface>::LoadLut::load_luts(
+                context,
+                textures,
+                Some(&self.0),
+            )?;
+        } else {
+            <CompatibilityGL as GLInterface>::LoadLut::load_luts(context, textures, Some(&self.0))?;
+        }
+        Ok(())
+    }
+}
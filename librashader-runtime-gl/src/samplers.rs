@@ -1,5 +1,6 @@
 use crate::error;
 use crate::error::FilterChainError;
+use crate::util;
 use glow::HasContext;
 use librashader_common::map::FastHashMap;
 use librashader_common::{FilterMode, WrapMode};
@@ -7,6 +8,8 @@ use librashader_common::{FilterMode, WrapMode};
 pub struct SamplerSet {
     // todo: may need to deal with differences in mip filter.
     samplers: FastHashMap<(WrapMode, FilterMode, FilterMode), glow::Sampler>,
+    // without sampler objects, sampler state is set as texture parameters instead.
+    emulated: bool,
 }
 
 impl SamplerSet {
@@ -22,6 +25,38 @@ impl SamplerSet {
         }
     }
 
+    /// Bind the sampler state to the texture unit, after the texture to sample was bound to it.
+    ///
+    /// If sampler objects are emulated, the state is set on the bound texture instead, so a
+    /// texture that is bound to multiple units in a pass is sampled with the last state.
+    pub fn bind(
+        &self,
+        context: &glow::Context,
+        unit: u32,
+        wrap: WrapMode,
+        filter: FilterMode,
+        mipmap: FilterMode,
+    ) {
+        unsafe {
+            if self.emulated {
+                for (parameter, value) in SamplerSet::parameters(wrap, filter, mipmap) {
+                    context.tex_parameter_i32(glow::TEXTURE_2D, parameter, value);
+                }
+            } else {
+                context.bind_sampler(unit, Some(self.get(wrap, filter, mipmap)));
+            }
+        }
+    }
+
+    fn parameters(wrap: WrapMode, filter: FilterMode, mip: FilterMode) -> [(u32, i32); 4] {
+        [
+            (glow::TEXTURE_WRAP_S, wrap.into()),
+            (glow::TEXTURE_WRAP_T, wrap.into()),
+            (glow::TEXTURE_MAG_FILTER, filter.into()),
+            (glow::TEXTURE_MIN_FILTER, filter.gl_mip(mip) as i32),
+        ]
+    }
+
     fn make_sampler(
         context: &glow::Context,
         sampler: glow::Sampler,
@@ -29,19 +64,21 @@ impl SamplerSet {
         filter: FilterMode,
         mip: FilterMode,
     ) {
-        unsafe {
-            context.sampler_parameter_i32(sampler, glow::TEXTURE_WRAP_S, wrap.into());
-            context.sampler_parameter_i32(sampler, glow::TEXTURE_WRAP_T, wrap.into());
-            context.sampler_parameter_i32(sampler, glow::TEXTURE_MAG_FILTER, filter.into());
-            context.sampler_parameter_i32(
-                sampler,
-                glow::TEXTURE_MIN_FILTER,
-                filter.gl_mip(mip) as i32,
-            );
+        for (parameter, value) in SamplerSet::parameters(wrap, filter, mip) {
+            unsafe {
+                context.sampler_parameter_i32(sampler, parameter, value);
+            }
         }
     }
 
     pub fn new(context: &glow::Context) -> error::Result<SamplerSet> {
+        if !util::gl_supports_sampler_objects(context) {
+            return Ok(SamplerSet {
+                samplers: FastHashMap::default(),
+                emulated: true,
+            });
+        }
+
         let mut samplers = FastHashMap::default();
         let wrap_modes = &[
             WrapMode::ClampToBorder,
@@ -73,6 +110,9 @@ impl SamplerSet {
 
         // assert all samplers were created.
         assert_eq!(samplers.len(), wrap_modes.len() * 2 * 2);
-        Ok(SamplerSet { samplers })
+        Ok(SamplerSet {
+            samplers,
+            emulated: false,
+        })
     }
 }
@@ -2,7 +2,9 @@ use glow::HasContext;
 
 use crate::error;
 use crate::error::FilterChainError;
+use librashader_common::{FormatFallback, ImageFormat};
 use librashader_reflect::back::glsl::GlslVersion;
+use librashader_runtime::warnings::OptionWarning;
 
 pub fn gl_compile_shader(
     context: &glow::Context,
@@ -26,12 +28,141 @@ pub fn gl_compile_shader(
     }
 }
 
+/// Select the first fallback of the format under the fallback policy that is color renderable.
+///
+/// OpenGL 3.3 can not query the support of a format directly, so support is probed by
+/// attaching an image of each format to a scratch framebuffer.
+pub fn gl_select_supported_format(
+    context: &glow::Context,
+    format: ImageFormat,
+    policy: FormatFallback,
+) -> error::Result<Option<ImageFormat>> {
+    unsafe {
+        let framebuffer = context
+            .create_framebuffer()
+            .map_err(FilterChainError::GlError)?;
+        context.bind_framebuffer(glow::FRAMEBUFFER, Some(framebuffer));
+
+        let selected = format.select_supported(policy, |format| {
+            let Ok(texture) = context.create_texture() else {
+                return false;
+            };
+
+            context.bind_texture(glow::TEXTURE_2D, Some(texture));
+            context.tex_storage_2d(glow::TEXTURE_2D, 1, format.into(), 1, 1);
+            context.framebuffer_texture_2d(
+                glow::FRAMEBUFFER,
+                glow::COLOR_ATTACHMENT0,
+                glow::TEXTURE_2D,
+                Some(texture),
+                0,
+            );
+
+            let status = context.check_framebuffer_status(glow::FRAMEBUFFER);
+
+            context.framebuffer_texture_2d(
+                glow::FRAMEBUFFER,
+                glow::COLOR_ATTACHMENT0,
+                glow::TEXTURE_2D,
+                None,
+                0,
+            );
+            context.bind_texture(glow::TEXTURE_2D, None);
+            context.delete_texture(texture);
+
+            status == glow::FRAMEBUFFER_COMPLETE
+        });
+
+        context.bind_framebuffer(glow::FRAMEBUFFER, None);
+        context.delete_framebuffer(framebuffer);
+        Ok(selected)
+    }
+}
+
+/// Whether the context supports a feature that was promoted to core in the given version
+/// from the given extension.
+///
+/// The version is a desktop OpenGL version, so it is never met by an OpenGL ES context.
+fn gl_supports(context: &glow::Context, major: u32, minor: u32, extension: &str) -> bool {
+    let version = context.version();
+    (!version.is_embedded && (version.major, version.minor) >= (major, minor))
+        || context.supported_extensions().contains(extension)
+}
+
+/// Whether the context supports sampler objects. If not, sampler state is emulated
+/// with texture parameters.
+pub fn gl_supports_sampler_objects(context: &glow::Context) -> bool {
+    // sampler objects are core in OpenGL ES 3.0, the minimum supported ES version.
+    context.version().is_embedded || gl_supports(context, 3, 3, "GL_ARB_sampler_objects")
+}
+
+/// Whether `GL_FRAMEBUFFER_SRGB` can be toggled. OpenGL ES always encodes writes to
+/// sRGB framebuffers, and does not have the capability.
+pub fn gl_supports_framebuffer_srgb(context: &glow::Context) -> bool {
+    !context.version().is_embedded
+}
+
+/// Check that the context supports the features that the filter chain requires.
+///
+/// The supported configurations are
+/// * desktop OpenGL 3.0 with uniform buffer objects and immutable texture storage, which
+///   every OpenGL 4.2 context has. Sampler objects are used if available.
+///   Direct State Access requires OpenGL 4.5 or `GL_ARB_direct_state_access`.
+/// * OpenGL ES 3.0 or later without Direct State Access, which is a desktop-only feature.
+///   Shaders are compiled to GLSL ES.
+pub fn gl_check_capabilities(context: &glow::Context, use_dsa: bool) -> error::Result<()> {
+    let unsupported =
+        |required, missing| Err(FilterChainError::GlUnsupported { required, missing });
+
+    let version = context.version();
+    if version.is_embedded {
+        // uniform buffers, immutable texture storage and sampler objects are all core in ES 3.0.
+        if version.major < 3 {
+            return unsupported("OpenGL ES 3.0", "framebuffer and vertex array objects");
+        }
+        if use_dsa {
+            return unsupported("desktop OpenGL 4.5", "direct state access");
+        }
+        return Ok(());
+    }
+    if version.major < 3 {
+        return unsupported("OpenGL 3.0", "framebuffer and vertex array objects");
+    }
+    if !gl_supports(context, 3, 1, "GL_ARB_uniform_buffer_object") {
+        return unsupported(
+            "OpenGL 3.1 or GL_ARB_uniform_buffer_object",
+            "uniform buffer objects",
+        );
+    }
+    if !gl_supports(context, 4, 2, "GL_ARB_texture_storage") {
+        return unsupported(
+            "OpenGL 4.2 or GL_ARB_texture_storage",
+            "immutable texture storage",
+        );
+    }
+    if use_dsa && !gl_supports(context, 4, 5, "GL_ARB_direct_state_access") {
+        return unsupported(
+            "OpenGL 4.5 or GL_ARB_direct_state_access",
+            "direct state access",
+        );
+    }
+    Ok(())
+}
+
 pub fn gl_get_version(context: &glow::Context) -> GlslVersion {
     let version = context.version();
 
     let maj_ver = version.major;
     let min_ver = version.minor;
 
+    if version.is_embedded {
+        return match min_ver {
+            0 => GlslVersion::Glsl300Es,
+            1 => GlslVersion::Glsl310Es,
+            _ => GlslVersion::Glsl320Es,
+        };
+    }
+
     match maj_ver {
         3 => match min_ver {
             3 => GlslVersion::Glsl330,
@@ -54,20 +185,52 @@ pub fn gl_get_version(context: &glow::Context) -> GlslVersion {
     }
 }
 
+/// The GLSL versions that can be requested with the `glsl_version` option.
+const GLSL_VERSIONS: &[(u16, GlslVersion)] = &[
+    (300, GlslVersion::Glsl130),
+    (310, GlslVersion::Glsl140),
+    (320, GlslVersion::Glsl150),
+    (330, GlslVersion::Glsl330),
+    (400, GlslVersion::Glsl400),
+    (410, GlslVersion::Glsl410),
+    (420, GlslVersion::Glsl420),
+    (430, GlslVersion::Glsl430),
+    (440, GlslVersion::Glsl440),
+    (450, GlslVersion::Glsl450),
+    (460, GlslVersion::Glsl460),
+];
+
+/// Requested versions below this are below GLSL 3.30, which most shader presets require.
+const GLSL_MINIMUM_VERSION: u16 = 330;
+
+fn gl_find_version(version: u16) -> Option<GlslVersion> {
+    GLSL_VERSIONS
+        .iter()
+        .find(|(requested, _)| *requested == version)
+        .map(|(_, glsl)| *glsl)
+}
+
 pub fn gl_u16_to_version(context: &glow::Context, version: u16) -> GlslVersion {
-    match version {
-        0 => gl_get_version(context),
-        300 => GlslVersion::Glsl130,
-        310 => GlslVersion::Glsl140,
-        320 => GlslVersion::Glsl150,
-        330 => GlslVersion::Glsl330,
-        400 => GlslVersion::Glsl400,
-        410 => GlslVersion::Glsl410,
-        420 => GlslVersion::Glsl420,
-        430 => GlslVersion::Glsl430,
-        440 => GlslVersion::Glsl440,
-        450 => GlslVersion::Glsl450,
-        460 => GlslVersion::Glsl460,
-        _ => GlslVersion::Glsl150,
+    // 0 requests the version of the context. Requested versions are desktop GLSL versions,
+    // which OpenGL ES can not compile.
+    if version == 0 || context.version().is_embedded {
+        return gl_get_version(context);
+    }
+
+    gl_find_version(version).unwrap_or(GlslVersion::Glsl150)
+}
+
+/// Check a requested GLSL version as mapped by `gl_u16_to_version`.
+pub fn gl_version_warning(version: u16) -> Option<OptionWarning> {
+    if version == 0 {
+        return None;
+    }
+
+    match gl_find_version(version) {
+        None => Some(OptionWarning::UnknownGlslVersion { requested: version }),
+        Some(_) if version < GLSL_MINIMUM_VERSION => {
+            Some(OptionWarning::GlslVersionBelowMinimum { requested: version })
+        }
+        Some(_) => None,
     }
 }
@@ -18,6 +18,20 @@ fn triangle_gl() {
                 use_dsa: false,
                 force_no_mipmaps: false,
                 disable_cache: true,
+                input_bottom_left: false,
+                input_external: false,
+                max_history: 0,
+                format_fallback: 0,
+                disable_temporal: false,
+                freeze_frame_count_on_rewind: false,
+                collect_statistics: false,
+                progress: None,
+                cancel: None,
+                lut_bundle: None,
+                device_context: None,
+                color_assist: false,
+                black_frame_insertion: false,
+                instance: None,
             }),
         )
         // FilterChain::load_from_path("../test/slang-shaders/bezel/Mega_Bezel/Presets/MBZ__0__SMOOTH-ADV.slangp", None)
@@ -42,6 +56,20 @@ fn triangle_gl46() {
                 use_dsa: true,
                 force_no_mipmaps: false,
                 disable_cache: false,
+                input_bottom_left: false,
+                input_external: false,
+                max_history: 0,
+                format_fallback: 0,
+                disable_temporal: false,
+                freeze_frame_count_on_rewind: false,
+                collect_statistics: false,
+                progress: None,
+                cancel: None,
+                lut_bundle: None,
+                device_context: None,
+                color_assist: false,
+                black_frame_insertion: false,
+                instance: None,
             }),
         )
         // FilterChain::load_from_path("../test/slang-shaders/bezel/Mega_Bezel/Presets/MBZ__0__SMOOTH-ADV.slangp", None)
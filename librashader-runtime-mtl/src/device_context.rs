@@ -0,0 +1,44 @@
+use crate::error::Result;
+use crate::luts::LutBundleMetal;
+use crate::samplers::SamplerSet;
+use objc2::runtime::ProtocolObject;
+use objc2_metal::MTLDevice;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// Device-level resources that can be shared between filter chains created on the same device
+/// with the `device_context` option.
+///
+/// A device context owns the sampler states of the filter chains, and a [`LutBundleMetal`] for
+/// their LUT textures, so that frontends that run several filter chains at once do not create
+/// duplicate objects for each of them. Clones of a device context share the same resources.
+#[derive(Clone)]
+pub struct DeviceContextMetal {
+    pub(crate) samplers: Rc<SamplerSet>,
+    pub(crate) luts: LutBundleMetal,
+}
+
+impl DeviceContextMetal {
+    /// Create a device context for the given device.
+    pub fn new(device: &ProtocolObject<dyn MTLDevice>) -> Result<Self> {
+        Ok(Self {
+            samplers: Rc::new(SamplerSet::new(device)?),
+            luts: LutBundleMetal::new(),
+        })
+    }
+
+    /// The bundle of LUT textures shared by filter chains created with this device context.
+    ///
+    /// Textures can be preloaded into the bundle before any filter chain is created.
+    pub fn lut_bundle(&self) -> &LutBundleMetal {
+        &self.luts
+    }
+}
+
+impl Debug for DeviceContextMetal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeviceContextMetal")
+            .field("luts", &self.luts)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,4 +1,5 @@
 use array_concat::concat_arrays;
+use librashader_runtime::mesh::OutputMesh;
 use librashader_runtime::quad::{QuadType, VertexInput};
 use objc2::rc::Retained;
 use objc2::runtime::ProtocolObject;
@@ -53,31 +54,67 @@ const VBO_DATA: [VertexInput; 8] = concat_arrays!(OFFSCREEN_VBO_DATA, FINAL_VBO_
 
 pub struct DrawQuad {
     buffer: Retained<ProtocolObject<dyn MTLBuffer>>,
+    mesh: Option<(Retained<ProtocolObject<dyn MTLBuffer>>, usize)>,
+}
+
+fn new_vertex_buffer(
+    device: &ProtocolObject<dyn MTLDevice>,
+    vbo_data: &[u8],
+) -> Result<Retained<ProtocolObject<dyn MTLBuffer>>> {
+    unsafe {
+        device
+            .newBufferWithBytes_length_options(
+                // SAFETY: this pointer is const.
+                // https://developer.apple.com/documentation/metal/mtldevice/1433429-newbufferwithbytes
+                NonNull::new_unchecked(vbo_data.as_ptr() as *mut c_void),
+                vbo_data.len(),
+                if cfg!(target_os = "ios") {
+                    MTLResourceOptions::MTLResourceStorageModeShared
+                } else {
+                    MTLResourceOptions::MTLResourceStorageModeManaged
+                },
+            )
+            .ok_or(FilterChainError::BufferError)
+    }
 }
 
 impl DrawQuad {
     pub fn new(device: &ProtocolObject<dyn MTLDevice>) -> Result<DrawQuad> {
-        let vbo_data: &'static [u8] = bytemuck::cast_slice(&VBO_DATA);
-        let buffer = unsafe {
-            device
-                .newBufferWithBytes_length_options(
-                    // SAFETY: this pointer is const.
-                    // https://developer.apple.com/documentation/metal/mtldevice/1433429-newbufferwithbytes
-                    NonNull::new_unchecked(vbo_data.as_ptr() as *mut c_void),
-                    vbo_data.len(),
-                    if cfg!(target_os = "ios") {
-                        MTLResourceOptions::MTLResourceStorageModeShared
-                    } else {
-                        MTLResourceOptions::MTLResourceStorageModeManaged
-                    },
-                )
-                .ok_or(FilterChainError::BufferError)?
+        let buffer = new_vertex_buffer(device, bytemuck::cast_slice(&VBO_DATA))?;
+        Ok(DrawQuad { buffer, mesh: None })
+    }
+
+    /// Set the mesh to draw the final pass with, or reset it to the default quad.
+    pub fn set_mesh(
+        &mut self,
+        device: &ProtocolObject<dyn MTLDevice>,
+        mesh: Option<&OutputMesh>,
+    ) -> Result<()> {
+        let Some(mesh) = mesh else {
+            self.mesh = None;
+            return Ok(());
         };
 
-        Ok(DrawQuad { buffer })
+        // the clip space of Metal points up.
+        let vertices = mesh.flipped_vertices(false);
+        let buffer = new_vertex_buffer(device, bytemuck::cast_slice(&vertices))?;
+        self.mesh = Some((buffer, vertices.len()));
+        Ok(())
     }
 
     pub fn draw_quad(&self, cmd: &ProtocolObject<dyn MTLRenderCommandEncoder>, vbo: QuadType) {
+        if let (QuadType::Final, Some((mesh, count))) = (&vbo, &self.mesh) {
+            unsafe {
+                cmd.setVertexBuffer_offset_atIndex(Some(mesh), 0, VERTEX_BUFFER_INDEX);
+                cmd.drawPrimitives_vertexStart_vertexCount(
+                    MTLPrimitiveType::TriangleStrip,
+                    0,
+                    *count,
+                );
+            }
+            return;
+        }
+
         // TODO: need to see how naga outputs MSL
         let offset = match vbo {
             QuadType::Offscreen => 0,
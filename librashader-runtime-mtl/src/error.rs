@@ -36,6 +36,10 @@ pub enum FilterChainError {
     FailedToCreateTexture,
     #[error("couldn't create command buffer")]
     FailedToCreateCommandBuffer,
+    #[error("filter chain creation was cancelled")]
+    Cancelled,
+    #[error("could not spawn the worker thread to create the filter chain on")]
+    WorkerThread(#[source] std::io::Error),
     #[error("unreachable")]
     Infallible(#[from] std::convert::Infallible),
 }
@@ -4,12 +4,13 @@ use crate::error;
 use crate::error::FilterChainError;
 use crate::filter_pass::FilterPass;
 use crate::graphics_pipeline::MetalGraphicsPipeline;
-use crate::luts::LutTexture;
+use crate::luts::{self, LutTexture};
 use crate::options::{FilterChainOptionsMetal, FrameOptionsMetal};
 use crate::samplers::SamplerSet;
 use crate::texture::{get_texture_size, InputTexture, MetalTextureRef, OwnedTexture};
+use crate::DeviceContextMetal;
 use librashader_common::map::FastHashMap;
-use librashader_common::{ImageFormat, Size, Viewport};
+use librashader_common::{ImageFormat, ScalingKernel, Size, Viewport};
 use librashader_presets::context::VideoDriver;
 use librashader_presets::{ShaderFeatures, ShaderPreset};
 use librashader_reflect::back::msl::MslVersion;
@@ -20,13 +21,24 @@ use librashader_reflect::reflect::cross::SpirvCross;
 use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
 use librashader_reflect::reflect::semantics::ShaderSemantics;
 use librashader_reflect::reflect::ReflectShader;
-use librashader_runtime::binding::BindingUtil;
-use librashader_runtime::framebuffer::FramebufferInit;
-use librashader_runtime::image::{ImageError, LoadedTexture, UVDirection, BGRA8};
+use librashader_runtime::binding::{BindingUtil, UniformBindings};
+use librashader_runtime::filter_pass::{
+    requested_format, FilterPassMeta, IdentityPasses, PassInfo,
+};
+use librashader_runtime::framebuffer::{FramebufferInit, HistoryDepth};
+use librashader_runtime::instance::LibrashaderInstance;
+use librashader_runtime::jitter;
+use librashader_runtime::mesh::OutputMesh;
+use librashader_runtime::mvp;
+use librashader_runtime::pending::LocalPendingFilterChain;
+use librashader_runtime::progress::{CancellationToken, LoadStage, PassProgress, ProgressCallback};
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
+use librashader_runtime::rewind::RewindFrameCount;
 use librashader_runtime::scaling::ScaleFramebuffer;
+use librashader_runtime::statistics::{FrameStatistics, FrameStatisticsRecorder};
 use librashader_runtime::uniforms::UniformStorage;
+use librashader_runtime::warnings::OptionWarning;
 use objc2::rc::Id;
 use objc2::runtime::ProtocolObject;
 use objc2_foundation::NSString;
@@ -34,10 +46,11 @@ use objc2_metal::{
     MTLCommandBuffer, MTLCommandEncoder, MTLCommandQueue, MTLDevice, MTLLoadAction, MTLPixelFormat,
     MTLRenderPassDescriptor, MTLResource, MTLStoreAction, MTLTexture,
 };
-use rayon::prelude::*;
 use std::collections::VecDeque;
 use std::fmt::{Debug, Formatter};
 use std::path::Path;
+use std::rc::Rc;
+use std::sync::Arc;
 
 mod compile {
     use super::*;
@@ -65,16 +78,62 @@ mod compile {
 }
 
 use compile::{compile_passes, ShaderPassMeta};
-use librashader_pack::{ShaderPresetPack, TextureResource};
+use librashader_pack::{PassResource, ShaderPresetPack};
 use librashader_runtime::parameters::RuntimeParameters;
 
+/// A shader preset with its passes compiled, to create a filter chain from on the thread that
+/// uses the command queue.
+struct CompiledPreset {
+    preset: ShaderPresetPack,
+    passes: Vec<ShaderPassMeta>,
+    semantics: ShaderSemantics,
+}
+
+impl CompiledPreset {
+    /// Append the requested built-in passes to the preset, and compile its passes.
+    ///
+    /// This does not use the device, so it can be done on any thread.
+    fn compile(
+        mut preset: ShaderPresetPack,
+        color_assist: bool,
+        black_frame_insertion: bool,
+        progress: Option<&ProgressCallback>,
+        cancel: Option<&CancellationToken>,
+    ) -> error::Result<Self> {
+        if color_assist {
+            preset.append_color_assist();
+        }
+        if black_frame_insertion {
+            preset.append_black_frame_insertion();
+        }
+
+        let progress = PassProgress::new(progress, preset.passes.len()).with_cancellation(cancel);
+
+        let shaders = std::mem::take(&mut preset.passes);
+        let (passes, semantics) = compile_passes(shaders, &preset.textures)?;
+        progress.report_all(LoadStage::Preprocessing);
+        if progress.is_cancelled() {
+            return Err(FilterChainError::Cancelled);
+        }
+
+        Ok(CompiledPreset {
+            preset,
+            passes,
+            semantics,
+        })
+    }
+}
+
 /// A Metal filter chain.
 pub struct FilterChainMetal {
     pub(crate) common: FilterCommon,
     passes: Box<[FilterPass]>,
+    identity: IdentityPasses<FilterPass>,
     output_framebuffers: Box<[OwnedTexture]>,
     feedback_framebuffers: Box<[OwnedTexture]>,
     history_framebuffers: VecDeque<OwnedTexture>,
+    history_depth: HistoryDepth,
+    option_warnings: Box<[OptionWarning]>,
     /// Metal does not allow us to push the input texture to history
     /// before recording framebuffers, so we double-buffer it.
     ///
@@ -86,6 +145,9 @@ pub struct FilterChainMetal {
     disable_mipmaps: bool,
     default_options: FrameOptionsMetal,
     draw_last_pass_feedback: bool,
+    disable_temporal: bool,
+    rewind: RewindFrameCount,
+    invalidated_history: usize,
 }
 
 impl Debug for FilterChainMetal {
@@ -98,14 +160,26 @@ pub(crate) struct FilterCommon {
     pub output_textures: Box<[Option<InputTexture>]>,
     pub feedback_textures: Box<[Option<InputTexture>]>,
     pub history_textures: Box<[Option<InputTexture>]>,
-    pub luts: FastHashMap<usize, LutTexture>,
-    pub samplers: SamplerSet,
+    pub luts: FastHashMap<usize, Arc<LutTexture>>,
+    pub samplers: Rc<SamplerSet>,
     pub config: RuntimeParameters,
     pub(crate) draw_quad: DrawQuad,
     device: Id<ProtocolObject<dyn MTLDevice>>,
+    pub(crate) frame_jitter: [f32; 2],
+    pub statistics: FrameStatisticsRecorder,
 }
 
 impl FilterChainMetal {
+    /// Create a filter chain without any shader passes, that only scales the input to the viewport.
+    ///
+    /// This is equivalent to loading a preset without any shader passes.
+    pub fn new_identity(
+        queue: &ProtocolObject<dyn MTLCommandQueue>,
+        options: Option<&FilterChainOptionsMetal>,
+    ) -> error::Result<FilterChainMetal> {
+        Self::load_from_pack(ShaderPresetPack::empty(), queue, options)
+    }
+
     /// Load the shader preset at the given path into a filter chain.
     pub fn load_from_path(
         path: impl AsRef<Path>,
@@ -129,18 +203,77 @@ impl FilterChainMetal {
         Self::load_from_pack(preset, queue, options)
     }
 
+    /// Load a filter chain from a pre-parsed `ShaderPreset`, compiling its shaders on a worker
+    /// thread.
+    ///
+    /// Shaders are compiled without blocking the calling thread. The rest of the filter chain is
+    /// created on the thread that takes it from the returned [`LocalPendingFilterChain`]. The
+    /// progress callback of the options is called on the worker thread while shaders are
+    /// compiled, and on the taking thread afterwards.
+    ///
+    /// Returns an error if the worker thread could not be spawned.
+    pub fn load_from_preset_async(
+        preset: ShaderPreset,
+        queue: &Id<ProtocolObject<dyn MTLCommandQueue>>,
+        options: Option<FilterChainOptionsMetal>,
+    ) -> error::Result<LocalPendingFilterChain<error::Result<FilterChainMetal>>> {
+        let color_assist = options.as_ref().map_or(false, |o| o.color_assist);
+        let black_frame_insertion = options.as_ref().map_or(false, |o| o.black_frame_insertion);
+        let progress = options.as_ref().and_then(|o| o.progress.clone());
+        let cancel = options.as_ref().and_then(|o| o.cancel.clone());
+        let instance = options.as_ref().and_then(|o| o.instance.clone());
+        let queue = Id::clone(queue);
+
+        LocalPendingFilterChain::spawn(
+            move || {
+                let _instance = instance.as_ref().map(LibrashaderInstance::enter);
+                let preset = ShaderPresetPack::load_from_preset::<FilterChainError>(preset)?;
+                CompiledPreset::compile(
+                    preset,
+                    color_assist,
+                    black_frame_insertion,
+                    progress.as_ref(),
+                    cancel.as_ref(),
+                )
+            },
+            move |compiled: error::Result<CompiledPreset>| {
+                Self::load_from_compiled(compiled?, &queue, options.as_ref())
+            },
+        )
+        .map_err(FilterChainError::WorkerThread)
+    }
+
     /// Load a filter chain from a pre-parsed `ShaderPreset`.
     pub fn load_from_pack(
         preset: ShaderPresetPack,
         queue: &ProtocolObject<dyn MTLCommandQueue>,
         options: Option<&FilterChainOptionsMetal>,
+    ) -> error::Result<FilterChainMetal> {
+        let _instance = options
+            .and_then(|o| o.instance.as_ref())
+            .map(LibrashaderInstance::enter);
+        let compiled = CompiledPreset::compile(
+            preset,
+            options.map_or(false, |o| o.color_assist),
+            options.map_or(false, |o| o.black_frame_insertion),
+            options.and_then(|o| o.progress.as_ref()),
+            options.and_then(|o| o.cancel.as_ref()),
+        )?;
+        Self::load_from_compiled(compiled, queue, options)
+    }
+
+    /// Load a filter chain from a compiled `ShaderPresetPack`.
+    fn load_from_compiled(
+        compiled: CompiledPreset,
+        queue: &ProtocolObject<dyn MTLCommandQueue>,
+        options: Option<&FilterChainOptionsMetal>,
     ) -> error::Result<FilterChainMetal> {
         let cmd = queue
             .commandBuffer()
             .ok_or(FilterChainError::FailedToCreateCommandBuffer)?;
 
         let filter_chain =
-            Self::load_from_pack_deferred_internal(preset, queue.device(), &cmd, options)?;
+            Self::load_from_compiled_deferred(compiled, queue.device(), &cmd, options)?;
 
         cmd.commit();
         unsafe { cmd.waitUntilCompleted() };
@@ -148,42 +281,46 @@ impl FilterChainMetal {
         Ok(filter_chain)
     }
 
-    fn load_luts(
-        device: &ProtocolObject<dyn MTLDevice>,
-        cmd: &ProtocolObject<dyn MTLCommandBuffer>,
-        textures: Vec<TextureResource>,
-    ) -> error::Result<FastHashMap<usize, LutTexture>> {
-        let mut luts = FastHashMap::default();
-
-        let mipmapper = cmd
-            .blitCommandEncoder()
-            .ok_or(FilterChainError::FailedToCreateCommandBuffer)?;
-
-        let textures = textures
-            .into_par_iter()
-            .map(|texture| LoadedTexture::<BGRA8>::from_texture(texture, UVDirection::TopLeft))
-            .collect::<Result<Vec<LoadedTexture<BGRA8>>, ImageError>>()?;
-        for (index, LoadedTexture { meta, image }) in textures.into_iter().enumerate() {
-            let texture = LutTexture::new(device, image, &meta, &mipmapper)?;
-            luts.insert(index, texture);
-        }
+    /// Create the identity pass that scales its input to the viewport with the given kernel.
+    fn init_identity(
+        device: &Id<ProtocolObject<dyn MTLDevice>>,
+        parameters: &RuntimeParameters,
+        kernel: ScalingKernel,
+    ) -> error::Result<FilterPass> {
+        let (passes, semantics) = compile_passes(vec![PassResource::identity(kernel)], &[])?;
 
-        mipmapper.endEncoding();
-        Ok(luts)
+        let pass = Self::init_passes(
+            device,
+            passes,
+            &semantics,
+            parameters,
+            PassProgress::default(),
+        )?
+        .into_vec()
+        .remove(0);
+
+        Ok(pass)
     }
 
     fn init_passes(
         device: &Id<ProtocolObject<dyn MTLDevice>>,
         passes: Vec<ShaderPassMeta>,
         semantics: &ShaderSemantics,
+        parameters: &RuntimeParameters,
+        progress: PassProgress,
     ) -> error::Result<Box<[FilterPass]>> {
         // todo: fix this to allow send
         let filters: Vec<error::Result<FilterPass>> = passes
             .into_iter()
             .enumerate()
             .map(|(index, (config, mut reflect))| {
+                if progress.is_cancelled() {
+                    return Err(FilterChainError::Cancelled);
+                }
+
                 let reflection = reflect.reflect(index, semantics)?;
                 let msl = reflect.compile(Some(MslVersion::new(2, 0, 0)))?;
+                progress.report(LoadStage::Compiling, index);
 
                 let ubo_size = reflection.ubo.as_ref().map_or(0, |ubo| ubo.size as usize);
                 let push_size = reflection
@@ -196,24 +333,19 @@ impl FilterChainMetal {
                     MetalBuffer::new(&device, push_size as usize, "pcb")?,
                 );
 
-                let uniform_bindings = reflection.meta.create_binding_map(|param| param.offset());
+                let uniform_bindings = UniformBindings::new(
+                    reflection.meta.create_binding_map(|param| param.offset()),
+                    parameters,
+                );
 
+                // every framebuffer format can be rendered to on the GPU families
+                // supported by Metal 2, so there is no need to fall back.
                 let render_pass_format: MTLPixelFormat =
-                    if let Some(format) = config.meta.get_format_override() {
-                        format.into()
-                    } else {
-                        config.data.format.into()
-                    };
-
-                let graphics_pipeline = MetalGraphicsPipeline::new(
-                    &device,
-                    &msl,
-                    if render_pass_format == MTLPixelFormat(0) {
-                        MTLPixelFormat::RGBA8Unorm
-                    } else {
-                        render_pass_format
-                    },
-                )?;
+                    requested_format(&config.meta, config.data.format).into();
+
+                let graphics_pipeline =
+                    MetalGraphicsPipeline::new(&device, &msl, render_pass_format)?;
+                progress.report(LoadStage::Pipeline, index);
 
                 Ok(FilterPass {
                     reflection,
@@ -221,6 +353,7 @@ impl FilterChainMetal {
                     uniform_bindings,
                     source: config.data,
                     meta: config.meta,
+                    path: config.path,
                     graphics_pipeline,
                 })
             })
@@ -231,6 +364,29 @@ impl FilterChainMetal {
         Ok(filters.into_boxed_slice())
     }
 
+    /// Clear the framebuffers to black, with a render pass per framebuffer.
+    fn clear_framebuffers<'a>(
+        cmd: &ProtocolObject<dyn MTLCommandBuffer>,
+        framebuffers: impl Iterator<Item = &'a OwnedTexture>,
+    ) -> error::Result<()> {
+        for framebuffer in framebuffers {
+            let clear_desc = unsafe { MTLRenderPassDescriptor::new() };
+            unsafe {
+                let ca = clear_desc.colorAttachments().objectAtIndexedSubscript(0);
+                ca.setTexture(Some(&framebuffer.texture));
+                ca.setLoadAction(MTLLoadAction::Clear);
+                ca.setStoreAction(MTLStoreAction::Store);
+            }
+
+            let clearpass = cmd
+                .renderCommandEncoderWithDescriptor(&clear_desc)
+                .ok_or(FilterChainError::FailedToCreateCommandBuffer)?;
+            clearpass.endEncoding();
+        }
+
+        Ok(())
+    }
+
     fn push_history(
         &mut self,
         cmd: &ProtocolObject<dyn MTLCommandBuffer>,
@@ -301,7 +457,17 @@ impl FilterChainMetal {
         cmd: &ProtocolObject<dyn MTLCommandBuffer>,
         options: Option<&FilterChainOptionsMetal>,
     ) -> error::Result<FilterChainMetal> {
-        Self::load_from_pack_deferred_internal(preset, queue.device(), &cmd, options)
+        let _instance = options
+            .and_then(|o| o.instance.as_ref())
+            .map(LibrashaderInstance::enter);
+        let compiled = CompiledPreset::compile(
+            preset,
+            options.map_or(false, |o| o.color_assist),
+            options.map_or(false, |o| o.black_frame_insertion),
+            options.and_then(|o| o.progress.as_ref()),
+            options.and_then(|o| o.cancel.as_ref()),
+        )?;
+        Self::load_from_compiled_deferred(compiled, queue.device(), &cmd, options)
     }
 
     /// Load a filter chain from a pre-parsed `ShaderPreset`, deferring and GPU-side initialization
@@ -311,18 +477,47 @@ impl FilterChainMetal {
     /// The provided command buffer must be ready for recording.
     /// The caller is responsible for ending the command buffer and immediately submitting it to a
     /// graphics queue. The command buffer must be completely executed before calling [`frame`](Self::frame).
-    fn load_from_pack_deferred_internal(
-        preset: ShaderPresetPack,
+    fn load_from_compiled_deferred(
+        compiled: CompiledPreset,
         device: Id<ProtocolObject<dyn MTLDevice>>,
         cmd: &ProtocolObject<dyn MTLCommandBuffer>,
         options: Option<&FilterChainOptionsMetal>,
     ) -> error::Result<FilterChainMetal> {
-        let (passes, semantics) = compile_passes(preset.passes, &preset.textures)?;
+        let CompiledPreset {
+            preset,
+            passes,
+            semantics,
+        } = compiled;
+        let _instance = options
+            .and_then(|o| o.instance.as_ref())
+            .map(LibrashaderInstance::enter);
+
+        let progress = PassProgress::new(options.and_then(|o| o.progress.as_ref()), passes.len())
+            .with_cancellation(options.and_then(|o| o.cancel.as_ref()));
+
+        let parameters = RuntimeParameters::new(
+            preset.pass_count as usize,
+            preset.parameters,
+            passes
+                .iter()
+                .flat_map(|(pass, _)| pass.data.parameters.values()),
+        );
 
-        let filters = Self::init_passes(&device, passes, &semantics)?;
+        let filters = Self::init_passes(&device, passes, &semantics, &parameters, progress)?;
 
-        let samplers = SamplerSet::new(&device)?;
-        let luts = FilterChainMetal::load_luts(&device, &cmd, preset.textures)?;
+        let device_context = options.and_then(|o| o.device_context.as_ref());
+        let samplers = match device_context {
+            Some(context) => Rc::clone(&context.samplers),
+            None => Rc::new(SamplerSet::new(&device)?),
+        };
+        let luts = luts::load_luts(
+            &device,
+            &cmd,
+            preset.textures,
+            options
+                .and_then(|o| o.lut_bundle.as_ref())
+                .or(device_context.map(DeviceContextMetal::lut_bundle)),
+        )?;
         let framebuffer_gen = || {
             Ok::<_, error::FilterChainError>(OwnedTexture::new(
                 &device,
@@ -336,7 +531,15 @@ impl FilterChainMetal {
             filters.iter().map(|f| &f.reflection.meta),
             &framebuffer_gen,
             &input_gen,
-        );
+        )
+        .with_max_history(options.map_or(0, |o| o.max_history as usize))
+        .with_temporal_disabled(options.is_some_and(|o| o.disable_temporal));
+        let history_depth = framebuffer_init.history_depth();
+        let mut option_warnings = framebuffer_init.option_warnings();
+        option_warnings.extend(OptionWarning::check_mipmap_input(
+            options.map(|f| f.force_no_mipmaps).unwrap_or(false),
+            filters.iter().map(|f| f.meta.mipmap_input),
+        ));
         let (output_framebuffers, output_textures) = framebuffer_init.init_output_framebuffers()?;
         //
         // initialize feedback framebuffers
@@ -351,26 +554,74 @@ impl FilterChainMetal {
         let draw_quad = DrawQuad::new(&device)?;
         Ok(FilterChainMetal {
             draw_last_pass_feedback: framebuffer_init.uses_final_pass_as_feedback(),
+            disable_temporal: options.is_some_and(|o| o.disable_temporal),
+            rewind: RewindFrameCount::new(options.is_some_and(|o| o.freeze_frame_count_on_rewind)),
+            invalidated_history: 0,
             common: FilterCommon {
+                statistics: FrameStatisticsRecorder::new(
+                    options.is_some_and(|o| o.collect_statistics),
+                ),
                 luts,
                 samplers,
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                config: parameters,
                 draw_quad,
                 device,
                 output_textures,
                 feedback_textures,
                 history_textures,
+                frame_jitter: [0.0, 0.0],
             },
             passes: filters,
+            identity: IdentityPasses::default(),
             output_framebuffers,
             feedback_framebuffers,
             history_framebuffers,
+            history_depth,
+            option_warnings: option_warnings.into_boxed_slice(),
             prev_frame_history_buffer: history_buffer,
             disable_mipmaps: options.map(|f| f.force_no_mipmaps).unwrap_or(false),
             default_options: Default::default(),
         })
     }
 
+    /// Get the depth of the history kept by the filter chain, and the depth
+    /// requested by the shader passes.
+    pub fn history_depth(&self) -> HistoryDepth {
+        self.history_depth
+    }
+
+    /// Get the warnings about options of the filter chain that could not be honoured as
+    /// given, such as mipmaps being forced off for shader passes that request them.
+    pub fn option_warnings(&self) -> &[OptionWarning] {
+        &self.option_warnings
+    }
+
+    /// Invalidate the last `frames` frames of history, such as after a rollback in runahead
+    /// or netplay, so that shader passes do not blend frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+    /// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+    pub fn invalidate_history(&mut self, frames: usize) {
+        self.invalidated_history = std::cmp::max(self.invalidated_history, frames);
+    }
+
+    /// Get information about the shader pass at the given index, including the
+    /// framebuffer format chosen for the pass.
+    pub fn pass_info(&self, index: usize) -> Option<PassInfo> {
+        self.passes.get(index).map(FilterPassMeta::pass_info)
+    }
+
+    /// Get the statistics of the commands recorded for the last frame, or `None` if the
+    /// filter chain was not created with the `collect_statistics` option.
+    pub fn frame_statistics(&self) -> Option<FrameStatistics> {
+        self.common.statistics.statistics()
+    }
+
+    /// Set the mesh to draw the final pass with, or reset it to the default quad if `None`.
+    pub fn set_output_mesh(&mut self, mesh: Option<&OutputMesh>) -> error::Result<()> {
+        self.common.draw_quad.set_mesh(&self.common.device, mesh)
+    }
+
     /// Records shader rendering commands to the provided command encoder.
     ///
     /// SAFETY: The `MTLCommandBuffer` provided must not have an active encoder.
@@ -382,6 +633,35 @@ impl FilterChainMetal {
         frame_count: usize,
         options: Option<&FrameOptionsMetal>,
     ) -> error::Result<()> {
+        self.frame_broadcast(
+            input,
+            std::slice::from_ref(viewport),
+            cmd,
+            frame_count,
+            options,
+        )
+    }
+
+    /// Records shader rendering commands to the provided command encoder, drawing the final pass
+    /// to every viewport.
+    ///
+    /// Intermediate passes are scaled relative to the first viewport and are only run once.
+    /// If `viewports` is empty, nothing is recorded.
+    ///
+    /// SAFETY: The `MTLCommandBuffer` provided must not have an active encoder.
+    pub fn frame_broadcast(
+        &mut self,
+        input: &ProtocolObject<dyn MTLTexture>,
+        viewports: &[Viewport<MetalTextureRef>],
+        cmd: &ProtocolObject<dyn MTLCommandBuffer>,
+        frame_count: usize,
+        options: Option<&FrameOptionsMetal>,
+    ) -> error::Result<()> {
+        // passes are scaled relative to the first viewport.
+        let Some(viewport) = viewports.first() else {
+            return Ok(());
+        };
+
         let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
         if let Some(options) = &options {
             let clear_desc = unsafe { MTLRenderPassDescriptor::new() };
@@ -404,13 +684,50 @@ impl FilterChainMetal {
             }
         }
 
-        self.push_history(&cmd, &input)?;
-
-        let passes = &mut self.passes[0..max];
-        if passes.is_empty() {
-            return Ok(());
+        if self.disable_temporal {
+            // with history and feedback disabled, the history is kept black.
+            Self::clear_framebuffers(cmd, self.history_framebuffers.iter())?;
+        } else {
+            self.push_history(&cmd, &input)?;
         }
 
+        // clear the history frames invalidated since the last frame.
+        let invalidated_history = std::mem::take(&mut self.invalidated_history);
+        Self::clear_framebuffers(
+            cmd,
+            self.history_framebuffers.iter().take(invalidated_history),
+        )?;
+
+        let options = options.unwrap_or(&self.default_options);
+        self.common.statistics.reset();
+        let frame_count = self
+            .rewind
+            .frame_count(frame_count, options.frame_direction);
+        self.common.frame_jitter = jitter::resolve(
+            options.jitter_sequence.into(),
+            options.jitter_offset,
+            frame_count,
+        );
+
+        // the identity pass draws to the viewport instead of the final pass if there are no
+        // enabled passes, or if the output of the final pass is scaled to the viewport.
+        let identity = if max == 0 || options.scale_final_pass {
+            Some(
+                self.identity
+                    .get_or_try_init(options.identity_scaling, |kernel| {
+                        Self::init_identity(&self.common.device, &self.common.config, kernel)
+                    })?,
+            )
+        } else {
+            None
+        };
+
+        let scale_final_pass = max > 0 && identity.is_some();
+        let (passes, final_scaling) = match identity {
+            Some(identity) if max == 0 => (std::slice::from_mut(identity), None),
+            identity => (&mut self.passes[0..max], identity),
+        };
+
         let filter = passes[0].meta.filter;
         let wrap_mode = passes[0].meta.wrap_mode;
 
@@ -440,10 +757,12 @@ impl FilterChainMetal {
             .setLabel(Some(&*NSString::from_str("librashader_sourcetex")));
 
         // swap output and feedback **before** recording command buffers
-        std::mem::swap(
-            &mut self.output_framebuffers,
-            &mut self.feedback_framebuffers,
-        );
+        if !self.disable_temporal {
+            std::mem::swap(
+                &mut self.output_framebuffers,
+                &mut self.feedback_framebuffers,
+            );
+        }
 
         // rescale render buffers to ensure all bindings are valid.
         OwnedTexture::scale_framebuffers_with_context(
@@ -467,9 +786,17 @@ impl FilterChainMetal {
             }),
         )?;
 
+        // feedback is kept black, including after the feedback framebuffers are resized.
+        // the feedback of the previous frame is also cleared if it was invalidated.
+        if self.disable_temporal || invalidated_history > 0 {
+            Self::clear_framebuffers(cmd, self.feedback_framebuffers.iter())?;
+        }
+
         let passes_len = passes.len();
-        let (pass, last) = passes.split_at_mut(passes_len - 1);
-        let options = options.unwrap_or(&self.default_options);
+        let (pass, last) = match final_scaling {
+            Some(identity) => (passes, std::slice::from_mut(identity)),
+            None => passes.split_at_mut(passes_len - 1),
+        };
 
         for (index, pass) in pass.iter_mut().enumerate() {
             let target = &self.output_framebuffers[index];
@@ -507,13 +834,15 @@ impl FilterChainMetal {
         assert_eq!(last.len(), 1);
 
         if let Some(pass) = last.iter_mut().next() {
-            if !pass
-                .graphics_pipeline
-                .has_format(viewport.output.pixelFormat())
-            {
-                // need to recompile
-                pass.graphics_pipeline
-                    .recompile(&self.common.device, viewport.output.pixelFormat())?;
+            for viewport in viewports {
+                if !pass
+                    .graphics_pipeline
+                    .has_format(viewport.output.pixelFormat())
+                {
+                    // need to recompile
+                    pass.graphics_pipeline
+                        .recompile(&self.common.device, viewport.output.pixelFormat())?;
+                }
             }
 
             source.filter_mode = pass.meta.filter;
@@ -521,9 +850,13 @@ impl FilterChainMetal {
             source.mip_filter = pass.meta.filter;
             let index = passes_len - 1;
 
-            if self.draw_last_pass_feedback {
+            // the output of the final pass is already drawn if it is scaled to the viewport.
+            if self.draw_last_pass_feedback && !scale_final_pass {
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
                 let output_image = &self.output_framebuffers[index].texture;
-                let out = RenderTarget::viewport_with_output(output_image.as_ref(), viewport);
+                let out =
+                    RenderTarget::viewport_with_output(output_image.as_ref(), viewport, &final_mvp);
                 pass.draw(
                     &cmd,
                     passes_len - 1,
@@ -538,19 +871,23 @@ impl FilterChainMetal {
                 )?;
             }
 
-            let out = RenderTarget::viewport(viewport);
-            pass.draw(
-                &cmd,
-                index,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                &out,
-                QuadType::Final,
-            )?;
+            for viewport in viewports {
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
+                let out = RenderTarget::viewport(viewport, &final_mvp);
+                pass.draw(
+                    &cmd,
+                    index,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    &out,
+                    QuadType::Final,
+                )?;
+            }
         }
 
         Ok(())
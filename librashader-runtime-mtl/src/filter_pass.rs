@@ -5,19 +5,21 @@ use crate::graphics_pipeline::MetalGraphicsPipeline;
 use crate::options::FrameOptionsMetal;
 use crate::samplers::SamplerSet;
 use crate::texture::{get_texture_size, InputTexture};
-use librashader_common::map::FastHashMap;
 use librashader_common::{ImageFormat, Size, Viewport};
 use librashader_preprocess::ShaderSource;
 use librashader_presets::PassMeta;
-use librashader_reflect::reflect::semantics::{MemberOffset, TextureBinding, UniformBinding};
+use librashader_reflect::reflect::semantics::{MemberOffset, TextureBinding};
 use librashader_reflect::reflect::ShaderReflection;
-use librashader_runtime::binding::{BindSemantics, TextureInput, UniformInputs};
+use librashader_runtime::binding::{
+    BindSemantics, TextureInput, UniformBindings, UniformInputs, DEFAULT_SCENE_LUMINANCE,
+};
 use librashader_runtime::filter_pass::FilterPassMeta;
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
 use librashader_runtime::uniforms::{NoUniformBinder, UniformStorage};
 use objc2::runtime::ProtocolObject;
 use objc2_metal::{MTLCommandBuffer, MTLCommandEncoder, MTLRenderCommandEncoder, MTLTexture};
+use std::path::{Path, PathBuf};
 
 impl TextureInput for InputTexture {
     fn size(&self) -> Size<u32> {
@@ -53,9 +55,10 @@ pub struct FilterPass {
     pub reflection: ShaderReflection,
     pub(crate) uniform_storage:
         UniformStorage<NoUniformBinder, Option<()>, MetalBuffer, MetalBuffer>,
-    pub uniform_bindings: FastHashMap<UniformBinding, MemberOffset>,
+    pub uniform_bindings: UniformBindings<MemberOffset>,
     pub source: ShaderSource,
     pub meta: PassMeta,
+    pub path: Option<PathBuf>,
     pub graphics_pipeline: MetalGraphicsPipeline,
 }
 
@@ -73,6 +76,8 @@ impl FilterPass {
         output: &RenderTarget<ProtocolObject<dyn MTLTexture>>,
         vbo_type: QuadType,
     ) -> error::Result<()> {
+        parent.statistics.record_pass(&self.reflection);
+
         let cmd = self.graphics_pipeline.begin_rendering(output, &cmd)?;
 
         self.build_semantics(
@@ -154,6 +159,8 @@ impl FilterPass {
                 frametime_delta: options.frametime_delta,
                 framebuffer_size: fb_size,
                 viewport_size,
+                scene_luminance: DEFAULT_SCENE_LUMINANCE,
+                frame_jitter: parent.frame_jitter,
             },
             original,
             source,
@@ -165,7 +172,6 @@ impl FilterPass {
             parent.feedback_textures.iter().map(|o| o.as_ref()),
             parent.history_textures.iter().map(|o| o.as_ref()),
             parent.luts.iter().map(|(u, i)| (*u, i.as_ref())),
-            &self.source.parameters,
             &parent.config,
         );
 
@@ -183,4 +189,8 @@ impl FilterPassMeta for FilterPass {
     fn meta(&self) -> &PassMeta {
         &self.meta
     }
+
+    fn source_path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
 }
@@ -7,6 +7,7 @@
 #![cfg_attr(not(feature = "stable"), feature(type_alias_impl_trait))]
 
 mod buffer;
+mod device_context;
 mod draw_quad;
 mod filter_chain;
 mod filter_pass;
@@ -15,7 +16,9 @@ mod luts;
 mod samplers;
 mod texture;
 
+pub use device_context::DeviceContextMetal;
 pub use filter_chain::FilterChainMetal;
+pub use luts::LutBundleMetal;
 use objc2_metal::MTLPixelFormat;
 
 pub mod error;
@@ -1,15 +1,21 @@
 use crate::error::{FilterChainError, Result};
 use crate::texture::InputTexture;
+use librashader_common::map::FastHashMap;
+use librashader_pack::TextureResource;
 use librashader_presets::TextureMeta;
-use librashader_runtime::image::{Image, BGRA8};
+use librashader_runtime::image::{Image, ImageError, LoadedTexture, UVDirection, BGRA8};
+use librashader_runtime::luts::LutBundle;
 use librashader_runtime::scaling::MipmapSize;
 use objc2::runtime::ProtocolObject;
 use objc2_metal::{
-    MTLBlitCommandEncoder, MTLDevice, MTLOrigin, MTLPixelFormat, MTLRegion, MTLSize,
-    MTLStorageMode, MTLTexture, MTLTextureDescriptor, MTLTextureUsage,
+    MTLBlitCommandEncoder, MTLCommandBuffer, MTLCommandEncoder, MTLDevice, MTLOrigin,
+    MTLPixelFormat, MTLRegion, MTLSize, MTLStorageMode, MTLTexture, MTLTextureDescriptor,
+    MTLTextureUsage,
 };
+use rayon::prelude::*;
 use std::ffi::c_void;
 use std::ptr::NonNull;
+use std::sync::Arc;
 
 pub(crate) struct LutTexture(InputTexture);
 
@@ -26,10 +32,17 @@ impl LutTexture {
         config: &TextureMeta,
         mipmapper: &ProtocolObject<dyn MTLBlitCommandEncoder>,
     ) -> Result<Self> {
+        // sRGB textures are decoded to linear when sampled, so filtering happens in linear space.
+        let format = if config.srgb {
+            MTLPixelFormat::BGRA8Unorm_sRGB
+        } else {
+            MTLPixelFormat::BGRA8Unorm
+        };
+
         let descriptor = unsafe {
             let descriptor =
                 MTLTextureDescriptor::texture2DDescriptorWithPixelFormat_width_height_mipmapped(
-                    MTLPixelFormat::BGRA8Unorm,
+                    format,
                     image.size.width as usize,
                     image.size.height as usize,
                     config.mipmap,
@@ -90,3 +103,78 @@ impl LutTexture {
         }))
     }
 }
+
+/// A set of LUT textures uploaded to a Metal device, that can be shared between filter chains
+/// created on the same device with the `lut_bundle` option.
+///
+/// Frontends that switch between presets sharing texture packs can keep a bundle around, so
+/// that textures are only uploaded once. Clones of a bundle share the same textures.
+#[derive(Clone, Debug, Default)]
+pub struct LutBundleMetal(LutBundle<LutTexture>);
+
+impl LutBundleMetal {
+    /// Create an empty bundle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of textures in the bundle.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the bundle has no textures.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Remove every texture from the bundle.
+    ///
+    /// Textures in use by filter chains are released once those filter chains are dropped.
+    pub fn clear(&self) {
+        self.0.clear()
+    }
+
+    /// Upload LUT textures to the bundle ahead of filter chain creation, such as the textures
+    /// of a `ShaderPresetPack`, deferring mipmap generation to the caller.
+    ///
+    /// Textures that are already in the bundle are not uploaded again. The provided command
+    /// buffer must be ready for recording, and must be completely executed before any filter
+    /// chain using the textures draws a frame.
+    pub fn preload_deferred(
+        &self,
+        textures: Vec<TextureResource>,
+        device: &ProtocolObject<dyn MTLDevice>,
+        cmd: &ProtocolObject<dyn MTLCommandBuffer>,
+    ) -> Result<()> {
+        load_luts(device, cmd, textures, Some(self))?;
+        Ok(())
+    }
+}
+
+/// Load the LUT textures of a preset, reusing the textures in the bundle if one is given.
+pub(crate) fn load_luts(
+    device: &ProtocolObject<dyn MTLDevice>,
+    cmd: &ProtocolObject<dyn MTLCommandBuffer>,
+    textures: Vec<TextureResource>,
+    bundle: Option<&LutBundleMetal>,
+) -> Result<FastHashMap<usize, Arc<LutTexture>>> {
+    let mipmapper = cmd
+        .blitCommandEncoder()
+        .ok_or(FilterChainError::FailedToCreateCommandBuffer)?;
+
+    let luts = LutBundle::load(
+        bundle.map(|bundle| &bundle.0),
+        textures,
+        |textures| {
+            textures
+                .into_par_iter()
+                .map(|texture| LoadedTexture::<BGRA8>::from_texture(texture, UVDirection::TopLeft))
+                .collect::<std::result::Result<Vec<LoadedTexture<BGRA8>>, ImageError>>()
+        },
+        |LoadedTexture { meta, image }| LutTexture::new(device, image, &meta, &mipmapper),
+    );
+
+    mipmapper.endEncoding();
+    luts
+}
@@ -0,0 +1,107 @@
+use crate::error;
+use crate::error::FilterChainError;
+use crate::graphics_pipeline::{PipelineLayoutObjects, VulkanShaderModule, ENTRY_POINT};
+use ash::vk;
+use librashader_cache::cache_pipeline;
+use librashader_reflect::back::ShaderCompilerOutput;
+use librashader_reflect::error::ShaderReflectError;
+use librashader_reflect::reflect::ShaderReflection;
+use std::sync::Arc;
+
+pub struct VulkanComputePipeline {
+    pub layout: PipelineLayoutObjects,
+    pub pipeline: vk::Pipeline,
+    device: Arc<ash::Device>,
+    cache: vk::PipelineCache,
+}
+
+impl VulkanComputePipeline {
+    fn create_pipeline(
+        device: &ash::Device,
+        cache: &vk::PipelineCache,
+        pipeline_layout: &PipelineLayoutObjects,
+        compute_module: &VulkanShaderModule,
+    ) -> error::Result<vk::Pipeline> {
+        let stage = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::COMPUTE)
+            .name(ENTRY_POINT)
+            .module(compute_module.shader);
+
+        let pipeline_info = vk::ComputePipelineCreateInfo::default()
+            .stage(stage)
+            .layout(pipeline_layout.layout);
+
+        let pipeline = unsafe {
+            // panic_safety: if this is successful this should return 1 pipelines.
+            device
+                .create_compute_pipelines(*cache, &[pipeline_info], None)
+                .map_err(|e| e.1)?[0]
+        };
+
+        Ok(pipeline)
+    }
+
+    pub fn new(
+        device: &Arc<ash::Device>,
+        shader_assembly: &ShaderCompilerOutput<Vec<u32>>,
+        reflection: &ShaderReflection,
+        replicas: u32,
+        bypass_cache: bool,
+    ) -> error::Result<VulkanComputePipeline> {
+        let Some(compute) = shader_assembly.compute.as_ref() else {
+            return Err(FilterChainError::ShaderReflectError(
+                ShaderReflectError::UnsupportedComputePass,
+            ));
+        };
+
+        let pipeline_layout = PipelineLayoutObjects::new(reflection, replicas, device, None)?;
+
+        let compute_info = vk::ShaderModuleCreateInfo::default().code(compute.as_ref());
+        let compute_module = VulkanShaderModule::new(device, &compute_info)?;
+
+        let (pipeline, pipeline_cache) = cache_pipeline(
+            "vulkan",
+            &[compute],
+            |pipeline_data| {
+                let mut cache_info = vk::PipelineCacheCreateInfo::default();
+                if let Some(pipeline_data) = pipeline_data.as_ref() {
+                    cache_info = cache_info.initial_data(pipeline_data);
+                }
+                let cache_info = cache_info;
+
+                let pipeline_cache = unsafe { device.create_pipeline_cache(&cache_info, None)? };
+
+                let pipeline = Self::create_pipeline(
+                    device,
+                    &pipeline_cache,
+                    &pipeline_layout,
+                    &compute_module,
+                )?;
+                Ok::<_, FilterChainError>((pipeline, pipeline_cache))
+            },
+            |(_pipeline, cache)| unsafe { Ok(device.get_pipeline_cache_data(*cache)?) },
+            bypass_cache,
+        )?;
+
+        Ok(VulkanComputePipeline {
+            layout: pipeline_layout,
+            pipeline,
+            device: Arc::clone(device),
+            cache: pipeline_cache,
+        })
+    }
+}
+
+impl Drop for VulkanComputePipeline {
+    fn drop(&mut self) {
+        unsafe {
+            if self.pipeline != vk::Pipeline::null() {
+                self.device.destroy_pipeline(self.pipeline, None)
+            }
+
+            if self.cache != vk::PipelineCache::null() {
+                self.device.destroy_pipeline_cache(self.cache, None)
+            }
+        }
+    }
+}
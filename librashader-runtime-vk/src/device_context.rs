@@ -0,0 +1,50 @@
+use crate::error;
+use crate::error::FilterChainError;
+use crate::filter_chain::VulkanObjects;
+use crate::luts::LutBundleVulkan;
+use crate::samplers::SamplerSet;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Device-level resources that can be shared between filter chains created on the same device
+/// with the `device_context` option.
+///
+/// A device context owns the samplers of the filter chains, and a [`LutBundleVulkan`] for their
+/// LUT textures and staging buffers, so that frontends that run several filter chains at once
+/// do not create duplicate objects for each of them. Clones of a device context share the same
+/// resources, which are released once the last filter chain using them is dropped.
+#[derive(Clone)]
+pub struct DeviceContextVulkan {
+    pub(crate) samplers: Arc<SamplerSet>,
+    pub(crate) luts: LutBundleVulkan,
+}
+
+impl DeviceContextVulkan {
+    /// Create a device context for the given device.
+    pub fn new<V, E>(vulkan: V) -> error::Result<Self>
+    where
+        V: TryInto<VulkanObjects, Error = E>,
+        FilterChainError: From<E>,
+    {
+        let vulkan: VulkanObjects = vulkan.try_into()?;
+        Ok(Self {
+            samplers: Arc::new(SamplerSet::new(&vulkan.device)?),
+            luts: LutBundleVulkan::new(),
+        })
+    }
+
+    /// The bundle of LUT textures shared by filter chains created with this device context.
+    ///
+    /// Textures can be preloaded into the bundle before any filter chain is created.
+    pub fn lut_bundle(&self) -> &LutBundleVulkan {
+        &self.luts
+    }
+}
+
+impl Debug for DeviceContextVulkan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeviceContextVulkan")
+            .field("luts", &self.luts)
+            .finish_non_exhaustive()
+    }
+}
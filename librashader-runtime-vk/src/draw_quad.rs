@@ -3,6 +3,7 @@ use crate::memory::VulkanBuffer;
 use array_concat::concat_arrays;
 use ash::vk;
 use gpu_allocator::vulkan::Allocator;
+use librashader_runtime::mesh::OutputMesh;
 use librashader_runtime::quad::{QuadType, VertexInput};
 use parking_lot::Mutex;
 use std::sync::Arc;
@@ -49,6 +50,7 @@ static VBO_DATA: &[VertexInput; 8] = &concat_arrays!(OFFSCREEN_VBO_DATA, FINAL_V
 
 pub struct DrawQuad {
     buffer: VulkanBuffer,
+    mesh: Option<(VulkanBuffer, u32)>,
 }
 
 impl DrawQuad {
@@ -68,7 +70,36 @@ impl DrawQuad {
             slice.copy_from_slice(bytemuck::cast_slice(VBO_DATA));
         }
 
-        Ok(DrawQuad { buffer })
+        Ok(DrawQuad { buffer, mesh: None })
+    }
+
+    /// Set the mesh to draw the final pass with, or reset it to the default quad.
+    pub fn set_mesh(
+        &mut self,
+        device: &Arc<ash::Device>,
+        allocator: &Arc<Mutex<Allocator>>,
+        mesh: Option<&OutputMesh>,
+    ) -> error::Result<()> {
+        let Some(mesh) = mesh else {
+            self.mesh = None;
+            return Ok(());
+        };
+
+        let vertices = mesh.vertices();
+        let mut buffer = VulkanBuffer::new(
+            device,
+            allocator,
+            vk::BufferUsageFlags::VERTEX_BUFFER,
+            std::mem::size_of_val(vertices),
+        )?;
+
+        {
+            let slice = buffer.as_mut_slice()?;
+            slice.copy_from_slice(bytemuck::cast_slice(vertices));
+        }
+
+        self.mesh = Some((buffer, mesh.vertex_count()));
+        Ok(())
     }
 
     pub fn bind_vbo_for_frame(&self, device: &ash::Device, cmd: vk::CommandBuffer) {
@@ -78,6 +109,16 @@ impl DrawQuad {
     }
 
     pub fn draw_quad(&self, device: &ash::Device, cmd: vk::CommandBuffer, vbo: QuadType) {
+        if let (QuadType::Final, Some((mesh, count))) = (&vbo, &self.mesh) {
+            unsafe {
+                device.cmd_bind_vertex_buffers(cmd, 0, &[mesh.handle], &[0 as vk::DeviceSize]);
+                device.cmd_draw(cmd, *count, 1, 0, 0);
+            }
+            // the quad is bound again for the passes drawn after the final pass.
+            self.bind_vbo_for_frame(device, cmd);
+            return;
+        }
+
         let offset = match vbo {
             QuadType::Offscreen => 0,
             QuadType::Final => 4,
@@ -1,5 +1,6 @@
 //! Vulkan shader runtime errors.
 use gpu_allocator::AllocationError;
+use librashader_common::ImageFormat;
 use librashader_preprocess::PreprocessError;
 use librashader_presets::ParsePresetError;
 use librashader_reflect::error::{ShaderCompileError, ShaderReflectError};
@@ -30,6 +31,18 @@ pub enum FilterChainError {
     AllocationError(#[from] AllocationError),
     #[error("allocation is already freed")]
     AllocationDoesNotExist,
+    #[error("the output format {0:?} of a compute pass can not be used as a storage image")]
+    UnsupportedComputeFormat(ImageFormat),
+    #[error("the framebuffer format {0:?} is not supported by the device")]
+    UnsupportedFormat(ImageFormat),
+    #[error("the final pass of a filter chain can not be a compute pass")]
+    ComputeFinalPass,
+    #[error("filter chain creation was cancelled")]
+    Cancelled,
+    #[error("could not spawn the worker thread to create the filter chain on")]
+    WorkerThread(#[source] std::io::Error),
+    #[error("the push constants of pass {0} are {1} bytes, but the device supports {2} bytes")]
+    PushConstantTooLarge(usize, u32, u32),
     #[error("unreachable")]
     Infallible(#[from] std::convert::Infallible),
 }
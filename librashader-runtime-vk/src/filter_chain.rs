@@ -1,17 +1,21 @@
+use crate::compute_pipeline::VulkanComputePipeline;
 use crate::draw_quad::DrawQuad;
 use crate::error::FilterChainError;
-use crate::filter_pass::FilterPass;
+use crate::filter_pass::{FilterPass, PassPipeline};
 use crate::framebuffer::OutputImage;
 use crate::graphics_pipeline::VulkanGraphicsPipeline;
-use crate::luts::LutTexture;
+use crate::hardware_buffer::HardwareBufferImage;
+use crate::luts::{self, LutTexture};
 use crate::memory::RawVulkanBuffer;
 use crate::options::{FilterChainOptionsVulkan, FrameOptionsVulkan};
 use crate::queue_selection::get_graphics_queue;
 use crate::samplers::SamplerSet;
+use crate::scene_analysis::SceneAnalysis;
 use crate::texture::{InputImage, OwnedImage, OwnedImageLayout, VulkanImage};
-use crate::{error, memory, util};
+use crate::DeviceContextVulkan;
+use crate::{error, memory, texture, util};
 use ash::vk;
-use librashader_common::{ImageFormat, Size, Viewport};
+use librashader_common::{FormatFallback, ImageFormat, ScalingKernel, Size, Viewport};
 
 use ash::vk::Handle;
 use gpu_allocator::vulkan::Allocator;
@@ -24,15 +28,26 @@ use librashader_reflect::back::{CompileReflectShader, CompileShader};
 use librashader_reflect::front::SpirvCompilation;
 use librashader_reflect::reflect::cross::SpirvCross;
 use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
-use librashader_reflect::reflect::semantics::ShaderSemantics;
+use librashader_reflect::reflect::semantics::{ShaderSemantics, UniqueSemantics};
 use librashader_reflect::reflect::ReflectShader;
-use librashader_runtime::binding::BindingUtil;
-use librashader_runtime::framebuffer::FramebufferInit;
-use librashader_runtime::image::{ImageError, LoadedTexture, UVDirection, BGRA8};
+use librashader_runtime::binding::{BindingUtil, UniformBindings, DEFAULT_SCENE_LUMINANCE};
+use librashader_runtime::filter_pass::{
+    requested_format, FilterPassMeta, IdentityPasses, PassInfo,
+};
+use librashader_runtime::framebuffer::{FramebufferInit, HistoryDepth};
+use librashader_runtime::instance::{self, LibrashaderInstance};
+use librashader_runtime::jitter;
+use librashader_runtime::latency::{LatencyMarker, LatencyMarkerCallback};
+use librashader_runtime::mesh::OutputMesh;
+use librashader_runtime::mvp;
+use librashader_runtime::progress::{LoadStage, PassProgress};
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
+use librashader_runtime::rewind::RewindFrameCount;
 use librashader_runtime::scaling::ScaleFramebuffer;
+use librashader_runtime::statistics::{FrameStatistics, FrameStatisticsRecorder};
 use librashader_runtime::uniforms::UniformStorage;
+use librashader_runtime::warnings::OptionWarning;
 use parking_lot::Mutex;
 use rayon::prelude::*;
 use std::collections::VecDeque;
@@ -48,6 +63,14 @@ pub struct VulkanObjects {
     pub alloc: Arc<Mutex<Allocator>>,
     /// The graphics queue to do work on.
     pub queue: vk::Queue,
+    /// The instance and physical device to query the supported framebuffer formats of.
+    ///
+    /// If this is `None`, every framebuffer format is assumed to be supported.
+    pub physical_device: Option<(ash::Instance, vk::PhysicalDevice)>,
+    /// The highest Vulkan API version supported by the instance, if known.
+    ///
+    /// The device is used with the lower of this and the API version of the physical device.
+    pub instance_version: Option<u32>,
 }
 
 /// A collection of handles needed to access the Vulkan instance.
@@ -67,6 +90,77 @@ pub struct VulkanInstance {
     pub queue: Option<vk::Queue>,
 }
 
+impl VulkanObjects {
+    /// The API version of the device, which is the lower of the instance and physical device
+    /// API versions, or `None` if the physical device is not known.
+    fn api_version(&self) -> Option<u32> {
+        let (instance, physical_device) = self.physical_device.as_ref()?;
+        let device_version =
+            unsafe { instance.get_physical_device_properties(*physical_device) }.api_version;
+        Some(
+            self.instance_version
+                .map_or(device_version, |version| version.min(device_version)),
+        )
+    }
+
+    /// Whether the device supports dynamic rendering, which requires Vulkan 1.3, or Vulkan 1.2
+    /// with `VK_KHR_dynamic_rendering`.
+    ///
+    /// If the physical device is not known, dynamic rendering is assumed to be supported.
+    fn supports_dynamic_rendering(&self) -> bool {
+        let (Some((instance, physical_device)), Some(api_version)) =
+            (&self.physical_device, self.api_version())
+        else {
+            return true;
+        };
+
+        unsafe {
+            if api_version < vk::API_VERSION_1_2 {
+                return false;
+            }
+
+            if api_version < vk::API_VERSION_1_3 {
+                let Ok(extensions) =
+                    instance.enumerate_device_extension_properties(*physical_device)
+                else {
+                    return false;
+                };
+                if !extensions.iter().any(|extension| {
+                    extension.extension_name_as_c_str() == Ok(ash::khr::dynamic_rendering::NAME)
+                }) {
+                    return false;
+                }
+
+                let mut dynamic_rendering =
+                    vk::PhysicalDeviceDynamicRenderingFeaturesKHR::default();
+                let mut features =
+                    vk::PhysicalDeviceFeatures2::default().push_next(&mut dynamic_rendering);
+                instance.get_physical_device_features2(*physical_device, &mut features);
+                return dynamic_rendering.dynamic_rendering == vk::TRUE;
+            }
+
+            let mut dynamic_rendering = vk::PhysicalDeviceDynamicRenderingFeatures::default();
+            let mut features =
+                vk::PhysicalDeviceFeatures2::default().push_next(&mut dynamic_rendering);
+            instance.get_physical_device_features2(*physical_device, &mut features);
+            dynamic_rendering.dynamic_rendering == vk::TRUE
+        }
+    }
+
+    /// Get the `VK_KHR_dynamic_rendering` functions if dynamic rendering is not part of the
+    /// API version of the device, or `None` if the core Vulkan 1.3 functions are used.
+    pub(crate) fn dynamic_rendering_khr(&self) -> Option<ash::khr::dynamic_rendering::Device> {
+        let (instance, _) = self.physical_device.as_ref()?;
+        if self.api_version()? >= vk::API_VERSION_1_3 {
+            return None;
+        }
+        Some(ash::khr::dynamic_rendering::Device::new(
+            instance,
+            &self.device,
+        ))
+    }
+}
+
 impl TryFrom<VulkanInstance> for VulkanObjects {
     type Error = FilterChainError;
 
@@ -91,6 +185,13 @@ impl TryFrom<VulkanInstance> for VulkanObjects {
             );
 
             let device = ash::Device::load(instance.fp_v1_0(), vulkan.device);
+            let entry = ash::Entry::from_static_fn(ash::StaticFn {
+                get_instance_proc_addr,
+            });
+            let instance_version = entry
+                .try_enumerate_instance_version()
+                .ok()
+                .map(|version| version.unwrap_or(vk::API_VERSION_1_0));
 
             let queue = vulkan.queue.unwrap_or(get_graphics_queue(
                 &instance,
@@ -98,12 +199,15 @@ impl TryFrom<VulkanInstance> for VulkanObjects {
                 vulkan.physical_device,
             ));
 
-            let alloc = memory::create_allocator(device.clone(), instance, vulkan.physical_device)?;
+            let alloc =
+                memory::create_allocator(device.clone(), instance.clone(), vulkan.physical_device)?;
 
             Ok(VulkanObjects {
                 device: Arc::new(device),
                 alloc,
                 queue,
+                physical_device: Some((instance, vulkan.physical_device)),
+                instance_version,
             })
         }
     }
@@ -121,12 +225,14 @@ impl TryFrom<(vk::PhysicalDevice, ash::Instance, ash::Device)> for VulkanObjects
 
         let queue = get_graphics_queue(&value.1, &device, value.0);
 
-        let alloc = memory::create_allocator(device.clone(), value.1, value.0)?;
+        let alloc = memory::create_allocator(device.clone(), value.1.clone(), value.0)?;
 
         Ok(VulkanObjects {
             alloc,
             device: Arc::new(device),
             queue,
+            physical_device: Some((value.1, value.0)),
+            instance_version: None,
         })
     }
 }
@@ -148,12 +254,14 @@ impl TryFrom<(vk::PhysicalDevice, ash::Instance, ash::Device, vk::Queue)> for Vu
             value.3
         };
 
-        let alloc = memory::create_allocator(device.clone(), value.1, value.0)?;
+        let alloc = memory::create_allocator(device.clone(), value.1.clone(), value.0)?;
 
         Ok(VulkanObjects {
             alloc,
             device: Arc::new(device),
             queue,
+            physical_device: Some((value.1, value.0)),
+            instance_version: None,
         })
     }
 }
@@ -162,19 +270,53 @@ impl TryFrom<(vk::PhysicalDevice, ash::Instance, ash::Device, vk::Queue)> for Vu
 pub struct FilterChainVulkan {
     pub(crate) common: FilterCommon,
     passes: Box<[FilterPass]>,
+    identity: IdentityPasses<FilterPass>,
+    pass_options: PassOptions,
     vulkan: VulkanObjects,
     output_framebuffers: Box<[OwnedImage]>,
     feedback_framebuffers: Box<[OwnedImage]>,
     history_framebuffers: VecDeque<OwnedImage>,
+    /// The oldest history frame of the previous frame, while the final pass that may sample it
+    /// is recorded to a command buffer that runs after the history is updated.
+    history_spare: Option<OwnedImage>,
+    history_depth: HistoryDepth,
+    option_warnings: Box<[OptionWarning]>,
     disable_mipmaps: bool,
     residuals: Box<[FrameResiduals]>,
     default_options: FrameOptionsVulkan,
     draw_last_pass_feedback: bool,
+    disable_temporal: bool,
+    rewind: RewindFrameCount,
+    async_pipeline_rebuild: bool,
+    pipelines_pending: bool,
+    invalidated_history: usize,
+    latency_markers: Option<LatencyMarkerCallback>,
+    timestamp_queries: Option<(vk::QueryPool, u32)>,
+    ycbcr: Option<YcbcrConversionPass>,
+    scene_analysis: Option<SceneAnalysis>,
+}
+
+/// The options that the shader passes were created with, to create the identity passes with
+/// when they are first used.
+#[derive(Clone, Copy)]
+struct PassOptions {
+    frames_in_flight: u32,
+    use_dynamic_rendering: bool,
+    disable_cache: bool,
+    format_fallback: FormatFallback,
+    portability: bool,
+}
+
+/// An internal pass that converts Y'CbCr inputs to RGB before the shader passes run.
+struct YcbcrConversionPass {
+    conversion: vk::SamplerYcbcrConversion,
+    pass: FilterPass,
+    output: OwnedImage,
 }
 
 pub(crate) struct FilterCommon {
-    pub(crate) luts: FastHashMap<usize, LutTexture>,
-    pub samplers: SamplerSet,
+    pub(crate) luts: FastHashMap<usize, Arc<LutTexture>>,
+    pub samplers: Arc<SamplerSet>,
     pub(crate) draw_quad: DrawQuad,
     pub output_textures: Box<[Option<InputImage>]>,
     pub feedback_textures: Box<[Option<InputImage>]>,
@@ -182,6 +324,9 @@ pub(crate) struct FilterCommon {
     pub config: RuntimeParameters,
     pub device: Arc<ash::Device>,
     pub(crate) internal_frame_count: usize,
+    pub(crate) scene_luminance: f32,
+    pub(crate) frame_jitter: [f32; 2],
+    pub statistics: FrameStatisticsRecorder,
 }
 
 /// Contains residual intermediate `VkImageView` and `VkImage` objects created
@@ -219,6 +364,10 @@ impl FrameResiduals {
         self.framebuffers.push(fb)
     }
 
+    pub(crate) fn dispose_image_view(&mut self, image_view: vk::ImageView) {
+        self.image_views.push(image_view)
+    }
+
     /// Dispose of the intermediate objects created during a frame.
     pub fn dispose(&mut self) {
         for image_view in self.image_views.drain(0..) {
@@ -284,10 +433,30 @@ mod compile {
 }
 
 use compile::{compile_passes, ShaderPassMeta};
-use librashader_pack::{ShaderPresetPack, TextureResource};
+use librashader_pack::{PassResource, ShaderPresetPack, TextureResource};
 use librashader_runtime::parameters::RuntimeParameters;
+use librashader_runtime::pending::PendingFilterChain;
 
 impl FilterChainVulkan {
+    /// Create a filter chain without any shader passes, that only scales the input to the viewport.
+    ///
+    /// This is equivalent to loading a preset without any shader passes.
+    ///
+    /// ## Safety
+    /// The handles of `vulkan` must be valid for the command buffers that [`frame`](Self::frame)
+    /// will record to. The queue of `vulkan` is submitted to and waited on until idle, so it must
+    /// not be used by any other thread while the filter chain is created.
+    pub unsafe fn new_identity<V, E>(
+        vulkan: V,
+        options: Option<&FilterChainOptionsVulkan>,
+    ) -> error::Result<FilterChainVulkan>
+    where
+        V: TryInto<VulkanObjects, Error = E>,
+        FilterChainError: From<E>,
+    {
+        unsafe { Self::load_from_pack(ShaderPresetPack::empty(), vulkan, options) }
+    }
+
     /// Load the shader preset at the given path into a filter chain.
     pub unsafe fn load_from_path<V, E>(
         path: impl AsRef<Path>,
@@ -319,6 +488,36 @@ impl FilterChainVulkan {
         unsafe { Self::load_from_pack(pack, vulkan, options) }
     }
 
+    /// Load a filter chain from a pre-parsed `ShaderPreset` on a worker thread.
+    ///
+    /// Shaders are compiled without blocking the calling thread, and the returned
+    /// [`PendingFilterChain`] can be polled or awaited for the filter chain. The progress
+    /// callback of the options is called on the worker thread.
+    ///
+    /// Returns an error if the worker thread could not be spawned.
+    ///
+    /// ## Safety
+    /// The instance and device of `vulkan` must stay valid until the filter chain is created,
+    /// even if the pending filter chain is dropped before.
+    ///
+    /// LUTs are uploaded by submitting to the queue of `vulkan` on the worker thread. The queue
+    /// must not be used by any other thread until the filter chain is created, so a queue that
+    /// the frontend does not submit to, such as a second graphics queue, should be given.
+    pub unsafe fn load_from_preset_async<V, E>(
+        preset: ShaderPreset,
+        vulkan: V,
+        options: Option<FilterChainOptionsVulkan>,
+    ) -> error::Result<PendingFilterChain<error::Result<FilterChainVulkan>>>
+    where
+        V: TryInto<VulkanObjects, Error = E> + Send + 'static,
+        FilterChainError: From<E>,
+    {
+        PendingFilterChain::spawn(move || unsafe {
+            Self::load_from_preset(preset, vulkan, options.as_ref())
+        })
+        .map_err(FilterChainError::WorkerThread)
+    }
+
     /// Load a filter chain from a pre-parsed and loaded `ShaderPresetPack`.
     pub unsafe fn load_from_pack<V, E>(
         preset: ShaderPresetPack,
@@ -407,7 +606,7 @@ impl FilterChainVulkan {
     /// The caller is responsible for ending the command buffer and immediately submitting it to a
     /// graphics queue. The command buffer must be completely executed before calling [`frame`](Self::frame).
     pub unsafe fn load_from_pack_deferred<V, E>(
-        preset: ShaderPresetPack,
+        mut preset: ShaderPresetPack,
         vulkan: V,
         cmd: vk::CommandBuffer,
         options: Option<&FilterChainOptionsVulkan>,
@@ -417,7 +616,27 @@ impl FilterChainVulkan {
         FilterChainError: From<E>,
     {
         let disable_cache = options.map_or(false, |o| o.disable_cache);
+        let instance = options.and_then(|o| o.instance.as_ref());
+        let _instance = instance.map(LibrashaderInstance::enter);
+
+        if options.map_or(false, |o| o.color_assist) {
+            preset.append_color_assist();
+        }
+        if options.map_or(false, |o| o.black_frame_insertion) {
+            preset.append_black_frame_insertion();
+        }
+
+        let progress = PassProgress::new(
+            options.and_then(|o| o.progress.as_ref()),
+            preset.passes.len(),
+        )
+        .with_cancellation(options.and_then(|o| o.cancel.as_ref()));
+
         let (passes, semantics) = compile_passes(preset.passes, &preset.textures, disable_cache)?;
+        progress.report_all(LoadStage::Preprocessing);
+        if progress.is_cancelled() {
+            return Err(FilterChainError::Cancelled);
+        }
 
         let device = vulkan.try_into().map_err(From::from)?;
 
@@ -426,18 +645,60 @@ impl FilterChainVulkan {
             frames_in_flight = 3;
         }
 
+        let parameters = RuntimeParameters::new(
+            preset.pass_count as usize,
+            preset.parameters,
+            passes
+                .iter()
+                .flat_map(|(pass, _)| pass.data.parameters.values()),
+        );
+
+        let dynamic_rendering_requested = options.map_or(false, |o| o.use_dynamic_rendering);
+        let use_dynamic_rendering =
+            dynamic_rendering_requested && device.supports_dynamic_rendering();
+        let format_fallback = options.map_or(FormatFallback::default(), |o| {
+            FormatFallback::from(o.format_fallback)
+        });
+        let portability = options.map_or(false, |o| o.portability);
+
         // initialize passes
-        let filters = Self::init_passes(
+        let filters = instance::install(instance, || {
+            Self::init_passes(
+                &device,
+                passes,
+                &semantics,
+                &parameters,
+                frames_in_flight,
+                use_dynamic_rendering,
+                disable_cache,
+                format_fallback,
+                portability,
+                None,
+                progress,
+            )
+        })?;
+
+        // the final pass draws to the viewport, which can not be written as a storage image.
+        if filters
+            .last()
+            .is_some_and(|pass| matches!(pass.pipeline, PassPipeline::Compute(_)))
+        {
+            return Err(FilterChainError::ComputeFinalPass);
+        }
+
+        let device_context = options.and_then(|o| o.device_context.as_ref());
+        let luts = luts::load_luts(
             &device,
-            passes,
-            &semantics,
-            frames_in_flight,
-            options.map_or(false, |o| o.use_dynamic_rendering),
-            disable_cache,
+            cmd,
+            preset.textures,
+            options
+                .and_then(|o| o.lut_bundle.as_ref())
+                .or(device_context.map(DeviceContextVulkan::lut_bundle)),
         )?;
-
-        let luts = FilterChainVulkan::load_luts(&device, cmd, preset.textures)?;
-        let samplers = SamplerSet::new(&device.device)?;
+        let samplers = match device_context {
+            Some(context) => Arc::clone(&context.samplers),
+            None => Arc::new(SamplerSet::new(&device.device)?),
+        };
 
         let framebuffer_gen =
             || OwnedImage::new(&device, Size::new(1, 1), ImageFormat::R8G8B8A8Unorm, 1);
@@ -446,18 +707,96 @@ impl FilterChainVulkan {
             filters.iter().map(|f| &f.reflection.meta),
             &framebuffer_gen,
             &input_gen,
-        );
+        )
+        .with_max_history(options.map_or(0, |o| o.max_history as usize))
+        .with_temporal_disabled(options.map_or(false, |o| o.disable_temporal));
+        let history_depth = framebuffer_init.history_depth();
+        let mut option_warnings = framebuffer_init.option_warnings();
+        option_warnings.extend(OptionWarning::check_mipmap_input(
+            options.map_or(false, |o| o.force_no_mipmaps),
+            filters.iter().map(|f| f.meta.mipmap_input),
+        ));
+        if dynamic_rendering_requested && !use_dynamic_rendering {
+            option_warnings.push(OptionWarning::DynamicRenderingUnsupported);
+        }
 
         // initialize output framebuffers
-        let (output_framebuffers, output_textures) = framebuffer_init.init_output_framebuffers()?;
+        let (mut output_framebuffers, output_textures) =
+            framebuffer_init.init_output_framebuffers()?;
 
         // initialize feedback framebuffers
-        let (feedback_framebuffers, feedback_textures) =
+        let (mut feedback_framebuffers, feedback_textures) =
             framebuffer_init.init_output_framebuffers()?;
 
+        // compute passes write their output as a storage image.
+        for (index, pass) in filters.iter().enumerate() {
+            if pass.reflection.compute.is_some() {
+                for framebuffer in [&mut output_framebuffers, &mut feedback_framebuffers] {
+                    framebuffer[index] = OwnedImage::new_storage(
+                        &device,
+                        Size::new(1, 1),
+                        ImageFormat::R8G8B8A8Unorm,
+                        1,
+                    )?;
+                }
+            }
+        }
+
         // initialize history
         let (history_framebuffers, history_textures) = framebuffer_init.init_history()?;
 
+        let ycbcr =
+            match options.filter(|o| o.ycbcr_conversion != vk::SamplerYcbcrConversion::null()) {
+                Some(options) => {
+                    if options.ycbcr_sampler.is_null() {
+                        return Err(FilterChainError::HandleIsNull);
+                    }
+
+                    // the conversion is an identity pass that samples the input through
+                    // the immutable Y'CbCr sampler.
+                    let (passes, semantics) = compile_passes(
+                        vec![PassResource::identity(ScalingKernel::Nearest)],
+                        &[],
+                        disable_cache,
+                    )?;
+
+                    let pass = Self::init_passes(
+                        &device,
+                        passes,
+                        &semantics,
+                        &parameters,
+                        frames_in_flight,
+                        use_dynamic_rendering,
+                        disable_cache,
+                        format_fallback,
+                        portability,
+                        Some(options.ycbcr_sampler),
+                        PassProgress::default(),
+                    )?
+                    .into_vec()
+                    .remove(0);
+
+                    Some(YcbcrConversionPass {
+                        conversion: options.ycbcr_conversion,
+                        pass,
+                        output: framebuffer_gen()?,
+                    })
+                }
+                None => None,
+            };
+
+        // the scene analysis only runs if a pass reads its result.
+        let scene_analysis = if filters.iter().any(|pass| {
+            pass.reflection
+                .meta
+                .unique_meta
+                .contains_key(&UniqueSemantics::SceneLuminance)
+        }) {
+            Some(SceneAnalysis::new(&device, frames_in_flight)?)
+        } else {
+            None
+        };
+
         let mut intermediates = Vec::new();
         intermediates.resize_with(frames_in_flight as usize, || {
             FrameResiduals::new(&device.device)
@@ -465,44 +804,134 @@ impl FilterChainVulkan {
 
         Ok(FilterChainVulkan {
             draw_last_pass_feedback: framebuffer_init.uses_final_pass_as_feedback(),
+            disable_temporal: options.map_or(false, |o| o.disable_temporal),
+            rewind: RewindFrameCount::new(
+                options.map_or(false, |o| o.freeze_frame_count_on_rewind),
+            ),
+            async_pipeline_rebuild: options.map_or(false, |o| o.async_pipeline_rebuild),
+            pipelines_pending: false,
+            invalidated_history: 0,
+            latency_markers: options.and_then(|o| o.latency_markers.clone()),
+            timestamp_queries: None,
             common: FilterCommon {
+                statistics: FrameStatisticsRecorder::new(
+                    options.map_or(false, |o| o.collect_statistics),
+                ),
                 luts,
                 samplers,
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                config: parameters,
                 draw_quad: DrawQuad::new(&device.device, &device.alloc)?,
                 device: device.device.clone(),
                 output_textures,
                 feedback_textures,
                 history_textures,
                 internal_frame_count: 0,
+                scene_luminance: DEFAULT_SCENE_LUMINANCE,
+                frame_jitter: [0.0, 0.0],
             },
             passes: filters,
+            identity: IdentityPasses::default(),
+            pass_options: PassOptions {
+                frames_in_flight,
+                use_dynamic_rendering,
+                disable_cache,
+                format_fallback,
+                portability,
+            },
             vulkan: device,
             output_framebuffers,
             feedback_framebuffers,
             history_framebuffers,
+            history_spare: None,
+            history_depth,
+            option_warnings: option_warnings.into_boxed_slice(),
             residuals: intermediates.into_boxed_slice(),
             disable_mipmaps: options.map_or(false, |o| o.force_no_mipmaps),
             default_options: Default::default(),
+            ycbcr,
+            scene_analysis,
         })
     }
 
+    /// Create the identity pass that scales its input to the viewport with the given kernel.
+    fn init_identity(
+        vulkan: &VulkanObjects,
+        parameters: &RuntimeParameters,
+        options: PassOptions,
+        kernel: ScalingKernel,
+    ) -> error::Result<FilterPass> {
+        let (passes, semantics) = compile_passes(
+            vec![PassResource::identity(kernel)],
+            &[],
+            options.disable_cache,
+        )?;
+
+        let pass = Self::init_passes(
+            vulkan,
+            passes,
+            &semantics,
+            parameters,
+            options.frames_in_flight,
+            options.use_dynamic_rendering,
+            options.disable_cache,
+            options.format_fallback,
+            options.portability,
+            None,
+            PassProgress::default(),
+        )?
+        .into_vec()
+        .remove(0);
+
+        Ok(pass)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn init_passes(
         vulkan: &VulkanObjects,
         passes: Vec<ShaderPassMeta>,
         semantics: &ShaderSemantics,
+        parameters: &RuntimeParameters,
         frames_in_flight: u32,
         use_dynamic_rendering: bool,
         disable_cache: bool,
+        format_fallback: FormatFallback,
+        portability: bool,
+        immutable_sampler: Option<vk::Sampler>,
+        progress: PassProgress,
     ) -> error::Result<Box<[FilterPass]>> {
         let frames_in_flight = std::cmp::max(1, frames_in_flight);
+        let max_push_constants_size =
+            vulkan
+                .physical_device
+                .as_ref()
+                .map(|(instance, physical_device)| unsafe {
+                    instance
+                        .get_physical_device_properties(*physical_device)
+                        .limits
+                        .max_push_constants_size
+                });
 
         let filters: Vec<error::Result<FilterPass>> = passes
             .into_par_iter()
             .enumerate()
             .map(|(index, (config, mut reflect))| {
+                if progress.is_cancelled() {
+                    return Err(FilterChainError::Cancelled);
+                }
+
                 let reflection = reflect.reflect(index, semantics)?;
                 let spirv_words = reflect.compile(None)?;
+                progress.report(LoadStage::Compiling, index);
+
+                if let (Some(push), Some(limit)) =
+                    (&reflection.push_constant, max_push_constants_size)
+                {
+                    if push.size > limit {
+                        return Err(FilterChainError::PushConstantTooLarge(
+                            index, push.size, limit,
+                        ));
+                    }
+                }
 
                 let ubo_size = reflection.ubo.as_ref().map_or(0, |ubo| ubo.size as usize);
                 let uniform_storage = UniformStorage::new_with_ubo_storage(
@@ -518,26 +947,66 @@ impl FilterChainVulkan {
                         .map_or(0, |push| push.size as usize),
                 );
 
-                let uniform_bindings = reflection.meta.create_binding_map(|param| param.offset());
+                let uniform_bindings = UniformBindings::new(
+                    reflection.meta.create_binding_map(|param| param.offset()),
+                    parameters,
+                );
+
+                let mut required_features = if reflection.compute.is_some() {
+                    vk::FormatFeatureFlags::STORAGE_IMAGE | vk::FormatFeatureFlags::SAMPLED_IMAGE
+                } else {
+                    vk::FormatFeatureFlags::COLOR_ATTACHMENT | vk::FormatFeatureFlags::SAMPLED_IMAGE
+                };
+
+                // portability implementations may not blit or linearly filter every format
+                // they can render to, which mipmap generation requires.
+                if portability {
+                    required_features |= vk::FormatFeatureFlags::BLIT_SRC
+                        | vk::FormatFeatureFlags::BLIT_DST
+                        | vk::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR;
+                }
+                let requested_format = requested_format(&config.meta, config.data.format);
+                let format = texture::select_supported_format(
+                    vulkan,
+                    requested_format,
+                    format_fallback,
+                    required_features,
+                )
+                .ok_or(FilterChainError::UnsupportedFormat(requested_format))?;
 
                 let render_pass_format = if use_dynamic_rendering {
                     vk::Format::UNDEFINED
-                } else if let Some(format) = config.meta.get_format_override() {
-                    format.into()
-                } else if config.data.format != ImageFormat::Unknown {
-                    config.data.format.into()
                 } else {
-                    ImageFormat::R8G8B8A8Unorm.into()
+                    format.into()
                 };
 
-                let graphics_pipeline = VulkanGraphicsPipeline::new(
-                    &vulkan.device,
-                    &spirv_words,
-                    &reflection,
-                    frames_in_flight,
-                    render_pass_format,
-                    disable_cache,
-                )?;
+                let pipeline = if reflection.compute.is_some() {
+                    if !texture::supports_storage(requested_format) {
+                        return Err(FilterChainError::UnsupportedComputeFormat(requested_format));
+                    }
+
+                    PassPipeline::Compute(VulkanComputePipeline::new(
+                        &vulkan.device,
+                        &spirv_words,
+                        &reflection,
+                        frames_in_flight,
+                        disable_cache,
+                    )?)
+                } else {
+                    PassPipeline::Graphics(Box::new(VulkanGraphicsPipeline::new(
+                        &vulkan.device,
+                        &spirv_words,
+                        &reflection,
+                        frames_in_flight,
+                        render_pass_format,
+                        disable_cache,
+                        immutable_sampler,
+                        use_dynamic_rendering
+                            .then(|| vulkan.dynamic_rendering_khr())
+                            .flatten(),
+                    )?))
+                };
+                progress.report(LoadStage::Pipeline, index);
 
                 Ok(FilterPass {
                     reflection,
@@ -546,7 +1015,9 @@ impl FilterChainVulkan {
                     uniform_bindings,
                     source: config.data,
                     meta: config.meta,
-                    graphics_pipeline,
+                    path: config.path,
+                    format,
+                    pipeline,
                     // ubo_ring,
                     frames_in_flight,
                 })
@@ -558,26 +1029,24 @@ impl FilterChainVulkan {
         Ok(filters.into_boxed_slice())
     }
 
-    fn load_luts(
-        vulkan: &VulkanObjects,
-        command_buffer: vk::CommandBuffer,
-        textures: Vec<TextureResource>,
-    ) -> error::Result<FastHashMap<usize, LutTexture>> {
-        let mut luts = FastHashMap::default();
-        let textures = textures
-            .into_par_iter()
-            .map(|texture| LoadedTexture::from_texture(texture, UVDirection::TopLeft))
-            .collect::<Result<Vec<LoadedTexture<BGRA8>>, ImageError>>()?;
-        for (index, LoadedTexture { meta, image }) in textures.into_iter().enumerate() {
-            let texture = LutTexture::new(vulkan, command_buffer, image, &meta)?;
-            luts.insert(index, texture);
-        }
-        Ok(luts)
-    }
-
     // image must be in SHADER_READ_OPTIMAL
-    fn push_history(&mut self, input: &VulkanImage, cmd: vk::CommandBuffer) -> error::Result<()> {
+    fn push_history(
+        &mut self,
+        input: &VulkanImage,
+        cmd: vk::CommandBuffer,
+        keep_oldest: bool,
+    ) -> error::Result<()> {
         if let Some(mut back) = self.history_framebuffers.pop_back() {
+            // the oldest frame is set aside instead of being overwritten, if the final pass
+            // that may sample it runs after the copy.
+            if keep_oldest {
+                let spare = match self.history_spare.take() {
+                    Some(spare) => spare,
+                    None => OwnedImage::new(&self.vulkan, input.size, input.format.into(), 1)?,
+                };
+                self.history_spare = Some(std::mem::replace(&mut back, spare));
+            }
+
             if back.image.size != input.size
                 || (input.format != vk::Format::UNDEFINED && input.format != back.image.format)
             {
@@ -625,19 +1094,109 @@ impl FilterChainVulkan {
                 );
             }
 
+            // the input is transitioned for the copy, which transitions the history frame too.
+            self.common.statistics.record_barriers(4);
+
             self.history_framebuffers.push_front(back)
         }
 
         Ok(())
     }
+
+    /// Get the depth of the history kept by the filter chain, and the depth
+    /// requested by the shader passes.
+    pub fn history_depth(&self) -> HistoryDepth {
+        self.history_depth
+    }
+
+    /// Get the warnings about options of the filter chain that could not be honoured as
+    /// given, such as mipmaps being forced off for shader passes that request them.
+    pub fn option_warnings(&self) -> &[OptionWarning] {
+        &self.option_warnings
+    }
+
+    /// Invalidate the last `frames` frames of history, such as after a rollback in runahead
+    /// or netplay, so that shader passes do not blend frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+    /// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+    pub fn invalidate_history(&mut self, frames: usize) {
+        self.invalidated_history = std::cmp::max(self.invalidated_history, frames);
+    }
+
+    /// Get information about the shader pass at the given index, including the
+    /// framebuffer format chosen for the pass.
+    pub fn pass_info(&self, index: usize) -> Option<PassInfo> {
+        self.passes.get(index).map(FilterPassMeta::pass_info)
+    }
+
+    /// Get the statistics of the commands recorded for the last frame, or `None` if the
+    /// filter chain was not created with the `collect_statistics` option.
+    pub fn frame_statistics(&self) -> Option<FrameStatistics> {
+        self.common.statistics.statistics()
+    }
+
+    /// Whether the final pass of the last recorded frame was not drawn to some outputs,
+    /// because the pipelines for their formats were still being built with the
+    /// `async_pipeline_rebuild` option. Outputs that were not drawn are left unchanged.
+    pub fn has_pending_pipelines(&self) -> bool {
+        self.pipelines_pending
+    }
+
+    /// Get the logical device that the filter chain was created with.
+    pub fn device(&self) -> &ash::Device {
+        &self.vulkan.device
+    }
+
+    /// Set the mesh to draw the final pass with, or reset it to the default quad if `None`.
+    ///
+    /// ## Safety
+    /// The command buffers recorded with the previous mesh must have completed execution.
+    pub unsafe fn set_output_mesh(&mut self, mesh: Option<&OutputMesh>) -> error::Result<()> {
+        self.common
+            .draw_quad
+            .set_mesh(&self.vulkan.device, &self.vulkan.alloc, mesh)
+    }
+
+    /// Import an Android `AHardwareBuffer` of the given size as an image that can be used
+    /// as the input of this filter chain without copying.
+    ///
+    /// The filter chain must have been created with the instance and physical device
+    /// of the device.
+    ///
+    /// ## Safety
+    /// - The device must have been created with the
+    ///   `VK_ANDROID_external_memory_android_hardware_buffer` extension enabled.
+    /// - `buffer` must be a valid `AHardwareBuffer` created with
+    ///   `AHARDWAREBUFFER_USAGE_GPU_SAMPLED_IMAGE`, and `size` must be its size.
+    pub unsafe fn import_hardware_buffer(
+        &self,
+        buffer: *mut vk::AHardwareBuffer,
+        size: Size<u32>,
+    ) -> error::Result<HardwareBufferImage> {
+        unsafe { HardwareBufferImage::import(&self.vulkan, buffer, size) }
+    }
+
     /// Records shader rendering commands to the provided command buffer.
     ///
     /// * The input image must be in the `VK_SHADER_READ_ONLY_OPTIMAL` layout.
-    /// * The output image must be in `VK_COLOR_ATTACHMENT_OPTIMAL` layout.
+    /// * The output image must be in `VK_COLOR_ATTACHMENT_OPTIMAL` layout, unless its current
+    ///   layout is given in [`FrameOptionsVulkan::output_layout`](crate::options::FrameOptionsVulkan::output_layout).
+    /// * If the filter chain was created with a Y'CbCr conversion, the input image must be in the
+    ///   format of the conversion, and is converted to RGB before the shader passes run.
     ///
+    /// Unless a final layout is given in
+    /// [`FrameOptionsVulkan::final_output_layout`](crate::options::FrameOptionsVulkan::final_output_layout),
     /// librashader **will not** create a pipeline barrier for the final pass. The output image will
-    /// remain in `VK_COLOR_ATTACHMENT_OPTIMAL` after all shader passes. The caller must transition
+    /// remain in `VK_COLOR_ATTACHMENT_OPTIMAL` after all shader passes, and the caller must transition
     /// the output image to the final layout.
+    ///
+    /// If a render pass is given in
+    /// [`FrameOptionsVulkan::final_render_pass`](crate::options::FrameOptionsVulkan::final_render_pass),
+    /// the final pass is recorded to the secondary command buffer in
+    /// [`FrameOptionsVulkan::final_command_buffer`](crate::options::FrameOptionsVulkan::final_command_buffer)
+    /// to be drawn in that render pass, and the layout of the output image is left to the render pass.
+    /// The other passes are recorded to `cmd`, which must be executed before the secondary command buffer.
     pub unsafe fn frame(
         &mut self,
         input: &VulkanImage,
@@ -646,13 +1205,174 @@ impl FilterChainVulkan {
         frame_count: usize,
         options: Option<&FrameOptionsVulkan>,
     ) -> error::Result<()> {
+        unsafe {
+            self.frame_broadcast(
+                input,
+                std::slice::from_ref(viewport),
+                cmd,
+                frame_count,
+                options,
+            )
+        }
+    }
+
+    /// Write GPU timestamps around the commands of the next frame to the given query pool.
+    ///
+    /// The timestamp at `first_query` is written before the first command of the frame, and the
+    /// timestamp at `first_query + 1` after the last command, so that latency-focused frontends
+    /// can measure the GPU time of the filter chain within their frame. The queries are only
+    /// written for the next frame, so that frontends can rotate queries between frames in flight.
+    ///
+    /// ## Safety
+    /// The query pool must be a `VK_QUERY_TYPE_TIMESTAMP` pool with at least `first_query + 2`
+    /// queries, and both queries must have been reset before the command buffer of the next
+    /// frame executes. The queue must support timestamps.
+    pub unsafe fn set_timestamp_queries(&mut self, pool: vk::QueryPool, first_query: u32) {
+        self.timestamp_queries = Some((pool, first_query));
+    }
+
+    /// Records shader rendering commands to the provided command buffer, drawing the final pass
+    /// to every viewport.
+    ///
+    /// Intermediate passes are scaled relative to the first viewport and are only run once.
+    /// If `viewports` is empty, nothing is recorded.
+    ///
+    /// The same layout requirements as [`FilterChainVulkan::frame`] apply to every output image.
+    ///
+    /// ## Safety
+    /// - `cmd` must be in the recording state, and must be executed on a queue of the device
+    ///   the filter chain was created with.
+    /// - The input image and the output image of every viewport must stay valid until `cmd` has
+    ///   completed execution. Viewports may draw to different regions of the same output image,
+    ///   but no output image may be the input image.
+    /// - If a final layout is given, every distinct output image is transitioned to it once.
+    ///   Otherwise, unless a final render pass is given, every output image is left in
+    ///   `VK_COLOR_ATTACHMENT_OPTIMAL`, and the caller must transition each of them.
+    /// - Commands outside of `cmd` that access an output image while `cmd` executes must be
+    ///   synchronized with it by the caller.
+    pub unsafe fn frame_broadcast(
+        &mut self,
+        input: &VulkanImage,
+        viewports: &[Viewport<VulkanImage>],
+        cmd: vk::CommandBuffer,
+        frame_count: usize,
+        options: Option<&FrameOptionsVulkan>,
+    ) -> error::Result<()> {
+        if let Some(markers) = &self.latency_markers {
+            markers.mark(LatencyMarker::RecordBegin, frame_count);
+        }
+
+        let timestamp_queries = self.timestamp_queries.take();
+        if let Some((pool, query)) = timestamp_queries {
+            unsafe {
+                self.vulkan.device.cmd_write_timestamp(
+                    cmd,
+                    vk::PipelineStageFlags::TOP_OF_PIPE,
+                    pool,
+                    query,
+                );
+            }
+        }
+
+        let result = unsafe { self.record_frame(input, viewports, cmd, frame_count, options) };
+
+        if let Some((pool, query)) = timestamp_queries {
+            unsafe {
+                self.vulkan.device.cmd_write_timestamp(
+                    cmd,
+                    vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+                    pool,
+                    query + 1,
+                );
+            }
+        }
+
+        if let Some(markers) = &self.latency_markers {
+            markers.mark(LatencyMarker::RecordEnd, frame_count);
+        }
+
+        result
+    }
+
+    /// Transition every distinct output image of the viewports between layouts,
+    /// returning the number of barriers recorded.
+    #[allow(clippy::too_many_arguments)]
+    fn transition_outputs(
+        device: &ash::Device,
+        cmd: vk::CommandBuffer,
+        viewports: &[Viewport<VulkanImage>],
+        old_layout: vk::ImageLayout,
+        new_layout: vk::ImageLayout,
+        src_access: vk::AccessFlags,
+        dst_access: vk::AccessFlags,
+        src_stage: vk::PipelineStageFlags,
+        dst_stage: vk::PipelineStageFlags,
+    ) -> u32 {
+        let mut barriers = 0;
+        for (index, viewport) in viewports.iter().enumerate() {
+            // viewports may draw to regions of the same image, which must only be transitioned once.
+            if viewports[..index]
+                .iter()
+                .any(|previous| previous.output.image == viewport.output.image)
+            {
+                continue;
+            }
+
+            unsafe {
+                util::vulkan_image_layout_transition_levels(
+                    device,
+                    cmd,
+                    viewport.output.image,
+                    1,
+                    old_layout,
+                    new_layout,
+                    src_access,
+                    dst_access,
+                    src_stage,
+                    dst_stage,
+                    vk::QUEUE_FAMILY_IGNORED,
+                    vk::QUEUE_FAMILY_IGNORED,
+                )
+            }
+            barriers += 1;
+        }
+        barriers
+    }
+
+    unsafe fn record_frame(
+        &mut self,
+        input: &VulkanImage,
+        viewports: &[Viewport<VulkanImage>],
+        cmd: vk::CommandBuffer,
+        frame_count: usize,
+        options: Option<&FrameOptionsVulkan>,
+    ) -> error::Result<()> {
+        self.pipelines_pending = false;
+
+        // passes are scaled relative to the first viewport.
+        let Some(viewport) = viewports.first() else {
+            return Ok(());
+        };
+
+        let external_render_pass = options
+            .filter(|o| o.final_render_pass != vk::RenderPass::null())
+            .map(|o| (o.final_render_pass, o.final_subpass));
+        let final_cmd = match options {
+            Some(options) if external_render_pass.is_some() => {
+                if options.final_command_buffer == vk::CommandBuffer::null() {
+                    return Err(FilterChainError::HandleIsNull);
+                }
+                options.final_command_buffer
+            }
+            _ => cmd,
+        };
+
         let intermediates =
             &mut self.residuals[self.common.internal_frame_count % self.residuals.len()];
         intermediates.dispose();
 
         // limit number of passes to those enabled.
         let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
-        let passes = &mut self.passes[0..max];
 
         if let Some(options) = &options {
             if options.clear_history {
@@ -662,10 +1382,148 @@ impl FilterChainVulkan {
             }
         }
 
-        if passes.is_empty() {
-            return Ok(());
+        // with history and feedback disabled, the history is kept black.
+        if self.disable_temporal {
+            for history in &mut self.history_framebuffers {
+                history.clear(cmd);
+            }
+        }
+
+        // clear the history frames invalidated since the last frame.
+        let invalidated_history = std::mem::take(&mut self.invalidated_history);
+        for history in self
+            .history_framebuffers
+            .iter_mut()
+            .take(invalidated_history)
+        {
+            history.clear(cmd);
+        }
+
+        let options = options.unwrap_or(&self.default_options);
+        self.common.statistics.reset();
+        let frame_count = self
+            .rewind
+            .frame_count(frame_count, options.frame_direction);
+        self.common.frame_jitter = jitter::resolve(
+            options.jitter_sequence.into(),
+            options.jitter_offset,
+            frame_count,
+        );
+
+        // the layout of the output is owned by the render pass of the caller, if any.
+        if external_render_pass.is_none()
+            && options.output_layout != vk::ImageLayout::UNDEFINED
+            && options.output_layout != vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL
+        {
+            let barriers = Self::transition_outputs(
+                &self.vulkan.device,
+                cmd,
+                viewports,
+                options.output_layout,
+                vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+                vk::AccessFlags::MEMORY_WRITE,
+                vk::AccessFlags::COLOR_ATTACHMENT_READ | vk::AccessFlags::COLOR_ATTACHMENT_WRITE,
+                vk::PipelineStageFlags::ALL_COMMANDS,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+            );
+            self.common.statistics.record_barriers(barriers);
         }
 
+        // convert Y'CbCr inputs to RGB, and use the converted image as the input from here on.
+        let converted;
+        let input = if let Some(ycbcr) = &mut self.ycbcr {
+            if ycbcr.output.image.size != input.size {
+                let old_output = std::mem::replace(
+                    &mut ycbcr.output,
+                    OwnedImage::new(&self.vulkan, input.size, ImageFormat::R8G8B8A8Unorm, 1)?,
+                );
+                intermediates.dispose_owned(old_output);
+            }
+
+            let mut conversion_info =
+                vk::SamplerYcbcrConversionInfo::default().conversion(ycbcr.conversion);
+            let ycbcr_image_view = unsafe {
+                let create_info = vk::ImageViewCreateInfo::default()
+                    .image(input.image)
+                    .format(input.format)
+                    .view_type(vk::ImageViewType::TYPE_2D)
+                    .subresource_range(
+                        vk::ImageSubresourceRange::default()
+                            .aspect_mask(vk::ImageAspectFlags::COLOR)
+                            .level_count(1)
+                            .layer_count(1),
+                    )
+                    .push_next(&mut conversion_info);
+
+                self.vulkan.device.create_image_view(&create_info, None)?
+            };
+            intermediates.dispose_image_view(ycbcr_image_view);
+
+            let ycbcr_input = InputImage {
+                image: input.clone(),
+                image_view: ycbcr_image_view,
+                wrap_mode: ycbcr.pass.meta.wrap_mode,
+                filter_mode: ycbcr.pass.meta.filter,
+                mip_filter: ycbcr.pass.meta.filter,
+            };
+
+            let output_image = OutputImage::new(&self.vulkan.device, ycbcr.output.image.clone())?;
+            let out = RenderTarget::identity(&output_image)?;
+
+            self.common
+                .draw_quad
+                .bind_vbo_for_frame(&self.vulkan.device, cmd);
+            let residual_fb = ycbcr.pass.draw(
+                cmd,
+                ycbcr.output.image.format,
+                0,
+                &self.common,
+                ycbcr.pass.meta.get_frame_count(frame_count),
+                options,
+                viewport,
+                &ycbcr_input,
+                &ycbcr_input,
+                &out,
+                QuadType::Offscreen,
+                false,
+                None,
+            )?;
+            out.output.end_pass(&self.vulkan.device, cmd);
+            self.common.statistics.record_barriers(1);
+
+            intermediates.dispose_outputs(output_image);
+            intermediates.dispose_framebuffers(residual_fb);
+
+            converted = ycbcr.output.image.clone();
+            &converted
+        } else {
+            input
+        };
+
+        // the identity pass draws to the viewport instead of the final pass if there are no
+        // enabled passes, or if the output of the final pass is scaled to the viewport.
+        let identity = if max == 0 || options.scale_final_pass {
+            Some(
+                self.identity
+                    .get_or_try_init(options.identity_scaling, |kernel| {
+                        Self::init_identity(
+                            &self.vulkan,
+                            &self.common.config,
+                            self.pass_options,
+                            kernel,
+                        )
+                    })?,
+            )
+        } else {
+            None
+        };
+
+        let scale_final_pass = max > 0 && identity.is_some();
+        let (passes, final_scaling) = match identity {
+            Some(identity) if max == 0 => (std::slice::from_mut(identity), None),
+            identity => (&mut self.passes[0..max], identity),
+        };
+
         let original_image_view = unsafe {
             let create_info = vk::ImageViewCreateInfo::default()
                 .image(input.image)
@@ -709,13 +1567,24 @@ impl FilterChainVulkan {
             mip_filter: filter,
         };
 
+        if let Some(scene_analysis) = &self.scene_analysis {
+            self.common.scene_luminance = scene_analysis.dispatch(
+                cmd,
+                self.common.internal_frame_count,
+                &original,
+                &self.common.samplers,
+            );
+        }
+
         let mut source = original.clone();
 
         // swap output and feedback **before** recording command buffers
-        std::mem::swap(
-            &mut self.output_framebuffers,
-            &mut self.feedback_framebuffers,
-        );
+        if !self.disable_temporal {
+            std::mem::swap(
+                &mut self.output_framebuffers,
+                &mut self.feedback_framebuffers,
+            );
+        }
 
         // rescale render buffers to ensure all bindings are valid.
         OwnedImage::scale_framebuffers_with_context(
@@ -745,10 +1614,19 @@ impl FilterChainVulkan {
             }),
         )?;
 
-        let passes_len = passes.len();
-        let (pass, last) = passes.split_at_mut(passes_len - 1);
+        // feedback is kept black, including after the feedback framebuffers are resized.
+        // the feedback of the previous frame is also cleared if it was invalidated.
+        if self.disable_temporal || invalidated_history > 0 {
+            for feedback in &self.feedback_framebuffers {
+                feedback.clear(cmd);
+            }
+        }
 
-        let options = options.unwrap_or(&self.default_options);
+        let passes_len = passes.len();
+        let (pass, last) = match final_scaling {
+            Some(identity) => (passes, std::slice::from_mut(identity)),
+            None => passes.split_at_mut(passes_len - 1),
+        };
 
         self.common
             .draw_quad
@@ -775,12 +1653,15 @@ impl FilterChainVulkan {
                 &out,
                 QuadType::Offscreen,
                 false,
+                None,
             )?;
 
             if target.max_miplevels > 1 && !self.disable_mipmaps {
                 target.generate_mipmaps_and_end_pass(cmd);
+                self.common.statistics.record_barriers(target.levels);
             } else {
                 out.output.end_pass(&self.vulkan.device, cmd);
+                self.common.statistics.record_barriers(1);
             }
 
             source = self.common.output_textures[index].clone().unwrap();
@@ -792,25 +1673,37 @@ impl FilterChainVulkan {
         assert_eq!(last.len(), 1);
         if let Some(pass) = last.iter_mut().next() {
             let index = passes_len - 1;
-            if pass
-                .graphics_pipeline
-                .render_passes
-                .get(&viewport.output.format)
-                .is_none()
-            {
-                // need to recompile
-                pass.graphics_pipeline.recompile(viewport.output.format)?;
+            if let PassPipeline::Graphics(graphics_pipeline) = &mut pass.pipeline {
+                graphics_pipeline.swap_finished()?;
+                // pipelines for render passes of the caller are created when they are drawn.
+                for viewport in viewports.iter().filter(|_| external_render_pass.is_none()) {
+                    if graphics_pipeline
+                        .render_passes
+                        .get(&viewport.output.format)
+                        .is_none()
+                    {
+                        // need to recompile
+                        if self.async_pipeline_rebuild {
+                            graphics_pipeline.recompile_async(viewport.output.format);
+                        } else {
+                            graphics_pipeline.recompile(viewport.output.format)?;
+                        }
+                    }
+                }
             }
 
             source.filter_mode = pass.meta.filter;
             source.wrap_mode = pass.meta.wrap_mode;
             source.mip_filter = pass.meta.filter;
 
-            if self.draw_last_pass_feedback {
+            // the output of the final pass is already drawn if it is scaled to the viewport.
+            if self.draw_last_pass_feedback && !scale_final_pass {
                 let target = &self.output_framebuffers[index];
 
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
                 let output_image = OutputImage::new(&self.vulkan.device, target.image.clone())?;
-                let out = RenderTarget::viewport_with_output(&output_image, viewport);
+                let out = RenderTarget::viewport_with_output(&output_image, viewport, &final_mvp);
 
                 let residual_fb = pass.draw(
                     cmd,
@@ -825,35 +1718,86 @@ impl FilterChainVulkan {
                     &out,
                     QuadType::Final,
                     true,
+                    None,
                 )?;
                 out.output.end_pass(&self.vulkan.device, cmd);
+                self.common.statistics.record_barriers(1);
                 intermediates.dispose_outputs(output_image);
                 intermediates.dispose_framebuffers(residual_fb);
             }
 
-            let output_image = OutputImage::new(&self.vulkan.device, viewport.output.clone())?;
-            let out = RenderTarget::viewport_with_output(&output_image, viewport);
+            if external_render_pass.is_some() {
+                self.common
+                    .draw_quad
+                    .bind_vbo_for_frame(&self.vulkan.device, final_cmd);
+            }
 
-            let residual_fb = pass.draw(
-                cmd,
-                viewport.output.format,
-                index,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                &out,
-                QuadType::Final,
-                false,
-            )?;
+            for viewport in viewports {
+                if external_render_pass.is_none()
+                    && matches!(&pass.pipeline, PassPipeline::Graphics(graphics_pipeline)
+                        if graphics_pipeline.is_pending(viewport.output.format))
+                {
+                    self.pipelines_pending = true;
+                    continue;
+                }
 
-            intermediates.dispose_outputs(output_image);
-            intermediates.dispose_framebuffers(residual_fb);
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
+                // the view given by the caller is only used when there is one output image,
+                // and no view is needed to draw in a render pass of the caller.
+                let caller_view = external_render_pass.is_some()
+                    || (viewports.len() == 1 && options.output_view != vk::ImageView::null());
+                let output_image = if caller_view {
+                    OutputImage::from_view(&viewport.output, options.output_view)
+                } else {
+                    OutputImage::new(&self.vulkan.device, viewport.output.clone())?
+                };
+                let out = RenderTarget::viewport_with_output(&output_image, viewport, &final_mvp);
+
+                let residual_fb = pass.draw(
+                    final_cmd,
+                    viewport.output.format,
+                    index,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    &out,
+                    QuadType::Final,
+                    false,
+                    external_render_pass,
+                )?;
+
+                if !caller_view {
+                    intermediates.dispose_outputs(output_image);
+                }
+                intermediates.dispose_framebuffers(residual_fb);
+            }
+        }
+
+        if external_render_pass.is_none()
+            && options.final_output_layout != vk::ImageLayout::UNDEFINED
+            && options.final_output_layout != vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL
+        {
+            let barriers = Self::transition_outputs(
+                &self.vulkan.device,
+                cmd,
+                viewports,
+                vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+                options.final_output_layout,
+                vk::AccessFlags::COLOR_ATTACHMENT_WRITE,
+                vk::AccessFlags::MEMORY_READ,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+                vk::PipelineStageFlags::ALL_COMMANDS,
+            );
+            self.common.statistics.record_barriers(barriers);
         }
 
-        self.push_history(input, cmd)?;
+        if !self.disable_temporal {
+            self.push_history(input, cmd, external_render_pass.is_some())?;
+        }
         self.common.internal_frame_count = self.common.internal_frame_count.wrapping_add(1);
         Ok(())
     }
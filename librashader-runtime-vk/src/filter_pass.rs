@@ -1,3 +1,4 @@
+use crate::compute_pipeline::VulkanComputePipeline;
 use crate::filter_chain::FilterCommon;
 use crate::framebuffer::OutputImage;
 use crate::graphics_pipeline::VulkanGraphicsPipeline;
@@ -7,32 +8,42 @@ use crate::samplers::SamplerSet;
 use crate::texture::InputImage;
 use crate::{error, VulkanImage};
 use ash::vk;
-use librashader_common::map::FastHashMap;
 use librashader_common::{ImageFormat, Size, Viewport};
 use librashader_preprocess::ShaderSource;
 use librashader_presets::PassMeta;
 use librashader_reflect::reflect::semantics::{
-    BindingStage, MemberOffset, TextureBinding, UniformBinding,
+    BindingStage, ComputeReflection, MemberOffset, TextureBinding,
 };
 use librashader_reflect::reflect::ShaderReflection;
-use librashader_runtime::binding::{BindSemantics, TextureInput, UniformInputs};
+use librashader_runtime::binding::{BindSemantics, TextureInput, UniformBindings, UniformInputs};
 use librashader_runtime::filter_pass::FilterPassMeta;
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
 use librashader_runtime::uniforms::{NoUniformBinder, UniformStorage, UniformStorageAccess};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 pub struct FilterPass {
     pub reflection: ShaderReflection,
     pub(crate) uniform_storage:
         UniformStorage<NoUniformBinder, Option<()>, RawVulkanBuffer, Box<[u8]>, Arc<ash::Device>>,
-    pub uniform_bindings: FastHashMap<UniformBinding, MemberOffset>,
+    pub uniform_bindings: UniformBindings<MemberOffset>,
     pub source: ShaderSource,
     pub meta: PassMeta,
-    pub graphics_pipeline: VulkanGraphicsPipeline,
+    pub path: Option<PathBuf>,
+    /// The framebuffer format of the pass, after falling back to a format supported
+    /// by the device.
+    pub format: ImageFormat,
+    pub pipeline: PassPipeline,
     pub frames_in_flight: u32,
 }
 
+/// The pipeline a pass is drawn or dispatched with.
+pub enum PassPipeline {
+    Graphics(Box<VulkanGraphicsPipeline>),
+    Compute(VulkanComputePipeline),
+}
+
 impl TextureInput for InputImage {
     fn size(&self) -> Size<u32> {
         self.image.size
@@ -81,6 +92,14 @@ impl FilterPassMeta for FilterPass {
     fn meta(&self) -> &PassMeta {
         &self.meta
     }
+
+    fn source_path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn get_supported_format(&self) -> ImageFormat {
+        self.format
+    }
 }
 
 impl FilterPass {
@@ -98,12 +117,33 @@ impl FilterPass {
         output: &RenderTarget<OutputImage>,
         vbo_type: QuadType,
         use_alt_descriptors: bool,
+        external_render_pass: Option<(vk::RenderPass, u32)>,
     ) -> error::Result<Option<vk::Framebuffer>> {
+        parent.statistics.record_pass(&self.reflection);
+
+        let graphics_pipeline = match &self.pipeline {
+            PassPipeline::Graphics(graphics_pipeline) => graphics_pipeline,
+            PassPipeline::Compute(_) => {
+                self.dispatch(
+                    cmd,
+                    pass_index,
+                    parent,
+                    frame_count,
+                    options,
+                    viewport,
+                    original,
+                    source,
+                    output,
+                )?;
+                return Ok(None);
+            }
+        };
+
         let mut descriptor = if use_alt_descriptors {
-            self.graphics_pipeline.layout.descriptor_sets_alt
+            graphics_pipeline.layout.descriptor_sets_alt
                 [parent.internal_frame_count % self.frames_in_flight as usize]
         } else {
-            self.graphics_pipeline.layout.descriptor_sets
+            graphics_pipeline.layout.descriptor_sets
                 [parent.internal_frame_count % self.frames_in_flight as usize]
         };
 
@@ -120,15 +160,24 @@ impl FilterPass {
             source,
         );
 
-        let Some(pipeline) = self
-            .graphics_pipeline
-            .pipelines
-            .get(&format)
-            .or_else(|| self.graphics_pipeline.pipelines.values().next())
-        else {
-            panic!("No available render pipelines found")
+        let PassPipeline::Graphics(graphics_pipeline) = &mut self.pipeline else {
+            unreachable!("compute passes are dispatched above")
         };
 
+        let pipeline = if let Some((render_pass, subpass)) = external_render_pass {
+            graphics_pipeline.external_pipeline(render_pass, subpass)?
+        } else {
+            let Some(pipeline) = graphics_pipeline
+                .pipelines
+                .get(&format)
+                .or_else(|| graphics_pipeline.pipelines.values().next())
+            else {
+                panic!("No available render pipelines found")
+            };
+            *pipeline
+        };
+        let graphics_pipeline = &*graphics_pipeline;
+
         if let Some(ubo) = &self.reflection.ubo {
             self.uniform_storage.inner_ubo().bind_to_descriptor_set(
                 descriptor,
@@ -137,21 +186,24 @@ impl FilterPass {
             )?;
         }
 
-        output.output.begin_pass(&parent.device, cmd);
-
-        let residual = self
-            .graphics_pipeline
-            .begin_rendering(output, format, cmd)?;
+        // inside a render pass of the caller, the caller owns the layout of the output.
+        let residual = if external_render_pass.is_none() {
+            output.output.begin_pass(&parent.device, cmd);
+            parent.statistics.record_barriers(1);
+            graphics_pipeline.begin_rendering(output, format, cmd)?
+        } else {
+            None
+        };
 
         unsafe {
             parent
                 .device
-                .cmd_bind_pipeline(cmd, vk::PipelineBindPoint::GRAPHICS, *pipeline);
+                .cmd_bind_pipeline(cmd, vk::PipelineBindPoint::GRAPHICS, pipeline);
 
             parent.device.cmd_bind_descriptor_sets(
                 cmd,
                 vk::PipelineBindPoint::GRAPHICS,
-                self.graphics_pipeline.layout.layout,
+                graphics_pipeline.layout.layout,
                 0,
                 &[descriptor],
                 &[],
@@ -168,7 +220,7 @@ impl FilterPass {
 
                 parent.device.cmd_push_constants(
                     cmd,
-                    self.graphics_pipeline.layout.layout,
+                    graphics_pipeline.layout.layout,
                     stage_mask,
                     0,
                     self.uniform_storage.push_slice(),
@@ -191,11 +243,120 @@ impl FilterPass {
                 .device
                 .cmd_set_viewport(cmd, 0, &[output.size.into()]);
             parent.draw_quad.draw_quad(&parent.device, cmd, vbo_type);
-            self.graphics_pipeline.end_rendering(cmd);
+            if external_render_pass.is_none() {
+                graphics_pipeline.end_rendering(cmd);
+            }
         }
         Ok(residual)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn dispatch(
+        &mut self,
+        cmd: vk::CommandBuffer,
+        pass_index: usize,
+        parent: &FilterCommon,
+        frame_count: u32,
+        options: &FrameOptionsVulkan,
+        viewport: &Viewport<VulkanImage>,
+        original: &InputImage,
+        source: &InputImage,
+        output: &RenderTarget<OutputImage>,
+    ) -> error::Result<()> {
+        let PassPipeline::Compute(compute_pipeline) = &self.pipeline else {
+            unreachable!("only compute passes are dispatched")
+        };
+        let mut descriptor = compute_pipeline.layout.descriptor_sets
+            [parent.internal_frame_count % self.frames_in_flight as usize];
+
+        self.build_semantics(
+            pass_index,
+            parent,
+            output.mvp,
+            frame_count,
+            options,
+            output.output.size,
+            viewport.output.size,
+            &mut descriptor,
+            original,
+            source,
+        );
+
+        let PassPipeline::Compute(compute_pipeline) = &self.pipeline else {
+            unreachable!("only compute passes are dispatched")
+        };
+        let Some(compute) = &self.reflection.compute else {
+            unreachable!("compute pipelines are only created for compute passes")
+        };
+
+        if let Some(ubo) = &self.reflection.ubo {
+            self.uniform_storage.inner_ubo().bind_to_descriptor_set(
+                descriptor,
+                ubo.binding,
+                &self.uniform_storage,
+            )?;
+        }
+
+        Self::bind_output(&parent.device, descriptor, compute, output.output);
+        output.output.begin_compute_pass(&parent.device, cmd);
+        parent.statistics.record_barriers(1);
+
+        unsafe {
+            parent.device.cmd_bind_pipeline(
+                cmd,
+                vk::PipelineBindPoint::COMPUTE,
+                compute_pipeline.pipeline,
+            );
+
+            parent.device.cmd_bind_descriptor_sets(
+                cmd,
+                vk::PipelineBindPoint::COMPUTE,
+                compute_pipeline.layout.layout,
+                0,
+                &[descriptor],
+                &[],
+            );
+
+            if self.reflection.push_constant.is_some() {
+                parent.device.cmd_push_constants(
+                    cmd,
+                    compute_pipeline.layout.layout,
+                    vk::ShaderStageFlags::COMPUTE,
+                    0,
+                    self.uniform_storage.push_slice(),
+                );
+            }
+
+            let [x, y, z] = compute.workgroup_count(output.output.size);
+            parent.device.cmd_dispatch(cmd, x, y, z);
+        }
+
+        output.output.end_compute_pass(&parent.device, cmd);
+        parent.statistics.record_barriers(1);
+        Ok(())
+    }
+
+    fn bind_output(
+        device: &ash::Device,
+        descriptor: vk::DescriptorSet,
+        compute: &ComputeReflection,
+        output: &OutputImage,
+    ) {
+        let image_info = [vk::DescriptorImageInfo::default()
+            .image_view(output.image_view)
+            .image_layout(vk::ImageLayout::GENERAL)];
+
+        let write_desc = vk::WriteDescriptorSet::default()
+            .dst_set(descriptor)
+            .dst_binding(compute.output_binding)
+            .dst_array_element(0)
+            .descriptor_type(vk::DescriptorType::STORAGE_IMAGE)
+            .image_info(&image_info);
+        unsafe {
+            device.update_descriptor_sets(&[write_desc], &[]);
+        }
+    }
+
     fn build_semantics(
         &mut self,
         pass_index: usize,
@@ -226,6 +387,8 @@ impl FilterPass {
                 frametime_delta: options.frametime_delta,
                 framebuffer_size: fb_size,
                 viewport_size,
+                scene_luminance: parent.scene_luminance,
+                frame_jitter: parent.frame_jitter,
             },
             original,
             source,
@@ -236,8 +399,7 @@ impl FilterPass {
                 .map(|o| o.as_ref()),
             parent.feedback_textures.iter().map(|o| o.as_ref()),
             parent.history_textures.iter().map(|o| o.as_ref()),
-            parent.luts.iter().map(|(u, i)| (*u, i.as_ref())),
-            &self.source.parameters,
+            parent.luts.iter().map(|(u, i)| (*u, &i.image)),
             &parent.config,
         );
     }
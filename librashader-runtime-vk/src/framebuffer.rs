@@ -41,6 +41,17 @@ impl OutputImage {
         })
     }
 
+    /// Wrap a view of the output image that is owned by the caller.
+    ///
+    /// The view is not destroyed by the filter chain.
+    pub fn from_view(image: &VulkanImage, image_view: vk::ImageView) -> OutputImage {
+        OutputImage {
+            size: image.size,
+            image: image.image,
+            image_view,
+        }
+    }
+
     pub fn begin_pass(&self, device: &ash::Device, cmd: vk::CommandBuffer) {
         unsafe {
             util::vulkan_image_layout_transition_levels(
@@ -78,6 +89,65 @@ impl OutputImage {
             )
         }
     }
+
+    /// Transition the output for writes from a compute pass.
+    ///
+    /// This also makes the inputs of the pass, which are prepared for reads in the
+    /// fragment shader stage, visible to the compute shader stage.
+    pub fn begin_compute_pass(&self, device: &ash::Device, cmd: vk::CommandBuffer) {
+        let input_barrier = vk::MemoryBarrier::default()
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_access_mask(vk::AccessFlags::SHADER_READ);
+
+        let output_barrier = vk::ImageMemoryBarrier::default()
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_access_mask(vk::AccessFlags::SHADER_WRITE)
+            .old_layout(vk::ImageLayout::UNDEFINED)
+            .new_layout(vk::ImageLayout::GENERAL)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .image(self.image)
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: vk::REMAINING_ARRAY_LAYERS,
+            });
+
+        unsafe {
+            device.cmd_pipeline_barrier(
+                cmd,
+                vk::PipelineStageFlags::ALL_GRAPHICS,
+                vk::PipelineStageFlags::COMPUTE_SHADER,
+                vk::DependencyFlags::empty(),
+                &[input_barrier],
+                &[],
+                &[output_barrier],
+            )
+        }
+    }
+
+    /// Transition the output of a compute pass to the layout a graphics pass leaves its
+    /// output in, so that it can be ended like any other pass.
+    pub fn end_compute_pass(&self, device: &ash::Device, cmd: vk::CommandBuffer) {
+        unsafe {
+            util::vulkan_image_layout_transition_levels(
+                device,
+                cmd,
+                self.image,
+                1,
+                vk::ImageLayout::GENERAL,
+                vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+                vk::AccessFlags::SHADER_WRITE,
+                vk::AccessFlags::COLOR_ATTACHMENT_READ | vk::AccessFlags::COLOR_ATTACHMENT_WRITE,
+                vk::PipelineStageFlags::COMPUTE_SHADER,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+                vk::QUEUE_FAMILY_IGNORED,
+                vk::QUEUE_FAMILY_IGNORED,
+            )
+        }
+    }
 }
 
 impl GetSize<u32> for OutputImage {
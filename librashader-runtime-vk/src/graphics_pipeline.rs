@@ -9,14 +9,17 @@ use bytemuck::offset_of;
 use librashader_cache::cache_pipeline;
 use librashader_common::map::FastHashMap;
 use librashader_reflect::back::ShaderCompilerOutput;
-use librashader_reflect::reflect::semantics::{BufferReflection, TextureBinding};
+use librashader_reflect::reflect::semantics::{
+    BufferReflection, ComputeReflection, TextureBinding,
+};
 use librashader_reflect::reflect::ShaderReflection;
+use librashader_runtime::pipeline_build::PendingPipelines;
 use librashader_runtime::quad::VertexInput;
 use librashader_runtime::render_target::RenderTarget;
 use std::ffi::CStr;
 use std::sync::Arc;
 
-const ENTRY_POINT: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"main\0") };
+pub(crate) const ENTRY_POINT: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"main\0") };
 
 pub struct PipelineDescriptors<'a> {
     pub replicas: u32,
@@ -53,20 +56,48 @@ impl PipelineDescriptors<'_> {
         }
     }
 
-    pub fn add_texture_bindings<'a>(&mut self, textures: impl Iterator<Item = &'a TextureBinding>) {
-        let texture_mask = vk::ShaderStageFlags::FRAGMENT;
+    pub fn add_texture_bindings<'a>(
+        &mut self,
+        textures: impl Iterator<Item = &'a TextureBinding>,
+        texture_mask: vk::ShaderStageFlags,
+        immutable_sampler: Option<&'a vk::Sampler>,
+    ) {
+        // a Y'CbCr conversion sampler may consume one descriptor per plane.
+        let (p_immutable_samplers, descriptors_per_texture) = match immutable_sampler {
+            Some(sampler) => (std::ptr::from_ref(sampler), 3),
+            None => (std::ptr::null(), 1),
+        };
+
         for texture in textures {
             self.layout_bindings.push(vk::DescriptorSetLayoutBinding {
                 binding: texture.binding,
                 descriptor_type: vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
                 descriptor_count: 1,
                 stage_flags: texture_mask,
-                p_immutable_samplers: std::ptr::null(),
+                p_immutable_samplers,
                 _marker: Default::default(),
             });
 
             self.pool_sizes.push(vk::DescriptorPoolSize {
                 ty: vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+                descriptor_count: self.replicas * descriptors_per_texture,
+            })
+        }
+    }
+
+    pub fn add_storage_image_binding(&mut self, compute: Option<&ComputeReflection>) {
+        if let Some(compute) = compute {
+            self.layout_bindings.push(vk::DescriptorSetLayoutBinding {
+                binding: compute.output_binding,
+                descriptor_type: vk::DescriptorType::STORAGE_IMAGE,
+                descriptor_count: 1,
+                stage_flags: vk::ShaderStageFlags::COMPUTE,
+                p_immutable_samplers: std::ptr::null(),
+                _marker: Default::default(),
+            });
+
+            self.pool_sizes.push(vk::DescriptorPoolSize {
+                ty: vk::DescriptorType::STORAGE_IMAGE,
                 descriptor_count: self.replicas,
             })
         }
@@ -104,10 +135,21 @@ impl PipelineLayoutObjects {
         reflection: &ShaderReflection,
         replicas: u32,
         device: &ash::Device,
+        immutable_sampler: Option<vk::Sampler>,
     ) -> error::Result<Self> {
         let mut descriptors = PipelineDescriptors::new(replicas);
         descriptors.add_ubo_binding(reflection.ubo.as_ref());
-        descriptors.add_texture_bindings(reflection.meta.texture_meta.values());
+        let texture_mask = if reflection.compute.is_some() {
+            vk::ShaderStageFlags::COMPUTE
+        } else {
+            vk::ShaderStageFlags::FRAGMENT
+        };
+        descriptors.add_texture_bindings(
+            reflection.meta.texture_meta.values(),
+            texture_mask,
+            immutable_sampler.as_ref(),
+        );
+        descriptors.add_storage_image_binding(reflection.compute.as_ref());
 
         let descriptor_set_layout = [descriptors.create_descriptor_set_layout(device)?];
 
@@ -171,7 +213,7 @@ impl PipelineLayoutObjects {
 }
 
 pub struct VulkanShaderModule {
-    shader: vk::ShaderModule,
+    pub(crate) shader: vk::ShaderModule,
     device: ash::Device,
 }
 
@@ -197,21 +239,27 @@ pub struct VulkanGraphicsPipeline {
     pub layout: PipelineLayoutObjects,
     pub pipelines: FastHashMap<vk::Format, vk::Pipeline>,
     pub render_passes: FastHashMap<vk::Format, Option<VulkanRenderPass>>,
+    /// Pipelines for render passes owned by the caller, by render pass and subpass index.
+    external_pipelines: FastHashMap<(vk::RenderPass, u32), vk::Pipeline>,
     device: Arc<ash::Device>,
     vertex: VulkanShaderModule,
     fragment: VulkanShaderModule,
     cache: vk::PipelineCache,
     use_render_pass: bool,
+    /// The `VK_KHR_dynamic_rendering` functions, if dynamic rendering is not core on the device.
+    dynamic_rendering_khr: Option<ash::khr::dynamic_rendering::Device>,
+    pending:
+        PendingPipelines<vk::Format, (vk::Pipeline, Option<VulkanRenderPass>), FilterChainError>,
 }
 
 impl VulkanGraphicsPipeline {
     fn create_pipeline(
         device: &ash::Device,
         cache: &vk::PipelineCache,
-        pipeline_layout: &PipelineLayoutObjects,
-        vertex_module: &VulkanShaderModule,
-        fragment_module: &VulkanShaderModule,
-        render_pass: Option<&VulkanRenderPass>,
+        pipeline_layout: vk::PipelineLayout,
+        vertex_module: vk::ShaderModule,
+        fragment_module: vk::ShaderModule,
+        render_pass: Option<(vk::RenderPass, u32)>,
     ) -> error::Result<vk::Pipeline> {
         let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
             .topology(vk::PrimitiveTopology::TRIANGLE_STRIP);
@@ -280,11 +328,11 @@ impl VulkanGraphicsPipeline {
             vk::PipelineShaderStageCreateInfo::default()
                 .stage(vk::ShaderStageFlags::VERTEX)
                 .name(ENTRY_POINT)
-                .module(vertex_module.shader),
+                .module(vertex_module),
             vk::PipelineShaderStageCreateInfo::default()
                 .stage(vk::ShaderStageFlags::FRAGMENT)
                 .name(ENTRY_POINT)
-                .module(fragment_module.shader),
+                .module(fragment_module),
         ];
 
         let mut pipeline_info = vk::GraphicsPipelineCreateInfo::default()
@@ -297,10 +345,10 @@ impl VulkanGraphicsPipeline {
             .viewport_state(&viewport_state)
             .depth_stencil_state(&depth_stencil_state)
             .dynamic_state(&dynamic_state)
-            .layout(pipeline_layout.layout);
+            .layout(pipeline_layout);
 
-        if let Some(render_pass) = render_pass {
-            pipeline_info = pipeline_info.render_pass(render_pass.handle)
+        if let Some((render_pass, subpass)) = render_pass {
+            pipeline_info = pipeline_info.render_pass(render_pass).subpass(subpass)
         }
 
         let pipeline = unsafe {
@@ -320,8 +368,11 @@ impl VulkanGraphicsPipeline {
         replicas: u32,
         render_pass_format: vk::Format,
         bypass_cache: bool,
+        immutable_sampler: Option<vk::Sampler>,
+        dynamic_rendering_khr: Option<ash::khr::dynamic_rendering::Device>,
     ) -> error::Result<VulkanGraphicsPipeline> {
-        let pipeline_layout = PipelineLayoutObjects::new(reflection, replicas, device)?;
+        let pipeline_layout =
+            PipelineLayoutObjects::new(reflection, replicas, device, immutable_sampler)?;
 
         let vertex_info =
             vk::ShaderModuleCreateInfo::default().code(shader_assembly.vertex.as_ref());
@@ -356,10 +407,12 @@ impl VulkanGraphicsPipeline {
                 let pipeline = Self::create_pipeline(
                     &device,
                     &pipeline_cache,
-                    &pipeline_layout,
-                    &vertex_module,
-                    &fragment_module,
-                    render_pass.as_ref(),
+                    pipeline_layout.layout,
+                    vertex_module.shader,
+                    fragment_module.shader,
+                    render_pass
+                        .as_ref()
+                        .map(|render_pass| (render_pass.handle, 0)),
                 )?;
                 Ok::<_, FilterChainError>((pipeline, pipeline_cache))
             },
@@ -378,34 +431,107 @@ impl VulkanGraphicsPipeline {
             layout: pipeline_layout,
             pipelines,
             render_passes,
+            external_pipelines: FastHashMap::default(),
             vertex: vertex_module,
             fragment: fragment_module,
             cache: pipeline_cache,
             use_render_pass,
+            dynamic_rendering_khr,
+            pending: PendingPipelines::default(),
         })
     }
 
-    pub(crate) fn recompile(&mut self, format: vk::Format) -> error::Result<()> {
-        let new_renderpass = if self.use_render_pass {
-            Some(VulkanRenderPass::create_render_pass(&self.device, format)?)
-        } else {
-            None
-        };
+    /// Get a function that builds the pipeline for the given output format, which can run
+    /// on any thread.
+    fn builder(
+        &self,
+        format: vk::Format,
+    ) -> impl FnOnce() -> error::Result<(vk::Pipeline, Option<VulkanRenderPass>)> + Send + 'static
+    {
+        let device = Arc::clone(&self.device);
+        let cache = self.cache;
+        let layout = self.layout.layout;
+        let vertex = self.vertex.shader;
+        let fragment = self.fragment.shader;
+        let use_render_pass = self.use_render_pass;
+
+        move || {
+            let render_pass = if use_render_pass {
+                Some(VulkanRenderPass::create_render_pass(&device, format)?)
+            } else {
+                None
+            };
 
-        let new_pipeline = Self::create_pipeline(
-            &self.device,
-            &self.cache,
-            &self.layout,
-            &self.vertex,
-            &self.fragment,
-            new_renderpass.as_ref(),
-        )?;
+            let pipeline = Self::create_pipeline(
+                &device,
+                &cache,
+                layout,
+                vertex,
+                fragment,
+                render_pass
+                    .as_ref()
+                    .map(|render_pass| (render_pass.handle, 0)),
+            )?;
+
+            Ok((pipeline, render_pass))
+        }
+    }
+
+    pub(crate) fn recompile(&mut self, format: vk::Format) -> error::Result<()> {
+        let (new_pipeline, new_renderpass) = self.builder(format)()?;
 
         self.render_passes.insert(format, new_renderpass);
         self.pipelines.insert(format, new_pipeline);
 
         Ok(())
     }
+
+    /// Start building the pipeline for the given output format on a worker thread.
+    ///
+    /// The pipeline is swapped in by [`Self::swap_finished`] once it is built.
+    pub(crate) fn recompile_async(&mut self, format: vk::Format) {
+        let builder = self.builder(format);
+        self.pending.spawn(format, builder);
+    }
+
+    /// Swap in the pipelines that finished building on a worker thread.
+    pub(crate) fn swap_finished(&mut self) -> error::Result<()> {
+        for (format, result) in self.pending.take_finished() {
+            let (new_pipeline, new_renderpass) = result?;
+            self.render_passes.insert(format, new_renderpass);
+            self.pipelines.insert(format, new_pipeline);
+        }
+        Ok(())
+    }
+
+    /// Get the pipeline for a subpass of a render pass owned by the caller, creating it
+    /// if it was not created yet.
+    pub(crate) fn external_pipeline(
+        &mut self,
+        render_pass: vk::RenderPass,
+        subpass: u32,
+    ) -> error::Result<vk::Pipeline> {
+        if let Some(pipeline) = self.external_pipelines.get(&(render_pass, subpass)) {
+            return Ok(*pipeline);
+        }
+
+        let pipeline = Self::create_pipeline(
+            &self.device,
+            &self.cache,
+            self.layout.layout,
+            self.vertex.shader,
+            self.fragment.shader,
+            Some((render_pass, subpass)),
+        )?;
+        self.external_pipelines
+            .insert((render_pass, subpass), pipeline);
+        Ok(pipeline)
+    }
+
+    /// Whether the pipeline for the given output format is still being built.
+    pub(crate) fn is_pending(&self, format: vk::Format) -> bool {
+        self.pending.is_pending(&format)
+    }
     #[inline(always)]
     pub(crate) fn begin_rendering(
         &self,
@@ -466,7 +592,11 @@ impl VulkanGraphicsPipeline {
                 .color_attachments(&attachments);
 
             unsafe {
-                self.device.cmd_begin_rendering(cmd, &rendering_info);
+                if let Some(dynamic_rendering) = &self.dynamic_rendering_khr {
+                    dynamic_rendering.cmd_begin_rendering(cmd, &rendering_info);
+                } else {
+                    self.device.cmd_begin_rendering(cmd, &rendering_info);
+                }
             }
             Ok(None)
         }
@@ -475,7 +605,11 @@ impl VulkanGraphicsPipeline {
     pub(crate) fn end_rendering(&self, cmd: vk::CommandBuffer) {
         unsafe {
             if !self.use_render_pass {
-                self.device.cmd_end_rendering(cmd);
+                if let Some(dynamic_rendering) = &self.dynamic_rendering_khr {
+                    dynamic_rendering.cmd_end_rendering(cmd);
+                } else {
+                    self.device.cmd_end_rendering(cmd);
+                }
             } else {
                 self.device.cmd_end_render_pass(cmd)
             }
@@ -486,12 +620,22 @@ impl VulkanGraphicsPipeline {
 impl Drop for VulkanGraphicsPipeline {
     fn drop(&mut self) {
         unsafe {
+            for (_, result) in self.pending.wait_all() {
+                if let Ok((pipeline, _)) = result {
+                    self.device.destroy_pipeline(pipeline, None)
+                }
+            }
+
             for (_, pipeline) in self.pipelines.iter_mut() {
                 if *pipeline != vk::Pipeline::null() {
                     self.device.destroy_pipeline(*pipeline, None)
                 }
             }
 
+            for (_, pipeline) in self.external_pipelines.drain() {
+                self.device.destroy_pipeline(pipeline, None)
+            }
+
             if self.cache != vk::PipelineCache::null() {
                 self.device.destroy_pipeline_cache(self.cache, None)
             }
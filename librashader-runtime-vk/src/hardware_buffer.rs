@@ -0,0 +1,160 @@
+use crate::error;
+use crate::error::FilterChainError;
+use crate::filter_chain::VulkanObjects;
+use crate::memory::find_vulkan_memory_type;
+use crate::texture::VulkanImage;
+use ash::vk;
+use librashader_common::Size;
+use std::sync::Arc;
+
+/// A `VkImage` imported from an Android `AHardwareBuffer` without copying, for use as the
+/// input of a filter chain.
+///
+/// The image and its memory are released when this is dropped. The hardware buffer itself
+/// is owned by the frontend, and must outlive the image.
+pub struct HardwareBufferImage {
+    device: Arc<ash::Device>,
+    image: VulkanImage,
+    memory: vk::DeviceMemory,
+    external_format: u64,
+}
+
+impl HardwareBufferImage {
+    /// Import an `AHardwareBuffer` of the given size as a sampled image.
+    ///
+    /// ## Safety
+    /// - The device must have been created with the
+    ///   `VK_ANDROID_external_memory_android_hardware_buffer` extension enabled.
+    /// - `buffer` must be a valid `AHardwareBuffer` created with
+    ///   `AHARDWAREBUFFER_USAGE_GPU_SAMPLED_IMAGE`, and `size` must be its size.
+    pub(crate) unsafe fn import(
+        vulkan: &VulkanObjects,
+        buffer: *mut vk::AHardwareBuffer,
+        size: Size<u32>,
+    ) -> error::Result<HardwareBufferImage> {
+        let Some((instance, physical_device)) = &vulkan.physical_device else {
+            return Err(FilterChainError::HandleIsNull);
+        };
+
+        let loader = ash::android::external_memory_android_hardware_buffer::Device::new(
+            instance,
+            &vulkan.device,
+        );
+
+        let mut format_properties = vk::AndroidHardwareBufferFormatPropertiesANDROID::default();
+        let mut properties =
+            vk::AndroidHardwareBufferPropertiesANDROID::default().push_next(&mut format_properties);
+        unsafe {
+            loader.get_android_hardware_buffer_properties(buffer, &mut properties)?;
+        }
+        let allocation_size = properties.allocation_size;
+        let memory_type_bits = properties.memory_type_bits;
+
+        // buffers with an implementation-defined format have an undefined format,
+        // and can only be sampled through a Y'CbCr conversion of the external format.
+        let format = format_properties.format;
+        let external_format = if format == vk::Format::UNDEFINED {
+            format_properties.external_format
+        } else {
+            0
+        };
+
+        let mut external_memory_info = vk::ExternalMemoryImageCreateInfo::default()
+            .handle_types(vk::ExternalMemoryHandleTypeFlags::ANDROID_HARDWARE_BUFFER_ANDROID);
+        let mut external_format_info =
+            vk::ExternalFormatANDROID::default().external_format(external_format);
+
+        let image_create_info = vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .format(format)
+            .extent(size.into())
+            .mip_levels(1)
+            .array_layers(1)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .usage(vk::ImageUsageFlags::SAMPLED)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .push_next(&mut external_memory_info)
+            .push_next(&mut external_format_info);
+
+        let image = unsafe { vulkan.device.create_image(&image_create_info, None)? };
+
+        let memory_properties =
+            unsafe { instance.get_physical_device_memory_properties(*physical_device) };
+        let memory_type_index = match find_vulkan_memory_type(
+            &memory_properties,
+            memory_type_bits,
+            vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        ) {
+            Ok(index) => index,
+            Err(error) => {
+                unsafe { vulkan.device.destroy_image(image, None) };
+                return Err(error);
+            }
+        };
+
+        let mut import_info = vk::ImportAndroidHardwareBufferInfoANDROID::default().buffer(buffer);
+        let mut dedicated_info = vk::MemoryDedicatedAllocateInfo::default().image(image);
+        let allocate_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(allocation_size)
+            .memory_type_index(memory_type_index)
+            .push_next(&mut import_info)
+            .push_next(&mut dedicated_info);
+
+        let memory = match unsafe { vulkan.device.allocate_memory(&allocate_info, None) } {
+            Ok(memory) => memory,
+            Err(error) => {
+                unsafe { vulkan.device.destroy_image(image, None) };
+                return Err(error.into());
+            }
+        };
+
+        if let Err(error) = unsafe { vulkan.device.bind_image_memory(image, memory, 0) } {
+            unsafe {
+                vulkan.device.free_memory(memory, None);
+                vulkan.device.destroy_image(image, None);
+            }
+            return Err(error.into());
+        }
+
+        Ok(HardwareBufferImage {
+            device: Arc::clone(&vulkan.device),
+            image: VulkanImage {
+                image,
+                size,
+                format,
+            },
+            memory,
+            external_format,
+        })
+    }
+
+    /// Get the imported image to use as the input of a filter chain.
+    ///
+    /// The image is created in the `VK_IMAGE_LAYOUT_UNDEFINED` layout. Before it is used as
+    /// an input, it must be acquired from `VK_QUEUE_FAMILY_FOREIGN_EXT` and transitioned to
+    /// the `VK_SHADER_READ_ONLY_OPTIMAL` layout.
+    pub fn image(&self) -> &VulkanImage {
+        &self.image
+    }
+
+    /// Get the implementation-defined external format of the hardware buffer, or zero if the
+    /// format of the buffer has a `VkFormat` equivalent.
+    ///
+    /// Images with an external format can only be sampled through a sampler Y'CbCr conversion
+    /// created with a `VkExternalFormatANDROID` of this format, which must be provided
+    /// as the `ycbcr_conversion` of the filter chain.
+    pub fn external_format(&self) -> u64 {
+        self.external_format
+    }
+}
+
+impl Drop for HardwareBufferImage {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.destroy_image(self.image.image, None);
+            self.device.free_memory(self.memory, None);
+        }
+    }
+}
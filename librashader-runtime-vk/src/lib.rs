@@ -5,21 +5,28 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 #![cfg_attr(not(feature = "stable"), feature(type_alias_impl_trait))]
 
+mod compute_pipeline;
+mod device_context;
 mod draw_quad;
 mod filter_chain;
 mod filter_pass;
 mod framebuffer;
 mod graphics_pipeline;
+mod hardware_buffer;
 mod luts;
 mod memory;
 mod queue_selection;
 mod samplers;
+mod scene_analysis;
 mod texture;
 mod util;
 
+pub use device_context::DeviceContextVulkan;
 pub use filter_chain::FilterChainVulkan;
 pub use filter_chain::VulkanInstance;
 pub use filter_chain::VulkanObjects;
+pub use hardware_buffer::HardwareBufferImage;
+pub use luts::LutBundleVulkan;
 pub use texture::VulkanImage;
 
 use librashader_runtime::impl_filter_chain_parameters;
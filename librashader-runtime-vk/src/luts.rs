@@ -1,11 +1,17 @@
+use crate::error::FilterChainError;
 use crate::filter_chain::VulkanObjects;
 use crate::memory::{VulkanBuffer, VulkanImageMemory};
 use crate::texture::{InputImage, VulkanImage};
 use crate::{error, util};
 use ash::vk;
+use librashader_common::map::FastHashMap;
+use librashader_pack::TextureResource;
 use librashader_presets::TextureMeta;
-use librashader_runtime::image::{Image, BGRA8};
+use librashader_runtime::image::{Image, ImageError, LoadedTexture, UVDirection, BGRA8};
+use librashader_runtime::luts::LutBundle;
 use librashader_runtime::scaling::MipmapSize;
+use rayon::prelude::*;
+use std::sync::Arc;
 
 pub(crate) struct LutTexture {
     _memory: VulkanImageMemory,
@@ -20,9 +26,16 @@ impl LutTexture {
         image: Image<BGRA8>,
         config: &TextureMeta,
     ) -> error::Result<LutTexture> {
+        // sRGB textures are decoded to linear when sampled, so filtering happens in linear space.
+        let format = if config.srgb {
+            vk::Format::B8G8R8A8_SRGB
+        } else {
+            vk::Format::B8G8R8A8_UNORM
+        };
+
         let image_info = vk::ImageCreateInfo::default()
             .image_type(vk::ImageType::TYPE_2D)
-            .format(vk::Format::B8G8R8A8_UNORM)
+            .format(format)
             .extent(image.size.into())
             .mip_levels(if config.mipmap {
                 image.size.calculate_miplevels()
@@ -59,7 +72,7 @@ impl LutTexture {
 
         let view_info = vk::ImageViewCreateInfo::default()
             .view_type(vk::ImageViewType::TYPE_2D)
-            .format(vk::Format::B8G8R8A8_UNORM)
+            .format(format)
             .image(texture)
             .subresource_range(image_subresource)
             .components(swizzle_components);
@@ -216,7 +229,7 @@ impl LutTexture {
                 image: VulkanImage {
                     size: image.size,
                     image: texture,
-                    format: vk::Format::B8G8R8A8_UNORM,
+                    format,
                 },
                 filter_mode: config.filter_mode,
                 wrap_mode: config.wrap_mode,
@@ -231,3 +244,79 @@ impl AsRef<InputImage> for LutTexture {
         &self.image
     }
 }
+
+/// A set of LUT textures uploaded to a Vulkan device, that can be shared between filter chains
+/// created on the same device with the `lut_bundle` option.
+///
+/// Frontends that switch between presets sharing texture packs can keep a bundle around, so
+/// that textures are only uploaded once. Clones of a bundle share the same textures.
+#[derive(Clone, Debug, Default)]
+pub struct LutBundleVulkan(LutBundle<LutTexture>);
+
+impl LutBundleVulkan {
+    /// Create an empty bundle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of textures in the bundle.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the bundle has no textures.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Remove every texture from the bundle.
+    ///
+    /// Textures in use by filter chains are released once those filter chains are dropped.
+    pub fn clear(&self) {
+        self.0.clear()
+    }
+
+    /// Upload LUT textures to the bundle ahead of filter chain creation, such as the textures
+    /// of a `ShaderPresetPack`, deferring GPU-side initialization to the caller.
+    ///
+    /// Textures that are already in the bundle are not uploaded again.
+    ///
+    /// ## Safety
+    /// The provided command buffer must be ready for recording. The caller is responsible for
+    /// ending the command buffer and submitting it to a graphics queue. The command buffer must
+    /// be completely executed before any filter chain using the textures draws a frame.
+    pub unsafe fn preload_deferred<V, E>(
+        &self,
+        textures: Vec<TextureResource>,
+        vulkan: V,
+        cmd: vk::CommandBuffer,
+    ) -> error::Result<()>
+    where
+        V: TryInto<VulkanObjects, Error = E>,
+        FilterChainError: From<E>,
+    {
+        let vulkan: VulkanObjects = vulkan.try_into()?;
+        load_luts(&vulkan, cmd, textures, Some(self))?;
+        Ok(())
+    }
+}
+
+/// Load the LUT textures of a preset, reusing the textures in the bundle if one is given.
+pub(crate) fn load_luts(
+    vulkan: &VulkanObjects,
+    cmd: vk::CommandBuffer,
+    textures: Vec<TextureResource>,
+    bundle: Option<&LutBundleVulkan>,
+) -> error::Result<FastHashMap<usize, Arc<LutTexture>>> {
+    LutBundle::load(
+        bundle.map(|bundle| &bundle.0),
+        textures,
+        |textures| {
+            textures
+                .into_par_iter()
+                .map(|texture| LoadedTexture::from_texture(texture, UVDirection::TopLeft))
+                .collect::<Result<Vec<LoadedTexture<BGRA8>>, ImageError>>()
+        },
+        |LoadedTexture { meta, image }| LutTexture::new(vulkan, cmd, image, &meta),
+    )
+}
@@ -150,6 +150,10 @@ impl RawVulkanBuffer {
         Ok(RawVulkanBuffer { buffer, ptr })
     }
 
+    pub fn handle(&self) -> vk::Buffer {
+        self.buffer.handle
+    }
+
     pub fn bind_to_descriptor_set(
         &self,
         descriptor_set: vk::DescriptorSet,
@@ -201,7 +205,6 @@ impl DerefMut for RawVulkanBuffer {
     }
 }
 
-#[allow(unused)]
 pub fn find_vulkan_memory_type(
     props: &vk::PhysicalDeviceMemoryProperties,
     device_reqs: u32,
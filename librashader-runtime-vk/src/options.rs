@@ -1,7 +1,43 @@
 //! Vulkan shader runtime options.
 
+use crate::{DeviceContextVulkan, LutBundleVulkan};
+use ash::vk;
 use librashader_runtime::impl_default_frame_options;
-impl_default_frame_options!(FrameOptionsVulkan);
+use librashader_runtime::instance::LibrashaderInstance;
+use librashader_runtime::latency::LatencyMarkerCallback;
+use librashader_runtime::progress::{CancellationToken, ProgressCallback};
+impl_default_frame_options!(FrameOptionsVulkan, {
+    /// The layout the output images are in when the frame is recorded. The output images are
+    /// transitioned from this layout to `VK_IMAGE_LAYOUT_COLOR_ATTACHMENT_OPTIMAL` before they
+    /// are drawn to. If `VK_IMAGE_LAYOUT_UNDEFINED`, the output images must already be in
+    /// `VK_IMAGE_LAYOUT_COLOR_ATTACHMENT_OPTIMAL`. Default is `VK_IMAGE_LAYOUT_UNDEFINED`.
+    output_layout: vk::ImageLayout = vk::ImageLayout::UNDEFINED,
+    /// The layout to transition the output images to after the final pass, such as
+    /// `VK_IMAGE_LAYOUT_PRESENT_SRC_KHR`. If `VK_IMAGE_LAYOUT_UNDEFINED`, the output images are
+    /// left in `VK_IMAGE_LAYOUT_COLOR_ATTACHMENT_OPTIMAL`. Default is `VK_IMAGE_LAYOUT_UNDEFINED`.
+    final_output_layout: vk::ImageLayout = vk::ImageLayout::UNDEFINED,
+    /// A view of the output image for the final pass to draw to, such as a view that the
+    /// frontend already created for its swapchain image. If null, the filter chain creates a
+    /// view of the output image for the frame. The view must be a 2D view of the first mip level
+    /// and array layer of the output image, in the format of the output image.
+    ///
+    /// The view is only used when drawing to a single viewport. Default is null.
+    output_view: vk::ImageView = vk::ImageView::null(),
+    /// A render pass owned by the caller to draw the final pass in, instead of a render pass
+    /// begun by the filter chain. If not null, the final pass is recorded to
+    /// `final_command_buffer` instead of the command buffer of the frame, and `output_layout`
+    /// and `final_output_layout` are ignored. Default is null.
+    final_render_pass: vk::RenderPass = vk::RenderPass::null(),
+    /// The index of the subpass of `final_render_pass` to draw the final pass in. Default is 0.
+    final_subpass: u32 = 0,
+    /// A secondary command buffer to record the final pass to, if `final_render_pass` is not null.
+    ///
+    /// The command buffer must be in the recording state, begun with
+    /// `VK_COMMAND_BUFFER_USAGE_RENDER_PASS_CONTINUE_BIT` for `final_render_pass` and
+    /// `final_subpass`, and must be executed after the command buffer of the frame.
+    /// Default is null.
+    final_command_buffer: vk::CommandBuffer = vk::CommandBuffer::null(),
+});
 
 /// Options for filter chain creation.
 #[repr(C)]
@@ -14,8 +50,96 @@ pub struct FilterChainOptionsVulkan {
     /// Use dynamic rendering instead of explicit render pass objects.
     /// It is recommended if possible to use dynamic rendering,
     /// because render-pass mode will create new framebuffers per pass.
+    ///
+    /// Dynamic rendering requires the `dynamicRendering` feature of Vulkan 1.3, or of
+    /// `VK_KHR_dynamic_rendering` on Vulkan 1.2, in which case the device must be created with
+    /// the extension enabled. The API version of the device is the lower of the instance and
+    /// physical device API versions. If the device does not support dynamic rendering, render
+    /// pass objects are used instead, and an [`OptionWarning::DynamicRenderingUnsupported`]
+    /// warning is reported. The device is only checked if its instance and physical device
+    /// are known to the filter chain.
+    ///
+    /// [`OptionWarning::DynamicRenderingUnsupported`]: librashader_runtime::warnings::OptionWarning::DynamicRenderingUnsupported
     pub use_dynamic_rendering: bool,
     /// Disable the shader object cache. Shaders will be
     /// recompiled rather than loaded from the cache.
     pub disable_cache: bool,
+    /// The maximum number of history frames to keep. If zero, the number of history frames
+    /// is not capped. Shader passes that sample older frames receive the oldest kept frame.
+    pub max_history: u32,
+    /// The policy to follow when the framebuffer format of a shader pass is not supported.
+    /// 0 = fall back to a wider format, 1 = fall back to the smallest supported format,
+    /// 2 = fail filter chain creation. Default is 0.
+    pub format_fallback: u32,
+    /// A sampler Y'CbCr conversion for inputs in a Y'CbCr format, such as NV12 frames from
+    /// video decoders. If not null, input images are sampled through the conversion and
+    /// converted to RGB in an internal pass before the shader passes run.
+    ///
+    /// The conversion must have been created for the format of the input images.
+    pub ycbcr_conversion: vk::SamplerYcbcrConversion,
+    /// The sampler to sample input images through `ycbcr_conversion` with.
+    ///
+    /// This must be a sampler created with `ycbcr_conversion`, and must not be null if
+    /// `ycbcr_conversion` is not null.
+    pub ycbcr_sampler: vk::Sampler,
+    /// Disable history and feedback, for latency-sensitive modes such as runahead or netplay,
+    /// where frames are invalidated frequently. No history frames are kept, and shader passes
+    /// that sample history or feedback textures receive black textures instead.
+    pub disable_temporal: bool,
+    /// Freeze the `FrameCount` uniform while the `frame_direction` frame option is negative,
+    /// as RetroArch does while rewinding. Frontends that keep counting frames while rewinding
+    /// should enable this, so that shaders animated by the frame count do not jump.
+    pub freeze_frame_count_on_rewind: bool,
+    /// Build pipelines for new output formats on a worker thread instead of stalling the
+    /// frame that needs them, to keep frame pacing smooth when the output format changes.
+    /// Until the pipeline is built, the final pass is not drawn to outputs of that format,
+    /// which are left unchanged, and
+    /// [`FilterChainVulkan::has_pending_pipelines`](crate::FilterChainVulkan::has_pending_pipelines)
+    /// returns `true` after the frame is recorded. The pipeline is swapped in at the start of
+    /// a later frame.
+    pub async_pipeline_rebuild: bool,
+    /// Record CPU-side statistics of the commands recorded for every frame, such as the number
+    /// of draw calls, which can be read with `frame_statistics` after the frame is drawn.
+    /// This is meant for performance tuning and regression tracking, and has a small cost.
+    pub collect_statistics: bool,
+    /// A callback that is called as every shader pass of the preset is preprocessed, compiled,
+    /// and has its pipeline created, so that frontends can show the progress of loading
+    /// large presets.
+    pub progress: Option<ProgressCallback>,
+    /// A token to cancel filter chain creation with from another thread. If cancelled,
+    /// creation stops between shader passes and fails with
+    /// [`FilterChainError::Cancelled`](crate::error::FilterChainError::Cancelled).
+    pub cancel: Option<CancellationToken>,
+    /// Enable behaviour that is safe on implementations of `VK_KHR_portability_subset`,
+    /// such as MoltenVK. Framebuffer formats that can not be blitted or linearly filtered
+    /// by the device fall back to formats that can, as mipmap generation requires.
+    ///
+    /// This has no effect if the physical device is not known to the filter chain.
+    pub portability: bool,
+    /// A bundle of LUT textures to share with other filter chains on the same device.
+    /// LUT textures of the preset that are in the bundle are reused instead of uploaded again,
+    /// and the rest are uploaded and added to the bundle.
+    pub lut_bundle: Option<LutBundleVulkan>,
+    /// A device context to share samplers and LUT textures with other filter chains on the
+    /// same device. The device context must have been created for the same device.
+    /// If `lut_bundle` is also set, LUT textures are shared through `lut_bundle` instead.
+    pub device_context: Option<DeviceContextVulkan>,
+    /// Append a built-in pass after the passes of the preset that simulates or corrects for
+    /// colour vision deficiencies. The pass is controlled at runtime with the
+    /// `LIBRA_COLOR_ASSIST_MODE` (0 = off, 1 = protanopia, 2 = deuteranopia, 3 = tritanopia),
+    /// `LIBRA_COLOR_ASSIST_SIMULATE` and `LIBRA_COLOR_ASSIST_STRENGTH` parameters.
+    pub color_assist: bool,
+    /// Append a built-in pass after the passes of the preset that inserts black frames between
+    /// the subframes of every frame, for motion clarity on high refresh rate displays.
+    /// The pass follows the `current_subframe` and `total_subframes` frame options, and is
+    /// controlled at runtime with the `LIBRA_BFI_ENABLE`, `LIBRA_BFI_LIT_SUBFRAMES`,
+    /// `LIBRA_BFI_DARK_LEVEL` and `LIBRA_BFI_COMPENSATION` parameters.
+    pub black_frame_insertion: bool,
+    /// A callback that is called before and after the commands of every frame are recorded,
+    /// to forward to latency SDKs such as NVIDIA Reflex or AMD Anti-Lag.
+    pub latency_markers: Option<LatencyMarkerCallback>,
+    /// The librashader instance to create the filter chain from. Shaders are compiled on the
+    /// thread pool of the instance, with the cache directory and log callback of the instance.
+    /// If `None`, the global thread pool, default cache directory and global logger are used.
+    pub instance: Option<LibrashaderInstance>,
 }
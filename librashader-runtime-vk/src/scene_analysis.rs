@@ -0,0 +1,267 @@
+use crate::error;
+use crate::filter_chain::VulkanObjects;
+use crate::graphics_pipeline::{VulkanShaderModule, ENTRY_POINT};
+use crate::memory::RawVulkanBuffer;
+use crate::samplers::SamplerSet;
+use crate::texture::InputImage;
+use ash::vk;
+use librashader_common::{FilterMode, WrapMode};
+use librashader_runtime::binding::DEFAULT_SCENE_LUMINANCE;
+use std::sync::Arc;
+
+const SCENE_ANALYSIS_CS: &[u8] = include_bytes!("../shader/scene_analysis/scene_analysis.spv");
+
+/// The number of bins in the luminance histogram.
+const HISTOGRAM_BINS: usize = 64;
+
+/// The size of the `SceneStatistics` buffer: the luminance, followed by the histogram.
+const STATISTICS_SIZE: usize =
+    std::mem::size_of::<f32>() + HISTOGRAM_BINS * std::mem::size_of::<u32>();
+
+/// The built-in analysis stage that computes the `SceneLuminance` of the original input.
+///
+/// The analysis is dispatched once per frame into a host visible storage buffer per frame
+/// in flight. The result is read back when the buffer is next used, once the frame that
+/// wrote it is guaranteed to have completed.
+pub(crate) struct SceneAnalysis {
+    device: Arc<ash::Device>,
+    pipeline: vk::Pipeline,
+    layout: vk::PipelineLayout,
+    descriptor_set_layout: vk::DescriptorSetLayout,
+    pool: vk::DescriptorPool,
+    descriptor_sets: Vec<vk::DescriptorSet>,
+    statistics: Vec<RawVulkanBuffer>,
+}
+
+impl SceneAnalysis {
+    pub fn new(vulkan: &VulkanObjects, frames_in_flight: u32) -> error::Result<SceneAnalysis> {
+        let device = &vulkan.device;
+        let spirv: Vec<u32> = SCENE_ANALYSIS_CS
+            .chunks_exact(4)
+            .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+            .collect();
+
+        let bindings = [
+            vk::DescriptorSetLayoutBinding::default()
+                .binding(0)
+                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::COMPUTE),
+            vk::DescriptorSetLayoutBinding::default()
+                .binding(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::COMPUTE),
+        ];
+
+        let descriptor_set_layout = unsafe {
+            device.create_descriptor_set_layout(
+                &vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings),
+                None,
+            )?
+        };
+
+        let set_layouts = [descriptor_set_layout];
+        let layout = unsafe {
+            device.create_pipeline_layout(
+                &vk::PipelineLayoutCreateInfo::default().set_layouts(&set_layouts),
+                None,
+            )?
+        };
+
+        let pool_sizes = [
+            vk::DescriptorPoolSize {
+                ty: vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+                descriptor_count: frames_in_flight,
+            },
+            vk::DescriptorPoolSize {
+                ty: vk::DescriptorType::STORAGE_BUFFER,
+                descriptor_count: frames_in_flight,
+            },
+        ];
+
+        let pool = unsafe {
+            device.create_descriptor_pool(
+                &vk::DescriptorPoolCreateInfo::default()
+                    .max_sets(frames_in_flight)
+                    .pool_sizes(&pool_sizes),
+                None,
+            )?
+        };
+
+        let set_layouts = vec![descriptor_set_layout; frames_in_flight as usize];
+        let descriptor_sets = unsafe {
+            device.allocate_descriptor_sets(
+                &vk::DescriptorSetAllocateInfo::default()
+                    .descriptor_pool(pool)
+                    .set_layouts(&set_layouts),
+            )?
+        };
+
+        let module =
+            VulkanShaderModule::new(device, &vk::ShaderModuleCreateInfo::default().code(&spirv))?;
+
+        let stage = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::COMPUTE)
+            .name(ENTRY_POINT)
+            .module(module.shader);
+
+        let pipeline = unsafe {
+            // panic_safety: if this is successful this should return 1 pipelines.
+            device
+                .create_compute_pipelines(
+                    vk::PipelineCache::null(),
+                    &[vk::ComputePipelineCreateInfo::default()
+                        .stage(stage)
+                        .layout(layout)],
+                    None,
+                )
+                .map_err(|e| e.1)?[0]
+        };
+
+        let mut statistics = Vec::new();
+        for descriptor_set in &descriptor_sets {
+            let mut buffer = RawVulkanBuffer::new(
+                device,
+                &vulkan.alloc,
+                vk::BufferUsageFlags::STORAGE_BUFFER,
+                STATISTICS_SIZE,
+            )?;
+
+            // until the first analysis is read back, the luminance is middle grey.
+            buffer.fill(0);
+            buffer[0..4].copy_from_slice(&DEFAULT_SCENE_LUMINANCE.to_ne_bytes());
+
+            let buffer_info = [vk::DescriptorBufferInfo::default()
+                .buffer(buffer.handle())
+                .offset(0)
+                .range(STATISTICS_SIZE as vk::DeviceSize)];
+
+            let write_info = vk::WriteDescriptorSet::default()
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .dst_set(*descriptor_set)
+                .dst_binding(1)
+                .dst_array_element(0)
+                .buffer_info(&buffer_info);
+
+            unsafe {
+                device.update_descriptor_sets(&[write_info], &[]);
+            }
+            statistics.push(buffer);
+        }
+
+        Ok(SceneAnalysis {
+            device: Arc::clone(device),
+            pipeline,
+            layout,
+            descriptor_set_layout,
+            pool,
+            descriptor_sets,
+            statistics,
+        })
+    }
+
+    /// Read back the luminance of an earlier frame, and record the analysis of the original
+    /// input of this frame into the command buffer.
+    pub fn dispatch(
+        &self,
+        cmd: vk::CommandBuffer,
+        frame_index: usize,
+        original: &InputImage,
+        samplers: &SamplerSet,
+    ) -> f32 {
+        let index = frame_index % self.statistics.len();
+        let descriptor_set = self.descriptor_sets[index];
+        let statistics = &self.statistics[index];
+
+        let mut luminance = [0u8; 4];
+        luminance.copy_from_slice(&statistics[0..4]);
+        let luminance = f32::from_ne_bytes(luminance);
+
+        let sampler = samplers.get(
+            WrapMode::ClampToEdge,
+            FilterMode::Linear,
+            FilterMode::Nearest,
+        );
+        let image_info = [vk::DescriptorImageInfo::default()
+            .sampler(sampler.handle)
+            .image_view(original.image_view)
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)];
+
+        let write_info = vk::WriteDescriptorSet::default()
+            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .dst_set(descriptor_set)
+            .dst_binding(0)
+            .dst_array_element(0)
+            .image_info(&image_info);
+
+        let input_barrier = vk::MemoryBarrier::default()
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_access_mask(vk::AccessFlags::SHADER_READ);
+
+        let readback_barrier = vk::BufferMemoryBarrier::default()
+            .src_access_mask(vk::AccessFlags::SHADER_WRITE)
+            .dst_access_mask(vk::AccessFlags::HOST_READ)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .buffer(statistics.handle())
+            .offset(0)
+            .size(vk::WHOLE_SIZE);
+
+        unsafe {
+            self.device.update_descriptor_sets(&[write_info], &[]);
+
+            self.device.cmd_pipeline_barrier(
+                cmd,
+                vk::PipelineStageFlags::ALL_GRAPHICS,
+                vk::PipelineStageFlags::COMPUTE_SHADER,
+                vk::DependencyFlags::empty(),
+                &[input_barrier],
+                &[],
+                &[],
+            );
+
+            self.device
+                .cmd_bind_pipeline(cmd, vk::PipelineBindPoint::COMPUTE, self.pipeline);
+            self.device.cmd_bind_descriptor_sets(
+                cmd,
+                vk::PipelineBindPoint::COMPUTE,
+                self.layout,
+                0,
+                &[descriptor_set],
+                &[],
+            );
+            self.device.cmd_dispatch(cmd, 1, 1, 1);
+
+            self.device.cmd_pipeline_barrier(
+                cmd,
+                vk::PipelineStageFlags::COMPUTE_SHADER,
+                vk::PipelineStageFlags::HOST,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[readback_barrier],
+                &[],
+            );
+        }
+
+        if luminance.is_finite() {
+            luminance
+        } else {
+            DEFAULT_SCENE_LUMINANCE
+        }
+    }
+}
+
+impl Drop for SceneAnalysis {
+    fn drop(&mut self) {
+        unsafe {
+            if self.pipeline != vk::Pipeline::null() {
+                self.device.destroy_pipeline(self.pipeline, None);
+            }
+            self.device.destroy_pipeline_layout(self.layout, None);
+            self.device.destroy_descriptor_pool(self.pool, None);
+            self.device
+                .destroy_descriptor_set_layout(self.descriptor_set_layout, None);
+        }
+    }
+}
@@ -7,7 +7,7 @@ use parking_lot::Mutex;
 use std::sync::Arc;
 
 use crate::error::FilterChainError;
-use librashader_common::{FilterMode, GetSize, ImageFormat, Size, WrapMode};
+use librashader_common::{FilterMode, FormatFallback, GetSize, ImageFormat, Size, WrapMode};
 use librashader_presets::Scale2D;
 use librashader_runtime::scaling::{MipmapSize, ScaleFramebuffer, ViewportSize};
 
@@ -18,9 +18,56 @@ pub struct OwnedImage {
     pub image: VulkanImage,
     pub max_miplevels: u32,
     pub levels: u32,
+    pub storage: bool,
     pub _memory: VulkanImageMemory,
 }
 
+/// Whether images of this format can be written as storage images by compute passes.
+///
+/// These are the formats that every Vulkan implementation must support for
+/// `VK_FORMAT_FEATURE_STORAGE_IMAGE_BIT` with optimal tiling.
+pub(crate) fn supports_storage(format: ImageFormat) -> bool {
+    matches!(
+        format,
+        ImageFormat::Unknown
+            | ImageFormat::R8G8B8A8Unorm
+            | ImageFormat::R8G8B8A8Uint
+            | ImageFormat::R8G8B8A8Sint
+            | ImageFormat::R16G16B16A16Uint
+            | ImageFormat::R16G16B16A16Sint
+            | ImageFormat::R16G16B16A16Sfloat
+            | ImageFormat::R32Uint
+            | ImageFormat::R32Sint
+            | ImageFormat::R32Sfloat
+            | ImageFormat::R32G32Uint
+            | ImageFormat::R32G32Sint
+            | ImageFormat::R32G32Sfloat
+            | ImageFormat::R32G32B32A32Uint
+            | ImageFormat::R32G32B32A32Sint
+            | ImageFormat::R32G32B32A32Sfloat
+    )
+}
+
+/// Select the first fallback of the format under the fallback policy that the device supports
+/// with the given features for optimally tiled images.
+pub(crate) fn select_supported_format(
+    vulkan: &VulkanObjects,
+    format: ImageFormat,
+    policy: FormatFallback,
+    features: vk::FormatFeatureFlags,
+) -> Option<ImageFormat> {
+    let Some((instance, physical_device)) = &vulkan.physical_device else {
+        return Some(format);
+    };
+
+    format.select_supported(policy, |format| unsafe {
+        instance
+            .get_physical_device_format_properties(*physical_device, format.into())
+            .optimal_tiling_features
+            .contains(features)
+    })
+}
+
 #[derive(Clone)]
 pub struct OwnedImageLayout {
     pub(crate) dst_layout: vk::ImageLayout,
@@ -37,11 +84,22 @@ impl OwnedImage {
         size: Size<u32>,
         mut format: ImageFormat,
         max_miplevels: u32,
+        storage: bool,
     ) -> error::Result<OwnedImage> {
         // default to something sane
         if format == ImageFormat::Unknown {
             format = ImageFormat::R8G8B8A8Unorm
         }
+
+        let mut usage = vk::ImageUsageFlags::SAMPLED
+            | vk::ImageUsageFlags::COLOR_ATTACHMENT
+            | vk::ImageUsageFlags::TRANSFER_DST
+            | vk::ImageUsageFlags::TRANSFER_SRC;
+
+        // allow compute passes to write to their output if the format supports it.
+        if storage && supports_storage(format) {
+            usage |= vk::ImageUsageFlags::STORAGE;
+        }
         let image_create_info = vk::ImageCreateInfo::default()
             .image_type(vk::ImageType::TYPE_2D)
             .format(format.into())
@@ -50,13 +108,7 @@ impl OwnedImage {
             .array_layers(1)
             .samples(vk::SampleCountFlags::TYPE_1)
             .tiling(vk::ImageTiling::OPTIMAL)
-            .flags(vk::ImageCreateFlags::MUTABLE_FORMAT)
-            .usage(
-                vk::ImageUsageFlags::SAMPLED
-                    | vk::ImageUsageFlags::COLOR_ATTACHMENT
-                    | vk::ImageUsageFlags::TRANSFER_DST
-                    | vk::ImageUsageFlags::TRANSFER_SRC,
-            )
+            .usage(usage)
             .sharing_mode(vk::SharingMode::EXCLUSIVE)
             .initial_layout(vk::ImageLayout::UNDEFINED);
 
@@ -98,6 +150,7 @@ impl OwnedImage {
             _memory: memory,
             max_miplevels,
             levels: std::cmp::min(max_miplevels, size.calculate_miplevels()),
+            storage,
         })
     }
 
@@ -113,6 +166,24 @@ impl OwnedImage {
             size,
             format,
             max_miplevels,
+            false,
+        )
+    }
+
+    /// Create an image that compute passes can write to as a storage image.
+    pub(crate) fn new_storage(
+        vulkan: &VulkanObjects,
+        size: Size<u32>,
+        format: ImageFormat,
+        max_miplevels: u32,
+    ) -> error::Result<OwnedImage> {
+        Self::new_internal(
+            vulkan.device.clone(),
+            &vulkan.alloc,
+            size,
+            format,
+            max_miplevels,
+            true,
         )
     }
 
@@ -144,6 +215,7 @@ impl OwnedImage {
                     format
                 },
                 max_levels,
+                self.storage,
             )?;
 
             let old = std::mem::replace(self, new);
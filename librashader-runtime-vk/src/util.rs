@@ -11,6 +11,10 @@ pub fn binding_stage_to_vulkan_stage(stage_mask: BindingStage) -> vk::ShaderStag
         mask |= vk::ShaderStageFlags::FRAGMENT;
     }
 
+    if stage_mask.contains(BindingStage::COMPUTE) {
+        mask |= vk::ShaderStageFlags::COMPUTE;
+    }
+
     mask
 }
 
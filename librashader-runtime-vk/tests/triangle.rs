@@ -1,5 +1,6 @@
 mod hello_triangle;
 
+use ash::vk;
 use hello_triangle::vulkan_base::VulkanBase;
 use librashader_common::shader_features::ShaderFeatures;
 use librashader_runtime_vk::options::FilterChainOptionsVulkan;
@@ -23,6 +24,23 @@ fn triangle_vk() {
                 force_no_mipmaps: false,
                 use_dynamic_rendering: false,
                 disable_cache: true,
+                max_history: 0,
+                format_fallback: 0,
+                ycbcr_conversion: vk::SamplerYcbcrConversion::null(),
+                ycbcr_sampler: vk::Sampler::null(),
+                disable_temporal: false,
+                freeze_frame_count_on_rewind: false,
+                async_pipeline_rebuild: false,
+                collect_statistics: false,
+                progress: None,
+                cancel: None,
+                portability: false,
+                lut_bundle: None,
+                device_context: None,
+                color_assist: false,
+                black_frame_insertion: false,
+                latency_markers: None,
+                instance: None,
             }),
         )
         .unwrap();
@@ -0,0 +1,45 @@
+use crate::luts::LutBundleWgpu;
+use crate::mipmap::MipmapGen;
+use crate::samplers::SamplerSet;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+/// Device-level resources that can be shared between filter chains created on the same device
+/// with the `device_context` option.
+///
+/// A device context owns the samplers and mipmap pipelines of the filter chains, and a
+/// [`LutBundleWgpu`] for their LUT textures, so that frontends that run several filter chains
+/// at once do not create duplicate objects for each of them. Clones of a device context share
+/// the same resources.
+#[derive(Clone)]
+pub struct DeviceContextWgpu {
+    pub(crate) samplers: Arc<SamplerSet>,
+    pub(crate) mipmapper: Arc<Mutex<MipmapGen>>,
+    pub(crate) luts: LutBundleWgpu,
+}
+
+impl DeviceContextWgpu {
+    /// Create a device context for the given device.
+    pub fn new(device: &wgpu::Device) -> Self {
+        Self {
+            samplers: Arc::new(SamplerSet::new(device)),
+            mipmapper: Arc::new(Mutex::new(MipmapGen::new(device))),
+            luts: LutBundleWgpu::new(),
+        }
+    }
+
+    /// The bundle of LUT textures shared by filter chains created with this device context.
+    ///
+    /// Textures can be preloaded into the bundle before any filter chain is created.
+    pub fn lut_bundle(&self) -> &LutBundleWgpu {
+        &self.luts
+    }
+}
+
+impl Debug for DeviceContextWgpu {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeviceContextWgpu")
+            .field("luts", &self.luts)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,4 +1,5 @@
 use array_concat::concat_arrays;
+use librashader_runtime::mesh::OutputMesh;
 use librashader_runtime::quad::{QuadType, VertexInput};
 use wgpu::util::{BufferInitDescriptor, DeviceExt};
 use wgpu::{Buffer, Device, RenderPass};
@@ -45,6 +46,7 @@ static VBO_DATA: &[VertexInput; 8] = &concat_arrays!(OFFSCREEN_VBO_DATA, FINAL_V
 
 pub struct DrawQuad {
     buffer: Buffer,
+    mesh: Option<(Buffer, u32)>,
 }
 
 impl DrawQuad {
@@ -55,10 +57,28 @@ impl DrawQuad {
             usage: wgpu::BufferUsages::VERTEX,
         });
 
-        DrawQuad { buffer }
+        DrawQuad { buffer, mesh: None }
+    }
+
+    /// Set the mesh to draw the final pass with, or reset it to the default quad.
+    pub fn set_mesh(&mut self, device: &Device, mesh: Option<&OutputMesh>) {
+        self.mesh = mesh.map(|mesh| {
+            let buffer = device.create_buffer_init(&BufferInitDescriptor {
+                label: Some("librashader output mesh"),
+                contents: bytemuck::cast_slice(mesh.vertices()),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+            (buffer, mesh.vertex_count())
+        });
     }
 
     pub fn draw_quad<'a, 'b: 'a>(&'b self, cmd: &mut RenderPass<'a>, vbo: QuadType) {
+        if let (QuadType::Final, Some((mesh, count))) = (&vbo, &self.mesh) {
+            cmd.set_vertex_buffer(0, mesh.slice(0..));
+            cmd.draw(0..*count, 0..1);
+            return;
+        }
+
         cmd.set_vertex_buffer(0, self.buffer.slice(0..));
 
         let offset = match vbo {
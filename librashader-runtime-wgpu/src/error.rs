@@ -1,4 +1,5 @@
 //! wgpu shader runtime errors.
+use librashader_common::ImageFormat;
 use librashader_preprocess::PreprocessError;
 use librashader_presets::ParsePresetError;
 use librashader_reflect::error::{ShaderCompileError, ShaderReflectError};
@@ -19,6 +20,12 @@ pub enum FilterChainError {
     ShaderReflectError(#[from] ShaderReflectError),
     #[error("lut loading error")]
     LutLoadError(#[from] ImageError),
+    #[error("the framebuffer format {0:?} is not supported by the device")]
+    UnsupportedFormat(ImageFormat),
+    #[error("filter chain creation was cancelled")]
+    Cancelled,
+    #[error("could not spawn the worker thread to create the filter chain on")]
+    WorkerThread(#[source] std::io::Error),
     #[error("unreachable")]
     Infallible(#[from] std::convert::Infallible),
 }
@@ -6,9 +6,15 @@ use librashader_reflect::front::SpirvCompilation;
 use librashader_reflect::reflect::presets::{CompilePresetTarget, ShaderPassArtifact};
 use librashader_reflect::reflect::semantics::ShaderSemantics;
 use librashader_reflect::reflect::ReflectShader;
-use librashader_runtime::binding::BindingUtil;
-use librashader_runtime::image::{ImageError, LoadedTexture, UVDirection};
+use librashader_runtime::binding::{BindingUtil, UniformBindings};
+use librashader_runtime::instance::LibrashaderInstance;
+use librashader_runtime::jitter;
+use librashader_runtime::mesh::OutputMesh;
+use librashader_runtime::mvp;
+use librashader_runtime::pending::PendingFilterChain;
+use librashader_runtime::progress::{LoadStage, PassProgress};
 use librashader_runtime::quad::QuadType;
+use librashader_runtime::rewind::RewindFrameCount;
 use librashader_runtime::uniforms::UniformStorage;
 #[cfg(not(target_arch = "wasm32"))]
 use rayon::prelude::*;
@@ -16,15 +22,20 @@ use std::collections::VecDeque;
 use std::path::Path;
 
 use rayon::ThreadPoolBuilder;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
 
 use crate::buffer::WgpuStagedBuffer;
 use crate::draw_quad::DrawQuad;
-use librashader_common::{FilterMode, Size, Viewport, WrapMode};
+use librashader_common::{FilterMode, FormatFallback, ScalingKernel, Size, Viewport, WrapMode};
 use librashader_reflect::reflect::naga::{Naga, NagaLoweringOptions};
-use librashader_runtime::framebuffer::FramebufferInit;
+use librashader_runtime::filter_pass::{
+    requested_format, FilterPassMeta, IdentityPasses, PassInfo,
+};
+use librashader_runtime::framebuffer::{FramebufferInit, HistoryDepth};
 use librashader_runtime::render_target::RenderTarget;
 use librashader_runtime::scaling::ScaleFramebuffer;
+use librashader_runtime::statistics::{FrameStatistics, FrameStatisticsRecorder};
+use librashader_runtime::warnings::OptionWarning;
 use wgpu::{Device, TextureFormat};
 
 use crate::error;
@@ -32,11 +43,12 @@ use crate::error::FilterChainError;
 use crate::filter_pass::FilterPass;
 use crate::framebuffer::WgpuOutputView;
 use crate::graphics_pipeline::WgpuGraphicsPipeline;
-use crate::luts::LutTexture;
+use crate::luts::{self, LutTexture};
 use crate::mipmap::MipmapGen;
 use crate::options::{FilterChainOptionsWgpu, FrameOptionsWgpu};
 use crate::samplers::SamplerSet;
 use crate::texture::{InputImage, OwnedImage};
+use crate::DeviceContextWgpu;
 
 mod compile {
     use super::*;
@@ -64,35 +76,63 @@ mod compile {
 }
 
 use compile::{compile_passes, ShaderPassMeta};
-use librashader_pack::{ShaderPresetPack, TextureResource};
+use librashader_pack::{PassResource, ShaderPresetPack};
 use librashader_runtime::parameters::RuntimeParameters;
 
 /// A wgpu filter chain.
 pub struct FilterChainWgpu {
     pub(crate) common: FilterCommon,
     passes: Box<[FilterPass]>,
+    identity: IdentityPasses<FilterPass>,
+    pass_options: PassOptions,
     output_framebuffers: Box<[OwnedImage]>,
     feedback_framebuffers: Box<[OwnedImage]>,
     history_framebuffers: VecDeque<OwnedImage>,
+    history_depth: HistoryDepth,
+    option_warnings: Box<[OptionWarning]>,
     disable_mipmaps: bool,
-    mipmapper: MipmapGen,
+    mipmapper: Arc<Mutex<MipmapGen>>,
     default_frame_options: FrameOptionsWgpu,
     draw_last_pass_feedback: bool,
+    disable_temporal: bool,
+    rewind: RewindFrameCount,
+    invalidated_history: usize,
+}
+
+/// The options that the shader passes were created with, to create the identity passes with
+/// when they are first used.
+struct PassOptions {
+    adapter_info: Option<wgpu::AdapterInfo>,
+    disable_cache: bool,
+    format_fallback: FormatFallback,
 }
 
 pub(crate) struct FilterCommon {
     pub output_textures: Box<[Option<InputImage>]>,
     pub feedback_textures: Box<[Option<InputImage>]>,
     pub history_textures: Box<[Option<InputImage>]>,
-    pub luts: FastHashMap<usize, LutTexture>,
-    pub samplers: SamplerSet,
+    pub luts: FastHashMap<usize, Arc<LutTexture>>,
+    pub samplers: Arc<SamplerSet>,
     pub config: RuntimeParameters,
     pub(crate) draw_quad: DrawQuad,
     pub(crate) device: Arc<Device>,
     pub(crate) queue: Arc<wgpu::Queue>,
+    pub(crate) frame_jitter: [f32; 2],
+    pub statistics: FrameStatisticsRecorder,
 }
 
 impl FilterChainWgpu {
+    /// Create a filter chain without any shader passes, that only scales the input to the viewport.
+    ///
+    /// This is equivalent to loading a preset without any shader passes.
+    pub fn new_identity(
+        device: Arc<Device>,
+        queue: Arc<wgpu::Queue>,
+        options: Option<&FilterChainOptionsWgpu>,
+    ) -> error::Result<FilterChainWgpu> {
+        Self::load_from_pack(ShaderPresetPack::empty(), device, queue, options)
+    }
+
     /// Load the shader preset at the given path into a filter chain.
     pub fn load_from_path(
         path: impl AsRef<Path>,
@@ -118,6 +158,25 @@ impl FilterChainWgpu {
         Self::load_from_pack(preset, device, queue, options)
     }
 
+    /// Load a filter chain from a pre-parsed `ShaderPreset` on a worker thread.
+    ///
+    /// Shaders are compiled without blocking the calling thread, and the returned
+    /// [`PendingFilterChain`] can be polled or awaited for the filter chain. The progress
+    /// callback of the options is called on the worker thread.
+    ///
+    /// Returns an error if the worker thread could not be spawned.
+    pub fn load_from_preset_async(
+        preset: ShaderPreset,
+        device: Arc<Device>,
+        queue: Arc<wgpu::Queue>,
+        options: Option<FilterChainOptionsWgpu>,
+    ) -> error::Result<PendingFilterChain<error::Result<FilterChainWgpu>>> {
+        PendingFilterChain::spawn(move || {
+            Self::load_from_preset(preset, device, queue, options.as_ref())
+        })
+        .map_err(FilterChainError::WorkerThread)
+    }
+
     /// Load a filter chain from a pre-parsed and loaded `ShaderPresetPack`.
     pub fn load_from_pack(
         preset: ShaderPresetPack,
@@ -171,35 +230,87 @@ impl FilterChainWgpu {
     /// The caller is responsible for ending the command buffer and immediately submitting it to a
     /// graphics queue. The command buffer must be completely executed before calling [`frame`](Self::frame).
     pub fn load_from_pack_deferred(
-        preset: ShaderPresetPack,
+        mut preset: ShaderPresetPack,
         device: Arc<Device>,
         queue: Arc<wgpu::Queue>,
         cmd: &mut wgpu::CommandEncoder,
         options: Option<&FilterChainOptionsWgpu>,
     ) -> error::Result<FilterChainWgpu> {
+        let instance = options.and_then(|o| o.instance.as_ref());
+        let _instance = instance.map(LibrashaderInstance::enter);
+
+        if options.map_or(false, |o| o.color_assist) {
+            preset.append_color_assist();
+        }
+        if options.map_or(false, |o| o.black_frame_insertion) {
+            preset.append_black_frame_insertion();
+        }
+
+        let progress = PassProgress::new(
+            options.and_then(|o| o.progress.as_ref()),
+            preset.passes.len(),
+        )
+        .with_cancellation(options.and_then(|o| o.cancel.as_ref()));
+
         let (passes, semantics) = compile_passes(preset.passes, &preset.textures)?;
+        progress.report_all(LoadStage::Preprocessing);
+        if progress.is_cancelled() {
+            return Err(FilterChainError::Cancelled);
+        }
 
         // cache is opt-in for wgpu, not opt-out because of feature requirements.
         let disable_cache = options.map_or(true, |o| !o.enable_cache);
 
+        let parameters = RuntimeParameters::new(
+            preset.pass_count as usize,
+            preset.parameters,
+            passes
+                .iter()
+                .flat_map(|(pass, _)| pass.data.parameters.values()),
+        );
+
+        let pass_options = PassOptions {
+            adapter_info: options.and_then(|o| o.adapter_info.clone()),
+            disable_cache,
+            format_fallback: options.map_or(FormatFallback::default(), |o| {
+                FormatFallback::from(o.format_fallback)
+            }),
+        };
+
         // initialize passes
         let filters = Self::init_passes(
             &device,
             passes,
             &semantics,
-            options.and_then(|o| o.adapter_info.as_ref()),
+            &parameters,
+            pass_options.adapter_info.as_ref(),
             disable_cache,
+            pass_options.format_fallback,
+            progress,
+            instance,
         )?;
 
-        let samplers = SamplerSet::new(&device);
-        let mut mipmapper = MipmapGen::new(&device);
-        let luts = FilterChainWgpu::load_luts(
+        let device_context = options.and_then(|o| o.device_context.as_ref());
+        let (samplers, mipmapper) = match device_context {
+            Some(context) => (
+                Arc::clone(&context.samplers),
+                Arc::clone(&context.mipmapper),
+            ),
+            None => (
+                Arc::new(SamplerSet::new(&device)),
+                Arc::new(Mutex::new(MipmapGen::new(&device))),
+            ),
+        };
+        let luts = luts::load_luts(
             &device,
             &queue,
             cmd,
-            &mut mipmapper,
+            &mut mipmapper.lock().unwrap_or_else(PoisonError::into_inner),
             &samplers,
             preset.textures,
+            options
+                .and_then(|o| o.lut_bundle.as_ref())
+                .or(device_context.map(DeviceContextWgpu::lut_bundle)),
         )?;
         //
         let framebuffer_gen = || {
@@ -215,7 +326,15 @@ impl FilterChainWgpu {
             filters.iter().map(|f| &f.reflection.meta),
             &framebuffer_gen,
             &input_gen,
-        );
+        )
+        .with_max_history(options.map_or(0, |o| o.max_history as usize))
+        .with_temporal_disabled(options.map_or(false, |o| o.disable_temporal));
+        let history_depth = framebuffer_init.history_depth();
+        let mut option_warnings = framebuffer_init.option_warnings();
+        option_warnings.extend(OptionWarning::check_mipmap_input(
+            options.map(|f| f.force_no_mipmaps).unwrap_or(false),
+            filters.iter().map(|f| f.meta.mipmap_input),
+        ));
 
         //
         // // initialize output framebuffers
@@ -232,53 +351,40 @@ impl FilterChainWgpu {
 
         Ok(FilterChainWgpu {
             draw_last_pass_feedback: framebuffer_init.uses_final_pass_as_feedback(),
+            disable_temporal: options.map_or(false, |o| o.disable_temporal),
+            rewind: RewindFrameCount::new(
+                options.map_or(false, |o| o.freeze_frame_count_on_rewind),
+            ),
+            invalidated_history: 0,
             common: FilterCommon {
+                statistics: FrameStatisticsRecorder::new(
+                    options.map_or(false, |o| o.collect_statistics),
+                ),
                 luts,
                 samplers,
-                config: RuntimeParameters::new(preset.pass_count as usize, preset.parameters),
+                config: parameters,
                 draw_quad,
                 device,
                 queue,
                 output_textures,
                 feedback_textures,
                 history_textures,
+                frame_jitter: [0.0, 0.0],
             },
             passes: filters,
+            identity: IdentityPasses::default(),
+            pass_options,
             output_framebuffers,
             feedback_framebuffers,
             history_framebuffers,
+            history_depth,
+            option_warnings: option_warnings.into_boxed_slice(),
             disable_mipmaps: options.map(|f| f.force_no_mipmaps).unwrap_or(false),
             mipmapper,
             default_frame_options: Default::default(),
         })
     }
 
-    fn load_luts(
-        device: &wgpu::Device,
-        queue: &wgpu::Queue,
-        cmd: &mut wgpu::CommandEncoder,
-        mipmapper: &mut MipmapGen,
-        sampler_set: &SamplerSet,
-        textures: Vec<TextureResource>,
-    ) -> error::Result<FastHashMap<usize, LutTexture>> {
-        let mut luts = FastHashMap::default();
-
-        #[cfg(not(target_arch = "wasm32"))]
-        let images_iter = textures.into_par_iter();
-
-        #[cfg(target_arch = "wasm32")]
-        let images_iter = textures.into_iter();
-
-        let textures = images_iter
-            .map(|texture| LoadedTexture::from_texture(texture, UVDirection::TopLeft))
-            .collect::<Result<Vec<LoadedTexture>, ImageError>>()?;
-        for (index, LoadedTexture { meta, image }) in textures.into_iter().enumerate() {
-            let texture = LutTexture::new(device, queue, cmd, image, &meta, mipmapper, sampler_set);
-            luts.insert(index, texture);
-        }
-        Ok(luts)
-    }
-
     fn push_history(&mut self, input: &wgpu::Texture, cmd: &mut wgpu::CommandEncoder) {
         if let Some(mut back) = self.history_framebuffers.pop_back() {
             if back.image.size() != input.size() || input.format() != back.image.format() {
@@ -300,12 +406,43 @@ impl FilterChainWgpu {
         }
     }
 
+    /// Create the identity pass that scales its input to the viewport with the given kernel.
+    fn init_identity(
+        device: &wgpu::Device,
+        parameters: &RuntimeParameters,
+        options: &PassOptions,
+        kernel: ScalingKernel,
+    ) -> error::Result<FilterPass> {
+        let (passes, semantics) = compile_passes(vec![PassResource::identity(kernel)], &[])?;
+
+        let pass = Self::init_passes(
+            device,
+            passes,
+            &semantics,
+            parameters,
+            options.adapter_info.as_ref(),
+            options.disable_cache,
+            options.format_fallback,
+            PassProgress::default(),
+            None,
+        )?
+        .into_vec()
+        .remove(0);
+
+        Ok(pass)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn init_passes(
         device: &wgpu::Device,
         passes: Vec<ShaderPassMeta>,
         semantics: &ShaderSemantics,
+        parameters: &RuntimeParameters,
         adapter_info: Option<&wgpu::AdapterInfo>,
         disable_cache: bool,
+        format_fallback: FormatFallback,
+        progress: PassProgress,
+        instance: Option<&LibrashaderInstance>,
     ) -> error::Result<Box<[FilterPass]>> {
         #[cfg(not(target_arch = "wasm32"))]
         let filter_creation_fn = || {
@@ -316,11 +453,16 @@ impl FilterChainWgpu {
             let filters: Vec<error::Result<FilterPass>> = passes_iter
                 .enumerate()
                 .map(|(index, (config, mut reflect))| {
+                    if progress.is_cancelled() {
+                        return Err(FilterChainError::Cancelled);
+                    }
+
                     let reflection = reflect.reflect(index, semantics)?;
                     let wgsl = reflect.compile(NagaLoweringOptions {
                         write_pcb_as_ubo: true,
                         sampler_bind_group: 1,
                     })?;
+                    progress.report(LoadStage::Compiling, index);
 
                     let ubo_size = reflection.ubo.as_ref().map_or(0, |ubo| ubo.size as usize);
                     let push_size = reflection
@@ -343,15 +485,26 @@ impl FilterChainWgpu {
                         ),
                     );
 
-                    let uniform_bindings =
-                        reflection.meta.create_binding_map(|param| param.offset());
+                    let uniform_bindings = UniformBindings::new(
+                        reflection.meta.create_binding_map(|param| param.offset()),
+                        parameters,
+                    );
 
-                    let render_pass_format: Option<TextureFormat> =
-                        if let Some(format) = config.meta.get_format_override() {
-                            format.into()
-                        } else {
-                            config.data.format.into()
-                        };
+                    let requested_format = requested_format(&config.meta, config.data.format);
+                    let format = requested_format
+                        .select_supported(format_fallback, |format| {
+                            Option::<TextureFormat>::from(format).is_some_and(|format| {
+                                format
+                                    .guaranteed_format_features(device.features())
+                                    .allowed_usages
+                                    .contains(
+                                        wgpu::TextureUsages::RENDER_ATTACHMENT
+                                            | wgpu::TextureUsages::TEXTURE_BINDING,
+                                    )
+                            })
+                        })
+                        .ok_or(FilterChainError::UnsupportedFormat(requested_format))?;
+                    let render_pass_format: Option<TextureFormat> = format.into();
 
                     let graphics_pipeline = WgpuGraphicsPipeline::new(
                         &device,
@@ -361,6 +514,7 @@ impl FilterChainWgpu {
                         adapter_info,
                         disable_cache,
                     );
+                    progress.report(LoadStage::Pipeline, index);
 
                     Ok(FilterPass {
                         reflection,
@@ -368,6 +522,8 @@ impl FilterChainWgpu {
                         uniform_bindings,
                         source: config.data,
                         meta: config.meta,
+                        path: config.path,
+                        format,
                         graphics_pipeline,
                     })
                 })
@@ -376,10 +532,16 @@ impl FilterChainWgpu {
         };
 
         #[cfg(target_arch = "wasm32")]
-        let filters = filter_creation_fn();
+        let filters = {
+            let _ = instance;
+            filter_creation_fn()
+        };
 
         #[cfg(not(target_arch = "wasm32"))]
-        let filters = if let Ok(thread_pool) = ThreadPoolBuilder::new()
+        let filters = if let Some(instance) = instance {
+            // the stack size of the compile threads is up to the instance.
+            instance.install(filter_creation_fn)
+        } else if let Ok(thread_pool) = ThreadPoolBuilder::new()
             // naga compilations can possibly use degenerate stack sizes.
             .stack_size(10 * 1048576)
             .build()
@@ -394,6 +556,44 @@ impl FilterChainWgpu {
         Ok(filters.into_boxed_slice())
     }
 
+    /// Get the depth of the history kept by the filter chain, and the depth
+    /// requested by the shader passes.
+    pub fn history_depth(&self) -> HistoryDepth {
+        self.history_depth
+    }
+
+    /// Get the warnings about options of the filter chain that could not be honoured as
+    /// given, such as mipmaps being forced off for shader passes that request them.
+    pub fn option_warnings(&self) -> &[OptionWarning] {
+        &self.option_warnings
+    }
+
+    /// Invalidate the last `frames` frames of history, such as after a rollback in runahead
+    /// or netplay, so that shader passes do not blend frames that were rolled back.
+    ///
+    /// When the next frame is drawn, the invalidated history frames are cleared to black, as is
+    /// the feedback of the previous frame. Unlike `clear_history`, older history frames are kept.
+    pub fn invalidate_history(&mut self, frames: usize) {
+        self.invalidated_history = std::cmp::max(self.invalidated_history, frames);
+    }
+
+    /// Get information about the shader pass at the given index, including the
+    /// framebuffer format chosen for the pass.
+    pub fn pass_info(&self, index: usize) -> Option<PassInfo> {
+        self.passes.get(index).map(FilterPassMeta::pass_info)
+    }
+
+    /// Get the statistics of the commands recorded for the last frame, or `None` if the
+    /// filter chain was not created with the `collect_statistics` option.
+    pub fn frame_statistics(&self) -> Option<FrameStatistics> {
+        self.common.statistics.statistics()
+    }
+
+    /// Set the mesh to draw the final pass with, or reset it to the default quad if `None`.
+    pub fn set_output_mesh(&mut self, mesh: Option<&OutputMesh>) {
+        self.common.draw_quad.set_mesh(&self.common.device, mesh)
+    }
+
     /// Records shader rendering commands to the provided command encoder.
     pub fn frame<'a>(
         &mut self,
@@ -403,8 +603,34 @@ impl FilterChainWgpu {
         frame_count: usize,
         options: Option<&FrameOptionsWgpu>,
     ) -> error::Result<()> {
+        self.frame_broadcast(
+            input,
+            std::slice::from_ref(viewport),
+            cmd,
+            frame_count,
+            options,
+        )
+    }
+
+    /// Records shader rendering commands to the provided command encoder, drawing the final pass
+    /// to every viewport.
+    ///
+    /// Intermediate passes are scaled relative to the first viewport and are only run once.
+    /// If `viewports` is empty, nothing is recorded.
+    pub fn frame_broadcast<'a>(
+        &mut self,
+        input: Arc<wgpu::Texture>,
+        viewports: &[Viewport<WgpuOutputView<'a>>],
+        cmd: &mut wgpu::CommandEncoder,
+        frame_count: usize,
+        options: Option<&FrameOptionsWgpu>,
+    ) -> error::Result<()> {
+        // passes are scaled relative to the first viewport.
+        let Some(viewport) = viewports.first() else {
+            return Ok(());
+        };
+
         let max = std::cmp::min(self.passes.len(), self.common.config.passes_enabled());
-        let passes = &mut self.passes[0..max];
 
         if let Some(options) = &options {
             if options.clear_history {
@@ -414,10 +640,58 @@ impl FilterChainWgpu {
             }
         }
 
-        if passes.is_empty() {
-            return Ok(());
+        // with history and feedback disabled, the history is kept black.
+        if self.disable_temporal {
+            for history in &mut self.history_framebuffers {
+                history.clear(cmd);
+            }
+        }
+
+        // clear the history frames invalidated since the last frame.
+        let invalidated_history = std::mem::take(&mut self.invalidated_history);
+        for history in self
+            .history_framebuffers
+            .iter_mut()
+            .take(invalidated_history)
+        {
+            history.clear(cmd);
         }
 
+        let options = options.unwrap_or(&self.default_frame_options);
+        self.common.statistics.reset();
+        let frame_count = self
+            .rewind
+            .frame_count(frame_count, options.frame_direction);
+        self.common.frame_jitter = jitter::resolve(
+            options.jitter_sequence.into(),
+            options.jitter_offset,
+            frame_count,
+        );
+
+        // the identity pass draws to the viewport instead of the final pass if there are no
+        // enabled passes, or if the output of the final pass is scaled to the viewport.
+        let identity = if max == 0 || options.scale_final_pass {
+            Some(
+                self.identity
+                    .get_or_try_init(options.identity_scaling, |kernel| {
+                        Self::init_identity(
+                            &self.common.device,
+                            &self.common.config,
+                            &self.pass_options,
+                            kernel,
+                        )
+                    })?,
+            )
+        } else {
+            None
+        };
+
+        let scale_final_pass = max > 0 && identity.is_some();
+        let (passes, final_scaling) = match identity {
+            Some(identity) if max == 0 => (std::slice::from_mut(identity), None),
+            identity => (&mut self.passes[0..max], identity),
+        };
+
         let original_image_view = input.create_view(&wgpu::TextureViewDescriptor::default());
 
         let filter = passes[0].meta.filter;
@@ -444,10 +718,12 @@ impl FilterChainWgpu {
         let mut source = original.clone();
 
         // swap output and feedback **before** recording command buffers
-        std::mem::swap(
-            &mut self.output_framebuffers,
-            &mut self.feedback_framebuffers,
-        );
+        if !self.disable_temporal {
+            std::mem::swap(
+                &mut self.output_framebuffers,
+                &mut self.feedback_framebuffers,
+            );
+        }
 
         // rescale render buffers to ensure all bindings are valid.
         OwnedImage::scale_framebuffers_with_context(
@@ -471,10 +747,19 @@ impl FilterChainWgpu {
             }),
         )?;
 
-        let passes_len = passes.len();
-        let (pass, last) = passes.split_at_mut(passes_len - 1);
+        // feedback is kept black, including after the feedback framebuffers are resized.
+        // the feedback of the previous frame is also cleared if it was invalidated.
+        if self.disable_temporal || invalidated_history > 0 {
+            for feedback in &self.feedback_framebuffers {
+                feedback.clear(cmd);
+            }
+        }
 
-        let options = options.unwrap_or(&self.default_frame_options);
+        let passes_len = passes.len();
+        let (pass, last) = match final_scaling {
+            Some(identity) => (passes, std::slice::from_mut(identity)),
+            None => passes.split_at_mut(passes_len - 1),
+        };
 
         for (index, pass) in pass.iter_mut().enumerate() {
             source.filter_mode = pass.meta.filter;
@@ -505,7 +790,11 @@ impl FilterChainWgpu {
                     FilterMode::Nearest,
                 );
 
-                target.generate_mipmaps(&self.common.device, cmd, &mut self.mipmapper, &sampler);
+                let mut mipmapper = self
+                    .mipmapper
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner);
+                target.generate_mipmaps(&self.common.device, cmd, &mut mipmapper, &sampler);
             }
 
             source = self.common.output_textures[index].clone().unwrap();
@@ -516,20 +805,25 @@ impl FilterChainWgpu {
 
         if let Some(pass) = last.iter_mut().next() {
             let index = passes_len - 1;
-            if !pass.graphics_pipeline.has_format(viewport.output.format) {
-                // need to recompile
-                pass.graphics_pipeline
-                    .recompile(&self.common.device, viewport.output.format);
+            for viewport in viewports {
+                if !pass.graphics_pipeline.has_format(viewport.output.format) {
+                    // need to recompile
+                    pass.graphics_pipeline
+                        .recompile(&self.common.device, viewport.output.format);
+                }
             }
 
             source.filter_mode = pass.meta.filter;
             source.wrap_mode = pass.meta.wrap_mode;
             source.mip_filter = pass.meta.filter;
 
-            if self.draw_last_pass_feedback {
+            // the output of the final pass is already drawn if it is scaled to the viewport.
+            if self.draw_last_pass_feedback && !scale_final_pass {
                 let target = &self.output_framebuffers[index];
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
                 let output_image = WgpuOutputView::from(target);
-                let out = RenderTarget::viewport_with_output(&output_image, viewport);
+                let out = RenderTarget::viewport_with_output(&output_image, viewport, &final_mvp);
 
                 pass.draw(
                     cmd,
@@ -545,22 +839,28 @@ impl FilterChainWgpu {
                 )?;
             }
 
-            let out = RenderTarget::viewport(viewport);
-            pass.draw(
-                cmd,
-                index,
-                &self.common,
-                pass.meta.get_frame_count(frame_count),
-                options,
-                viewport,
-                &original,
-                &source,
-                &out,
-                QuadType::Final,
-            )?;
+            for viewport in viewports {
+                let final_mvp =
+                    mvp::resolve(viewport.mvp, options.transpose_mvp, options.flip_mvp_y);
+                let out = RenderTarget::viewport(viewport, &final_mvp);
+                pass.draw(
+                    cmd,
+                    index,
+                    &self.common,
+                    pass.meta.get_frame_count(frame_count),
+                    options,
+                    viewport,
+                    &original,
+                    &source,
+                    &out,
+                    QuadType::Final,
+                )?;
+            }
         }
 
-        self.push_history(&input, cmd);
+        if !self.disable_temporal {
+            self.push_history(&input, cmd);
+        }
         Ok(())
     }
 }
@@ -10,15 +10,16 @@ use librashader_common::map::FastHashMap;
 use librashader_common::{ImageFormat, Size, Viewport};
 use librashader_preprocess::ShaderSource;
 use librashader_presets::PassMeta;
-use librashader_reflect::reflect::semantics::{
-    BindingStage, MemberOffset, TextureBinding, UniformBinding,
-};
+use librashader_reflect::reflect::semantics::{BindingStage, MemberOffset, TextureBinding};
 use librashader_reflect::reflect::ShaderReflection;
-use librashader_runtime::binding::{BindSemantics, TextureInput, UniformInputs};
+use librashader_runtime::binding::{
+    BindSemantics, TextureInput, UniformBindings, UniformInputs, DEFAULT_SCENE_LUMINANCE,
+};
 use librashader_runtime::filter_pass::FilterPassMeta;
 use librashader_runtime::quad::QuadType;
 use librashader_runtime::render_target::RenderTarget;
 use librashader_runtime::uniforms::{NoUniformBinder, UniformStorage, UniformStorageAccess};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use wgpu::{BindGroupDescriptor, BindGroupEntry, BindingResource, BufferBinding, ShaderStages};
 
@@ -31,9 +32,13 @@ pub struct FilterPass {
         WgpuStagedBuffer,
         wgpu::Device,
     >,
-    pub uniform_bindings: FastHashMap<UniformBinding, MemberOffset>,
+    pub uniform_bindings: UniformBindings<MemberOffset>,
     pub source: ShaderSource,
     pub meta: PassMeta,
+    pub path: Option<PathBuf>,
+    /// The framebuffer format of the pass, after falling back to a format supported
+    /// by the device.
+    pub format: ImageFormat,
     pub graphics_pipeline: WgpuGraphicsPipeline,
 }
 
@@ -101,6 +106,8 @@ impl FilterPass {
         output: &RenderTarget<WgpuOutputView>,
         vbo_type: QuadType,
     ) -> error::Result<()> {
+        parent.statistics.record_pass(&self.reflection);
+
         let mut main_heap = FastHashMap::default();
         let mut sampler_heap = FastHashMap::default();
 
@@ -231,6 +238,8 @@ impl FilterPass {
                 frametime_delta: options.frametime_delta,
                 framebuffer_size: fb_size,
                 viewport_size,
+                scene_luminance: DEFAULT_SCENE_LUMINANCE,
+                frame_jitter: parent.frame_jitter,
             },
             original,
             source,
@@ -241,8 +250,7 @@ impl FilterPass {
                 .map(|o| o.as_ref()),
             parent.feedback_textures.iter().map(|o| o.as_ref()),
             parent.history_textures.iter().map(|o| o.as_ref()),
-            parent.luts.iter().map(|(u, i)| (*u, i.as_ref())),
-            &self.source.parameters,
+            parent.luts.iter().map(|(u, i)| (*u, (**i).as_ref())),
             &parent.config,
         );
 
@@ -260,4 +268,12 @@ impl FilterPassMeta for FilterPass {
     fn meta(&self) -> &PassMeta {
         &self.meta
     }
+
+    fn source_path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn get_supported_format(&self) -> ImageFormat {
+        self.format
+    }
 }
@@ -6,6 +6,7 @@
 #![cfg_attr(not(feature = "stable"), feature(type_alias_impl_trait))]
 
 mod buffer;
+mod device_context;
 mod draw_quad;
 mod filter_chain;
 mod filter_pass;
@@ -18,8 +19,10 @@ mod samplers;
 mod texture;
 mod util;
 
+pub use device_context::DeviceContextWgpu;
 pub use filter_chain::FilterChainWgpu;
 pub use framebuffer::WgpuOutputView;
+pub use luts::LutBundleWgpu;
 
 pub mod error;
 pub mod options;
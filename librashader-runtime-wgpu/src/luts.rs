@@ -1,10 +1,17 @@
+use crate::error;
+use crate::error::FilterChainError;
 use crate::mipmap::MipmapGen;
 use crate::samplers::SamplerSet;
 use crate::texture::InputImage;
+use librashader_common::map::FastHashMap;
 use librashader_common::{Size, WrapMode};
+use librashader_pack::TextureResource;
 use librashader_presets::TextureMeta;
-use librashader_runtime::image::Image;
+use librashader_runtime::image::{Image, ImageError, LoadedTexture, UVDirection};
+use librashader_runtime::luts::LutBundle;
 use librashader_runtime::scaling::MipmapSize;
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
 use std::sync::Arc;
 use wgpu::TextureDescriptor;
 
@@ -25,6 +32,13 @@ impl LutTexture {
         mipmapper: &mut MipmapGen,
         sampler_set: &SamplerSet,
     ) -> LutTexture {
+        // sRGB textures are decoded to linear when sampled, so filtering happens in linear space.
+        let format = if config.srgb {
+            wgpu::TextureFormat::Rgba8UnormSrgb
+        } else {
+            wgpu::TextureFormat::Rgba8Unorm
+        };
+
         let texture = device.create_texture(&TextureDescriptor {
             label: Some(&config.name),
             size: image.size.into(),
@@ -35,12 +49,12 @@ impl LutTexture {
             },
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8Unorm,
+            format,
             usage: wgpu::TextureUsages::TEXTURE_BINDING
                 | wgpu::TextureUsages::COPY_DST
                 // need render attachment for mipmaps...
                 | wgpu::TextureUsages::RENDER_ATTACHMENT,
-            view_formats: &[wgpu::TextureFormat::Rgba8Unorm],
+            view_formats: &[format],
         });
 
         queue.write_texture(
@@ -86,3 +100,100 @@ impl LutTexture {
         Self(image)
     }
 }
+
+/// A set of LUT textures uploaded to a wgpu device, that can be shared between filter chains
+/// created on the same device with the `lut_bundle` option.
+///
+/// Frontends that switch between presets sharing texture packs can keep a bundle around, so
+/// that textures are only uploaded once. Clones of a bundle share the same textures.
+#[derive(Clone, Debug, Default)]
+pub struct LutBundleWgpu(LutBundle<LutTexture>);
+
+impl LutBundleWgpu {
+    /// Create an empty bundle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of textures in the bundle.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the bundle has no textures.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Remove every texture from the bundle.
+    ///
+    /// Textures in use by filter chains are released once those filter chains are dropped.
+    pub fn clear(&self) {
+        self.0.clear()
+    }
+
+    /// Upload LUT textures to the bundle ahead of filter chain creation, such as the textures
+    /// of a `ShaderPresetPack`.
+    ///
+    /// Textures that are already in the bundle are not uploaded again. Mipmaps are generated
+    /// with the provided command encoder, which must be submitted before any filter chain using
+    /// the textures draws a frame.
+    pub fn preload(
+        &self,
+        textures: Vec<TextureResource>,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        cmd: &mut wgpu::CommandEncoder,
+    ) -> error::Result<()> {
+        let mut mipmapper = MipmapGen::new(device);
+        let samplers = SamplerSet::new(device);
+        load_luts(
+            device,
+            queue,
+            cmd,
+            &mut mipmapper,
+            &samplers,
+            textures,
+            Some(self),
+        )?;
+        Ok(())
+    }
+}
+
+/// Load the LUT textures of a preset, reusing the textures in the bundle if one is given.
+pub(crate) fn load_luts(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    cmd: &mut wgpu::CommandEncoder,
+    mipmapper: &mut MipmapGen,
+    sampler_set: &SamplerSet,
+    textures: Vec<TextureResource>,
+    bundle: Option<&LutBundleWgpu>,
+) -> error::Result<FastHashMap<usize, Arc<LutTexture>>> {
+    LutBundle::load(
+        bundle.map(|bundle| &bundle.0),
+        textures,
+        |textures| {
+            #[cfg(not(target_arch = "wasm32"))]
+            let images_iter = textures.into_par_iter();
+
+            #[cfg(target_arch = "wasm32")]
+            let images_iter = textures.into_iter();
+
+            images_iter
+                .map(|texture| LoadedTexture::from_texture(texture, UVDirection::TopLeft))
+                .collect::<Result<Vec<LoadedTexture>, ImageError>>()
+        },
+        |LoadedTexture { meta, image }| {
+            Ok::<_, FilterChainError>(LutTexture::new(
+                device,
+                queue,
+                cmd,
+                image,
+                &meta,
+                mipmapper,
+                sampler_set,
+            ))
+        },
+    )
+}
@@ -0,0 +1,220 @@
+//! Micro-benchmarks for the per-frame hot paths shared by every runtime.
+//!
+//! Run with `cargo bench -p librashader-runtime`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use image::RgbaImage;
+use librashader_common::map::{FastHashMap, ShortString};
+use librashader_common::Size;
+use librashader_pack::TextureBuffer;
+use librashader_presets::{ParameterMeta, Scale2D, ScaleFactor, ScaleType, Scaling};
+use librashader_reflect::reflect::semantics::{
+    MemberOffset, Semantic, TextureSemantics, UniformBinding, UniqueSemantics,
+};
+use librashader_runtime::binding::UniformBindings;
+use librashader_runtime::image::{Image, UVDirection, BGRA8, RGBA8};
+use librashader_runtime::parameters::RuntimeParameters;
+use librashader_runtime::scaling::{MipmapSize, ViewportSize};
+use librashader_runtime::uniforms::{NoUniformBinder, UniformStorage};
+
+const UNIQUE_SEMANTICS: [UniqueSemantics; 6] = [
+    UniqueSemantics::MVP,
+    UniqueSemantics::Output,
+    UniqueSemantics::FinalViewport,
+    UniqueSemantics::FrameCount,
+    UniqueSemantics::FrameDirection,
+    UniqueSemantics::Rotation,
+];
+
+const TEXTURE_SEMANTICS: [TextureSemantics; 4] = [
+    TextureSemantics::Original,
+    TextureSemantics::Source,
+    TextureSemantics::OriginalHistory,
+    TextureSemantics::PassOutput,
+];
+
+const PARAMETER_COUNT: usize = 32;
+
+fn ubo_offset(offset: usize) -> MemberOffset {
+    MemberOffset {
+        ubo: Some(offset),
+        push: None,
+    }
+}
+
+fn parameter_name(index: usize) -> ShortString {
+    ShortString::from(format!("PARAMETER_{index}").as_str())
+}
+
+fn runtime_parameters() -> RuntimeParameters {
+    let parameters = (0..PARAMETER_COUNT)
+        .map(|index| ParameterMeta {
+            name: parameter_name(index),
+            value: index as f32,
+        })
+        .collect();
+    RuntimeParameters::new(1, parameters, [])
+}
+
+// A uniform binding map shaped like one of a typical pass.
+fn uniform_bindings() -> FastHashMap<UniformBinding, MemberOffset> {
+    let mut bindings = FastHashMap::default();
+    let mut offset = 0;
+    for semantic in UNIQUE_SEMANTICS {
+        bindings.insert(semantic.into(), ubo_offset(offset));
+        offset += 64;
+    }
+    for semantics in TEXTURE_SEMANTICS {
+        for index in 0..4 {
+            bindings.insert(
+                UniformBinding::TextureSize(Semantic { semantics, index }),
+                ubo_offset(offset),
+            );
+            offset += 16;
+        }
+    }
+    for index in 0..PARAMETER_COUNT {
+        bindings.insert(
+            UniformBinding::Parameter(parameter_name(index)),
+            ubo_offset(offset),
+        );
+        offset += 4;
+    }
+    bindings
+}
+
+fn bench_uniform_binding(c: &mut Criterion) {
+    let mut storage = UniformStorage::<NoUniformBinder, Option<()>>::new(1024, 128);
+    let mvp = [0.5f32; 16];
+
+    c.bench_function("uniforms/bind_mat4", |b| {
+        b.iter(|| storage.bind_mat4(ubo_offset(0), black_box(&mvp), None, &()))
+    });
+
+    c.bench_function("uniforms/bind_vec4", |b| {
+        b.iter(|| storage.bind_vec4(ubo_offset(64), black_box([1.0, 2.0, 3.0, 4.0]), None, &()))
+    });
+
+    c.bench_function("uniforms/bind_scalar", |b| {
+        b.iter(|| storage.bind_scalar(ubo_offset(128), black_box(1.0f32), None, &()))
+    });
+
+    c.bench_function("uniforms/bind_pass", |b| {
+        b.iter(|| {
+            storage.bind_mat4(ubo_offset(0), &mvp, None, &());
+            for index in 0..16 {
+                storage.bind_vec4(ubo_offset(64 + index * 16), [1.0; 4], None, &());
+            }
+            for index in 0..PARAMETER_COUNT {
+                storage.bind_scalar(ubo_offset(320 + index * 4), index as f32, None, &());
+            }
+        })
+    });
+}
+
+fn bench_semantic_lookup(c: &mut Criterion) {
+    let parameters = runtime_parameters();
+    let bindings = UniformBindings::new(uniform_bindings(), &parameters);
+
+    c.bench_function("semantics/unique", |b| {
+        b.iter(|| {
+            for semantic in UNIQUE_SEMANTICS {
+                black_box(bindings.unique(semantic));
+            }
+        })
+    });
+
+    c.bench_function("semantics/texture_size", |b| {
+        b.iter(|| {
+            for semantics in TEXTURE_SEMANTICS {
+                for index in 0..4 {
+                    black_box(bindings.texture_size(Semantic { semantics, index }));
+                }
+            }
+        })
+    });
+
+    c.bench_function("semantics/parameter", |b| {
+        b.iter(|| {
+            let values = parameters.values();
+            for (handle, offset) in bindings.parameters() {
+                black_box((values[handle.index()], offset));
+            }
+        })
+    });
+}
+
+fn bench_parameters(c: &mut Criterion) {
+    let parameters = runtime_parameters();
+    let name = parameter_name(PARAMETER_COUNT / 2);
+    let handle = parameters.handle(&name).unwrap();
+
+    c.bench_function("parameters/value_by_name", |b| {
+        b.iter(|| black_box(parameters.parameter_value(black_box(&name))))
+    });
+
+    c.bench_function("parameters/value_by_handle", |b| {
+        b.iter(|| black_box(parameters.parameter_value_by_handle(black_box(handle))))
+    });
+
+    c.bench_function("parameters/set_by_name", |b| {
+        b.iter(|| parameters.set_parameter_value(black_box(&name), black_box(1.0)))
+    });
+
+    c.bench_function("parameters/set_by_handle", |b| {
+        b.iter(|| parameters.set_parameter_by_handle(black_box(handle), black_box(1.0)))
+    });
+}
+
+fn bench_scaling(c: &mut Criterion) {
+    let source = Size::new(256u32, 224);
+    let viewport = Size::new(1920u32, 1080);
+
+    let scale_2d = |scale_type, factor| Scale2D {
+        valid: true,
+        x: Scaling { scale_type, factor },
+        y: Scaling { scale_type, factor },
+    };
+
+    let scalings = [
+        scale_2d(ScaleType::Input, ScaleFactor::Float(2.0)),
+        scale_2d(ScaleType::Absolute, ScaleFactor::Absolute(640)),
+        scale_2d(ScaleType::Viewport, ScaleFactor::Float(1.0)),
+        scale_2d(ScaleType::Original, ScaleFactor::Float(4.0)),
+    ];
+
+    c.bench_function("scaling/scale_viewport", |b| {
+        b.iter(|| {
+            for scaling in &scalings {
+                black_box(black_box(source).scale_viewport(scaling.clone(), viewport, source));
+            }
+        })
+    });
+
+    c.bench_function("scaling/calculate_miplevels", |b| {
+        b.iter(|| black_box(black_box(viewport).calculate_miplevels()))
+    });
+}
+
+fn bench_image(c: &mut Criterion) {
+    // Roughly the size of a large mask or LUT texture.
+    let image = TextureBuffer::from(RgbaImage::new(2048, 2048));
+
+    c.bench_function("image/load_rgba8", |b| {
+        b.iter(|| Image::<RGBA8>::load_from_buffer(image.clone(), UVDirection::TopLeft))
+    });
+
+    c.bench_function("image/load_bgra8_flipped", |b| {
+        b.iter(|| Image::<BGRA8>::load_from_buffer(image.clone(), UVDirection::BottomLeft))
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_uniform_binding,
+    bench_semantic_lookup,
+    bench_parameters,
+    bench_scaling,
+    bench_image
+);
+criterion_main!(benches);
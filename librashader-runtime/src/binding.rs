@@ -1,8 +1,7 @@
-use crate::parameters::RuntimeParameters;
+use crate::parameters::{ParameterHandle, RuntimeParameters};
 use crate::uniforms::{BindUniform, NoUniformBinder, UniformStorage};
-use librashader_common::map::{FastHashMap, ShortString};
+use librashader_common::map::FastHashMap;
 use librashader_common::Size;
-use librashader_preprocess::ShaderParameter;
 use librashader_reflect::reflect::semantics::{
     BindingMeta, MemberOffset, Semantic, TextureBinding, TextureSemantics, UniformBinding,
     UniformMeta, UniqueSemantics,
@@ -49,6 +48,81 @@ where
     }
 }
 
+const UNIQUE_SEMANTICS_COUNT: usize = UniqueSemantics::FrameJitter as usize + 1;
+const TEXTURE_SEMANTICS_COUNT: usize = TextureSemantics::User as usize + 1;
+
+/// The uniform bindings of a shader pass, indexed by semantic and parameter handle
+/// so that binding semantics every frame does not need to hash any names.
+#[derive(Debug, Clone)]
+pub struct UniformBindings<T> {
+    unique: [Option<T>; UNIQUE_SEMANTICS_COUNT],
+    texture_size: [Vec<Option<T>>; TEXTURE_SEMANTICS_COUNT],
+    parameters: Vec<(ParameterHandle, T)>,
+}
+
+impl<T> UniformBindings<T> {
+    /// Index a uniform binding map, resolving parameters to handles of the given runtime parameters.
+    ///
+    /// Parameter bindings that do not have a handle in the runtime parameters are never bound.
+    pub fn new(bindings: FastHashMap<UniformBinding, T>, parameters: &RuntimeParameters) -> Self {
+        let mut unique = std::array::from_fn(|_| None);
+        let mut texture_size: [Vec<Option<T>>; TEXTURE_SEMANTICS_COUNT] = Default::default();
+        let mut parameter_bindings = Vec::new();
+
+        for (binding, offset) in bindings {
+            match binding {
+                UniformBinding::Parameter(name) => {
+                    if let Some(handle) = parameters.handle(&name) {
+                        parameter_bindings.push((handle, offset));
+                    }
+                }
+                UniformBinding::SemanticVariable(semantics) => {
+                    unique[semantics as usize] = Some(offset);
+                }
+                UniformBinding::TextureSize(semantic) => {
+                    let indices = &mut texture_size[semantic.semantics as usize];
+                    if indices.len() <= semantic.index {
+                        indices.resize_with(semantic.index + 1, || None);
+                    }
+                    indices[semantic.index] = Some(offset);
+                }
+            }
+        }
+
+        UniformBindings {
+            unique,
+            texture_size,
+            parameters: parameter_bindings,
+        }
+    }
+
+    /// Get the binding of a unique semantic.
+    #[inline(always)]
+    pub fn unique(&self, semantics: UniqueSemantics) -> Option<&T> {
+        self.unique[semantics as usize].as_ref()
+    }
+
+    /// Get the binding of the size of a texture semantic.
+    #[inline(always)]
+    pub fn texture_size(&self, semantic: Semantic<TextureSemantics>) -> Option<&T> {
+        self.texture_size[semantic.semantics as usize]
+            .get(semantic.index)?
+            .as_ref()
+    }
+
+    /// Get the number of indices of a texture semantic that may have a size binding.
+    #[inline(always)]
+    pub fn texture_size_count(&self, semantics: TextureSemantics) -> usize {
+        self.texture_size[semantics as usize].len()
+    }
+
+    /// Get the bindings of the parameters of the pass.
+    #[inline(always)]
+    pub fn parameters(&self) -> &[(ParameterHandle, T)] {
+        &self.parameters
+    }
+}
+
 /// Inputs to binding semantics
 pub struct UniformInputs<'a> {
     /// MVP
@@ -73,8 +147,18 @@ pub struct UniformInputs<'a> {
     pub framebuffer_size: Size<u32>,
     /// FinalViewportSize
     pub viewport_size: Size<u32>,
+    /// SceneLuminance
+    pub scene_luminance: f32,
+    /// FrameJitter (in pixels, need to normalize)
+    pub frame_jitter: [f32; 2],
 }
 
+/// The `SceneLuminance` bound by runtimes that do not support scene analysis, and
+/// before the first analysis has been read back.
+///
+/// This is the luminance of middle grey.
+pub const DEFAULT_SCENE_LUMINANCE: f32 = 0.18;
+
 /// Trait that abstracts binding of semantics to shader uniforms.
 pub trait BindSemantics<H = NoUniformBinder, C = Option<()>, U = Box<[u8]>, P = Box<[u8]>>
 where
@@ -121,18 +205,17 @@ where
         uniform_inputs: UniformInputs<'_>,
         original: &Self::InputTexture,
         source: &Self::InputTexture,
-        uniform_bindings: &FastHashMap<UniformBinding, Self::UniformOffset>,
+        uniform_bindings: &UniformBindings<Self::UniformOffset>,
         texture_meta: &FastHashMap<Semantic<TextureSemantics>, TextureBinding>,
         pass_outputs: impl Iterator<Item = Option<impl AsRef<Self::InputTexture>>>,
         pass_feedback: impl Iterator<Item = Option<impl AsRef<Self::InputTexture>>>,
         original_history: impl Iterator<Item = Option<impl AsRef<Self::InputTexture>>>,
         lookup_textures: impl Iterator<Item = (usize, impl AsRef<Self::InputTexture>)>,
-        parameter_defaults: &FastHashMap<ShortString, ShaderParameter>,
         runtime_parameters: &RuntimeParameters,
     ) {
-        let runtime_parameters = runtime_parameters.parameters.load();
+        let runtime_parameters = runtime_parameters.values.load();
         // Bind MVP
-        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::MVP.into()) {
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::MVP) {
             uniform_storage.bind_mat4(
                 offset.offset(),
                 uniform_inputs.mvp,
@@ -142,7 +225,7 @@ where
         }
 
         // Bind OutputSize
-        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::Output.into()) {
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::Output) {
             uniform_storage.bind_vec4(
                 offset.offset(),
                 uniform_inputs.framebuffer_size,
@@ -152,7 +235,7 @@ where
         }
 
         // bind FinalViewportSize
-        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::FinalViewport.into()) {
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::FinalViewport) {
             uniform_storage.bind_vec4(
                 offset.offset(),
                 uniform_inputs.viewport_size,
@@ -162,7 +245,7 @@ where
         }
 
         // bind FrameCount
-        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::FrameCount.into()) {
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::FrameCount) {
             uniform_storage.bind_scalar(
                 offset.offset(),
                 uniform_inputs.frame_count,
@@ -172,7 +255,7 @@ where
         }
 
         // bind FrameDirection
-        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::FrameDirection.into()) {
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::FrameDirection) {
             uniform_storage.bind_scalar(
                 offset.offset(),
                 uniform_inputs.frame_direction,
@@ -182,7 +265,7 @@ where
         }
 
         // bind Rotation
-        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::Rotation.into()) {
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::Rotation) {
             uniform_storage.bind_scalar(
                 offset.offset(),
                 uniform_inputs.rotation,
@@ -192,7 +275,7 @@ where
         }
 
         // bind TotalSubFrames
-        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::TotalSubFrames.into()) {
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::TotalSubFrames) {
             uniform_storage.bind_scalar(
                 offset.offset(),
                 uniform_inputs.total_subframes,
@@ -202,7 +285,7 @@ where
         }
 
         // bind CurrentSubFrames
-        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::CurrentSubFrame.into()) {
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::CurrentSubFrame) {
             uniform_storage.bind_scalar(
                 offset.offset(),
                 uniform_inputs.current_subframe,
@@ -212,7 +295,7 @@ where
         }
 
         // bind OriginalFPS
-        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::OriginalFPS.into()) {
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::OriginalFPS) {
             uniform_storage.bind_scalar(
                 offset.offset(),
                 uniform_inputs.frames_per_second,
@@ -222,7 +305,7 @@ where
         }
 
         // bind FrameTimeDelta
-        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::FrameTimeDelta.into()) {
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::FrameTimeDelta) {
             uniform_storage.bind_scalar(
                 offset.offset(),
                 uniform_inputs.frametime_delta,
@@ -231,17 +314,39 @@ where
             );
         }
 
+        // bind SceneLuminance
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::SceneLuminance) {
+            uniform_storage.bind_scalar(
+                offset.offset(),
+                uniform_inputs.scene_luminance,
+                offset.context(),
+                device,
+            );
+        }
+
+        // bind FrameJitter
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::FrameJitter) {
+            let [x, y] = uniform_inputs.frame_jitter;
+            let size = original.size();
+            uniform_storage.bind_vec4(
+                offset.offset(),
+                [x, y, x / size.width as f32, y / size.height as f32],
+                offset.context(),
+                device,
+            );
+        }
+
         let mut aspect_ratio = uniform_inputs.aspect_ratio;
         if aspect_ratio.is_zero() {
             aspect_ratio = original.size().aspect_ratio();
         }
 
         // bind OriginalAspect
-        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::OriginalAspect.into()) {
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::OriginalAspect) {
             uniform_storage.bind_scalar(offset.offset(), aspect_ratio, offset.context(), device);
         }
 
-        if let Some(offset) = uniform_bindings.get(&UniqueSemantics::OriginalAspectRotated.into()) {
+        if let Some(offset) = uniform_bindings.unique(UniqueSemantics::OriginalAspectRotated) {
             let rotated_aspect = if uniform_inputs.rotation == 1 || uniform_inputs.rotation == 3 {
                 1.0f32 / aspect_ratio
             } else {
@@ -257,7 +362,7 @@ where
         }
 
         // bind OriginalSize
-        if let Some(offset) = uniform_bindings.get(&TextureSemantics::Original.semantics(0).into())
+        if let Some(offset) = uniform_bindings.texture_size(TextureSemantics::Original.semantics(0))
         {
             uniform_storage.bind_vec4(offset.offset(), original.size(), offset.context(), device);
         }
@@ -268,7 +373,7 @@ where
         }
 
         // bind SourceSize
-        if let Some(offset) = uniform_bindings.get(&TextureSemantics::Source.semantics(0).into()) {
+        if let Some(offset) = uniform_bindings.texture_size(TextureSemantics::Source.semantics(0)) {
             uniform_storage.bind_vec4(offset.offset(), source.size(), offset.context(), device);
         }
 
@@ -281,33 +386,62 @@ where
 
         // bind OriginalHistory0Size
         if let Some(offset) =
-            uniform_bindings.get(&TextureSemantics::OriginalHistory.semantics(0).into())
+            uniform_bindings.texture_size(TextureSemantics::OriginalHistory.semantics(0))
         {
             uniform_storage.bind_vec4(offset.offset(), original.size(), offset.context(), device);
         }
 
         // bind OriginalHistory1-..
+        let mut history_depth = 0;
+        let mut oldest_history = None;
         for (index, history) in original_history.enumerate() {
+            history_depth = index + 1;
             let Some(history) = history else {
                 continue;
             };
 
-            let history = history.as_ref();
+            let texture = history.as_ref();
 
             if let Some(binding) =
                 texture_meta.get(&TextureSemantics::OriginalHistory.semantics(index + 1))
             {
-                Self::bind_texture(descriptor_set, sampler_set, binding, history, device);
+                Self::bind_texture(descriptor_set, sampler_set, binding, texture, device);
+            }
+
+            if let Some(offset) = uniform_bindings
+                .texture_size(TextureSemantics::OriginalHistory.semantics(index + 1))
+            {
+                uniform_storage.bind_vec4(
+                    offset.offset(),
+                    texture.size(),
+                    offset.context(),
+                    device,
+                );
+            }
+
+            oldest_history = Some(history);
+        }
+
+        // If the history depth was capped, history frames older than the oldest kept frame
+        // are clamped to the oldest kept frame, or to Original if no frames are kept.
+        let oldest_history = oldest_history.as_ref().map_or(original, AsRef::as_ref);
+        for (semantic, binding) in texture_meta {
+            if semantic.semantics == TextureSemantics::OriginalHistory
+                && semantic.index > history_depth
+            {
+                Self::bind_texture(descriptor_set, sampler_set, binding, oldest_history, device);
             }
+        }
 
-            if let Some(offset) = uniform_bindings.get(
-                &TextureSemantics::OriginalHistory
-                    .semantics(index + 1)
-                    .into(),
-            ) {
+        for index in history_depth + 1
+            ..uniform_bindings.texture_size_count(TextureSemantics::OriginalHistory)
+        {
+            if let Some(offset) =
+                uniform_bindings.texture_size(TextureSemantics::OriginalHistory.semantics(index))
+            {
                 uniform_storage.bind_vec4(
                     offset.offset(),
-                    history.size(),
+                    oldest_history.size(),
                     offset.context(),
                     device,
                 );
@@ -330,7 +464,7 @@ where
             }
 
             if let Some(offset) =
-                uniform_bindings.get(&TextureSemantics::PassOutput.semantics(index).into())
+                uniform_bindings.texture_size(TextureSemantics::PassOutput.semantics(index))
             {
                 uniform_storage.bind_vec4(offset.offset(), output.size(), offset.context(), device);
             }
@@ -351,7 +485,7 @@ where
             }
 
             if let Some(offset) =
-                uniform_bindings.get(&TextureSemantics::PassFeedback.semantics(index).into())
+                uniform_bindings.texture_size(TextureSemantics::PassFeedback.semantics(index))
             {
                 uniform_storage.bind_vec4(
                     offset.offset(),
@@ -363,18 +497,11 @@ where
         }
 
         // bind User parameters
-        for (id, offset) in uniform_bindings
-            .iter()
-            .filter_map(|(binding, value)| match binding {
-                UniformBinding::Parameter(id) => Some((id, value)),
-                _ => None,
-            })
-        {
-            let id = id.as_str();
-
-            let default = parameter_defaults.get(id).map_or(0f32, |f| f.initial);
-
-            let value = *runtime_parameters.get(id).unwrap_or(&default);
+        for (handle, offset) in uniform_bindings.parameters() {
+            let value = runtime_parameters
+                .get(handle.index())
+                .copied()
+                .unwrap_or_default();
 
             uniform_storage.bind_scalar(offset.offset(), value, offset.context(), device);
         }
@@ -387,7 +514,7 @@ where
             }
 
             if let Some(offset) =
-                uniform_bindings.get(&TextureSemantics::User.semantics(index).into())
+                uniform_bindings.texture_size(TextureSemantics::User.semantics(index))
             {
                 uniform_storage.bind_vec4(offset.offset(), lut.size(), offset.context(), device);
             }
@@ -399,6 +526,7 @@ where
 pub struct BindingRequirements {
     pub(crate) required_history: usize,
     pub(crate) uses_final_pass_as_feedback: bool,
+    pub(crate) uses_feedback: bool,
 }
 
 /// Trait for objects that can be used to create a binding map.
@@ -494,13 +622,18 @@ impl BindingUtil for BindingMeta {
         BindingRequirements {
             required_history: required_images,
             uses_final_pass_as_feedback: uses_feedback,
+            uses_feedback: !latest_feedback_pass.is_negative(),
         }
     }
 }
 
+/// Declare the frame options of a runtime, with the options common to every runtime.
+///
+/// Options specific to the runtime can be declared after the name of the type, along with
+/// their default value.
 #[macro_export]
 macro_rules! impl_default_frame_options {
-    ($ty:ident) => {
+    ($ty:ident $(, { $($(#[$meta:meta])* $field:ident: $field_ty:ty = $default:expr),+ $(,)? })?) => {
         /// Options for each frame.
         #[repr(C)]
         #[derive(Debug, Clone)]
@@ -527,6 +660,30 @@ macro_rules! impl_default_frame_options {
             pub frames_per_second: f32,
             /// Time in milliseconds between the current and previous frame. Default is 0.
             pub frametime_delta: u32,
+            /// Whether the provided MVP is in row-major order and needs to be transposed.
+            /// Default is false.
+            pub transpose_mvp: bool,
+            /// Whether to flip the Y axis of the MVP, which flips the output vertically.
+            /// This applies to the default MVP if no MVP is provided. Default is false.
+            pub flip_mvp_y: bool,
+            /// The kernel used to scale the input to the viewport when there are no enabled
+            /// shader passes, and to scale the output of the final pass to the viewport if
+            /// `scale_final_pass` is set. Default is nearest-neighbour.
+            pub identity_scaling: $crate::scaling::ScalingKernel,
+            /// Whether to draw the final pass at the size given by its scaling, and scale its
+            /// output to the viewport with the `identity_scaling` kernel. Default is false,
+            /// which draws the final pass directly to the viewport.
+            pub scale_final_pass: bool,
+            /// The sequence of sub-pixel offsets bound to the `FrameJitter` uniform.
+            /// 0 = no jitter, 1 = Halton (2, 3), 2 = `jitter_offset`. Default is 0.
+            pub jitter_sequence: u32,
+            /// The sub-pixel offset of the current frame in pixels, if `jitter_sequence` is 2.
+            /// Default is (0, 0).
+            pub jitter_offset: [f32; 2],
+            $($(
+                $(#[$meta])*
+                pub $field: $field_ty,
+            )+)?
         }
 
         impl Default for $ty {
@@ -540,6 +697,13 @@ macro_rules! impl_default_frame_options {
                     aspect_ratio: 0.0,
                     frametime_delta: 0,
                     frames_per_second: 1.0,
+                    transpose_mvp: false,
+                    flip_mvp_y: false,
+                    identity_scaling: $crate::scaling::ScalingKernel::Nearest,
+                    scale_final_pass: false,
+                    jitter_sequence: 0,
+                    jitter_offset: [0.0, 0.0],
+                    $($($field: $default,)+)?
                 }
             }
         }
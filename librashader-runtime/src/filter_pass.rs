@@ -1,5 +1,7 @@
-use librashader_common::ImageFormat;
-use librashader_presets::PassMeta;
+use librashader_common::map::ShortString;
+use librashader_common::{FilterMode, ImageFormat, ScalingKernel, WrapMode};
+use librashader_presets::{PassMeta, Scale2D};
+use std::path::{Path, PathBuf};
 
 /// Trait for metadata about a filter pass.
 pub trait FilterPassMeta {
@@ -9,16 +11,108 @@ pub trait FilterPassMeta {
     /// Gets a reference to the filter pass config.
     fn meta(&self) -> &PassMeta;
 
+    /// Gets the path of the shader source of the pass, if it was loaded from a file.
+    fn source_path(&self) -> Option<&Path>;
+
     /// Gets the format of the filter pass framebuffer.
     #[inline(always)]
     fn get_format(&self) -> ImageFormat {
-        let fb_format = self.framebuffer_format();
-        if let Some(format) = self.meta().get_format_override() {
-            format
-        } else if fb_format == ImageFormat::Unknown {
-            ImageFormat::R8G8B8A8Unorm
-        } else {
-            fb_format
+        requested_format(self.meta(), self.framebuffer_format())
+    }
+
+    /// Gets the format of the filter pass framebuffer that is supported by the device.
+    ///
+    /// By default, every format is assumed to be supported.
+    #[inline(always)]
+    fn get_supported_format(&self) -> ImageFormat {
+        self.get_format()
+    }
+
+    /// Gets information about the filter pass.
+    fn pass_info(&self) -> PassInfo {
+        let meta = self.meta();
+        PassInfo {
+            requested_format: self.get_format(),
+            format: self.get_supported_format(),
+            alias: meta.alias.clone(),
+            scaling: meta.scaling.clone(),
+            filter: meta.filter,
+            wrap_mode: meta.wrap_mode,
+            path: self.source_path().map(Path::to_path_buf),
+        }
+    }
+}
+
+/// Information about a filter pass of a loaded filter chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassInfo {
+    /// The framebuffer format requested by the preset or by the `#pragma format` of the shader.
+    pub requested_format: ImageFormat,
+    /// The framebuffer format that the pass renders to.
+    ///
+    /// This differs from the requested format if the device does not support rendering
+    /// to the requested format, and a fallback format was chosen instead.
+    pub format: ImageFormat,
+    /// The alias of the pass, if any.
+    pub alias: Option<ShortString>,
+    /// The scaling of the framebuffer of the pass.
+    pub scaling: Scale2D,
+    /// The filtering mode that the pass expects for its input.
+    pub filter: FilterMode,
+    /// The texture addressing (wrap) mode that the pass expects for its input.
+    pub wrap_mode: WrapMode,
+    /// The path of the shader source of the pass, if it was loaded from a file.
+    ///
+    /// Built-in passes and passes loaded from a serialized preset pack have no path.
+    pub path: Option<PathBuf>,
+}
+
+/// Gets the format of the framebuffer of a pass from the pass config and the format
+/// declared by the shader.
+///
+/// The preset overrides the format declared by the shader, and passes that do not declare
+/// a format render to `R8G8B8A8Unorm`.
+#[inline(always)]
+pub fn requested_format(meta: &PassMeta, framebuffer_format: ImageFormat) -> ImageFormat {
+    if let Some(format) = meta.get_format_override() {
+        format
+    } else if framebuffer_format == ImageFormat::Unknown {
+        ImageFormat::R8G8B8A8Unorm
+    } else {
+        framebuffer_format
+    }
+}
+
+/// The built-in identity passes of a filter chain, one for every scaling kernel.
+///
+/// Identity passes are only compiled the first time their kernel is used, so that filter
+/// chains that always have enabled passes and draw the final pass directly to the viewport
+/// do not compile any of them.
+pub struct IdentityPasses<P> {
+    passes: [Option<P>; ScalingKernel::ALL.len()],
+}
+
+impl<P> Default for IdentityPasses<P> {
+    fn default() -> Self {
+        Self {
+            passes: std::array::from_fn(|_| None),
         }
     }
 }
+
+impl<P> IdentityPasses<P> {
+    /// Get the identity pass for the given kernel, creating it with `init` if the kernel
+    /// was not used before.
+    pub fn get_or_try_init<E>(
+        &mut self,
+        kernel: ScalingKernel,
+        init: impl FnOnce(ScalingKernel) -> Result<P, E>,
+    ) -> Result<&mut P, E> {
+        let slot = &mut self.passes[kernel as usize];
+        let pass = match slot.take() {
+            Some(pass) => pass,
+            None => init(kernel)?,
+        };
+        Ok(slot.insert(pass))
+    }
+}
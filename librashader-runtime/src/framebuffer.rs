@@ -1,4 +1,5 @@
 use crate::binding::{BindingRequirements, BindingUtil};
+use crate::warnings::OptionWarning;
 use librashader_reflect::reflect::semantics::BindingMeta;
 use std::collections::VecDeque;
 
@@ -8,6 +9,20 @@ pub struct FramebufferInit<'a, F, I, E> {
     input_generator: &'a dyn Fn() -> I,
     requirements: BindingRequirements,
     filters_count: usize,
+    max_history: Option<usize>,
+    disable_temporal: bool,
+}
+
+/// The depth of the history of a filter chain.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct HistoryDepth {
+    /// The number of history frames requested by the shader passes.
+    pub requested: usize,
+    /// The number of history frames kept by the filter chain.
+    ///
+    /// This is less than the requested number of frames if the history depth was capped.
+    /// Shader passes that sample older frames than are kept receive the oldest kept frame.
+    pub kept: usize,
 }
 
 impl<'a, F, I, E> FramebufferInit<'a, F, I, E> {
@@ -18,7 +33,8 @@ impl<'a, F, I, E> FramebufferInit<'a, F, I, E> {
         owned_generator: &'a dyn Fn() -> Result<F, E>,
         input_generator: &'a dyn Fn() -> I,
     ) -> Self {
-        let filters_count = filters.len();
+        // an empty chain still needs an output for the identity pass.
+        let filters_count = filters.len().max(1);
         let requirements = BindingMeta::calculate_requirements(filters);
 
         Self {
@@ -26,16 +42,52 @@ impl<'a, F, I, E> FramebufferInit<'a, F, I, E> {
             input_generator,
             filters_count,
             requirements,
+            max_history: None,
+            disable_temporal: false,
         }
     }
 
+    /// Cap the number of history frames kept to `max_history`.
+    ///
+    /// If `max_history` is zero, the number of history frames is not capped.
+    pub fn with_max_history(mut self, max_history: usize) -> Self {
+        self.max_history = (max_history != 0).then_some(max_history);
+        self
+    }
+
+    /// Disable history and feedback if `disable_temporal` is true.
+    ///
+    /// No history frames are kept, and the final pass is not drawn as feedback. If the shader
+    /// passes request history, a single history framebuffer is still initialized, which the
+    /// filter chain keeps cleared to black and never pushes frames into, so that all history
+    /// semantics after `OriginalHistory0` are bound to black.
+    pub fn with_temporal_disabled(mut self, disable_temporal: bool) -> Self {
+        self.disable_temporal = disable_temporal;
+        self
+    }
+
+    /// Get the depth of the history that will be initialized.
+    pub fn history_depth(&self) -> HistoryDepth {
+        let requested = self.requirements.required_history;
+        let kept = if self.disable_temporal {
+            0
+        } else {
+            self.max_history
+                .map_or(requested, |max_history| requested.min(max_history))
+        };
+
+        HistoryDepth { requested, kept }
+    }
+
     /// Initialize history framebuffers and views.
     pub fn init_history(&self) -> Result<(VecDeque<F>, Box<[I]>), E> {
-        init_history(
-            self.requirements.required_history,
-            self.owned_generator,
-            self.input_generator,
-        )
+        let required_images = if self.disable_temporal {
+            self.requirements.required_history.min(1)
+        } else {
+            self.history_depth().kept
+        };
+
+        init_history(required_images, self.owned_generator, self.input_generator)
     }
 
     /// Initialize output framebuffers and views.
@@ -47,9 +99,25 @@ impl<'a, F, I, E> FramebufferInit<'a, F, I, E> {
         )
     }
 
+    /// Get the warnings about the `max_history` and `disable_temporal` options.
+    pub fn option_warnings(&self) -> Vec<OptionWarning> {
+        let depth = self.history_depth();
+        if self.disable_temporal {
+            let uses_temporal = depth.requested > 0 || self.requirements.uses_feedback;
+            uses_temporal
+                .then_some(OptionWarning::TemporalDisabled)
+                .into_iter()
+                .collect()
+        } else if depth.kept < depth.requested {
+            vec![OptionWarning::HistoryCapped { depth }]
+        } else {
+            Vec::new()
+        }
+    }
+
     /// Get if the final pass is used as feedback.
     pub const fn uses_final_pass_as_feedback(&self) -> bool {
-        self.requirements.uses_final_pass_as_feedback
+        self.requirements.uses_final_pass_as_feedback && !self.disable_temporal
     }
 }
 
@@ -46,15 +46,15 @@ impl PixelFormat for RGBA8 {
 
 impl PixelFormat for BGRA8 {
     fn convert(pixels: &mut Vec<u8>) {
-        const BGRA_SWIZZLE: &[usize; 32] = &generate_swizzle([2, 1, 0, 3]);
-        swizzle_pixels(pixels, BGRA_SWIZZLE);
+        swizzle_pixels(pixels, |rgba| {
+            (rgba & 0xff00ff00) | ((rgba >> 16) & 0xff) | ((rgba & 0xff) << 16)
+        });
     }
 }
 
 impl PixelFormat for ARGB8 {
     fn convert(pixels: &mut Vec<u8>) {
-        const ARGB_SWIZZLE: &[usize; 32] = &generate_swizzle([3, 0, 1, 2]);
-        swizzle_pixels(pixels, ARGB_SWIZZLE);
+        swizzle_pixels(pixels, |rgba| rgba.rotate_left(8));
     }
 }
 
@@ -88,11 +88,7 @@ impl<P: PixelFormat> Image<P> {
         Ok(Self::convert(image, direction))
     }
 
-    fn convert(mut image: DynamicImage, direction: UVDirection) -> Self {
-        if direction == UVDirection::BottomLeft {
-            image = image.flipv();
-        }
-
+    fn convert(image: DynamicImage, direction: UVDirection) -> Self {
         let image = if let DynamicImage::ImageRgba8(image) = image {
             image
         } else {
@@ -107,6 +103,9 @@ impl<P: PixelFormat> Image<P> {
             .max(image.sample_layout().width_stride);
 
         let mut bytes = image.into_raw();
+        if direction == UVDirection::BottomLeft {
+            flip_rows(&mut bytes, pitch);
+        }
         P::convert(&mut bytes);
         Image {
             bytes,
@@ -138,87 +137,72 @@ impl<P: PixelFormat> LoadedTexture<P> {
     }
 }
 
-// load-bearing #[inline(always)], without it llvm will not vectorize.
-#[inline(always)]
-fn swizzle_pixels(pixels: &mut Vec<u8>, swizzle: &'static [usize; 32]) {
-    assert!(pixels.len() % 4 == 0);
-    let mut chunks = pixels.chunks_exact_mut(32);
-
-    // This should vectorize faster than a naive mem swap
-    for chunk in &mut chunks {
-        let tmp = swizzle.map(|i| chunk[i]);
-        chunk.copy_from_slice(&tmp[..])
+/// Flip the rows of the image in place.
+///
+/// Flipping with `DynamicImage::flipv` copies the whole image into a new buffer, which
+/// dominates loading large LUTs on handhelds with slow memory.
+fn flip_rows(pixels: &mut [u8], pitch: usize) {
+    if pitch == 0 {
+        return;
     }
 
-    let remainder = chunks.into_remainder();
-    for chunk in remainder.chunks_exact_mut(4) {
-        let argb = [
-            chunk[swizzle[0]],
-            chunk[swizzle[1]],
-            chunk[swizzle[2]],
-            chunk[swizzle[3]],
-        ];
-        chunk.copy_from_slice(&argb[..])
+    let rows = pixels.len() / pitch;
+    let (top, bottom) = pixels.split_at_mut(rows / 2 * pitch);
+    let bottom = &mut bottom[(rows % 2) * pitch..];
+    for (top, bottom) in top
+        .chunks_exact_mut(pitch)
+        .zip(bottom.chunks_exact_mut(pitch).rev())
+    {
+        top.swap_with_slice(bottom);
     }
 }
 
-const fn generate_swizzle<const LEN: usize>(swizzle: [usize; 4]) -> [usize; LEN] {
-    assert!(LEN % 4 == 0, "length of swizzle must be divisible by 4");
-    let mut out: [usize; LEN] = [0; LEN];
-
-    let mut index = 0;
-    while index < LEN {
-        let chunk = [index, index + 1, index + 2, index + 3];
-        out[index + 0] = chunk[swizzle[0]];
-        out[index + 1] = chunk[swizzle[1]];
-        out[index + 2] = chunk[swizzle[2]];
-        out[index + 3] = chunk[swizzle[3]];
-
-        index += 4;
+// load-bearing #[inline(always)], without it llvm will not vectorize.
+#[inline(always)]
+fn swizzle_pixels(pixels: &mut [u8], swizzle: impl Fn(u32) -> u32) {
+    assert!(pixels.len() % 4 == 0);
+    // Allocations are aligned in practice, which allows working on whole pixels.
+    if let Ok(pixels) = bytemuck::try_cast_slice_mut::<u8, u32>(pixels) {
+        for pixel in pixels {
+            *pixel = u32::from_le(swizzle(u32::to_le(*pixel)));
+        }
+        return;
     }
 
-    out
+    for pixel in pixels.chunks_exact_mut(4) {
+        let rgba = u32::from_le_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+        pixel.copy_from_slice(&swizzle(rgba).to_le_bytes());
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::image::generate_swizzle;
+    use crate::image::{Image, PixelFormat, UVDirection, ARGB8, BGRA8};
+    use image::RgbaImage;
+    use librashader_pack::TextureBuffer;
 
     #[test]
-    pub fn generate_normal_swizzle() {
-        let swizzle = generate_swizzle::<32>([0, 1, 2, 3]);
-        assert_eq!(
-            swizzle,
-            #[rustfmt::skip]
-            [
-                0, 1, 2, 3,
-                4, 5, 6, 7,
-                8, 9, 10, 11,
-                12, 13, 14, 15,
-                16, 17, 18, 19,
-                20, 21, 22, 23,
-                24, 25, 26, 27,
-                28, 29, 30, 31
-            ]
-        )
+    pub fn flip_and_swizzle() {
+        let rows = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+        let image = RgbaImage::from_raw(1, 3, rows.concat()).unwrap();
+
+        let image =
+            Image::<BGRA8>::load_from_buffer(TextureBuffer::from(image), UVDirection::BottomLeft)
+                .unwrap();
+        assert_eq!(image.pitch, 4);
+        assert_eq!(image.bytes, [11, 10, 9, 12, 7, 6, 5, 8, 3, 2, 1, 4]);
     }
 
     #[test]
-    pub fn generate_argb_swizzle() {
-        let swizzle = generate_swizzle::<32>([3, 0, 1, 2]);
-        assert_eq!(
-            swizzle,
-            #[rustfmt::skip]
-            [
-                3, 0, 1, 2,
-                7, 4, 5, 6,
-                11, 8, 9, 10,
-                15, 12, 13, 14,
-                19, 16, 17, 18,
-                23, 20, 21, 22,
-                27, 24, 25, 26,
-                31, 28, 29, 30
-            ]
-        )
+    pub fn swizzle() {
+        let rgba: Vec<u8> = (0..9).flat_map(|_| [1, 2, 3, 4]).collect();
+
+        let mut bgra = rgba.clone();
+        BGRA8::convert(&mut bgra);
+        assert!(bgra.chunks_exact(4).all(|pixel| pixel == [3, 2, 1, 4]));
+
+        let mut argb = rgba.clone();
+        ARGB8::convert(&mut argb);
+        assert!(argb.chunks_exact(4).all(|pixel| pixel == [4, 1, 2, 3]));
     }
 }
@@ -0,0 +1,267 @@
+use std::cell::RefCell;
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Once};
+
+pub use log::Level as LogLevel;
+
+/// A callback that receives the log messages of librashader.
+#[derive(Clone)]
+pub struct LogCallback(Arc<dyn Fn(LogLevel, &str) + Send + Sync>);
+
+impl LogCallback {
+    /// Create a log callback from a closure.
+    pub fn new(callback: impl Fn(LogLevel, &str) + Send + Sync + 'static) -> Self {
+        LogCallback(Arc::new(callback))
+    }
+
+    /// Report a log message.
+    pub fn log(&self, level: LogLevel, message: &str) {
+        (self.0)(level, message)
+    }
+}
+
+impl Debug for LogCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LogCallback")
+    }
+}
+
+/// Options for a librashader instance.
+#[derive(Debug, Clone, Default)]
+pub struct InstanceOptions {
+    /// The number of threads used to compile shaders. If zero, the number of threads is
+    /// chosen by the number of available CPUs.
+    pub compile_threads: usize,
+    /// The stack size in bytes of the compile threads. If zero, the default stack size is used.
+    pub compile_stack_size: usize,
+    /// The directory of the shader and pipeline cache. If `None`, the default cache directory
+    /// of the platform is used.
+    pub cache_dir: Option<PathBuf>,
+    /// A callback that receives the log messages of librashader while the instance is in use.
+    pub log_callback: Option<LogCallback>,
+}
+
+/// An error when creating a librashader instance.
+#[derive(Debug)]
+pub struct InstanceError(rayon::ThreadPoolBuildError);
+
+impl Display for InstanceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to create the compile thread pool: {}", self.0)
+    }
+}
+
+impl std::error::Error for InstanceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+struct InstanceInner {
+    thread_pool: rayon::ThreadPool,
+    cache_dir: Option<PathBuf>,
+    log_callback: Option<LogCallback>,
+}
+
+/// A librashader instance, that owns the services shared by the filter chains created from it.
+///
+/// An instance owns the thread pool that compiles shaders, the directory of the
+/// shader and pipeline cache, and the callback that receives log messages. Filter chains
+/// are created from an instance by passing it in the options of the filter chain. Without an
+/// instance, filter chains use the global thread pool, the default cache directory and
+/// the global logger.
+///
+/// Instances are cheap to clone, and clones share the same thread pool.
+#[derive(Clone)]
+pub struct LibrashaderInstance(Arc<InstanceInner>);
+
+thread_local! {
+    static LOG_CALLBACK: RefCell<Option<LogCallback>> = const { RefCell::new(None) };
+}
+
+/// Forwards log records to the log callback of the instance in use by the current thread.
+struct InstanceLogger;
+
+impl log::Log for InstanceLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        LOG_CALLBACK.with_borrow(|callback| callback.is_some())
+    }
+
+    fn log(&self, record: &log::Record) {
+        LOG_CALLBACK.with_borrow(|callback| {
+            if let Some(callback) = callback {
+                callback.log(record.level(), &record.args().to_string())
+            }
+        })
+    }
+
+    fn flush(&self) {}
+}
+
+static INSTANCE_LOGGER: InstanceLogger = InstanceLogger;
+static INSTALL_LOGGER: Once = Once::new();
+
+/// A guard that keeps the cache directory and log callback of an instance in use by the
+/// current thread, returned by [`LibrashaderInstance::enter`].
+///
+/// The previous cache directory and log callback of the thread are restored when dropped.
+#[must_use]
+pub struct EnterGuard {
+    cache_dir: Option<PathBuf>,
+    log_callback: Option<LogCallback>,
+    // the guard restores thread-local state, so it must be dropped on the same thread.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for EnterGuard {
+    fn drop(&mut self) {
+        librashader_cache::set_thread_cache_dir(self.cache_dir.take());
+        LOG_CALLBACK.set(self.log_callback.take());
+    }
+}
+
+impl LibrashaderInstance {
+    /// Create a new librashader instance.
+    ///
+    /// If a log callback is given and no global logger was set by the application, a logger
+    /// that forwards log messages to the callback of the instance in use is set as the global
+    /// logger. Otherwise, log messages go to the global logger of the application.
+    pub fn new(options: InstanceOptions) -> Result<LibrashaderInstance, InstanceError> {
+        let InstanceOptions {
+            compile_threads,
+            compile_stack_size,
+            cache_dir,
+            log_callback,
+        } = options;
+
+        if log_callback.is_some() {
+            INSTALL_LOGGER.call_once(|| {
+                if log::set_logger(&INSTANCE_LOGGER).is_ok() {
+                    log::set_max_level(log::LevelFilter::Trace);
+                }
+            });
+        }
+
+        let thread_cache_dir = cache_dir.clone();
+        let thread_log_callback = log_callback.clone();
+        let mut builder = rayon::ThreadPoolBuilder::new()
+            .num_threads(compile_threads)
+            .thread_name(|index| format!("librashader-compile-{index}"))
+            .start_handler(move |_| {
+                librashader_cache::set_thread_cache_dir(thread_cache_dir.clone());
+                LOG_CALLBACK.set(thread_log_callback.clone());
+            });
+
+        if compile_stack_size != 0 {
+            builder = builder.stack_size(compile_stack_size);
+        }
+
+        let thread_pool = builder.build().map_err(InstanceError)?;
+
+        Ok(LibrashaderInstance(Arc::new(InstanceInner {
+            thread_pool,
+            cache_dir,
+            log_callback,
+        })))
+    }
+
+    /// Run the closure on the compile thread pool of the instance.
+    ///
+    /// Parallel iterators used by the closure run on the compile threads of the instance.
+    pub fn install<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        self.0.thread_pool.install(f)
+    }
+
+    /// Use the cache directory and log callback of the instance on the current thread,
+    /// until the returned guard is dropped.
+    ///
+    /// This is used for the work of filter chain creation that must stay on the current thread,
+    /// such as work that needs a graphics context that is current to the thread.
+    pub fn enter(&self) -> EnterGuard {
+        EnterGuard {
+            cache_dir: librashader_cache::set_thread_cache_dir(self.0.cache_dir.clone()),
+            log_callback: LOG_CALLBACK.replace(self.0.log_callback.clone()),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// The number of threads in the compile thread pool of the instance.
+    pub fn compile_threads(&self) -> usize {
+        self.0.thread_pool.current_num_threads()
+    }
+
+    /// The directory of the shader and pipeline cache, if it was set for the instance.
+    pub fn cache_dir(&self) -> Option<&Path> {
+        self.0.cache_dir.as_deref()
+    }
+
+    /// Report a message to the log callback of the instance, if any.
+    pub fn log(&self, level: LogLevel, message: &str) {
+        if let Some(callback) = &self.0.log_callback {
+            callback.log(level, message)
+        }
+    }
+}
+
+impl Debug for LibrashaderInstance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LibrashaderInstance")
+            .field("compile_threads", &self.compile_threads())
+            .field("cache_dir", &self.0.cache_dir)
+            .field("log_callback", &self.0.log_callback)
+            .finish()
+    }
+}
+
+/// Run the closure on the compile thread pool of the instance if there is one, or on the
+/// global thread pool otherwise.
+pub fn install<R: Send>(instance: Option<&LibrashaderInstance>, f: impl FnOnce() -> R + Send) -> R {
+    match instance {
+        Some(instance) => instance.install(f),
+        None => f(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn install_runs_on_compile_threads() {
+        let instance = LibrashaderInstance::new(InstanceOptions {
+            compile_threads: 2,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(instance.compile_threads(), 2);
+        let name = instance.install(|| std::thread::current().name().map(String::from));
+        assert!(name.unwrap().starts_with("librashader-compile-"));
+    }
+
+    #[test]
+    fn enter_scopes_log_callback() {
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&messages);
+        let instance = LibrashaderInstance::new(InstanceOptions {
+            compile_threads: 1,
+            log_callback: Some(LogCallback::new(move |_, message| {
+                sink.lock().unwrap().push(message.to_string())
+            })),
+            ..Default::default()
+        })
+        .unwrap();
+
+        {
+            let _guard = instance.enter();
+            log::warn!("inside");
+        }
+        log::warn!("outside");
+        instance.install(|| log::warn!("compile"));
+
+        assert_eq!(*messages.lock().unwrap(), vec!["inside", "compile"]);
+    }
+}
@@ -0,0 +1,60 @@
+/// The number of offsets in the Halton sequence before it repeats.
+pub const HALTON_SEQUENCE_LENGTH: usize = 16;
+
+/// A sequence of sub-pixel offsets bound to the `FrameJitter` uniform.
+#[repr(u32)]
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Hash)]
+pub enum JitterSequence {
+    /// No jitter. The offset is always `(0, 0)`.
+    #[default]
+    None = 0,
+    /// The Halton (2, 3) sequence, indexed by the frame count and repeating every
+    /// [`HALTON_SEQUENCE_LENGTH`] frames.
+    Halton,
+    /// A custom offset provided by the frontend for every frame.
+    Custom,
+}
+
+impl From<u32> for JitterSequence {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => JitterSequence::Halton,
+            2 => JitterSequence::Custom,
+            _ => JitterSequence::None,
+        }
+    }
+}
+
+/// Get the element at `index` of the Halton sequence of the given base, in the range `[0, 1)`.
+pub fn halton(mut index: usize, base: usize) -> f32 {
+    let mut fraction = 1.0f32;
+    let mut result = 0.0f32;
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+    result
+}
+
+/// Get the offset of the Halton (2, 3) sequence for the given frame, in pixels in the
+/// range `[-0.5, 0.5)`.
+///
+/// Frontends that render jittered content for the `Halton` sequence should offset the
+/// content by the same amount.
+pub fn halton_jitter(frame_count: usize) -> [f32; 2] {
+    // the sequence starts at 1, because every Halton sequence starts with 0 at index 0.
+    let index = frame_count % HALTON_SEQUENCE_LENGTH + 1;
+    [halton(index, 2) - 0.5, halton(index, 3) - 0.5]
+}
+
+/// Resolve the jitter offset of a frame in pixels.
+///
+/// `offset` is only used for the `Custom` sequence.
+pub fn resolve(sequence: JitterSequence, offset: [f32; 2], frame_count: usize) -> [f32; 2] {
+    match sequence {
+        JitterSequence::None => [0.0, 0.0],
+        JitterSequence::Halton => halton_jitter(frame_count),
+        JitterSequence::Custom => offset,
+    }
+}
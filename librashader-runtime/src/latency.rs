@@ -0,0 +1,50 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// A point in the recording of the commands of a filter chain for a frame.
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum LatencyMarker {
+    /// The filter chain is about to record the commands for the frame.
+    RecordBegin = 0,
+    /// The filter chain recorded all commands for the frame.
+    RecordEnd,
+}
+
+/// A latency marker for a frame.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LatencyEvent {
+    /// The point in the recording of the frame.
+    pub marker: LatencyMarker,
+    /// The frame count that was passed to the filter chain for the frame.
+    pub frame_count: usize,
+}
+
+/// A callback that is called at the start and end of the recording of the commands of a frame,
+/// so that frontends can forward the markers to latency SDKs such as NVIDIA Reflex or
+/// AMD Anti-Lag, and schedule the filter chain within their frame.
+///
+/// The callback is called on the thread that records the frame, at the time of the marker.
+#[derive(Clone)]
+pub struct LatencyMarkerCallback(Arc<dyn Fn(LatencyEvent) + Send + Sync>);
+
+impl LatencyMarkerCallback {
+    /// Create a latency marker callback from a closure.
+    pub fn new(callback: impl Fn(LatencyEvent) + Send + Sync + 'static) -> Self {
+        LatencyMarkerCallback(Arc::new(callback))
+    }
+
+    /// Report a marker for the frame with the given frame count.
+    pub fn mark(&self, marker: LatencyMarker, frame_count: usize) {
+        (self.0)(LatencyEvent {
+            marker,
+            frame_count,
+        })
+    }
+}
+
+impl Debug for LatencyMarkerCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LatencyMarkerCallback")
+    }
+}
@@ -8,6 +8,16 @@
 //! help in maintaining consistent behaviour in binding semantics and image handling.
 
 /// Scaling helpers.
+///
+/// These implement the scaling rules of shader presets, and are shared by every runtime so
+/// that pass outputs have the same size everywhere. Runtimes outside of librashader should use
+/// these helpers rather than re-implementing the rules.
+///
+/// * The size of each axis is the scaled size rounded to the nearest integer, with halfway
+///   cases rounded away from zero.
+/// * The size of each axis is clamped to at least 1, and at most [`MAX_TEXEL_SIZE`](crate::scaling::MAX_TEXEL_SIZE).
+///   Scaled sizes that are not a number are clamped to 1.
+/// * Mipmap levels halve the size of each axis, rounding down, to a minimum of 1.
 pub mod scaling;
 
 /// Uniform binding helpers.
@@ -28,6 +38,33 @@ pub mod binding;
 /// VBO helper utilities.
 pub mod quad;
 
+/// Custom meshes for the final pass.
+///
+/// Frontends that warp the output geometry, such as for curved displays or projection
+/// mapping, can draw the final pass with a tessellated mesh instead of the default quad.
+pub mod mesh;
+
+/// MVP matrix helpers.
+///
+/// MVP matrices are 4x4 matrices of 16 consecutive `f32` in column-major order, that
+/// transform the vertices of the final pass quad from `(0, 0)` to `(1, 1)` into clip space.
+///
+/// Every runtime accounts for the clip space and texture origin of its graphics API, so the
+/// same MVP renders the same output on every runtime. An MVP built for the clip space of a
+/// specific graphics API, such as one where the Y axis points down, needs to be flipped with
+/// [`mvp::flip_y`](crate::mvp::flip_y) or the `flip_mvp_y` frame option.
+pub mod mvp;
+
+/// Sub-pixel jitter sequences for temporal anti-aliasing.
+///
+/// The `FrameJitter` uniform is the sub-pixel offset of the original input for the current frame.
+/// The frontend is expected to render its content with the same offset, so that shaders can
+/// accumulate jittered frames over time.
+pub mod jitter;
+
+/// Frame count handling for frontends that rewind.
+pub mod rewind;
+
 /// Filter pass helpers and common traits.
 pub mod filter_pass;
 
@@ -36,3 +73,30 @@ pub mod render_target;
 
 /// Helpers for handling framebuffers.
 pub mod framebuffer;
+
+/// Progress reporting and cancellation for filter chain creation.
+pub mod progress;
+
+/// Building pipelines on worker threads.
+pub mod pipeline_build;
+
+/// Creating filter chains on worker threads.
+pub mod pending;
+
+/// Reloading filter chains when the files of their preset change.
+pub mod watch;
+
+/// Per-frame statistics of the commands recorded by a filter chain.
+pub mod statistics;
+
+/// Latency markers around the commands recorded by a filter chain.
+pub mod latency;
+
+/// Instances that own the services shared by filter chains.
+pub mod instance;
+
+/// Warnings about filter chain options that could not be honoured as given.
+pub mod warnings;
+
+/// Sharing uploaded LUT textures between filter chains.
+pub mod luts;
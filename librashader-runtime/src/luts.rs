@@ -0,0 +1,190 @@
+use crate::image::{ImageError, LoadedTexture, PixelFormat};
+use librashader_common::map::FastHashMap;
+use librashader_pack::TextureResource;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// A set of LUT textures uploaded to a device, that can be shared between filter chains
+/// created on the same device.
+///
+/// Textures are identified by their pixels and sampling configuration, so presets that share
+/// texture packs reuse the same uploaded textures, even if they bind them under different names.
+/// Clones of a bundle share the same textures. A texture stays alive as long as the bundle or
+/// any filter chain created with it holds it.
+pub struct LutBundle<T>(Arc<Mutex<FastHashMap<[u8; 32], Arc<T>>>>);
+
+impl<T> LutBundle<T> {
+    /// Create an empty bundle.
+    pub fn new() -> Self {
+        LutBundle(Arc::new(Mutex::new(FastHashMap::default())))
+    }
+
+    fn textures(&self) -> MutexGuard<'_, FastHashMap<[u8; 32], Arc<T>>> {
+        // The map is never left in an inconsistent state, so a poisoned lock can be recovered.
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The number of textures in the bundle.
+    pub fn len(&self) -> usize {
+        self.textures().len()
+    }
+
+    /// Whether the bundle has no textures.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove every texture from the bundle.
+    ///
+    /// Textures held by filter chains are released once those filter chains are dropped.
+    pub fn clear(&self) {
+        self.textures().clear()
+    }
+
+    /// Load the LUT textures of a preset, reusing the textures that are already in the bundle.
+    ///
+    /// The remaining textures are decoded together with `decode`, so that runtimes can decode
+    /// them in parallel, then uploaded one by one with `upload` and added to the bundle.
+    /// Without a bundle, every texture is decoded and uploaded.
+    pub fn load<P, E>(
+        bundle: Option<&Self>,
+        textures: Vec<TextureResource>,
+        decode: impl FnOnce(Vec<TextureResource>) -> Result<Vec<LoadedTexture<P>>, ImageError>,
+        mut upload: impl FnMut(LoadedTexture<P>) -> Result<T, E>,
+    ) -> Result<FastHashMap<usize, Arc<T>>, E>
+    where
+        P: PixelFormat,
+        E: From<ImageError>,
+    {
+        let mut luts = FastHashMap::default();
+        let mut pending = Vec::new();
+        let mut pending_keys = Vec::new();
+
+        for (index, texture) in textures.into_iter().enumerate() {
+            let key = bundle.map(|_| texture.content_hash());
+            if let Some((bundle, key)) = bundle.zip(key) {
+                if let Some(texture) = bundle.textures().get(&key) {
+                    luts.insert(index, Arc::clone(texture));
+                    continue;
+                }
+            }
+
+            pending.push(texture);
+            pending_keys.push((index, key));
+        }
+
+        if pending.is_empty() {
+            return Ok(luts);
+        }
+
+        for ((index, key), texture) in pending_keys.into_iter().zip(decode(pending)?) {
+            let texture = Arc::new(upload(texture)?);
+            if let Some((bundle, key)) = bundle.zip(key) {
+                bundle.textures().insert(key, Arc::clone(&texture));
+            }
+            luts.insert(index, texture);
+        }
+
+        Ok(luts)
+    }
+}
+
+impl<T> Default for LutBundle<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for LutBundle<T> {
+    fn clone(&self) -> Self {
+        LutBundle(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Debug for LutBundle<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LutBundle")
+            .field("textures", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LutBundle;
+    use crate::image::{ImageError, LoadedTexture, UVDirection, RGBA8};
+    use image::RgbaImage;
+    use librashader_common::{FilterMode, WrapMode};
+    use librashader_pack::TextureResource;
+    use librashader_presets::TextureMeta;
+
+    fn texture(name: &str, value: u8) -> TextureResource {
+        TextureResource {
+            data: RgbaImage::from_pixel(2, 2, image::Rgba([value, 0, 0, 255])).into(),
+            meta: TextureMeta {
+                name: name.into(),
+                wrap_mode: WrapMode::ClampToEdge,
+                filter_mode: FilterMode::Linear,
+                mipmap: false,
+                srgb: false,
+            },
+            path: None,
+        }
+    }
+
+    fn load(
+        bundle: Option<&LutBundle<u8>>,
+        textures: Vec<TextureResource>,
+        uploads: &mut usize,
+    ) -> Vec<u8> {
+        let luts = LutBundle::load(
+            bundle,
+            textures,
+            |textures| {
+                textures
+                    .into_iter()
+                    .map(|texture| {
+                        LoadedTexture::<RGBA8>::from_texture(texture, UVDirection::TopLeft)
+                    })
+                    .collect()
+            },
+            |texture| {
+                *uploads += 1;
+                Ok::<_, ImageError>(texture.image.bytes[0])
+            },
+        )
+        .unwrap();
+
+        (0..luts.len()).map(|index| *luts[&index]).collect()
+    }
+
+    #[test]
+    fn reuses_textures() {
+        let bundle = LutBundle::new();
+        let mut uploads = 0;
+
+        let luts = load(
+            Some(&bundle),
+            vec![texture("a", 1), texture("b", 2)],
+            &mut uploads,
+        );
+        assert_eq!(luts, [1, 2]);
+        assert_eq!((uploads, bundle.len()), (2, 2));
+
+        // the same pixels under a different name are reused.
+        let luts = load(
+            Some(&bundle),
+            vec![texture("c", 3), texture("renamed", 2)],
+            &mut uploads,
+        );
+        assert_eq!(luts, [3, 2]);
+        assert_eq!((uploads, bundle.len()), (3, 3));
+
+        let luts = load(None, vec![texture("a", 1)], &mut uploads);
+        assert_eq!(luts, [1]);
+        assert_eq!((uploads, bundle.len()), (4, 3));
+
+        bundle.clear();
+        assert!(bundle.is_empty());
+    }
+}
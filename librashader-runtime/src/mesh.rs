@@ -0,0 +1,122 @@
+use crate::quad::VertexInput;
+
+/// A mesh to draw the final pass with, in place of the default quad.
+///
+/// The vertices are drawn as a triangle strip. Positions are in the space of the default
+/// final pass quad, where `(0, 0)` is the top-left and `(1, 1)` the bottom-right corner of the
+/// viewport before the MVP is applied. Texture coordinates are in the same orientation,
+/// where `(0, 0)` is the top-left corner of the image.
+///
+/// Runtimes account for the clip space and texture origin of their graphics API, so the
+/// same mesh renders the same output on every runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputMesh {
+    vertices: Vec<VertexInput>,
+}
+
+impl OutputMesh {
+    /// Create a mesh from the vertices of a triangle strip.
+    ///
+    /// Returns `None` if there are fewer than 3 vertices, or more than `u32::MAX`.
+    pub fn new(vertices: Vec<VertexInput>) -> Option<Self> {
+        if vertices.len() < 3 || u32::try_from(vertices.len()).is_err() {
+            return None;
+        }
+
+        Some(Self { vertices })
+    }
+
+    /// Create a tessellated grid of `columns` by `rows` cells covering the image.
+    ///
+    /// `warp` maps the texture coordinates of each vertex to its position, which allows the
+    /// output to be curved or projected onto non-planar surfaces. Rows are joined with
+    /// degenerate triangles, so the grid is a single triangle strip.
+    pub fn grid(columns: u32, rows: u32, mut warp: impl FnMut([f32; 2]) -> [f32; 4]) -> Self {
+        let columns = columns.max(1);
+        let rows = rows.max(1);
+
+        let mut vertex = |column: u32, row: u32| {
+            let texcoord = [column as f32 / columns as f32, row as f32 / rows as f32];
+            VertexInput {
+                position: warp(texcoord),
+                texcoord,
+            }
+        };
+
+        let mut vertices = Vec::with_capacity((rows * (2 * columns + 4)) as usize);
+        for row in 0..rows {
+            if row > 0 {
+                // repeat the first vertex of the row to join it to the previous row.
+                vertices.push(vertex(0, row));
+            }
+
+            for column in 0..=columns {
+                vertices.push(vertex(column, row));
+                vertices.push(vertex(column, row + 1));
+            }
+
+            if row + 1 < rows {
+                // repeat the last vertex of the row to join it to the next row.
+                vertices.push(vertex(columns, row + 1));
+            }
+        }
+
+        Self { vertices }
+    }
+
+    /// The vertices of the triangle strip.
+    pub fn vertices(&self) -> &[VertexInput] {
+        &self.vertices
+    }
+
+    /// The number of vertices to draw.
+    pub fn vertex_count(&self) -> u32 {
+        // the number of vertices is checked when the mesh is created.
+        self.vertices.len() as u32
+    }
+
+    /// Get the vertices with the Y axis of the positions flipped, and of the texture coordinates
+    /// if `texcoord` is true, for graphics APIs whose clip space or texture origin is at the bottom.
+    pub fn flipped_vertices(&self, texcoord: bool) -> Vec<VertexInput> {
+        self.vertices
+            .iter()
+            .map(|vertex| {
+                let mut vertex = *vertex;
+                vertex.position[1] = 1.0 - vertex.position[1];
+                if texcoord {
+                    vertex.texcoord[1] = 1.0 - vertex.texcoord[1];
+                }
+                vertex
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OutputMesh;
+
+    #[test]
+    fn grid_is_one_strip() {
+        let mesh = OutputMesh::grid(2, 3, |[x, y]| [x, y, 0.0, 1.0]);
+        let vertices = mesh.vertices();
+
+        // 2 * (columns + 1) vertices per row, and 2 degenerate vertices between rows.
+        assert_eq!(vertices.len(), 3 * 6 + 2 * 2);
+        assert_eq!(vertices[0].texcoord, [0.0, 0.0]);
+        assert_eq!(vertices[vertices.len() - 1].texcoord, [1.0, 1.0]);
+
+        // rows are joined by repeating the last vertex of a row and the first of the next.
+        assert_eq!(vertices[5].texcoord, vertices[6].texcoord);
+        assert_eq!(vertices[7].texcoord, vertices[8].texcoord);
+    }
+
+    #[test]
+    fn rejects_degenerate_mesh() {
+        assert!(OutputMesh::new(Vec::new()).is_none());
+        assert!(OutputMesh::new(
+            OutputMesh::grid(1, 1, |[x, y]| [x, y, 0.0, 1.0]).vertices[..2].to_vec()
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,47 @@
+use crate::quad::DEFAULT_MVP;
+
+/// Transpose an MVP from row-major to column-major order, or vice versa.
+pub fn transpose(mvp: &[f32; 16]) -> [f32; 16] {
+    std::array::from_fn(|index| mvp[(index % 4) * 4 + index / 4])
+}
+
+/// Flip the Y axis of the clip space output of an MVP, which flips the rendered output vertically.
+pub fn flip_y(mvp: &[f32; 16]) -> [f32; 16] {
+    let mut mvp = *mvp;
+    for column in mvp.chunks_exact_mut(4) {
+        column[1] = -column[1];
+    }
+    mvp
+}
+
+/// Build an MVP that maps the final pass quad to the given rectangle in clip space.
+///
+/// The default MVP is equivalent to `from_clip_rect(-1.0, 1.0, -1.0, 1.0)`.
+#[rustfmt::skip]
+pub fn from_clip_rect(left: f32, right: f32, bottom: f32, top: f32) -> [f32; 16] {
+    [
+        right - left, 0.0, 0.0, 0.0,
+        0.0, top - bottom, 0.0, 0.0,
+        0.0, 0.0, 0.0, 0.0,
+        left, bottom, 0.0, 1.0,
+    ]
+}
+
+/// Resolve the MVP to render the final pass with.
+///
+/// If `transpose` is true, the provided MVP is in row-major order and is transposed.
+/// If `flip_y` is true, the Y axis of the resolved MVP is flipped. If no MVP is provided,
+/// the default MVP is used.
+pub fn resolve(mvp: Option<&[f32; 16]>, transpose: bool, flip_y: bool) -> [f32; 16] {
+    let mut resolved = match mvp {
+        Some(mvp) if transpose => self::transpose(mvp),
+        Some(mvp) => *mvp,
+        None => *DEFAULT_MVP,
+    };
+
+    if flip_y {
+        resolved = self::flip_y(&resolved);
+    }
+
+    resolved
+}
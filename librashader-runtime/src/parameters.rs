@@ -1,5 +1,6 @@
 use arc_swap::ArcSwap;
-use librashader_common::map::{FastHashMap, ShortString};
+use librashader_common::map::{FastHashMap, OrderedHashMap, ShortString};
+use librashader_preprocess::ShaderParameter;
 use librashader_presets::ParameterMeta;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -10,33 +11,154 @@ pub trait FilterChainParameters {
     fn parameters(&self) -> &RuntimeParameters;
 }
 
+/// A handle to a shader parameter of a filter chain.
+///
+/// Handles are resolved once by name with [`RuntimeParameters::handle`], and can then be
+/// used to get or set the parameter without looking it up by name. A handle is only valid
+/// for the [`RuntimeParameters`] it was resolved from.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[repr(transparent)]
+pub struct ParameterHandle(u32);
+
+impl ParameterHandle {
+    /// The index of the parameter in the runtime parameter values.
+    #[inline(always)]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Handles can be converted to and from integers to pass them across an FFI boundary.
+///
+/// Handles that were not resolved from the [`RuntimeParameters`] they are used with
+/// are either rejected, or refer to an arbitrary parameter.
+impl From<u32> for ParameterHandle {
+    fn from(value: u32) -> Self {
+        ParameterHandle(value)
+    }
+}
+
+impl From<ParameterHandle> for u32 {
+    fn from(value: ParameterHandle) -> Self {
+        value.0
+    }
+}
+
 /// Runtime reflection of shader parameters for filter chains.
 ///
+/// Every parameter set by the preset or declared by any shader pass is interned to a
+/// [`ParameterHandle`] when the filter chain is created. Parameters that are not set by
+/// the preset start at their declared initial value.
+///
+/// Handles are assigned in a stable order: first the parameters set by the preset in the
+/// order they appear in the preset, then the remaining parameters in the order of the passes
+/// that declare them, and in declaration order within a pass.
+///
 /// All operations on runtime parameters are atomic and can be done on
 /// any thread.
 pub struct RuntimeParameters {
     passes_enabled: AtomicUsize,
-    pub(crate) parameters: ArcSwap<FastHashMap<ShortString, f32>>,
+    handles: FastHashMap<ShortString, ParameterHandle>,
+    names: Box<[ShortString]>,
+    meta: Box<[Option<ShaderParameter>]>,
+    initial: Box<[f32]>,
+    pub(crate) values: ArcSwap<Box<[f32]>>,
 }
 
 impl RuntimeParameters {
     /// Create a new instance of runtime parameters from a `Vec` of
-    /// shader parameters from a [`ShaderPreset`](librashader_presets::ShaderPreset).
-    pub fn new(passes_enabled: usize, parameters: Vec<ParameterMeta>) -> Self {
+    /// shader parameters from a [`ShaderPreset`](librashader_presets::ShaderPreset),
+    /// and the parameters declared by the shader passes of the preset.
+    pub fn new<'a>(
+        passes_enabled: usize,
+        parameters: Vec<ParameterMeta>,
+        declared: impl IntoIterator<Item = &'a ShaderParameter>,
+    ) -> Self {
+        let mut handles = FastHashMap::default();
+        let mut names = Vec::new();
+        let mut values = Vec::new();
+
+        for param in parameters {
+            if handles.contains_key(&param.name) {
+                continue;
+            }
+
+            handles.insert(param.name.clone(), ParameterHandle(names.len() as u32));
+            names.push(param.name);
+            values.push(param.value);
+        }
+
+        let mut meta = vec![None; names.len()];
+        for param in declared {
+            if let Some(handle) = handles.get(&param.id) {
+                let meta = &mut meta[handle.index()];
+                if meta.is_none() {
+                    *meta = Some(param.clone());
+                }
+                continue;
+            }
+
+            handles.insert(param.id.clone(), ParameterHandle(names.len() as u32));
+            names.push(param.id.clone());
+            values.push(param.initial);
+            meta.push(Some(param.clone()));
+        }
+
         RuntimeParameters {
             passes_enabled: AtomicUsize::new(passes_enabled),
-            parameters: ArcSwap::new(Arc::new(
-                parameters
-                    .into_iter()
-                    .map(|param| (param.name, param.value))
-                    .collect(),
-            )),
+            handles,
+            names: names.into_boxed_slice(),
+            meta: meta.into_boxed_slice(),
+            initial: values.clone().into_boxed_slice(),
+            values: ArcSwap::new(Arc::new(values.into_boxed_slice())),
         }
     }
 
+    /// Get the handle of a runtime parameter.
+    pub fn handle(&self, name: &str) -> Option<ParameterHandle> {
+        self.handles.get::<str>(name.as_ref()).copied()
+    }
+
+    /// Get the name of the runtime parameter with the given handle.
+    pub fn parameter_name(&self, handle: ParameterHandle) -> Option<&str> {
+        self.names.get(handle.index()).map(ShortString::as_str)
+    }
+
+    /// Get the declaration of the runtime parameter with the given handle.
+    ///
+    /// If the parameter is declared by more than one shader pass, the first declaration is
+    /// returned. Returns `None` if the parameter is set by the preset but not declared by
+    /// any shader pass.
+    pub fn parameter_meta(&self, handle: ParameterHandle) -> Option<&ShaderParameter> {
+        self.meta.get(handle.index())?.as_ref()
+    }
+
+    /// Get the number of runtime parameters.
+    ///
+    /// Handles of the parameters range from zero up to the number of parameters.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Whether there are no runtime parameters.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
     /// Get the value of a runtime parameter
     pub fn parameter_value(&self, name: &str) -> Option<f32> {
-        self.parameters.load().get::<str>(name.as_ref()).copied()
+        self.parameter_value_by_handle(self.handle(name)?)
+    }
+
+    /// Get the value of a runtime parameter by its handle.
+    #[inline(always)]
+    pub fn parameter_value_by_handle(&self, handle: ParameterHandle) -> Option<f32> {
+        self.values.load().get(handle.index()).copied()
+    }
+
+    /// Get the value a runtime parameter had when the filter chain was created.
+    pub fn initial_value(&self, name: &str) -> Option<f32> {
+        self.initial.get(self.handle(name)?.index()).copied()
     }
 
     /// Set a runtime parameter.
@@ -44,30 +166,64 @@ impl RuntimeParameters {
     /// This is a relatively slow operation as it will be synchronized across threads.
     /// If updating multiple parameters, see [`RuntimeParameters::update_parameters`].
     pub fn set_parameter_value(&self, name: &str, new_value: f32) -> Option<f32> {
-        let mut updated_map = FastHashMap::clone(&self.parameters.load());
-
-        if let Some(value) = updated_map.get_mut::<str>(name.as_ref()) {
-            let old = *value;
-            *value = new_value;
-
-            self.parameters.store(Arc::new(updated_map));
+        self.set_parameter_by_handle(self.handle(name)?, new_value)
+    }
 
-            Some(old)
-        } else {
-            None
-        }
+    /// Set a runtime parameter by its handle.
+    ///
+    /// This is a relatively slow operation as it will be synchronized across threads.
+    /// If updating multiple parameters, see [`RuntimeParameters::update_parameters_by_handle`].
+    pub fn set_parameter_by_handle(&self, handle: ParameterHandle, new_value: f32) -> Option<f32> {
+        let mut updated_values = Box::clone(&self.values.load());
+        let value = updated_values.get_mut(handle.index())?;
+        let old = std::mem::replace(value, new_value);
+        self.values.store(Arc::new(updated_values));
+        Some(old)
     }
 
     /// Update multiple runtime parameters atomically through a function.
-    pub fn update_parameters(&self, updater: impl FnOnce(&mut FastHashMap<ShortString, f32>)) {
-        let mut updated_map = FastHashMap::clone(&self.parameters.load());
+    ///
+    /// Parameters inserted into the map that are neither set by the preset nor declared
+    /// by any shader pass are ignored.
+    pub fn update_parameters(&self, updater: impl FnOnce(&mut OrderedHashMap<ShortString, f32>)) {
+        let mut updated_map = OrderedHashMap::clone(&self.parameters());
         updater(&mut updated_map);
-        self.parameters.store(Arc::new(updated_map));
+        self.update_parameters_by_handle(|values| {
+            for (name, value) in updated_map {
+                if let Some(handle) = self.handle(&name) {
+                    values[handle.index()] = value;
+                }
+            }
+        });
+    }
+
+    /// Update multiple runtime parameters atomically through a function.
+    ///
+    /// The values are indexed by [`ParameterHandle::index`].
+    pub fn update_parameters_by_handle(&self, updater: impl FnOnce(&mut [f32])) {
+        let mut updated_values = Box::clone(&self.values.load());
+        updater(&mut updated_values);
+        self.values.store(Arc::new(updated_values));
     }
 
-    /// Get a reference to the runtime parameters.
-    pub fn parameters(&self) -> Arc<FastHashMap<ShortString, f32>> {
-        self.parameters.load_full()
+    /// Get a snapshot of the runtime parameters.
+    ///
+    /// The snapshot iterates in the same order as the parameter handles are assigned.
+    pub fn parameters(&self) -> Arc<OrderedHashMap<ShortString, f32>> {
+        let values = self.values.load();
+        Arc::new(
+            self.names
+                .iter()
+                .cloned()
+                .zip(values.iter().copied())
+                .collect(),
+        )
+    }
+
+    /// Get a reference to the values of the runtime parameters, indexed by [`ParameterHandle::index`].
+    #[inline(always)]
+    pub fn values(&self) -> Arc<Box<[f32]>> {
+        self.values.load_full()
     }
 
     /// Get the number of passes enabled.
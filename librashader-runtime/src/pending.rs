@@ -0,0 +1,276 @@
+use std::any::Any;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::task::{Context, Poll, Waker};
+use std::thread::Result as ThreadResult;
+
+enum State<T> {
+    Pending(Option<Waker>),
+    Ready(ThreadResult<T>),
+    Taken,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    ready: Condvar,
+}
+
+/// A filter chain that is being created on a worker thread.
+///
+/// The filter chain can be polled for without blocking with [`PendingFilterChain::try_take`],
+/// waited for with [`PendingFilterChain::wait`], or awaited as a [`Future`].
+///
+/// If the worker thread panicked, the panic is resumed on the thread that takes the result.
+/// Dropping a pending filter chain does not stop its creation, but the filter chain is dropped
+/// once it is created. Creation can be stopped with the cancellation token of the filter chain
+/// options.
+///
+/// Everything that the filter chain is created with, such as the device and the callbacks and
+/// cancellation token of the options, is used by the worker thread until the result is ready.
+/// The progress callback is called on the worker thread.
+pub struct PendingFilterChain<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Send + 'static> PendingFilterChain<T> {
+    /// Start creating a filter chain on a new worker thread.
+    ///
+    /// Returns an error if the worker thread could not be spawned.
+    pub fn spawn(load: impl FnOnce() -> T + Send + 'static) -> std::io::Result<Self> {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State::Pending(None)),
+            ready: Condvar::new(),
+        });
+
+        let worker = Arc::clone(&shared);
+        std::thread::Builder::new()
+            .name(String::from("librashader-load"))
+            .spawn(move || {
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(load));
+                let mut state = worker.state.lock().unwrap_or_else(PoisonError::into_inner);
+                if let State::Pending(Some(waker)) =
+                    std::mem::replace(&mut *state, State::Ready(result))
+                {
+                    waker.wake();
+                }
+                worker.ready.notify_all();
+            })?;
+
+        Ok(PendingFilterChain { shared })
+    }
+}
+
+impl<T> PendingFilterChain<T> {
+    /// Whether the filter chain has finished creating, and can be taken without blocking.
+    pub fn is_ready(&self) -> bool {
+        matches!(
+            *self
+                .shared
+                .state
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+            State::Ready(_)
+        )
+    }
+
+    /// Take the result of filter chain creation if it has finished, without blocking.
+    ///
+    /// Returns `None` if the filter chain is still being created, or if the result was already taken.
+    pub fn try_take(&mut self) -> Option<T> {
+        let mut state = self
+            .shared
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match std::mem::replace(&mut *state, State::Taken) {
+            State::Ready(result) => Some(unwrap_result(result)),
+            other => {
+                *state = other;
+                None
+            }
+        }
+    }
+
+    /// Block until the filter chain is created, and take the result.
+    ///
+    /// ## Panics
+    /// Panics if the result was already taken with [`PendingFilterChain::try_take`].
+    pub fn wait(self) -> T {
+        let mut state = self
+            .shared
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        while matches!(*state, State::Pending(_)) {
+            state = self
+                .shared
+                .ready
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+
+        match std::mem::replace(&mut *state, State::Taken) {
+            State::Ready(result) => unwrap_result(result),
+            _ => panic!("the result of the pending filter chain was already taken"),
+        }
+    }
+}
+
+fn unwrap_result<T>(result: ThreadResult<T>) -> T {
+    result.unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+}
+
+impl<T> Future for PendingFilterChain<T> {
+    type Output = T;
+
+    /// ## Panics
+    /// Panics if polled after the result was taken.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self
+            .shared
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match std::mem::replace(&mut *state, State::Taken) {
+            State::Ready(result) => Poll::Ready(unwrap_result(result)),
+            State::Pending(_) => {
+                *state = State::Pending(Some(cx.waker().clone()));
+                Poll::Pending
+            }
+            State::Taken => panic!("the result of the pending filter chain was already taken"),
+        }
+    }
+}
+
+/// A filter chain whose shader passes are being compiled on a worker thread.
+///
+/// Unlike [`PendingFilterChain`], only the shader passes are compiled on the worker thread.
+/// The rest of the filter chain is created on the thread that takes the result, for runtimes
+/// whose device or context can not be used from other threads. The taking thread must therefore
+/// be able to use the device or context of the filter chain, and the pending filter chain can not
+/// be sent to other threads.
+///
+/// Otherwise, this behaves like [`PendingFilterChain`]. The progress callback and cancellation
+/// token of the options are used by the worker thread until the shader passes are compiled.
+pub struct LocalPendingFilterChain<T> {
+    compiled: PendingFilterChain<Box<dyn Any + Send>>,
+    finish: Option<Box<dyn FnOnce(Box<dyn Any + Send>) -> T>>,
+}
+
+impl<T> LocalPendingFilterChain<T> {
+    /// Start compiling the shader passes of a filter chain on a new worker thread, to finish
+    /// creating the filter chain with on the thread that takes the result.
+    ///
+    /// Returns an error if the worker thread could not be spawned.
+    pub fn spawn<C: Send + 'static>(
+        compile: impl FnOnce() -> C + Send + 'static,
+        finish: impl FnOnce(C) -> T + 'static,
+    ) -> std::io::Result<Self> {
+        let compiled =
+            PendingFilterChain::spawn(move || Box::new(compile()) as Box<dyn Any + Send>)?;
+
+        Ok(LocalPendingFilterChain {
+            compiled,
+            finish: Some(Box::new(move |compiled: Box<dyn Any + Send>| {
+                // the worker thread always returns the output of `compile`.
+                let Ok(compiled) = compiled.downcast::<C>() else {
+                    unreachable!()
+                };
+                finish(*compiled)
+            })),
+        })
+    }
+
+    /// Whether the shader passes have finished compiling, and the filter chain can be taken
+    /// without waiting for the worker thread.
+    pub fn is_ready(&self) -> bool {
+        self.compiled.is_ready()
+    }
+
+    /// Finish creating the filter chain if its shader passes have finished compiling, without
+    /// waiting for the worker thread.
+    ///
+    /// Returns `None` if the shader passes are still being compiled, or if the result was already taken.
+    pub fn try_take(&mut self) -> Option<T> {
+        let compiled = self.compiled.try_take()?;
+        let finish = self.finish.take()?;
+        Some(finish(compiled))
+    }
+
+    /// Block until the shader passes are compiled, and finish creating the filter chain.
+    ///
+    /// ## Panics
+    /// Panics if the result was already taken with [`LocalPendingFilterChain::try_take`].
+    pub fn wait(self) -> T {
+        let compiled = self.compiled.wait();
+        match self.finish {
+            Some(finish) => finish(compiled),
+            None => panic!("the result of the pending filter chain was already taken"),
+        }
+    }
+}
+
+impl<T> Future for LocalPendingFilterChain<T> {
+    type Output = T;
+
+    /// ## Panics
+    /// Panics if polled after the result was taken.
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let Poll::Ready(compiled) = Pin::new(&mut self.compiled).poll(cx) else {
+            return Poll::Pending;
+        };
+        match self.finish.take() {
+            Some(finish) => Poll::Ready(finish(compiled)),
+            None => panic!("the result of the pending filter chain was already taken"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LocalPendingFilterChain, PendingFilterChain};
+    use std::rc::Rc;
+    use std::sync::mpsc;
+
+    #[test]
+    fn try_take_after_finished() {
+        let (sender, receiver) = mpsc::channel::<()>();
+        let mut pending = PendingFilterChain::spawn(move || {
+            receiver.recv().unwrap();
+            10
+        })
+        .unwrap();
+
+        assert!(!pending.is_ready());
+        assert_eq!(pending.try_take(), None);
+
+        sender.send(()).unwrap();
+        assert_eq!(pending.wait(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "load failed")]
+    fn wait_resumes_panic() {
+        let pending = PendingFilterChain::<()>::spawn(|| panic!("load failed")).unwrap();
+        pending.wait();
+    }
+
+    #[test]
+    fn local_finishes_on_taking_thread() {
+        // the context is not `Send`, so it can only be used by the thread that takes the result.
+        let context = Rc::new(2);
+        let taking_thread = std::thread::current().id();
+        let pending = LocalPendingFilterChain::spawn(
+            || (5, std::thread::current().id()),
+            move |(compiled, compile_thread)| {
+                assert_ne!(compile_thread, std::thread::current().id());
+                assert_eq!(taking_thread, std::thread::current().id());
+                compiled * *context
+            },
+        )
+        .unwrap();
+
+        assert_eq!(pending.wait(), 10);
+    }
+}
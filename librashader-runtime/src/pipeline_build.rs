@@ -0,0 +1,115 @@
+use librashader_common::map::FastHashMap;
+use std::hash::Hash;
+use std::thread::JoinHandle;
+
+/// Pipelines that are being built on worker threads, keyed by the output format they
+/// are built for.
+///
+/// Finished pipelines are taken at the start of a frame, so they are swapped in at a frame
+/// boundary instead of stalling the frame that needed them. Dropping this waits for the
+/// pipelines that are still being built, since they may refer to objects owned by the
+/// filter chain.
+pub struct PendingPipelines<K, P, E> {
+    pending: FastHashMap<K, JoinHandle<Result<P, E>>>,
+}
+
+impl<K, P, E> Default for PendingPipelines<K, P, E> {
+    fn default() -> Self {
+        Self {
+            pending: FastHashMap::default(),
+        }
+    }
+}
+
+impl<K, P, E> PendingPipelines<K, P, E>
+where
+    K: Hash + Eq + Clone,
+    P: Send + 'static,
+    E: Send + 'static,
+{
+    /// Start building the pipeline for the given key on a worker thread, unless it is
+    /// already being built.
+    pub fn spawn(&mut self, key: K, build: impl FnOnce() -> Result<P, E> + Send + 'static) {
+        self.pending
+            .entry(key)
+            .or_insert_with(|| std::thread::spawn(build));
+    }
+
+    /// Whether the pipeline for the given key is still being built.
+    pub fn is_pending(&self, key: &K) -> bool {
+        self.pending.contains_key(key)
+    }
+
+    /// Take every pipeline that has finished building.
+    ///
+    /// If a worker thread panicked, the panic is resumed on the calling thread.
+    pub fn take_finished(&mut self) -> Vec<(K, Result<P, E>)> {
+        let finished: Vec<K> = self
+            .pending
+            .iter()
+            .filter(|(_, handle)| handle.is_finished())
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        finished
+            .into_iter()
+            .filter_map(|key| {
+                let handle = self.pending.remove(&key)?;
+                let result = handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+                Some((key, result))
+            })
+            .collect()
+    }
+
+    /// Wait for every pipeline that is still being built, and take all of them.
+    ///
+    /// Pipelines of worker threads that panicked are skipped.
+    pub fn wait_all(&mut self) -> Vec<(K, Result<P, E>)> {
+        self.pending
+            .drain()
+            .filter_map(|(key, handle)| Some((key, handle.join().ok()?)))
+            .collect()
+    }
+}
+
+impl<K, P, E> Drop for PendingPipelines<K, P, E> {
+    fn drop(&mut self) {
+        for (_, handle) in self.pending.drain() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PendingPipelines;
+    use std::sync::mpsc;
+
+    #[test]
+    fn swaps_finished_pipelines() {
+        let mut pending = PendingPipelines::<u32, u32, ()>::default();
+        let (sender, receiver) = mpsc::channel::<()>();
+
+        pending.spawn(1, move || {
+            receiver.recv().unwrap();
+            Ok(10)
+        });
+        pending.spawn(1, || Ok(20));
+        assert!(pending.is_pending(&1));
+        assert!(pending.take_finished().is_empty());
+
+        sender.send(()).unwrap();
+        let finished = loop {
+            let finished = pending.take_finished();
+            if !finished.is_empty() {
+                break finished;
+            }
+            std::thread::yield_now();
+        };
+
+        assert_eq!(finished, [(1, Ok(10))]);
+        assert!(!pending.is_pending(&1));
+    }
+}
@@ -0,0 +1,131 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A stage of filter chain creation that a shader pass goes through.
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum LoadStage {
+    /// The shader pass was preprocessed and compiled to SPIR-V.
+    Preprocessing = 0,
+    /// The shader pass was reflected and compiled for the graphics API of the runtime.
+    Compiling,
+    /// The pipeline of the shader pass was created.
+    Pipeline,
+}
+
+/// The progress of filter chain creation.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LoadProgress {
+    /// The stage that the shader pass completed.
+    pub stage: LoadStage,
+    /// The index of the shader pass.
+    pub pass: usize,
+    /// The number of shader passes in the preset.
+    pub total: usize,
+}
+
+/// A callback that is called every time a shader pass completes a stage of filter chain
+/// creation, so that frontends can show the progress of loading large presets.
+///
+/// Shader passes are compiled in parallel by most runtimes, so the callback may be called
+/// from multiple threads, and passes may complete a stage out of order.
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<dyn Fn(LoadProgress) + Send + Sync>);
+
+impl ProgressCallback {
+    /// Create a progress callback from a closure.
+    pub fn new(callback: impl Fn(LoadProgress) + Send + Sync + 'static) -> Self {
+        ProgressCallback(Arc::new(callback))
+    }
+}
+
+impl Debug for ProgressCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+/// A token to cancel filter chain creation from another thread.
+///
+/// Clones of a token share the same state, so a frontend can keep a clone and cancel it
+/// when the user switches presets before the filter chain is created. Creation then stops
+/// between shader passes, releases the objects that were already created, and fails with
+/// a cancellation error.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel filter chain creation with this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether this token was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Reports the progress of the shader passes of a preset to an optional callback, and
+/// checks an optional token for cancellation.
+///
+/// The default reporter reports nothing, and is never cancelled.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PassProgress<'a> {
+    callback: Option<&'a ProgressCallback>,
+    cancel: Option<&'a CancellationToken>,
+    total: usize,
+}
+
+impl<'a> PassProgress<'a> {
+    /// Create a reporter for a preset with `total` shader passes.
+    pub fn new(callback: Option<&'a ProgressCallback>, total: usize) -> Self {
+        PassProgress {
+            callback,
+            cancel: None,
+            total,
+        }
+    }
+
+    /// Check the given token for cancellation.
+    pub fn with_cancellation(mut self, cancel: Option<&'a CancellationToken>) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Whether filter chain creation was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Report that the shader pass at `pass` completed a stage.
+    ///
+    /// Passes compiled after the passes of the preset, such as the built-in identity passes,
+    /// are not reported.
+    pub fn report(&self, stage: LoadStage, pass: usize) {
+        let Some(callback) = self.callback else {
+            return;
+        };
+
+        if pass < self.total {
+            (callback.0)(LoadProgress {
+                stage,
+                pass,
+                total: self.total,
+            });
+        }
+    }
+
+    /// Report that every shader pass of the preset completed a stage.
+    pub fn report_all(&self, stage: LoadStage) {
+        for pass in 0..self.total {
+            self.report(stage, pass);
+        }
+    }
+}
@@ -30,7 +30,7 @@ pub static DEFAULT_MVP: &[f32; 16] = &[
 ///
 /// See [IO interface variables](https://github.com/libretro/slang-shaders?tab=readme-ov-file#io-interface-variables)
 #[repr(C)]
-#[derive(Debug, Copy, Clone, Default, Zeroable, Pod)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Zeroable, Pod)]
 pub struct VertexInput {
     pub position: [f32; 4], // vec4 position
     pub texcoord: [f32; 2], // vec2 texcoord;
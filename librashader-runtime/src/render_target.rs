@@ -1,4 +1,4 @@
-use crate::quad::{DEFAULT_MVP, IDENTITY_MVP};
+use crate::quad::IDENTITY_MVP;
 use librashader_common::{GetSize, Size, Viewport};
 use num_traits::{zero, AsPrimitive, Num};
 use std::borrow::Borrow;
@@ -49,22 +49,30 @@ impl<'a, T, C: Num + Copy + 'static> RenderTarget<'a, T, C>
 where
     f32: AsPrimitive<C>,
 {
-    /// Create a viewport render target.
-    pub fn viewport(viewport: &'a Viewport<'a, impl Borrow<T>>) -> Self {
+    /// Create a viewport render target with the given MVP.
+    ///
+    /// The MVP should be resolved from the viewport with [`mvp::resolve`](crate::mvp::resolve).
+    pub fn viewport(viewport: &'a Viewport<'a, impl Borrow<T>>, mvp: &'a [f32; 16]) -> Self {
         RenderTarget {
             output: viewport.output.borrow(),
-            mvp: viewport.mvp.unwrap_or(DEFAULT_MVP),
+            mvp,
             x: viewport.x.as_(),
             y: viewport.y.as_(),
             size: viewport.size,
         }
     }
 
-    /// Create a viewport render target with the given output.
-    pub fn viewport_with_output<S>(output: &'a T, viewport: &'a Viewport<'a, S>) -> Self {
+    /// Create a viewport render target with the given output and MVP.
+    ///
+    /// The MVP should be resolved from the viewport with [`mvp::resolve`](crate::mvp::resolve).
+    pub fn viewport_with_output<S>(
+        output: &'a T,
+        viewport: &'a Viewport<'a, S>,
+        mvp: &'a [f32; 16],
+    ) -> Self {
         RenderTarget {
             output,
-            mvp: viewport.mvp.unwrap_or(DEFAULT_MVP),
+            mvp,
             x: viewport.x.as_(),
             y: viewport.y.as_(),
             size: viewport.size,
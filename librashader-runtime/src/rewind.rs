@@ -0,0 +1,54 @@
+/// Tracks the frame count bound to the `FrameCount` uniform while the frontend is rewinding.
+///
+/// RetroArch does not advance the frame count while rewinding, so shaders that animate with
+/// the frame count stand still instead of jumping ahead. Frontends that keep counting frames
+/// while rewinding can enable freezing to get the same behaviour.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RewindFrameCount {
+    freeze: bool,
+    last_forward: Option<usize>,
+}
+
+impl RewindFrameCount {
+    /// Create a new tracker. If `freeze` is false, frame counts are passed through unchanged.
+    pub fn new(freeze: bool) -> Self {
+        Self {
+            freeze,
+            last_forward: None,
+        }
+    }
+
+    /// Get the frame count to bind for a frame with the given frame direction.
+    ///
+    /// While the frame direction is negative, the frame count of the last frame that was not
+    /// rewound is returned instead.
+    pub fn frame_count(&mut self, frame_count: usize, frame_direction: i32) -> usize {
+        if !self.freeze {
+            return frame_count;
+        }
+
+        if frame_direction < 0 {
+            return *self.last_forward.get_or_insert(frame_count);
+        }
+
+        self.last_forward = Some(frame_count);
+        frame_count
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RewindFrameCount;
+
+    #[test]
+    fn freezes_while_rewinding() {
+        let mut rewind = RewindFrameCount::new(true);
+        assert_eq!(rewind.frame_count(10, 1), 10);
+        assert_eq!(rewind.frame_count(11, -1), 10);
+        assert_eq!(rewind.frame_count(12, -1), 10);
+        assert_eq!(rewind.frame_count(13, 1), 13);
+
+        let mut passthrough = RewindFrameCount::new(false);
+        assert_eq!(passthrough.frame_count(11, -1), 11);
+    }
+}
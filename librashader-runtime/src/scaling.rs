@@ -1,10 +1,12 @@
 use crate::filter_pass::FilterPassMeta;
 use crate::scaling;
+pub use librashader_common::ScalingKernel;
 use librashader_common::{ImageFormat, Size};
 use librashader_presets::{Scale2D, ScaleFactor, ScaleType, Scaling};
 use num_traits::AsPrimitive;
 use std::ops::Mul;
 
+/// The maximum size of each axis of a scaled framebuffer.
 pub const MAX_TEXEL_SIZE: f32 = 16384f32;
 
 /// Trait for size scaling relative to the viewport.
@@ -55,22 +57,21 @@ impl MipmapSize<u32> for Size<u32> {
     }
 
     fn scale_mipmap(self, miplevel: u32) -> Size<u32> {
-        let scaled_width = std::cmp::max(self.width >> miplevel, 1);
-        let scaled_height = std::cmp::max(self.height >> miplevel, 1);
+        let scaled_width = std::cmp::max(self.width.checked_shr(miplevel).unwrap_or(0), 1);
+        let scaled_height = std::cmp::max(self.height.checked_shr(miplevel).unwrap_or(0), 1);
         Size::new(scaled_width, scaled_height)
     }
 }
 
-fn scale<T>(scaling: Scale2D, source: Size<T>, viewport: Size<T>, original: Size<T>) -> Size<T>
+fn scale_axis<T>(scaling: &Scaling, source: T, viewport: T, original: T, fit: f32) -> Option<f32>
 where
-    T: Mul<ScaleFactor, Output = f32> + Copy + Ord + 'static,
-    f32: AsPrimitive<T>,
+    T: Mul<ScaleFactor, Output = f32>,
 {
-    let width = match scaling.x {
+    let size = match *scaling {
         Scaling {
             scale_type: ScaleType::Input,
             factor,
-        } => source.width * factor,
+        } => source * factor,
         Scaling {
             scale_type: ScaleType::Absolute,
             factor,
@@ -78,30 +79,70 @@ where
         Scaling {
             scale_type: ScaleType::Viewport,
             factor,
-        } => viewport.width * factor,
+        } => viewport * factor,
         Scaling {
             scale_type: ScaleType::Original,
             factor,
-        } => original.width * factor,
-    };
-
-    let height = match scaling.y {
+        } => original * factor,
         Scaling {
-            scale_type: ScaleType::Input,
+            scale_type: ScaleType::ViewportAspect,
             factor,
-        } => source.height * factor,
+        } => fit * factor,
+        // depends on the size of the other axis.
         Scaling {
-            scale_type: ScaleType::Absolute,
-            factor,
-        } => factor.into(),
-        Scaling {
-            scale_type: ScaleType::Viewport,
-            factor,
-        } => viewport.height * factor,
-        Scaling {
-            scale_type: ScaleType::Original,
-            factor,
-        } => original.height * factor,
+            scale_type: ScaleType::OriginalAspect,
+            ..
+        } => return None,
+    };
+    Some(size)
+}
+
+fn scale<T>(scaling: Scale2D, source: Size<T>, viewport: Size<T>, original: Size<T>) -> Size<T>
+where
+    T: Mul<ScaleFactor, Output = f32> + Copy + Ord + 'static,
+    f32: AsPrimitive<T>,
+{
+    let unit = ScaleFactor::Float(1.0);
+    let original_width = original.width * unit;
+    let original_height = original.height * unit;
+    let viewport_width = viewport.width * unit;
+    let viewport_height = viewport.height * unit;
+
+    // The largest size with the aspect ratio of the original image that fits in the viewport.
+    // If the original image has no area, this is the size of the viewport.
+    let fit_width = viewport_width.min(viewport_height * original_width / original_height);
+    let fit_height = viewport_height.min(viewport_width * original_height / original_width);
+
+    let width = scale_axis(
+        &scaling.x,
+        source.width,
+        viewport.width,
+        original.width,
+        fit_width,
+    );
+    let height = scale_axis(
+        &scaling.y,
+        source.height,
+        viewport.height,
+        original.height,
+        fit_height,
+    );
+
+    let (width, height) = match (width, height) {
+        (Some(width), Some(height)) => (width, height),
+        (None, Some(height)) => (
+            height * original_width / original_height * scaling.x.factor,
+            height,
+        ),
+        (Some(width), None) => (
+            width,
+            width * original_height / original_width * scaling.y.factor,
+        ),
+        // without a fixed axis, keep the aspect ratio of the original image.
+        (None, None) => (
+            original.width * scaling.x.factor,
+            original.height * scaling.y.factor,
+        ),
     };
 
     Size {
@@ -216,7 +257,7 @@ where
 
         let next_size = output[index].scale(
             pass.meta().scaling.clone(),
-            pass.get_format(),
+            pass.get_supported_format(),
             &viewport_size,
             &target_size,
             &original_size,
@@ -226,7 +267,7 @@ where
 
         feedback[index].scale(
             pass.meta().scaling.clone(),
-            pass.get_format(),
+            pass.get_supported_format(),
             &viewport_size,
             &target_size,
             &original_size,
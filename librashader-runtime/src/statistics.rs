@@ -0,0 +1,129 @@
+use librashader_reflect::reflect::ShaderReflection;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// CPU-side statistics of the commands recorded by a filter chain for a single frame.
+///
+/// These count the commands recorded by the shader passes of the filter chain, and are
+/// meant for tracking performance regressions rather than profiling a frame in detail.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct FrameStatistics {
+    /// The number of draw calls and compute dispatches.
+    pub draw_calls: u32,
+    /// The number of barriers and resource transitions of pass framebuffers and history frames.
+    ///
+    /// This is always 0 for runtimes that do not have explicit barriers.
+    pub barriers: u32,
+    /// The number of uniform buffer and push constant uploads.
+    pub uploads: u32,
+    /// The number of texture and uniform buffer descriptors written or bound.
+    pub descriptor_writes: u32,
+}
+
+/// Records [`FrameStatistics`] while a frame is drawn.
+///
+/// The counters are atomic, so that shader passes can record their commands through a shared
+/// reference to the state of the filter chain. If the recorder is disabled, nothing is recorded.
+#[derive(Debug, Default)]
+pub struct FrameStatisticsRecorder {
+    enabled: bool,
+    draw_calls: AtomicU32,
+    barriers: AtomicU32,
+    uploads: AtomicU32,
+    descriptor_writes: AtomicU32,
+}
+
+impl FrameStatisticsRecorder {
+    /// Create a new recorder.
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    /// Reset the counters before a new frame is drawn.
+    pub fn reset(&self) {
+        if !self.enabled {
+            return;
+        }
+
+        self.draw_calls.store(0, Ordering::Relaxed);
+        self.barriers.store(0, Ordering::Relaxed);
+        self.uploads.store(0, Ordering::Relaxed);
+        self.descriptor_writes.store(0, Ordering::Relaxed);
+    }
+
+    /// Record a draw or dispatch of a shader pass with the given reflection.
+    ///
+    /// This counts one upload for each of the uniform buffer and push constant block that the pass
+    /// uses, and one descriptor write for each texture and the uniform buffer.
+    pub fn record_pass(&self, reflection: &ShaderReflection) {
+        if !self.enabled {
+            return;
+        }
+
+        let buffers = reflection.ubo.is_some() as u32;
+        let uploads = buffers + reflection.push_constant.is_some() as u32;
+        let descriptor_writes = buffers + reflection.meta.texture_meta.len() as u32;
+
+        self.draw_calls.fetch_add(1, Ordering::Relaxed);
+        self.uploads.fetch_add(uploads, Ordering::Relaxed);
+        self.descriptor_writes
+            .fetch_add(descriptor_writes, Ordering::Relaxed);
+    }
+
+    /// Record the given number of barriers or resource transitions.
+    pub fn record_barriers(&self, count: u32) {
+        if !self.enabled {
+            return;
+        }
+
+        self.barriers.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Get the statistics recorded since the last reset, or `None` if the recorder is disabled.
+    pub fn statistics(&self) -> Option<FrameStatistics> {
+        if !self.enabled {
+            return None;
+        }
+
+        Some(FrameStatistics {
+            draw_calls: self.draw_calls.load(Ordering::Relaxed),
+            barriers: self.barriers.load(Ordering::Relaxed),
+            uploads: self.uploads.load(Ordering::Relaxed),
+            descriptor_writes: self.descriptor_writes.load(Ordering::Relaxed),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FrameStatisticsRecorder;
+    use librashader_reflect::reflect::ShaderReflection;
+
+    #[test]
+    fn records_only_when_enabled() {
+        let reflection = ShaderReflection {
+            ubo: None,
+            push_constant: None,
+            meta: Default::default(),
+            compute: None,
+        };
+
+        let recorder = FrameStatisticsRecorder::new(true);
+        recorder.record_pass(&reflection);
+        recorder.record_barriers(2);
+
+        let statistics = recorder.statistics().unwrap();
+        assert_eq!(statistics.draw_calls, 1);
+        assert_eq!(statistics.barriers, 2);
+        assert_eq!(statistics.uploads, 0);
+
+        recorder.reset();
+        assert_eq!(recorder.statistics(), Some(Default::default()));
+
+        let disabled = FrameStatisticsRecorder::new(false);
+        disabled.record_pass(&reflection);
+        assert_eq!(disabled.statistics(), None);
+    }
+}
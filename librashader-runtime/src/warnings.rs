@@ -0,0 +1,118 @@
+use crate::framebuffer::HistoryDepth;
+use std::fmt::{Display, Formatter};
+
+/// An adjustment made while creating a filter chain, because its options could not be
+/// honoured as given or conflict with what the shader preset requires.
+///
+/// Filter chain creation does not fail because of these, but the filter chain may render
+/// differently than the shader preset intends.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionWarning {
+    /// The requested GLSL version is not a known GLSL version, so GLSL 1.50 is used instead.
+    ///
+    /// This is only reported by the OpenGL runtime.
+    UnknownGlslVersion {
+        /// The requested GLSL version.
+        requested: u16,
+    },
+    /// The requested GLSL version is below GLSL 3.30, which most shader presets require.
+    ///
+    /// This is only reported by the OpenGL runtime.
+    GlslVersionBelowMinimum {
+        /// The requested GLSL version.
+        requested: u16,
+    },
+    /// Mipmaps are disabled with the `force_no_mipmaps` option, but shader passes request
+    /// mipmaps of their input, and sample their input without mipmaps instead.
+    MipmapInputDisabled {
+        /// The indices of the shader passes that request mipmaps of their input.
+        passes: Vec<usize>,
+    },
+    /// The number of history frames is capped with the `max_history` option below the
+    /// number of history frames the shader passes sample. Shader passes that sample older
+    /// frames receive the oldest kept frame.
+    HistoryCapped {
+        /// The depth of the history.
+        depth: HistoryDepth,
+    },
+    /// History and feedback are disabled with the `disable_temporal` option, but the shader
+    /// passes sample history or feedback textures, which are black instead.
+    TemporalDisabled,
+    /// Dynamic rendering was requested with the `use_dynamic_rendering` option, but the
+    /// device does not support it, so render pass objects are used instead.
+    ///
+    /// This is only reported by the Vulkan runtime.
+    DynamicRenderingUnsupported,
+}
+
+impl OptionWarning {
+    /// Check the `force_no_mipmaps` option against whether each shader pass of a filter chain
+    /// requests mipmaps of its input.
+    pub fn check_mipmap_input(
+        force_no_mipmaps: bool,
+        mipmap_input: impl IntoIterator<Item = bool>,
+    ) -> Option<OptionWarning> {
+        if !force_no_mipmaps {
+            return None;
+        }
+
+        let passes: Vec<usize> = mipmap_input
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, mipmap)| mipmap.then_some(index))
+            .collect();
+
+        (!passes.is_empty()).then_some(OptionWarning::MipmapInputDisabled { passes })
+    }
+}
+
+impl Display for OptionWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OptionWarning::UnknownGlslVersion { requested } => write!(
+                f,
+                "{requested} is not a known GLSL version, GLSL 150 is used instead"
+            ),
+            OptionWarning::GlslVersionBelowMinimum { requested } => write!(
+                f,
+                "GLSL {requested} is below GLSL 330, which shader presets may require"
+            ),
+            OptionWarning::MipmapInputDisabled { passes } => write!(
+                f,
+                "mipmaps are forced off, but passes {passes:?} request mipmapped input"
+            ),
+            OptionWarning::HistoryCapped { depth } => write!(
+                f,
+                "history is capped to {} frames, but the preset samples {} frames",
+                depth.kept, depth.requested
+            ),
+            OptionWarning::TemporalDisabled => write!(
+                f,
+                "history and feedback are disabled, but the preset samples them"
+            ),
+            OptionWarning::DynamicRenderingUnsupported => write!(
+                f,
+                "dynamic rendering is not supported by the device, render passes are used instead"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OptionWarning;
+
+    #[test]
+    fn mipmap_input() {
+        assert_eq!(
+            OptionWarning::check_mipmap_input(false, [false, true, true]),
+            None
+        );
+        assert_eq!(
+            OptionWarning::check_mipmap_input(true, [false, true, true]),
+            Some(OptionWarning::MipmapInputDisabled { passes: vec![1, 2] })
+        );
+        assert_eq!(OptionWarning::check_mipmap_input(true, [false]), None);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::parameters::FilterChainParameters;
+use librashader_common::shader_features::ShaderFeatures;
+use librashader_preprocess::ShaderSource;
+use librashader_presets::{ParsePresetError, ShaderPreset};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+type PresetParser = Box<dyn FnMut(&Path) -> Result<ShaderPreset, ParsePresetError> + Send>;
+
+/// Watches a shader preset and the files it references for changes, to reload a filter chain
+/// from the preset when any of them are modified.
+///
+/// The watcher watches the preset, the presets it includes with `#reference`, the shader
+/// sources of every pass and the files they include, and the textures of the preset.
+/// Files are watched by polling their modification times, so [`PresetWatcher::poll_reload`]
+/// should be called at most once every few frames.
+pub struct PresetWatcher {
+    path: PathBuf,
+    parse: PresetParser,
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl PresetWatcher {
+    /// Create a watcher for the preset at the given path, that parses the preset with the
+    /// given shader features.
+    pub fn new(path: impl AsRef<Path>, features: ShaderFeatures) -> PresetWatcher {
+        Self::with_parser(path, move |path| ShaderPreset::try_parse(path, features))
+    }
+
+    /// Create a watcher for the preset at the given path, that parses the preset with the
+    /// given function.
+    ///
+    /// This allows the preset to be parsed with a wildcard context or a sandbox.
+    pub fn with_parser(
+        path: impl AsRef<Path>,
+        parse: impl FnMut(&Path) -> Result<ShaderPreset, ParsePresetError> + Send + 'static,
+    ) -> PresetWatcher {
+        PresetWatcher {
+            path: path.as_ref().to_path_buf(),
+            parse: Box::new(parse),
+            files: Vec::new(),
+        }
+    }
+
+    /// The path of the watched preset.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Parse the preset and start watching the files it references.
+    ///
+    /// The modification times are taken before the preset is parsed, so changes made while the
+    /// preset is being loaded are picked up by the next poll. If the preset fails to parse,
+    /// only the preset itself is watched.
+    pub fn load(&mut self) -> Result<ShaderPreset, ParsePresetError> {
+        self.files.clear();
+        self.watch(self.path.clone());
+
+        let preset = (self.parse)(&self.path)?;
+        for reference in &preset.references {
+            self.watch(reference.clone());
+        }
+        for pass in &preset.passes {
+            // if the includes can not be resolved, filter chain creation reports the error.
+            match ShaderSource::source_files(&pass.path, preset.sandbox.as_ref()) {
+                Ok(files) => files.into_iter().for_each(|file| self.watch(file)),
+                Err(_) => self.watch(pass.path.clone()),
+            }
+        }
+        for texture in &preset.textures {
+            self.watch(texture.path.clone());
+        }
+
+        Ok(preset)
+    }
+
+    fn watch(&mut self, path: PathBuf) {
+        if !self.files.iter().any(|(file, _)| *file == path) {
+            let time = modified(&path);
+            self.files.push((path, time));
+        }
+    }
+
+    /// Whether any of the watched files were modified, created or removed since the
+    /// preset was last loaded.
+    pub fn is_modified(&self) -> bool {
+        self.files
+            .iter()
+            .any(|(path, time)| modified(path) != *time)
+    }
+
+    /// Reload the filter chain if any of the watched files were modified.
+    ///
+    /// The preset is parsed again and passed to `create` to create the new filter chain, which
+    /// replaces `filter_chain`. Parameters that were changed from their initial value on the
+    /// old filter chain keep their value on the new filter chain, and parameters that were not
+    /// changed take the value of the reloaded preset.
+    ///
+    /// Returns `Ok(true)` if the filter chain was reloaded. If the preset fails to parse or the
+    /// filter chain fails to be created, the error is returned and the old filter chain is kept.
+    /// The failed load is not retried until the files are modified again.
+    pub fn poll_reload<T, E>(
+        &mut self,
+        filter_chain: &mut T,
+        create: impl FnOnce(ShaderPreset) -> Result<T, E>,
+    ) -> Result<bool, E>
+    where
+        T: FilterChainParameters,
+        E: From<ParsePresetError>,
+    {
+        if !self.is_modified() {
+            return Ok(false);
+        }
+
+        let preset = self.load()?;
+        let new_chain = create(preset)?;
+
+        let old_parameters = filter_chain.parameters();
+        let new_parameters = new_chain.parameters();
+        for (name, value) in old_parameters.parameters().iter() {
+            if old_parameters.initial_value(name) != Some(*value) {
+                new_parameters.set_parameter_value(name, *value);
+            }
+        }
+
+        *filter_chain = new_chain;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PresetWatcher;
+    use crate::parameters::{FilterChainParameters, RuntimeParameters};
+    use librashader_common::shader_features::ShaderFeatures;
+    use librashader_presets::{ParsePresetError, ShaderPreset};
+
+    struct TestChain(RuntimeParameters);
+
+    impl FilterChainParameters for TestChain {
+        fn parameters(&self) -> &RuntimeParameters {
+            &self.0
+        }
+    }
+
+    fn create(preset: ShaderPreset) -> Result<TestChain, ParsePresetError> {
+        Ok(TestChain(RuntimeParameters::new(0, preset.parameters, [])))
+    }
+
+    #[test]
+    fn reload_keeps_changed_parameters() {
+        let dir = std::env::temp_dir().join(format!("librashader-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("watch.slangp");
+        let write = |a: f32, b: f32| {
+            let preset = format!("shaders = 0\nparameters = \"A;B\"\nA = {a:.1}\nB = {b:.1}\n");
+            std::fs::write(&path, preset).unwrap()
+        };
+        write(1.0, 1.0);
+
+        let mut watcher = PresetWatcher::new(&path, ShaderFeatures::NONE);
+        let mut chain = create(watcher.load().unwrap()).unwrap();
+        assert!(!watcher.poll_reload(&mut chain, create).unwrap());
+
+        chain.parameters().set_parameter_value("A", 5.0);
+        // make sure the modification time changes on file systems with coarse timestamps.
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        write(2.0, 2.0);
+
+        assert!(watcher.poll_reload(&mut chain, create).unwrap());
+        assert_eq!(chain.parameters().parameter_value("A"), Some(5.0));
+        assert_eq!(chain.parameters().parameter_value("B"), Some(2.0));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,292 @@
+//! Property tests for the scaling rules.
+//!
+//! Each property is checked against a deterministic stream of pseudo-random inputs, mixed with
+//! the extreme values that runtimes are most likely to get wrong.
+
+use librashader_common::Size;
+use librashader_presets::{Scale2D, ScaleFactor, ScaleType, Scaling};
+use librashader_runtime::scaling::{MipmapSize, ViewportSize, MAX_TEXEL_SIZE};
+
+const CASES: usize = 4096;
+
+const EXTREME_SIZES: [u32; 8] = [0, 1, 2, 16383, 16384, 16385, i32::MAX as u32, u32::MAX];
+
+const EXTREME_FACTORS: [f32; 9] = [
+    0.0,
+    -1.0,
+    0.5,
+    1.0,
+    f32::MIN_POSITIVE,
+    f32::MAX,
+    f32::INFINITY,
+    f32::NEG_INFINITY,
+    f32::NAN,
+];
+
+const SCALE_TYPES: [ScaleType; 6] = [
+    ScaleType::Input,
+    ScaleType::Absolute,
+    ScaleType::Viewport,
+    ScaleType::Original,
+    ScaleType::ViewportAspect,
+    ScaleType::OriginalAspect,
+];
+
+/// A xorshift generator, so that failures are reproducible.
+struct Cases(u64);
+
+impl Cases {
+    fn new() -> Cases {
+        Cases(0x9E37_79B9_7F4A_7C15)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn size_axis(&mut self) -> u32 {
+        match self.next() % 4 {
+            0 => EXTREME_SIZES[(self.next() % EXTREME_SIZES.len() as u64) as usize],
+            1 => (self.next() % 64) as u32,
+            _ => (self.next() % 20000) as u32,
+        }
+    }
+
+    fn size(&mut self) -> Size<u32> {
+        Size::new(self.size_axis(), self.size_axis())
+    }
+
+    fn factor(&mut self) -> ScaleFactor {
+        match self.next() % 4 {
+            0 => ScaleFactor::Float(
+                EXTREME_FACTORS[(self.next() % EXTREME_FACTORS.len() as u64) as usize],
+            ),
+            1 => ScaleFactor::Absolute(self.next() as i32),
+            2 => ScaleFactor::Absolute((self.next() % 20000) as i32),
+            _ => ScaleFactor::Float((self.next() % 8000) as f32 / 1000.0),
+        }
+    }
+
+    fn scaling(&mut self) -> Scaling {
+        Scaling {
+            scale_type: SCALE_TYPES[(self.next() % SCALE_TYPES.len() as u64) as usize],
+            factor: self.factor(),
+        }
+    }
+
+    fn scale(&mut self) -> Scale2D {
+        Scale2D {
+            valid: true,
+            x: self.scaling(),
+            y: self.scaling(),
+        }
+    }
+}
+
+fn uniform(scale_type: ScaleType, factor: ScaleFactor) -> Scale2D {
+    let scaling = Scaling { scale_type, factor };
+    Scale2D {
+        valid: true,
+        x: scaling.clone(),
+        y: scaling,
+    }
+}
+
+fn expected_axis(size: f32) -> u32 {
+    if size.is_nan() {
+        return 1;
+    }
+    size.round().clamp(1.0, MAX_TEXEL_SIZE) as u32
+}
+
+#[test]
+fn scaled_size_is_within_bounds() {
+    let mut cases = Cases::new();
+    for _ in 0..CASES {
+        let scale = cases.scale();
+        let (source, viewport, original) = (cases.size(), cases.size(), cases.size());
+        let size = source.scale_viewport(scale.clone(), viewport, original);
+
+        for axis in [size.width, size.height] {
+            assert!(
+                (1..=MAX_TEXEL_SIZE as u32).contains(&axis),
+                "{scale:?} of {source:?}, {viewport:?}, {original:?} was {size:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn scaled_size_is_rounded_to_nearest() {
+    let mut cases = Cases::new();
+    for _ in 0..CASES {
+        let source = cases.size();
+        let factor = cases.next() as u16 as f32 / 4096.0;
+        let size = source.scale_viewport(
+            uniform(ScaleType::Input, ScaleFactor::Float(factor)),
+            Size::new(1, 1),
+            Size::new(1, 1),
+        );
+
+        assert_eq!(
+            size,
+            Size::new(
+                expected_axis(source.width as f32 * factor),
+                expected_axis(source.height as f32 * factor)
+            ),
+            "{factor} of {source:?}"
+        );
+    }
+
+    // halfway cases round away from zero.
+    let size = Size::new(3, 5).scale_viewport(
+        uniform(ScaleType::Input, ScaleFactor::Float(0.5)),
+        Size::new(1, 1),
+        Size::new(1, 1),
+    );
+    assert_eq!(size, Size::new(2, 3));
+}
+
+#[test]
+fn scaled_size_depends_only_on_its_scale_type() {
+    let mut cases = Cases::new();
+    for _ in 0..CASES {
+        let scale = cases.scale();
+        let (source, viewport, original) = (cases.size(), cases.size(), cases.size());
+        let other = cases.size();
+
+        let size = source.scale_viewport(scale.clone(), viewport, original);
+        let (source, viewport, original) = match (scale.x.scale_type, scale.y.scale_type) {
+            (ScaleType::Absolute, ScaleType::Absolute) => (other, other, other),
+            (ScaleType::Input, ScaleType::Input) => (source, other, other),
+            (ScaleType::Viewport, ScaleType::Viewport) => (other, viewport, other),
+            (ScaleType::Original, ScaleType::Original) => (other, other, original),
+            (ScaleType::OriginalAspect, ScaleType::OriginalAspect) => (other, other, original),
+            _ => continue,
+        };
+
+        assert_eq!(
+            size,
+            source.scale_viewport(scale.clone(), viewport, original),
+            "{scale:?}"
+        );
+    }
+}
+
+#[test]
+fn scaled_size_is_monotonic_in_factor() {
+    let mut cases = Cases::new();
+    for _ in 0..CASES {
+        let scale_type = SCALE_TYPES[(cases.next() % SCALE_TYPES.len() as u64) as usize];
+        let (source, viewport, original) = (cases.size(), cases.size(), cases.size());
+        let low = (cases.next() % 8000) as f32 / 1000.0;
+        let high = low + (cases.next() % 8000) as f32 / 1000.0;
+
+        let (low, high) = if matches!(scale_type, ScaleType::Absolute) {
+            (
+                ScaleFactor::Absolute(low as i32),
+                ScaleFactor::Absolute(high as i32),
+            )
+        } else {
+            (ScaleFactor::Float(low), ScaleFactor::Float(high))
+        };
+
+        let low_size = source.scale_viewport(uniform(scale_type, low), viewport, original);
+        let high_size = source.scale_viewport(uniform(scale_type, high), viewport, original);
+        assert!(
+            low_size.width <= high_size.width,
+            "{scale_type:?} {low:?} {high:?}"
+        );
+        assert!(
+            low_size.height <= high_size.height,
+            "{scale_type:?} {low:?} {high:?}"
+        );
+    }
+}
+
+#[test]
+fn unit_input_scale_is_identity() {
+    let mut cases = Cases::new();
+    for _ in 0..CASES {
+        let source = Size::new(
+            cases.size_axis().clamp(1, MAX_TEXEL_SIZE as u32),
+            cases.size_axis().clamp(1, MAX_TEXEL_SIZE as u32),
+        );
+        let size = source.scale_viewport(
+            uniform(ScaleType::Input, ScaleFactor::Float(1.0)),
+            cases.size(),
+            cases.size(),
+        );
+        assert_eq!(size, source);
+    }
+}
+
+#[test]
+fn aspect_scale_keeps_original_aspect_ratio() {
+    let original = Size::new(256, 224);
+    let viewport = Size::new(1920, 1080);
+
+    let size = Size::new(1, 1).scale_viewport(
+        uniform(ScaleType::ViewportAspect, ScaleFactor::Float(1.0)),
+        viewport,
+        original,
+    );
+    assert_eq!(size, Size::new(1234, 1080));
+
+    let size = Size::new(1, 1).scale_viewport(
+        Scale2D {
+            valid: true,
+            x: Scaling {
+                scale_type: ScaleType::Absolute,
+                factor: ScaleFactor::Absolute(512),
+            },
+            y: Scaling {
+                scale_type: ScaleType::OriginalAspect,
+                factor: ScaleFactor::Float(1.0),
+            },
+        },
+        viewport,
+        original,
+    );
+    assert_eq!(size, Size::new(512, 448));
+
+    // an original image without area falls back to the viewport.
+    let size = Size::new(1, 1).scale_viewport(
+        uniform(ScaleType::ViewportAspect, ScaleFactor::Float(0.5)),
+        viewport,
+        Size::new(0, 0),
+    );
+    assert_eq!(size, Size::new(960, 540));
+}
+
+#[test]
+fn mipmap_chain_ends_at_one_texel() {
+    let mut cases = Cases::new();
+    for _ in 0..CASES {
+        let size = cases.size();
+        let levels = size.calculate_miplevels();
+        let largest = std::cmp::max(size.width, size.height);
+        assert_eq!(levels, u32::BITS - largest.leading_zeros(), "{size:?}");
+
+        if levels > 0 {
+            assert_eq!(
+                size.scale_mipmap(0),
+                Size::new(size.width.max(1), size.height.max(1))
+            );
+            let last = size.scale_mipmap(levels - 1);
+            assert_eq!(std::cmp::max(last.width, last.height), 1, "{size:?}");
+        }
+
+        let mut previous = size.scale_mipmap(0);
+        for level in 1..=64 {
+            let mip = size.scale_mipmap(level);
+            assert!(mip.width >= 1 && mip.height >= 1, "{size:?} at {level}");
+            assert_eq!(mip.width, std::cmp::max(previous.width / 2, 1));
+            assert_eq!(mip.height, std::cmp::max(previous.height / 2, 1));
+            previous = mip;
+        }
+    }
+}
@@ -50,6 +50,7 @@
 //! or [`librashader.h`](https://github.com/SnowflakePowered/librashader/blob/master/include/librashader.h).
 
 pub use librashader_common::map::FastHashMap;
+pub use librashader_common::map::OrderedHashMap;
 pub use librashader_common::map::ShortString;
 
 #[cfg(feature = "presets")]
@@ -67,25 +68,81 @@ pub use librashader_common::map::ShortString;
 /// to the preset file. The handful of shaders that fail to parse due to this or other reasons are
 /// listed at [`BROKEN_SHADERS.md`](https://github.com/SnowflakePowered/librashader/blob/master/BROKEN_SHADERS.md).
 pub mod presets {
+    use librashader_common::map::{FastHashMap, ShortString};
     use librashader_preprocess::{PreprocessError, ShaderParameter, ShaderSource};
     pub use librashader_presets::*;
 
     pub use librashader_pack::*;
 
+    /// Localized descriptions for shader parameters, keyed by parameter name.
+    ///
+    /// The same translations can be used with any number of presets, since parameters
+    /// are looked up by name.
+    #[derive(Debug, Clone, Default)]
+    pub struct ParameterTranslations {
+        descriptions: FastHashMap<ShortString, String>,
+    }
+
+    impl ParameterTranslations {
+        /// Create an empty set of translations.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Set the localized description of the parameter with the given name,
+        /// replacing any previous translation.
+        pub fn insert(&mut self, name: impl Into<ShortString>, description: impl Into<String>) {
+            self.descriptions.insert(name.into(), description.into());
+        }
+
+        /// Get the localized description of the parameter with the given name.
+        pub fn get(&self, name: &str) -> Option<&str> {
+            self.descriptions.get(name).map(String::as_str)
+        }
+    }
+
     /// Get full parameter metadata from a shader preset.
+    ///
+    /// This only preprocesses the shaders of the preset, and does not require a filter chain.
+    /// Parameters that are declared in more than one pass are only returned once, with the
+    /// metadata from the first pass that declares it.
+    ///
+    /// Parameters are returned in the order of the passes that declare them, and in declaration
+    /// order within a pass.
     pub fn get_parameter_meta(
         preset: &ShaderPreset,
     ) -> Result<impl Iterator<Item = ShaderParameter>, PreprocessError> {
-        let iters: Result<Vec<Vec<ShaderParameter>>, PreprocessError> = preset
-            .passes
-            .iter()
-            .map(|s| {
-                ShaderSource::load(&s.path, preset.features)
-                    .map(|s| s.parameters.into_iter().map(|(_, v)| v).collect())
-            })
-            .collect();
-        let iters = iters?;
-        Ok(iters.into_iter().flatten())
+        let mut parameters: Vec<ShaderParameter> = Vec::new();
+        for pass in &preset.passes {
+            let source = ShaderSource::load_with_defines(
+                &pass.path,
+                preset.features,
+                &pass.meta.defines,
+                preset.sandbox.as_ref(),
+            )?;
+            for (_, parameter) in source.parameters {
+                if !parameters.iter().any(|p| p.id == parameter.id) {
+                    parameters.push(parameter);
+                }
+            }
+        }
+        Ok(parameters.into_iter())
+    }
+
+    /// Get full parameter metadata from a shader preset, with parameter descriptions
+    /// replaced by their translations.
+    ///
+    /// Parameters without a translation keep the description declared in the shader.
+    pub fn get_parameter_meta_localized<'a>(
+        preset: &ShaderPreset,
+        translations: &'a ParameterTranslations,
+    ) -> Result<impl Iterator<Item = ShaderParameter> + 'a, PreprocessError> {
+        Ok(get_parameter_meta(preset)?.map(|mut parameter| {
+            if let Some(description) = translations.get(&parameter.id) {
+                parameter.description = description.to_string();
+            }
+            parameter
+        }))
     }
 }
 
@@ -186,6 +243,10 @@ pub mod reflect {
         ///
         pub use librashader_reflect::back::glsl::GlslVersion;
 
+        /// Options for GLSL compilation.
+        ///
+        pub use librashader_reflect::back::glsl::GlslOptions;
+
         /// The HLSL Shader Model to target.
         ///
         pub use librashader_reflect::back::hlsl::HlslShaderModel;
@@ -247,8 +308,74 @@ pub mod reflect {
 #[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime")))]
 pub mod runtime {
     pub use librashader_common::{Size, Viewport};
+    pub use librashader_runtime::filter_pass::PassInfo;
+    pub use librashader_runtime::framebuffer::HistoryDepth;
+    pub use librashader_runtime::instance::{
+        InstanceError, InstanceOptions, LibrashaderInstance, LogCallback, LogLevel,
+    };
+    pub use librashader_runtime::jitter;
+    pub use librashader_runtime::latency::{LatencyEvent, LatencyMarker, LatencyMarkerCallback};
+    pub use librashader_runtime::mesh::OutputMesh;
+    pub use librashader_runtime::mvp;
     pub use librashader_runtime::parameters::FilterChainParameters;
-    pub use librashader_runtime::parameters::RuntimeParameters;
+    pub use librashader_runtime::parameters::{ParameterHandle, RuntimeParameters};
+    pub use librashader_runtime::pending::{LocalPendingFilterChain, PendingFilterChain};
+    pub use librashader_runtime::progress::{
+        CancellationToken, LoadProgress, LoadStage, ProgressCallback,
+    };
+    pub use librashader_runtime::quad::VertexInput;
+    pub use librashader_runtime::statistics::FrameStatistics;
+    pub use librashader_runtime::warnings::OptionWarning;
+    pub use librashader_runtime::watch::PresetWatcher;
+
+    /// Precompile every variant of a preset in a [`VariantMatrix`](crate::presets::VariantMatrix)
+    /// into the shader cache.
+    ///
+    /// Filter chains created later from any of the variants then load their SPIR-V from the
+    /// cache instead of compiling it, so toggling the defines of the matrix at runtime does not
+    /// need to recompile the shaders. Runtime specific compilation is cached the first time a
+    /// filter chain is created from a variant, unless the cache is disabled.
+    ///
+    /// This does not require a device, so it can be called on a background thread while the
+    /// frontend renders with the preset. Passes that are the same in more than one variant
+    /// are only compiled once.
+    pub fn precompile_variants<E>(
+        preset: &librashader_presets::ShaderPreset,
+        matrix: &librashader_presets::VariantMatrix,
+    ) -> Result<(), E>
+    where
+        E: From<librashader_preprocess::PreprocessError>,
+        E: From<librashader_reflect::error::ShaderCompileError>,
+    {
+        let mut compiled = Vec::new();
+        for variant in matrix.variants(preset) {
+            for pass in variant.passes {
+                if compiled
+                    .iter()
+                    .any(|(path, defines)| path == &pass.path && defines == &pass.meta.defines)
+                {
+                    continue;
+                }
+
+                let source = librashader_preprocess::ShaderSource::load_with_defines(
+                    &pass.path,
+                    variant.features,
+                    &pass.meta.defines,
+                    variant.sandbox.as_ref(),
+                )?;
+                librashader_cache::precompile_spirv(&source)?;
+                compiled.push((pass.path, pass.meta.defines));
+            }
+        }
+        Ok(())
+    }
+
+    /// Helpers to compute the size of pass outputs and their mipmaps.
+    ///
+    /// These follow the same scaling rules as the librashader runtimes.
+    pub mod scaling {
+        pub use librashader_runtime::scaling::{MipmapSize, ViewportSize, MAX_TEXEL_SIZE};
+    }
 
     #[cfg(feature = "runtime-gl")]
     #[cfg_attr(feature = "docsrs", doc(cfg(feature = "runtime-gl")))]
@@ -259,7 +386,8 @@ pub mod runtime {
         pub use librashader_runtime_gl::{
             error,
             options::{FilterChainOptionsGL as FilterChainOptions, FrameOptionsGL as FrameOptions},
-            FilterChainGL as FilterChain, GLImage,
+            DeviceContextGL as DeviceContext, FilterChainGL as FilterChain, GLImage,
+            LutBundleGL as LutBundle,
         };
     }
 
@@ -275,7 +403,8 @@ pub mod runtime {
             options::{
                 FilterChainOptionsD3D11 as FilterChainOptions, FrameOptionsD3D11 as FrameOptions,
             },
-            FilterChainD3D11 as FilterChain,
+            DeviceContextD3D11 as DeviceContext, FilterChainD3D11 as FilterChain,
+            LutBundleD3D11 as LutBundle,
         };
     }
 
@@ -289,6 +418,7 @@ pub mod runtime {
         pub use librashader_runtime_d3d12::{
             error,
             options::{
+                D3D12DescriptorHeapRange, D3D12MipmapHandoff, D3D12MipmapHandoffCallback,
                 FilterChainOptionsD3D12 as FilterChainOptions, FrameOptionsD3D12 as FrameOptions,
             },
             D3D12InputImage, D3D12OutputView, FilterChainD3D12 as FilterChain,
@@ -307,7 +437,8 @@ pub mod runtime {
             options::{
                 FilterChainOptionsD3D9 as FilterChainOptions, FrameOptionsD3D9 as FrameOptions,
             },
-            FilterChainD3D9 as FilterChain,
+            DeviceContextD3D9 as DeviceContext, FilterChainD3D9 as FilterChain,
+            LutBundleD3D9 as LutBundle,
         };
     }
 
@@ -320,7 +451,9 @@ pub mod runtime {
             options::{
                 FilterChainOptionsVulkan as FilterChainOptions, FrameOptionsVulkan as FrameOptions,
             },
-            FilterChainVulkan as FilterChain, VulkanImage, VulkanInstance, VulkanObjects,
+            DeviceContextVulkan as DeviceContext, FilterChainVulkan as FilterChain,
+            HardwareBufferImage, LutBundleVulkan as LutBundle, VulkanImage, VulkanInstance,
+            VulkanObjects,
         };
     }
 
@@ -336,7 +469,8 @@ pub mod runtime {
             options::{
                 FilterChainOptionsMetal as FilterChainOptions, FrameOptionsMetal as FrameOptions,
             },
-            FilterChainMetal as FilterChain, MetalTextureRef,
+            DeviceContextMetal as DeviceContext, FilterChainMetal as FilterChain,
+            LutBundleMetal as LutBundle, MetalTextureRef,
         };
     }
 
@@ -349,9 +483,10 @@ pub mod runtime {
             options::{
                 FilterChainOptionsWgpu as FilterChainOptions, FrameOptionsWgpu as FrameOptions,
             },
-            FilterChainWgpu as FilterChain, WgpuOutputView,
+            DeviceContextWgpu as DeviceContext, FilterChainWgpu as FilterChain,
+            LutBundleWgpu as LutBundle, WgpuOutputView,
         };
     }
 }
 
-pub use librashader_common::{FilterMode, ImageFormat, WrapMode};
+pub use librashader_common::{FilterMode, FormatFallback, ImageFormat, ScalingKernel, WrapMode};
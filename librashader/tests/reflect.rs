@@ -151,6 +151,7 @@ where
                     PassMeta::load(&p.path, ShaderFeatures::all()).map(|data| PassResource {
                         meta: p.meta.clone(),
                         data,
+                        path: Some(p.path.clone()),
                     }),
                     &p.path,
                 )
@@ -0,0 +1,348 @@
+/*
+ * Apply a slang shader preset with librashader.
+ *
+ * This filter renders headlessly with a surfaceless EGL context. Every frame is
+ * uploaded from system memory into a texture, processed by an OpenGL filter chain,
+ * and read back into the output frame.
+ */
+
+#define LIBRA_RUNTIME_OPENGL
+#include "librashader_ld.h"
+
+#include <EGL/egl.h>
+#include <EGL/eglext.h>
+
+#include "libavutil/internal.h"
+#include "libavutil/opt.h"
+#include "libavutil/pixdesc.h"
+
+#include "avfilter.h"
+#include "filters.h"
+#include "formats.h"
+#include "video.h"
+
+#define GL_TEXTURE_2D 0x0DE1
+#define GL_RGBA 0x1908
+#define GL_RGBA8 0x8058
+#define GL_UNSIGNED_BYTE 0x1401
+#define GL_FRAMEBUFFER 0x8D40
+#define GL_COLOR_ATTACHMENT0 0x8CE0
+#define GL_UNPACK_ROW_LENGTH 0x0CF2
+#define GL_PACK_ROW_LENGTH 0x0D02
+#define GL_UNPACK_ALIGNMENT 0x0CF5
+#define GL_PACK_ALIGNMENT 0x0D05
+
+typedef struct GLFunctions {
+    void (*GenTextures)(int32_t n, uint32_t *textures);
+    void (*DeleteTextures)(int32_t n, const uint32_t *textures);
+    void (*BindTexture)(uint32_t target, uint32_t texture);
+    void (*TexStorage2D)(uint32_t target, int32_t levels, uint32_t format,
+                         int32_t width, int32_t height);
+    void (*TexSubImage2D)(uint32_t target, int32_t level, int32_t x, int32_t y,
+                          int32_t width, int32_t height, uint32_t format,
+                          uint32_t type, const void *pixels);
+    void (*PixelStorei)(uint32_t pname, int32_t param);
+    void (*GenFramebuffers)(int32_t n, uint32_t *framebuffers);
+    void (*DeleteFramebuffers)(int32_t n, const uint32_t *framebuffers);
+    void (*BindFramebuffer)(uint32_t target, uint32_t framebuffer);
+    void (*FramebufferTexture2D)(uint32_t target, uint32_t attachment,
+                                 uint32_t textarget, uint32_t texture,
+                                 int32_t level);
+    void (*ReadPixels)(int32_t x, int32_t y, int32_t width, int32_t height,
+                       uint32_t format, uint32_t type, void *pixels);
+} GLFunctions;
+
+typedef struct LibrashaderContext {
+    const AVClass *class;
+
+    char *preset_path;
+    int w, h;
+
+    libra_instance_t libra;
+    libra_gl_filter_chain_t chain;
+
+    EGLDisplay display;
+    EGLContext context;
+    GLFunctions gl;
+
+    uint32_t input_texture;
+    uint32_t output_texture;
+    uint32_t output_framebuffer;
+    size_t frame_count;
+} LibrashaderContext;
+
+static const void *get_proc_address(const char *name)
+{
+    return (const void *)eglGetProcAddress(name);
+}
+
+static void print_error(AVFilterContext *ctx, libra_error_t error)
+{
+    LibrashaderContext *s = ctx->priv;
+    char *message = NULL;
+
+    s->libra.error_write(error, &message);
+    av_log(ctx, AV_LOG_ERROR, "librashader: %s\n", message ? message : "unknown error");
+    s->libra.error_free_string(&message);
+    s->libra.error_free(&error);
+}
+
+static int init_egl(AVFilterContext *ctx)
+{
+    LibrashaderContext *s = ctx->priv;
+    PFNEGLGETPLATFORMDISPLAYEXTPROC get_platform_display =
+        (PFNEGLGETPLATFORMDISPLAYEXTPROC)eglGetProcAddress("eglGetPlatformDisplayEXT");
+    EGLConfig config;
+    EGLint num_configs = 0;
+
+    static const EGLint config_attribs[] = {
+        EGL_RENDERABLE_TYPE, EGL_OPENGL_BIT,
+        EGL_NONE,
+    };
+    static const EGLint context_attribs[] = {
+        EGL_CONTEXT_MAJOR_VERSION, 3,
+        EGL_CONTEXT_MINOR_VERSION, 3,
+        EGL_CONTEXT_OPENGL_PROFILE_MASK, EGL_CONTEXT_OPENGL_CORE_PROFILE_BIT,
+        EGL_NONE,
+    };
+
+    if (get_platform_display)
+        s->display = get_platform_display(EGL_PLATFORM_SURFACELESS_MESA,
+                                          EGL_DEFAULT_DISPLAY, NULL);
+    if (s->display == EGL_NO_DISPLAY)
+        s->display = eglGetDisplay(EGL_DEFAULT_DISPLAY);
+
+    if (s->display == EGL_NO_DISPLAY || !eglInitialize(s->display, NULL, NULL)) {
+        av_log(ctx, AV_LOG_ERROR, "Could not initialize an EGL display.\n");
+        return AVERROR_EXTERNAL;
+    }
+
+    if (!eglBindAPI(EGL_OPENGL_API) ||
+        !eglChooseConfig(s->display, config_attribs, &config, 1, &num_configs) ||
+        num_configs == 0) {
+        av_log(ctx, AV_LOG_ERROR, "Could not find an OpenGL EGL config.\n");
+        return AVERROR_EXTERNAL;
+    }
+
+    s->context = eglCreateContext(s->display, config, EGL_NO_CONTEXT, context_attribs);
+    if (s->context == EGL_NO_CONTEXT ||
+        !eglMakeCurrent(s->display, EGL_NO_SURFACE, EGL_NO_SURFACE, s->context)) {
+        av_log(ctx, AV_LOG_ERROR,
+               "Could not create a surfaceless OpenGL 3.3 core context.\n");
+        return AVERROR_EXTERNAL;
+    }
+
+    s->gl.GenTextures = get_proc_address("glGenTextures");
+    s->gl.DeleteTextures = get_proc_address("glDeleteTextures");
+    s->gl.BindTexture = get_proc_address("glBindTexture");
+    s->gl.TexStorage2D = get_proc_address("glTexStorage2D");
+    s->gl.TexSubImage2D = get_proc_address("glTexSubImage2D");
+    s->gl.PixelStorei = get_proc_address("glPixelStorei");
+    s->gl.GenFramebuffers = get_proc_address("glGenFramebuffers");
+    s->gl.DeleteFramebuffers = get_proc_address("glDeleteFramebuffers");
+    s->gl.BindFramebuffer = get_proc_address("glBindFramebuffer");
+    s->gl.FramebufferTexture2D = get_proc_address("glFramebufferTexture2D");
+    s->gl.ReadPixels = get_proc_address("glReadPixels");
+
+    return 0;
+}
+
+static av_cold int init(AVFilterContext *ctx)
+{
+    LibrashaderContext *s = ctx->priv;
+    libra_shader_preset_t preset = NULL;
+    libra_error_t error;
+    int ret;
+
+    if (!s->preset_path) {
+        av_log(ctx, AV_LOG_ERROR, "No shader preset was given.\n");
+        return AVERROR(EINVAL);
+    }
+
+    s->libra = librashader_load_instance();
+    if (!s->libra.instance_loaded) {
+        av_log(ctx, AV_LOG_ERROR, "Could not load librashader.\n");
+        return AVERROR_EXTERNAL;
+    }
+
+    if ((ret = init_egl(ctx)) < 0)
+        return ret;
+
+    error = s->libra.preset_create(s->preset_path, &preset);
+    if (error) {
+        print_error(ctx, error);
+        return AVERROR(EINVAL);
+    }
+
+    error = s->libra.gl_filter_chain_create(&preset, get_proc_address, NULL, &s->chain);
+    if (error) {
+        print_error(ctx, error);
+        return AVERROR_EXTERNAL;
+    }
+
+    return 0;
+}
+
+static av_cold void uninit(AVFilterContext *ctx)
+{
+    LibrashaderContext *s = ctx->priv;
+
+    if (s->context != EGL_NO_CONTEXT) {
+        eglMakeCurrent(s->display, EGL_NO_SURFACE, EGL_NO_SURFACE, s->context);
+        if (s->chain)
+            s->libra.gl_filter_chain_free(&s->chain);
+        if (s->output_framebuffer)
+            s->gl.DeleteFramebuffers(1, &s->output_framebuffer);
+        if (s->input_texture)
+            s->gl.DeleteTextures(1, &s->input_texture);
+        if (s->output_texture)
+            s->gl.DeleteTextures(1, &s->output_texture);
+        eglMakeCurrent(s->display, EGL_NO_SURFACE, EGL_NO_SURFACE, EGL_NO_CONTEXT);
+        eglDestroyContext(s->display, s->context);
+    }
+
+    if (s->display != EGL_NO_DISPLAY)
+        eglTerminate(s->display);
+}
+
+static uint32_t create_texture(LibrashaderContext *s, int width, int height)
+{
+    uint32_t texture = 0;
+
+    s->gl.GenTextures(1, &texture);
+    s->gl.BindTexture(GL_TEXTURE_2D, texture);
+    s->gl.TexStorage2D(GL_TEXTURE_2D, 1, GL_RGBA8, width, height);
+    s->gl.BindTexture(GL_TEXTURE_2D, 0);
+    return texture;
+}
+
+static int config_input(AVFilterLink *inlink)
+{
+    AVFilterContext *ctx = inlink->dst;
+    LibrashaderContext *s = ctx->priv;
+
+    eglMakeCurrent(s->display, EGL_NO_SURFACE, EGL_NO_SURFACE, s->context);
+    if (s->input_texture)
+        s->gl.DeleteTextures(1, &s->input_texture);
+    s->input_texture = create_texture(s, inlink->w, inlink->h);
+    return 0;
+}
+
+static int config_output(AVFilterLink *outlink)
+{
+    AVFilterContext *ctx = outlink->src;
+    LibrashaderContext *s = ctx->priv;
+    AVFilterLink *inlink = ctx->inputs[0];
+
+    outlink->w = s->w > 0 ? s->w : inlink->w;
+    outlink->h = s->h > 0 ? s->h : inlink->h;
+
+    eglMakeCurrent(s->display, EGL_NO_SURFACE, EGL_NO_SURFACE, s->context);
+    if (s->output_texture)
+        s->gl.DeleteTextures(1, &s->output_texture);
+    s->output_texture = create_texture(s, outlink->w, outlink->h);
+
+    if (!s->output_framebuffer)
+        s->gl.GenFramebuffers(1, &s->output_framebuffer);
+    s->gl.BindFramebuffer(GL_FRAMEBUFFER, s->output_framebuffer);
+    s->gl.FramebufferTexture2D(GL_FRAMEBUFFER, GL_COLOR_ATTACHMENT0, GL_TEXTURE_2D,
+                               s->output_texture, 0);
+    s->gl.BindFramebuffer(GL_FRAMEBUFFER, 0);
+
+    return 0;
+}
+
+static int filter_frame(AVFilterLink *inlink, AVFrame *in)
+{
+    AVFilterContext *ctx = inlink->dst;
+    LibrashaderContext *s = ctx->priv;
+    AVFilterLink *outlink = ctx->outputs[0];
+    libra_image_gl_t input, output;
+    libra_viewport_t viewport;
+    libra_error_t error;
+    AVFrame *out;
+
+    out = ff_get_video_buffer(outlink, outlink->w, outlink->h);
+    if (!out) {
+        av_frame_free(&in);
+        return AVERROR(ENOMEM);
+    }
+    av_frame_copy_props(out, in);
+
+    // libavfilter may call the filter from any thread.
+    eglMakeCurrent(s->display, EGL_NO_SURFACE, EGL_NO_SURFACE, s->context);
+
+    // rows are uploaded top to bottom, which is the origin the filter chain
+    // expects for input images by default.
+    s->gl.BindTexture(GL_TEXTURE_2D, s->input_texture);
+    s->gl.PixelStorei(GL_UNPACK_ALIGNMENT, 4);
+    s->gl.PixelStorei(GL_UNPACK_ROW_LENGTH, in->linesize[0] / 4);
+    s->gl.TexSubImage2D(GL_TEXTURE_2D, 0, 0, 0, inlink->w, inlink->h, GL_RGBA,
+                        GL_UNSIGNED_BYTE, in->data[0]);
+    s->gl.PixelStorei(GL_UNPACK_ROW_LENGTH, 0);
+    s->gl.BindTexture(GL_TEXTURE_2D, 0);
+
+    input = (libra_image_gl_t){ s->input_texture, GL_RGBA8, inlink->w, inlink->h };
+    output = (libra_image_gl_t){ s->output_texture, GL_RGBA8, outlink->w, outlink->h };
+    viewport = (libra_viewport_t){ 0, 0, outlink->w, outlink->h };
+
+    error = s->libra.gl_filter_chain_frame(&s->chain, s->frame_count++, input,
+                                           output, &viewport, NULL, NULL);
+    av_frame_free(&in);
+    if (error) {
+        print_error(ctx, error);
+        av_frame_free(&out);
+        return AVERROR_EXTERNAL;
+    }
+
+    s->gl.BindFramebuffer(GL_FRAMEBUFFER, s->output_framebuffer);
+    s->gl.PixelStorei(GL_PACK_ALIGNMENT, 4);
+    s->gl.PixelStorei(GL_PACK_ROW_LENGTH, out->linesize[0] / 4);
+    s->gl.ReadPixels(0, 0, outlink->w, outlink->h, GL_RGBA, GL_UNSIGNED_BYTE,
+                     out->data[0]);
+    s->gl.PixelStorei(GL_PACK_ROW_LENGTH, 0);
+    s->gl.BindFramebuffer(GL_FRAMEBUFFER, 0);
+
+    return ff_filter_frame(outlink, out);
+}
+
+#define OFFSET(x) offsetof(LibrashaderContext, x)
+#define FLAGS AV_OPT_FLAG_VIDEO_PARAM | AV_OPT_FLAG_FILTERING_PARAM
+
+static const AVOption librashader_options[] = {
+    { "preset", "path to the shader preset", OFFSET(preset_path), AV_OPT_TYPE_STRING, { .str = NULL }, 0, 0, FLAGS },
+    { "w", "output width, or 0 for the input width", OFFSET(w), AV_OPT_TYPE_INT, { .i64 = 0 }, 0, 16384, FLAGS },
+    { "h", "output height, or 0 for the input height", OFFSET(h), AV_OPT_TYPE_INT, { .i64 = 0 }, 0, 16384, FLAGS },
+    { NULL }
+};
+
+AVFILTER_DEFINE_CLASS(librashader);
+
+static const AVFilterPad librashader_inputs[] = {
+    {
+        .name         = "default",
+        .type         = AVMEDIA_TYPE_VIDEO,
+        .config_props = config_input,
+        .filter_frame = filter_frame,
+    },
+};
+
+static const AVFilterPad librashader_outputs[] = {
+    {
+        .name         = "default",
+        .type         = AVMEDIA_TYPE_VIDEO,
+        .config_props = config_output,
+    },
+};
+
+const AVFilter ff_vf_librashader = {
+    .name          = "librashader",
+    .description   = NULL_IF_CONFIG_SMALL("Apply a slang shader preset with librashader."),
+    .priv_size     = sizeof(LibrashaderContext),
+    .priv_class    = &librashader_class,
+    .init          = init,
+    .uninit        = uninit,
+    FILTER_INPUTS(librashader_inputs),
+    FILTER_OUTPUTS(librashader_outputs),
+    FILTER_SINGLE_PIXFMT(AV_PIX_FMT_RGBA),
+};
@@ -49,6 +49,21 @@ int main()
 
     instance.preset_free_runtime_params(parameters);
 
+    // Keep a pristine copy so parameter edits can be reset to the preset defaults.
+    libra_shader_preset_t working;
+    instance.preset_clone(&preset, &working);
+    instance.preset_set_param(&working, "CRT_GAMMA_OUT", 1.5);
+
+    float edited = 0;
+    instance.preset_get_param(&working, "CRT_GAMMA_OUT", &edited);
+    instance.preset_free(&working);
+
+    instance.preset_clone(&preset, &working);
+    float reset = 0;
+    instance.preset_get_param(&working, "CRT_GAMMA_OUT", &reset);
+    printf("CRT_GAMMA_OUT edited %f, reset %f\n", edited, reset);
+    instance.preset_free(&working);
+
     /*libra_shader_preset_t preset;
     auto error = libra_preset_create("../../../slang-shaders/border/gameboy-player/gameboy-player-crt-royale.slangp", &preset);
     if (error != NULL) {
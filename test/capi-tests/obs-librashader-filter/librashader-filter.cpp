@@ -0,0 +1,449 @@
+// A reference OBS Studio video filter that applies a slang shader preset to a source.
+//
+// See README.md for the caveats of integrating with the renderer of OBS.
+
+#include <obs-module.h>
+#include <graphics/graphics.h>
+
+#include "librashader_ld.h"
+
+#if defined(_WIN32)
+#include <windows.h>
+#else
+#include <dlfcn.h>
+#endif
+
+OBS_DECLARE_MODULE()
+
+static libra_instance_t libra;
+
+#define GL_TEXTURE_2D 0x0DE1
+#define GL_RGBA8 0x8058
+#define GL_BLEND 0x0BE2
+#define GL_SCISSOR_TEST 0x0C11
+#define GL_FRAMEBUFFER_SRGB 0x8DB9
+#define GL_VIEWPORT 0x0BA2
+#define GL_CURRENT_PROGRAM 0x8B8D
+#define GL_ARRAY_BUFFER 0x8892
+#define GL_ARRAY_BUFFER_BINDING 0x8894
+#define GL_VERTEX_ARRAY_BINDING 0x85B5
+#define GL_UNIFORM_BUFFER 0x8A11
+#define GL_UNIFORM_BUFFER_BINDING 0x8A28
+#define GL_READ_FRAMEBUFFER 0x8CA8
+#define GL_DRAW_FRAMEBUFFER 0x8CA9
+#define GL_READ_FRAMEBUFFER_BINDING 0x8CAA
+#define GL_DRAW_FRAMEBUFFER_BINDING 0x8CA6
+#define GL_ACTIVE_TEXTURE 0x84E0
+#define GL_TEXTURE_BINDING_2D 0x8069
+#define GL_SAMPLER_BINDING 0x8919
+#define GL_TEXTURE0 0x84C0
+
+// The number of texture units to restore after a frame. OBS binds at most this many
+// textures for its own effects.
+#define RESTORED_TEXTURE_UNITS 8
+
+struct gl_functions {
+    void (*GetIntegerv)(uint32_t pname, int32_t *data);
+    uint8_t (*IsEnabled)(uint32_t cap);
+    void (*Enable)(uint32_t cap);
+    void (*Disable)(uint32_t cap);
+    void (*Viewport)(int32_t x, int32_t y, int32_t width, int32_t height);
+    void (*UseProgram)(uint32_t program);
+    void (*BindVertexArray)(uint32_t array);
+    void (*BindBuffer)(uint32_t target, uint32_t buffer);
+    void (*BindFramebuffer)(uint32_t target, uint32_t framebuffer);
+    void (*ActiveTexture)(uint32_t texture);
+    void (*BindTexture)(uint32_t target, uint32_t texture);
+    void (*BindSampler)(uint32_t unit, uint32_t sampler);
+};
+
+static gl_functions gl;
+
+static const void *gl_get_proc_address(const char *name) {
+#if defined(_WIN32)
+    auto proc = (const void *)wglGetProcAddress(name);
+    if (proc == nullptr || proc == (const void *)1 || proc == (const void *)2 ||
+        proc == (const void *)3 || proc == (const void *)-1) {
+        static HMODULE opengl32 = LoadLibraryA("opengl32.dll");
+        proc = (const void *)GetProcAddress(opengl32, name);
+    }
+    return proc;
+#elif defined(__APPLE__)
+    return dlsym(RTLD_DEFAULT, name);
+#else
+    // OBS renders with EGL on both X11 and Wayland.
+    using get_proc_address_t = const void *(*)(const char *);
+    static auto egl_get_proc_address =
+        (get_proc_address_t)dlsym(RTLD_DEFAULT, "eglGetProcAddress");
+    if (egl_get_proc_address == nullptr) {
+        return dlsym(RTLD_DEFAULT, name);
+    }
+    return egl_get_proc_address(name);
+#endif
+}
+
+template <typename T>
+static void gl_load(T &function, const char *name) {
+    function = (T)gl_get_proc_address(name);
+}
+
+static void gl_load_functions() {
+    gl_load(gl.GetIntegerv, "glGetIntegerv");
+    gl_load(gl.IsEnabled, "glIsEnabled");
+    gl_load(gl.Enable, "glEnable");
+    gl_load(gl.Disable, "glDisable");
+    gl_load(gl.Viewport, "glViewport");
+    gl_load(gl.UseProgram, "glUseProgram");
+    gl_load(gl.BindVertexArray, "glBindVertexArray");
+    gl_load(gl.BindBuffer, "glBindBuffer");
+    gl_load(gl.BindFramebuffer, "glBindFramebuffer");
+    gl_load(gl.ActiveTexture, "glActiveTexture");
+    gl_load(gl.BindTexture, "glBindTexture");
+    gl_load(gl.BindSampler, "glBindSampler");
+}
+
+// The OpenGL state that librashader changes, and that OBS keeps track of.
+struct gl_state {
+    int32_t program;
+    int32_t vertex_array;
+    int32_t array_buffer;
+    int32_t uniform_buffer;
+    int32_t read_framebuffer;
+    int32_t draw_framebuffer;
+    int32_t viewport[4];
+    int32_t active_texture;
+    int32_t textures[RESTORED_TEXTURE_UNITS];
+    int32_t samplers[RESTORED_TEXTURE_UNITS];
+    bool blend;
+    bool scissor_test;
+    bool framebuffer_srgb;
+};
+
+static void gl_set_enabled(uint32_t cap, bool enabled) {
+    if (enabled) {
+        gl.Enable(cap);
+    } else {
+        gl.Disable(cap);
+    }
+}
+
+static void gl_save_state(gl_state &state) {
+    gl.GetIntegerv(GL_CURRENT_PROGRAM, &state.program);
+    gl.GetIntegerv(GL_VERTEX_ARRAY_BINDING, &state.vertex_array);
+    gl.GetIntegerv(GL_ARRAY_BUFFER_BINDING, &state.array_buffer);
+    gl.GetIntegerv(GL_UNIFORM_BUFFER_BINDING, &state.uniform_buffer);
+    gl.GetIntegerv(GL_READ_FRAMEBUFFER_BINDING, &state.read_framebuffer);
+    gl.GetIntegerv(GL_DRAW_FRAMEBUFFER_BINDING, &state.draw_framebuffer);
+    gl.GetIntegerv(GL_VIEWPORT, state.viewport);
+    gl.GetIntegerv(GL_ACTIVE_TEXTURE, &state.active_texture);
+    for (uint32_t unit = 0; unit < RESTORED_TEXTURE_UNITS; unit++) {
+        gl.ActiveTexture(GL_TEXTURE0 + unit);
+        gl.GetIntegerv(GL_TEXTURE_BINDING_2D, &state.textures[unit]);
+        gl.GetIntegerv(GL_SAMPLER_BINDING, &state.samplers[unit]);
+    }
+    gl.ActiveTexture(state.active_texture);
+    state.blend = gl.IsEnabled(GL_BLEND);
+    state.scissor_test = gl.IsEnabled(GL_SCISSOR_TEST);
+    state.framebuffer_srgb = gl.IsEnabled(GL_FRAMEBUFFER_SRGB);
+}
+
+static void gl_restore_state(const gl_state &state) {
+    gl.UseProgram(state.program);
+    gl.BindVertexArray(state.vertex_array);
+    gl.BindBuffer(GL_ARRAY_BUFFER, state.array_buffer);
+    gl.BindBuffer(GL_UNIFORM_BUFFER, state.uniform_buffer);
+    gl.BindFramebuffer(GL_READ_FRAMEBUFFER, state.read_framebuffer);
+    gl.BindFramebuffer(GL_DRAW_FRAMEBUFFER, state.draw_framebuffer);
+    gl.Viewport(state.viewport[0], state.viewport[1], state.viewport[2],
+                state.viewport[3]);
+    for (uint32_t unit = 0; unit < RESTORED_TEXTURE_UNITS; unit++) {
+        gl.ActiveTexture(GL_TEXTURE0 + unit);
+        gl.BindTexture(GL_TEXTURE_2D, state.textures[unit]);
+        gl.BindSampler(unit, state.samplers[unit]);
+    }
+    gl.ActiveTexture(state.active_texture);
+    gl_set_enabled(GL_BLEND, state.blend);
+    gl_set_enabled(GL_SCISSOR_TEST, state.scissor_test);
+    gl_set_enabled(GL_FRAMEBUFFER_SRGB, state.framebuffer_srgb);
+}
+
+struct librashader_filter {
+    obs_source_t *source;
+    gs_texrender_t *input;
+    gs_texture_t *output;
+    size_t frame_count;
+
+    libra_gl_filter_chain_t gl_chain;
+#if defined(_WIN32)
+    libra_d3d11_filter_chain_t d3d11_chain;
+    ID3D11DeviceContext *deferred_context;
+#endif
+};
+
+static void print_error(libra_error_t error) {
+    if (error == nullptr) {
+        return;
+    }
+
+    char *message = nullptr;
+    libra.error_write(error, &message);
+    blog(LOG_WARNING, "[librashader] %s", message ? message : "unknown error");
+    libra.error_free_string(&message);
+    libra.error_free(&error);
+}
+
+static void free_filter_chain(librashader_filter *filter) {
+    if (filter->gl_chain) {
+        libra.gl_filter_chain_free(&filter->gl_chain);
+    }
+#if defined(_WIN32)
+    if (filter->d3d11_chain) {
+        libra.d3d11_filter_chain_free(&filter->d3d11_chain);
+    }
+#endif
+}
+
+// Must be called in the graphics context.
+static void load_filter_chain(librashader_filter *filter, const char *path) {
+    free_filter_chain(filter);
+    filter->frame_count = 0;
+
+    if (path == nullptr || *path == '\0') {
+        return;
+    }
+
+    libra_shader_preset_t preset = nullptr;
+    libra_error_t error = libra.preset_create(path, &preset);
+    if (error != nullptr) {
+        print_error(error);
+        return;
+    }
+
+    // the filter chains take ownership of the preset.
+    switch (gs_get_device_type()) {
+        case GS_DEVICE_OPENGL:
+            print_error(libra.gl_filter_chain_create(
+                &preset, gl_get_proc_address, nullptr, &filter->gl_chain));
+            break;
+#if defined(_WIN32)
+        case GS_DEVICE_DIRECT3D_11:
+            print_error(libra.d3d11_filter_chain_create(
+                &preset, (ID3D11Device *)gs_get_device_obj(), nullptr,
+                &filter->d3d11_chain));
+            break;
+#endif
+        default:
+            break;
+    }
+
+    if (preset != nullptr) {
+        libra.preset_free(&preset);
+    }
+}
+
+static const char *filter_get_name(void *) { return "librashader"; }
+
+static void filter_update(void *data, obs_data_t *settings) {
+    auto filter = (librashader_filter *)data;
+
+    obs_enter_graphics();
+    load_filter_chain(filter, obs_data_get_string(settings, "preset"));
+    obs_leave_graphics();
+}
+
+static void *filter_create(obs_data_t *settings, obs_source_t *source) {
+    auto filter = (librashader_filter *)bzalloc(sizeof(librashader_filter));
+    filter->source = source;
+
+    obs_enter_graphics();
+    filter->input = gs_texrender_create(GS_BGRA, GS_ZS_NONE);
+#if defined(_WIN32)
+    if (gs_get_device_type() == GS_DEVICE_DIRECT3D_11) {
+        auto device = (ID3D11Device *)gs_get_device_obj();
+        device->CreateDeferredContext(0, &filter->deferred_context);
+    }
+#endif
+    obs_leave_graphics();
+
+    filter_update(filter, settings);
+    return filter;
+}
+
+static void filter_destroy(void *data) {
+    auto filter = (librashader_filter *)data;
+
+    obs_enter_graphics();
+    free_filter_chain(filter);
+    gs_texrender_destroy(filter->input);
+    gs_texture_destroy(filter->output);
+#if defined(_WIN32)
+    if (filter->deferred_context) {
+        filter->deferred_context->Release();
+    }
+#endif
+    obs_leave_graphics();
+
+    bfree(filter);
+}
+
+static obs_properties_t *filter_properties(void *) {
+    obs_properties_t *props = obs_properties_create();
+    obs_properties_add_path(props, "preset", "Shader preset", OBS_PATH_FILE,
+                            "Shader presets (*.slangp)", nullptr);
+    return props;
+}
+
+static void filter_frame_gl(librashader_filter *filter, uint32_t width,
+                            uint32_t height) {
+    libra_image_gl_t input = {
+        *(uint32_t *)gs_texture_get_obj(gs_texrender_get_texture(filter->input)),
+        GL_RGBA8, width, height};
+    libra_image_gl_t output = {*(uint32_t *)gs_texture_get_obj(filter->output),
+                               GL_RGBA8, width, height};
+    libra_viewport_t viewport = {0, 0, width, height};
+
+    gl_state state;
+    gl_save_state(state);
+    print_error(libra.gl_filter_chain_frame(&filter->gl_chain,
+                                            filter->frame_count, input, output,
+                                            &viewport, nullptr, nullptr));
+    gl_restore_state(state);
+}
+
+#if defined(_WIN32)
+static void filter_frame_d3d11(librashader_filter *filter, uint32_t width,
+                               uint32_t height) {
+    auto device = (ID3D11Device *)gs_get_device_obj();
+    auto input = (ID3D11Texture2D *)gs_texture_get_obj(
+        gs_texrender_get_texture(filter->input));
+    auto output = (ID3D11Texture2D *)gs_texture_get_obj(filter->output);
+
+    // the textures of OBS are typeless, so the format of the views has to be
+    // explicit.
+    D3D11_SHADER_RESOURCE_VIEW_DESC srv_desc = {};
+    srv_desc.Format = DXGI_FORMAT_B8G8R8A8_UNORM;
+    srv_desc.ViewDimension = D3D11_SRV_DIMENSION_TEXTURE2D;
+    srv_desc.Texture2D.MipLevels = 1;
+
+    D3D11_RENDER_TARGET_VIEW_DESC rtv_desc = {};
+    rtv_desc.Format = DXGI_FORMAT_B8G8R8A8_UNORM;
+    rtv_desc.ViewDimension = D3D11_RTV_DIMENSION_TEXTURE2D;
+
+    ID3D11ShaderResourceView *srv = nullptr;
+    ID3D11RenderTargetView *rtv = nullptr;
+    if (SUCCEEDED(device->CreateShaderResourceView(input, &srv_desc, &srv)) &&
+        SUCCEEDED(device->CreateRenderTargetView(output, &rtv_desc, &rtv))) {
+        libra_viewport_t viewport = {0, 0, width, height};
+        print_error(libra.d3d11_filter_chain_frame(
+            &filter->d3d11_chain, filter->deferred_context,
+            filter->frame_count, srv, rtv, &viewport, nullptr, nullptr));
+
+        // executing the command list restores the state of the immediate
+        // context.
+        ID3D11CommandList *commands = nullptr;
+        if (SUCCEEDED(filter->deferred_context->FinishCommandList(FALSE,
+                                                                  &commands))) {
+            ID3D11DeviceContext *immediate = nullptr;
+            device->GetImmediateContext(&immediate);
+            immediate->ExecuteCommandList(commands, TRUE);
+            immediate->Release();
+            commands->Release();
+        }
+    }
+
+    if (srv) {
+        srv->Release();
+    }
+    if (rtv) {
+        rtv->Release();
+    }
+}
+#endif
+
+static void filter_render(void *data, gs_effect_t *) {
+    auto filter = (librashader_filter *)data;
+    obs_source_t *target = obs_filter_get_target(filter->source);
+    uint32_t width = obs_source_get_base_width(target);
+    uint32_t height = obs_source_get_base_height(target);
+
+    bool has_chain = filter->gl_chain != nullptr;
+#if defined(_WIN32)
+    has_chain = has_chain || filter->d3d11_chain != nullptr;
+#endif
+
+    if (!has_chain || width == 0 || height == 0) {
+        obs_source_skip_video_filter(filter->source);
+        return;
+    }
+
+    // render the source into a BGRA texture to use as the input.
+    gs_texrender_reset(filter->input);
+    if (!gs_texrender_begin(filter->input, width, height)) {
+        obs_source_skip_video_filter(filter->source);
+        return;
+    }
+
+    struct vec4 clear_color;
+    vec4_zero(&clear_color);
+    gs_clear(GS_CLEAR_COLOR, &clear_color, 0.0f, 0);
+    gs_ortho(0.0f, (float)width, 0.0f, (float)height, -100.0f, 100.0f);
+    if (obs_source_process_filter_begin(filter->source, GS_BGRA,
+                                        OBS_NO_DIRECT_RENDERING)) {
+        obs_source_process_filter_end(filter->source,
+                                      obs_get_base_effect(OBS_EFFECT_DEFAULT),
+                                      width, height);
+    }
+    gs_texrender_end(filter->input);
+
+    if (filter->output == nullptr ||
+        gs_texture_get_width(filter->output) != width ||
+        gs_texture_get_height(filter->output) != height) {
+        gs_texture_destroy(filter->output);
+        filter->output = gs_texture_create(width, height, GS_BGRA, 1, nullptr,
+                                           GS_RENDER_TARGET);
+    }
+
+    if (filter->gl_chain) {
+        filter_frame_gl(filter, width, height);
+    }
+#if defined(_WIN32)
+    if (filter->d3d11_chain) {
+        filter_frame_d3d11(filter, width, height);
+    }
+#endif
+    filter->frame_count++;
+
+    gs_effect_t *effect = obs_get_base_effect(OBS_EFFECT_DEFAULT);
+    gs_eparam_t *image = gs_effect_get_param_by_name(effect, "image");
+    gs_effect_set_texture(image, filter->output);
+    while (gs_effect_loop(effect, "Draw")) {
+        gs_draw_sprite(filter->output, 0, width, height);
+    }
+}
+
+bool obs_module_load(void) {
+    libra = librashader_load_instance();
+    if (!libra.instance_loaded) {
+        blog(LOG_WARNING, "[librashader] could not load librashader");
+        return false;
+    }
+
+    obs_enter_graphics();
+    if (gs_get_device_type() == GS_DEVICE_OPENGL) {
+        gl_load_functions();
+    }
+    obs_leave_graphics();
+
+    struct obs_source_info info = {};
+    info.id = "librashader_filter";
+    info.type = OBS_SOURCE_TYPE_FILTER;
+    info.output_flags = OBS_SOURCE_VIDEO;
+    info.get_name = filter_get_name;
+    info.create = filter_create;
+    info.destroy = filter_destroy;
+    info.update = filter_update;
+    info.get_properties = filter_properties;
+    info.video_render = filter_render;
+    obs_register_source(&info);
+    return true;
+}